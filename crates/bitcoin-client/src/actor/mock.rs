@@ -0,0 +1,122 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::messages::{
+    BroadcastTransactionMessage, GetBestBlockHashMessage, GetBlockHashMessage,
+    GetBlockHeaderInfoMessage, GetBlockMessage, GetChainTipsMessage, GetRawTransactionMessage,
+    GetTxOutMessage,
+};
+use crate::mock::MockBitcoinNode;
+use anyhow::Result;
+use async_trait::async_trait;
+use bitcoin::Transaction;
+use bitcoincore_rpc::{bitcoin::Txid, json};
+use coerce::actor::{context::ActorContext, message::Handler, Actor};
+
+/// The Bitcoin client actor backed by a `MockBitcoinNode` instead of the bitcoind RPC.
+pub struct MockBitcoinClientActor {
+    node: MockBitcoinNode,
+}
+
+impl MockBitcoinClientActor {
+    pub fn new(node: MockBitcoinNode) -> Self {
+        Self { node }
+    }
+}
+
+impl Actor for MockBitcoinClientActor {}
+
+#[async_trait]
+impl Handler<GetBlockMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: GetBlockMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<bitcoin::Block> {
+        let GetBlockMessage { hash } = msg;
+        self.node.block(&hash)
+    }
+}
+
+#[async_trait]
+impl Handler<GetBestBlockHashMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        _msg: GetBestBlockHashMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<bitcoin::BlockHash> {
+        Ok(self.node.best_block_hash())
+    }
+}
+
+#[async_trait]
+impl Handler<GetBlockHashMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: GetBlockHashMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<bitcoin::BlockHash> {
+        let GetBlockHashMessage { height } = msg;
+        self.node.block_hash(height)
+    }
+}
+
+#[async_trait]
+impl Handler<GetBlockHeaderInfoMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: GetBlockHeaderInfoMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<json::GetBlockHeaderResult> {
+        let GetBlockHeaderInfoMessage { hash } = msg;
+        self.node.get_block_header_info(&hash)
+    }
+}
+
+#[async_trait]
+impl Handler<GetChainTipsMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        _msg: GetChainTipsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<json::GetChainTipsResult> {
+        self.node.get_chain_tips()
+    }
+}
+
+#[async_trait]
+impl Handler<BroadcastTransactionMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: BroadcastTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Txid> {
+        let BroadcastTransactionMessage { hex, .. } = msg;
+        let tx: Transaction = bitcoin::consensus::encode::deserialize_hex(&hex)?;
+        self.node.broadcast_transaction(tx)
+    }
+}
+
+#[async_trait]
+impl Handler<GetTxOutMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: GetTxOutMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<json::GetTxOutResult>> {
+        let GetTxOutMessage { txid, vout, .. } = msg;
+        self.node.get_tx_out(&txid, vout)
+    }
+}
+
+#[async_trait]
+impl Handler<GetRawTransactionMessage> for MockBitcoinClientActor {
+    async fn handle(
+        &mut self,
+        msg: GetRawTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Transaction> {
+        let GetRawTransactionMessage { txid } = msg;
+        self.node.get_raw_transaction(&txid)
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use bitcoin::{Amount, Block, FeeRate, Network};
+
+/// The default number of recent blocks used to estimate the fee rate
+pub const DEFAULT_FEE_ESTIMATE_BLOCKS: u64 = 6;
+/// The max number of recent blocks used to estimate the fee rate, about 4 hours of blocks
+pub const MAX_FEE_ESTIMATE_BLOCKS: u64 = 24;
+/// The estimated fee rate is never lower than the min relay fee rate
+pub const MIN_FEE_RATE: FeeRate = FeeRate::BROADCAST_MIN;
+
+const INITIAL_BLOCK_SUBSIDY: u64 = 50 * 100_000_000;
+
+/// The block subsidy at the given height, see `GetBlockSubsidy` in Bitcoin Core.
+pub fn block_subsidy(network: Network, block_height: u64) -> Amount {
+    let halving_interval = match network {
+        Network::Regtest => 150,
+        _ => 210_000,
+    };
+    let halvings = block_height / halving_interval;
+    if halvings >= 64 {
+        return Amount::ZERO;
+    }
+    Amount::from_sat(INITIAL_BLOCK_SUBSIDY >> halvings)
+}
+
+/// The average fee rate paid by the non-coinbase transactions of the block.
+/// The total fee is the coinbase outputs minus the block subsidy, so the input values are not required.
+/// Return None if the block only contains the coinbase transaction.
+pub fn block_fee_rate(network: Network, block_height: u64, block: &Block) -> Option<FeeRate> {
+    let (coinbase, txs) = block.txdata.split_first()?;
+    if txs.is_empty() {
+        return None;
+    }
+    let coinbase_value = coinbase
+        .output
+        .iter()
+        .map(|output| output.value)
+        .sum::<Amount>();
+    let fees = coinbase_value
+        .checked_sub(block_subsidy(network, block_height))
+        .unwrap_or(Amount::ZERO);
+    let weight = txs.iter().map(|tx| tx.weight().to_wu()).sum::<u64>();
+    Some(FeeRate::from_sat_per_kwu(
+        fees.to_sat().saturating_mul(1000) / weight.max(1),
+    ))
+}
+
+/// Estimate the fee rate via the median of the fee rates of the recent blocks,
+/// the result is rounded up to sat/vB and never lower than `MIN_FEE_RATE`.
+pub fn estimate_fee_rate(mut block_fee_rates: Vec<FeeRate>) -> FeeRate {
+    if block_fee_rates.is_empty() {
+        return MIN_FEE_RATE;
+    }
+    block_fee_rates.sort();
+    let median = block_fee_rates[block_fee_rates.len() / 2];
+    let sat_per_vb = median.to_sat_per_vb_ceil();
+    FeeRate::from_sat_per_vb(sat_per_vb)
+        .unwrap_or(MIN_FEE_RATE)
+        .max(MIN_FEE_RATE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockBitcoinNode;
+    use bitcoin::{ScriptBuf, TxOut};
+
+    #[test]
+    fn test_block_subsidy() {
+        assert_eq!(
+            block_subsidy(Network::Bitcoin, 0),
+            Amount::from_sat(INITIAL_BLOCK_SUBSIDY)
+        );
+        assert_eq!(
+            block_subsidy(Network::Bitcoin, 840_000),
+            Amount::from_sat(312_500_000)
+        );
+        assert_eq!(
+            block_subsidy(Network::Regtest, 150),
+            Amount::from_sat(INITIAL_BLOCK_SUBSIDY / 2)
+        );
+        assert_eq!(block_subsidy(Network::Regtest, 150 * 64), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_block_fee_rate() {
+        let node = MockBitcoinNode::new(Network::Regtest);
+        let block_height = 1;
+        let coinbase_only = node.block_builder().build().unwrap();
+        assert_eq!(
+            block_fee_rate(Network::Regtest, block_height, &coinbase_only),
+            None
+        );
+
+        let script_pubkey = ScriptBuf::from_bytes(vec![0x51]);
+        let (builder, utxo) = node
+            .block_builder()
+            .add_utxo(script_pubkey.clone(), Amount::from_sat(100_000));
+        let mut block = builder.build().unwrap();
+        // The funding transaction pays no fee
+        assert_eq!(
+            block_fee_rate(Network::Regtest, block_height, &block),
+            Some(FeeRate::ZERO)
+        );
+
+        block.txdata.push(MockBitcoinNode::new_transaction(
+            vec![utxo],
+            vec![TxOut {
+                value: Amount::from_sat(90_000),
+                script_pubkey,
+            }],
+        ));
+        // The coinbase collects the 10_000 sat fee
+        block.txdata[0].output[0].value =
+            block_subsidy(Network::Regtest, block_height) + Amount::from_sat(10_000);
+        let weight = block.txdata[1..]
+            .iter()
+            .map(|tx| tx.weight().to_wu())
+            .sum::<u64>();
+        let fee_rate = block_fee_rate(Network::Regtest, block_height, &block).unwrap();
+        assert_eq!(fee_rate.to_sat_per_kwu(), 10_000 * 1000 / weight);
+
+        let estimated = estimate_fee_rate(vec![
+            FeeRate::from_sat_per_vb(300).unwrap(),
+            fee_rate,
+            FeeRate::from_sat_per_vb(2).unwrap(),
+        ]);
+        assert_eq!(
+            estimated,
+            FeeRate::from_sat_per_vb(fee_rate.to_sat_per_vb_ceil()).unwrap()
+        );
+        assert_eq!(estimate_fee_rate(vec![FeeRate::ZERO]), MIN_FEE_RATE);
+        assert_eq!(estimate_fee_rate(vec![]), MIN_FEE_RATE);
+    }
+}
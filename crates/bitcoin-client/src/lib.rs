@@ -2,4 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod actor;
+pub mod fee;
+pub mod mock;
 pub mod proxy;
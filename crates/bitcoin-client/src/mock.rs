@@ -0,0 +1,568 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+//! An in-process mock Bitcoin node, it keeps a block tree in memory and answers the
+//! `BitcoinClientProxy` requests without a running bitcoind, so the relayer and the
+//! bitcoin framework can be tested offline.
+
+use anyhow::{bail, Result};
+use bitcoin::block::{Header, Version as BlockVersion};
+use bitcoin::blockdata::constants::genesis_block;
+use bitcoin::hashes::Hash;
+use bitcoin::hex::DisplayHex;
+use bitcoin::script::PushBytesBuf;
+use bitcoin::transaction::Version;
+use bitcoin::{
+    absolute, Address, Amount, Block, BlockHash, Network, OutPoint, ScriptBuf, Sequence,
+    Transaction, TxIn, TxMerkleNode, TxOut, Txid, Witness, Work,
+};
+use bitcoincore_rpc::json;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// The block subsidy of the mock coinbase, the halving is ignored.
+pub const MOCK_BLOCK_SUBSIDY: Amount = Amount::from_sat(50 * 100_000_000);
+/// The block interval in seconds of the mock chain.
+pub const MOCK_BLOCK_INTERVAL: u32 = 600;
+
+#[derive(Debug, Clone)]
+struct MockBlockEntry {
+    block: Block,
+    height: u64,
+    chain_work: Work,
+}
+
+#[derive(Debug)]
+struct MockChain {
+    network: Network,
+    blocks: HashMap<BlockHash, MockBlockEntry>,
+    /// The block hashes of the active chain, indexed by height.
+    active_chain: Vec<BlockHash>,
+    mempool: Vec<Transaction>,
+    /// Used to make the coinbase of every mined block unique, so blocks at the same height
+    /// on different branches never share the same hash.
+    mined_count: u64,
+}
+
+impl MockChain {
+    fn new(network: Network) -> Self {
+        let genesis = genesis_block(network);
+        let genesis_hash = genesis.block_hash();
+        let chain_work = genesis.header.work();
+        let mut blocks = HashMap::new();
+        blocks.insert(
+            genesis_hash,
+            MockBlockEntry {
+                block: genesis,
+                height: 0,
+                chain_work,
+            },
+        );
+        Self {
+            network,
+            blocks,
+            active_chain: vec![genesis_hash],
+            mempool: vec![],
+            mined_count: 0,
+        }
+    }
+
+    fn tip_height(&self) -> u64 {
+        (self.active_chain.len() - 1) as u64
+    }
+
+    fn tip_hash(&self) -> BlockHash {
+        *self
+            .active_chain
+            .last()
+            .expect("active chain should not be empty")
+    }
+
+    fn entry(&self, hash: &BlockHash) -> Result<&MockBlockEntry> {
+        self.blocks
+            .get(hash)
+            .ok_or_else(|| anyhow::anyhow!("Block {} not found", hash))
+    }
+
+    fn is_active(&self, entry: &MockBlockEntry) -> bool {
+        self.active_chain.get(entry.height as usize) == Some(&entry.block.block_hash())
+    }
+
+    fn submit_block(&mut self, block: Block) -> Result<BlockHash> {
+        let hash = block.block_hash();
+        if self.blocks.contains_key(&hash) {
+            bail!("Block {} already exists", hash);
+        }
+        let parent = self.entry(&block.header.prev_blockhash)?;
+        let height = parent.height + 1;
+        let chain_work = parent.chain_work + block.header.work();
+        let tip_work = self.entry(&self.tip_hash())?.chain_work;
+        self.blocks.insert(
+            hash,
+            MockBlockEntry {
+                block,
+                height,
+                chain_work,
+            },
+        );
+        // Switch to the branch with the most work, like bitcoind does.
+        if chain_work > tip_work {
+            self.activate(hash)?;
+        }
+        Ok(hash)
+    }
+
+    fn activate(&mut self, tip: BlockHash) -> Result<()> {
+        let mut branch = vec![];
+        let mut cursor = tip;
+        loop {
+            let entry = self.entry(&cursor)?;
+            if self.is_active(entry) {
+                break;
+            }
+            branch.push(cursor);
+            cursor = entry.block.header.prev_blockhash;
+        }
+        let fork_height = self.entry(&cursor)?.height;
+        self.active_chain.truncate(fork_height as usize + 1);
+        self.active_chain.extend(branch.into_iter().rev());
+        let confirmed = self
+            .active_chain
+            .iter()
+            .flat_map(|hash| self.blocks[hash].block.txdata.iter())
+            .map(|tx| tx.compute_txid())
+            .collect::<Vec<_>>();
+        self.mempool
+            .retain(|tx| !confirmed.contains(&tx.compute_txid()));
+        Ok(())
+    }
+
+    fn median_time_past(&self, entry: &MockBlockEntry) -> u32 {
+        let mut times = vec![];
+        let mut cursor = Some(entry);
+        while let Some(current) = cursor {
+            if times.len() == 11 {
+                break;
+            }
+            times.push(current.block.header.time);
+            cursor = if current.height == 0 {
+                None
+            } else {
+                self.blocks.get(&current.block.header.prev_blockhash)
+            };
+        }
+        times.sort_unstable();
+        times[times.len() / 2]
+    }
+
+    fn find_transaction(&self, txid: &Txid) -> Option<Transaction> {
+        self.active_chain
+            .iter()
+            .flat_map(|hash| self.blocks[hash].block.txdata.iter())
+            .chain(self.mempool.iter())
+            .find(|tx| tx.compute_txid() == *txid)
+            .cloned()
+    }
+}
+
+/// A mock Bitcoin node shared by the tests and the `MockBitcoinClientActor`.
+/// Cloning the node shares the same underlying chain.
+#[derive(Debug, Clone)]
+pub struct MockBitcoinNode {
+    chain: Arc<RwLock<MockChain>>,
+}
+
+impl MockBitcoinNode {
+    /// Create a mock node which only contains the genesis block of the `network`.
+    pub fn new(network: Network) -> Self {
+        Self {
+            chain: Arc::new(RwLock::new(MockChain::new(network))),
+        }
+    }
+
+    pub fn network(&self) -> Network {
+        self.chain.read().unwrap().network
+    }
+
+    pub fn genesis_hash(&self) -> BlockHash {
+        self.chain.read().unwrap().active_chain[0]
+    }
+
+    pub fn best_block_hash(&self) -> BlockHash {
+        self.chain.read().unwrap().tip_hash()
+    }
+
+    pub fn best_block_height(&self) -> u64 {
+        self.chain.read().unwrap().tip_height()
+    }
+
+    /// Get the block hash of the active chain at `height`
+    pub fn block_hash(&self, height: u64) -> Result<BlockHash> {
+        let chain = self.chain.read().unwrap();
+        chain
+            .active_chain
+            .get(height as usize)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Block height {} out of range", height))
+    }
+
+    /// Get the block by hash, the blocks of the stale branches are also returned
+    pub fn block(&self, hash: &BlockHash) -> Result<Block> {
+        let chain = self.chain.read().unwrap();
+        Ok(chain.entry(hash)?.block.clone())
+    }
+
+    pub fn block_height(&self, hash: &BlockHash) -> Result<u64> {
+        let chain = self.chain.read().unwrap();
+        Ok(chain.entry(hash)?.height)
+    }
+
+    /// Start to build a block on top of the current best block,
+    /// the transactions in the mempool are included.
+    pub fn block_builder(&self) -> MockBlockBuilder {
+        let parent = self.best_block_hash();
+        let mempool = self.chain.read().unwrap().mempool.clone();
+        MockBlockBuilder::new(self.clone(), parent).add_transactions(mempool)
+    }
+
+    /// Start to build a block on top of any known block, it is used to create forks.
+    /// The branch becomes the active chain once it has more work than the current one.
+    pub fn fork_builder(&self, parent: BlockHash) -> Result<MockBlockBuilder> {
+        self.chain.read().unwrap().entry(&parent)?;
+        Ok(MockBlockBuilder::new(self.clone(), parent))
+    }
+
+    /// Build a fork branch of `count` empty blocks from the block at `height` of the active chain,
+    /// returns the hashes of the new blocks.
+    pub fn fork_at(&self, height: u64, count: u64) -> Result<Vec<BlockHash>> {
+        let mut parent = self.block_hash(height)?;
+        let mut hashes = vec![];
+        for _ in 0..count {
+            parent = self.fork_builder(parent)?.mine()?.block_hash();
+            hashes.push(parent);
+        }
+        Ok(hashes)
+    }
+
+    /// Mine `count` blocks on top of the best block, returns the hashes of the new blocks.
+    pub fn generate_blocks(&self, count: u64) -> Result<Vec<BlockHash>> {
+        (0..count)
+            .map(|_| self.block_builder().mine().map(|block| block.block_hash()))
+            .collect()
+    }
+
+    /// Submit a block built outside, the parent block must be known.
+    pub fn submit_block(&self, block: Block) -> Result<BlockHash> {
+        self.chain.write().unwrap().submit_block(block)
+    }
+
+    /// Add a transaction to the mempool, it will be included in the next block built by `block_builder`.
+    pub fn broadcast_transaction(&self, tx: Transaction) -> Result<Txid> {
+        let txid = tx.compute_txid();
+        let mut chain = self.chain.write().unwrap();
+        if chain.find_transaction(&txid).is_some() {
+            bail!("Transaction {} already exists", txid);
+        }
+        chain.mempool.push(tx);
+        Ok(txid)
+    }
+
+    pub fn mempool(&self) -> Vec<Transaction> {
+        self.chain.read().unwrap().mempool.clone()
+    }
+
+    /// Get a transaction from the active chain or the mempool
+    pub fn get_raw_transaction(&self, txid: &Txid) -> Result<Transaction> {
+        self.chain
+            .read()
+            .unwrap()
+            .find_transaction(txid)
+            .ok_or_else(|| anyhow::anyhow!("Transaction {} not found", txid))
+    }
+
+    /// Get the unspent output of the active chain, the mempool is not included.
+    pub fn get_tx_out(&self, txid: &Txid, vout: u32) -> Result<Option<json::GetTxOutResult>> {
+        let chain = self.chain.read().unwrap();
+        let outpoint = OutPoint::new(*txid, vout);
+        let mut found = None;
+        for hash in chain.active_chain.iter() {
+            let entry = &chain.blocks[hash];
+            for tx in entry.block.txdata.iter() {
+                if found.is_some()
+                    && !tx.is_coinbase()
+                    && tx
+                        .input
+                        .iter()
+                        .any(|input| input.previous_output == outpoint)
+                {
+                    return Ok(None);
+                }
+                if tx.compute_txid() == *txid {
+                    found = tx
+                        .output
+                        .get(vout as usize)
+                        .map(|output| (output.clone(), entry.height, tx.is_coinbase()));
+                }
+            }
+        }
+        let Some((output, height, coinbase)) = found else {
+            return Ok(None);
+        };
+        let address = Address::from_script(&output.script_pubkey, chain.network)
+            .ok()
+            .map(|address| address.to_string());
+        let result = json!({
+            "bestblock": chain.tip_hash().to_string(),
+            "confirmations": chain.tip_height() - height + 1,
+            "value": output.value.to_btc(),
+            "scriptPubKey": {
+                "asm": output.script_pubkey.to_asm_string(),
+                "hex": output.script_pubkey.to_hex_string(),
+                "address": address,
+            },
+            "coinbase": coinbase,
+        });
+        Ok(Some(serde_json::from_value(result)?))
+    }
+
+    /// Get the block header info in the same shape as bitcoind `getblockheader`,
+    /// the confirmations of a block on a stale branch is -1.
+    pub fn get_block_header_info(&self, hash: &BlockHash) -> Result<json::GetBlockHeaderResult> {
+        let chain = self.chain.read().unwrap();
+        let entry = chain.entry(hash)?;
+        let header = entry.block.header;
+        let is_active = chain.is_active(entry);
+        let confirmations = if is_active {
+            (chain.tip_height() - entry.height + 1) as i64
+        } else {
+            -1
+        };
+        let next_block_hash = if is_active {
+            chain
+                .active_chain
+                .get(entry.height as usize + 1)
+                .map(|hash| hash.to_string())
+        } else {
+            None
+        };
+        let previous_block_hash = (entry.height > 0).then(|| header.prev_blockhash.to_string());
+        let result = json!({
+            "hash": hash.to_string(),
+            "confirmations": confirmations,
+            "height": entry.height,
+            "version": header.version.to_consensus(),
+            "versionHex": format!("{:08x}", header.version.to_consensus()),
+            "merkleroot": header.merkle_root.to_string(),
+            "time": header.time,
+            "mediantime": chain.median_time_past(entry),
+            "nonce": header.nonce,
+            "bits": format!("{:08x}", header.bits.to_consensus()),
+            "difficulty": header.target().difficulty_float(),
+            "chainwork": entry.chain_work.to_be_bytes().to_lower_hex_string(),
+            "nTx": entry.block.txdata.len(),
+            "previousblockhash": previous_block_hash,
+            "nextblockhash": next_block_hash,
+        });
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Get the chain tips in the same shape as bitcoind `getchaintips`
+    pub fn get_chain_tips(&self) -> Result<json::GetChainTipsResult> {
+        let chain = self.chain.read().unwrap();
+        let parents = chain
+            .blocks
+            .values()
+            .map(|entry| entry.block.header.prev_blockhash)
+            .collect::<Vec<_>>();
+        let mut tips = chain
+            .blocks
+            .iter()
+            .filter(|(hash, _)| !parents.contains(hash))
+            .map(|(hash, entry)| {
+                let mut branch_len = 0u64;
+                let mut cursor = entry;
+                while !chain.is_active(cursor) {
+                    branch_len += 1;
+                    cursor = &chain.blocks[&cursor.block.header.prev_blockhash];
+                }
+                let status = if branch_len == 0 {
+                    "active"
+                } else {
+                    "valid-fork"
+                };
+                json!({
+                    "height": entry.height,
+                    "hash": hash.to_string(),
+                    "branchlen": branch_len,
+                    "status": status,
+                })
+            })
+            .collect::<Vec<_>>();
+        tips.sort_by_key(|tip| std::cmp::Reverse(tip["height"].as_u64()));
+        Ok(serde_json::from_value(serde_json::Value::Array(tips))?)
+    }
+
+    /// Build a transaction spending the `inputs` with empty witnesses, the mock node does not verify scripts.
+    pub fn new_transaction(inputs: Vec<OutPoint>, outputs: Vec<TxOut>) -> Transaction {
+        Transaction {
+            version: Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| TxIn {
+                    previous_output,
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: outputs,
+        }
+    }
+
+    /// Build a taproot script path spending transaction which reveals the `tapscript`,
+    /// such as an inscription envelope, the signature and control block are dummy.
+    pub fn new_reveal_transaction(
+        commit_outpoint: OutPoint,
+        tapscript: ScriptBuf,
+        outputs: Vec<TxOut>,
+    ) -> Transaction {
+        let mut tx = Self::new_transaction(vec![commit_outpoint], outputs);
+        // leaf version 0xc0 with the x-only internal key
+        let mut control_block = vec![0xc0];
+        control_block.extend_from_slice(&[1u8; 32]);
+        tx.input[0].witness =
+            Witness::from_slice(&[&[0u8; 64][..], tapscript.as_bytes(), &control_block[..]]);
+        tx
+    }
+}
+
+/// The builder of a mock block, the coinbase and merkle root are filled when mining.
+pub struct MockBlockBuilder {
+    node: MockBitcoinNode,
+    parent: BlockHash,
+    time: Option<u32>,
+    coinbase_script_pubkey: ScriptBuf,
+    transactions: Vec<Transaction>,
+}
+
+impl MockBlockBuilder {
+    fn new(node: MockBitcoinNode, parent: BlockHash) -> Self {
+        Self {
+            node,
+            parent,
+            time: None,
+            // OP_TRUE, anyone can spend the coinbase output
+            coinbase_script_pubkey: ScriptBuf::from_bytes(vec![0x51]),
+            transactions: vec![],
+        }
+    }
+
+    /// Set the block time, default is the parent block time plus `MOCK_BLOCK_INTERVAL`
+    pub fn time(mut self, time: u32) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Set the receiver of the coinbase output
+    pub fn coinbase_to(mut self, script_pubkey: ScriptBuf) -> Self {
+        self.coinbase_script_pubkey = script_pubkey;
+        self
+    }
+
+    pub fn add_transaction(mut self, tx: Transaction) -> Self {
+        if !self.transactions.contains(&tx) {
+            self.transactions.push(tx);
+        }
+        self
+    }
+
+    pub fn add_transactions(self, txs: Vec<Transaction>) -> Self {
+        txs.into_iter()
+            .fold(self, |builder, tx| builder.add_transaction(tx))
+    }
+
+    /// Pay `amount` to `script_pubkey` from a dummy input, returns the builder and the new output.
+    pub fn add_utxo(self, script_pubkey: ScriptBuf, amount: Amount) -> (Self, OutPoint) {
+        let dummy_input = OutPoint::new(
+            Txid::from_byte_array([0xff; 32]),
+            self.transactions.len() as u32,
+        );
+        let tx = MockBitcoinNode::new_transaction(
+            vec![dummy_input],
+            vec![TxOut {
+                value: amount,
+                script_pubkey,
+            }],
+        );
+        let outpoint = OutPoint::new(tx.compute_txid(), 0);
+        (self.add_transaction(tx), outpoint)
+    }
+
+    /// Build the block and submit it to the node
+    pub fn mine(self) -> Result<Block> {
+        let block = self.build()?;
+        self.node.submit_block(block.clone())?;
+        Ok(block)
+    }
+
+    /// Build the block without submitting it to the node
+    pub fn build(&self) -> Result<Block> {
+        let (network, parent_header, height, mined_count) = {
+            let mut chain = self.node.chain.write().unwrap();
+            let parent = chain.entry(&self.parent)?;
+            let (parent_header, height) = (parent.block.header, parent.height + 1);
+            chain.mined_count += 1;
+            (chain.network, parent_header, height, chain.mined_count)
+        };
+
+        let mut script_sig_tag = PushBytesBuf::new();
+        script_sig_tag.extend_from_slice(&mined_count.to_le_bytes())?;
+        let coinbase = Transaction {
+            version: Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                // BIP34 block height and a unique tag
+                script_sig: bitcoin::script::Builder::new()
+                    .push_int(height as i64)
+                    .push_slice(script_sig_tag)
+                    .into_script(),
+                sequence: Sequence::MAX,
+                witness: Witness::new(),
+            }],
+            output: vec![TxOut {
+                value: MOCK_BLOCK_SUBSIDY,
+                script_pubkey: self.coinbase_script_pubkey.clone(),
+            }],
+        };
+
+        let mut txdata = vec![coinbase];
+        txdata.extend(self.transactions.iter().cloned());
+        let mut block = Block {
+            header: Header {
+                version: BlockVersion::from_consensus(0x2000_0000),
+                prev_blockhash: self.parent,
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: self
+                    .time
+                    .unwrap_or(parent_header.time + MOCK_BLOCK_INTERVAL),
+                bits: parent_header.bits,
+                nonce: 0,
+            },
+            txdata,
+        };
+        block.header.merkle_root = block
+            .compute_merkle_root()
+            .expect("block should contain the coinbase");
+        // The regtest target is easy, grind the nonce to get a valid proof of work.
+        // The other networks are too hard to mine, the proof of work is skipped.
+        if network == Network::Regtest {
+            while block.header.validate_pow(block.header.target()).is_err() {
+                block.header.nonce += 1;
+            }
+        }
+        Ok(block)
+    }
+}
@@ -7,43 +7,76 @@ use crate::actor::messages::{
     GetBlockHeaderInfoMessage, GetBlockMessage, GetChainTipsMessage, GetRawTransactionMessage,
     GetTxOutMessage,
 };
+use crate::actor::mock::MockBitcoinClientActor;
 use anyhow::Result;
 use bitcoin::Transaction;
 use bitcoincore_rpc::bitcoin::Txid;
 use bitcoincore_rpc::json;
+use coerce::actor::message::{Handler, Message};
 use coerce::actor::ActorRef;
 
+#[derive(Clone)]
+pub enum BitcoinClientActorRef {
+    Rpc(ActorRef<BitcoinClientActor>),
+    Mock(ActorRef<MockBitcoinClientActor>),
+}
+
 #[derive(Clone)]
 pub struct BitcoinClientProxy {
-    pub actor: ActorRef<BitcoinClientActor>,
+    pub actor: BitcoinClientActorRef,
 }
 
 impl BitcoinClientProxy {
     pub fn new(actor: ActorRef<BitcoinClientActor>) -> Self {
-        Self { actor }
+        Self {
+            actor: BitcoinClientActorRef::Rpc(actor),
+        }
+    }
+
+    /// Create a proxy backed by the in-process mock Bitcoin node
+    pub fn mock(actor: ActorRef<MockBitcoinClientActor>) -> Self {
+        Self {
+            actor: BitcoinClientActorRef::Mock(actor),
+        }
+    }
+
+    pub fn is_mock(&self) -> bool {
+        matches!(self.actor, BitcoinClientActorRef::Mock(_))
+    }
+
+    async fn send<M>(&self, msg: M) -> Result<M::Result>
+    where
+        M: Message,
+        BitcoinClientActor: Handler<M>,
+        MockBitcoinClientActor: Handler<M>,
+    {
+        match &self.actor {
+            BitcoinClientActorRef::Rpc(actor) => Ok(actor.send(msg).await?),
+            BitcoinClientActorRef::Mock(actor) => Ok(actor.send(msg).await?),
+        }
     }
 
     pub async fn get_block(&self, hash: bitcoin::BlockHash) -> Result<bitcoin::Block> {
-        self.actor.send(GetBlockMessage { hash }).await?
+        self.send(GetBlockMessage { hash }).await?
     }
 
     pub async fn get_best_block_hash(&self) -> Result<bitcoin::BlockHash> {
-        self.actor.send(GetBestBlockHashMessage {}).await?
+        self.send(GetBestBlockHashMessage {}).await?
     }
 
     pub async fn get_block_hash(&self, height: u64) -> Result<bitcoin::BlockHash> {
-        self.actor.send(GetBlockHashMessage { height }).await?
+        self.send(GetBlockHashMessage { height }).await?
     }
 
     pub async fn get_block_header_info(
         &self,
         hash: bitcoin::BlockHash,
     ) -> Result<json::GetBlockHeaderResult> {
-        self.actor.send(GetBlockHeaderInfoMessage { hash }).await?
+        self.send(GetBlockHeaderInfoMessage { hash }).await?
     }
 
     pub async fn get_chain_tips(&self) -> Result<json::GetChainTipsResult> {
-        self.actor.send(GetChainTipsMessage {}).await?
+        self.send(GetChainTipsMessage {}).await?
     }
 
     pub async fn broadcast_transaction(
@@ -52,21 +85,20 @@ impl BitcoinClientProxy {
         maxfeerate: Option<f64>,
         maxburnamount: Option<f64>,
     ) -> Result<Txid> {
-        self.actor
-            .send(BroadcastTransactionMessage {
-                hex,
-                maxfeerate,
-                maxburnamount,
-            })
-            .await?
+        self.send(BroadcastTransactionMessage {
+            hex,
+            maxfeerate,
+            maxburnamount,
+        })
+        .await?
     }
 
     /// Get transaction output, do not include the mempool
     pub async fn get_tx_out(&self, txid: Txid, vout: u32) -> Result<Option<json::GetTxOutResult>> {
-        self.actor.send(GetTxOutMessage::new(txid, vout)).await?
+        self.send(GetTxOutMessage::new(txid, vout)).await?
     }
 
     pub async fn get_raw_transaction(&self, txid: Txid) -> Result<Transaction> {
-        self.actor.send(GetRawTransactionMessage { txid }).await?
+        self.send(GetRawTransactionMessage { txid }).await?
     }
 }
@@ -4,6 +4,7 @@
 use crate::config::Config;
 use crate::{retrieve_map_config_value, BaseConfig, MapConfigValueSource};
 use moveos_types::h256::H256;
+use rooch_types::da::chunk::ChunkCodec;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -85,16 +86,27 @@ pub enum OpenDAScheme {
     /// - `credential`: The authentication credential (or `credential_path`, using a file path).
     Gcs,
 
-    /// Amazon S3-compatible backend.
+    /// Amazon S3-compatible backend (e.g. MinIO).
     ///
     /// Main configuration:
     /// - `bucket`: The storage bucket.
-    /// - `region`: The AWS region.
-    /// - `endpoint`: The S3 endpoint URL.
-    /// - `access_key_id`: The AWS access key ID.
-    /// - `secret_access_key`: The AWS secret access key.
+    /// - `region` (optional): The AWS region, `us-east-1` by default.
+    /// - `endpoint` (optional): The S3 endpoint URL.
+    /// - `access_key_id` (optional): The AWS access key ID.
+    /// - `secret_access_key` (optional): The AWS secret access key.
+    /// - `path_style` (optional): Use path style requests, `true` by default; `false` for virtual host style.
     S3,
 
+    /// Content-addressed backend over an object storage, IPFS-style.
+    ///
+    /// Segments are stored as `blobs/<sha256 hex>` and deduplicated by content,
+    /// `refs/<segment_id>` holds the digest of each segment.
+    ///
+    /// Main configuration:
+    /// - `storage` (optional): The underlying storage scheme, one of `fs`, `gcs` and `s3`, `fs` by default.
+    /// - The configuration of the underlying storage scheme.
+    Cas,
+
     /// Avail Fusion backend, supporting TurboDA and Light Client.
     ///
     /// Main configuration:
@@ -117,6 +129,7 @@ impl Display for OpenDAScheme {
             OpenDAScheme::Fs => write!(f, "fs"),
             OpenDAScheme::Gcs => write!(f, "gcs"),
             OpenDAScheme::S3 => write!(f, "s3"),
+            OpenDAScheme::Cas => write!(f, "cas"),
             OpenDAScheme::Avail => write!(f, "avail"),
             OpenDAScheme::Celestia => write!(f, "celestia"),
         }
@@ -130,6 +143,7 @@ impl FromStr for OpenDAScheme {
         match s.to_lowercase().as_str() {
             "gcs" => Ok(OpenDAScheme::Gcs),
             "s3" => Ok(OpenDAScheme::S3),
+            "cas" => Ok(OpenDAScheme::Cas),
             "fs" => Ok(OpenDAScheme::Fs),
             "avail" => Ok(OpenDAScheme::Avail),
             "celestia" => Ok(OpenDAScheme::Celestia),
@@ -145,6 +159,7 @@ impl From<OpenDAScheme> for opendal::Scheme {
             OpenDAScheme::Fs => opendal::Scheme::Fs,
             OpenDAScheme::Gcs => opendal::Scheme::Gcs,
             OpenDAScheme::S3 => opendal::Scheme::S3,
+            OpenDAScheme::Cas => opendal::Scheme::Custom("cas"),
             OpenDAScheme::Avail => opendal::Scheme::Custom("avail"),
             OpenDAScheme::Celestia => opendal::Scheme::Custom("celestia"),
         }
@@ -382,6 +397,13 @@ pub struct DABackendOpenDAConfig {
     /// - This configuration can help fine-tune the reliability of segment submission in case of transient errors.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_retries: Option<usize>,
+
+    /// Specifies the codec (`none`, `zstd` or `brotli`) compressing batches into chunks.
+    ///
+    /// - If not set, batches are packed into v0 chunks (lz4), which every DA reader understands.
+    /// - If set, batches are packed into v1 chunks carrying the codec, which need an upgraded reader.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_codec: Option<ChunkCodec>,
 }
 
 /// Derives a namespace from the genesis hash for the DA backend.
@@ -410,6 +432,7 @@ mod tests {
                     namespace: None,
                     max_segment_size: None,
                     max_retries: None,
+                    chunk_codec: None,
                 }),
                 DABackendConfigType::OpenDa(DABackendOpenDAConfig {
                     scheme: OpenDAScheme::Fs,
@@ -417,6 +440,7 @@ mod tests {
                     namespace: None,
                     max_segment_size: None,
                     max_retries: None,
+                    chunk_codec: None,
                 }),
             ],
         };
@@ -435,6 +459,18 @@ mod tests {
         assert_eq!(da_backend_config.calculate_submit_threshold(), 2);
     }
 
+    #[test]
+    fn da_config_chunk_codec_from_str() {
+        let da_config_str = r#"{"da-backend": {"backends": [{"open-da": {"scheme": "fs", "config": {}, "chunk-codec": "zstd"}}]}}"#;
+        let da_config = DAConfig::from_str(da_config_str).unwrap();
+        let backends = da_config.da_backend.unwrap().backends;
+        let DABackendConfigType::OpenDa(open_da_config) = &backends[0];
+        assert_eq!(open_da_config.chunk_codec, Some(ChunkCodec::Zstd));
+
+        let da_config_str = r#"{"da-backend": {"backends": [{"open-da": {"scheme": "fs", "config": {}, "chunk-codec": "lz4"}}]}}"#;
+        assert!(DAConfig::from_str(da_config_str).is_err());
+    }
+
     #[test]
     fn da_config_from_str() {
         let da_config_str = r#"{"da-backend": {"submit-strategy": "all",
@@ -453,6 +489,7 @@ mod tests {
             namespace: None,
             max_segment_size: None,
             max_retries: None,
+            chunk_codec: None,
         };
         let exp_celestia_config = DABackendOpenDAConfig {
             scheme: OpenDAScheme::Celestia,
@@ -467,6 +504,7 @@ mod tests {
             ),
             max_segment_size: None,
             max_retries: None,
+            chunk_codec: None,
         };
         let exp_fs_config = DABackendOpenDAConfig {
             scheme: OpenDAScheme::Fs,
@@ -474,6 +512,7 @@ mod tests {
             namespace: None,
             max_segment_size: None,
             max_retries: None,
+            chunk_codec: None,
         };
         let exp_da_config = DAConfig {
             da_backend: Some(DABackendConfig {
@@ -542,6 +581,7 @@ mod tests {
                         namespace: None,
                         max_segment_size: None,
                         max_retries: None,
+                        chunk_codec: None,
                     }),
                     DABackendConfigType::OpenDa(DABackendOpenDAConfig {
                         scheme: OpenDAScheme::Avail,
@@ -560,6 +600,7 @@ mod tests {
                         namespace: None,
                         max_segment_size: None,
                         max_retries: None,
+                        chunk_codec: None,
                     }),
                 ],
             }),
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::da_config::DAConfig;
+use crate::online_gc_config::OnlineGCConfig;
 use crate::proposer_config::ProposerConfig;
 use crate::store_config::StoreConfig;
 use anyhow::Result;
@@ -22,6 +23,7 @@ use std::{fmt::Debug, path::Path, path::PathBuf};
 
 pub mod config;
 pub mod da_config;
+pub mod online_gc_config;
 pub mod proposer_config;
 pub mod server_config;
 pub mod settings;
@@ -153,6 +155,9 @@ pub struct RoochOpt {
     #[clap(flatten)]
     pub proposer: ProposerConfig,
 
+    #[clap(flatten)]
+    pub gc: OnlineGCConfig,
+
     #[clap(long, default_value_t, value_enum)]
     pub service_status: ServiceStatus,
 
@@ -178,9 +183,20 @@ pub struct RoochOpt {
     #[clap(long)]
     pub requests_per_second: Option<f64>,
 
+    /// The yaml file of the per method and per API key rate limits of the JSON-RPC calls.
+    /// See `RateLimitConfig` for the fields.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    pub rate_limit_config: Option<PathBuf>,
+
     #[clap(long, default_value_t, value_enum)]
     pub service_type: ServiceType,
 
+    /// Serve the GraphQL read API at `/graphql`, alongside the JSON-RPC over HTTP.
+    #[serde(default)]
+    #[clap(long)]
+    pub graphql: bool,
+
     #[serde(skip)]
     #[clap(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -216,12 +232,15 @@ impl RoochOpt {
             proposer_account: None,
             da: DAConfig::default(),
             proposer: ProposerConfig::default(),
+            gc: OnlineGCConfig::default(),
             service_status: ServiceStatus::default(),
             traffic_per_second: None,
             traffic_burst_size: None,
             requests_per_second: None,
+            rate_limit_config: None,
             base: None,
             service_type: ServiceType::default(),
+            graphql: false,
         };
         opt.init()?;
         Ok(opt)
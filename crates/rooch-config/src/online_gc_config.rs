@@ -0,0 +1,96 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::Config;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+pub const DEFAULT_ONLINE_GC_INTERVAL_SECS: u64 = 3600;
+pub const DEFAULT_ONLINE_GC_BATCH_SIZE: usize = 10_000;
+pub const DEFAULT_ONLINE_GC_DELETE_RATE_LIMIT: u64 = 50_000;
+pub const DEFAULT_ONLINE_GC_MARKER_TARGET_FP_RATE: f64 = 0.0001;
+
+/// The garbage collection of the stale state nodes running inside the server
+#[derive(Clone, Default, Debug, Deserialize, PartialEq, Serialize, Parser)]
+#[serde(deny_unknown_fields)]
+pub struct OnlineGCConfig {
+    #[serde(default)]
+    #[clap(
+        name = "online-gc",
+        long,
+        help = "Run the state node garbage collection in the background"
+    )]
+    pub enable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "online-gc-interval",
+        long,
+        help = "The interval in seconds between two online GC cycles, default is 3600"
+    )]
+    pub interval: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "online-gc-retained-roots",
+        long,
+        help = "The number of recent state roots retained by the online GC, the older states are not readable after GC. Default is based on network: Local=1, Dev=1000, Test=1000, Main=30000"
+    )]
+    pub retained_roots: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "online-gc-batch-size",
+        long,
+        help = "The number of nodes deleted in a batch by the online GC, default is 10000"
+    )]
+    pub batch_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(
+        name = "online-gc-delete-rate-limit",
+        long,
+        help = "The max number of nodes deleted per second by the online GC, default is 50000"
+    )]
+    pub delete_rate_limit: Option<u64>,
+    #[serde(default)]
+    #[clap(
+        name = "online-gc-recycle-bin",
+        long,
+        help = "Save the nodes deleted by the online GC to the recycle bin"
+    )]
+    pub use_recycle_bin: bool,
+}
+
+impl OnlineGCConfig {
+    pub fn interval(&self) -> u64 {
+        self.interval.unwrap_or(DEFAULT_ONLINE_GC_INTERVAL_SECS)
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.unwrap_or(DEFAULT_ONLINE_GC_BATCH_SIZE)
+    }
+
+    pub fn delete_rate_limit(&self) -> u64 {
+        self.delete_rate_limit
+            .unwrap_or(DEFAULT_ONLINE_GC_DELETE_RATE_LIMIT)
+    }
+}
+
+impl Config for OnlineGCConfig {}
+
+impl std::fmt::Display for OnlineGCConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_e| std::fmt::Error)?
+        )
+    }
+}
+
+impl FromStr for OnlineGCConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, Self::Err> {
+        let deserialized: OnlineGCConfig = serde_json::from_str(s)?;
+        Ok(deserialized)
+    }
+}
@@ -4,13 +4,20 @@
 use crate::config::Config;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Result, Write};
+use std::net::IpAddr;
+
+pub const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+pub const DEFAULT_COMPUTE_UNITS: u64 = 1;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
     pub block_propose_duration_in_seconds: u16,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
 }
 
 impl ServerConfig {
@@ -26,6 +33,11 @@ impl ServerConfig {
             ..Default::default()
         }
     }
+
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
 }
 
 impl Config for ServerConfig {}
@@ -47,6 +59,71 @@ impl Default for ServerConfig {
             host: "0.0.0.0".to_string(),
             port: 6767,
             block_propose_duration_in_seconds: 5,
+            rate_limit: RateLimitConfig::default(),
+        }
+    }
+}
+
+/// A token bucket of compute units, it holds `burst` units at most and
+/// replenishes `per_second` units every second.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    pub per_second: u64,
+    pub burst: u64,
+}
+
+/// The rate limits of the JSON-RPC calls and the SSE subscriptions, on top of the per IP traffic limit.
+///
+/// Every call is charged the compute units of the method. The client is identified by the API key
+/// in `api_key_header`, or by the IP if the request has no configured API key. The forwarded IP
+/// headers are used only if the request comes from one of `trusted_proxies`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// The request header carrying the API key
+    pub api_key_header: String,
+    /// The compute units of a call to the methods not in `method_compute_units`
+    pub default_compute_units: u64,
+    /// The compute units of a call by method name, e.g. `rooch_queryObjectStates: 20`
+    pub method_compute_units: BTreeMap<String, u64>,
+    /// The compute units limit of each client calling the method, by method name
+    pub method_limits: BTreeMap<String, RateLimit>,
+    /// The compute units limit across all methods, by API key
+    pub api_key_limits: BTreeMap<String, RateLimit>,
+    /// The compute units limit across all methods of each IP without API key, no limit if None
+    pub anonymous_limit: Option<RateLimit>,
+    /// The IPs of the reverse proxies whose `x-forwarded-for` and `x-real-ip` headers are trusted
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimitConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.method_limits.is_empty()
+            || !self.api_key_limits.is_empty()
+            || self.anonymous_limit.is_some()
+    }
+
+    pub fn compute_units(&self, method: &str) -> u64 {
+        self.method_compute_units
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_compute_units)
+    }
+}
+
+impl Config for RateLimitConfig {}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            default_compute_units: DEFAULT_COMPUTE_UNITS,
+            method_compute_units: BTreeMap::new(),
+            method_limits: BTreeMap::new(),
+            api_key_limits: BTreeMap::new(),
+            anonymous_limit: None,
+            trusted_proxies: vec![],
         }
     }
 }
@@ -132,12 +132,18 @@ impl DAServerActor {
         {
             let identifier = identifier.clone();
             // Get the latest done chunk id
-            // (it's block number too for ChunkV0, which is the only version now)
+            // (it's block number too, both ChunkV0 and ChunkV1 map one chunk to one batch)
             let future = stat.get_latest_done_chunk_id();
             let result = future.await; // Resolve the future
             let result = result.unwrap_or(0); // for compatibility with old version which doesn't have this Optional field
             avail_backends.push((identifier, result));
         }
+        let backend_submit_stats = self
+            .backend_identifiers
+            .iter()
+            .cloned()
+            .zip(self.adapter_stats.iter().map(|stat| stat.snapshot()))
+            .collect();
 
         Ok(DAServerStatus {
             last_block_number: self.last_block_number,
@@ -147,6 +153,7 @@ impl DAServerActor {
             last_avail_tx_order,
             last_avail_block_update_time,
             avail_backends,
+            backend_submit_stats,
         })
     }
 
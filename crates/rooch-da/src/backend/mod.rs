@@ -13,10 +13,11 @@ pub mod openda;
 
 // manually set backend priority
 // lower index means higher priority
-pub const BACKENDS_PRIORITY: [&str; 5] = [
+pub const BACKENDS_PRIORITY: [&str; 6] = [
     "openda-fs",
     "openda-gcs",
     "openda-s3",
+    "openda-cas",
     "openda-avail",
     "openda-celestia",
 ];
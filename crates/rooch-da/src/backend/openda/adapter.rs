@@ -8,17 +8,22 @@ use crate::backend::openda::celestia::{
     CelestiaAdapter, WrappedNamespace, DEFAULT_CELESTIA_MAX_RETRIES,
     DEFAULT_CELESTIA_MAX_SEGMENT_SIZE,
 };
-use crate::backend::openda::opendal::OpenDalAdapter;
+use crate::backend::openda::opendal::{OpenDalAdapter, CAS_STORAGE_KEY};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use rooch_config::da_config::{DABackendOpenDAConfig, OpenDAScheme};
 use rooch_config::retrieve_map_config_value;
+use rooch_types::da::chunk::ChunkCodec;
 use rooch_types::da::segment::SegmentID;
+use rooch_types::da::status::AdapterSubmitStatSnapshot;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 const DEFAULT_MAX_SEGMENT_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_S3_REGION: &str = "us-east-1";
 pub(crate) const DEFAULT_MAX_RETRY_TIMES: usize = 3;
 
 /// OpenDAAdapter connecting to OpenDA-compatible backends
@@ -30,11 +35,29 @@ pub(crate) trait OpenDAAdapter: Sync + Send {
         segment_bytes: &[u8],
         is_last_segment: bool,
     ) -> anyhow::Result<()>;
+
+    /// Read back the bytes of a submitted segment.
+    async fn read_segment(&self, segment_id: SegmentID) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow!(
+            "reading segment {} is not supported by this backend",
+            segment_id
+        ))
+    }
 }
 
 #[derive(Clone)]
 pub struct AdapterSubmitStat {
     inner: Arc<RwLock<AdapterSubmitStatInner>>,
+    counters: Arc<AdapterSubmitCounters>,
+}
+
+#[derive(Default)]
+struct AdapterSubmitCounters {
+    submitted_segments: AtomicU64,
+    submitted_bytes: AtomicU64,
+    deduplicated_segments: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
 }
 
 struct AdapterSubmitStatInner {
@@ -57,6 +80,7 @@ impl AdapterSubmitStat {
                 submitted_set: Vec::new(),
                 latest_done_chunk_id: u128::MAX,
             })),
+            counters: Arc::new(AdapterSubmitCounters::default()),
         }
     }
 
@@ -107,6 +131,38 @@ impl AdapterSubmitStat {
             Some(chunk_id)
         }
     }
+
+    pub fn add_submitted_segment(&self, segment_size: usize, deduplicated: bool) {
+        self.counters
+            .submitted_segments
+            .fetch_add(1, Ordering::Relaxed);
+        self.counters
+            .submitted_bytes
+            .fetch_add(segment_size as u64, Ordering::Relaxed);
+        if deduplicated {
+            self.counters
+                .deduplicated_segments
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn add_retry(&self) {
+        self.counters.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn add_failure(&self) {
+        self.counters.failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> AdapterSubmitStatSnapshot {
+        AdapterSubmitStatSnapshot {
+            submitted_segments: self.counters.submitted_segments.load(Ordering::Relaxed),
+            submitted_bytes: self.counters.submitted_bytes.load(Ordering::Relaxed),
+            deduplicated_segments: self.counters.deduplicated_segments.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+            failures: self.counters.failures.load(Ordering::Relaxed),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -116,6 +172,7 @@ pub(crate) struct OpenDAAdapterConfig {
     pub(crate) max_retries: usize,
     pub(crate) scheme: OpenDAScheme,
     pub(crate) scheme_config: HashMap<String, String>,
+    pub(crate) chunk_codec: Option<ChunkCodec>,
 }
 
 impl OpenDAAdapterConfig {
@@ -148,6 +205,7 @@ impl OpenDAAdapterConfig {
             max_retries,
             scheme,
             scheme_config,
+            chunk_codec: open_da_config.chunk_codec,
         })
     }
 
@@ -235,9 +293,86 @@ fn check_scheme_config(
             }?;
         }
 
+        OpenDAScheme::S3 => {
+            retrieve_map_config_value(config, "bucket", Some("OPENDA_S3_BUCKET"), None);
+            retrieve_map_config_value(config, "endpoint", Some("OPENDA_S3_ENDPOINT"), None);
+            retrieve_map_config_value(
+                config,
+                "region",
+                Some("OPENDA_S3_REGION"),
+                Some(DEFAULT_S3_REGION),
+            );
+            retrieve_map_config_value(
+                config,
+                "access_key_id",
+                Some("OPENDA_S3_ACCESS_KEY_ID"),
+                None,
+            );
+            retrieve_map_config_value(
+                config,
+                "secret_access_key",
+                Some("OPENDA_S3_SECRET_ACCESS_KEY"),
+                None,
+            );
+            retrieve_map_config_value(
+                config,
+                "path_style",
+                Some("OPENDA_S3_PATH_STYLE"),
+                Some("true"),
+            );
+
+            check_config_exist(OpenDAScheme::S3, config, "bucket")?;
+            // credentials must be set in pairs, or neither for loading from the environment
+            match (
+                check_config_exist(OpenDAScheme::S3, config, "access_key_id"),
+                check_config_exist(OpenDAScheme::S3, config, "secret_access_key"),
+            ) {
+                (Ok(_), Ok(_)) | (Err(_), Err(_)) => Ok(()),
+                _ => Err(anyhow!(
+                    "access_key_id and secret_access_key must be both set for scheme {:?}",
+                    OpenDAScheme::S3
+                )),
+            }?;
+
+            // path style is what S3-compatible services (e.g. MinIO) serve by default,
+            // opendal uses it unless virtual host style is enabled
+            let path_style = config.remove("path_style").unwrap_or_default();
+            let path_style = bool::from_str(&path_style).map_err(|_| {
+                anyhow!(
+                    "path_style must be true or false for scheme {:?}, got: {}",
+                    OpenDAScheme::S3,
+                    path_style
+                )
+            })?;
+            if !path_style {
+                config.insert("enable_virtual_host_style".to_string(), "true".to_string());
+            }
+        }
         OpenDAScheme::Celestia => {
             check_config_exist(OpenDAScheme::Celestia, config, "endpoint")?;
         }
+        OpenDAScheme::Cas => {
+            retrieve_map_config_value(
+                config,
+                CAS_STORAGE_KEY,
+                Some("OPENDA_CAS_STORAGE"),
+                Some("fs"),
+            );
+            let storage = OpenDAScheme::from_str(&config[CAS_STORAGE_KEY])
+                .map_err(|e| anyhow!("invalid storage for scheme {:?}: {}", scheme, e))?;
+            if !matches!(
+                storage,
+                OpenDAScheme::Fs | OpenDAScheme::Gcs | OpenDAScheme::S3
+            ) {
+                return Err(anyhow!(
+                    "storage of scheme {:?} must be one of fs, gcs and s3, got: {}",
+                    scheme,
+                    storage
+                ));
+            }
+            // the storage config is flattened into the same map
+            return check_scheme_config(storage, config, namespace);
+        }
         _ => {}
     };
 
@@ -355,6 +490,104 @@ mod tests {
         assert_eq!(map_config.get("default_storage_class").unwrap(), "STANDARD");
     }
 
+    #[test]
+    fn check_scheme_config_s3() {
+        let scheme = OpenDAScheme::S3;
+        let mut map_config = HashMap::new();
+        map_config.insert("endpoint".to_string(), "http://127.0.0.1:9000".to_string());
+        let result =
+            check_scheme_config(scheme.clone(), &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(
+            result.is_err(),
+            "S3 scheme should return Err if 'bucket' is missing"
+        );
+
+        map_config.insert("bucket".to_string(), "test_bucket".to_string());
+        map_config.insert("access_key_id".to_string(), "minioadmin".to_string());
+        let result =
+            check_scheme_config(scheme.clone(), &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(
+            result.is_err(),
+            "S3 scheme should return Err if only 'access_key_id' is provided"
+        );
+
+        map_config.insert("secret_access_key".to_string(), "minioadmin".to_string());
+        let result =
+            check_scheme_config(scheme.clone(), &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        assert_eq!(map_config.get("region").unwrap(), DEFAULT_S3_REGION);
+        assert_eq!(map_config.get("root").unwrap(), "/test_namespace");
+        assert!(!map_config.contains_key("path_style"));
+        assert!(!map_config.contains_key("enable_virtual_host_style"));
+
+        let mut map_config = HashMap::new();
+        map_config.insert("bucket".to_string(), "test_bucket".to_string());
+        map_config.insert("path_style".to_string(), "false".to_string());
+        let result =
+            check_scheme_config(scheme.clone(), &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        assert_eq!(map_config.get("enable_virtual_host_style").unwrap(), "true");
+
+        let mut map_config = HashMap::new();
+        map_config.insert("bucket".to_string(), "test_bucket".to_string());
+        map_config.insert("path_style".to_string(), "yes".to_string());
+        let result = check_scheme_config(scheme, &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(
+            result.is_err(),
+            "S3 scheme should reject invalid 'path_style'"
+        );
+    }
+
+    #[test]
+    fn check_scheme_config_cas() {
+        let scheme = OpenDAScheme::Cas;
+        let mut map_config = HashMap::new();
+        map_config.insert("root".to_string(), "/some/path".to_string());
+        let result =
+            check_scheme_config(scheme.clone(), &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+        assert_eq!(map_config.get(CAS_STORAGE_KEY).unwrap(), "fs");
+        assert_eq!(map_config.get("root").unwrap(), "/some/path/test_namespace");
+
+        let mut map_config = HashMap::new();
+        map_config.insert(CAS_STORAGE_KEY.to_string(), "s3".to_string());
+        let result =
+            check_scheme_config(scheme.clone(), &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(
+            result.is_err(),
+            "CAS scheme should check the config of its storage"
+        );
+
+        let mut map_config = HashMap::new();
+        map_config.insert(CAS_STORAGE_KEY.to_string(), "celestia".to_string());
+        let result = check_scheme_config(scheme, &mut map_config, TEST_NAMESPACE.to_string());
+        assert!(
+            result.is_err(),
+            "CAS scheme should return Err for non object storage"
+        );
+    }
+
+    #[test]
+    fn test_adapter_submit_counters() {
+        let stats = AdapterSubmitStat::new();
+        assert_eq!(stats.snapshot(), AdapterSubmitStatSnapshot::default());
+
+        stats.add_submitted_segment(10, false);
+        stats.add_submitted_segment(20, true);
+        stats.add_retry();
+        stats.clone().add_failure();
+        assert_eq!(
+            stats.snapshot(),
+            AdapterSubmitStatSnapshot {
+                submitted_segments: 2,
+                submitted_bytes: 30,
+                deduplicated_segments: 1,
+                retries: 1,
+                failures: 1,
+            }
+        );
+    }
+
     #[tokio::test]
     async fn test_adapter_submit_stats() {
         let stats = AdapterSubmitStat::new();
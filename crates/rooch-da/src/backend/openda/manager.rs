@@ -7,7 +7,8 @@ use crate::backend::DABackend;
 use async_trait::async_trait;
 use rooch_config::da_config::DABackendOpenDAConfig;
 use rooch_types::da::batch::DABatch;
-use rooch_types::da::chunk::{Chunk, ChunkV0};
+use rooch_types::da::chunk::chunk_from_batch;
+use rooch_types::da::segment::SegmentID;
 use std::sync::Arc;
 
 /// manage OpenDA backends while integrating specific adapter logic
@@ -34,12 +35,18 @@ impl OpenDABackendManager {
             adapter,
         })
     }
+
+    /// Read back the bytes of a submitted segment.
+    /// For the content-addressed scheme, the ref is resolved and the blob digest is verified.
+    pub async fn read_segment(&self, segment_id: SegmentID) -> anyhow::Result<Vec<u8>> {
+        self.adapter.read_segment(segment_id).await
+    }
 }
 
 #[async_trait]
 impl DABackend for OpenDABackendManager {
     async fn submit_batch(&self, batch: Arc<DABatch>) -> anyhow::Result<()> {
-        let chunk: ChunkV0 = (*batch).clone().into();
+        let chunk = chunk_from_batch((*batch).clone(), self.adapter_config.chunk_codec);
 
         let max_segment_size = self.adapter_config.max_segment_size;
 
@@ -2,35 +2,89 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::backend::openda::adapter::{AdapterSubmitStat, OpenDAAdapter};
+use anyhow::anyhow;
 use async_trait::async_trait;
-use opendal::layers::{LoggingLayer, RetryLayer};
-use opendal::{Operator, Scheme};
+use opendal::layers::{LoggingLayer, RetryInterceptor, RetryLayer};
+use opendal::{ErrorKind, Operator, Scheme};
 use rooch_config::da_config::OpenDAScheme;
 use rooch_types::da::segment::SegmentID;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::time::Duration;
 
 pub(crate) const BACK_OFF_MIN_DELAY: Duration = Duration::from_millis(300);
 
+/// Config key of the content-addressed scheme selecting the underlying storage.
+pub(crate) const CAS_STORAGE_KEY: &str = "storage";
+/// Directory of the content-addressed blobs, named by the sha256 hex of the segment bytes.
+pub(crate) const CAS_BLOBS_DIR: &str = "blobs";
+/// Directory of the refs, each one maps a segment id to its blob digest.
+pub(crate) const CAS_REFS_DIR: &str = "refs";
+
+pub(crate) fn cas_blob_path(digest: &str) -> String {
+    format!("{}/{}", CAS_BLOBS_DIR, digest)
+}
+
+pub(crate) fn cas_ref_path(segment_id: SegmentID) -> String {
+    format!("{}/{}", CAS_REFS_DIR, segment_id)
+}
+
+/// Counts the retries of the operator into the submit stats.
+struct SubmitRetryInterceptor {
+    scheme: OpenDAScheme,
+    stats: AdapterSubmitStat,
+}
+
+impl RetryInterceptor for SubmitRetryInterceptor {
+    fn intercept(&self, err: &opendal::Error, dur: Duration) {
+        self.stats.add_retry();
+        tracing::warn!(
+            "Retry submitting to {:?} after {:?}, error: {}",
+            self.scheme,
+            dur,
+            err
+        );
+    }
+}
+
 pub(crate) struct OpenDalAdapter {
     stats: AdapterSubmitStat,
     operator: Operator,
     scheme: OpenDAScheme,
+    // store segments as content-addressed blobs with refs, instead of by segment id
+    content_addressed: bool,
 }
 
 impl OpenDalAdapter {
     pub(crate) async fn new(
         scheme: OpenDAScheme,
-        scheme_config: HashMap<String, String>,
+        mut scheme_config: HashMap<String, String>,
         max_retries: usize,
         stats: AdapterSubmitStat,
     ) -> anyhow::Result<Self> {
-        let mut op = opendal::Operator::via_iter(Scheme::from(scheme.clone()), scheme_config)?;
+        let (storage_scheme, content_addressed) = match scheme {
+            OpenDAScheme::Cas => {
+                let storage = scheme_config.remove(CAS_STORAGE_KEY).ok_or(anyhow!(
+                    "key {} must be existed in config for scheme {:?}",
+                    CAS_STORAGE_KEY,
+                    scheme
+                ))?;
+                let storage = OpenDAScheme::from_str(&storage).map_err(|e| anyhow!(e))?;
+                (Scheme::from(storage), true)
+            }
+            _ => (Scheme::from(scheme.clone()), false),
+        };
+        let mut op = opendal::Operator::via_iter(storage_scheme, scheme_config)?;
         op = op
             .layer(
                 RetryLayer::new()
                     .with_max_times(max_retries)
-                    .with_min_delay(BACK_OFF_MIN_DELAY),
+                    .with_min_delay(BACK_OFF_MIN_DELAY)
+                    .with_notify(SubmitRetryInterceptor {
+                        scheme: scheme.clone(),
+                        stats: stats.clone(),
+                    }),
             )
             .layer(LoggingLayer::default());
         op.check().await?;
@@ -38,16 +92,48 @@ impl OpenDalAdapter {
             stats,
             operator: op,
             scheme,
+            content_addressed,
         })
     }
 
-    async fn submit(&self, segment_id: SegmentID, segment_bytes: &[u8]) -> anyhow::Result<()> {
-        let path = segment_id.to_string();
-        let mut w = self.operator.writer(&path).await?;
-        w.write(segment_bytes.to_vec()).await?;
+    async fn write(&self, path: &str, bytes: Vec<u8>) -> anyhow::Result<()> {
+        let mut w = self.operator.writer(path).await?;
+        w.write(bytes).await?;
         w.close().await?;
         Ok(())
     }
+
+    async fn read(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(self.operator.read(path).await?.to_vec())
+    }
+
+    async fn exists(&self, path: &str) -> anyhow::Result<bool> {
+        match self.operator.stat(path).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // returns true if the segment bytes have been stored before
+    async fn submit(&self, segment_id: SegmentID, segment_bytes: &[u8]) -> anyhow::Result<bool> {
+        if !self.content_addressed {
+            self.write(&segment_id.to_string(), segment_bytes.to_vec())
+                .await?;
+            return Ok(false);
+        }
+
+        // write the blob before the ref, so a ref always points to an existing blob
+        let digest = hex::encode(Sha256::digest(segment_bytes));
+        let blob_path = cas_blob_path(&digest);
+        let deduplicated = self.exists(&blob_path).await?;
+        if !deduplicated {
+            self.write(&blob_path, segment_bytes.to_vec()).await?;
+        }
+        self.write(&cas_ref_path(segment_id), digest.into_bytes())
+            .await?;
+        Ok(deduplicated)
+    }
 }
 
 #[async_trait]
@@ -59,14 +145,318 @@ impl OpenDAAdapter for OpenDalAdapter {
         is_last_segment: bool,
     ) -> anyhow::Result<()> {
         match self.submit(segment_id, segment_bytes).await {
-            Ok(_) => {
-                tracing::info!("Submitted segment: {} to {:?}", segment_id, self.scheme,);
+            Ok(deduplicated) => {
+                tracing::info!(
+                    "Submitted segment: {} to {:?}, deduplicated: {}",
+                    segment_id,
+                    self.scheme,
+                    deduplicated
+                );
+                self.stats
+                    .add_submitted_segment(segment_bytes.len(), deduplicated);
                 self.stats
                     .add_done_segment(segment_id, is_last_segment)
                     .await;
                 Ok(())
             }
-            Err(error) => Err(error),
+            Err(error) => {
+                self.stats.add_failure();
+                Err(error)
+            }
+        }
+    }
+
+    async fn read_segment(&self, segment_id: SegmentID) -> anyhow::Result<Vec<u8>> {
+        if !self.content_addressed {
+            return self.read(&segment_id.to_string()).await;
+        }
+
+        let digest = String::from_utf8(self.read(&cas_ref_path(segment_id)).await?)
+            .map_err(|e| anyhow!("invalid ref of segment {}: {}", segment_id, e))?;
+        if digest.len() != 64 || !digest.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(anyhow!("invalid ref of segment {}: {}", segment_id, digest));
+        }
+        let bytes = self.read(&cas_blob_path(&digest)).await?;
+        // the blob is named by its digest, a mismatch means the blob is corrupted
+        let actual_digest = hex::encode(Sha256::digest(&bytes));
+        if actual_digest != digest {
+            return Err(anyhow!(
+                "blob of segment {} is corrupted, expected digest: {}, actual: {}",
+                segment_id,
+                digest,
+                actual_digest
+            ));
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fs_config(root: &std::path::Path) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("root".to_string(), root.to_string_lossy().to_string());
+        config
+    }
+
+    #[tokio::test]
+    async fn test_submit_segment_fs() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let stats = AdapterSubmitStat::new();
+        let adapter = OpenDalAdapter::new(
+            OpenDAScheme::Fs,
+            fs_config(tmp_dir.path()),
+            1,
+            stats.clone(),
+        )
+        .await
+        .unwrap();
+
+        let segment_id = SegmentID {
+            chunk_id: 1,
+            segment_number: 0,
+        };
+        adapter
+            .submit_segment(segment_id, b"segment", true)
+            .await
+            .unwrap();
+        let bytes = std::fs::read(tmp_dir.path().join(segment_id.to_string())).unwrap();
+        assert_eq!(bytes, b"segment");
+        assert_eq!(adapter.read_segment(segment_id).await.unwrap(), b"segment");
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.submitted_segments, 1);
+        assert_eq!(snapshot.submitted_bytes, 7);
+        assert_eq!(snapshot.deduplicated_segments, 0);
+        assert_eq!(stats.get_latest_done_chunk_id().await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_submit_segment_content_addressed() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let stats = AdapterSubmitStat::new();
+        let mut config = fs_config(tmp_dir.path());
+        config.insert(CAS_STORAGE_KEY.to_string(), "fs".to_string());
+        let adapter = OpenDalAdapter::new(OpenDAScheme::Cas, config, 1, stats.clone())
+            .await
+            .unwrap();
+
+        let segment_id1 = SegmentID {
+            chunk_id: 1,
+            segment_number: 0,
+        };
+        let segment_id2 = SegmentID {
+            chunk_id: 2,
+            segment_number: 0,
+        };
+        adapter
+            .submit_segment(segment_id1, b"segment", true)
+            .await
+            .unwrap();
+        adapter
+            .submit_segment(segment_id2, b"segment", true)
+            .await
+            .unwrap();
+
+        let digest = hex::encode(Sha256::digest(b"segment"));
+        let blob = std::fs::read(tmp_dir.path().join(cas_blob_path(&digest))).unwrap();
+        assert_eq!(blob, b"segment");
+        for segment_id in [segment_id1, segment_id2] {
+            let reference = std::fs::read(tmp_dir.path().join(cas_ref_path(segment_id))).unwrap();
+            assert_eq!(reference, digest.as_bytes());
+            assert_eq!(adapter.read_segment(segment_id).await.unwrap(), b"segment");
+        }
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.submitted_segments, 2);
+        assert_eq!(snapshot.deduplicated_segments, 1);
+        assert_eq!(snapshot.failures, 0);
+        assert_eq!(stats.get_latest_done_chunk_id().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_read_segment_content_addressed_corrupted() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let mut config = fs_config(tmp_dir.path());
+        config.insert(CAS_STORAGE_KEY.to_string(), "fs".to_string());
+        let adapter = OpenDalAdapter::new(OpenDAScheme::Cas, config, 1, AdapterSubmitStat::new())
+            .await
+            .unwrap();
+
+        let segment_id = SegmentID {
+            chunk_id: 1,
+            segment_number: 0,
+        };
+        assert!(adapter.read_segment(segment_id).await.is_err());
+
+        adapter
+            .submit_segment(segment_id, b"segment", true)
+            .await
+            .unwrap();
+        let digest = hex::encode(Sha256::digest(b"segment"));
+        std::fs::write(tmp_dir.path().join(cas_blob_path(&digest)), b"tampered").unwrap();
+        let err = adapter.read_segment(segment_id).await.unwrap_err();
+        assert!(err.to_string().contains("corrupted"), "{}", err);
+
+        // a ref must be a digest, not a path
+        std::fs::write(
+            tmp_dir.path().join(cas_ref_path(segment_id)),
+            format!("../{}", segment_id),
+        )
+        .unwrap();
+        let err = adapter.read_segment(segment_id).await.unwrap_err();
+        assert!(err.to_string().contains("invalid ref"), "{}", err);
+    }
+
+    /// A minimal S3 stand-in serving path style object requests from memory,
+    /// it implements what the adapter uses: list (for the check), put, head and get.
+    mod s3_stand_in {
+        use axum::body::Bytes;
+        use axum::extract::{Path, State};
+        use axum::http::{header, StatusCode};
+        use axum::response::{IntoResponse, Response};
+        use axum::routing::get;
+        use axum::Router;
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+
+        pub(super) type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+        async fn list_objects(Path(_bucket): Path<String>) -> Response {
+            (
+                [(header::CONTENT_TYPE, "application/xml")],
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?><ListBucketResult><IsTruncated>false</IsTruncated></ListBucketResult>",
+            )
+                .into_response()
+        }
+
+        async fn get_object(
+            State(objects): State<Objects>,
+            Path((_bucket, key)): Path<(String, String)>,
+        ) -> Response {
+            match objects.lock().unwrap().get(&key) {
+                Some(bytes) => (
+                    [
+                        (header::CONTENT_TYPE, "application/octet-stream"),
+                        (header::ETAG, "\"etag\""),
+                    ],
+                    bytes.clone(),
+                )
+                    .into_response(),
+                None => StatusCode::NOT_FOUND.into_response(),
+            }
+        }
+
+        async fn put_object(
+            State(objects): State<Objects>,
+            Path((_bucket, key)): Path<(String, String)>,
+            body: Bytes,
+        ) -> Response {
+            objects.lock().unwrap().insert(key, body.to_vec());
+            ([(header::ETAG, "\"etag\"")], "").into_response()
+        }
+
+        /// Serve the stand-in on a random local port, returns its endpoint and objects.
+        pub(super) async fn serve() -> (String, Objects) {
+            let objects = Objects::default();
+            let app = Router::new()
+                .route("/:bucket", get(list_objects))
+                .route("/:bucket/*key", get(get_object).put(put_object))
+                .with_state(objects.clone());
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let endpoint = format!("http://{}", listener.local_addr().unwrap());
+            tokio::spawn(async move {
+                axum::serve(listener, app).await.unwrap();
+            });
+            (endpoint, objects)
+        }
+    }
+
+    fn s3_config(endpoint: &str) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("bucket".to_string(), "rooch".to_string());
+        config.insert("endpoint".to_string(), endpoint.to_string());
+        config.insert("region".to_string(), "us-east-1".to_string());
+        config.insert("access_key_id".to_string(), "minioadmin".to_string());
+        config.insert("secret_access_key".to_string(), "minioadmin".to_string());
+        config.insert("root".to_string(), "/test_namespace".to_string());
+        // never load credentials from the environment of the test runner
+        config.insert("disable_config_load".to_string(), "true".to_string());
+        config.insert("disable_ec2_metadata".to_string(), "true".to_string());
+        config
+    }
+
+    #[tokio::test]
+    async fn test_submit_segment_s3() {
+        let (endpoint, objects) = s3_stand_in::serve().await;
+        let stats = AdapterSubmitStat::new();
+        let adapter = OpenDalAdapter::new(OpenDAScheme::S3, s3_config(&endpoint), 1, stats.clone())
+            .await
+            .unwrap();
+
+        let segment_id = SegmentID {
+            chunk_id: 1,
+            segment_number: 0,
+        };
+        adapter
+            .submit_segment(segment_id, b"segment", true)
+            .await
+            .unwrap();
+        assert_eq!(
+            objects
+                .lock()
+                .unwrap()
+                .get(&format!("test_namespace/{}", segment_id))
+                .unwrap(),
+            b"segment"
+        );
+        assert_eq!(adapter.read_segment(segment_id).await.unwrap(), b"segment");
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.submitted_segments, 1);
+        assert_eq!(snapshot.submitted_bytes, 7);
+        assert_eq!(snapshot.failures, 0);
+        assert_eq!(stats.get_latest_done_chunk_id().await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_submit_segment_content_addressed_s3() {
+        let (endpoint, objects) = s3_stand_in::serve().await;
+        let stats = AdapterSubmitStat::new();
+        let mut config = s3_config(&endpoint);
+        config.insert(CAS_STORAGE_KEY.to_string(), "s3".to_string());
+        let adapter = OpenDalAdapter::new(OpenDAScheme::Cas, config, 1, stats.clone())
+            .await
+            .unwrap();
+
+        let segment_ids = [1, 2].map(|chunk_id| SegmentID {
+            chunk_id,
+            segment_number: 0,
+        });
+        for segment_id in segment_ids {
+            adapter
+                .submit_segment(segment_id, b"segment", true)
+                .await
+                .unwrap();
+        }
+
+        let digest = hex::encode(Sha256::digest(b"segment"));
+        {
+            let objects = objects.lock().unwrap();
+            assert_eq!(
+                objects
+                    .get(&format!("test_namespace/{}", cas_blob_path(&digest)))
+                    .unwrap(),
+                b"segment"
+            );
+            // one blob and a ref per segment
+            assert_eq!(objects.len(), 3);
+        }
+        for segment_id in segment_ids {
+            assert_eq!(adapter.read_segment(segment_id).await.unwrap(), b"segment");
         }
+        assert_eq!(stats.snapshot().deduplicated_segments, 1);
     }
 }
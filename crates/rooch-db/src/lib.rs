@@ -72,8 +72,10 @@ impl RoochDB {
         let indexer_dir = config.get_indexer_dir();
         let moveos_store = MoveOSStore::new_with_instance(instance.clone(), registry)?;
         let rooch_store = RoochStore::new_with_instance(instance.clone(), registry)?;
-        let indexer_store = IndexerStore::new(indexer_dir.clone(), registry)?;
-        let indexer_reader = IndexerReader::new(indexer_dir, registry)?;
+        let indexer_db_url = config.get_indexer_db_url();
+        let indexer_store =
+            IndexerStore::new_with_db_url(indexer_dir.clone(), indexer_db_url, registry)?;
+        let indexer_reader = IndexerReader::new_with_db_url(indexer_dir, indexer_db_url, registry)?;
 
         Ok(Self {
             moveos_store,
@@ -6,6 +6,9 @@ use super::messages::{
     ExecuteTransactionMessage, ExecuteTransactionResult, GetRootMessage, SaveStateChangeSetMessage,
     ValidateL1BlockMessage, ValidateL1TxMessage, ValidateL2TxMessage,
 };
+use super::reader_executor::{
+    check_historical_vm_error_message, is_missing_node_error, map_missing_node_error,
+};
 use crate::metrics::ExecutorMetrics;
 use anyhow::Result;
 use async_trait::async_trait;
@@ -13,7 +16,7 @@ use coerce::actor::{context::ActorContext, message::Handler, Actor, LocalActorRe
 use function_name::named;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::vm_status::VMStatus;
-use moveos::moveos::{MoveOS, MoveOSConfig};
+use moveos::moveos::{GasPaymentAccount, MoveOS, MoveOSConfig};
 use moveos::vm::vm_status_explainer::explain_vm_status;
 use moveos_eventbus::bus::EventData;
 use moveos_store::MoveOSStore;
@@ -25,7 +28,9 @@ use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::moveos_std::tx_meta::TxMeta;
 use moveos_types::state::{ObjectState, StateChangeSetExt};
 use moveos_types::state_resolver::RootObjectResolver;
-use moveos_types::transaction::{FunctionCall, MoveOSTransaction, VerifiedMoveAction};
+use moveos_types::transaction::{
+    FunctionCall, MoveOSTransaction, RawTransactionOutput, VerifiedMoveAction,
+};
 use moveos_types::transaction::{MoveAction, VerifiedMoveOSTransaction};
 use prometheus::Registry;
 use rooch_genesis::FrameworksGasParameters;
@@ -45,9 +50,10 @@ use rooch_types::framework::ethereum::EthereumModule;
 use rooch_types::framework::transaction_validator::TransactionValidator;
 use rooch_types::framework::{system_post_execute_functions, system_pre_execute_functions};
 use rooch_types::multichain_id::RoochMultiChainID;
-use rooch_types::transaction::authenticator::AUTH_PAYLOAD_SIZE;
+use rooch_types::transaction::authenticator::{SponsorAuthenticator, AUTH_PAYLOAD_SIZE};
+use rooch_types::transaction::state_diff::StateDiff;
 use rooch_types::transaction::{
-    AuthenticatorInfo, L1Block, L1BlockWithBody, L1Transaction, RoochTransaction,
+    Authenticator, AuthenticatorInfo, L1Block, L1BlockWithBody, L1Transaction, RoochTransaction,
     RoochTransactionData,
 };
 use std::str::FromStr;
@@ -166,13 +172,69 @@ impl ExecutorActor {
             .executor_execute_tx_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
-        let (raw_output, vm_error_info) = self.moveos.execute_only(tx)?;
+        let pre_root = tx.root.clone();
+        let state_root = pre_root.state_root();
+        // The historical root may be partially pruned, report it instead of the execution error
+        let is_historical = state_root != self.root.state_root();
+        let map_err = |err: anyhow::Error| {
+            if is_historical {
+                map_missing_node_error(state_root, err)
+            } else {
+                err
+            }
+        };
+        let (raw_output, vm_error_info) = self.moveos.execute_only(tx).map_err(map_err)?;
+        if let (true, Some(vm_error_info)) = (is_historical, &vm_error_info) {
+            check_historical_vm_error_message(state_root, &vm_error_info.error_message)?;
+        }
+        let resolver = RootObjectResolver::new(pre_root, &self.moveos_store);
+        let state_diff =
+            match StateDiff::build(&resolver, &raw_output.changeset, &raw_output.events) {
+                Ok(state_diff) => state_diff,
+                Err(err) if is_historical && is_missing_node_error(&err) => {
+                    return Err(map_err(err));
+                }
+                // The diff is informational, the dry run result is returned with the raw diff
+                Err(err) => {
+                    tracing::warn!(
+                        "Build the state diff of the dry run tx failed: {:?}, return the raw diff",
+                        err
+                    );
+                    StateDiff::raw(&raw_output.changeset, &raw_output.events)
+                }
+            };
         Ok(DryRunTransactionResult {
             raw_output,
             vm_error_info,
+            state_diff,
         })
     }
 
+    /// Execute the transaction and only write the changed state nodes to the store.
+    /// The execution info, events and startup info are not saved, so the execution records of
+    /// the store are untouched. It is used to re-execute historical transactions for verification.
+    #[named]
+    pub fn execute_state_only(
+        &mut self,
+        tx: VerifiedMoveOSTransaction,
+    ) -> Result<RawTransactionOutput> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .executor_execute_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let (mut raw_output, _) = self.moveos.execute_only(tx)?;
+        self.moveos_store
+            .get_state_store()
+            .apply_change_set(&mut raw_output.changeset)?;
+        self.root = ObjectMeta::root_metadata(
+            raw_output.changeset.state_root,
+            raw_output.changeset.global_size,
+        );
+        Ok(raw_output)
+    }
+
     #[named]
     pub fn validate_l1_block(
         &self,
@@ -297,6 +359,8 @@ impl ExecutorActor {
         tracing::debug!("executor validate_l2_tx: {:?}, sender: {}", tx_hash, sender);
 
         let authenticator = tx.authenticator_info();
+        let sponsor = SponsorAuthenticator::try_from_authenticator(&authenticator.authenticator)?
+            .map(|sponsor_authenticator| sponsor_authenticator.sponsor());
         let mut moveos_tx: MoveOSTransaction = tx.into_moveos_transaction(self.root.clone());
         let tx_size = moveos_tx.ctx.tx_size;
         let tx_result = self.validate_authenticator(&moveos_tx.ctx, authenticator);
@@ -308,6 +372,16 @@ impl ExecutorActor {
                         .ctx
                         .add(tx_validate_result)
                         .expect("add tx_validate_result failed");
+                    // The sponsor is validated by the sponsor validator, it pays the gas
+                    if let Some(sponsor) = sponsor {
+                        moveos_tx
+                            .ctx
+                            .add(GasPaymentAccount {
+                                account: sponsor.into(),
+                                pay_gas_by_module_account: false,
+                            })
+                            .expect("add gas payment account failed");
+                    }
 
                     let verify_result = self.moveos.verify(moveos_tx);
                     match verify_result {
@@ -392,15 +466,29 @@ impl ExecutorActor {
         Ok(vm_result)
     }
 
+    /// Convert the transaction for the dry run without validating the authenticator. If the
+    /// `authenticator` is a sponsor authenticator, the gas is paid by the sponsor.
     pub fn convert_to_verified_tx_for_dry_run(
         &self,
         tx_data: RoochTransactionData,
+        authenticator: Option<Authenticator>,
+        root: Option<ObjectMeta>,
     ) -> Result<VerifiedMoveOSTransaction> {
-        let root = self.root.clone();
+        let root = root.unwrap_or_else(|| self.root.clone());
 
         // The dry run supports unsigned transactions, but when calculating the transaction size,
         // the length of the signature part needs to be included.
-        let tx_size = tx_data.tx_size() + AUTH_PAYLOAD_SIZE;
+        let tx_size = match &authenticator {
+            Some(authenticator) => {
+                RoochTransaction::new(tx_data.clone(), authenticator.clone()).tx_size()
+            }
+            None => tx_data.tx_size() + AUTH_PAYLOAD_SIZE,
+        };
+        let sponsor = match &authenticator {
+            Some(authenticator) => SponsorAuthenticator::try_from_authenticator(authenticator)?
+                .map(|sponsor_authenticator| sponsor_authenticator.sponsor()),
+            None => None,
+        };
 
         let mut tx_ctx = TxContext::new(
             tx_data.sender.into(),
@@ -428,6 +516,13 @@ impl ExecutorActor {
         };
 
         tx_ctx.add(dummy_result)?;
+        // The same as the validation of the sponsored transaction, the sponsor pays the gas
+        if let Some(sponsor) = sponsor {
+            tx_ctx.add(GasPaymentAccount {
+                account: sponsor.into(),
+                pay_gas_by_module_account: false,
+            })?;
+        }
 
         let verified_action = match tx_data.action {
             MoveAction::Script(script_call) => VerifiedMoveAction::Script { call: script_call },
@@ -575,7 +670,7 @@ impl Handler<ConvertL2TransactionData> for ExecutorActor {
         msg: ConvertL2TransactionData,
         _ctx: &mut ActorContext,
     ) -> Result<VerifiedMoveOSTransaction> {
-        self.convert_to_verified_tx_for_dry_run(msg.tx_data)
+        self.convert_to_verified_tx_for_dry_run(msg.tx_data, msg.authenticator, msg.root)
     }
 }
 
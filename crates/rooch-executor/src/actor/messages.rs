@@ -10,14 +10,16 @@ use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
 use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState, StateChangeSetExt};
+use moveos_types::state_proof::StateProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::TransactionExecutionInfo;
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::transaction::VerifiedMoveOSTransaction;
 use moveos_types::transaction::{FunctionCall, RawTransactionOutput, VMErrorInfo};
 use rooch_types::address::MultiChainAddress;
+use rooch_types::transaction::state_diff::StateDiff;
 use rooch_types::transaction::{
-    L1BlockWithBody, L1Transaction, RoochTransaction, RoochTransactionData,
+    Authenticator, L1BlockWithBody, L1Transaction, RoochTransaction, RoochTransactionData,
 };
 use serde::{Deserialize, Serialize};
 
@@ -67,6 +69,8 @@ impl Message for ExecuteTransactionMessage {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteViewFunctionMessage {
+    /// Execute against the historical root if `Some`, otherwise the latest root
+    pub root: Option<ObjectMeta>,
     pub call: FunctionCall,
 }
 
@@ -74,6 +78,36 @@ impl Message for ExecuteViewFunctionMessage {
     type Result = Result<AnnotatedFunctionResult, anyhow::Error>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecuteViewFunctionsMessage {
+    pub state_root: Option<H256>,
+    pub calls: Vec<FunctionCall>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExecuteViewFunctionsResult {
+    /// The state root all the calls were executed against
+    pub state_root: H256,
+    /// The result and the gas used of every call, in the same order as the calls
+    pub results: Vec<(AnnotatedFunctionResult, u64)>,
+}
+
+impl Message for ExecuteViewFunctionsMessage {
+    type Result = Result<ExecuteViewFunctionsResult, anyhow::Error>;
+}
+
+/// Resolve the historical root object by the state root or by the tx order, and ensure the
+/// state nodes of the root are not pruned.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolveHistoricalRootMessage {
+    pub state_root: Option<H256>,
+    pub tx_order: Option<u64>,
+}
+
+impl Message for ResolveHistoricalRootMessage {
+    type Result = Result<ObjectMeta, anyhow::Error>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StatesMessage {
     pub state_root: Option<H256>,
@@ -84,6 +118,18 @@ impl Message for StatesMessage {
     type Result = Result<Vec<Option<ObjectState>>>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetStateProofMessage {
+    pub state_root: Option<H256>,
+    pub object_id: ObjectID,
+    pub field_key: FieldKey,
+}
+
+impl Message for GetStateProofMessage {
+    /// The state root the proof is generated against, and the proof
+    type Result = Result<(H256, StateProof)>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResolveMessage {
     pub address: MultiChainAddress,
@@ -225,7 +271,12 @@ impl Message for CheckStateChangeSetsMessage {
 
 #[derive(Debug)]
 pub struct ConvertL2TransactionData {
+    /// Dry run against the historical root if `Some`, otherwise the latest root
+    pub root: Option<ObjectMeta>,
     pub tx_data: RoochTransactionData,
+    /// The authenticator of the signed transaction, the sponsor of a sponsored transaction pays
+    /// the gas
+    pub authenticator: Option<Authenticator>,
 }
 impl Message for ConvertL2TransactionData {
     type Result = Result<VerifiedMoveOSTransaction>;
@@ -243,4 +294,5 @@ impl Message for DryRunTransactionMessage {
 pub struct DryRunTransactionResult {
     pub raw_output: RawTransactionOutput,
     pub vm_error_info: Option<VMErrorInfo>,
+    pub state_diff: StateDiff,
 }
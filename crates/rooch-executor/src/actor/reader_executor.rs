@@ -3,8 +3,10 @@
 
 use super::messages::{
     AnnotatedStatesMessage, CheckStateChangeSetsMessage, ExecuteViewFunctionMessage,
+    ExecuteViewFunctionsMessage, ExecuteViewFunctionsResult,
     GetAnnotatedEventsByEventHandleMessage, GetAnnotatedEventsByEventIDsMessage,
-    GetEventsByEventHandleMessage, GetStateChangeSetsMessage, RefreshStateMessage, StatesMessage,
+    GetEventsByEventHandleMessage, GetStateChangeSetsMessage, GetStateProofMessage,
+    RefreshStateMessage, ResolveHistoricalRootMessage, StatesMessage,
 };
 use crate::actor::messages::{
     GetEventsByEventIDsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
@@ -13,6 +15,7 @@ use crate::actor::messages::{
 use anyhow::Result;
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor, LocalActorRef};
+use move_core_types::vm_status::VMStatus;
 use move_resource_viewer::MoveValueAnnotator;
 use moveos::moveos::MoveOS;
 use moveos::moveos::MoveOSConfig;
@@ -21,18 +24,23 @@ use moveos_store::transaction_store::TransactionStore;
 use moveos_store::MoveOSStore;
 use moveos_types::function_return_value::AnnotatedFunctionResult;
 use moveos_types::function_return_value::AnnotatedFunctionReturnValue;
+use moveos_types::function_return_value::FunctionResult;
+use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event};
-use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::moveos_std::object::{ObjectMeta, GENESIS_STATE_ROOT};
 use moveos_types::state::{AnnotatedState, ObjectState, StateChangeSetExt};
+use moveos_types::state_proof::StateProof;
 use moveos_types::state_resolver::RootObjectResolver;
 use moveos_types::state_resolver::{AnnotatedStateKV, AnnotatedStateReader, StateKV, StateReader};
-use moveos_types::transaction::TransactionExecutionInfo;
+use moveos_types::transaction::{FunctionCall, TransactionExecutionInfo};
 use rooch_genesis::FrameworksGasParameters;
 use rooch_notify::actor::NotifyActor;
 use rooch_notify::event::GasUpgradeEvent;
 use rooch_notify::messages::NotifyActorSubscribeMessage;
 use rooch_store::RoochStore;
+use rooch_types::error::RoochError;
 use rooch_types::framework::{system_post_execute_functions, system_pre_execute_functions};
+use smt::{MissingNodeError, MISSING_NODE_ERROR_PREFIX};
 
 pub struct ReaderExecutorActor {
     root: ObjectMeta,
@@ -94,6 +102,83 @@ impl ReaderExecutorActor {
         self.root = root;
         self.moveos.flush_module_cache(is_upgrade)
     }
+
+    /// The state nodes of the historical roots out of the retained window are deleted by the
+    /// pruner, executing against them fails on a missing node in the middle of the execution.
+    fn ensure_state_root_available(&self, state_root: H256) -> Result<()> {
+        if state_root == *GENESIS_STATE_ROOT {
+            return Ok(());
+        }
+        let root_node = self
+            .moveos_store
+            .get_state_node_store()
+            .multi_get(&[state_root])?
+            .pop()
+            .flatten();
+        if root_node.is_none() {
+            return Err(state_root_not_available(
+                state_root,
+                "has been pruned or does not exist",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Execute the view function against the historical root if `root` is `Some`, returns
+    /// `RoochError::StateRootNotAvailable` if the historical root has been pruned.
+    pub fn execute_view_function(
+        &self,
+        call: FunctionCall,
+        root: Option<ObjectMeta>,
+    ) -> Result<AnnotatedFunctionResult> {
+        let root = root.unwrap_or_else(|| self.root.clone());
+        let state_root = root.state_root();
+        let resolver = RootObjectResolver::new(root.clone(), &self.moveos_store);
+        let function_result = self.moveos().execute_view_function(root, call);
+        if state_root != self.root.state_root() {
+            check_historical_vm_status(state_root, &function_result.vm_status)?;
+        }
+        annotate_function_result(&resolver, function_result)
+    }
+
+    /// Resolve the root by the state root or by the tx order, returns the latest root if neither
+    /// is specified.
+    pub fn resolve_historical_root(
+        &self,
+        state_root: Option<H256>,
+        tx_order: Option<u64>,
+    ) -> Result<ObjectMeta> {
+        let root = match (state_root, tx_order) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("Only one of the state root and the tx order can be specified")
+            }
+            (Some(state_root), None) => ObjectMeta::root_metadata(state_root, 0),
+            (None, Some(tx_order)) => {
+                let tx_hash = self
+                    .rooch_store
+                    .get_transaction_store()
+                    .get_tx_hashes(vec![tx_order])?
+                    .pop()
+                    .flatten()
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("The transaction of tx order {} does not exist", tx_order)
+                    })?;
+                let execution_info = self
+                    .moveos_store
+                    .get_tx_execution_info(tx_hash)?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "The execution info of tx order {} does not exist",
+                            tx_order
+                        )
+                    })?;
+                ObjectMeta::root_metadata(execution_info.state_root, execution_info.size)
+            }
+            (None, None) => return Ok(self.root.clone()),
+        };
+        self.ensure_state_root_available(root.state_root())?;
+        Ok(root)
+    }
 }
 
 #[async_trait]
@@ -113,32 +198,116 @@ impl Handler<ExecuteViewFunctionMessage> for ReaderExecutorActor {
         msg: ExecuteViewFunctionMessage,
         _ctx: &mut ActorContext,
     ) -> Result<AnnotatedFunctionResult, anyhow::Error> {
-        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
-        let function_result = self
+        self.execute_view_function(msg.call, msg.root)
+    }
+}
+
+#[async_trait]
+impl Handler<ResolveHistoricalRootMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ResolveHistoricalRootMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<ObjectMeta, anyhow::Error> {
+        self.resolve_historical_root(msg.state_root, msg.tx_order)
+    }
+}
+
+#[async_trait]
+impl Handler<ExecuteViewFunctionsMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ExecuteViewFunctionsMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<ExecuteViewFunctionsResult, anyhow::Error> {
+        let root = self.resolve_historical_root(msg.state_root, None)?;
+        let state_root = root.state_root();
+        let is_historical = state_root != self.root.state_root();
+        let resolver = RootObjectResolver::new(root.clone(), &self.moveos_store);
+        let results = self
             .moveos()
-            .execute_view_function(self.root.clone(), msg.call);
-
-        Ok(AnnotatedFunctionResult {
-            vm_status: function_result.vm_status,
-            return_values: match function_result.return_values {
-                Some(values) => Some(
-                    values
-                        .into_iter()
-                        .map(|v| {
-                            let decoded_value = resolver.view_value(&v.type_tag, &v.value)?;
-                            Ok(AnnotatedFunctionReturnValue {
-                                value: v,
-                                decoded_value,
-                            })
-                        })
-                        .collect::<Result<Vec<AnnotatedFunctionReturnValue>, anyhow::Error>>()?,
-                ),
-                None => None,
-            },
+            .execute_view_functions(root, msg.calls)
+            .into_iter()
+            .map(|(function_result, gas_used)| {
+                if is_historical {
+                    check_historical_vm_status(state_root, &function_result.vm_status)?;
+                }
+                annotate_function_result(&resolver, function_result)
+                    .map(|result| (result, gas_used))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(ExecuteViewFunctionsResult {
+            state_root,
+            results,
         })
     }
 }
 
+fn state_root_not_available(state_root: H256, reason: &str) -> anyhow::Error {
+    RoochError::StateRootNotAvailable(format!("the state root {:?} {}", state_root, reason)).into()
+}
+
+/// The root node of a historical root may be retained while some of its nodes are pruned, the
+/// reads of the pruned nodes fail in the middle of the execution.
+pub(crate) fn is_missing_node_error(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|e| e.is::<MissingNodeError>() || e.to_string().contains(MISSING_NODE_ERROR_PREFIX))
+}
+
+/// Map the missing node error of a historical root to `RoochError::StateRootNotAvailable`
+pub(crate) fn map_missing_node_error(state_root: H256, err: anyhow::Error) -> anyhow::Error {
+    if is_missing_node_error(&err) {
+        state_root_not_available(state_root, "has been pruned")
+    } else {
+        err
+    }
+}
+
+/// The Move VM turns the resolver errors into an extension error and keeps only the message
+pub(crate) fn check_historical_vm_error_message(state_root: H256, message: &str) -> Result<()> {
+    if message.contains(MISSING_NODE_ERROR_PREFIX) {
+        return Err(state_root_not_available(
+            state_root,
+            "has been partially pruned",
+        ));
+    }
+    Ok(())
+}
+
+fn check_historical_vm_status(state_root: H256, vm_status: &VMStatus) -> Result<()> {
+    match vm_status {
+        VMStatus::Error {
+            message: Some(message),
+            ..
+        } => check_historical_vm_error_message(state_root, message),
+        _ => Ok(()),
+    }
+}
+
+fn annotate_function_result(
+    resolver: &RootObjectResolver<MoveOSStore>,
+    function_result: FunctionResult,
+) -> Result<AnnotatedFunctionResult> {
+    Ok(AnnotatedFunctionResult {
+        vm_status: function_result.vm_status,
+        return_values: match function_result.return_values {
+            Some(values) => Some(
+                values
+                    .into_iter()
+                    .map(|v| {
+                        let decoded_value = resolver.view_value(&v.type_tag, &v.value)?;
+                        Ok(AnnotatedFunctionReturnValue {
+                            value: v,
+                            decoded_value,
+                        })
+                    })
+                    .collect::<Result<Vec<AnnotatedFunctionReturnValue>, anyhow::Error>>()?,
+            ),
+            None => None,
+        },
+    })
+}
+
 #[async_trait]
 impl Handler<StatesMessage> for ReaderExecutorActor {
     async fn handle(
@@ -152,7 +321,29 @@ impl Handler<StatesMessage> for ReaderExecutorActor {
         } else {
             RootObjectResolver::new(self.root.clone(), &self.moveos_store)
         };
-        resolver.get_states(msg.access_path)
+        resolver
+            .get_states(msg.access_path)
+            .map_err(|err| match msg.state_root {
+                Some(state_root) => map_missing_node_error(state_root, err),
+                None => err,
+            })
+    }
+}
+
+#[async_trait]
+impl Handler<GetStateProofMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: GetStateProofMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<(H256, StateProof), anyhow::Error> {
+        let state_root = msg.state_root.unwrap_or(self.root.state_root());
+        let proof = self.moveos_store.get_state_store().get_state_proof(
+            state_root,
+            &msg.object_id,
+            msg.field_key,
+        )?;
+        Ok((state_root, proof))
     }
 }
 
@@ -169,7 +360,12 @@ impl Handler<AnnotatedStatesMessage> for ReaderExecutorActor {
         } else {
             RootObjectResolver::new(self.root.clone(), &self.moveos_store)
         };
-        resolver.get_annotated_states(msg.access_path)
+        resolver
+            .get_annotated_states(msg.access_path)
+            .map_err(|err| match msg.state_root {
+                Some(state_root) => map_missing_node_error(state_root, err),
+                None => err,
+            })
     }
 }
 
@@ -186,7 +382,12 @@ impl Handler<ListStatesMessage> for ReaderExecutorActor {
         } else {
             RootObjectResolver::new(self.root.clone(), &self.moveos_store)
         };
-        resolver.list_states(msg.access_path, msg.cursor, msg.limit)
+        resolver
+            .list_states(msg.access_path, msg.cursor, msg.limit)
+            .map_err(|err| match msg.state_root {
+                Some(state_root) => map_missing_node_error(state_root, err),
+                None => err,
+            })
     }
 }
 
@@ -203,7 +404,12 @@ impl Handler<ListAnnotatedStatesMessage> for ReaderExecutorActor {
         } else {
             RootObjectResolver::new(self.root.clone(), &self.moveos_store)
         };
-        resolver.list_annotated_states(msg.access_path, msg.cursor, msg.limit)
+        resolver
+            .list_annotated_states(msg.access_path, msg.cursor, msg.limit)
+            .map_err(|err| match msg.state_root {
+                Some(state_root) => map_missing_node_error(state_root, err),
+                None => err,
+            })
     }
 }
 
@@ -3,10 +3,11 @@
 
 use crate::actor::messages::{
     CheckStateChangeSetsMessage, ConvertL2TransactionData, DryRunTransactionResult,
-    GetAnnotatedEventsByEventIDsMessage, GetEventsByEventHandleMessage, GetEventsByEventIDsMessage,
-    GetStateChangeSetsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
-    ListStatesMessage, RefreshStateMessage, SaveStateChangeSetMessage, ValidateL1BlockMessage,
-    ValidateL1TxMessage,
+    ExecuteViewFunctionsMessage, ExecuteViewFunctionsResult, GetAnnotatedEventsByEventIDsMessage,
+    GetEventsByEventHandleMessage, GetEventsByEventIDsMessage, GetStateChangeSetsMessage,
+    GetStateProofMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
+    ListStatesMessage, RefreshStateMessage, ResolveHistoricalRootMessage,
+    SaveStateChangeSetMessage, ValidateL1BlockMessage, ValidateL1TxMessage,
 };
 use crate::actor::reader_executor::ReaderExecutorActor;
 use crate::actor::{
@@ -27,6 +28,7 @@ use moveos_types::moveos_std::event::{Event, EventID};
 use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::{FieldKey, StateChangeSetExt};
+use moveos_types::state_proof::StateProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::FunctionCall;
 use moveos_types::transaction::TransactionExecutionInfo;
@@ -39,7 +41,7 @@ use moveos_types::{
 use rooch_types::bitcoin::network::BitcoinNetwork;
 use rooch_types::framework::chain_id::ChainID;
 use rooch_types::transaction::{
-    L1BlockWithBody, L1Transaction, RoochTransaction, RoochTransactionData,
+    Authenticator, L1BlockWithBody, L1Transaction, RoochTransaction, RoochTransactionData,
 };
 use tokio::runtime::Handle;
 
@@ -84,12 +86,21 @@ impl ExecutorProxy {
             .await?
     }
 
+    /// Convert the transaction for the dry run, against the historical root if `root` is `Some`.
+    /// The `authenticator` of a signed transaction is not validated, but the sponsor of a
+    /// sponsored transaction pays the gas.
     pub async fn convert_to_verified_tx(
         &self,
         tx_data: RoochTransactionData,
+        authenticator: Option<Authenticator>,
+        root: Option<ObjectMeta>,
     ) -> Result<VerifiedMoveOSTransaction> {
         self.actor
-            .send(ConvertL2TransactionData { tx_data })
+            .send(ConvertL2TransactionData {
+                root,
+                tx_data,
+                authenticator,
+            })
             .await?
     }
 
@@ -117,12 +128,43 @@ impl ExecutorProxy {
         Ok(result)
     }
 
+    /// Execute the view function, against the historical root if `root` is `Some`
     pub async fn execute_view_function(
         &self,
         call: FunctionCall,
+        root: Option<ObjectMeta>,
     ) -> Result<AnnotatedFunctionResult> {
         self.reader_actor
-            .send(ExecuteViewFunctionMessage { call })
+            .send(ExecuteViewFunctionMessage { root, call })
+            .await?
+    }
+
+    /// Resolve the historical root by the state root or by the tx order, returns None for the
+    /// latest root if neither is specified.
+    pub async fn resolve_historical_root(
+        &self,
+        state_root: Option<H256>,
+        tx_order: Option<u64>,
+    ) -> Result<Option<ObjectMeta>> {
+        if state_root.is_none() && tx_order.is_none() {
+            return Ok(None);
+        }
+        self.reader_actor
+            .send(ResolveHistoricalRootMessage {
+                state_root,
+                tx_order,
+            })
+            .await?
+            .map(Some)
+    }
+
+    pub async fn execute_view_functions(
+        &self,
+        calls: Vec<FunctionCall>,
+        state_root: Option<H256>,
+    ) -> Result<ExecuteViewFunctionsResult> {
+        self.reader_actor
+            .send(ExecuteViewFunctionsMessage { state_root, calls })
             .await?
     }
 
@@ -139,6 +181,21 @@ impl ExecutorProxy {
             .await?
     }
 
+    pub async fn get_state_proof(
+        &self,
+        object_id: ObjectID,
+        field_key: FieldKey,
+        state_root: Option<H256>,
+    ) -> Result<(H256, StateProof)> {
+        self.reader_actor
+            .send(GetStateProofMessage {
+                state_root,
+                object_id,
+                field_key,
+            })
+            .await?
+    }
+
     pub async fn get_annotated_states(
         &self,
         access_path: AccessPath,
@@ -340,7 +397,7 @@ impl MoveFunctionCaller for ExecutorProxy {
         let executor = self.clone();
         let function_result = tokio::task::block_in_place(|| {
             Handle::current()
-                .block_on(async move { executor.execute_view_function(function_call).await })
+                .block_on(async move { executor.execute_view_function(function_call, None).await })
         })?;
         function_result.try_into()
     }
@@ -0,0 +1,193 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::actor::executor::ExecutorActor;
+use anyhow::Result;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_store::transaction_store::TransactionStore;
+use moveos_store::MoveOSStore;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::state::StateChangeSet;
+use moveos_types::transaction::{TransactionExecutionInfo, VerifiedMoveOSTransaction};
+use prometheus::Registry;
+use rooch_store::RoochStore;
+use rooch_types::block::Block;
+use rooch_types::transaction::{L1BlockWithBody, LedgerTransaction, LedgerTxData};
+use serde::{Deserialize, Serialize};
+
+/// The part of the execution result which is determined by the state transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub state_root: H256,
+    pub size: u64,
+    pub gas_used: u64,
+    pub status: KeptVMStatus,
+}
+
+impl From<&TransactionExecutionInfo> for StateTransition {
+    fn from(info: &TransactionExecutionInfo) -> Self {
+        Self {
+            state_root: info.state_root,
+            size: info.size,
+            gas_used: info.gas_used,
+            status: info.status.clone(),
+        }
+    }
+}
+
+/// The result of re-executing a transaction.
+#[derive(Debug, Clone)]
+pub struct TxVerifyResult {
+    pub tx_order: u64,
+    pub tx_hash: H256,
+    /// The execution info recorded in the store, None if the transaction has not been executed.
+    pub expected: Option<StateTransition>,
+    pub actual: StateTransition,
+    pub state_change_set: StateChangeSet,
+}
+
+impl TxVerifyResult {
+    pub fn is_match(&self) -> bool {
+        self.expected.as_ref() == Some(&self.actual)
+    }
+}
+
+/// The result of re-executing all the transactions of a proposed block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockVerifyResult {
+    pub block_number: u128,
+    /// The state root committed by the proposer in the block.
+    pub committed_state_root: H256,
+    pub actual_state_root: H256,
+}
+
+impl BlockVerifyResult {
+    pub fn is_match(&self) -> bool {
+        self.committed_state_root == self.actual_state_root
+    }
+}
+
+/// StateTransitionVerifier re-executes sequenced transactions from a historical state root,
+/// compares the state transition of every transaction with the recorded execution info,
+/// and the state root after every block with the state root committed by the proposer.
+///
+/// Only the state nodes are written to the store (they are content addressed),
+/// so the execution records of the store are never changed by the verification.
+pub struct StateTransitionVerifier {
+    executor: ExecutorActor,
+    root: ObjectMeta,
+    moveos_store: MoveOSStore,
+    rooch_store: RoochStore,
+}
+
+impl StateTransitionVerifier {
+    pub fn new(
+        root: ObjectMeta,
+        moveos_store: MoveOSStore,
+        rooch_store: RoochStore,
+    ) -> Result<Self> {
+        let executor = Self::build_executor(root.clone(), &moveos_store, &rooch_store)?;
+        Ok(Self {
+            executor,
+            root,
+            moveos_store,
+            rooch_store,
+        })
+    }
+
+    fn build_executor(
+        root: ObjectMeta,
+        moveos_store: &MoveOSStore,
+        rooch_store: &RoochStore,
+    ) -> Result<ExecutorActor> {
+        // the verifier metrics are not exported, use a standalone registry
+        ExecutorActor::new(
+            root,
+            moveos_store.clone(),
+            rooch_store.clone(),
+            &Registry::new(),
+            None,
+        )
+    }
+
+    /// The state root the next transaction is executed on.
+    pub fn root(&self) -> &ObjectMeta {
+        &self.root
+    }
+
+    /// Re-execute the transaction, the body of the L1 block is required for L1Block transaction.
+    pub fn verify_tx(
+        &mut self,
+        mut ledger_tx: LedgerTransaction,
+        l1_block_with_body: Option<L1BlockWithBody>,
+        bypass_l1_executed_check: bool,
+    ) -> Result<TxVerifyResult> {
+        let tx_order = ledger_tx.sequence_info.tx_order;
+        let tx_hash = ledger_tx.tx_hash();
+        let moveos_tx =
+            self.validate_ledger_tx(&ledger_tx, l1_block_with_body, bypass_l1_executed_check)?;
+
+        let raw_output = self.executor.execute_state_only(moveos_tx)?;
+        let actual = StateTransition {
+            state_root: raw_output.changeset.state_root,
+            size: raw_output.changeset.global_size,
+            gas_used: raw_output.gas_used,
+            status: raw_output.status.clone(),
+        };
+        self.root = ObjectMeta::root_metadata(actual.state_root, actual.size);
+        if raw_output.is_gas_upgrade {
+            // reload the gas parameters from the upgraded state
+            self.executor =
+                Self::build_executor(self.root.clone(), &self.moveos_store, &self.rooch_store)?;
+        }
+
+        let expected = self
+            .moveos_store
+            .get_tx_execution_info(tx_hash)?
+            .as_ref()
+            .map(StateTransition::from);
+        Ok(TxVerifyResult {
+            tx_order,
+            tx_hash,
+            expected,
+            actual,
+            state_change_set: raw_output.changeset,
+        })
+    }
+
+    /// Compare the current state root with the state root committed in the block,
+    /// it should be called after all the transactions of the block are verified.
+    pub fn verify_block(&self, block: &Block) -> BlockVerifyResult {
+        BlockVerifyResult {
+            block_number: block.block_number,
+            committed_state_root: block.state_root,
+            actual_state_root: self.root.state_root(),
+        }
+    }
+
+    fn validate_ledger_tx(
+        &self,
+        ledger_tx: &LedgerTransaction,
+        l1_block_with_body: Option<L1BlockWithBody>,
+        bypass_l1_executed_check: bool,
+    ) -> Result<VerifiedMoveOSTransaction> {
+        let mut moveos_tx = match &ledger_tx.data {
+            LedgerTxData::L1Block(_) => {
+                let l1_block_with_body = l1_block_with_body.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "L1 block body is required, tx_order: {}",
+                        ledger_tx.sequence_info.tx_order
+                    )
+                })?;
+                self.executor.validate_l1_block(l1_block_with_body)?
+            }
+            LedgerTxData::L1Tx(l1_tx) => self
+                .executor
+                .validate_l1_tx(l1_tx.clone(), bypass_l1_executed_check)?,
+            LedgerTxData::L2Tx(l2_tx) => self.executor.validate_l2_tx(l2_tx.clone())?,
+        };
+        moveos_tx.ctx.add(ledger_tx.sequence_info.clone())?;
+        Ok(moveos_tx)
+    }
+}
@@ -0,0 +1,614 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use bitcoin::hashes::Hash;
+use bitcoin::{opcodes, script, Amount, BlockHash, Network, ScriptBuf, TxOut};
+use bitcoin_client::actor::mock::MockBitcoinClientActor;
+use bitcoin_client::mock::MockBitcoinNode;
+use bitcoin_client::proxy::BitcoinClientProxy;
+use coerce::actor::{system::ActorSystem, IntoActor, LocalActorRef};
+use move_core_types::ident_str;
+use move_core_types::language_storage::StructTag;
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::move_std::option::MoveOption;
+use moveos_types::move_std::string::MoveString;
+use moveos_types::moveos_std::object::{self, ObjectID};
+use moveos_types::state::MoveType;
+use moveos_types::state_resolver::StateResolver;
+use rooch_config::store_config::DEFAULT_DB_INDEXER_SUBDIR;
+use rooch_config::BitcoinRelayerConfig;
+use rooch_executor::actor::executor::ExecutorActor;
+use rooch_executor::actor::reader_executor::ReaderExecutorActor;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_indexer::indexer_reader::IndexerReader;
+use rooch_indexer::store::traits::IndexerStoreTrait;
+use rooch_indexer::IndexerStore;
+use rooch_key::keystore::account_keystore::AccountKeystore;
+use rooch_key::keystore::memory_keystore::InMemKeystore;
+use rooch_relayer::actor::bitcoin_relayer::BitcoinRelayer;
+use rooch_relayer::actor::messages::{GetReadyL1BlockMessage, SyncTick};
+use rooch_types::addresses::BITCOIN_MOVE_ADDRESS;
+use rooch_types::bitcoin::ord::{Inscription, InscriptionID, InscriptionRecord};
+use rooch_types::bitcoin::pending_block::{self, BlockReorgEvent, PendingBlockModule};
+use rooch_types::bitcoin::types::Header;
+use rooch_types::bitcoin::utxo::{self, UTXO};
+use rooch_types::bitcoin::BitcoinModule;
+use rooch_types::indexer::state::{
+    handle_object_change, IndexerObjectStateChangeSet, IndexerObjectStatesIndexGenerator,
+    ObjectStateFilter, ObjectStateType,
+};
+use rooch_types::into_address::IntoAddress;
+use rooch_types::multichain_id::RoochMultiChainID;
+use rooch_types::transaction::rooch::RoochTransactionData;
+use rooch_types::transaction::{L1Block, L1BlockWithBody, L1Transaction};
+
+fn mock_l1_block(node: &MockBitcoinNode, block_hash: BlockHash) -> L1BlockWithBody {
+    let block = node.block(&block_hash).unwrap();
+    let block_height = node.block_height(&block_hash).unwrap();
+    L1BlockWithBody {
+        block: L1Block {
+            chain_id: RoochMultiChainID::Bitcoin.multichain_id(),
+            block_height,
+            block_hash: block_hash.to_byte_array().to_vec(),
+        },
+        block_body: rooch_types::bitcoin::types::Block::from(block).encode(),
+    }
+}
+
+fn execute_mock_block(
+    binding_test: &mut binding_test::RustBindingTest,
+    node: &MockBitcoinNode,
+    block_hash: BlockHash,
+) {
+    binding_test
+        .execute_l1_block(mock_l1_block(node, block_hash))
+        .unwrap();
+}
+
+// Add the block with the tx bodies as a pending block and execute its txs via `execute_l1_tx`,
+// the same as the relayer does for the ready pending txs
+fn execute_mock_block_with_txs(
+    binding_test: &mut binding_test::RustBindingTest,
+    node: &MockBitcoinNode,
+    block_hash: BlockHash,
+) {
+    let block = node.block(&block_hash).unwrap();
+    let block_height = node.block_height(&block_hash).unwrap();
+    let action = PendingBlockModule::create_add_pending_block_for_local_action(
+        block_height,
+        &rooch_types::bitcoin::types::Block::from(block),
+    );
+    let sender = binding_test.sequencer;
+    let sequence_number = binding_test.get_account_sequence_number(sender).unwrap();
+    let tx = RoochTransactionData::new_for_test(sender.into(), sequence_number, action)
+        .sign(binding_test.sequencer_kp());
+    binding_test.execute(tx).unwrap();
+
+    let pending_txs = binding_test
+        .as_module_binding::<PendingBlockModule>()
+        .get_ready_pending_txs()
+        .unwrap()
+        .expect("the pending block should be ready");
+    assert_eq!(pending_txs.block_hash, block_hash.into_address());
+    let mut txids = pending_txs.txs;
+    // The coinbase tx finishes the pending block, so it is executed after the other txs
+    let coinbase_txid = txids.remove(0);
+    txids.push(coinbase_txid);
+    for txid in txids {
+        binding_test
+            .execute_l1_tx(L1Transaction::new(
+                RoochMultiChainID::Bitcoin.multichain_id(),
+                block_hash.into_address().to_vec(),
+                txid.to_vec(),
+            ))
+            .unwrap();
+    }
+}
+
+// Execute the block and index the object changes, the same as the indexer actor does
+fn execute_and_index_mock_block(
+    binding_test: &mut binding_test::RustBindingTest,
+    node: &MockBitcoinNode,
+    block_hash: BlockHash,
+    indexer_store: &IndexerStore,
+    tx_order: u64,
+) {
+    let verified_tx = binding_test
+        .executor()
+        .validate_l1_block(mock_l1_block(node, block_hash))
+        .unwrap();
+    let result = binding_test.execute_verified_tx(verified_tx).unwrap();
+
+    let mut state_index_generator = IndexerObjectStatesIndexGenerator::default();
+    let mut indexer_object_state_change_set = IndexerObjectStateChangeSet::default();
+    for (_field_key, object_change) in result.output.changeset.changes {
+        handle_object_change(
+            &mut state_index_generator,
+            tx_order,
+            &mut indexer_object_state_change_set,
+            object_change,
+        )
+        .unwrap();
+    }
+    indexer_store
+        .apply_object_states(indexer_object_state_change_set)
+        .unwrap();
+}
+
+fn pending_block_struct_tag() -> StructTag {
+    StructTag {
+        address: BITCOIN_MOVE_ADDRESS,
+        module: pending_block::MODULE_NAME.to_owned(),
+        name: ident_str!("PendingBlock").to_owned(),
+        type_params: vec![],
+    }
+}
+
+fn pending_block_id(block_hash: BlockHash) -> ObjectID {
+    object::custom_object_id(&block_hash.into_address(), &pending_block_struct_tag())
+}
+
+fn indexed_pending_block_ids(indexer_reader: &IndexerReader) -> Vec<ObjectID> {
+    let mut object_ids = indexer_reader
+        .query_object_ids_with_filter(
+            ObjectStateFilter::ObjectType(pending_block_struct_tag()),
+            None,
+            10,
+            false,
+            ObjectStateType::ObjectState,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|(object_id, _state_id)| object_id)
+        .collect::<Vec<_>>();
+    object_ids.sort();
+    object_ids
+}
+
+fn update_reorg_block_count(binding_test: &mut binding_test::RustBindingTest, count: u64) {
+    let keystore = InMemKeystore::new_insecure_for_tests(1);
+    let sender = keystore.addresses()[0];
+    let action = PendingBlockModule::create_update_reorg_block_count_for_local_action(count);
+    let tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+    let tx = keystore.sign_transaction(&sender, tx_data, None).unwrap();
+    binding_test.execute(tx).unwrap();
+}
+
+async fn spawn_mock_bitcoin_client(
+    node: &MockBitcoinNode,
+    actor_system: &ActorSystem,
+    name: &str,
+) -> BitcoinClientProxy {
+    let actor_ref = MockBitcoinClientActor::new(node.clone())
+        .into_actor(Some(name), actor_system)
+        .await
+        .unwrap();
+    BitcoinClientProxy::mock(actor_ref.into())
+}
+
+// The executor actors share the store of the binding test, the reader is refreshed after every
+// block executed by the binding test.
+async fn spawn_executor_proxy(
+    binding_test: &binding_test::RustBindingTest,
+    actor_system: &ActorSystem,
+) -> ExecutorProxy {
+    let rooch_db = binding_test.rooch_db();
+    // The executor metrics are already registered in the registry of the binding test
+    let registry_service = metrics::RegistryService::default();
+    let executor = ExecutorActor::new(
+        binding_test.root().clone(),
+        rooch_db.moveos_store.clone(),
+        rooch_db.rooch_store.clone(),
+        &registry_service.default_registry(),
+        None,
+    )
+    .unwrap()
+    .into_actor(Some("executor_for_relayer_test"), actor_system)
+    .await
+    .unwrap();
+    let reader_executor = ReaderExecutorActor::new(
+        binding_test.root().clone(),
+        rooch_db.moveos_store.clone(),
+        rooch_db.rooch_store.clone(),
+        None,
+    )
+    .unwrap()
+    .into_actor(Some("reader_executor_for_relayer_test"), actor_system)
+    .await
+    .unwrap();
+    ExecutorProxy::new(executor.into(), reader_executor.into())
+}
+
+// Relay the blocks until the relayer catches up with the mock node, returns the relayed blocks
+async fn relay_blocks(
+    binding_test: &mut binding_test::RustBindingTest,
+    relayer: &LocalActorRef<BitcoinRelayer>,
+    executor: &ExecutorProxy,
+) -> Vec<(u64, BlockHash)> {
+    let mut relayed_blocks = vec![];
+    loop {
+        relayer.send(SyncTick {}).await.unwrap();
+        let Some(l1_block) = relayer
+            .send(GetReadyL1BlockMessage {})
+            .await
+            .unwrap()
+            .unwrap()
+        else {
+            break;
+        };
+        relayed_blocks.push((
+            l1_block.block.block_height,
+            BlockHash::from_slice(&l1_block.block.block_hash).unwrap(),
+        ));
+        binding_test.execute_l1_block(l1_block).unwrap();
+        executor
+            .refresh_state(binding_test.root().clone(), false)
+            .await
+            .unwrap();
+    }
+    relayed_blocks
+}
+
+#[tokio::test]
+async fn test_mock_bitcoin_client() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let node = MockBitcoinNode::new(Network::Regtest);
+    let actor_system = ActorSystem::global_system();
+    let proxy =
+        spawn_mock_bitcoin_client(&node, &actor_system, "mock_bitcoin_client_for_test").await;
+
+    let script_pubkey = ScriptBuf::from_bytes(vec![0x51]);
+    let (builder, utxo) = node
+        .block_builder()
+        .add_utxo(script_pubkey.clone(), Amount::from_sat(10_000));
+    let block1 = builder.mine().unwrap().block_hash();
+    let main_chain = node.generate_blocks(2).unwrap();
+    assert_eq!(proxy.get_best_block_hash().await.unwrap(), main_chain[1]);
+    assert_eq!(proxy.get_block_hash(1).await.unwrap(), block1);
+
+    let header_info = proxy.get_block_header_info(block1).await.unwrap();
+    assert_eq!(header_info.height, 1);
+    assert_eq!(header_info.confirmations, 3);
+    assert_eq!(header_info.next_block_hash, Some(main_chain[0]));
+    assert_eq!(header_info.previous_block_hash, Some(node.genesis_hash()));
+
+    let tx_out = proxy
+        .get_tx_out(utxo.txid, utxo.vout)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tx_out.value, Amount::from_sat(10_000));
+    assert_eq!(tx_out.confirmations, 3);
+
+    // Spend the utxo via the mempool
+    let spend_tx = MockBitcoinNode::new_transaction(
+        vec![utxo],
+        vec![TxOut {
+            value: Amount::from_sat(9_000),
+            script_pubkey,
+        }],
+    );
+    let txid = proxy
+        .broadcast_transaction(
+            bitcoin::consensus::encode::serialize_hex(&spend_tx),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(node.mempool().len(), 1);
+    node.generate_blocks(1).unwrap();
+    assert!(node.mempool().is_empty());
+    assert!(proxy
+        .get_tx_out(utxo.txid, utxo.vout)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(proxy.get_raw_transaction(txid).await.unwrap(), spend_tx);
+
+    // Fork from block 1, the longer branch becomes the active chain
+    let old_tip = node.best_block_hash();
+    let fork = node.fork_at(1, 4).unwrap();
+    assert_eq!(proxy.get_best_block_hash().await.unwrap(), fork[3]);
+    assert_eq!(proxy.get_block_hash(2).await.unwrap(), fork[0]);
+    let stale_header_info = proxy.get_block_header_info(main_chain[0]).await.unwrap();
+    assert_eq!(stale_header_info.confirmations, -1);
+    assert_eq!(stale_header_info.next_block_hash, None);
+    // The spend tx is reorged out
+    assert!(proxy
+        .get_tx_out(utxo.txid, utxo.vout)
+        .await
+        .unwrap()
+        .is_some());
+
+    let tips = proxy.get_chain_tips().await.unwrap();
+    assert_eq!(tips.len(), 2);
+    assert_eq!(tips[0].hash, fork[3]);
+    assert_eq!(tips[0].branch_len, 0);
+    assert_eq!(tips[1].hash, old_tip);
+    assert_eq!(tips[1].branch_len, 3);
+}
+
+#[tokio::test]
+async fn test_pending_block_reorg_with_mock_node() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let node = MockBitcoinNode::new(Network::Regtest);
+    let bitcoin_module = binding_test.as_module_binding::<BitcoinModule>();
+    assert_eq!(
+        bitcoin_module.get_genesis_block().unwrap().block_hash,
+        node.genesis_hash().into_address()
+    );
+
+    update_reorg_block_count(&mut binding_test, 2);
+
+    let main_chain = node.generate_blocks(3).unwrap();
+    for block_hash in main_chain.iter() {
+        execute_mock_block(&mut binding_test, &node, *block_hash);
+    }
+
+    // Fork from block 1, block 2 and 3 of the main chain are still pending and reorged out
+    let fork = node.fork_at(1, 3).unwrap();
+    for block_hash in fork.iter() {
+        execute_mock_block(&mut binding_test, &node, *block_hash);
+    }
+
+    let best_block = binding_test
+        .as_module_binding::<PendingBlockModule>()
+        .get_best_block()
+        .unwrap()
+        .unwrap();
+    assert_eq!(best_block.block_height, 4);
+    assert_eq!(best_block.block_hash, fork[2].into_address());
+
+    let reorg_events = binding_test
+        .events()
+        .iter()
+        .filter(|event| event.event_type.name.as_str() == "ReorgEvent")
+        .count();
+    assert_eq!(reorg_events, 2);
+
+    let block_reorg_events = binding_test
+        .events()
+        .iter()
+        .filter(|event| event.is::<BlockReorgEvent>())
+        .map(|event| event.decode_event::<BlockReorgEvent>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(block_reorg_events.len(), 1);
+    let block_reorg_event = &block_reorg_events[0];
+    assert_eq!(block_reorg_event.block_height, 2);
+    assert_eq!(block_reorg_event.block_hash, fork[0].into_address());
+    let orphaned_blocks = block_reorg_event
+        .orphaned_blocks
+        .iter()
+        .map(|block| (block.block_height, block.block_hash))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        orphaned_blocks,
+        vec![
+            (2, main_chain[1].into_address()),
+            (3, main_chain[2].into_address())
+        ]
+    );
+
+    let bitcoin_module = binding_test.as_module_binding::<BitcoinModule>();
+    let block2: Header = node.block(&fork[0]).unwrap().header.into();
+    assert_eq!(
+        bitcoin_module.get_block_by_height(2).unwrap().unwrap(),
+        block2
+    );
+    assert!(bitcoin_module.get_block(main_chain[1]).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_indexer_removes_orphaned_pending_blocks_with_mock_node() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let node = MockBitcoinNode::new(Network::Regtest);
+    let registry_service = metrics::RegistryService::default();
+    let tmpdir = moveos_config::temp_dir();
+    let indexer_db = tmpdir.path().join(DEFAULT_DB_INDEXER_SUBDIR);
+    let indexer_store =
+        IndexerStore::new(indexer_db.clone(), &registry_service.default_registry()).unwrap();
+    let indexer_reader =
+        IndexerReader::new(indexer_db, &registry_service.default_registry()).unwrap();
+
+    update_reorg_block_count(&mut binding_test, 2);
+
+    let mut tx_order = 0;
+    let main_chain = node.generate_blocks(3).unwrap();
+    for block_hash in main_chain.iter() {
+        tx_order += 1;
+        execute_and_index_mock_block(
+            &mut binding_test,
+            &node,
+            *block_hash,
+            &indexer_store,
+            tx_order,
+        );
+    }
+    // Block 1 is ready and removed from the pending blocks
+    let mut expected_ids = vec![
+        pending_block_id(main_chain[1]),
+        pending_block_id(main_chain[2]),
+    ];
+    expected_ids.sort();
+    assert_eq!(indexed_pending_block_ids(&indexer_reader), expected_ids);
+
+    // Fork from block 1, the rows of the orphaned block 2 and 3 are removed
+    let fork = node.fork_at(1, 3).unwrap();
+    for block_hash in fork.iter() {
+        tx_order += 1;
+        execute_and_index_mock_block(
+            &mut binding_test,
+            &node,
+            *block_hash,
+            &indexer_store,
+            tx_order,
+        );
+    }
+    let mut expected_ids = vec![pending_block_id(fork[1]), pending_block_id(fork[2])];
+    expected_ids.sort();
+    assert_eq!(indexed_pending_block_ids(&indexer_reader), expected_ids);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bitcoin_relayer_with_mock_node() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    update_reorg_block_count(&mut binding_test, 2);
+
+    let node = MockBitcoinNode::new(Network::Regtest);
+    let actor_system = ActorSystem::global_system();
+    let bitcoin_client =
+        spawn_mock_bitcoin_client(&node, &actor_system, "mock_bitcoin_client_for_relayer_test")
+            .await;
+    let executor = spawn_executor_proxy(&binding_test, &actor_system).await;
+    let block_store_dir = tempfile::tempdir().unwrap();
+    let config = BitcoinRelayerConfig {
+        // The blocks are fetched from the mock node, the RPC config is not used
+        btc_rpc_url: String::new(),
+        btc_rpc_user_name: String::new(),
+        btc_rpc_password: String::new(),
+        btc_end_block_height: None,
+        btc_sync_block_interval: Some(0),
+        btc_reorg_aware_block_store_dir: block_store_dir.path().to_path_buf(),
+        btc_reorg_aware_height: 3,
+    };
+    let relayer = BitcoinRelayer::new(config, bitcoin_client, executor.clone())
+        .unwrap()
+        .into_actor(Some("bitcoin_relayer_for_test"), &actor_system)
+        .await
+        .unwrap();
+
+    // The relayer starts from the genesis block
+    let main_chain = node.generate_blocks(3).unwrap();
+    let relayed_blocks = relay_blocks(&mut binding_test, &relayer, &executor).await;
+    assert_eq!(
+        relayed_blocks,
+        vec![
+            (0, node.genesis_hash()),
+            (1, main_chain[0]),
+            (2, main_chain[1]),
+            (3, main_chain[2]),
+        ]
+    );
+    assert!(relay_blocks(&mut binding_test, &relayer, &executor)
+        .await
+        .is_empty());
+
+    // Fork from block 1, the relayer finds the common ancestor and relays the new branch
+    let fork = node.fork_at(1, 3).unwrap();
+    let relayed_blocks = relay_blocks(&mut binding_test, &relayer, &executor).await;
+    assert_eq!(
+        relayed_blocks,
+        vec![(2, fork[0]), (3, fork[1]), (4, fork[2])]
+    );
+    let best_block = binding_test
+        .as_module_binding::<PendingBlockModule>()
+        .get_best_block()
+        .unwrap()
+        .unwrap();
+    assert_eq!(best_block.block_height, 4);
+    assert_eq!(best_block.block_hash, fork[2].into_address());
+
+    // The orphaned blocks are kept in the reorg aware block store
+    for block_hash in &main_chain[1..] {
+        assert!(block_store_dir.path().join(block_hash.to_string()).exists());
+    }
+    assert!(!block_store_dir
+        .path()
+        .join(main_chain[0].to_string())
+        .exists());
+}
+
+#[tokio::test]
+async fn test_inscription_reveal_with_mock_node() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let node = MockBitcoinNode::new(Network::Regtest);
+    let actor_system = ActorSystem::global_system();
+    let bitcoin_client = spawn_mock_bitcoin_client(
+        &node,
+        &actor_system,
+        "mock_bitcoin_client_for_inscription_test",
+    )
+    .await;
+
+    // The mock node does not verify the script path spending of the commit output
+    let script_pubkey = ScriptBuf::from_bytes(vec![0x51]);
+    let (builder, commit_outpoint) = node
+        .block_builder()
+        .add_utxo(script_pubkey.clone(), Amount::from_sat(10_000));
+    let commit_block = builder.mine().unwrap().block_hash();
+
+    let mut inscription = InscriptionRecord::default();
+    inscription.set_content_type("text/plain;charset=utf-8".to_string());
+    inscription.body = b"Hello, Rooch".to_vec();
+    let tapscript = inscription
+        .append_reveal_script_to_builder(
+            script::Builder::new()
+                .push_slice([1u8; 32])
+                .push_opcode(opcodes::all::OP_CHECKSIG),
+        )
+        .into_script();
+    let reveal_tx = MockBitcoinNode::new_reveal_transaction(
+        commit_outpoint,
+        tapscript,
+        vec![TxOut {
+            value: Amount::from_sat(9_000),
+            script_pubkey,
+        }],
+    );
+    let reveal_txid = bitcoin_client
+        .broadcast_transaction(
+            bitcoin::consensus::encode::serialize_hex(&reveal_tx),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let reveal_block = node.generate_blocks(1).unwrap()[0];
+    let block = bitcoin_client.get_block(reveal_block).await.unwrap();
+    assert_eq!(block.txdata.len(), 2);
+    assert_eq!(block.txdata[1].compute_txid(), reveal_txid);
+
+    // The header-only `execute_l1_block` does not process the block txs,
+    // so drive the blocks through the pending blocks with tx bodies and `execute_l1_tx`.
+    update_reorg_block_count(&mut binding_test, 0);
+    for block_hash in [commit_block, reveal_block] {
+        execute_mock_block_with_txs(&mut binding_test, &node, block_hash);
+    }
+
+    let inscription_id = InscriptionID::new(reveal_txid, 0);
+    let inscription_obj = binding_test
+        .get_object(&inscription_id.object_id())
+        .unwrap()
+        .expect("the inscription should be created by the reveal tx");
+    let revealed = inscription_obj.value_as::<Inscription>().unwrap();
+    assert_eq!(revealed.id, inscription_id);
+    assert_eq!(revealed.body, inscription.body);
+    assert_eq!(
+        revealed.content_type,
+        MoveOption::some(MoveString::from("text/plain;charset=utf-8"))
+    );
+    let reveal_outpoint: rooch_types::bitcoin::types::OutPoint =
+        bitcoin::OutPoint::new(reveal_txid, 0).into();
+    assert_eq!(revealed.location.outpoint, reveal_outpoint);
+
+    // The UTXO of the reveal output seals the inscription
+    let utxo_obj = binding_test
+        .get_object(&utxo::derive_utxo_id(&reveal_outpoint))
+        .unwrap()
+        .expect("the reveal output should be a UTXO");
+    let utxo = utxo_obj.value_as::<UTXO>().unwrap();
+    assert_eq!(utxo.value, 9_000);
+    let inscription_seals = utxo
+        .seals
+        .borrow(&MoveString::from(
+            Inscription::type_tag().to_canonical_string(),
+        ))
+        .expect("the UTXO should seal the inscription");
+    assert_eq!(inscription_seals, &vec![inscription_id.object_id()]);
+}
@@ -0,0 +1,207 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::ident_str;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::value::MoveValue;
+use move_core_types::vm_status::{KeptVMStatus, VMStatus};
+use moveos_store::MoveOSStore;
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::function_return_value::AnnotatedFunctionResult;
+use moveos_types::h256::H256;
+use moveos_types::move_types::FunctionId;
+use moveos_types::moveos_std::account;
+use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::state::{FieldKey, ObjectState};
+use moveos_types::transaction::{FunctionCall, MoveAction};
+use rooch_types::error::RoochError;
+use rooch_types::framework::empty::Empty;
+use rooch_types::transaction::RoochTransactionData;
+use smt::jellyfish_merkle::node_type::Node;
+use std::collections::HashSet;
+
+fn sequence_number_call(addr: AccountAddress) -> FunctionCall {
+    FunctionCall::new(
+        FunctionId::new(
+            ModuleId::new(MOVEOS_STD_ADDRESS, account::MODULE_NAME.to_owned()),
+            ident_str!("sequence_number").to_owned(),
+        ),
+        vec![],
+        vec![MoveValue::Address(addr).simple_serialize().unwrap()],
+    )
+}
+
+fn empty_call() -> FunctionCall {
+    FunctionCall::new(
+        Empty::function_id(Empty::EMPTY_FUNCTION_NAME),
+        vec![],
+        vec![],
+    )
+}
+
+fn decode_u64(result: &AnnotatedFunctionResult) -> u64 {
+    assert_eq!(result.vm_status, VMStatus::Executed);
+    let values = result.return_values.as_ref().unwrap();
+    bcs::from_bytes(&values[0].value.value).unwrap()
+}
+
+fn assert_state_root_not_available(err: anyhow::Error) {
+    assert!(
+        matches!(
+            err.downcast_ref::<RoochError>(),
+            Some(RoochError::StateRootNotAvailable(_))
+        ),
+        "unexpected error: {:?}",
+        err
+    );
+}
+
+/// The nodes of the state tree of the root, including the trees of the child objects
+fn reachable_nodes(store: &MoveOSStore, root: H256) -> HashSet<H256> {
+    let node_store = store.get_state_node_store();
+    let mut seen = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(node_hash) = stack.pop() {
+        if !seen.insert(node_hash) {
+            continue;
+        }
+        let Some(bytes) = node_store.multi_get(&[node_hash]).unwrap().pop().flatten() else {
+            continue;
+        };
+        match Node::<FieldKey, ObjectState>::decode(&bytes).unwrap() {
+            Node::Internal(internal) => {
+                stack.extend(internal.all_child().into_iter().map(Into::<H256>::into));
+            }
+            Node::Leaf(leaf) => {
+                let child_root = leaf.value().origin.metadata.state_root();
+                if child_root != *smt::SPARSE_MERKLE_PLACEHOLDER_HASH {
+                    stack.push(child_root);
+                }
+            }
+            Node::Null => {}
+        }
+    }
+    seen
+}
+
+/// Execute a tx after the returned root, so the account of the sequencer is different in them
+fn execute_after_pinned_root(binding_test: &mut binding_test::RustBindingTest) -> ObjectMeta {
+    let pinned_root = binding_test.root().clone();
+    binding_test
+        .execute_function_call_via_sequencer(empty_call())
+        .unwrap();
+    assert_ne!(binding_test.root().state_root(), pinned_root.state_root());
+    pinned_root
+}
+
+#[tokio::test]
+async fn test_historical_view_function() {
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let sequencer = binding_test.sequencer;
+    let sequence_number = binding_test.get_account_sequence_number(sequencer).unwrap();
+    let pinned_root = execute_after_pinned_root(&mut binding_test);
+
+    let reader_executor = &binding_test.reader_executor;
+    let root = reader_executor
+        .resolve_historical_root(Some(pinned_root.state_root()), None)
+        .unwrap();
+    assert_eq!(root.state_root(), pinned_root.state_root());
+    let result = reader_executor
+        .execute_view_function(sequence_number_call(sequencer), Some(root))
+        .unwrap();
+    assert_eq!(decode_u64(&result), sequence_number);
+
+    let latest = reader_executor.resolve_historical_root(None, None).unwrap();
+    assert_eq!(latest.state_root(), binding_test.root().state_root());
+    let result = reader_executor
+        .execute_view_function(sequence_number_call(sequencer), None)
+        .unwrap();
+    assert_eq!(decode_u64(&result), sequence_number + 1);
+
+    assert!(reader_executor
+        .resolve_historical_root(Some(pinned_root.state_root()), Some(0))
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_historical_dry_run() {
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let sequencer = binding_test.sequencer;
+    let sequence_number = binding_test.get_account_sequence_number(sequencer).unwrap();
+    let pinned_root = execute_after_pinned_root(&mut binding_test);
+
+    // The tx with the sequence number of the pinned root is valid against it
+    let tx_data = RoochTransactionData::new_for_test(
+        sequencer.into(),
+        sequence_number,
+        MoveAction::Function(empty_call()),
+    );
+    let verified_tx = binding_test
+        .executor()
+        .convert_to_verified_tx_for_dry_run(tx_data, None, Some(pinned_root.clone()))
+        .unwrap();
+    assert_eq!(verified_tx.root.state_root(), pinned_root.state_root());
+    let result = binding_test.executor.dry_run(verified_tx).unwrap();
+    assert_eq!(result.raw_output.status, KeptVMStatus::Executed);
+    assert!(result.vm_error_info.is_none());
+
+    // The dry run does not change the latest root
+    assert_eq!(
+        binding_test.get_account_sequence_number(sequencer).unwrap(),
+        sequence_number + 1
+    );
+}
+
+#[tokio::test]
+async fn test_pruned_historical_root() {
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let pinned_root = execute_after_pinned_root(&mut binding_test);
+    let store = binding_test.rooch_db().moveos_store.clone();
+
+    // Prune the nodes of the pinned root but keep its root node, like a pruner interrupted in
+    // the middle of the deletion. The states read before are cached by the state store, so the
+    // calls below read the account of an address never read before.
+    let pruned_nodes = reachable_nodes(&store, pinned_root.state_root())
+        .into_iter()
+        .filter(|node| *node != pinned_root.state_root())
+        .collect::<Vec<_>>();
+    assert!(!pruned_nodes.is_empty());
+    store
+        .get_state_node_store()
+        .delete_nodes(pruned_nodes)
+        .unwrap();
+
+    let reader_executor = &binding_test.reader_executor;
+    let root = reader_executor
+        .resolve_historical_root(Some(pinned_root.state_root()), None)
+        .unwrap();
+    let err = reader_executor
+        .execute_view_function(sequence_number_call(AccountAddress::random()), Some(root))
+        .unwrap_err();
+    assert_state_root_not_available(err);
+
+    let tx_data = RoochTransactionData::new_for_test(
+        AccountAddress::random().into(),
+        0,
+        MoveAction::Function(empty_call()),
+    );
+    let verified_tx = binding_test
+        .executor()
+        .convert_to_verified_tx_for_dry_run(tx_data, None, Some(pinned_root.clone()))
+        .unwrap();
+    let err = binding_test.executor.dry_run(verified_tx).unwrap_err();
+    assert_state_root_not_available(err);
+
+    // The root node is pruned at last
+    store
+        .get_state_node_store()
+        .delete_nodes(vec![pinned_root.state_root()])
+        .unwrap();
+    let err = binding_test
+        .reader_executor
+        .resolve_historical_root(Some(pinned_root.state_root()), None)
+        .unwrap_err();
+    assert_state_root_not_available(err);
+}
@@ -3,6 +3,7 @@
 
 mod bbn_test;
 mod bitcoin_data;
+mod bitcoin_mock_node_test;
 mod bitcoin_multisign_validator_tests;
 mod bitcoin_test;
 mod bitcoin_tester_test;
@@ -12,7 +13,10 @@ mod chain_id_test;
 mod check_tx_size;
 mod empty_tests;
 mod ethereum_test;
+mod historical_state_test;
 mod multisign_account_tests;
 mod ord_test;
 mod session_validator_tests;
+mod sponsor_validator_tests;
+mod state_transition_verifier_test;
 mod view_function_gas;
@@ -5,17 +5,22 @@ use crate::binding_test;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
 use move_core_types::language_storage::ModuleId;
+use move_core_types::u256::U256;
 use move_core_types::value::MoveValue;
 use move_core_types::vm_status::{AbortLocation, KeptVMStatus, VMStatus};
 use moveos_types::module_binding::MoveFunctionCaller;
 use moveos_types::move_std::string::MoveString;
 use moveos_types::move_types::FunctionId;
+use moveos_types::state::MoveStructType;
 use moveos_types::{module_binding::ModuleBinding, transaction::MoveAction};
 use rooch_key::keystore::account_keystore::AccountKeystore;
 use rooch_key::keystore::memory_keystore::InMemKeystore;
+use rooch_types::framework::gas_coin::RGas;
+use rooch_types::framework::payment_channel::PaymentChannelModule;
 use rooch_types::framework::session_key::SessionKeyModule;
 use rooch_types::framework::session_validator::SessionValidatorModule;
 use rooch_types::framework::timestamp::TimestampModule;
+use rooch_types::framework::transfer::TransferModule;
 use rooch_types::{addresses::ROOCH_FRAMEWORK_ADDRESS, framework::empty::Empty};
 use rooch_types::{framework::session_key::SessionScope, transaction::rooch::RoochTransactionData};
 use std::str::FromStr;
@@ -184,3 +189,176 @@ async fn test_session_key_rooch() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_session_key_limit() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let mut keystore = InMemKeystore::new_insecure_for_tests(1);
+    let sender = keystore.addresses()[0];
+    let sequence_number = 0;
+
+    let session_auth_key = keystore.generate_session_key(&sender, None).unwrap();
+    let session_scope = SessionScope::new(ROOCH_FRAMEWORK_ADDRESS, "*", "*").unwrap();
+    let max_tx_count = 1;
+    let action = SessionKeyModule::create_session_key_with_limit_action(
+        MoveString::from_str("test").unwrap(),
+        MoveString::from_str("https:://test.rooch.network").unwrap(),
+        session_auth_key.as_ref().to_vec(),
+        vec![session_scope],
+        0,
+        U256::zero(),
+        max_tx_count,
+        0,
+        vec![],
+    );
+    let tx_data = RoochTransactionData::new_for_test(sender, sequence_number, action);
+    let tx = keystore.sign_transaction(&sender, tx_data, None).unwrap();
+    binding_test.execute(tx).unwrap();
+
+    let session_key_module = binding_test.as_module_binding::<SessionKeyModule>();
+    let session_key = session_key_module
+        .get_session_key(sender.into(), &session_auth_key)
+        .unwrap()
+        .expect("Session key not found");
+    let limit = session_key_module
+        .get_session_key_limit(sender.into(), &session_auth_key)
+        .unwrap()
+        .expect("Session key limit not found");
+    assert_eq!(limit.max_tx_count, max_tx_count);
+    assert_eq!(limit.tx_count, 0);
+    keystore.binding_session_key(sender, session_key).unwrap();
+
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let tx_data = RoochTransactionData::new_for_test(sender, sequence_number + 1, action);
+    let tx = keystore
+        .sign_transaction_via_session_key(&sender, tx_data, &session_auth_key, None)
+        .unwrap();
+    binding_test.execute(tx).unwrap();
+
+    let limit = binding_test
+        .as_module_binding::<SessionKeyModule>()
+        .get_session_key_limit(sender.into(), &session_auth_key)
+        .unwrap()
+        .unwrap();
+    assert_eq!(limit.tx_count, 1);
+    assert!(limit.gas_used > U256::zero());
+
+    // The session key reaches the max tx count, so the transaction should be rejected when validate.
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let tx_data = RoochTransactionData::new_for_test(sender, sequence_number + 2, action);
+    let tx = keystore
+        .sign_transaction_via_session_key(&sender, tx_data, &session_auth_key, None)
+        .unwrap();
+    let error = binding_test
+        .execute_as_result(tx)
+        .expect_err("expect transaction validate error");
+    // ErrorValidateSessionLimitExceeded = 1014
+    assert!(
+        matches!(error.downcast_ref(), Some(VMStatus::MoveAbort(_, 1014))),
+        "expect ErrorValidateSessionLimitExceeded, got {:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_session_key_transfer_cap() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let mut keystore = InMemKeystore::new_insecure_for_tests(1);
+    let sender = keystore.addresses()[0];
+    let receiver = AccountAddress::random();
+    let rgas_type_name = MoveString::from(RGas::struct_tag().to_canonical_string());
+    let rgas = |amount: u64| U256::from(amount) * U256::from(100_000_000u64);
+
+    let session_auth_key = keystore.generate_session_key(&sender, None).unwrap();
+    let session_scope = SessionScope::new(ROOCH_FRAMEWORK_ADDRESS, "*", "*").unwrap();
+    let action = SessionKeyModule::create_session_key_with_limit_action(
+        MoveString::from_str("test").unwrap(),
+        MoveString::from_str("https:://test.rooch.network").unwrap(),
+        session_auth_key.as_ref().to_vec(),
+        vec![session_scope],
+        0,
+        U256::zero(),
+        0,
+        0,
+        vec![(rgas_type_name.clone(), rgas(80))],
+    );
+    let tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+    let tx = keystore.sign_transaction(&sender, tx_data, None).unwrap();
+    binding_test.execute(tx).unwrap();
+    let session_key = binding_test
+        .as_module_binding::<SessionKeyModule>()
+        .get_session_key(sender.into(), &session_auth_key)
+        .unwrap()
+        .expect("Session key not found");
+    keystore.binding_session_key(sender, session_key).unwrap();
+
+    let transferred = |binding_test: &binding_test::RustBindingTest| {
+        let limit = binding_test
+            .as_module_binding::<SessionKeyModule>()
+            .get_session_key_limit(sender.into(), &session_auth_key)
+            .unwrap()
+            .expect("Session key limit not found");
+        limit
+            .transfer_caps
+            .borrow(&rgas_type_name)
+            .unwrap()
+            .transferred
+    };
+    let mut sequence_number = 1;
+    let mut execute_via_session_key = |binding_test: &mut binding_test::RustBindingTest,
+                                       action: MoveAction| {
+        let tx_data = RoochTransactionData::new_for_test(sender, sequence_number, action);
+        sequence_number += 1;
+        let tx = keystore
+            .sign_transaction_via_session_key(&sender, tx_data, &session_auth_key, None)
+            .unwrap();
+        binding_test.execute_as_result(tx).unwrap().output.status
+    };
+
+    // The coins leaving the account coin store count to the cap, the gas does not
+    let status = execute_via_session_key(
+        &mut binding_test,
+        PaymentChannelModule::deposit_to_hub_entry_action(
+            RGas::struct_tag(),
+            sender.into(),
+            rgas(50),
+        ),
+    );
+    assert_eq!(status, KeptVMStatus::Executed);
+    assert_eq!(transferred(&binding_test), rgas(50));
+
+    // The coins leaving the payment hub count to the cap, the gas paid by the hub does not
+    let status = execute_via_session_key(
+        &mut binding_test,
+        PaymentChannelModule::transfer_to_hub_entry_action(RGas::struct_tag(), receiver, rgas(20)),
+    );
+    assert_eq!(status, KeptVMStatus::Executed);
+    assert_eq!(transferred(&binding_test), rgas(70));
+
+    // The transfer exceeds the cap
+    let status = execute_via_session_key(
+        &mut binding_test,
+        TransferModule::create_transfer_coin_action(RGas::struct_tag(), receiver, rgas(20)),
+    );
+    match status {
+        KeptVMStatus::MoveAbort(AbortLocation::Module(module_id), code) => {
+            assert_eq!(module_id, SessionKeyModule::module_id());
+            // ErrorSessionKeyTransferCapExceeded = 6
+            assert_eq!(code, 6, "expect ErrorSessionKeyTransferCapExceeded");
+        }
+        _ => panic!("Expect move abort, got {:?}", status),
+    }
+    assert_eq!(transferred(&binding_test), rgas(70));
+
+    // The transfer in the cap
+    let status = execute_via_session_key(
+        &mut binding_test,
+        TransferModule::create_transfer_coin_action(RGas::struct_tag(), receiver, rgas(10)),
+    );
+    assert_eq!(status, KeptVMStatus::Executed);
+    assert_eq!(transferred(&binding_test), rgas(80));
+}
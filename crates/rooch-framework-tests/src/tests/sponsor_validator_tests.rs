@@ -0,0 +1,129 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::u256::U256;
+use move_core_types::vm_status::{KeptVMStatus, VMStatus};
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::transaction::MoveAction;
+use rooch_key::keystore::account_keystore::AccountKeystore;
+use rooch_key::keystore::memory_keystore::InMemKeystore;
+use rooch_types::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use rooch_types::framework::empty::Empty;
+use rooch_types::framework::gas_sponsor::GasSponsorModule;
+use rooch_types::framework::timestamp::TimestampModule;
+use rooch_types::transaction::authenticator::Authenticator;
+use rooch_types::transaction::rooch::RoochTransactionData;
+use rooch_types::transaction::RoochTransaction;
+
+#[tokio::test]
+async fn test_sponsored_transaction() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let keystore = InMemKeystore::new_insecure_for_tests(2);
+    let sponsor = keystore.addresses()[0];
+    let sender = keystore.addresses()[1];
+    let sponsor_kp = keystore.get_key_pair(&sponsor, None).unwrap();
+    let sender_kp = keystore.get_key_pair(&sender, None).unwrap();
+
+    // The sponsor pays the gas of all the functions in the empty module
+    let initial_budget = U256::from(1_000_000_000_000u64);
+    let action = GasSponsorModule::set_budget_action(
+        ROOCH_FRAMEWORK_ADDRESS,
+        "empty",
+        GasSponsorModule::ANY_FUNCTION,
+        initial_budget,
+    )
+    .unwrap();
+    let tx_data = RoochTransactionData::new_for_test(sponsor, 0, action);
+    let tx = keystore.sign_transaction(&sponsor, tx_data, None).unwrap();
+    binding_test.execute(tx).unwrap();
+
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+    let sender_authenticator = Authenticator::bitcoin(&sender_kp, &tx_data);
+    let authenticator =
+        Authenticator::sponsor(sender_authenticator, &sponsor_kp, &tx_data).unwrap();
+    let result = binding_test
+        .execute(RoochTransaction::new(tx_data, authenticator))
+        .unwrap();
+    assert!(result.transaction_info.gas_used > 0);
+
+    // The used gas is charged to the sponsor's budget
+    let budget = binding_test
+        .as_module_binding::<GasSponsorModule>()
+        .budget(
+            sponsor.into(),
+            ROOCH_FRAMEWORK_ADDRESS,
+            "empty",
+            GasSponsorModule::ANY_FUNCTION,
+        )
+        .unwrap()
+        .unwrap();
+    assert!(budget < initial_budget);
+
+    // The function call out of the budget scopes is rejected when validate
+    let action = TimestampModule::create_fast_forward_seconds_for_local_action(1);
+    let tx_data = RoochTransactionData::new_for_test(sender, 1, action);
+    let sender_authenticator = Authenticator::bitcoin(&sender_kp, &tx_data);
+    let authenticator =
+        Authenticator::sponsor(sender_authenticator, &sponsor_kp, &tx_data).unwrap();
+    let error = binding_test
+        .execute_as_result(RoochTransaction::new(tx_data, authenticator))
+        .expect_err("expect transaction validate error");
+    assert!(
+        matches!(error.downcast_ref(), Some(VMStatus::MoveAbort(_, 2))),
+        "expect ErrorSponsorBudgetExceeded, got {:?}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_sponsored_dry_run() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let keystore = InMemKeystore::new_insecure_for_tests(2);
+    let sponsor = keystore.addresses()[0];
+    let sender = keystore.addresses()[1];
+    let sponsor_kp = keystore.get_key_pair(&sponsor, None).unwrap();
+    let sender_kp = keystore.get_key_pair(&sender, None).unwrap();
+
+    let action = GasSponsorModule::set_budget_action(
+        ROOCH_FRAMEWORK_ADDRESS,
+        "empty",
+        GasSponsorModule::ANY_FUNCTION,
+        U256::from(1_000_000_000_000u64),
+    )
+    .unwrap();
+    let tx_data = RoochTransactionData::new_for_test(sponsor, 0, action);
+    let tx = keystore.sign_transaction(&sponsor, tx_data, None).unwrap();
+    binding_test.execute(tx).unwrap();
+
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+    let sender_authenticator = Authenticator::bitcoin(&sender_kp, &tx_data);
+    let authenticator =
+        Authenticator::sponsor(sender_authenticator, &sponsor_kp, &tx_data).unwrap();
+    let verified_tx = binding_test
+        .executor()
+        .convert_to_verified_tx_for_dry_run(tx_data, Some(authenticator), None)
+        .unwrap();
+    let result = binding_test.executor.dry_run(verified_tx).unwrap();
+    assert_eq!(result.raw_output.status, KeptVMStatus::Executed);
+    assert!(result.raw_output.gas_used > 0);
+
+    // The gas is charged to the sponsor, the same as the executed sponsored transaction
+    let (sponsor, sender): (AccountAddress, AccountAddress) = (sponsor.into(), sender.into());
+    let balance_changes = &result.state_diff.balance_changes;
+    assert!(
+        balance_changes
+            .iter()
+            .any(|change| change.owner == sponsor && change.after < change.before),
+        "{:?}",
+        balance_changes
+    );
+    assert!(balance_changes.iter().all(|change| change.owner != sender));
+}
@@ -0,0 +1,129 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test::RustBindingTest;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::moveos_std::timestamp::Timestamp;
+use moveos_types::state::{MoveStructType, ObjectChange, ObjectState, StateChangeSet};
+use moveos_types::transaction::MoveAction;
+use rooch_executor::verifier::StateTransitionVerifier;
+use rooch_key::keystore::account_keystore::AccountKeystore;
+use rooch_key::keystore::memory_keystore::InMemKeystore;
+use rooch_types::block::Block;
+use rooch_types::framework::empty::Empty;
+use rooch_types::transaction::rooch::RoochTransactionData;
+use rooch_types::transaction::{LedgerTransaction, TransactionSequenceInfo};
+
+// Execute an empty tx with the sequence info like the sequencer does,
+// returns the ledger tx to re-execute.
+fn execute_sequenced_tx(binding_test: &mut RustBindingTest, tx_order: u64) -> LedgerTransaction {
+    let keystore = InMemKeystore::new_insecure_for_tests(1);
+    let sender = keystore.addresses()[0];
+    let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+    let tx_data = RoochTransactionData::new_for_test(sender, 0, action);
+    let tx = keystore.sign_transaction(&sender, tx_data, None).unwrap();
+
+    let sequence_info = TransactionSequenceInfo {
+        tx_order,
+        tx_order_signature: vec![],
+        tx_accumulator_root: H256::random(),
+        tx_timestamp: 1_700_000_000_000 + tx_order,
+        tx_accumulator_frozen_subtree_roots: vec![],
+        tx_accumulator_num_leaves: tx_order,
+        tx_accumulator_num_nodes: tx_order,
+    };
+    let mut verified_tx = binding_test.executor.validate_l2_tx(tx.clone()).unwrap();
+    verified_tx.ctx.add(sequence_info.clone()).unwrap();
+    binding_test.execute_verified_tx(verified_tx).unwrap();
+    LedgerTransaction::new_l2_tx(tx, sequence_info)
+}
+
+fn new_verifier(binding_test: &RustBindingTest, root: ObjectMeta) -> StateTransitionVerifier {
+    let rooch_db = binding_test.rooch_db();
+    StateTransitionVerifier::new(
+        root,
+        rooch_db.moveos_store.clone(),
+        rooch_db.rooch_store.clone(),
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_verify_tx_matching_root() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = RustBindingTest::new().unwrap();
+    let pre_root = binding_test.root().clone();
+    let ledger_tx = execute_sequenced_tx(&mut binding_test, 1);
+
+    let mut verifier = new_verifier(&binding_test, pre_root);
+    let result = verifier.verify_tx(ledger_tx, None, false).unwrap();
+    assert!(result.is_match(), "{:?}", result);
+    assert_eq!(
+        verifier.root().state_root(),
+        binding_test.root().state_root()
+    );
+}
+
+#[tokio::test]
+async fn test_verify_block_against_committed_root() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = RustBindingTest::new().unwrap();
+    let pre_root = binding_test.root().clone();
+    let ledger_tx = execute_sequenced_tx(&mut binding_test, 1);
+    let new_block = |state_root: H256| {
+        Block::new(
+            1,
+            1,
+            H256::random(),
+            H256::random(),
+            H256::random(),
+            state_root,
+        )
+    };
+
+    let mut verifier = new_verifier(&binding_test, pre_root);
+    verifier.verify_tx(ledger_tx, None, false).unwrap();
+    let result = verifier.verify_block(&new_block(binding_test.root().state_root()));
+    assert!(result.is_match(), "{:?}", result);
+
+    // the proposer committed another state root, the block must not pass
+    let result = verifier.verify_block(&new_block(H256::random()));
+    assert!(!result.is_match());
+    assert_eq!(result.actual_state_root, binding_test.root().state_root());
+}
+
+#[tokio::test]
+async fn test_verify_tx_divergent_root() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = RustBindingTest::new().unwrap();
+    let pre_root = binding_test.root().clone();
+    let ledger_tx = execute_sequenced_tx(&mut binding_test, 1);
+
+    // re-execute on a root with an extra object, the tx must not reproduce the recorded root
+    let mut change_set = StateChangeSet::new(pre_root.state_root(), pre_root.size);
+    let extra_object = ObjectState::new_with_struct(
+        ObjectMeta::genesis_meta(ObjectID::random(), Timestamp::type_tag()),
+        Timestamp { milliseconds: 1 },
+    )
+    .unwrap();
+    change_set
+        .add_change(ObjectChange::new_object(extra_object))
+        .unwrap();
+    binding_test
+        .rooch_db()
+        .moveos_store
+        .state_store
+        .apply_change_set(&mut change_set)
+        .unwrap();
+    let divergent_root = change_set.root_metadata();
+    assert_ne!(divergent_root.state_root(), pre_root.state_root());
+
+    let mut verifier = new_verifier(&binding_test, divergent_root);
+    let result = verifier.verify_tx(ledger_tx, None, false).unwrap();
+    assert!(!result.is_match());
+    let expected = result.expected.as_ref().unwrap();
+    assert_eq!(expected.state_root, binding_test.root().state_root());
+    assert_ne!(result.actual.state_root, expected.state_root);
+    assert_eq!(result.actual.size, expected.size + 1);
+}
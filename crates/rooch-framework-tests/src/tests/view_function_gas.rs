@@ -2,10 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::account_address::AccountAddress;
-use move_core_types::vm_status::StatusCode;
+use move_core_types::ident_str;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::value::MoveValue;
+use move_core_types::vm_status::{StatusCode, VMStatus};
 
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::function_return_value::FunctionResult;
 use moveos_types::h256::H256;
 use moveos_types::module_binding::{ModuleBinding, MoveFunctionCaller};
+use moveos_types::move_types::FunctionId;
+use moveos_types::moveos_std::account;
 use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::transaction::FunctionCall;
 use rooch_types::framework::empty::Empty;
@@ -28,3 +35,62 @@ async fn view_function_gas() {
         .unwrap();
     assert_eq!(result.vm_status.status_code(), StatusCode::OUT_OF_GAS)
 }
+
+fn sequence_number_call(addr: AccountAddress) -> FunctionCall {
+    FunctionCall::new(
+        FunctionId::new(
+            ModuleId::new(MOVEOS_STD_ADDRESS, account::MODULE_NAME.to_owned()),
+            ident_str!("sequence_number").to_owned(),
+        ),
+        vec![],
+        vec![MoveValue::Address(addr).simple_serialize().unwrap()],
+    )
+}
+
+fn decode_u64(result: &FunctionResult) -> u64 {
+    assert_eq!(result.vm_status, VMStatus::Executed);
+    let values = result.return_values.as_ref().unwrap();
+    bcs::from_bytes(&values[0].value).unwrap()
+}
+
+#[tokio::test]
+async fn view_functions_share_state_root() {
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let sequencer = binding_test.sequencer;
+    let empty_call = FunctionCall::new(
+        Empty::function_id(Empty::EMPTY_FUNCTION_NAME),
+        vec![],
+        vec![],
+    );
+
+    let pinned_root = binding_test.root().clone();
+    let sequence_number = binding_test.get_account_sequence_number(sequencer).unwrap();
+    binding_test
+        .execute_function_call_via_sequencer(empty_call.clone())
+        .unwrap();
+    assert_ne!(binding_test.root().state_root(), pinned_root.state_root());
+
+    let calls = vec![
+        sequence_number_call(sequencer),
+        empty_call.clone(),
+        sequence_number_call(sequencer),
+    ];
+    let moveos = binding_test.reader_executor.moveos();
+
+    // Every call of the batch sees the pinned root, not the latest one.
+    let results = moveos.execute_view_functions(pinned_root, calls.clone());
+    assert_eq!(results.len(), 3);
+    assert_eq!(decode_u64(&results[0].0), sequence_number);
+    assert_eq!(results[1].0.vm_status, VMStatus::Executed);
+    assert_eq!(decode_u64(&results[2].0), sequence_number);
+
+    let results = moveos.execute_view_functions(binding_test.root().clone(), calls);
+    assert_eq!(decode_u64(&results[0].0), sequence_number + 1);
+    assert_eq!(decode_u64(&results[2].0), sequence_number + 1);
+
+    // Every call has its own gas meter, so the gas used by a call does not
+    // depend on the calls that run before it in the batch.
+    assert_eq!(results[0].1, results[2].1);
+    let standalone = moveos.execute_view_functions(binding_test.root().clone(), vec![empty_call]);
+    assert_eq!(standalone[0].1, results[1].1);
+}
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS rune_balances;
\ No newline at end of file
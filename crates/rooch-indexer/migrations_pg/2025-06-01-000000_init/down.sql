@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS fields;
+DROP TABLE IF EXISTS inscriptions;
+DROP TABLE IF EXISTS utxos;
+DROP TABLE IF EXISTS object_states;
+DROP TABLE IF EXISTS events;
+DROP TABLE IF EXISTS transactions;
@@ -0,0 +1,102 @@
+-- PostgreSQL keeps all indexer tables in one database, unlike the SQLite backend which
+-- stores each table in its own file.
+-- Text columns that take part in range queries use the "C" collation so that ordering is
+-- bytewise, which the object type prefix queries rely on.
+
+CREATE TABLE transactions (
+    tx_order                            BIGINT       NOT NULL      PRIMARY KEY,
+    tx_hash                             VARCHAR      NOT NULL,
+    sequence_number                     BIGINT       NOT NULL,
+    sender                              VARCHAR      NOT NULL,
+    action_type                         SMALLINT     NOT NULL,
+    auth_validator_id                   BIGINT       NOT NULL,
+    gas_used                            BIGINT       NOT NULL,
+    status                              VARCHAR      NOT NULL,
+    created_at                          BIGINT       NOT NULL,
+    UNIQUE (tx_hash)
+);
+
+CREATE INDEX idx_transactions_sender ON transactions (sender, tx_order);
+CREATE INDEX idx_transactions_created_at ON transactions (created_at, tx_order);
+
+CREATE TABLE events
+(
+    event_handle_id    VARCHAR      NOT NULL,
+    event_seq          BIGINT       NOT NULL,
+    event_type         VARCHAR      COLLATE "C"    NOT NULL,
+    event_index        BIGINT       NOT NULL,
+
+    tx_hash            VARCHAR      NOT NULL,
+    tx_order           BIGINT       NOT NULL,
+    sender             VARCHAR      NOT NULL,
+    created_at         BIGINT       NOT NULL,
+    -- Constraints
+    PRIMARY KEY (tx_order, event_index),
+    UNIQUE (event_handle_id, event_seq)
+);
+
+CREATE INDEX idx_events_sender_and_event_type ON events (sender, event_type, tx_order, event_index);
+CREATE INDEX idx_events_event_type ON events (event_type, tx_order, event_index);
+CREATE INDEX idx_events_sender_and_event_handle ON events (sender, event_handle_id, tx_order, event_index);
+CREATE INDEX idx_events_event_handle ON events (event_handle_id, tx_order, event_index);
+CREATE INDEX idx_events_sender ON events (sender, tx_order, event_index);
+CREATE INDEX idx_events_tx_hash ON events (tx_hash, tx_order, event_index);
+CREATE INDEX idx_events_created_at ON events (created_at, tx_order, event_index);
+
+CREATE TABLE object_states
+(
+    id                 VARCHAR        NOT NULL       PRIMARY KEY,
+    owner              VARCHAR        NOT NULL,
+    object_type        VARCHAR        COLLATE "C"    NOT NULL,
+    tx_order           BIGINT         NOT NULL,
+    state_index        BIGINT         NOT NULL,
+    created_at         BIGINT         NOT NULL,
+    updated_at         BIGINT         NOT NULL,
+    UNIQUE (tx_order, state_index)
+);
+
+CREATE INDEX idx_object_states_owner_and_object_type ON object_states (owner, object_type, tx_order, state_index);
+CREATE INDEX idx_object_states_object_type ON object_states (object_type, tx_order, state_index);
+CREATE INDEX idx_object_states_owner ON object_states (owner, tx_order, state_index);
+CREATE INDEX idx_object_states_updated_at ON object_states (updated_at, tx_order, state_index);
+
+CREATE TABLE utxos
+(
+    id                 VARCHAR        NOT NULL       PRIMARY KEY,
+    owner              VARCHAR        NOT NULL,
+    tx_order           BIGINT         NOT NULL,
+    state_index        BIGINT         NOT NULL,
+    created_at         BIGINT         NOT NULL,
+    updated_at         BIGINT         NOT NULL,
+    UNIQUE (tx_order, state_index)
+);
+
+CREATE INDEX idx_object_state_utxos_owner ON utxos (owner, tx_order, state_index);
+CREATE INDEX idx_object_state_utxos_updated_at ON utxos (updated_at, tx_order, state_index);
+
+CREATE TABLE inscriptions
+(
+    id                 VARCHAR        NOT NULL       PRIMARY KEY,
+    owner              VARCHAR        NOT NULL,
+    tx_order           BIGINT         NOT NULL,
+    state_index        BIGINT         NOT NULL,
+    created_at         BIGINT         NOT NULL,
+    updated_at         BIGINT         NOT NULL,
+    UNIQUE (tx_order, state_index)
+);
+
+CREATE INDEX idx_object_state_inscriptions_owner ON inscriptions (owner, tx_order, state_index);
+CREATE INDEX idx_object_state_inscriptions_updated_at ON inscriptions (updated_at, tx_order, state_index);
+
+CREATE TABLE fields
+(
+    id                VARCHAR        NOT NULL       PRIMARY KEY,
+    parent_id         VARCHAR        NOT NULL,
+    field_key         VARCHAR        NOT NULL,
+    sort_key          BIGINT         NOT NULL,
+    created_at        BIGINT         NOT NULL,
+    updated_at        BIGINT         NOT NULL,
+    UNIQUE (parent_id, field_key)
+);
+
+CREATE INDEX idx_fields_parent_id_sort_key ON fields (parent_id, sort_key);
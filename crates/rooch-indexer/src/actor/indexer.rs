@@ -4,10 +4,13 @@
 use crate::actor::messages::{
     IndexerApplyObjectStatesMessage, IndexerDeleteAnyObjectStatesMessage, IndexerEventsMessage,
     IndexerPersistOrUpdateAnyObjectStatesMessage, IndexerRevertMessage, IndexerStatesMessage,
-    IndexerTransactionMessage, UpdateIndexerMessage,
+    IndexerTransactionMessage, QueryIndexedTxOrderMessage, UpdateIndexerMessage,
 };
 use crate::store::traits::IndexerStoreTrait;
-use crate::{list_field_indexer_keys, IndexerStore};
+use crate::{
+    list_field_indexer_keys, IndexerStore, IndexerTableName, INDEXER_EVENTS_TABLE_NAME,
+    INDEXER_TRANSACTIONS_TABLE_NAME,
+};
 use anyhow::Result;
 use async_trait::async_trait;
 use coerce::actor::{context::ActorContext, message::Handler, Actor, LocalActorRef};
@@ -23,12 +26,15 @@ use rooch_types::indexer::state::{
     IndexerObjectStatesIndexGenerator, ObjectStateType,
 };
 use rooch_types::indexer::transaction::IndexerTransaction;
+use std::collections::HashMap;
 
 pub struct IndexerActor {
     root: ObjectMeta,
     indexer_store: IndexerStore,
     moveos_store: MoveOSStore,
     _notify_actor: Option<LocalActorRef<NotifyActor>>,
+    // The last tx_order written to the table since the actor started
+    indexed_tx_orders: HashMap<IndexerTableName, u64>,
 }
 
 impl IndexerActor {
@@ -43,9 +49,15 @@ impl IndexerActor {
             indexer_store,
             moveos_store,
             _notify_actor: notify_actor,
+            indexed_tx_orders: HashMap::new(),
         })
     }
 
+    fn update_indexed_tx_order(&mut self, table_name: IndexerTableName, tx_order: u64) {
+        let indexed_tx_order = self.indexed_tx_orders.entry(table_name).or_insert(tx_order);
+        *indexed_tx_order = (*indexed_tx_order).max(tx_order);
+    }
+
     // TODO use EventBus to trigger field indexer update
     pub fn get_all_field_indexer_keys(&self) -> Result<Vec<ObjectID>> {
         let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
@@ -78,6 +90,7 @@ impl Handler<UpdateIndexerMessage> for IndexerActor {
         )?;
         let transactions = vec![indexer_transaction];
         self.indexer_store.persist_transactions(transactions)?;
+        self.update_indexed_tx_order(INDEXER_TRANSACTIONS_TABLE_NAME, tx_order);
 
         // 2. update indexer event
         let events: Vec<_> = events
@@ -91,6 +104,7 @@ impl Handler<UpdateIndexerMessage> for IndexerActor {
             })
             .collect();
         self.indexer_store.persist_events(events)?;
+        self.update_indexed_tx_order(INDEXER_EVENTS_TABLE_NAME, tx_order);
 
         // 3. update indexer full object state, including object_states, utxos and inscriptions
         // indexer object state index generator
@@ -171,11 +185,13 @@ impl Handler<IndexerTransactionMessage> for IndexerActor {
             tx_context,
         } = msg;
 
+        let tx_order = ledger_transaction.sequence_info.tx_order;
         let indexer_transaction =
             IndexerTransaction::new(ledger_transaction, execution_info, move_action, tx_context)?;
         let transactions = vec![indexer_transaction];
 
         self.indexer_store.persist_transactions(transactions)?;
+        self.update_indexed_tx_order(INDEXER_TRANSACTIONS_TABLE_NAME, tx_order);
         Ok(())
     }
 }
@@ -189,11 +205,13 @@ impl Handler<IndexerEventsMessage> for IndexerActor {
             tx_context,
         } = msg;
 
+        let tx_order = ledger_transaction.sequence_info.tx_order;
         let events: Vec<_> = events
             .into_iter()
             .map(|event| IndexerEvent::new(event, ledger_transaction.clone(), tx_context.clone()))
             .collect();
         self.indexer_store.persist_events(events)?;
+        self.update_indexed_tx_order(INDEXER_EVENTS_TABLE_NAME, tx_order);
         Ok(())
     }
 }
@@ -217,6 +235,9 @@ impl Handler<IndexerPersistOrUpdateAnyObjectStatesMessage> for IndexerActor {
             ObjectStateType::Inscription => self
                 .indexer_store
                 .persist_or_update_object_state_inscriptions(states)?,
+            ObjectStateType::RuneBalances => self
+                .indexer_store
+                .persist_or_update_object_state_rune_balances(states)?,
         }
         Ok(())
     }
@@ -241,6 +262,9 @@ impl Handler<IndexerDeleteAnyObjectStatesMessage> for IndexerActor {
             ObjectStateType::Inscription => self
                 .indexer_store
                 .delete_object_state_inscriptions(state_pks)?,
+            ObjectStateType::RuneBalances => self
+                .indexer_store
+                .delete_object_state_rune_balances(state_pks)?,
         }
         Ok(())
     }
@@ -281,6 +305,9 @@ impl Handler<IndexerRevertMessage> for IndexerActor {
 
         // 2. revert indexer event
         self.indexer_store.delete_events(vec![revert_tx_order])?;
+        for indexed_tx_order in self.indexed_tx_orders.values_mut() {
+            *indexed_tx_order = (*indexed_tx_order).min(revert_tx_order.saturating_sub(1));
+        }
 
         // 3. revert indexer full object state, including object_states, utxos and inscriptions
         // indexer object state index generator
@@ -302,3 +329,14 @@ impl Handler<IndexerRevertMessage> for IndexerActor {
         Ok(())
     }
 }
+
+#[async_trait]
+impl Handler<QueryIndexedTxOrderMessage> for IndexerActor {
+    async fn handle(
+        &mut self,
+        msg: QueryIndexedTxOrderMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<u64>> {
+        Ok(self.indexed_tx_orders.get(msg.table_name.as_str()).copied())
+    }
+}
@@ -132,6 +132,16 @@ impl Message for IndexerDeleteAnyObjectStatesMessage {
     type Result = Result<()>;
 }
 
+/// Query the last tx_order written to the indexer table since the indexer started
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryIndexedTxOrderMessage {
+    pub table_name: String,
+}
+
+impl Message for QueryIndexedTxOrderMessage {
+    type Result = Result<Option<u64>>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueryLastStateIndexByTxOrderMessage {
     pub tx_order: u64,
@@ -3,23 +3,24 @@
 
 use crate::errors::IndexerError;
 use crate::metrics::IndexerReaderMetrics;
-use crate::models::events::StoredEvent;
+use crate::models::events::{StoredEvent, StoredEventID};
 use crate::models::fields::StoredField;
 use crate::models::states::{StoredObjectStateInfo, StoredStateID};
-use crate::models::transactions::StoredTransaction;
-use crate::schema::{events, transactions};
+use crate::models::transactions::{StoredTransaction, StoredTxOrder};
+#[cfg(feature = "postgres")]
+use crate::postgres::{PgConnectionPoolConfig, PgInnerIndexerReader};
 use crate::utils::escape_sql_string;
 use crate::{
     IndexerResult, IndexerStoreMeta, IndexerTableName, SqliteConnectionConfig,
     SqliteConnectionPoolConfig, SqlitePoolConnection, DEFAULT_BUSY_TIMEOUT,
     INDEXER_EVENTS_TABLE_NAME, INDEXER_FIELDS_TABLE_NAME, INDEXER_OBJECT_STATES_TABLE_NAME,
-    INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME, INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME,
-    INDEXER_TRANSACTIONS_TABLE_NAME,
+    INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME, INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME,
+    INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME, INDEXER_TRANSACTIONS_TABLE_NAME,
 };
 use anyhow::{anyhow, Result};
-use diesel::{
-    r2d2::ConnectionManager, Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection,
-};
+use diesel::query_builder::QueryableByName;
+use diesel::sqlite::Sqlite;
+use diesel::{r2d2::ConnectionManager, Connection, RunQueryDsl, SqliteConnection};
 use function_name::named;
 use move_core_types::language_storage::StructTag;
 use moveos_types::moveos_std::object::ObjectID;
@@ -28,6 +29,7 @@ use rooch_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
 use rooch_types::indexer::field::{FieldFilter, IndexerField};
 use rooch_types::indexer::state::{IndexerStateID, ObjectStateFilter, ObjectStateType};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
+use rooch_types::indexer::MAX_FILTER_DEPTH;
 use std::collections::HashMap;
 use std::ops::DerefMut;
 use std::path::PathBuf;
@@ -136,9 +138,29 @@ impl InnerIndexerReader {
     }
 }
 
+/// A row type that can be loaded by raw SQL from every enabled indexer backend.
+#[cfg(not(feature = "postgres"))]
+pub trait IndexerRow: QueryableByName<Sqlite> + Send + 'static {}
+#[cfg(not(feature = "postgres"))]
+impl<T> IndexerRow for T where T: QueryableByName<Sqlite> + Send + 'static {}
+
+/// A row type that can be loaded by raw SQL from every enabled indexer backend.
+#[cfg(feature = "postgres")]
+pub trait IndexerRow:
+    QueryableByName<Sqlite> + QueryableByName<diesel::pg::Pg> + Send + 'static
+{
+}
+#[cfg(feature = "postgres")]
+impl<T> IndexerRow for T where
+    T: QueryableByName<Sqlite> + QueryableByName<diesel::pg::Pg> + Send + 'static
+{
+}
+
 #[derive(Clone)]
 pub struct IndexerReader {
     pub(crate) inner_indexer_reader_mapping: HashMap<String, InnerIndexerReader>,
+    #[cfg(feature = "postgres")]
+    pub(crate) pg_reader: Option<PgInnerIndexerReader>,
     metrics: Arc<IndexerReaderMetrics>,
 }
 
@@ -148,6 +170,37 @@ impl IndexerReader {
         Self::new_with_config(db_path, config, registry)
     }
 
+    /// Creates a reader on the PostgreSQL database at `db_url` if it is given,
+    /// otherwise on the SQLite databases under `db_path`.
+    /// Only the PostgreSQL db url schemes are supported.
+    pub fn new_with_db_url(
+        db_path: PathBuf,
+        db_url: Option<&str>,
+        registry: &Registry,
+    ) -> Result<Self> {
+        match db_url {
+            Some(db_url) if crate::is_postgres_url(db_url) => Self::new_postgres(db_url, registry),
+            Some(db_url) => Err(crate::unsupported_db_url_error(db_url)),
+            None => Self::new(db_path, registry),
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn new_postgres(db_url: &str, registry: &Registry) -> Result<Self> {
+        let config = PgConnectionPoolConfig::pool_config(true);
+        let pg_reader = PgInnerIndexerReader::new_with_config(db_url, config)?;
+        Ok(IndexerReader {
+            inner_indexer_reader_mapping: HashMap::new(),
+            pg_reader: Some(pg_reader),
+            metrics: Arc::new(IndexerReaderMetrics::new(registry)),
+        })
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn new_postgres(_db_url: &str, _registry: &Registry) -> Result<Self> {
+        Err(crate::postgres_not_enabled_error())
+    }
+
     pub fn new_with_config(
         db_path: PathBuf,
         config: SqliteConnectionPoolConfig,
@@ -170,6 +223,8 @@ impl IndexerReader {
 
         Ok(IndexerReader {
             inner_indexer_reader_mapping,
+            #[cfg(feature = "postgres")]
+            pg_reader: None,
             metrics: Arc::new(IndexerReaderMetrics::new(registry)),
         })
     }
@@ -182,6 +237,18 @@ impl IndexerReader {
             .clone())
     }
 
+    /// Runs a raw SQL query against the backend that holds `table_name`.
+    /// The query must be portable between SQLite and PostgreSQL.
+    fn load_by_sql<T: IndexerRow>(&self, table_name: &str, query: String) -> IndexerResult<Vec<T>> {
+        #[cfg(feature = "postgres")]
+        if let Some(pg_reader) = &self.pg_reader {
+            return pg_reader
+                .run_query_with_timeout(move |conn| diesel::sql_query(query).load::<T>(conn));
+        }
+        self.get_inner_indexer_reader(table_name)?
+            .run_query_with_timeout(move |conn| diesel::sql_query(query).load::<T>(conn))
+    }
+
     #[named]
     pub fn query_transactions_with_filter(
         &self,
@@ -200,55 +267,21 @@ impl IndexerReader {
         let tx_order = if let Some(cursor) = cursor {
             cursor as i64
         } else if descending_order {
-            let max_tx_order: i64 = self
-                .get_inner_indexer_reader(INDEXER_TRANSACTIONS_TABLE_NAME)?
-                .run_query_with_timeout(|conn| {
-                    transactions::dsl::transactions
-                        .select(transactions::tx_order)
-                        .order_by(transactions::tx_order.desc())
-                        .first::<i64>(conn)
-                })?;
+            let query = format!(
+                "SELECT {TX_ORDER_STR} FROM transactions ORDER BY {TX_ORDER_STR} DESC LIMIT 1"
+            );
+            let max_tx_order = self
+                .load_by_sql::<StoredTxOrder>(INDEXER_TRANSACTIONS_TABLE_NAME, query)?
+                .first()
+                .map(|v| v.tx_order)
+                .unwrap_or(-1);
             max_tx_order + 1
         } else {
             -1
         };
 
-        let main_where_clause = match filter {
-            TransactionFilter::Sender(sender) => {
-                format!("{TX_SENDER_STR} = \"{}\"", sender.to_hex_literal())
-            }
-            TransactionFilter::TxHashes(tx_hashes) => {
-                let in_tx_hash_str: String = tx_hashes
-                    .iter()
-                    .map(|tx_hash| format!("\"{:?}\"", tx_hash))
-                    .collect::<Vec<String>>()
-                    .join(",");
-                format!("{TX_HASH_STR} in ({})", in_tx_hash_str)
-            }
-            TransactionFilter::TimeRange {
-                start_time,
-                end_time,
-            } => {
-                format!(
-                    "({CREATED_AT_STR} >= {} AND {CREATED_AT_STR} < {})",
-                    start_time, end_time
-                )
-            }
-            TransactionFilter::TxOrderRange {
-                from_order,
-                to_order,
-            } => {
-                format!(
-                    "({TX_ORDER_STR} >= {} AND {TX_ORDER_STR} < {})",
-                    from_order, to_order
-                )
-            }
-            TransactionFilter::All => {
-                return Err(IndexerError::NotSupportedError(
-                    "Not implemented".to_string(),
-                ));
-            }
-        };
+        let main_where_clause = transaction_filter_where_clause(filter, 0)?
+            .ok_or_else(|| IndexerError::NotSupportedError("Not implemented".to_string()))?;
 
         let cursor_clause = if descending_order {
             format!("AND ({TX_ORDER_STR} < {})", tx_order)
@@ -264,7 +297,7 @@ impl IndexerReader {
         let query = format!(
             "
                 SELECT * FROM transactions \
-                WHERE {} {} \
+                WHERE ({}) {} \
                 ORDER BY {} \
                 LIMIT {}
             ",
@@ -272,11 +305,8 @@ impl IndexerReader {
         );
 
         tracing::debug!("Query transactions: {}", query);
-        let stored_transactions = self
-            .get_inner_indexer_reader(INDEXER_TRANSACTIONS_TABLE_NAME)?
-            .run_query_with_timeout(|conn| {
-                diesel::sql_query(query).load::<StoredTransaction>(conn)
-            })?;
+        let stored_transactions =
+            self.load_by_sql::<StoredTransaction>(INDEXER_TRANSACTIONS_TABLE_NAME, query)?;
 
         let result = stored_transactions
             .into_iter()
@@ -312,74 +342,21 @@ impl IndexerReader {
             } = cursor;
             (tx_order as i64, event_index as i64)
         } else if descending_order {
-            let (max_tx_order, event_index): (i64, i64) = self
-                .get_inner_indexer_reader(INDEXER_EVENTS_TABLE_NAME)?
-                .run_query_with_timeout(|conn| {
-                    events::dsl::events
-                        .select((events::tx_order, events::event_index))
-                        .order_by((events::tx_order.desc(), events::event_index.desc()))
-                        .first::<(i64, i64)>(conn)
-                })?;
+            let query = format!(
+                "SELECT {TX_ORDER_STR},{EVENT_INDEX_STR} FROM events ORDER BY {TX_ORDER_STR} DESC, {EVENT_INDEX_STR} DESC LIMIT 1"
+            );
+            let (max_tx_order, event_index) = self
+                .load_by_sql::<StoredEventID>(INDEXER_EVENTS_TABLE_NAME, query)?
+                .first()
+                .map(|v| (v.tx_order, v.event_index))
+                .unwrap_or((-1, -1));
             (max_tx_order, event_index + 1)
         } else {
             (-1, 0)
         };
 
-        let main_where_clause = match filter {
-            EventFilter::EventTypeWithSender { event_type, sender } => {
-                format!(
-                    "{TX_SENDER_STR} = \"{}\" AND {EVENT_TYPE_STR} = \"{}\"",
-                    sender.to_hex_literal(),
-                    event_type
-                )
-            }
-            EventFilter::EventType(event_type) => {
-                format!("{EVENT_TYPE_STR} = \"{}\"", event_type)
-            }
-            EventFilter::EventHandleWithSender {
-                event_handle_id,
-                sender,
-            } => {
-                format!(
-                    "{TX_SENDER_STR} = \"{}\" AND {EVENT_HANDLE_ID_STR} = \"{}\"",
-                    sender.to_hex_literal(),
-                    event_handle_id
-                )
-            }
-            EventFilter::EventHandle(event_handle_id) => {
-                format!("{EVENT_HANDLE_ID_STR} = \"{}\"", event_handle_id)
-            }
-            EventFilter::Sender(sender) => {
-                format!("{TX_SENDER_STR} = \"{}\"", sender.to_hex_literal())
-            }
-            EventFilter::TxHash(tx_hash) => {
-                let tx_hash_str = format!("{:?}", tx_hash);
-                format!("{TX_HASH_STR} = \"{}\"", tx_hash_str)
-            }
-            EventFilter::TimeRange {
-                start_time,
-                end_time,
-            } => {
-                format!(
-                    "({CREATED_AT_STR} >= {} AND {CREATED_AT_STR} < {})",
-                    start_time, end_time
-                )
-            }
-            EventFilter::TxOrderRange {
-                from_order,
-                to_order,
-            } => {
-                format!(
-                    "({TX_ORDER_STR} >= {} AND {TX_ORDER_STR} < {})",
-                    from_order, to_order
-                )
-            }
-            EventFilter::All => {
-                return Err(IndexerError::NotSupportedError(
-                    "Not implemented".to_string(),
-                ));
-            }
-        };
+        let main_where_clause = event_filter_where_clause(filter, 0)?
+            .ok_or_else(|| IndexerError::NotSupportedError("Not implemented".to_string()))?;
 
         let cursor_clause = if descending_order {
             format!(
@@ -401,7 +378,7 @@ impl IndexerReader {
         let query = format!(
             "
                 SELECT * FROM events \
-                WHERE {} {} \
+                WHERE ({}) {} \
                 ORDER BY {} \
                 LIMIT {}
             ",
@@ -409,9 +386,7 @@ impl IndexerReader {
         );
 
         tracing::debug!("Query events: {}", query);
-        let stored_events = self
-            .get_inner_indexer_reader(INDEXER_EVENTS_TABLE_NAME)?
-            .run_query_with_timeout(|conn| diesel::sql_query(query).load::<StoredEvent>(conn))?;
+        let stored_events = self.load_by_sql::<StoredEvent>(INDEXER_EVENTS_TABLE_NAME, query)?;
 
         let result = stored_events
             .into_iter()
@@ -457,65 +432,22 @@ impl IndexerReader {
             table_name
         );
 
-        let main_where_clause = match filter {
-            ObjectStateFilter::ObjectTypeWithOwner {
-                object_type,
-                owner,
-                filter_out,
-            } => {
-                match state_type {
-                    ObjectStateType::ObjectState => {
-                        let object_query = if filter_out {
-                            not_object_type_query(&object_type)
-                        } else {
-                            object_type_query(&object_type)
-                        };
-                        format!(
-                            "{STATE_OWNER_STR} = \"{}\" AND {}",
-                            owner.to_hex_literal(),
-                            object_query
-                        )
-                    }
-                    _ => {
-                        // Ignore object_type param for utxo and inscription query
-                        format!("{STATE_OWNER_STR} = \"{}\"", owner.to_hex_literal(),)
-                    }
-                }
-            }
-            ObjectStateFilter::ObjectType(object_type) => {
-                match state_type {
-                    ObjectStateType::ObjectState => object_type_query(&object_type),
-                    // Ignore object_type param for utxo and inscription query
-                    _ => " ".to_string(),
-                }
-            }
-
-            ObjectStateFilter::Owner(owner) => {
-                format!("{STATE_OWNER_STR} = \"{}\"", owner.to_hex_literal())
-            }
-            ObjectStateFilter::ObjectId(object_ids) => {
-                let object_ids_str = object_ids
-                    .into_iter()
-                    .map(|obj_id| format!("\"{}\"", obj_id))
-                    .collect::<Vec<_>>()
-                    .join(",");
-                format!("{OBJECT_ID_STR} IN ({object_ids_str})")
-            }
-        };
-
-        let has_main_where_clause = main_where_clause.ne(" ");
-        let cursor_clause_start = if has_main_where_clause { "AND" } else { " " };
         let cursor_clause = if descending_order {
             format!(
-                "{} ({TX_ORDER_STR} < {} OR ({TX_ORDER_STR} = {} AND {STATE_INDEX_STR} < {}))",
-                cursor_clause_start, tx_order, tx_order, state_index
+                "({TX_ORDER_STR} < {} OR ({TX_ORDER_STR} = {} AND {STATE_INDEX_STR} < {}))",
+                tx_order, tx_order, state_index
             )
         } else {
             format!(
-                "{} ({TX_ORDER_STR} > {} OR ({TX_ORDER_STR} = {} AND {STATE_INDEX_STR} > {}))",
-                cursor_clause_start, tx_order, tx_order, state_index
+                "({TX_ORDER_STR} > {} OR ({TX_ORDER_STR} = {} AND {STATE_INDEX_STR} > {}))",
+                tx_order, tx_order, state_index
             )
         };
+        let where_clause = match object_state_filter_where_clause(filter, &state_type, 0)? {
+            Some(main_where_clause) => format!("({}) AND {}", main_where_clause, cursor_clause),
+            // The filter does not constrain the rows of the table
+            None => cursor_clause,
+        };
         let order_clause = if descending_order {
             format!("{TX_ORDER_STR} DESC, {STATE_INDEX_STR} DESC")
         } else {
@@ -525,19 +457,16 @@ impl IndexerReader {
         let query = format!(
             "
                 {} \
-                WHERE {} {} \
+                WHERE {} \
                 ORDER BY {} \
                 LIMIT {}
             ",
-            select_clause, main_where_clause, cursor_clause, order_clause, limit,
+            select_clause, where_clause, order_clause, limit,
         );
 
         tracing::debug!("Query object states: {}", query);
-        let stored_object_state_infos = self
-            .get_inner_indexer_reader(table_name)?
-            .run_query_with_timeout(|conn| {
-                diesel::sql_query(query).load::<StoredObjectStateInfo>(conn)
-            })?;
+        let stored_object_state_infos =
+            self.load_by_sql::<StoredObjectStateInfo>(table_name, query)?;
 
         tracing::debug!("Query object states time elapsed: {:?}", start.elapsed());
         Ok(stored_object_state_infos)
@@ -592,9 +521,7 @@ impl IndexerReader {
         );
 
         tracing::debug!("query last indexer state id: {}", query);
-        let stored_state_ids = self
-            .get_inner_indexer_reader(table_name)?
-            .run_query_with_timeout(|conn| diesel::sql_query(query).load::<StoredStateID>(conn))?;
+        let stored_state_ids = self.load_by_sql::<StoredStateID>(table_name, query)?;
 
         let last_state_id = if stored_state_ids.is_empty() {
             None
@@ -613,7 +540,7 @@ impl IndexerReader {
         state_type: ObjectStateType,
     ) -> IndexerResult<Option<u64>> {
         let table_name = get_table_name_by_state_type(state_type);
-        let where_clause = format!("{TX_ORDER_STR} = \"{}\"", tx_order as i64);
+        let where_clause = format!("{TX_ORDER_STR} = {}", tx_order as i64);
         let order_clause = format!("{TX_ORDER_STR} DESC, {STATE_INDEX_STR} DESC");
         let query = format!(
             "
@@ -626,9 +553,7 @@ impl IndexerReader {
         );
 
         tracing::debug!("query last state index by tx order: {}", query);
-        let stored_state_ids = self
-            .get_inner_indexer_reader(table_name)?
-            .run_query_with_timeout(|conn| diesel::sql_query(query).load::<StoredStateID>(conn))?;
+        let stored_state_ids = self.load_by_sql::<StoredStateID>(table_name, query)?;
         let last_state_index = if stored_state_ids.is_empty() {
             None
         } else {
@@ -658,7 +583,7 @@ impl IndexerReader {
             FieldFilter::ObjectId(object_ids) => {
                 let object_ids_str = object_ids
                     .into_iter()
-                    .map(|obj_id| format!("\"{}\"", obj_id))
+                    .map(|obj_id| format!("'{}'", obj_id))
                     .collect::<Vec<_>>()
                     .join(",");
                 format!("{PARENT_OBJECT_ID_STR} IN ({object_ids_str})")
@@ -672,7 +597,7 @@ impl IndexerReader {
         };
         let mut start_limit = (page_of - 1) * (limit as u64);
         start_limit = start_limit.saturating_sub(1);
-        let page_clause = format!("{}, {}", start_limit, limit);
+        let page_clause = format!("{} OFFSET {}", limit, start_limit);
         let query = format!(
             "
                 SELECT * FROM fields \
@@ -684,9 +609,7 @@ impl IndexerReader {
         );
 
         tracing::debug!("Query fields: {}", query);
-        let stored_fields = self
-            .get_inner_indexer_reader(INDEXER_FIELDS_TABLE_NAME)?
-            .run_query_with_timeout(|conn| diesel::sql_query(query).load::<StoredField>(conn))?;
+        let stored_fields = self.load_by_sql::<StoredField>(INDEXER_FIELDS_TABLE_NAME, query)?;
 
         let result = stored_fields
             .into_iter()
@@ -701,11 +624,236 @@ impl IndexerReader {
     }
 }
 
+fn check_filter_depth(depth: usize) -> IndexerResult<()> {
+    if depth > MAX_FILTER_DEPTH {
+        return Err(IndexerError::NotSupportedError(format!(
+            "Filter nesting depth exceeds the maximum {}",
+            MAX_FILTER_DEPTH
+        )));
+    }
+    Ok(())
+}
+
+// `None` means the clause matches all the rows.
+fn and_where_clause(clauses: Vec<Option<String>>) -> Option<String> {
+    let clauses = clauses.into_iter().flatten().collect::<Vec<_>>();
+    if clauses.is_empty() {
+        None
+    } else {
+        Some(
+            clauses
+                .into_iter()
+                .map(|clause| format!("({})", clause))
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        )
+    }
+}
+
+fn or_where_clause(clauses: Vec<Option<String>>) -> Option<String> {
+    if clauses.is_empty() {
+        return Some("1 = 0".to_string());
+    }
+    let mut or_clauses = Vec::with_capacity(clauses.len());
+    for clause in clauses {
+        // Any clause matching all the rows makes the OR match all the rows
+        or_clauses.push(format!("({})", clause?));
+    }
+    Some(or_clauses.join(" OR "))
+}
+
+fn not_where_clause(clause: Option<String>) -> Option<String> {
+    match clause {
+        Some(clause) => Some(format!("NOT ({})", clause)),
+        None => Some("1 = 0".to_string()),
+    }
+}
+
+fn transaction_filter_where_clause(
+    filter: TransactionFilter,
+    depth: usize,
+) -> IndexerResult<Option<String>> {
+    check_filter_depth(depth)?;
+    Ok(match filter {
+        TransactionFilter::Sender(sender) => {
+            Some(format!("{TX_SENDER_STR} = '{}'", sender.to_hex_literal()))
+        }
+        TransactionFilter::TxHashes(tx_hashes) => {
+            let in_tx_hash_str: String = tx_hashes
+                .iter()
+                .map(|tx_hash| format!("'{:?}'", tx_hash))
+                .collect::<Vec<String>>()
+                .join(",");
+            Some(format!("{TX_HASH_STR} in ({})", in_tx_hash_str))
+        }
+        TransactionFilter::TimeRange {
+            start_time,
+            end_time,
+        } => Some(format!(
+            "({CREATED_AT_STR} >= {} AND {CREATED_AT_STR} < {})",
+            start_time, end_time
+        )),
+        TransactionFilter::TxOrderRange {
+            from_order,
+            to_order,
+        } => Some(format!(
+            "({TX_ORDER_STR} >= {} AND {TX_ORDER_STR} < {})",
+            from_order, to_order
+        )),
+        TransactionFilter::And(filters) => and_where_clause(
+            filters
+                .into_iter()
+                .map(|filter| transaction_filter_where_clause(filter, depth + 1))
+                .collect::<IndexerResult<Vec<_>>>()?,
+        ),
+        TransactionFilter::Or(filters) => or_where_clause(
+            filters
+                .into_iter()
+                .map(|filter| transaction_filter_where_clause(filter, depth + 1))
+                .collect::<IndexerResult<Vec<_>>>()?,
+        ),
+        TransactionFilter::Not(filter) => {
+            not_where_clause(transaction_filter_where_clause(*filter, depth + 1)?)
+        }
+        TransactionFilter::All => None,
+    })
+}
+
+fn event_filter_where_clause(filter: EventFilter, depth: usize) -> IndexerResult<Option<String>> {
+    check_filter_depth(depth)?;
+    Ok(match filter {
+        EventFilter::EventTypeWithSender { event_type, sender } => Some(format!(
+            "{TX_SENDER_STR} = '{}' AND {EVENT_TYPE_STR} = '{}'",
+            sender.to_hex_literal(),
+            event_type
+        )),
+        EventFilter::EventType(event_type) => Some(format!("{EVENT_TYPE_STR} = '{}'", event_type)),
+        EventFilter::EventHandleWithSender {
+            event_handle_id,
+            sender,
+        } => Some(format!(
+            "{TX_SENDER_STR} = '{}' AND {EVENT_HANDLE_ID_STR} = '{}'",
+            sender.to_hex_literal(),
+            event_handle_id
+        )),
+        EventFilter::EventHandle(event_handle_id) => {
+            Some(format!("{EVENT_HANDLE_ID_STR} = '{}'", event_handle_id))
+        }
+        EventFilter::Sender(sender) => {
+            Some(format!("{TX_SENDER_STR} = '{}'", sender.to_hex_literal()))
+        }
+        EventFilter::TxHash(tx_hash) => {
+            let tx_hash_str = format!("{:?}", tx_hash);
+            Some(format!("{TX_HASH_STR} = '{}'", tx_hash_str))
+        }
+        EventFilter::TimeRange {
+            start_time,
+            end_time,
+        } => Some(format!(
+            "({CREATED_AT_STR} >= {} AND {CREATED_AT_STR} < {})",
+            start_time, end_time
+        )),
+        EventFilter::TxOrderRange {
+            from_order,
+            to_order,
+        } => Some(format!(
+            "({TX_ORDER_STR} >= {} AND {TX_ORDER_STR} < {})",
+            from_order, to_order
+        )),
+        EventFilter::And(filters) => and_where_clause(
+            filters
+                .into_iter()
+                .map(|filter| event_filter_where_clause(filter, depth + 1))
+                .collect::<IndexerResult<Vec<_>>>()?,
+        ),
+        EventFilter::Or(filters) => or_where_clause(
+            filters
+                .into_iter()
+                .map(|filter| event_filter_where_clause(filter, depth + 1))
+                .collect::<IndexerResult<Vec<_>>>()?,
+        ),
+        EventFilter::Not(filter) => {
+            not_where_clause(event_filter_where_clause(*filter, depth + 1)?)
+        }
+        EventFilter::All => None,
+    })
+}
+
+fn object_state_filter_where_clause(
+    filter: ObjectStateFilter,
+    state_type: &ObjectStateType,
+    depth: usize,
+) -> IndexerResult<Option<String>> {
+    check_filter_depth(depth)?;
+    Ok(match filter {
+        ObjectStateFilter::ObjectTypeWithOwner {
+            object_type,
+            owner,
+            filter_out,
+        } => {
+            match state_type {
+                ObjectStateType::ObjectState => {
+                    let object_query = if filter_out {
+                        not_object_type_query(&object_type)
+                    } else {
+                        object_type_query(&object_type)
+                    };
+                    Some(format!(
+                        "{STATE_OWNER_STR} = '{}' AND {}",
+                        owner.to_hex_literal(),
+                        object_query
+                    ))
+                }
+                _ => {
+                    // Ignore object_type param for utxo and inscription query
+                    Some(format!("{STATE_OWNER_STR} = '{}'", owner.to_hex_literal()))
+                }
+            }
+        }
+        ObjectStateFilter::ObjectType(object_type) => {
+            match state_type {
+                ObjectStateType::ObjectState => Some(object_type_query(&object_type)),
+                // Ignore object_type param for utxo and inscription query
+                _ => None,
+            }
+        }
+        ObjectStateFilter::Owner(owner) => {
+            Some(format!("{STATE_OWNER_STR} = '{}'", owner.to_hex_literal()))
+        }
+        ObjectStateFilter::ObjectId(object_ids) => {
+            let object_ids_str = object_ids
+                .into_iter()
+                .map(|obj_id| format!("'{}'", obj_id))
+                .collect::<Vec<_>>()
+                .join(",");
+            Some(format!("{OBJECT_ID_STR} IN ({object_ids_str})"))
+        }
+        ObjectStateFilter::And(filters) => and_where_clause(
+            filters
+                .into_iter()
+                .map(|filter| object_state_filter_where_clause(filter, state_type, depth + 1))
+                .collect::<IndexerResult<Vec<_>>>()?,
+        ),
+        ObjectStateFilter::Or(filters) => or_where_clause(
+            filters
+                .into_iter()
+                .map(|filter| object_state_filter_where_clause(filter, state_type, depth + 1))
+                .collect::<IndexerResult<Vec<_>>>()?,
+        ),
+        ObjectStateFilter::Not(filter) => not_where_clause(object_state_filter_where_clause(
+            *filter,
+            state_type,
+            depth + 1,
+        )?),
+    })
+}
+
 fn get_table_name_by_state_type(state_type: ObjectStateType) -> IndexerTableName {
     match state_type {
         ObjectStateType::ObjectState => INDEXER_OBJECT_STATES_TABLE_NAME,
         ObjectStateType::UTXO => INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME,
         ObjectStateType::Inscription => INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME,
+        ObjectStateType::RuneBalances => INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME,
     }
 }
 fn object_type_query(object_type: &StructTag) -> String {
@@ -715,11 +863,11 @@ fn object_type_query(object_type: &StructTag) -> String {
         let (first_bound, second_bound, upper_bound) =
             optimize_object_type_like_query(object_type_str.as_str());
         format!(
-            "({STATE_OBJECT_TYPE_STR} = \"{}\" OR ({STATE_OBJECT_TYPE_STR} >= \"{}\" AND {STATE_OBJECT_TYPE_STR} < \"{}\" AND {STATE_OBJECT_TYPE_STR} < \"{}\"))",
+            "({STATE_OBJECT_TYPE_STR} = '{}' OR ({STATE_OBJECT_TYPE_STR} >= '{}' AND {STATE_OBJECT_TYPE_STR} < '{}' AND {STATE_OBJECT_TYPE_STR} < '{}'))",
             object_type_str, first_bound, second_bound, upper_bound
         )
     } else {
-        format!("{STATE_OBJECT_TYPE_STR} = \"{}\"", object_type_str)
+        format!("{STATE_OBJECT_TYPE_STR} = '{}'", object_type_str)
     }
 }
 
@@ -730,11 +878,11 @@ fn not_object_type_query(object_type: &StructTag) -> String {
         let (first_bound, second_bound, upper_bound) =
             optimize_object_type_like_query(object_type_str.as_str());
         format!(
-            "({STATE_OBJECT_TYPE_STR} != \"{}\" AND ({STATE_OBJECT_TYPE_STR} < \"{}\" OR {STATE_OBJECT_TYPE_STR} >= \"{}\" OR {STATE_OBJECT_TYPE_STR} >= \"{}\"))",
+            "({STATE_OBJECT_TYPE_STR} != '{}' AND ({STATE_OBJECT_TYPE_STR} < '{}' OR {STATE_OBJECT_TYPE_STR} >= '{}' OR {STATE_OBJECT_TYPE_STR} >= '{}'))",
             object_type_str, first_bound, second_bound, upper_bound
         )
     } else {
-        format!("{STATE_OBJECT_TYPE_STR} != \"{}\"", object_type_str)
+        format!("{STATE_OBJECT_TYPE_STR} != '{}'", object_type_str)
     }
 }
 
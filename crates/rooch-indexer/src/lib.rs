@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::store::metrics::IndexerDBMetrics;
+#[cfg(feature = "postgres")]
+use crate::store::pg_store::PgIndexerStore;
 use crate::store::sqlite_store::SqliteIndexerStore;
 use crate::store::traits::IndexerStoreTrait;
 use crate::utils::create_all_tables_if_not_exists;
@@ -36,6 +38,8 @@ pub mod errors;
 pub mod indexer_reader;
 pub mod metrics;
 pub mod models;
+#[cfg(feature = "postgres")]
+pub mod postgres;
 pub mod proxy;
 pub mod schema;
 pub mod store;
@@ -52,6 +56,7 @@ pub const INDEXER_EVENTS_TABLE_NAME: IndexerTableName = "events";
 pub const INDEXER_OBJECT_STATES_TABLE_NAME: IndexerTableName = "object_states";
 pub const INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME: IndexerTableName = "utxos";
 pub const INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME: IndexerTableName = "inscriptions";
+pub const INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME: IndexerTableName = "rune_balances";
 pub const INDEXER_TRANSACTIONS_TABLE_NAME: IndexerTableName = "transactions";
 pub const INDEXER_FIELDS_TABLE_NAME: IndexerTableName = "fields";
 
@@ -62,6 +67,7 @@ static INDEXER_VEC_TABLE_NAME: Lazy<Vec<IndexerTableName>> = Lazy::new(|| {
         INDEXER_OBJECT_STATES_TABLE_NAME,
         INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME,
         INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME,
+        INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME,
         INDEXER_TRANSACTIONS_TABLE_NAME,
         INDEXER_FIELDS_TABLE_NAME,
     ]
@@ -79,12 +85,61 @@ impl IndexerStoreMeta {
 pub type SqliteConnectionPool = diesel::r2d2::Pool<ConnectionManager<SqliteConnection>>;
 pub type SqlitePoolConnection = diesel::r2d2::PooledConnection<ConnectionManager<SqliteConnection>>;
 
+/// Forwards a store call to the PostgreSQL backend when the indexer runs on PostgreSQL.
+macro_rules! forward_to_pg_store {
+    ($self:ident, $method:ident($($arg:ident),*)) => {
+        #[cfg(feature = "postgres")]
+        if let Some(pg_store) = &$self.pg_store {
+            return pg_store.$method($($arg),*);
+        }
+    };
+}
+
 #[derive(Clone)]
 pub struct IndexerStore {
     pub sqlite_store_mapping: HashMap<String, SqliteIndexerStore>,
+    #[cfg(feature = "postgres")]
+    pub pg_store: Option<PgIndexerStore>,
 }
 
 impl IndexerStore {
+    /// Creates a store on the PostgreSQL database at `db_url` if it is given,
+    /// otherwise on the SQLite databases under `db_path`.
+    /// Only the PostgreSQL db url schemes are supported.
+    pub fn new_with_db_url(
+        db_path: PathBuf,
+        db_url: Option<&str>,
+        registry: &Registry,
+    ) -> Result<Self> {
+        match db_url {
+            Some(db_url) if is_postgres_url(db_url) => Self::new_postgres(db_url, registry),
+            Some(db_url) => Err(unsupported_db_url_error(db_url)),
+            None => Self::new(db_path, registry),
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn new_postgres(db_url: &str, registry: &Registry) -> Result<Self> {
+        let db_metrics = IndexerDBMetrics::get_or_init(registry).clone();
+        let pg_cp = postgres::new_pg_connection_pool(
+            db_url,
+            postgres::PgConnectionPoolConfig::default(),
+            false,
+        )?;
+        let mut connection = postgres::get_pg_pool_connection(&pg_cp)?;
+        postgres::create_all_pg_tables_if_not_exists(&mut connection)?;
+
+        Ok(Self {
+            sqlite_store_mapping: HashMap::new(),
+            pg_store: Some(PgIndexerStore::new(pg_cp, db_metrics)),
+        })
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn new_postgres(_db_url: &str, _registry: &Registry) -> Result<Self> {
+        Err(postgres_not_enabled_error())
+    }
+
     pub fn new(db_path: PathBuf, registry: &Registry) -> Result<Self> {
         if !db_path.exists() {
             std::fs::create_dir_all(&db_path)?;
@@ -109,6 +164,8 @@ impl IndexerStore {
 
         let store = Self {
             sqlite_store_mapping,
+            #[cfg(feature = "postgres")]
+            pg_store: None,
         };
         store.create_all_tables_if_not_exists()?;
         Ok(store)
@@ -146,11 +203,34 @@ pub fn new_sqlite_connection_pool(db_url: &str) -> Result<SqliteConnectionPool,
     new_sqlite_connection_pool_impl(db_url, None)
 }
 
+#[cfg(not(feature = "postgres"))]
+/// Returns true if the db url points to a PostgreSQL database.
+pub fn is_postgres_url(db_url: &str) -> bool {
+    db_url.starts_with("postgres://") || db_url.starts_with("postgresql://")
+}
+
+pub(crate) fn unsupported_db_url_error(db_url: &str) -> anyhow::Error {
+    let scheme = db_url
+        .split_once("://")
+        .map_or(db_url, |(scheme, _)| scheme);
+    anyhow::anyhow!(
+        "Unsupported indexer db url scheme `{}`, only postgres:// and postgresql:// are supported",
+        scheme
+    )
+}
+
+pub(crate) fn postgres_not_enabled_error() -> anyhow::Error {
+    anyhow::anyhow!(
+        "The indexer db url points to PostgreSQL, but rooch-indexer is built without the `postgres` feature"
+    )
+}
+
 impl IndexerStoreTrait for IndexerStore {
     fn apply_object_states(
         &self,
         object_state_change_set: IndexerObjectStateChangeSet,
     ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, apply_object_states(object_state_change_set));
         self.update_object_states_by_state_type(
             object_state_change_set.object_states,
             INDEXER_OBJECT_STATES_TABLE_NAME,
@@ -165,6 +245,11 @@ impl IndexerStoreTrait for IndexerStore {
             object_state_change_set.object_state_inscriptions,
             INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME,
             ObjectStateType::Inscription,
+        )?;
+        self.update_object_states_by_state_type(
+            object_state_change_set.object_state_rune_balances,
+            INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME,
+            ObjectStateType::RuneBalances,
         )
     }
 
@@ -172,11 +257,13 @@ impl IndexerStoreTrait for IndexerStore {
         &self,
         states: Vec<IndexerObjectState>,
     ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_or_update_object_states(states));
         self.get_sqlite_store(INDEXER_OBJECT_STATES_TABLE_NAME)?
             .persist_or_update_object_states(states)
     }
 
     fn delete_object_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_object_states(state_pks));
         self.get_sqlite_store(INDEXER_OBJECT_STATES_TABLE_NAME)?
             .delete_object_states(state_pks)
     }
@@ -185,11 +272,13 @@ impl IndexerStoreTrait for IndexerStore {
         &self,
         states: Vec<IndexerObjectState>,
     ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_or_update_object_state_utxos(states));
         self.get_sqlite_store(INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME)?
             .persist_or_update_object_state_utxos(states)
     }
 
     fn delete_object_state_utxos(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_object_state_utxos(state_pks));
         self.get_sqlite_store(INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME)?
             .delete_object_state_utxos(state_pks)
     }
@@ -198,54 +287,82 @@ impl IndexerStoreTrait for IndexerStore {
         &self,
         states: Vec<IndexerObjectState>,
     ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_or_update_object_state_inscriptions(states));
         self.get_sqlite_store(INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME)?
             .persist_or_update_object_state_inscriptions(states)
     }
 
     fn delete_object_state_inscriptions(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_object_state_inscriptions(state_pks));
         self.get_sqlite_store(INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME)?
             .delete_object_state_inscriptions(state_pks)
     }
 
+    fn persist_or_update_object_state_rune_balances(
+        &self,
+        states: Vec<IndexerObjectState>,
+    ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_or_update_object_state_rune_balances(states));
+        self.get_sqlite_store(INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME)?
+            .persist_or_update_object_state_rune_balances(states)
+    }
+
+    fn delete_object_state_rune_balances(
+        &self,
+        state_pks: Vec<String>,
+    ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_object_state_rune_balances(state_pks));
+        self.get_sqlite_store(INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME)?
+            .delete_object_state_rune_balances(state_pks)
+    }
+
     fn persist_transactions(
         &self,
         transactions: Vec<IndexerTransaction>,
     ) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_transactions(transactions));
         self.get_sqlite_store(INDEXER_TRANSACTIONS_TABLE_NAME)?
             .persist_transactions(transactions)
     }
 
     fn persist_events(&self, events: Vec<IndexerEvent>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_events(events));
         self.get_sqlite_store(INDEXER_EVENTS_TABLE_NAME)?
             .persist_events(events)
     }
 
     fn delete_transactions(&self, tx_orders: Vec<u64>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_transactions(tx_orders));
         self.get_sqlite_store(INDEXER_TRANSACTIONS_TABLE_NAME)?
             .delete_transactions(tx_orders)
     }
 
     fn delete_events(&self, tx_orders: Vec<u64>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_events(tx_orders));
         self.get_sqlite_store(INDEXER_EVENTS_TABLE_NAME)?
             .delete_events(tx_orders)
     }
 
     fn persist_or_update_fields(&self, fields: Vec<IndexerField>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, persist_or_update_fields(fields));
         self.get_sqlite_store(INDEXER_FIELDS_TABLE_NAME)?
             .persist_or_update_fields(fields)
     }
 
     fn delete_fields(&self, table_pks: Vec<String>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_fields(table_pks));
         self.get_sqlite_store(INDEXER_FIELDS_TABLE_NAME)?
             .delete_fields(table_pks)
     }
 
     fn delete_fields_by_parent_id(&self, ids: Vec<String>) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, delete_fields_by_parent_id(ids));
         self.get_sqlite_store(INDEXER_FIELDS_TABLE_NAME)?
             .delete_fields_by_parent_id(ids)
     }
 
     fn apply_fields(&self, mut field_changes: IndexerFieldChanges) -> Result<(), IndexerError> {
+        forward_to_pg_store!(self, apply_fields(field_changes));
         let mut fields_new_and_update = field_changes.new_fields;
         fields_new_and_update.append(&mut field_changes.update_fields);
         self.persist_or_update_fields(fields_new_and_update)?;
@@ -282,6 +399,12 @@ impl IndexerStore {
                 self.get_sqlite_store(table_name)?
                     .delete_object_state_inscriptions(object_state_change.remove_object_states)
             }
+            ObjectStateType::RuneBalances => {
+                self.get_sqlite_store(table_name)?
+                    .persist_or_update_object_state_rune_balances(object_states_new_and_update)?;
+                self.get_sqlite_store(table_name)?
+                    .delete_object_state_rune_balances(object_state_change.remove_object_states)
+            }
         }
     }
 }
@@ -76,3 +76,13 @@ impl StoredEvent {
         Ok(indexer_event)
     }
 }
+
+#[derive(Queryable, QueryableByName, Debug)]
+pub struct StoredEventID {
+    /// the tx order of this transaction.
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub tx_order: i64,
+    /// event index in the transaction events
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub event_index: i64,
+}
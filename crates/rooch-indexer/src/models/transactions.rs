@@ -76,6 +76,13 @@ impl TryFrom<StoredTransaction> for IndexerTransaction {
     }
 }
 
+#[derive(Queryable, QueryableByName, Debug)]
+pub struct StoredTxOrder {
+    /// The tx order of this transaction.
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub tx_order: i64,
+}
+
 pub fn escape_transaction(mut transaction: StoredTransaction) -> StoredTransaction {
     transaction.sender = escape_sql_string(transaction.sender.clone());
     transaction.status = escape_sql_string(transaction.status.clone());
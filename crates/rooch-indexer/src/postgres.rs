@@ -0,0 +1,434 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+//! PostgreSQL backend support for the indexer.
+//!
+//! Unlike the SQLite backend, which keeps every indexer table in its own database file,
+//! the PostgreSQL backend stores all tables in the single database given by the db url.
+
+use crate::errors::IndexerError;
+use crate::indexer_reader::DEFAULT_QUERY_TIMEOUT;
+use crate::models::events::StoredEvent;
+use crate::models::fields::StoredField;
+use crate::models::inscriptions::StoredInscription;
+use crate::models::rune_balances::StoredRuneBalances;
+use crate::models::states::StoredObjectState;
+use crate::models::transactions::StoredTransaction;
+use crate::models::utxos::StoredUTXO;
+use crate::schema::{
+    events, fields, inscriptions, object_states, rune_balances, transactions, utxos,
+};
+use crate::store::pg_store::PgIndexerStore;
+use crate::{
+    get_sqlite_pool_connection, new_sqlite_connection_pool, INDEXER_EVENTS_TABLE_NAME,
+    INDEXER_FIELDS_TABLE_NAME, INDEXER_OBJECT_STATES_TABLE_NAME,
+    INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME, INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME,
+    INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME, INDEXER_TRANSACTIONS_TABLE_NAME,
+};
+use anyhow::{anyhow, Result};
+use diesel::pg::PgConnection;
+use diesel::r2d2::ConnectionManager;
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use serde::{Deserialize, Serialize};
+use std::ops::DerefMut;
+use std::path::Path;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::time::timeout;
+use tracing::{debug, info};
+
+const PG_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations_pg");
+
+pub type PgConnectionPool = diesel::r2d2::Pool<ConnectionManager<PgConnection>>;
+pub type PgPoolConnection = diesel::r2d2::PooledConnection<ConnectionManager<PgConnection>>;
+
+/// The connection pool config of the PostgreSQL backend.
+///
+/// The PostgreSQL server limits the total connections of all clients, so the pool is
+/// smaller than the SQLite one, and idle connections are released back to the server.
+#[derive(Debug, Clone, Copy)]
+pub struct PgConnectionPoolConfig {
+    pool_size: u32,
+    min_idle: Option<u32>,
+    connection_timeout: Duration,
+    statement_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+}
+
+impl PgConnectionPoolConfig {
+    const DEFAULT_POOL_SIZE: u32 = 8;
+    const DEFAULT_READER_POOL_SIZE: u32 = 16;
+    const DEFAULT_MIN_IDLE: u32 = 1;
+    const DEFAULT_CONNECTION_TIMEOUT: u64 = 30; // second
+    const DEFAULT_IDLE_TIMEOUT: u64 = 600; // second
+    const DEFAULT_MAX_LIFETIME: u64 = 1800; // second
+
+    pub fn set_pool_size(&mut self, size: u32) {
+        self.pool_size = size;
+    }
+
+    pub fn set_min_idle(&mut self, min_idle: Option<u32>) {
+        self.min_idle = min_idle;
+    }
+
+    pub fn set_connection_timeout(&mut self, timeout: Duration) {
+        self.connection_timeout = timeout;
+    }
+
+    pub fn set_statement_timeout(&mut self, timeout: Duration) {
+        self.statement_timeout = timeout;
+    }
+
+    pub fn set_idle_timeout(&mut self, timeout: Option<Duration>) {
+        self.idle_timeout = timeout;
+    }
+
+    pub fn set_max_lifetime(&mut self, lifetime: Option<Duration>) {
+        self.max_lifetime = lifetime;
+    }
+
+    pub fn pool_config(read_only: bool) -> Self {
+        let env_u64 = |key: &str| std::env::var(key).ok().and_then(|s| s.parse::<u64>().ok());
+        let default_pool_size = if read_only {
+            Self::DEFAULT_READER_POOL_SIZE
+        } else {
+            Self::DEFAULT_POOL_SIZE
+        };
+        let pool_size = env_u64("PG_POOL_SIZE")
+            .map(|size| size as u32)
+            .unwrap_or(default_pool_size);
+        let min_idle = env_u64("PG_MIN_IDLE")
+            .map(|size| size as u32)
+            .unwrap_or(Self::DEFAULT_MIN_IDLE)
+            .min(pool_size);
+        let connection_timeout =
+            env_u64("PG_CONNECTION_TIMEOUT").unwrap_or(Self::DEFAULT_CONNECTION_TIMEOUT);
+        let statement_timeout = env_u64("PG_STATEMENT_TIMEOUT").unwrap_or(DEFAULT_QUERY_TIMEOUT);
+        let idle_timeout = env_u64("PG_IDLE_TIMEOUT").unwrap_or(Self::DEFAULT_IDLE_TIMEOUT);
+        let max_lifetime = env_u64("PG_MAX_LIFETIME").unwrap_or(Self::DEFAULT_MAX_LIFETIME);
+
+        Self {
+            pool_size,
+            min_idle: Some(min_idle),
+            connection_timeout: Duration::from_secs(connection_timeout),
+            statement_timeout: Duration::from_secs(statement_timeout),
+            // 0 disables the idle timeout and the max lifetime
+            idle_timeout: (idle_timeout > 0).then(|| Duration::from_secs(idle_timeout)),
+            max_lifetime: (max_lifetime > 0).then(|| Duration::from_secs(max_lifetime)),
+        }
+    }
+}
+
+impl Default for PgConnectionPoolConfig {
+    fn default() -> Self {
+        Self::pool_config(false)
+    }
+}
+
+pub fn new_pg_connection_pool(
+    db_url: &str,
+    config: PgConnectionPoolConfig,
+    read_only: bool,
+) -> Result<PgConnectionPool, IndexerError> {
+    let manager = ConnectionManager::<PgConnection>::new(db_url);
+    let connection_config = PgConnectionConfig {
+        read_only,
+        statement_timeout: config.statement_timeout,
+    };
+
+    diesel::r2d2::Pool::builder()
+        .max_size(config.pool_size)
+        .min_idle(config.min_idle)
+        .connection_timeout(config.connection_timeout)
+        .idle_timeout(config.idle_timeout)
+        .max_lifetime(config.max_lifetime)
+        .connection_customizer(Box::new(connection_config))
+        .build(manager)
+        .map_err(|e| {
+            IndexerError::PgConnectionPoolInitError(format!(
+                "Failed to initialize connection pool with error: {:?}",
+                e
+            ))
+        })
+}
+
+pub fn get_pg_pool_connection(pool: &PgConnectionPool) -> Result<PgPoolConnection, IndexerError> {
+    pool.get().map_err(|e| {
+        IndexerError::PgPoolConnectionError(format!(
+            "Failed to get connection from PostgreSQL connection pool with error: {:?}",
+            e
+        ))
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PgConnectionConfig {
+    read_only: bool,
+    statement_timeout: Duration,
+}
+
+impl diesel::r2d2::CustomizeConnection<PgConnection, diesel::r2d2::Error> for PgConnectionConfig {
+    fn on_acquire(&self, conn: &mut PgConnection) -> std::result::Result<(), diesel::r2d2::Error> {
+        use diesel::connection::SimpleConnection;
+
+        let mut settings = format!(
+            "SET statement_timeout = {};",
+            self.statement_timeout.as_millis()
+        );
+        if self.read_only {
+            settings.push_str("SET default_transaction_read_only = on;");
+        }
+        conn.batch_execute(&settings)
+            .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
+/// Creates all the tables in the PostgreSQL database by applying all pending migrations.
+pub fn create_all_pg_tables_if_not_exists(conn: &mut PgPoolConnection) -> Result<()> {
+    debug!("Indexer creates all tables in the postgres db ...");
+    conn.run_pending_migrations(PG_MIGRATIONS)
+        .map_err(|e| anyhow!("Failed to run postgres migrations {e}"))?;
+    debug!("Indexer creates all tables in the postgres db complete.");
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct PgInnerIndexerReader {
+    pub(crate) pool: PgConnectionPool,
+}
+
+impl PgInnerIndexerReader {
+    pub fn new_with_config(db_url: &str, config: PgConnectionPoolConfig) -> Result<Self> {
+        let pool = new_pg_connection_pool(db_url, config, true)?;
+        Ok(Self { pool })
+    }
+
+    pub fn get_connection(&self) -> Result<PgPoolConnection, IndexerError> {
+        get_pg_pool_connection(&self.pool)
+    }
+
+    pub fn run_query_with_timeout<T, E, F>(&self, query: F) -> Result<T, IndexerError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        E: From<diesel::result::Error> + std::error::Error + Send,
+        T: Send + 'static,
+    {
+        // The server side statement_timeout is also set on every connection,
+        // the client side timeout guards against a stuck connection.
+        let timeout_duration = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+        let mut connection = self.get_connection()?;
+
+        tokio::task::block_in_place(|| {
+            Handle::current().block_on(async move {
+                timeout(
+                    timeout_duration,
+                    tokio::task::spawn_blocking(move || {
+                        connection
+                            .deref_mut()
+                            .transaction(query)
+                            .map_err(|e| IndexerError::PgReadError(e.to_string()))
+                    }),
+                )
+                .await
+                .map_err(|e| IndexerError::PgAsyncReadError(e.to_string()))??
+            })
+        })
+    }
+}
+
+/// The number of rows copied per batch when migrating a SQLite index to PostgreSQL.
+pub const DEFAULT_MIGRATION_BATCH_SIZE: i64 = 5000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexerMigrationResult {
+    pub transactions: u64,
+    pub events: u64,
+    pub object_states: u64,
+    pub utxos: u64,
+    pub inscriptions: u64,
+    pub rune_balances: u64,
+    pub fields: u64,
+}
+
+/// Copies an existing SQLite index into a PostgreSQL database.
+///
+/// Rows are copied table by table in primary key order, and the writes are upserts,
+/// so an interrupted migration can simply be run again.
+pub fn migrate_sqlite_to_postgres(
+    sqlite_db_path: &Path,
+    pg_store: &PgIndexerStore,
+    batch_size: i64,
+) -> Result<IndexerMigrationResult> {
+    let sqlite_table_url = |table: &str| -> Result<String> {
+        sqlite_db_path
+            .join(table)
+            .to_str()
+            .map(|s| s.to_string())
+            .ok_or(anyhow!("Invalid indexer db path"))
+    };
+    let mut result = IndexerMigrationResult::default();
+
+    // transactions
+    {
+        let pool = new_sqlite_connection_pool(&sqlite_table_url(INDEXER_TRANSACTIONS_TABLE_NAME)?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut last_tx_order = -1i64;
+        loop {
+            let rows = transactions::table
+                .filter(transactions::tx_order.gt(last_tx_order))
+                .order_by(transactions::tx_order.asc())
+                .limit(batch_size)
+                .load::<StoredTransaction>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_tx_order = last.tx_order;
+            result.transactions += rows.len() as u64;
+            pg_store.upsert_stored_transactions(rows)?;
+        }
+        info!("Migrated {} transactions", result.transactions);
+    }
+
+    // events
+    {
+        let pool = new_sqlite_connection_pool(&sqlite_table_url(INDEXER_EVENTS_TABLE_NAME)?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut cursor = (-1i64, -1i64);
+        loop {
+            let rows = events::table
+                .filter(
+                    events::tx_order.gt(cursor.0).or(events::tx_order
+                        .eq(cursor.0)
+                        .and(events::event_index.gt(cursor.1))),
+                )
+                .order_by((events::tx_order.asc(), events::event_index.asc()))
+                .limit(batch_size)
+                .load::<StoredEvent>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            cursor = (last.tx_order, last.event_index);
+            result.events += rows.len() as u64;
+            pg_store.upsert_stored_events(rows)?;
+        }
+        info!("Migrated {} events", result.events);
+    }
+
+    // object states
+    {
+        let pool =
+            new_sqlite_connection_pool(&sqlite_table_url(INDEXER_OBJECT_STATES_TABLE_NAME)?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut last_id = String::new();
+        loop {
+            let rows = object_states::table
+                .filter(object_states::id.gt(last_id.clone()))
+                .order_by(object_states::id.asc())
+                .limit(batch_size)
+                .load::<StoredObjectState>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_id = last.id.clone();
+            result.object_states += rows.len() as u64;
+            pg_store.upsert_stored_object_states(rows)?;
+        }
+        info!("Migrated {} object states", result.object_states);
+    }
+
+    // utxos
+    {
+        let pool =
+            new_sqlite_connection_pool(&sqlite_table_url(INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME)?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut last_id = String::new();
+        loop {
+            let rows = utxos::table
+                .filter(utxos::id.gt(last_id.clone()))
+                .order_by(utxos::id.asc())
+                .limit(batch_size)
+                .load::<StoredUTXO>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_id = last.id.clone();
+            result.utxos += rows.len() as u64;
+            pg_store.upsert_stored_utxos(rows)?;
+        }
+        info!("Migrated {} utxos", result.utxos);
+    }
+
+    // inscriptions
+    {
+        let pool = new_sqlite_connection_pool(&sqlite_table_url(
+            INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME,
+        )?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut last_id = String::new();
+        loop {
+            let rows = inscriptions::table
+                .filter(inscriptions::id.gt(last_id.clone()))
+                .order_by(inscriptions::id.asc())
+                .limit(batch_size)
+                .load::<StoredInscription>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_id = last.id.clone();
+            result.inscriptions += rows.len() as u64;
+            pg_store.upsert_stored_inscriptions(rows)?;
+        }
+        info!("Migrated {} inscriptions", result.inscriptions);
+    }
+
+    // rune_balances, the table does not exist in the index created before runes
+    if sqlite_db_path
+        .join(INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME)
+        .exists()
+    {
+        let pool = new_sqlite_connection_pool(&sqlite_table_url(
+            INDEXER_OBJECT_STATE_RUNE_BALANCES_TABLE_NAME,
+        )?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut last_id = String::new();
+        loop {
+            let rows = rune_balances::table
+                .filter(rune_balances::id.gt(last_id.clone()))
+                .order_by(rune_balances::id.asc())
+                .limit(batch_size)
+                .load::<StoredRuneBalances>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_id = last.id.clone();
+            result.rune_balances += rows.len() as u64;
+            pg_store.upsert_stored_rune_balances(rows)?;
+        }
+        info!("Migrated {} rune balances", result.rune_balances);
+    }
+
+    // fields
+    {
+        let pool = new_sqlite_connection_pool(&sqlite_table_url(INDEXER_FIELDS_TABLE_NAME)?)?;
+        let mut conn = get_sqlite_pool_connection(&pool)?;
+        let mut last_id = String::new();
+        loop {
+            let rows = fields::table
+                .filter(fields::id.gt(last_id.clone()))
+                .order_by(fields::id.asc())
+                .limit(batch_size)
+                .load::<StoredField>(&mut conn)?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            last_id = last.id.clone();
+            result.fields += rows.len() as u64;
+            pg_store.upsert_stored_fields(rows)?;
+        }
+        info!("Migrated {} fields", result.fields);
+    }
+
+    Ok(result)
+}
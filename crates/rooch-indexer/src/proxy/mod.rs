@@ -5,8 +5,8 @@ use crate::actor::indexer::IndexerActor;
 use crate::actor::messages::{
     IndexerApplyObjectStatesMessage, IndexerDeleteAnyObjectStatesMessage, IndexerEventsMessage,
     IndexerPersistOrUpdateAnyObjectStatesMessage, IndexerRevertMessage, IndexerStatesMessage,
-    IndexerTransactionMessage, QueryIndexerEventsMessage, QueryIndexerFieldsMessage,
-    QueryIndexerObjectIdsMessage, QueryIndexerTransactionsMessage,
+    IndexerTransactionMessage, QueryIndexedTxOrderMessage, QueryIndexerEventsMessage,
+    QueryIndexerFieldsMessage, QueryIndexerObjectIdsMessage, QueryIndexerTransactionsMessage,
     QueryLastStateIndexByTxOrderMessage, UpdateIndexerMessage,
 };
 use crate::actor::reader_indexer::IndexerReaderActor;
@@ -200,6 +200,16 @@ impl IndexerProxy {
             .await?
     }
 
+    /// The last tx_order written to the table since the indexer started.
+    /// The messages sent to the indexer before are handled first.
+    pub async fn query_indexed_tx_order(&self, table_name: &str) -> Result<Option<u64>> {
+        self.actor
+            .send(QueryIndexedTxOrderMessage {
+                table_name: table_name.to_string(),
+            })
+            .await?
+    }
+
     pub async fn query_last_state_index_by_tx_order(
         &self,
         tx_order: u64,
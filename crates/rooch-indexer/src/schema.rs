@@ -50,6 +50,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    rune_balances (id) {
+        id -> Text,
+        owner -> Text,
+        tx_order -> BigInt,
+        state_index -> BigInt,
+        created_at -> BigInt,
+        updated_at -> BigInt,
+    }
+}
+
 diesel::table! {
     transactions (tx_order) {
         tx_order -> BigInt,
@@ -81,6 +92,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     object_states,
     utxos,
     inscriptions,
+    rune_balances,
     transactions,
     fields,
 );
@@ -2,5 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod metrics;
+#[cfg(feature = "postgres")]
+pub mod pg_store;
 pub mod sqlite_store;
 pub mod traits;
@@ -0,0 +1,447 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::{Context, IndexerError};
+use crate::models::events::StoredEvent;
+use crate::models::fields::StoredField;
+use crate::models::inscriptions::StoredInscription;
+use crate::models::rune_balances::StoredRuneBalances;
+use crate::models::states::StoredObjectState;
+use crate::models::transactions::StoredTransaction;
+use crate::models::utxos::StoredUTXO;
+use crate::postgres::{get_pg_pool_connection, PgConnectionPool};
+use crate::schema::{
+    events, fields, inscriptions, object_states, rune_balances, transactions, utxos,
+};
+use crate::store::metrics::IndexerDBMetrics;
+use crate::store::traits::IndexerStoreTrait;
+use anyhow::Result;
+use diesel::upsert::excluded;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use function_name::named;
+use rooch_types::indexer::event::IndexerEvent;
+use rooch_types::indexer::field::{IndexerField, IndexerFieldChanges};
+use rooch_types::indexer::state::{
+    IndexerObjectState, IndexerObjectStateChangeSet, IndexerObjectStateChanges,
+};
+use rooch_types::indexer::transaction::IndexerTransaction;
+use std::sync::Arc;
+
+/// PostgreSQL limits a statement to 65535 bind parameters,
+/// so batch writes are split into chunks of at most this many rows.
+const PG_WRITE_CHUNK_SIZE: usize = 4096;
+
+#[derive(Clone)]
+pub struct PgIndexerStore {
+    pub(crate) connection_pool: PgConnectionPool,
+    db_metrics: Arc<IndexerDBMetrics>,
+}
+
+impl PgIndexerStore {
+    pub fn new(connection_pool: PgConnectionPool, db_metrics: Arc<IndexerDBMetrics>) -> Self {
+        Self {
+            connection_pool,
+            db_metrics,
+        }
+    }
+
+    pub(crate) fn upsert_stored_object_states(
+        &self,
+        states: Vec<StoredObjectState>,
+    ) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in states.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(object_states::table)
+                .values(chunk)
+                .on_conflict(object_states::id)
+                .do_update()
+                .set((
+                    object_states::owner.eq(excluded(object_states::owner)),
+                    object_states::tx_order.eq(excluded(object_states::tx_order)),
+                    object_states::state_index.eq(excluded(object_states::state_index)),
+                    object_states::updated_at.eq(excluded(object_states::updated_at)),
+                ))
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write or update object states to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn upsert_stored_utxos(&self, states: Vec<StoredUTXO>) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in states.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(utxos::table)
+                .values(chunk)
+                .on_conflict(utxos::id)
+                .do_update()
+                .set((
+                    utxos::owner.eq(excluded(utxos::owner)),
+                    utxos::tx_order.eq(excluded(utxos::tx_order)),
+                    utxos::state_index.eq(excluded(utxos::state_index)),
+                    utxos::updated_at.eq(excluded(utxos::updated_at)),
+                ))
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write or update object state utxos to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn upsert_stored_inscriptions(
+        &self,
+        states: Vec<StoredInscription>,
+    ) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in states.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(inscriptions::table)
+                .values(chunk)
+                .on_conflict(inscriptions::id)
+                .do_update()
+                .set((
+                    inscriptions::owner.eq(excluded(inscriptions::owner)),
+                    inscriptions::tx_order.eq(excluded(inscriptions::tx_order)),
+                    inscriptions::state_index.eq(excluded(inscriptions::state_index)),
+                    inscriptions::updated_at.eq(excluded(inscriptions::updated_at)),
+                ))
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write or update object state inscriptions to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn upsert_stored_rune_balances(
+        &self,
+        states: Vec<StoredRuneBalances>,
+    ) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in states.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(rune_balances::table)
+                .values(chunk)
+                .on_conflict(rune_balances::id)
+                .do_update()
+                .set((
+                    rune_balances::owner.eq(excluded(rune_balances::owner)),
+                    rune_balances::tx_order.eq(excluded(rune_balances::tx_order)),
+                    rune_balances::state_index.eq(excluded(rune_balances::state_index)),
+                    rune_balances::updated_at.eq(excluded(rune_balances::updated_at)),
+                ))
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write or update object state rune_balances to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn upsert_stored_transactions(
+        &self,
+        transactions: Vec<StoredTransaction>,
+    ) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in transactions.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(transactions::table)
+                .values(chunk)
+                .on_conflict_do_nothing()
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write transactions to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn upsert_stored_events(
+        &self,
+        events: Vec<StoredEvent>,
+    ) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in events.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(events::table)
+                .values(chunk)
+                .on_conflict_do_nothing()
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write events to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn upsert_stored_fields(
+        &self,
+        fields: Vec<StoredField>,
+    ) -> Result<(), IndexerError> {
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        for chunk in fields.chunks(PG_WRITE_CHUNK_SIZE) {
+            diesel::insert_into(fields::table)
+                .values(chunk)
+                .on_conflict(fields::id)
+                .do_update()
+                .set((
+                    fields::parent_id.eq(excluded(fields::parent_id)),
+                    fields::field_key.eq(excluded(fields::field_key)),
+                    fields::sort_key.eq(excluded(fields::sort_key)),
+                    fields::updated_at.eq(excluded(fields::updated_at)),
+                ))
+                .execute(&mut connection)
+                .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+                .context("Failed to write or update fields to PostgreSQL")?;
+        }
+        Ok(())
+    }
+
+    fn update_object_state_changes(
+        &self,
+        mut object_state_change: IndexerObjectStateChanges,
+        persist: impl Fn(&Self, Vec<IndexerObjectState>) -> Result<(), IndexerError>,
+        delete: impl Fn(&Self, Vec<String>) -> Result<(), IndexerError>,
+    ) -> Result<(), IndexerError> {
+        let mut object_states_new_and_update = object_state_change.new_object_states;
+        object_states_new_and_update.append(&mut object_state_change.update_object_states);
+        persist(self, object_states_new_and_update)?;
+        delete(self, object_state_change.remove_object_states)
+    }
+
+    fn start_timer(&self, fn_name: &str) -> prometheus::HistogramTimer {
+        self.db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer()
+    }
+}
+
+impl IndexerStoreTrait for PgIndexerStore {
+    fn apply_object_states(
+        &self,
+        object_state_change_set: IndexerObjectStateChangeSet,
+    ) -> Result<(), IndexerError> {
+        self.update_object_state_changes(
+            object_state_change_set.object_states,
+            Self::persist_or_update_object_states,
+            Self::delete_object_states,
+        )?;
+        self.update_object_state_changes(
+            object_state_change_set.object_state_utxos,
+            Self::persist_or_update_object_state_utxos,
+            Self::delete_object_state_utxos,
+        )?;
+        self.update_object_state_changes(
+            object_state_change_set.object_state_inscriptions,
+            Self::persist_or_update_object_state_inscriptions,
+            Self::delete_object_state_inscriptions,
+        )?;
+        self.update_object_state_changes(
+            object_state_change_set.object_state_rune_balances,
+            Self::persist_or_update_object_state_rune_balances,
+            Self::delete_object_state_rune_balances,
+        )
+    }
+
+    #[named]
+    fn persist_or_update_object_states(
+        &self,
+        states: Vec<IndexerObjectState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_object_states(states.into_iter().map(StoredObjectState::from).collect())
+    }
+
+    #[named]
+    fn delete_object_states(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        diesel::delete(object_states::table.filter(object_states::id.eq_any(state_pks)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete object states to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn persist_or_update_object_state_utxos(
+        &self,
+        states: Vec<IndexerObjectState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_utxos(states.into_iter().map(StoredUTXO::from).collect())
+    }
+
+    #[named]
+    fn delete_object_state_utxos(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        diesel::delete(utxos::table.filter(utxos::id.eq_any(state_pks)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete object state utxos to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn persist_or_update_object_state_inscriptions(
+        &self,
+        states: Vec<IndexerObjectState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_inscriptions(states.into_iter().map(StoredInscription::from).collect())
+    }
+
+    #[named]
+    fn delete_object_state_inscriptions(&self, state_pks: Vec<String>) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        diesel::delete(inscriptions::table.filter(inscriptions::id.eq_any(state_pks)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete object state inscriptions to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn persist_or_update_object_state_rune_balances(
+        &self,
+        states: Vec<IndexerObjectState>,
+    ) -> Result<(), IndexerError> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_rune_balances(states.into_iter().map(StoredRuneBalances::from).collect())
+    }
+
+    #[named]
+    fn delete_object_state_rune_balances(
+        &self,
+        state_pks: Vec<String>,
+    ) -> Result<(), IndexerError> {
+        if state_pks.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        diesel::delete(rune_balances::table.filter(rune_balances::id.eq_any(state_pks)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete object state rune_balances to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn persist_transactions(
+        &self,
+        transactions: Vec<IndexerTransaction>,
+    ) -> Result<(), IndexerError> {
+        if transactions.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_transactions(
+            transactions
+                .into_iter()
+                .map(StoredTransaction::from)
+                .collect(),
+        )
+    }
+
+    #[named]
+    fn persist_events(&self, events: Vec<IndexerEvent>) -> Result<(), IndexerError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_events(events.into_iter().map(StoredEvent::from).collect())
+    }
+
+    #[named]
+    fn delete_transactions(&self, tx_orders: Vec<u64>) -> Result<(), IndexerError> {
+        if tx_orders.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        let tx_orders: Vec<_> = tx_orders.into_iter().map(|v| v as i64).collect();
+        diesel::delete(transactions::table.filter(transactions::tx_order.eq_any(tx_orders)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete transactions to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn delete_events(&self, tx_orders: Vec<u64>) -> Result<(), IndexerError> {
+        if tx_orders.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        let tx_orders: Vec<_> = tx_orders.into_iter().map(|v| v as i64).collect();
+        diesel::delete(events::table.filter(events::tx_order.eq_any(tx_orders)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete events to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn persist_or_update_fields(&self, fields: Vec<IndexerField>) -> Result<(), IndexerError> {
+        if fields.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        self.upsert_stored_fields(fields.into_iter().map(StoredField::from).collect())
+    }
+
+    #[named]
+    fn delete_fields(&self, field_pks: Vec<String>) -> Result<(), IndexerError> {
+        if field_pks.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        diesel::delete(fields::table.filter(fields::id.eq_any(field_pks)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete fields to PostgreSQL")?;
+        Ok(())
+    }
+
+    #[named]
+    fn delete_fields_by_parent_id(&self, ids: Vec<String>) -> Result<(), IndexerError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let _timer = self.start_timer(function_name!());
+        let mut connection = get_pg_pool_connection(&self.connection_pool)?;
+        diesel::delete(fields::table.filter(fields::parent_id.eq_any(ids)))
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::PgWriteError(e.to_string()))
+            .context("Failed to delete fields to PostgreSQL")?;
+        Ok(())
+    }
+
+    fn apply_fields(&self, mut field_changes: IndexerFieldChanges) -> Result<(), IndexerError> {
+        let mut fields_new_and_update = field_changes.new_fields;
+        fields_new_and_update.append(&mut field_changes.update_fields);
+        self.persist_or_update_fields(fields_new_and_update)?;
+        self.delete_fields(field_changes.remove_fields)?;
+        self.delete_fields_by_parent_id(field_changes.remove_fields_by_parent_id)
+    }
+}
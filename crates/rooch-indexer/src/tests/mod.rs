@@ -3,3 +3,5 @@
 
 mod test_concurrence;
 mod test_indexer;
+#[cfg(feature = "postgres")]
+mod test_pg_indexer;
@@ -17,7 +17,7 @@ use rand::random;
 use rooch_config::store_config::DEFAULT_DB_INDEXER_SUBDIR;
 use rooch_types::framework::coin_store::CoinStore;
 use rooch_types::framework::gas_coin::RGas;
-use rooch_types::indexer::event::{EventFilter, IndexerEvent};
+use rooch_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
 use rooch_types::indexer::field::FieldFilter;
 use rooch_types::indexer::state::{IndexerObjectState, ObjectStateFilter, ObjectStateType};
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
@@ -105,6 +105,62 @@ async fn test_event_store() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_composite_event_filter() -> Result<()> {
+    let registry_service = RegistryService::default();
+    let tmpdir = moveos_config::temp_dir();
+    let indexer_db = tmpdir.path().join(DEFAULT_DB_INDEXER_SUBDIR);
+    let indexer_store =
+        IndexerStore::new(indexer_db.clone(), &registry_service.default_registry())?;
+    let indexer_reader = IndexerReader::new(indexer_db, &registry_service.default_registry())?;
+
+    let senders = (0..3).map(|_| AccountAddress::random()).collect::<Vec<_>>();
+    let mut events = vec![];
+    for tx_order in 0..6u64 {
+        let mut indexer_event = IndexerEvent::new(
+            random_event(),
+            random_ledger_transaction(),
+            TxContext::new_readonly_ctx(senders[tx_order as usize % 3]),
+        );
+        indexer_event.indexer_event_id = IndexerEventID::new(tx_order, 0);
+        indexer_event.created_at = tx_order;
+        events.push(indexer_event);
+    }
+    indexer_store.persist_events(events)?;
+
+    // The OR must not escape the cursor clause of the pagination
+    let filter = EventFilter::Or(vec![
+        EventFilter::Sender(senders[0]),
+        EventFilter::Sender(senders[1]),
+    ]);
+    let mut cursor = None;
+    let mut tx_orders = vec![];
+    loop {
+        let page = indexer_reader.query_events_with_filter(filter.clone(), cursor, 1, false)?;
+        if page.is_empty() {
+            break;
+        }
+        cursor = Some(page[0].indexer_event_id);
+        tx_orders.push(page[0].indexer_event_id.tx_order);
+    }
+    assert_eq!(tx_orders, vec![0, 1, 3, 4]);
+
+    let filter = EventFilter::And(vec![
+        EventFilter::TimeRange {
+            start_time: 1,
+            end_time: 6,
+        },
+        EventFilter::Not(Box::new(EventFilter::Sender(senders[1]))),
+    ]);
+    let query_events = indexer_reader.query_events_with_filter(filter, None, 10, true)?;
+    let tx_orders = query_events
+        .iter()
+        .map(|event| event.indexer_event_id.tx_order)
+        .collect::<Vec<_>>();
+    assert_eq!(tx_orders, vec![5, 3, 2]);
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_state_store() -> Result<()> {
     let registry_service = RegistryService::default();
@@ -298,3 +354,27 @@ async fn test_field_store() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_unsupported_db_url() -> Result<()> {
+    let registry_service = RegistryService::default();
+    let tmpdir = moveos_config::temp_dir();
+    let indexer_db = tmpdir.path().join(DEFAULT_DB_INDEXER_SUBDIR);
+    let db_url = "mysql://root@localhost:3306/rooch";
+
+    let store_err = IndexerStore::new_with_db_url(
+        indexer_db.clone(),
+        Some(db_url),
+        &registry_service.default_registry(),
+    )
+    .unwrap_err();
+    assert!(store_err.to_string().contains("`mysql`"));
+    let reader_result = IndexerReader::new_with_db_url(
+        indexer_db,
+        Some(db_url),
+        &registry_service.default_registry(),
+    );
+    assert!(reader_result.is_err());
+    assert!(crate::is_postgres_url("postgresql://localhost/rooch"));
+    Ok(())
+}
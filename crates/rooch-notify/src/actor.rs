@@ -90,6 +90,8 @@ impl Handler<ProcessTxWithEventsMessage> for NotifyActor {
             message.tx,
             message.events,
             message.ctx,
+            message.state_change_set,
+            message.previous_owners,
         )?;
         Ok(())
     }
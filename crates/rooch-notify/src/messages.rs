@@ -2,11 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use coerce::actor::message::Message;
+use move_core_types::account_address::AccountAddress;
 use moveos_eventbus::bus::EventNotifier;
 use moveos_types::moveos_std::event::Event;
+use moveos_types::moveos_std::object::ObjectID;
 use moveos_types::moveos_std::tx_context::TxContext;
+use moveos_types::state::StateChangeSet;
 use rooch_types::service_status::ServiceStatus;
 use rooch_types::transaction::TransactionWithInfo;
+use std::collections::BTreeMap;
 
 #[derive(Default, Clone, Debug)]
 pub struct GasUpgradeMessage {}
@@ -53,6 +57,9 @@ pub struct ProcessTxWithEventsMessage {
     pub tx: TransactionWithInfo,
     pub events: Vec<Event>,
     pub ctx: TxContext,
+    pub state_change_set: StateChangeSet,
+    /// The owners of the changed objects before the tx
+    pub previous_owners: BTreeMap<ObjectID, AccountAddress>,
 }
 
 impl Message for ProcessTxWithEventsMessage {
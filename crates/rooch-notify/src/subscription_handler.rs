@@ -5,19 +5,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::streamer::Streamer;
+use move_core_types::account_address::AccountAddress;
 use moveos_types::moveos_std::event::Event;
+use moveos_types::moveos_std::object::ObjectID;
 use moveos_types::moveos_std::tx_context::TxContext;
+use moveos_types::state::StateChangeSet;
 use prometheus::{
     register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
     IntGaugeVec, Registry,
 };
+use rooch_rpc_api::jsonrpc_types::btc::reorg::{BitcoinReorgEventView, BitcoinReorgFilterView};
 use rooch_rpc_api::jsonrpc_types::event_view::{EventFilterView, IndexerEventView};
 use rooch_rpc_api::jsonrpc_types::transaction_view::{
     TransactionFilterView, TransactionWithInfoView,
 };
+use rooch_rpc_api::jsonrpc_types::{ObjectStateChangeView, ObjectStateFilterView};
+use rooch_types::bitcoin::pending_block::BlockReorgEvent;
 use rooch_types::indexer::event::IndexerEvent;
 use rooch_types::transaction::TransactionWithInfo;
 use tokio_stream::Stream;
@@ -25,6 +32,8 @@ use tracing::{error, trace};
 
 pub const TRANSACTION_DISPATCH_BUFFER_SIZE: usize = 1000;
 pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 5000;
+pub const OBJECT_CHANGE_DISPATCH_BUFFER_SIZE: usize = 5000;
+pub const BITCOIN_REORG_DISPATCH_BUFFER_SIZE: usize = 100;
 
 pub struct SubscriptionMetrics {
     pub streaming_success: IntCounterVec,
@@ -72,6 +81,10 @@ pub struct SubscriptionHandler {
     event_streamer: Streamer<IndexerEventView, IndexerEventView, EventFilterView>,
     transaction_streamer:
         Streamer<TransactionWithInfoView, TransactionWithInfoView, TransactionFilterView>,
+    object_change_streamer:
+        Streamer<ObjectStateChangeView, ObjectStateChangeView, ObjectStateFilterView>,
+    bitcoin_reorg_streamer:
+        Streamer<BitcoinReorgEventView, BitcoinReorgEventView, BitcoinReorgFilterView>,
 }
 
 impl SubscriptionHandler {
@@ -79,7 +92,21 @@ impl SubscriptionHandler {
         let metrics = Arc::new(SubscriptionMetrics::new(registry));
         Self {
             event_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE, metrics.clone(), "event"),
-            transaction_streamer: Streamer::spawn(TRANSACTION_DISPATCH_BUFFER_SIZE, metrics, "tx"),
+            transaction_streamer: Streamer::spawn(
+                TRANSACTION_DISPATCH_BUFFER_SIZE,
+                metrics.clone(),
+                "tx",
+            ),
+            object_change_streamer: Streamer::spawn(
+                OBJECT_CHANGE_DISPATCH_BUFFER_SIZE,
+                metrics.clone(),
+                "object_change",
+            ),
+            bitcoin_reorg_streamer: Streamer::spawn(
+                BITCOIN_REORG_DISPATCH_BUFFER_SIZE,
+                metrics,
+                "bitcoin_reorg",
+            ),
         }
     }
 }
@@ -90,6 +117,8 @@ impl SubscriptionHandler {
         tx: TransactionWithInfo,
         events: Vec<Event>,
         ctx: TxContext,
+        state_change_set: StateChangeSet,
+        previous_owners: BTreeMap<ObjectID, AccountAddress>,
     ) -> Result<()> {
         if tracing::enabled!(tracing::Level::TRACE) {
             trace!(
@@ -99,11 +128,24 @@ impl SubscriptionHandler {
             );
         }
 
+        let tx_order = tx.transaction.sequence_info.tx_order;
         let tx_view = TransactionWithInfoView::new_from_transaction_with_info(tx.clone(), None);
         if let Err(e) = self.transaction_streamer.try_send(tx_view) {
             error!("Failed to send transaction to dispatch: {:?}", e);
         }
 
+        for event in events.iter().filter(|event| event.is::<BlockReorgEvent>()) {
+            match event.decode_event::<BlockReorgEvent>() {
+                Ok(reorg_event) => {
+                    let reorg_view = BitcoinReorgEventView::new(tx_order, reorg_event);
+                    if let Err(e) = self.bitcoin_reorg_streamer.try_send(reorg_view) {
+                        error!("Failed to send bitcoin reorg to dispatch: {:?}", e);
+                    }
+                }
+                Err(e) => error!("Failed to decode bitcoin reorg event: {:?}", e),
+            }
+        }
+
         // serially dispatch event processing to normal events' orders.
         let indexer_events = events
             .into_iter()
@@ -117,6 +159,16 @@ impl SubscriptionHandler {
                 error!("Failed to send event to dispatch: {:?}", e);
             }
         }
+
+        for object_change in ObjectStateChangeView::from_state_change_set(
+            tx_order,
+            state_change_set,
+            &previous_owners,
+        ) {
+            if let Err(e) = self.object_change_streamer.try_send(object_change) {
+                error!("Failed to send object change to dispatch: {:?}", e);
+            }
+        }
         Ok(())
     }
 
@@ -135,4 +187,18 @@ impl SubscriptionHandler {
         // ) -> ReceiverStream<TransactionWithInfoView> {
         self.transaction_streamer.subscribe(filter)
     }
+
+    pub fn subscribe_object_changes(
+        &self,
+        filter: ObjectStateFilterView,
+    ) -> impl Stream<Item = ObjectStateChangeView> {
+        self.object_change_streamer.subscribe(filter)
+    }
+
+    pub fn subscribe_bitcoin_reorgs(
+        &self,
+        filter: BitcoinReorgFilterView,
+    ) -> impl Stream<Item = BitcoinReorgEventView> {
+        self.bitcoin_reorg_streamer.subscribe(filter)
+    }
 }
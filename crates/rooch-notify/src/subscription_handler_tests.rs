@@ -3,21 +3,34 @@
 
 use crate::subscription_handler::SubscriptionHandler;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::Op;
+use move_core_types::language_storage::TypeTag;
 use move_core_types::value::MoveTypeLayout;
 use move_core_types::{ident_str, language_storage::StructTag};
+use moveos_types::h256::H256;
 use moveos_types::move_std::string::MoveString;
+use moveos_types::moveos_std::event::{Event, EventID};
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::moveos_std::tx_context::TxContext;
-use moveos_types::state::MoveStructState;
+use moveos_types::state::{
+    MoveState, MoveStructState, MoveStructType, ObjectChange, StateChangeSet,
+};
 use moveos_types::test_utils::random_event;
 use moveos_types::transaction::TransactionExecutionInfo;
 use prometheus::Registry;
+use rooch_rpc_api::jsonrpc_types::btc::reorg::BitcoinReorgFilterView;
 use rooch_rpc_api::jsonrpc_types::event_view::EventFilterView;
 use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionFilterView;
-use rooch_rpc_api::jsonrpc_types::StrView;
+use rooch_rpc_api::jsonrpc_types::{
+    ObjectStateChangeTypeView, ObjectStateFilterView, RoochAddressView, StrView, UnitedAddressView,
+};
+use rooch_types::bitcoin::pending_block::BlockReorgEvent;
+use rooch_types::bitcoin::types::BlockHeightHash;
 use rooch_types::test_utils::random_ledger_transaction;
 use rooch_types::transaction::TransactionWithInfo;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tokio::time::timeout;
 use tokio_stream::StreamExt;
@@ -92,7 +105,15 @@ async fn test_event_subscription() {
     // let events = vec![test_event1, test_event2, test_event3];
 
     // Process the transaction with events
-    handler.process_tx_with_events(tx, events, ctx).unwrap();
+    handler
+        .process_tx_with_events(
+            tx,
+            events,
+            ctx,
+            StateChangeSet::new(H256::random(), 0),
+            BTreeMap::new(),
+        )
+        .unwrap();
 
     // Try to receive the event with a timeout
     let received_event = timeout(Duration::from_secs(1), event_stream.next()).await;
@@ -123,7 +144,13 @@ async fn test_transaction_subscription() {
     let ctx = TxContext::random_for_testing_only();
 
     handler
-        .process_tx_with_events(tx.clone(), vec![], ctx)
+        .process_tx_with_events(
+            tx.clone(),
+            vec![],
+            ctx,
+            StateChangeSet::new(H256::random(), 0),
+            BTreeMap::new(),
+        )
         .unwrap();
 
     // Try to receive the transaction with a timeout
@@ -170,7 +197,15 @@ async fn test_multiple_subscribers() {
     let event3 = random_event();
     let events = vec![event1, event2, event3];
 
-    handler.process_tx_with_events(tx, events, ctx).unwrap();
+    handler
+        .process_tx_with_events(
+            tx,
+            events,
+            ctx,
+            StateChangeSet::new(H256::random(), 0),
+            BTreeMap::new(),
+        )
+        .unwrap();
 
     // Both streams should receive the event
     let received1 = timeout(Duration::from_secs(1), stream1.next()).await;
@@ -182,6 +217,189 @@ async fn test_multiple_subscribers() {
     );
 }
 
+#[tokio::test]
+async fn test_object_change_subscription() {
+    let registry = Registry::new();
+    let handler = SubscriptionHandler::new(&registry);
+
+    let object_type = TypeTag::Struct(Box::new(TestEvent::type_layout()));
+    let new_object_id = ObjectID::random();
+    let removed_object_id = ObjectID::random();
+    let parent_object_id = ObjectID::random();
+    let other_object_id = ObjectID::random();
+
+    // Subscribe to the changes of three objects
+    let object_filter = ObjectStateFilterView::ObjectId(StrView(vec![
+        new_object_id.clone(),
+        removed_object_id.clone(),
+        parent_object_id.clone(),
+    ]));
+    let mut object_stream = handler.subscribe_object_changes(object_filter);
+
+    let mut state_change_set = StateChangeSet::new(H256::random(), 0);
+    for (object_id, op) in [
+        (new_object_id.clone(), Op::New(vec![1u8])),
+        (removed_object_id.clone(), Op::Delete),
+        (other_object_id, Op::Modify(vec![2u8])),
+    ] {
+        let metadata = ObjectMeta::genesis_meta(object_id, object_type.clone());
+        state_change_set
+            .add_change(ObjectChange::new(metadata, op))
+            .unwrap();
+    }
+    // Only the metadata is changed, e.g. the fields of the object are changed
+    state_change_set
+        .add_change(ObjectChange::meta(ObjectMeta::genesis_meta(
+            parent_object_id.clone(),
+            object_type.clone(),
+        )))
+        .unwrap();
+
+    let ledger_tx = random_ledger_transaction();
+    let tx_execution_info = TransactionExecutionInfo::random();
+    let tx = TransactionWithInfo::new(ledger_tx, tx_execution_info);
+    let tx_order = tx.transaction.sequence_info.tx_order;
+    let ctx = TxContext::random_for_testing_only();
+
+    handler
+        .process_tx_with_events(tx, vec![], ctx, state_change_set, BTreeMap::new())
+        .unwrap();
+
+    let mut received = vec![];
+    for _ in 0..3 {
+        let change = timeout(Duration::from_secs(1), object_stream.next())
+            .await
+            .expect("Should receive object change within timeout")
+            .unwrap();
+        assert_eq!(change.tx_order.0, tx_order);
+        if change.change_type != ObjectStateChangeTypeView::New {
+            assert!(change.value.is_none());
+        }
+        received.push((change.metadata.id, change.change_type));
+    }
+    received.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut expected = vec![
+        (new_object_id, ObjectStateChangeTypeView::New),
+        (removed_object_id, ObjectStateChangeTypeView::Removed),
+        (
+            parent_object_id,
+            ObjectStateChangeTypeView::MetadataModified,
+        ),
+    ];
+    expected.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(received, expected);
+
+    // The change of the other object is filtered out
+    let unexpected = timeout(Duration::from_millis(100), object_stream.next()).await;
+    assert!(
+        unexpected.is_err(),
+        "Should not receive the change of other objects"
+    );
+}
+
+#[tokio::test]
+async fn test_object_change_subscription_by_previous_owner() {
+    let registry = Registry::new();
+    let handler = SubscriptionHandler::new(&registry);
+
+    let object_type = TypeTag::Struct(Box::new(TestEvent::type_layout()));
+    let previous_owner = AccountAddress::random();
+    let new_owner = AccountAddress::random();
+    let owner_filter = |owner: AccountAddress| {
+        ObjectStateFilterView::Owner(UnitedAddressView::from(RoochAddressView::from(owner)))
+    };
+    let mut previous_owner_stream = handler.subscribe_object_changes(owner_filter(previous_owner));
+    let mut new_owner_stream = handler.subscribe_object_changes(owner_filter(new_owner));
+
+    // The object is transferred from the previous owner to the new owner
+    let object_id = ObjectID::random();
+    let mut metadata = ObjectMeta::genesis_meta(object_id.clone(), object_type);
+    metadata.owner = new_owner;
+    let mut state_change_set = StateChangeSet::new(H256::random(), 0);
+    state_change_set
+        .add_change(ObjectChange::meta(metadata))
+        .unwrap();
+    let previous_owners = BTreeMap::from([(object_id.clone(), previous_owner)]);
+
+    let ledger_tx = random_ledger_transaction();
+    let tx_execution_info = TransactionExecutionInfo::random();
+    let tx = TransactionWithInfo::new(ledger_tx, tx_execution_info);
+    let ctx = TxContext::random_for_testing_only();
+    handler
+        .process_tx_with_events(tx, vec![], ctx, state_change_set, previous_owners)
+        .unwrap();
+
+    for stream in [&mut previous_owner_stream, &mut new_owner_stream] {
+        let change = timeout(Duration::from_secs(1), stream.next())
+            .await
+            .expect("Should receive object change within timeout")
+            .unwrap();
+        assert_eq!(change.metadata.id, object_id);
+        assert_eq!(change.metadata.owner.0, new_owner.into());
+        assert_eq!(
+            change.previous_owner.map(|owner| owner.0),
+            Some(previous_owner.into())
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_bitcoin_reorg_subscription() {
+    let registry = Registry::new();
+    let handler = SubscriptionHandler::new(&registry);
+
+    let mut matching_stream =
+        handler.subscribe_bitcoin_reorgs(BitcoinReorgFilterView::FromBlockHeight(StrView(3)));
+    let mut non_matching_stream =
+        handler.subscribe_bitcoin_reorgs(BitcoinReorgFilterView::FromBlockHeight(StrView(4)));
+
+    let reorg_event = BlockReorgEvent {
+        block_height: 3,
+        block_hash: AccountAddress::random(),
+        orphaned_blocks: vec![BlockHeightHash {
+            block_height: 3,
+            block_hash: AccountAddress::random(),
+        }],
+    };
+    let event = Event::new(
+        EventID::new(ObjectID::random(), 0),
+        BlockReorgEvent::struct_tag(),
+        reorg_event.to_bytes(),
+        0,
+    );
+
+    let ledger_tx = random_ledger_transaction();
+    let tx_execution_info = TransactionExecutionInfo::random();
+    let tx = TransactionWithInfo::new(ledger_tx, tx_execution_info);
+    let tx_order = tx.transaction.sequence_info.tx_order;
+    let ctx = TxContext::random_for_testing_only();
+
+    handler
+        .process_tx_with_events(
+            tx,
+            vec![random_event(), event],
+            ctx,
+            StateChangeSet::new(H256::random(), 0),
+            BTreeMap::new(),
+        )
+        .unwrap();
+
+    let received = timeout(Duration::from_secs(1), matching_stream.next())
+        .await
+        .expect("Should receive bitcoin reorg within timeout")
+        .unwrap();
+    assert_eq!(received.tx_order.0, tx_order);
+    assert_eq!(received.block_height.0, 3);
+    assert_eq!(received.orphaned_blocks.len(), 1);
+    assert_eq!(received.orphaned_blocks[0].block_height.0, 3);
+
+    let unexpected = timeout(Duration::from_millis(100), non_matching_stream.next()).await;
+    assert!(
+        unexpected.is_err(),
+        "Should not receive the reorg which does not orphan the blocks above the height"
+    );
+}
+
 // #[tokio::test]
 // async fn test_filter_matching() {
 //     let registry = Registry::new();
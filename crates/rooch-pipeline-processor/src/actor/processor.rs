@@ -11,11 +11,16 @@ use bitcoin::hashes::Hash;
 use bitcoin_client::proxy::BitcoinClientProxy;
 use coerce::actor::{context::ActorContext, message::Handler, Actor, LocalActorRef};
 use function_name::named;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::Op;
 use moveos::moveos::VMPanicError;
+use moveos_types::access_path::AccessPath;
 use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::Event;
+use moveos_types::moveos_std::object::{is_dynamic_field_type, ObjectID, ObjectMeta};
 use moveos_types::moveos_std::tx_context::TxContext;
-use moveos_types::state::StateChangeSetExt;
+use moveos_types::state::{ObjectChange, StateChangeSet, StateChangeSetExt};
+use moveos_types::state_resolver::{RootObjectResolver, StateReader};
 use moveos_types::transaction::VerifiedMoveOSTransaction;
 use prometheus::Registry;
 use rooch_da::actor::messages::{AppendTransactionMessage, RevertTransactionMessage};
@@ -35,6 +40,7 @@ use rooch_types::{
         LedgerTxData, RoochTransaction,
     },
 };
+use std::collections::BTreeMap;
 use std::io;
 use std::sync::Arc;
 use tracing::{debug, info};
@@ -487,6 +493,12 @@ impl PipelineProcessorActor {
         // Add sequence info to tx context, let the Move contract can get the sequence info
         moveos_tx.ctx.add(tx.sequence_info.clone())?;
 
+        // The root before the tx, for resolving the previous owners of the changed objects
+        let pre_root = match self.notify_actor {
+            Some(_) => Some(self.executor.get_root().await?.metadata),
+            None => None,
+        };
+
         // Then execute
         let size = moveos_tx.ctx.tx_size;
         let (output, execution_info) = self
@@ -530,7 +542,13 @@ impl PipelineProcessorActor {
         // Process subscription, skip errors
         let tx_with_info = TransactionWithInfo::new(tx, execution_info.clone());
         let result = self
-            .process_subscription(tx_with_info, output.events.clone(), moveos_tx.ctx)
+            .process_subscription(
+                tx_with_info,
+                output.events.clone(),
+                moveos_tx.ctx,
+                &output.changeset,
+                pre_root,
+            )
             .await;
         match result {
             Ok(_) => {}
@@ -563,14 +581,60 @@ impl PipelineProcessorActor {
         tx: TransactionWithInfo,
         events: Vec<Event>,
         ctx: TxContext,
+        state_change_set: &StateChangeSet,
+        pre_root: Option<ObjectMeta>,
     ) -> Result<()> {
-        if let Some(notify_actor) = self.notify_actor.clone() {
+        if let (Some(notify_actor), Some(pre_root)) = (self.notify_actor.clone(), pre_root) {
+            let previous_owners = self.resolve_previous_owners(pre_root, state_change_set)?;
+            // the change set is also returned in the tx output, so it is cloned for the notify actor
+            // even if there is no object change subscriber
             return notify_actor
-                .notify(ProcessTxWithEventsMessage { tx, events, ctx })
+                .notify(ProcessTxWithEventsMessage {
+                    tx,
+                    events,
+                    ctx,
+                    state_change_set: state_change_set.clone(),
+                    previous_owners,
+                })
                 .map_err(|e| anyhow!(format!("Process subscription notify error: {:?}", e)));
         }
         Ok(())
     }
+
+    // the owners of the changed objects before the tx, the object change subscribers of
+    // an owner should also receive the changes of the objects transferred away from it
+    fn resolve_previous_owners(
+        &self,
+        pre_root: ObjectMeta,
+        state_change_set: &StateChangeSet,
+    ) -> Result<BTreeMap<ObjectID, AccountAddress>> {
+        let mut object_ids = vec![];
+        for object_change in state_change_set.changes.values() {
+            collect_existing_object_ids(object_change, &mut object_ids);
+        }
+        if object_ids.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+        let resolver = RootObjectResolver::new(pre_root, &self.rooch_db.moveos_store);
+        Ok(resolver
+            .get_states(AccessPath::objects(object_ids))?
+            .into_iter()
+            .flatten()
+            .map(|object| (object.metadata.id, object.metadata.owner))
+            .collect())
+    }
+}
+
+// the ids of the changed objects which exist before the tx, the dynamic field objects are skipped
+fn collect_existing_object_ids(object_change: &ObjectChange, object_ids: &mut Vec<ObjectID>) {
+    if !is_dynamic_field_type(&object_change.metadata.object_type)
+        && !matches!(object_change.value, Some(Op::New(_)))
+    {
+        object_ids.push(object_change.metadata.id.clone());
+    }
+    for field_change in object_change.fields.values() {
+        collect_existing_object_ids(field_change, object_ids);
+    }
 }
 
 #[async_trait]
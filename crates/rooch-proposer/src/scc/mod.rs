@@ -24,7 +24,11 @@ impl StateCommitmentChain {
     /// Create a new SCC
     pub fn new(rooch_store: RoochStore, moveos_store: MoveOSStore) -> anyhow::Result<Self> {
         Self::repair_last_proposed(rooch_store.clone())?;
+        Self::load(rooch_store, moveos_store)
+    }
 
+    /// Load the SCC from the store without repairing it, for reading the proposed blocks only
+    pub fn load(rooch_store: RoochStore, moveos_store: MoveOSStore) -> anyhow::Result<Self> {
         let last_proposed_block_number = rooch_store.get_last_proposed()?;
 
         let last_proposed_block_accumulator_root: H256 = match last_proposed_block_number {
@@ -71,8 +75,8 @@ impl StateCommitmentChain {
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn get_block(&self, block_number: u128) -> anyhow::Result<Block> {
+    /// Get the proposed block, the state root of it is the one committed by the proposer
+    pub fn get_block(&self, block_number: u128) -> anyhow::Result<Block> {
         match self.last_proposed_block_number {
             Some(last_proposed) => {
                 if block_number > last_proposed {
@@ -124,13 +128,11 @@ impl StateCommitmentChain {
         Ok((tx_accumulator_root, tx_state_root))
     }
 
-    #[allow(dead_code)]
     fn get_accumulator_root(&self, tx_order: u64) -> anyhow::Result<H256> {
         let ledger_tx = get_ledger_tx(self.rooch_store.clone(), tx_order)?;
         Ok(ledger_tx.sequence_info.tx_accumulator_root)
     }
 
-    #[allow(dead_code)]
     fn get_prev_accumulator_root(&self, block_number: u128) -> anyhow::Result<H256> {
         if block_number == 0 {
             return Ok(H256::zero());
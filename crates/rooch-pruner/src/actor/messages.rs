@@ -0,0 +1,13 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use coerce::actor::{message::Message, scheduler::timer::TimerTick};
+
+#[derive(Clone)]
+pub struct RunOnlineGC {}
+
+impl Message for RunOnlineGC {
+    type Result = ();
+}
+
+impl TimerTick for RunOnlineGC {}
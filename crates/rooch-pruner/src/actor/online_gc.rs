@@ -0,0 +1,68 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::messages::RunOnlineGC;
+use crate::metrics::OnlineGCMetrics;
+use crate::online_gc::OnlineGarbageCollector;
+use async_trait::async_trait;
+use coerce::actor::{context::ActorContext, message::Handler, Actor};
+use prometheus::Registry;
+use rooch_config::online_gc_config::OnlineGCConfig;
+use rooch_db::RoochDB;
+use rooch_types::framework::chain_id::ChainID;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+pub struct OnlineGCActor {
+    collector: Arc<OnlineGarbageCollector>,
+    running: Arc<AtomicBool>,
+}
+
+impl OnlineGCActor {
+    pub fn new(
+        rooch_db: RoochDB,
+        chain_id: &ChainID,
+        config: &OnlineGCConfig,
+        registry: &Registry,
+    ) -> anyhow::Result<Self> {
+        let metrics = Arc::new(OnlineGCMetrics::new(registry));
+        let collector = OnlineGarbageCollector::new(rooch_db, chain_id, config, metrics)?;
+        Ok(Self {
+            collector: Arc::new(collector),
+            running: Arc::new(AtomicBool::new(false)),
+        })
+    }
+}
+
+#[async_trait]
+impl Actor for OnlineGCActor {
+    async fn stopped(&mut self, _ctx: &mut ActorContext) {
+        self.collector.shutdown();
+    }
+}
+
+#[async_trait]
+impl Handler<RunOnlineGC> for OnlineGCActor {
+    async fn handle(&mut self, _message: RunOnlineGC, _ctx: &mut ActorContext) {
+        // A cycle may take longer than the interval, skip the tick if the last one is running.
+        if self.running.swap(true, Ordering::AcqRel) {
+            tracing::debug!("[OnlineGC] the last cycle is still running, skip");
+            return;
+        }
+        // Run the cycle on the blocking pool, so the actor keeps receiving messages.
+        let collector = self.collector.clone();
+        let running = self.running.clone();
+        tokio::task::spawn_blocking(move || {
+            match collector.run_cycle() {
+                Ok(report) => tracing::info!(
+                    "[OnlineGC] cycle done. deleted: {}, rescued: {}, duration: {:?}",
+                    report.deleted_count,
+                    report.rescued_count,
+                    report.duration
+                ),
+                Err(e) => tracing::error!("[OnlineGC] cycle failed: {:?}", e),
+            }
+            running.store(false, Ordering::Release);
+        });
+    }
+}
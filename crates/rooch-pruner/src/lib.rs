@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // GC-only modules after removing live pruner components
+pub mod actor; // Online GC actor running in the server
 pub mod config; // Unified GC configuration
 pub mod garbage_collector; // Core GC implementation
 pub mod historical_state; // Historical state collection for multi-root GC protection
 pub mod marker; // Node marking strategies for GC
+pub mod metrics; // Online GC metrics
+pub mod online_gc; // Online GC running alongside the execution
 pub mod reachability; // Reachability analysis for GC
 pub mod recycle_bin; // Recycle bin for debugging/recovery
 pub mod safety_verifier; // Safety verification for GC operations
+pub mod state_attribution; // State size attribution report
 pub mod state_prune; // State prune functionality
 #[cfg(test)]
 mod tests;
@@ -20,3 +24,5 @@ pub use config::GCConfig;
 pub use garbage_collector::{GCReport, GarbageCollector, MarkStats, SweepStats};
 pub use historical_state::{HistoricalStateCollector, HistoricalStateConfig};
 pub use marker::{BloomFilterMarker, NodeMarker};
+pub use online_gc::{OnlineGCReport, OnlineGarbageCollector};
+pub use state_attribution::{StateAttributionReport, StateAttributor};
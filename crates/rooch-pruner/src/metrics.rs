@@ -0,0 +1,87 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge, Registry,
+};
+
+/// The phase of the online GC, exported by `online_gc_phase`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnlineGCPhase {
+    Idle = 0,
+    Mark = 1,
+    Sweep = 2,
+}
+
+#[derive(Debug)]
+pub struct OnlineGCMetrics {
+    pub online_gc_phase: IntGauge,
+    pub online_gc_cycles: IntCounterVec,
+    pub online_gc_retained_roots: IntGauge,
+    pub online_gc_marked_nodes: IntGauge,
+    pub online_gc_scanned_nodes: IntGauge,
+    pub online_gc_deleted_nodes: IntCounter,
+    pub online_gc_rescued_nodes: IntCounter,
+    pub online_gc_last_cycle_duration_ms: IntGauge,
+}
+
+impl OnlineGCMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        OnlineGCMetrics {
+            online_gc_phase: register_int_gauge_with_registry!(
+                "online_gc_phase",
+                "Online GC phase, 0: idle, 1: mark, 2: sweep",
+                registry,
+            )
+            .unwrap(),
+            online_gc_cycles: register_int_counter_vec_with_registry!(
+                "online_gc_cycles",
+                "Online GC cycles by result",
+                &["result"],
+                registry,
+            )
+            .unwrap(),
+            online_gc_retained_roots: register_int_gauge_with_registry!(
+                "online_gc_retained_roots",
+                "Online GC retained state roots in the current cycle",
+                registry,
+            )
+            .unwrap(),
+            online_gc_marked_nodes: register_int_gauge_with_registry!(
+                "online_gc_marked_nodes",
+                "Online GC nodes marked reachable in the current cycle",
+                registry,
+            )
+            .unwrap(),
+            online_gc_scanned_nodes: register_int_gauge_with_registry!(
+                "online_gc_scanned_nodes",
+                "Online GC nodes scanned by the sweep in the current cycle",
+                registry,
+            )
+            .unwrap(),
+            online_gc_deleted_nodes: register_int_counter_with_registry!(
+                "online_gc_deleted_nodes",
+                "Online GC deleted nodes",
+                registry,
+            )
+            .unwrap(),
+            online_gc_rescued_nodes: register_int_counter_with_registry!(
+                "online_gc_rescued_nodes",
+                "Online GC sweep candidates kept for becoming reachable from a new state root",
+                registry,
+            )
+            .unwrap(),
+            online_gc_last_cycle_duration_ms: register_int_gauge_with_registry!(
+                "online_gc_last_cycle_duration_ms",
+                "Online GC duration of the last finished cycle in milliseconds",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn set_phase(&self, phase: OnlineGCPhase) {
+        self.online_gc_phase.set(phase as i64);
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::historical_state::{HistoricalStateCollector, HistoricalStateConfig};
+use crate::marker::{estimate_bloom_parameters, AtomicBloomFilterMarker, NodeMarker};
+use crate::metrics::{OnlineGCMetrics, OnlineGCPhase};
+use crate::reachability::ReachableBuilder;
+use crate::recycle_bin::RecycleBinStore;
+use crate::safety_verifier::OnlineSafetyVerifier;
+use crate::util::estimate_state_node_count;
+use anyhow::Result;
+use moveos_common::bloom_filter::BloomFilter;
+use moveos_store::STATE_NODE_COLUMN_FAMILY_NAME;
+use moveos_types::h256::H256;
+use parking_lot::Mutex;
+use raw_store::SchemaStore;
+use rooch_config::online_gc_config::{OnlineGCConfig, DEFAULT_ONLINE_GC_MARKER_TARGET_FP_RATE};
+use rooch_db::RoochDB;
+use rooch_types::framework::chain_id::ChainID;
+use smt::NodeReader;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Cap the auto sized marker, same as the offline GC
+const MAX_MARKER_BITS: usize = 1 << 33;
+/// The filter of the nodes written during a cycle, 16 MiB
+const WRITTEN_NODES_BITS: usize = 1 << 27;
+const WRITTEN_NODES_HASH_FNS: u8 = 4;
+
+/// The report of an online GC cycle
+#[derive(Debug, Clone, Default)]
+pub struct OnlineGCReport {
+    pub retained_roots: usize,
+    pub marked_count: u64,
+    pub scanned_count: u64,
+    pub deleted_count: u64,
+    /// The candidates kept for becoming reachable from a state root committed during the cycle
+    pub rescued_count: u64,
+    /// Whether the cycle is stopped before the sweep finished
+    pub interrupted: bool,
+    pub duration: Duration,
+}
+
+/// Mark-Sweep garbage collection of the stale state nodes, running while the server executes
+/// transactions.
+///
+/// The retained roots are the latest `retained_roots` state roots from `historical_state`,
+/// the states older than the window are not readable after the cycle. The sweep deletes nodes
+/// in batches guarded by the `OnlineSafetyVerifier`, and is paced by `delete_rate_limit` to
+/// bound the IO taken from the execution.
+pub struct OnlineGarbageCollector {
+    rooch_db: RoochDB,
+    retained_roots: usize,
+    batch_size: usize,
+    delete_rate_limit: u64,
+    recycle_bin: Option<RecycleBinStore>,
+    metrics: Arc<OnlineGCMetrics>,
+    shutdown: AtomicBool,
+}
+
+impl OnlineGarbageCollector {
+    pub fn new(
+        rooch_db: RoochDB,
+        chain_id: &ChainID,
+        config: &OnlineGCConfig,
+        metrics: Arc<OnlineGCMetrics>,
+    ) -> Result<Self> {
+        let retained_roots = config.retained_roots.unwrap_or_else(|| {
+            HistoricalStateConfig::new_with_network(chain_id).protected_roots_count
+        });
+        anyhow::ensure!(
+            retained_roots > 0,
+            "online-gc-retained-roots must be positive"
+        );
+        let batch_size = config.batch_size();
+        anyhow::ensure!(batch_size > 0, "online-gc-batch-size must be positive");
+        let delete_rate_limit = config.delete_rate_limit();
+        anyhow::ensure!(
+            delete_rate_limit > 0,
+            "online-gc-delete-rate-limit must be positive"
+        );
+        let recycle_bin = if config.use_recycle_bin {
+            Some(RecycleBinStore::new(
+                rooch_db.moveos_store.get_node_recycle_store().clone(),
+            )?)
+        } else {
+            None
+        };
+        Ok(Self {
+            rooch_db,
+            retained_roots,
+            batch_size,
+            delete_rate_limit,
+            recycle_bin,
+            metrics,
+            shutdown: AtomicBool::new(false),
+        })
+    }
+
+    /// Stop the running cycle at the next batch
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+
+    /// Run a GC cycle, it blocks the current thread until the sweep is done.
+    pub fn run_cycle(&self) -> Result<OnlineGCReport> {
+        let start_time = Instant::now();
+        // the nodes written from now on are kept by this cycle, even if no committed root
+        // reaches them yet
+        let fence = self.rooch_db.moveos_store.get_state_write_fence();
+        fence.start_recording(WRITTEN_NODES_BITS, WRITTEN_NODES_HASH_FNS);
+        let result = self.do_run_cycle(start_time);
+        fence.stop_recording();
+        self.metrics.set_phase(OnlineGCPhase::Idle);
+        match &result {
+            Ok(report) => {
+                self.metrics
+                    .online_gc_cycles
+                    .with_label_values(&[if report.interrupted {
+                        "interrupted"
+                    } else {
+                        "success"
+                    }])
+                    .inc();
+                self.metrics
+                    .online_gc_last_cycle_duration_ms
+                    .set(report.duration.as_millis() as i64);
+            }
+            Err(_) => self
+                .metrics
+                .online_gc_cycles
+                .with_label_values(&["failed"])
+                .inc(),
+        }
+        result
+    }
+
+    fn do_run_cycle(&self, start_time: Instant) -> Result<OnlineGCReport> {
+        let moveos_store = &self.rooch_db.moveos_store;
+        let verifier = OnlineSafetyVerifier::new(moveos_store.clone(), self.batch_size);
+
+        // Mark phase
+        self.metrics.set_phase(OnlineGCPhase::Mark);
+        let mut roots = HistoricalStateCollector::new(
+            moveos_store.clone(),
+            self.rooch_db.rooch_store.clone(),
+            HistoricalStateConfig {
+                protected_roots_count: self.retained_roots,
+            },
+        )
+        .collect_recent_state_roots()?;
+        let latest_root = verifier.latest_state_root()?;
+        if !roots.contains(&latest_root) {
+            roots.push(latest_root);
+        }
+        self.metrics
+            .online_gc_retained_roots
+            .set(roots.len() as i64);
+
+        let estimated_nodes =
+            estimate_state_node_count(moveos_store.get_state_node_store()).unwrap_or(1_000_000);
+        let (bloom_bits, bloom_hash_fns) =
+            estimate_bloom_parameters(estimated_nodes, DEFAULT_ONLINE_GC_MARKER_TARGET_FP_RATE);
+        let marker = AtomicBloomFilterMarker::new(bloom_bits.min(MAX_MARKER_BITS), bloom_hash_fns);
+        info!(
+            "Online GC mark phase started, retained roots {}, estimated nodes {}, marker bits {}",
+            roots.len(),
+            estimated_nodes,
+            bloom_bits.min(MAX_MARKER_BITS)
+        );
+        let bloom = Arc::new(Mutex::new(BloomFilter::new(1 << 10, 4)));
+        let reachable_builder = ReachableBuilder::new(moveos_store.clone(), bloom);
+        let marked_count =
+            reachable_builder.build_with_marker(roots.clone(), &marker, self.batch_size)?;
+        self.metrics.online_gc_marked_nodes.set(marked_count as i64);
+
+        // Sweep phase
+        self.metrics.set_phase(OnlineGCPhase::Sweep);
+        let mut report = OnlineGCReport {
+            retained_roots: roots.len(),
+            marked_count,
+            ..Default::default()
+        };
+        self.sweep(&verifier, &marker, &mut report)?;
+        report.duration = start_time.elapsed();
+        info!("Online GC cycle done: {:?}", report);
+        Ok(report)
+    }
+
+    fn sweep(
+        &self,
+        verifier: &OnlineSafetyVerifier,
+        marker: &dyn NodeMarker,
+        report: &mut OnlineGCReport,
+    ) -> Result<()> {
+        let node_store = self.rooch_db.moveos_store.get_state_node_store();
+        let wrapper = node_store
+            .get_store()
+            .store()
+            .db()
+            .ok_or_else(|| anyhow::anyhow!("State node store is not backed by RocksDB"))?;
+        let raw_db = wrapper.inner();
+        let cf = raw_db
+            .cf_handle(STATE_NODE_COLUMN_FAMILY_NAME)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Column family {} not found", STATE_NODE_COLUMN_FAMILY_NAME)
+            })?;
+
+        let sweep_start = Instant::now();
+        let mut candidates = Vec::with_capacity(self.batch_size);
+        let mut iter = raw_db.raw_iterator_cf(&cf);
+        iter.seek_to_first();
+        while iter.valid() {
+            if let Some(key) = iter.key() {
+                report.scanned_count += 1;
+                if key.len() == 32 {
+                    let node_hash = H256::from_slice(key);
+                    if !marker.is_marked(&node_hash) {
+                        candidates.push(node_hash);
+                    }
+                } else {
+                    warn!("Skipping non-32B node key len={}", key.len());
+                }
+            }
+            if candidates.len() >= self.batch_size {
+                self.delete_batch(verifier, marker, &candidates, report)?;
+                candidates.clear();
+                self.metrics
+                    .online_gc_scanned_nodes
+                    .set(report.scanned_count as i64);
+                if self.shutdown.load(Ordering::Relaxed) {
+                    info!("Online GC is stopped during the sweep phase");
+                    report.interrupted = true;
+                    break;
+                }
+                self.throttle(sweep_start, report.deleted_count);
+            }
+            iter.next();
+        }
+        iter.status()?;
+        drop(iter);
+        if !report.interrupted && !candidates.is_empty() {
+            self.delete_batch(verifier, marker, &candidates, report)?;
+        }
+        self.metrics
+            .online_gc_scanned_nodes
+            .set(report.scanned_count as i64);
+
+        if report.deleted_count > 0 {
+            node_store.flush_only()?;
+        }
+        Ok(())
+    }
+
+    fn delete_batch(
+        &self,
+        verifier: &OnlineSafetyVerifier,
+        marker: &dyn NodeMarker,
+        candidates: &[H256],
+        report: &mut OnlineGCReport,
+    ) -> Result<()> {
+        let node_store = self.rooch_db.moveos_store.get_state_node_store();
+        let deletion = verifier.delete_unreachable(candidates, marker, |batch| {
+            let to_delete = match &self.recycle_bin {
+                Some(recycle_bin) => {
+                    // keep the nodes which are not saved to the recycle bin
+                    let mut recycled = Vec::with_capacity(batch.len());
+                    for node_hash in batch {
+                        match node_store.get(node_hash) {
+                            Ok(Some(bytes)) => {
+                                let record = recycle_bin.create_record(bytes);
+                                match recycle_bin.put_record(*node_hash, record) {
+                                    Ok(()) => recycled.push(*node_hash),
+                                    Err(e) => {
+                                        warn!(?node_hash, "Failed to store recycle record: {}", e)
+                                    }
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => warn!(?node_hash, "Failed to read node to recycle: {}", e),
+                        }
+                    }
+                    recycled
+                }
+                None => batch.to_vec(),
+            };
+            let deleted = to_delete.len() as u64;
+            if !to_delete.is_empty() {
+                node_store.delete_nodes_with_flush(to_delete, false)?;
+            }
+            Ok(deleted)
+        })?;
+        report.deleted_count += deletion.deleted;
+        report.rescued_count += deletion.rescued;
+        self.metrics
+            .online_gc_deleted_nodes
+            .inc_by(deletion.deleted);
+        self.metrics
+            .online_gc_rescued_nodes
+            .inc_by(deletion.rescued);
+        Ok(())
+    }
+
+    // keep the deletion under `delete_rate_limit` nodes per second
+    fn throttle(&self, sweep_start: Instant, deleted_count: u64) {
+        let expected =
+            Duration::from_secs_f64(deleted_count as f64 / self.delete_rate_limit as f64);
+        let elapsed = sweep_start.elapsed();
+        if expected > elapsed {
+            std::thread::sleep(expected - elapsed);
+        }
+    }
+}
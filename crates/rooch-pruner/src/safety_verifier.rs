@@ -1,8 +1,15 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::marker::NodeMarker;
+use crate::reachability::ReachableBuilder;
 use anyhow::Result;
+use moveos_common::bloom_filter::BloomFilter;
+use moveos_store::MoveOSStore;
+use moveos_types::h256::H256;
+use parking_lot::Mutex;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Minimal database safety verifier
 pub struct SafetyVerifier {
@@ -92,6 +99,87 @@ impl SafetyVerifier {
     }
 }
 
+/// Safety verifier for the GC running inside the server, the node store is written concurrently.
+///
+/// The mark phase only covers the roots committed before it, the nodes written later may
+/// reuse the swept candidates. Before deleting a batch, the verifier waits for the in-flight
+/// writes with the store's `StateWriteFence`, marks the latest committed root (only the nodes
+/// written after the last check are walked) and drops the candidates which become reachable
+/// or are recorded by the fence as written during the cycle (e.g. the nodes of an uncommitted
+/// state-only execution).
+pub struct OnlineSafetyVerifier {
+    moveos_store: MoveOSStore,
+    reachable_builder: ReachableBuilder,
+    batch_size: usize,
+}
+
+/// The result of a guarded deletion
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GuardedDeletion {
+    /// The candidates deleted
+    pub deleted: u64,
+    /// The candidates reachable from the latest committed root or written during the cycle,
+    /// they are kept
+    pub rescued: u64,
+}
+
+impl OnlineSafetyVerifier {
+    pub fn new(moveos_store: MoveOSStore, batch_size: usize) -> Self {
+        // the bloom filter is only used by `ReachableBuilder::build`, keep it small
+        let bloom = Arc::new(Mutex::new(BloomFilter::new(1 << 10, 4)));
+        let reachable_builder = ReachableBuilder::new(moveos_store.clone(), bloom);
+        Self {
+            moveos_store,
+            reachable_builder,
+            batch_size,
+        }
+    }
+
+    /// The latest committed state root
+    pub fn latest_state_root(&self) -> Result<H256> {
+        let startup_info = self
+            .moveos_store
+            .config_store
+            .get_startup_info()?
+            .ok_or_else(|| anyhow::anyhow!("Startup info not found"))?;
+        Ok(startup_info.state_root)
+    }
+
+    /// Delete the `candidates` which are still unreachable from the latest committed root and
+    /// not written during the cycle. `delete` returns the number of nodes it actually deleted.
+    /// The state writes are blocked until `delete` returns, so it should be a single batch.
+    pub fn delete_unreachable<F>(
+        &self,
+        candidates: &[H256],
+        marker: &dyn NodeMarker,
+        delete: F,
+    ) -> Result<GuardedDeletion>
+    where
+        F: FnOnce(&[H256]) -> Result<u64>,
+    {
+        let fence = self.moveos_store.get_state_write_fence();
+        let _guard = fence.delete_guard();
+        let latest_root = self.latest_state_root()?;
+        self.reachable_builder
+            .build_with_marker(vec![latest_root], marker, self.batch_size)?;
+
+        let unreachable: Vec<H256> = candidates
+            .iter()
+            .filter(|hash| !marker.is_marked(hash) && !fence.maybe_written(hash))
+            .copied()
+            .collect();
+        let deleted = if unreachable.is_empty() {
+            0
+        } else {
+            delete(&unreachable)?
+        };
+        Ok(GuardedDeletion {
+            deleted,
+            rescued: (candidates.len() - unreachable.len()) as u64,
+        })
+    }
+}
+
 #[cfg(all(test, feature = "gc-tests"))]
 mod tests {
     use super::*;
@@ -0,0 +1,458 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::marker::NodeMarker;
+use crate::reachability::ReachableBuilder;
+use crate::util::estimate_state_node_count;
+use anyhow::Result;
+use moveos_common::bloom_filter::BloomFilter;
+use moveos_store::{MoveOSStore, STATE_NODE_COLUMN_FAMILY_NAME};
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::state::{FieldKey, ObjectState};
+use parking_lot::Mutex;
+use primitive_types::H256;
+use raw_store::SchemaStore;
+use serde::{Deserialize, Serialize};
+use smt::jellyfish_merkle::node_type::Node;
+use smt::{NodeReader, SPARSE_MERKLE_PLACEHOLDER_HASH};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Every node is stored as `hash(32B) -> encoded node`, the key is counted in the node bytes.
+const NODE_KEY_BYTES: u64 = 32;
+
+/// Node count and bytes of a group of state nodes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeStat {
+    pub nodes: u64,
+    pub bytes: u64,
+}
+
+impl SizeStat {
+    fn node(value_len: usize) -> Self {
+        Self {
+            nodes: 1,
+            bytes: value_len as u64 + NODE_KEY_BYTES,
+        }
+    }
+
+    fn add(&mut self, other: SizeStat) {
+        self.nodes += other.nodes;
+        self.bytes += other.bytes;
+    }
+}
+
+/// The state size attributed to an object type or an owner
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedSizeStat {
+    pub name: String,
+    /// The number of objects, for stale nodes it is the number of stale leaf nodes
+    pub objects: u64,
+    pub nodes: u64,
+    pub bytes: u64,
+}
+
+/// The state size of an object with dynamic fields, including the nested objects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSizeStat {
+    pub object_id: ObjectID,
+    pub object_type: String,
+    pub owner: String,
+    /// The fields count of the object
+    pub fields: u64,
+    pub nodes: u64,
+    pub bytes: u64,
+}
+
+/// The live state size at a state root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveStateAttribution {
+    pub state_root: H256,
+    /// All the unique nodes reachable from the state root
+    pub total: SizeStat,
+    /// The internal nodes of the global state tree, they are not owned by any object
+    pub root_internal: SizeStat,
+    /// The number of top-level objects
+    pub objects: u64,
+    /// The top-level objects grouped by object type, each object includes its dynamic fields
+    pub by_object_type: Vec<NamedSizeStat>,
+    /// The top-level objects grouped by owner, each object includes its dynamic fields
+    pub by_owner: Vec<NamedSizeStat>,
+    /// The largest objects with dynamic fields at any level
+    pub largest_tables: Vec<TableSizeStat>,
+    pub duration_ms: u128,
+}
+
+/// The reclaimable nodes which are not reachable from any protected root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleStateAttribution {
+    pub protected_roots: Vec<H256>,
+    /// All the nodes in the state node store
+    pub scanned: SizeStat,
+    /// The reclaimable nodes
+    pub total: SizeStat,
+    /// The stale internal nodes, an internal node does not carry the object type
+    pub internal: SizeStat,
+    /// The stale nodes which could not be decoded
+    pub unknown: SizeStat,
+    /// The stale leaf nodes grouped by the object type of the old object version
+    pub by_object_type: Vec<NamedSizeStat>,
+    pub duration_ms: u128,
+}
+
+/// Report which object types, owners and dynamic field tables hold the state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateAttributionReport {
+    pub live: LiveStateAttribution,
+    pub stale: Option<StaleStateAttribution>,
+}
+
+#[derive(Debug, Default)]
+struct ObjectGroupStat {
+    objects: u64,
+    size: SizeStat,
+}
+
+impl ObjectGroupStat {
+    fn add_object(&mut self, size: SizeStat) {
+        self.objects += 1;
+        self.size.add(size);
+    }
+}
+
+#[derive(Default)]
+struct LiveWalkState {
+    root_internal: SizeStat,
+    objects: u64,
+    by_object_type: HashMap<String, ObjectGroupStat>,
+    by_owner: HashMap<String, ObjectGroupStat>,
+    tables: Vec<TableSizeStat>,
+}
+
+/// Attribute the state nodes to the objects which hold them.
+///
+/// The live tree is walked from a state root like `ReachableBuilder`, every unique node is
+/// attributed once with the `NodeMarker`, a node shared by several objects is attributed to the
+/// first object walked. After the live walk, the marker holds the reachable set, the state node
+/// store is scanned like the GC sweep phase to attribute the reclaimable nodes.
+///
+/// The marker may be a Bloom filter, a false positive skips a live node, so the marker should be
+/// sized with a low false positive rate.
+pub struct StateAttributor {
+    moveos_store: MoveOSStore,
+    top_n: usize,
+}
+
+impl StateAttributor {
+    pub fn new(moveos_store: MoveOSStore, top_n: usize) -> Self {
+        Self {
+            moveos_store,
+            top_n: top_n.max(1),
+        }
+    }
+
+    /// Estimate the node count of the state node store for sizing the marker
+    pub fn estimate_node_count(&self) -> Option<usize> {
+        estimate_state_node_count(self.moveos_store.get_state_node_store())
+    }
+
+    /// Walk the live tree at `state_root` and attribute every reachable node.
+    pub fn attribute_live(
+        &self,
+        state_root: H256,
+        marker: &dyn NodeMarker,
+    ) -> Result<LiveStateAttribution> {
+        let start_time = Instant::now();
+        let mut state = LiveWalkState::default();
+        let total = self.walk_tree(state_root, true, marker, &mut state)?;
+
+        let mut tables = state.tables;
+        sort_and_truncate_tables(&mut tables, self.top_n);
+        let report = LiveStateAttribution {
+            state_root,
+            total,
+            root_internal: state.root_internal,
+            objects: state.objects,
+            by_object_type: into_sorted_stats(state.by_object_type, self.top_n),
+            by_owner: into_sorted_stats(state.by_owner, self.top_n),
+            largest_tables: tables,
+            duration_ms: start_time.elapsed().as_millis(),
+        };
+        info!(
+            "Live state attribution done, state_root {:?}, nodes {}, bytes {}, objects {}, duration {:?}",
+            state_root,
+            report.total.nodes,
+            report.total.bytes,
+            report.objects,
+            start_time.elapsed()
+        );
+        Ok(report)
+    }
+
+    /// Attribute the nodes which are not reachable from the `protected_roots` and the roots
+    /// already walked with the `marker`.
+    pub fn attribute_stale(
+        &self,
+        protected_roots: Vec<H256>,
+        marker: &dyn NodeMarker,
+        batch_size: usize,
+    ) -> Result<StaleStateAttribution> {
+        let start_time = Instant::now();
+        if !protected_roots.is_empty() {
+            let bloom = Arc::new(Mutex::new(BloomFilter::new(1 << 20, 4)));
+            let reachable_builder = ReachableBuilder::new(self.moveos_store.clone(), bloom);
+            reachable_builder.build_with_marker(protected_roots.clone(), marker, batch_size)?;
+        }
+
+        let mut scanned = SizeStat::default();
+        let mut total = SizeStat::default();
+        let mut internal = SizeStat::default();
+        let mut unknown = SizeStat::default();
+        let mut by_object_type: HashMap<String, ObjectGroupStat> = HashMap::new();
+
+        let node_store = self.moveos_store.get_state_node_store();
+        let wrapper = node_store
+            .get_store()
+            .store()
+            .db()
+            .ok_or_else(|| anyhow::anyhow!("State node store is not backed by RocksDB"))?;
+        let raw_db = wrapper.inner();
+        let cf = raw_db
+            .cf_handle(STATE_NODE_COLUMN_FAMILY_NAME)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Column family {} not found", STATE_NODE_COLUMN_FAMILY_NAME)
+            })?;
+        let mut iter = raw_db.raw_iterator_cf(&cf);
+        iter.seek_to_first();
+        let mut last_log = Instant::now();
+        while iter.valid() {
+            if let (Some(key), Some(value)) = (iter.key(), iter.value()) {
+                let size = SizeStat::node(value.len());
+                scanned.add(size);
+                if key.len() != 32 {
+                    warn!("Skipping non-32B node key len={}", key.len());
+                } else if !marker.is_marked(&H256::from_slice(key)) {
+                    total.add(size);
+                    match Node::<FieldKey, ObjectState>::decode(value) {
+                        Ok(Node::Leaf(leaf)) => by_object_type
+                            .entry(type_name(&leaf.value().origin.metadata))
+                            .or_default()
+                            .add_object(size),
+                        Ok(Node::Internal(_)) => internal.add(size),
+                        Ok(Node::Null) | Err(_) => unknown.add(size),
+                    }
+                }
+            }
+            if last_log.elapsed() > Duration::from_secs(30) {
+                info!(
+                    "Stale attribution progress: scanned={}, stale={}",
+                    scanned.nodes, total.nodes
+                );
+                last_log = Instant::now();
+            }
+            iter.next();
+        }
+        iter.status()?;
+
+        info!(
+            "Stale state attribution done, scanned {}, stale nodes {}, stale bytes {}, duration {:?}",
+            scanned.nodes,
+            total.nodes,
+            total.bytes,
+            start_time.elapsed()
+        );
+        Ok(StaleStateAttribution {
+            protected_roots,
+            scanned,
+            total,
+            internal,
+            unknown,
+            by_object_type: into_sorted_stats(by_object_type, self.top_n),
+            duration_ms: start_time.elapsed().as_millis(),
+        })
+    }
+
+    /// Walk a state tree, recursing into the field trees of the objects.
+    /// Returns the size of the tree, including the nested field trees.
+    fn walk_tree(
+        &self,
+        root: H256,
+        is_global: bool,
+        marker: &dyn NodeMarker,
+        state: &mut LiveWalkState,
+    ) -> Result<SizeStat> {
+        let node_store = self.moveos_store.get_state_node_store();
+        let mut tree_size = SizeStat::default();
+        let mut stack = vec![root];
+        while let Some(node_hash) = stack.pop() {
+            if node_hash == *SPARSE_MERKLE_PLACEHOLDER_HASH || marker.is_marked(&node_hash) {
+                continue;
+            }
+            marker.mark(node_hash)?;
+            let bytes = match node_store.get(&node_hash)? {
+                Some(bytes) => bytes,
+                None => {
+                    warn!("State node {:?} is missing", node_hash);
+                    continue;
+                }
+            };
+            let node_size = SizeStat::node(bytes.len());
+            let node = match Node::<FieldKey, ObjectState>::decode(&bytes) {
+                Ok(node) => node,
+                Err(e) => {
+                    warn!("Failed to decode state node {:?}: {}", node_hash, e);
+                    tree_size.add(node_size);
+                    continue;
+                }
+            };
+            match node {
+                Node::Internal(internal) => {
+                    tree_size.add(node_size);
+                    if is_global {
+                        state.root_internal.add(node_size);
+                    }
+                    stack.extend(internal.all_child().into_iter().map(Into::<H256>::into));
+                }
+                Node::Leaf(leaf) => {
+                    let metadata = &leaf.value().origin.metadata;
+                    let mut object_size = node_size;
+                    if let Some(field_root) = metadata.state_root {
+                        if field_root != *SPARSE_MERKLE_PLACEHOLDER_HASH {
+                            let fields_size = self.walk_tree(field_root, false, marker, state)?;
+                            object_size.add(fields_size);
+                            self.record_table(metadata, object_size, state);
+                        }
+                    }
+                    tree_size.add(object_size);
+                    if is_global {
+                        state.objects += 1;
+                        state
+                            .by_object_type
+                            .entry(type_name(metadata))
+                            .or_default()
+                            .add_object(object_size);
+                        state
+                            .by_owner
+                            .entry(metadata.owner.to_hex_literal())
+                            .or_default()
+                            .add_object(object_size);
+                    }
+                }
+                Node::Null => tree_size.add(node_size),
+            }
+        }
+        Ok(tree_size)
+    }
+
+    fn record_table(&self, metadata: &ObjectMeta, size: SizeStat, state: &mut LiveWalkState) {
+        state.tables.push(TableSizeStat {
+            object_id: metadata.id.clone(),
+            object_type: type_name(metadata),
+            owner: metadata.owner.to_hex_literal(),
+            fields: metadata.size,
+            nodes: size.nodes,
+            bytes: size.bytes,
+        });
+        // keep the memory bounded, there may be millions of objects with fields
+        if state.tables.len() >= self.top_n * 4 {
+            sort_and_truncate_tables(&mut state.tables, self.top_n);
+        }
+    }
+}
+
+fn type_name(metadata: &ObjectMeta) -> String {
+    metadata.object_type.to_canonical_string()
+}
+
+fn sort_and_truncate_tables(tables: &mut Vec<TableSizeStat>, top_n: usize) {
+    tables.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| b.nodes.cmp(&a.nodes)));
+    tables.truncate(top_n);
+}
+
+fn into_sorted_stats(stats: HashMap<String, ObjectGroupStat>, top_n: usize) -> Vec<NamedSizeStat> {
+    let mut stats: Vec<NamedSizeStat> = stats
+        .into_iter()
+        .map(|(name, stat)| NamedSizeStat {
+            name,
+            objects: stat.objects,
+            nodes: stat.size.nodes,
+            bytes: stat.size.bytes,
+        })
+        .collect();
+    stats.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    stats.truncate(top_n);
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marker::BloomFilterMarker;
+    use moveos_types::test_utils::random_table_object;
+    use smt::{SMTree, UpdateSet};
+
+    fn table_state(state_root: Option<H256>, size: u64) -> ObjectState {
+        let table = random_table_object();
+        let mut meta = table.metadata().clone();
+        meta.state_root = state_root;
+        meta.size = size;
+        ObjectState::new_with_struct(meta, table.value.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_state_attribution() {
+        let (store, _tmpdir) = MoveOSStore::mock_moveos_store().unwrap();
+        let node_store = store.get_state_node_store().clone();
+        let registry = prometheus::Registry::new();
+        let smt = SMTree::<FieldKey, ObjectState, _>::new(node_store.clone(), &registry);
+
+        let mut fields = UpdateSet::new();
+        for _ in 0..3 {
+            fields.put(FieldKey::random(), table_state(None, 0));
+        }
+        let field_changeset = smt.puts(*SPARSE_MERKLE_PLACEHOLDER_HASH, fields).unwrap();
+        let field_nodes = field_changeset.nodes.len() as u64;
+        node_store.write_nodes(field_changeset.nodes).unwrap();
+
+        let table = table_state(Some(field_changeset.state_root), 3);
+        let table_id = table.metadata.id.clone();
+        let mut objects = UpdateSet::new();
+        objects.put(FieldKey::random(), table);
+        objects.put(FieldKey::random(), table_state(None, 0));
+        let changeset = smt.puts(*SPARSE_MERKLE_PLACEHOLDER_HASH, objects).unwrap();
+        let global_nodes = changeset.nodes.len() as u64;
+        node_store.write_nodes(changeset.nodes).unwrap();
+
+        // an old object version which is not reachable anymore
+        let mut stale = UpdateSet::new();
+        stale.put(FieldKey::random(), table_state(None, 0));
+        let stale_changeset = smt.puts(*SPARSE_MERKLE_PLACEHOLDER_HASH, stale).unwrap();
+        node_store.write_nodes(stale_changeset.nodes).unwrap();
+
+        let attributor = StateAttributor::new(store, 10);
+        let marker = BloomFilterMarker::new(1 << 16, 4);
+        let live = attributor
+            .attribute_live(changeset.state_root, &marker)
+            .unwrap();
+        assert_eq!(live.total.nodes, global_nodes + field_nodes);
+        assert_eq!(live.objects, 2);
+        assert_eq!(live.by_object_type.len(), 1);
+        assert_eq!(live.by_object_type[0].objects, 2);
+        assert_eq!(
+            live.by_object_type[0].nodes + live.root_internal.nodes,
+            live.total.nodes
+        );
+        assert_eq!(live.largest_tables.len(), 1);
+        assert_eq!(live.largest_tables[0].object_id, table_id);
+        assert_eq!(live.largest_tables[0].fields, 3);
+        assert_eq!(live.largest_tables[0].nodes, field_nodes + 1);
+
+        let stale = attributor.attribute_stale(vec![], &marker, 100).unwrap();
+        assert_eq!(stale.scanned.nodes, live.total.nodes + 1);
+        assert_eq!(stale.total.nodes, 1);
+        assert_eq!(stale.by_object_type.len(), 1);
+        assert_eq!(stale.by_object_type[0].objects, 1);
+    }
+}
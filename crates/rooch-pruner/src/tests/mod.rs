@@ -9,6 +9,7 @@ mod gc_integration_test;
 mod gc_performance_test;
 #[cfg(feature = "gc-tests")]
 mod node_counting_test;
+mod online_gc_test;
 #[cfg(feature = "gc-tests")]
 mod reachability_correctness;
 #[cfg(feature = "gc-tests")]
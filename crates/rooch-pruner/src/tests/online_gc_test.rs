@@ -0,0 +1,189 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test the online GC while the state is written concurrently
+
+use crate::historical_state::{HistoricalStateCollector, HistoricalStateConfig};
+use crate::metrics::OnlineGCMetrics;
+use crate::online_gc::OnlineGarbageCollector;
+use accumulator::accumulator_info::AccumulatorInfo;
+use anyhow::Result;
+use move_core_types::effects::Op;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::moveos_std::timestamp::Timestamp;
+use moveos_types::state::{MoveStructType, ObjectChange, ObjectState, StateChangeSet};
+use moveos_types::transaction::RawTransactionOutput;
+use prometheus::Registry;
+use rooch_config::online_gc_config::OnlineGCConfig;
+use rooch_config::RoochOpt;
+use rooch_db::RoochDB;
+use rooch_types::rooch_network::BuiltinChainID;
+use rooch_types::sequencer::SequencerInfo;
+use rooch_types::transaction::{LedgerTransaction, RoochTransaction, TransactionSequenceInfo};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const OBJECT_COUNT: usize = 16;
+
+/// Commits state roots like the executor, every root modifies all the objects so the nodes of
+/// the previous roots become stale.
+struct StateWriter {
+    rooch_db: RoochDB,
+    objects: Vec<ObjectMeta>,
+    root: ObjectMeta,
+    next_order: u64,
+}
+
+impl StateWriter {
+    fn new(rooch_db: RoochDB) -> Self {
+        Self {
+            rooch_db,
+            objects: vec![],
+            root: ObjectMeta::genesis_root(),
+            next_order: 0,
+        }
+    }
+
+    fn new_change_set(&mut self, milliseconds: u64, add_object: bool) -> Result<StateChangeSet> {
+        let mut change_set = StateChangeSet::new(self.root.state_root(), self.root.size);
+        for meta in &self.objects {
+            let (metadata, value) =
+                ObjectState::new_with_struct(meta.clone(), Timestamp { milliseconds })?
+                    .into_inner();
+            change_set.add_change(ObjectChange::new(metadata, Op::Modify(value)))?;
+        }
+        if add_object && self.objects.len() < OBJECT_COUNT {
+            let object = ObjectState::new_with_struct(
+                ObjectMeta::genesis_meta(ObjectID::random(), Timestamp::type_tag()),
+                Timestamp { milliseconds },
+            )?;
+            self.objects.push(object.metadata.clone());
+            change_set.add_change(ObjectChange::new_object(object))?;
+        }
+        Ok(change_set)
+    }
+
+    /// Execute and sequence a tx, returns the committed root
+    fn commit(&mut self) -> Result<ObjectMeta> {
+        let tx_order = self.next_order;
+        let changeset = self.new_change_set(tx_order, true)?;
+        let tx_hash = H256::random();
+        let output = RawTransactionOutput {
+            status: KeptVMStatus::Executed,
+            changeset,
+            events: vec![],
+            gas_used: 0,
+            is_upgrade: false,
+            is_gas_upgrade: false,
+        };
+        let (_output, execution_info) = self
+            .rooch_db
+            .moveos_store
+            .handle_tx_output(tx_order, tx_hash, output)?;
+        let sequence_info =
+            TransactionSequenceInfo::new(tx_order, vec![], AccumulatorInfo::default(), tx_order);
+        self.rooch_db.rooch_store.save_sequenced_tx(
+            tx_hash,
+            LedgerTransaction::new_l2_tx(RoochTransaction::mock(), sequence_info),
+            SequencerInfo::new(tx_order, AccumulatorInfo::default()),
+            None,
+            true,
+        )?;
+        self.next_order += 1;
+        self.root = ObjectMeta::root_metadata(execution_info.state_root, execution_info.size);
+        Ok(self.root.clone())
+    }
+
+    /// Apply a change set without committing it, like a state-only execution
+    fn apply_uncommitted(&mut self) -> Result<ObjectMeta> {
+        let mut change_set = self.new_change_set(u64::MAX, false)?;
+        self.rooch_db
+            .moveos_store
+            .state_store
+            .apply_change_set(&mut change_set)?;
+        Ok(change_set.root_metadata())
+    }
+}
+
+fn assert_root_readable(rooch_db: &RoochDB, root: &ObjectMeta) {
+    let states = rooch_db
+        .moveos_store
+        .state_store
+        .iter(root.state_root(), None)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap_or_else(|e| panic!("State root {} is not readable: {}", root.state_root(), e));
+    assert_eq!(states.len() as u64, root.size);
+}
+
+#[test]
+fn test_online_gc_with_concurrent_writes() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let rooch_opt = RoochOpt::new_with_default(Some(temp_dir.path().to_path_buf()), None, None)?;
+    let rooch_db = RoochDB::init_with_mock_metrics_for_test(rooch_opt.store_config())?;
+
+    let mut writer = StateWriter::new(rooch_db.clone());
+    let mut committed = vec![];
+    for _ in 0..32 {
+        committed.push(writer.commit()?);
+    }
+
+    let config = OnlineGCConfig {
+        retained_roots: Some(4),
+        batch_size: Some(8),
+        delete_rate_limit: Some(2_000),
+        ..Default::default()
+    };
+    let retained: Vec<H256> = HistoricalStateCollector::new(
+        rooch_db.moveos_store.clone(),
+        rooch_db.rooch_store.clone(),
+        HistoricalStateConfig {
+            protected_roots_count: 4,
+        },
+    )
+    .collect_recent_state_roots()?;
+    assert!(!retained.is_empty());
+    let gc = OnlineGarbageCollector::new(
+        rooch_db.clone(),
+        &BuiltinChainID::Local.chain_id(),
+        &config,
+        Arc::new(OnlineGCMetrics::new(&Registry::new())),
+    )?;
+
+    // keep committing and applying uncommitted states while the cycle runs
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer_stop = stop.clone();
+    let writer_thread = std::thread::spawn(move || -> Result<(Vec<ObjectMeta>, StateWriter)> {
+        let mut written = vec![];
+        while !writer_stop.load(Ordering::Relaxed) {
+            written.push(writer.commit()?);
+            written.push(writer.apply_uncommitted()?);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        Ok((written, writer))
+    });
+    let report = gc.run_cycle()?;
+    stop.store(true, Ordering::Relaxed);
+    let (written, writer) = writer_thread.join().unwrap()?;
+
+    assert!(report.deleted_count > 0, "{:?}", report);
+    assert!(!written.is_empty());
+    for root in committed
+        .iter()
+        .filter(|root| retained.contains(&root.state_root()))
+    {
+        assert_root_readable(&rooch_db, root);
+    }
+    for root in &written {
+        assert_root_readable(&rooch_db, root);
+    }
+    assert_root_readable(&rooch_db, &writer.root);
+    assert_eq!(
+        rooch_db.latest_root()?.unwrap().state_root(),
+        writer.root.state_root()
+    );
+    Ok(())
+}
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use moveos_store::state_store::NodeDBStore;
+use moveos_store::STATE_NODE_COLUMN_FAMILY_NAME;
 use moveos_types::state::FieldKey;
 use primitive_types::H256;
+use raw_store::SchemaStore;
 use smt::jellyfish_merkle::node_type::Node;
 use smt::SPARSE_MERKLE_PLACEHOLDER_HASH;
-use tracing::warn;
+use tracing::{debug, warn};
 
 /// Read unsigned LEB128 from the given byte slice.
 /// Returns the parsed value and the number of bytes consumed.
@@ -90,6 +93,21 @@ pub fn extract_child_nodes_strict(bytes: &[u8]) -> Result<Vec<H256>> {
     Ok(children)
 }
 
+/// Estimate the node count of the state node store from RocksDB statistics,
+/// it is used to size the marker before walking the state tree.
+pub fn estimate_state_node_count(node_store: &NodeDBStore) -> Option<usize> {
+    let wrapper = node_store.get_store().store().db()?;
+    let raw_db = wrapper.inner();
+    let cf = raw_db.cf_handle(STATE_NODE_COLUMN_FAMILY_NAME)?;
+    match raw_db.property_int_value_cf(&cf, "rocksdb.estimate-num-keys") {
+        Ok(Some(count)) if count > 0 => Some(count as usize),
+        _ => {
+            debug!("Unable to get node count from RocksDB statistics");
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,6 +33,7 @@ pub struct RelayerActor {
     processor: PipelineProcessorProxy,
     ethereum_config: Option<EthereumRelayerConfig>,
     bitcoin_config: Option<BitcoinRelayerConfig>,
+    bitcoin_client: Option<BitcoinClientProxy>,
     notify_actor: Option<LocalActorRef<NotifyActor>>,
     paused: bool,
 }
@@ -51,11 +52,19 @@ impl RelayerActor {
             processor,
             ethereum_config,
             bitcoin_config,
+            bitcoin_client: None,
             notify_actor,
             paused: false,
         })
     }
 
+    /// Relay the Bitcoin blocks from the `bitcoin_client` instead of the RPC client built from the
+    /// `bitcoin_config`, such as a client backed by the `MockBitcoinNode` in the tests.
+    pub fn with_bitcoin_client(mut self, bitcoin_client: BitcoinClientProxy) -> Self {
+        self.bitcoin_client = Some(bitcoin_client);
+        self
+    }
+
     pub async fn subscribe_event(
         &self,
         notify_actor_ref: LocalActorRef<NotifyActor>,
@@ -80,15 +89,20 @@ impl RelayerActor {
         }
 
         if let Some(bitcoin_config) = &self.bitcoin_config {
-            let bitcoin_client = BitcoinClientActor::new(
-                &bitcoin_config.btc_rpc_url,
-                &bitcoin_config.btc_rpc_user_name,
-                &bitcoin_config.btc_rpc_password,
-                None, // for relayer, only store, no need to get from local
-            )?;
-            let bitcoin_client_actor_ref =
-                ctx.spawn("bitcoin_client".into(), bitcoin_client).await?;
-            let bitcoin_client_proxy = BitcoinClientProxy::new(bitcoin_client_actor_ref.into());
+            let bitcoin_client_proxy = match &self.bitcoin_client {
+                Some(bitcoin_client) => bitcoin_client.clone(),
+                None => {
+                    let bitcoin_client = BitcoinClientActor::new(
+                        &bitcoin_config.btc_rpc_url,
+                        &bitcoin_config.btc_rpc_user_name,
+                        &bitcoin_config.btc_rpc_password,
+                        None, // for relayer, only store, no need to get from local
+                    )?;
+                    let bitcoin_client_actor_ref =
+                        ctx.spawn("bitcoin_client".into(), bitcoin_client).await?;
+                    BitcoinClientProxy::new(bitcoin_client_actor_ref.into())
+                }
+            };
             let bitcoin_relayer = BitcoinRelayer::new(
                 bitcoin_config.clone(),
                 bitcoin_client_proxy,
@@ -1,10 +1,12 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::jsonrpc_types::btc::fee::FeeRateEstimateView;
 use crate::jsonrpc_types::btc::ord::InscriptionFilterView;
+use crate::jsonrpc_types::btc::runes::RuneBalancesFilterView;
 use crate::jsonrpc_types::btc::utxo::UTXOFilterView;
 use crate::jsonrpc_types::{
-    BytesView, IndexerStateIDView, InscriptionPageView, StrView, UTXOPageView,
+    BytesView, IndexerStateIDView, InscriptionPageView, RuneBalancesPageView, StrView, UTXOPageView,
 };
 use crate::RpcResult;
 use jsonrpsee::proc_macros::rpc;
@@ -36,6 +38,19 @@ pub trait BtcAPI {
         descending_order: Option<bool>,
     ) -> RpcResult<InscriptionPageView>;
 
+    /// Query the rune balances of the UTXOs via global index by rune balances filter
+    /// The runes, like the inscriptions, are only processed for the Bitcoin txs executed via execute_l1_tx,
+    /// they are inactive in the header-only mode, and no rune balances are indexed for the header-only blocks.
+    #[method(name = "queryRunes")]
+    async fn query_runes(
+        &self,
+        filter: RuneBalancesFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<RuneBalancesPageView>;
+
     /// Broadcast a Bitcoin transaction
     #[method(name = "broadcastTX")]
     async fn broadcast_tx(
@@ -44,4 +59,12 @@ pub trait BtcAPI {
         maxfeerate: Option<f64>,
         maxburnamount: Option<f64>,
     ) -> RpcResult<String>;
+
+    /// Estimate the fee rate(sat/vB) from the recent relayed Bitcoin blocks
+    #[method(name = "estimateFeeRate")]
+    async fn estimate_fee_rate(
+        &self,
+        // the number of the recent blocks, default is 6, max is 24
+        num_blocks: Option<StrView<u64>>,
+    ) -> RpcResult<FeeRateEstimateView>;
 }
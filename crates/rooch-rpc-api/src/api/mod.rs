@@ -13,6 +13,8 @@ pub const MAX_RESULT_LIMIT: u64 = 200;
 pub const MAX_RESULT_LIMIT_USIZE: usize = MAX_RESULT_LIMIT as usize;
 pub const MAX_INTERNAL_LIMIT_USIZE: usize = 2000;
 
+pub const MAX_VIEW_FUNCTION_CALLS: usize = 50;
+
 // pub fn validate_limit(limit: Option<u64>, max: usize) -> Result<usize, anyhow::Error> {
 //     match limit {
 //         Some(l) if l > max => Err(anyhow!("Page size limit {l} exceeds max limit {max}")),
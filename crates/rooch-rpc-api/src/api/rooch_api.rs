@@ -3,20 +3,23 @@
 
 use crate::jsonrpc_types::account_view::BalanceInfoView;
 use crate::jsonrpc_types::address::UnitedAddressView;
+use crate::jsonrpc_types::btc::reorg::{BitcoinReorgEventView, BitcoinReorgFilterView};
 use crate::jsonrpc_types::event_view::{EventFilterView, IndexerEventIDView, IndexerEventView};
 use crate::jsonrpc_types::field_view::FieldFilterView;
 use crate::jsonrpc_types::repair_view::{RepairIndexerParamsView, RepairIndexerTypeView};
-use crate::jsonrpc_types::transaction_view::{TransactionFilterView, TransactionWithInfoView};
+use crate::jsonrpc_types::transaction_view::{
+    TransactionAccumulatorProofView, TransactionFilterView, TransactionWithInfoView,
+};
 use crate::jsonrpc_types::{
     AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView, EventOptions,
     EventPageView, ExecuteTransactionResponseView, FieldKeyView, FieldPageView, FunctionCallView,
     H256View, IndexerEventPageView, IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView,
-    ObjectIDVecView, ObjectIDView, ObjectStateFilterView, ObjectStateView, QueryOptions,
-    RoochAddressView, StateChangeSetPageView, StateOptions, StatePageView, StrView,
-    StructTagOrObjectIDView, StructTagView, SyncStateFilterView, TransactionWithInfoPageView,
-    TxOptions,
+    ObjectIDVecView, ObjectIDView, ObjectStateChangeView, ObjectStateFilterView, ObjectStateView,
+    QueryOptions, RoochAddressView, StateChangeSetPageView, StateOptions, StatePageView,
+    StateProofView, StrView, StructTagOrObjectIDView, StructTagView, SyncStateFilterView,
+    TransactionWithInfoPageView, TxOptions, ViewFunctionsResultView,
 };
-use crate::jsonrpc_types::{DryRunTransactionResponseView, Status};
+use crate::jsonrpc_types::{DryRunTransactionResponseView, HistoricalStateOptions, Status};
 use crate::RpcResult;
 use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::proc_macros::rpc;
@@ -44,17 +47,37 @@ pub trait RoochAPI {
         tx_option: Option<TxOptions>,
     ) -> RpcResult<ExecuteTransactionResponseView>;
 
+    /// Dry run the transaction in bcs hex format, against the latest state or the historical
+    /// state of the historical_state option. It is the transaction data, or the signed
+    /// transaction whose authenticator is not validated, the sponsor of a sponsored transaction
+    /// pays the gas.
     #[method(name = "dryRunRawTransaction")]
-    async fn dry_run(&self, tx_bcs_hex: BytesView) -> RpcResult<DryRunTransactionResponseView>;
+    async fn dry_run(
+        &self,
+        tx_bcs_hex: BytesView,
+        historical_state: Option<HistoricalStateOptions>,
+    ) -> RpcResult<DryRunTransactionResponseView>;
 
     /// Execute a read-only function call
     /// The function do not change the state of Application
+    /// It is executed against the latest state or the historical state of the historical_state option
     #[method(name = "executeViewFunction")]
     async fn execute_view_function(
         &self,
         function_call: FunctionCallView,
+        historical_state: Option<HistoricalStateOptions>,
     ) -> RpcResult<AnnotatedFunctionResultView>;
 
+    /// Execute a batch of read-only function calls against the same state root
+    /// If the state_root is not provided, the latest state root is used.
+    /// The result of every call is returned in order, along with the state root that was used.
+    #[method(name = "executeViewFunctions")]
+    async fn execute_view_functions(
+        &self,
+        function_calls: Vec<FunctionCallView>,
+        state_root: Option<H256View>,
+    ) -> RpcResult<ViewFunctionsResultView>;
+
     /// Get the states by access_path
     /// If the StateOptions.decode is true, the state is decoded and the decoded value is returned in the response.
     #[method(name = "getStates")]
@@ -97,6 +120,17 @@ pub trait RoochAPI {
         self.get_states(access_path_view, state_option).await
     }
 
+    /// Get the Object Field state with the merkle proof against the state root.
+    /// The object itself can be proved as a field of its parent object, top level objects are fields of the root object `0x`.
+    /// If the state_root is not provided, the latest state root is used.
+    #[method(name = "getStateProof")]
+    async fn get_state_proof(
+        &self,
+        object_id: ObjectIDView,
+        field_key: FieldKeyView,
+        state_root: Option<H256View>,
+    ) -> RpcResult<StateProofView>;
+
     /// List Object Fields via ObjectID.
     #[method(name = "listFieldStates")]
     async fn list_field_states(
@@ -137,6 +171,18 @@ pub trait RoochAPI {
         descending_order: Option<bool>,
     ) -> RpcResult<TransactionWithInfoPageView>;
 
+    /// Get the inclusion proof of a transaction in the tx accumulator.
+    /// The transaction is specified by `tx_order` or `tx_hash`, one of them is required, if both are provided, they must refer to the same transaction.
+    /// The proof is generated against the accumulator root after the tx of `accumulator_tx_order` is appended,
+    /// if `accumulator_tx_order` is not provided, the accumulator root of the transaction itself is used.
+    #[method(name = "getTransactionAccumulatorProof")]
+    async fn get_transaction_accumulator_proof(
+        &self,
+        tx_order: Option<StrView<u64>>,
+        tx_hash: Option<H256View>,
+        accumulator_tx_order: Option<StrView<u64>>,
+    ) -> RpcResult<Option<TransactionAccumulatorProofView>>;
+
     /// get account balance by RoochAddress and CoinType
     #[method(name = "getBalance")]
     async fn get_balance(
@@ -238,10 +284,29 @@ pub trait RoochAPI {
     ) -> RpcResult<Vec<u64>>;
 
     /// Subscribe to a stream of event
+    /// If `from_tx_order` is set, the matched events from the tx_order(inclusive) are replayed before the live events,
+    /// every event is delivered once in tx_order. Resume from the tx_order after the last completely received transaction.
     #[subscription(name = "subscribeEvents", item = IndexerEventView)]
-    fn subscribe_events(&self, filter: EventFilterView) -> SubscriptionResult;
+    fn subscribe_events(
+        &self,
+        filter: EventFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult;
 
     /// Subscribe to a stream of transaction with execution info
+    /// If `from_tx_order` is set, the matched transactions from the tx_order(inclusive) are replayed before the live transactions.
     #[subscription(name = "subscribeTransactions", item = TransactionWithInfoView)]
-    fn subscribe_transactions(&self, filter: TransactionFilterView) -> SubscriptionResult;
+    fn subscribe_transactions(
+        &self,
+        filter: TransactionFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult;
+
+    /// Subscribe to a stream of object state changes made by the transactions
+    #[subscription(name = "subscribeObjectChanges", item = ObjectStateChangeView)]
+    fn subscribe_object_changes(&self, filter: ObjectStateFilterView) -> SubscriptionResult;
+
+    /// Subscribe to a stream of bitcoin block reorgs, with the orphaned blocks
+    #[subscription(name = "subscribeBitcoinReorgs", item = BitcoinReorgEventView)]
+    fn subscribe_bitcoin_reorgs(&self, filter: BitcoinReorgFilterView) -> SubscriptionResult;
 }
@@ -0,0 +1,17 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::StrView;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The fee rate estimated from the recent relayed Bitcoin blocks
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FeeRateEstimateView {
+    /// The estimated fee rate in sat/vB
+    pub sat_per_vbyte: StrView<u64>,
+    /// The number of the blocks used to estimate the fee rate
+    pub block_count: StrView<u64>,
+    /// The height of the latest relayed block, None if no block is relayed
+    pub latest_block_height: Option<StrView<u64>>,
+}
@@ -1,6 +1,9 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod fee;
 pub mod ord;
+pub mod reorg;
+pub mod runes;
 pub mod transaction;
 pub mod utxo;
@@ -0,0 +1,55 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{BlockHeightHashView, StrView};
+use bitcoin::BlockHash;
+use rooch_types::bitcoin::pending_block::BlockReorgEvent;
+use rooch_types::indexer::Filter;
+use rooch_types::into_address::FromAddress;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The bitcoin block reorg notification. Used as the item of `subscribeBitcoinReorgs`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BitcoinReorgEventView {
+    /// The tx order of the transaction which triggers the reorg
+    pub tx_order: StrView<u64>,
+    /// The height of the new block which triggers the reorg
+    pub block_height: StrView<u64>,
+    /// The hash of the new block which triggers the reorg
+    pub block_hash: String,
+    /// The orphaned blocks replaced by the new block
+    pub orphaned_blocks: Vec<BlockHeightHashView>,
+}
+
+impl BitcoinReorgEventView {
+    pub fn new(tx_order: u64, event: BlockReorgEvent) -> Self {
+        Self {
+            tx_order: tx_order.into(),
+            block_height: event.block_height.into(),
+            block_hash: BlockHash::from_address(event.block_hash).to_string(),
+            orphaned_blocks: event.orphaned_blocks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BitcoinReorgFilterView {
+    /// All the reorgs.
+    All,
+    /// The reorgs which orphan any block at or above the given height.
+    FromBlockHeight(StrView<u64>),
+}
+
+impl Filter<BitcoinReorgEventView> for BitcoinReorgFilterView {
+    fn matches(&self, item: &BitcoinReorgEventView) -> bool {
+        match self {
+            BitcoinReorgFilterView::All => true,
+            BitcoinReorgFilterView::FromBlockHeight(block_height) => item
+                .orphaned_blocks
+                .iter()
+                .any(|block| block.block_height.0 >= block_height.0),
+        }
+    }
+}
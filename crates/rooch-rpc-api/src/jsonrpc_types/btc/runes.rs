@@ -0,0 +1,128 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::transaction::TxidView;
+use super::utxo::OutPointView;
+use crate::jsonrpc_types::{
+    IndexerObjectStateView, IndexerStateIDView, ObjectIDVecView, ObjectMetaView, StrView,
+    UnitedAddressView,
+};
+use anyhow::Result;
+use moveos_types::state::{MoveState, MoveStructType};
+use rooch_types::bitcoin::runes::{self, RuneBalance, RuneBalances, RuneId};
+use rooch_types::bitcoin::types::OutPoint;
+use rooch_types::indexer::state::ObjectStateFilter;
+use rooch_types::into_address::IntoAddress;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub type RuneIdView = StrView<RuneId>;
+
+impl FromStr for RuneIdView {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(StrView(RuneId::from_str(s)?))
+    }
+}
+
+impl Display for RuneIdView {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<RuneIdView> for RuneId {
+    fn from(view: RuneIdView) -> Self {
+        view.0
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuneBalancesFilterView {
+    /// Query by owner, support rooch address and bitcoin address
+    Owner(UnitedAddressView),
+    /// Query by bitcoin outpoint, represent by bitcoin txid and vout
+    OutPoint { txid: TxidView, vout: u32 },
+    /// Query by object ids.
+    ObjectId(ObjectIDVecView),
+    /// Query all.
+    All,
+}
+
+impl RuneBalancesFilterView {
+    pub fn into_global_state_filter(filter: RuneBalancesFilterView) -> Result<ObjectStateFilter> {
+        Ok(match filter {
+            RuneBalancesFilterView::Owner(owner) => ObjectStateFilter::ObjectTypeWithOwner {
+                object_type: RuneBalances::struct_tag(),
+                filter_out: false,
+                owner: owner.0.rooch_address.into(),
+            },
+            RuneBalancesFilterView::OutPoint { txid, vout } => {
+                let outpoint = OutPoint::new(txid.0.into_address(), vout);
+                ObjectStateFilter::ObjectId(vec![runes::derive_rune_balances_id(&outpoint)])
+            }
+            RuneBalancesFilterView::ObjectId(object_id_vec_view) => {
+                ObjectStateFilter::ObjectId(object_id_vec_view.into())
+            }
+            RuneBalancesFilterView::All => {
+                ObjectStateFilter::ObjectType(RuneBalances::struct_tag())
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RuneBalanceView {
+    /// The rune id, represent by {block}:{tx}
+    pub id: RuneIdView,
+    pub amount: StrView<u128>,
+}
+
+impl From<RuneBalance> for RuneBalanceView {
+    fn from(balance: RuneBalance) -> Self {
+        RuneBalanceView {
+            id: balance.id.into(),
+            amount: StrView(balance.amount),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct RuneBalancesView {
+    pub outpoint: OutPointView,
+    pub balances: Vec<RuneBalanceView>,
+}
+
+impl From<RuneBalances> for RuneBalancesView {
+    fn from(rune_balances: RuneBalances) -> Self {
+        RuneBalancesView {
+            outpoint: rune_balances.outpoint.into(),
+            balances: rune_balances.balances.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RuneBalancesStateView {
+    #[serde(flatten)]
+    pub metadata: ObjectMetaView,
+    pub value: RuneBalancesView,
+    #[serde(flatten)]
+    pub indexer_id: IndexerStateIDView,
+}
+
+impl TryFrom<IndexerObjectStateView> for RuneBalancesStateView {
+    type Error = anyhow::Error;
+
+    fn try_from(state: IndexerObjectStateView) -> Result<Self, Self::Error> {
+        let rune_balances = RuneBalances::from_bytes(&state.value.0)?;
+        Ok(RuneBalancesStateView {
+            metadata: state.metadata,
+            value: rune_balances.into(),
+            indexer_id: state.indexer_id,
+        })
+    }
+}
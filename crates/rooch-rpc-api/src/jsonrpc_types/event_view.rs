@@ -224,6 +224,12 @@ pub enum EventFilterView {
         /// right endpoint of transaction order, exclusive
         to_order: StrView<u64>,
     },
+    /// Return events matching all of the filters.
+    And(Vec<EventFilterView>),
+    /// Return events matching any of the filters.
+    Or(Vec<EventFilterView>),
+    /// Return events not matching the filter.
+    Not(Box<EventFilterView>),
     All,
 }
 
@@ -261,6 +267,11 @@ impl From<EventFilterView> for EventFilter {
                 from_order: from_order.0,
                 to_order: to_order.0,
             },
+            EventFilterView::And(filters) => {
+                Self::And(filters.into_iter().map(Into::into).collect())
+            }
+            EventFilterView::Or(filters) => Self::Or(filters.into_iter().map(Into::into).collect()),
+            EventFilterView::Not(filter) => Self::Not(Box::new((*filter).into())),
             EventFilterView::All => Self::All,
         }
     }
@@ -4,11 +4,17 @@
 use super::BytesView;
 use super::{HumanReadableDisplay, ModuleIdView, StateChangeSetView, StrView};
 use crate::jsonrpc_types::event_view::EventView;
-use crate::jsonrpc_types::H256View;
+use crate::jsonrpc_types::{
+    AnnotatedMoveStructView, H256View, ObjectMetaView, RoochAddressView, StructTagView,
+};
 use ethers::types::H256;
+use move_core_types::u256::U256;
 use move_core_types::vm_status::{AbortLocation, KeptVMStatus};
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::transaction::{TransactionExecutionInfo, VMErrorInfo};
+use rooch_types::transaction::state_diff::{
+    AnnotatedTransactionEvent, BalanceChange, ObjectDiff, ObjectDiffType, StateDiff,
+};
 use rooch_types::transaction::ExecuteTransactionResponse;
 use rooch_types::transaction::{authenticator::Authenticator, TransactionSequenceInfo};
 use schemars::JsonSchema;
@@ -223,10 +229,127 @@ pub struct RawTransactionOutputView {
     pub is_upgrade: bool,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectDiffTypeView {
+    New,
+    Modified,
+    Removed,
+}
+
+impl From<ObjectDiffType> for ObjectDiffTypeView {
+    fn from(diff_type: ObjectDiffType) -> Self {
+        match diff_type {
+            ObjectDiffType::New => Self::New,
+            ObjectDiffType::Modified => Self::Modified,
+            ObjectDiffType::Removed => Self::Removed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectDiffView {
+    pub diff_type: ObjectDiffTypeView,
+    pub metadata: ObjectMetaView,
+    /// The decoded value before the transaction
+    pub before: Option<serde_json::Value>,
+    /// The decoded value after the transaction
+    pub after: Option<serde_json::Value>,
+    /// The BCS bytes of the value before the transaction, returned even if it can not be decoded
+    pub before_value: Option<BytesView>,
+    /// The BCS bytes of the value after the transaction, returned even if it can not be decoded
+    pub after_value: Option<BytesView>,
+}
+
+impl From<ObjectDiff> for ObjectDiffView {
+    fn from(diff: ObjectDiff) -> Self {
+        Self {
+            diff_type: diff.diff_type.into(),
+            metadata: diff.metadata.into(),
+            before: diff
+                .before
+                .map(|value| AnnotatedMoveStructView::from(value).into()),
+            after: diff
+                .after
+                .map(|value| AnnotatedMoveStructView::from(value).into()),
+            before_value: diff.before_value.map(StrView),
+            after_value: diff.after_value.map(StrView),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceChangeView {
+    pub owner: RoochAddressView,
+    pub coin_type: StructTagView,
+    pub before: StrView<U256>,
+    pub after: StrView<U256>,
+    /// The signed balance change, e.g. `-100`
+    pub delta: String,
+}
+
+impl From<BalanceChange> for BalanceChangeView {
+    fn from(change: BalanceChange) -> Self {
+        let delta = if change.after >= change.before {
+            (change.after - change.before).to_string()
+        } else {
+            format!("-{}", change.before - change.after)
+        };
+        Self {
+            owner: change.owner.into(),
+            coin_type: change.coin_type.into(),
+            before: StrView(change.before),
+            after: StrView(change.after),
+            delta,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionEventView {
+    pub event_type: StructTagView,
+    pub event_data: BytesView,
+    pub event_index: StrView<u64>,
+    pub decoded_event_data: Option<serde_json::Value>,
+}
+
+impl From<AnnotatedTransactionEvent> for TransactionEventView {
+    fn from(event: AnnotatedTransactionEvent) -> Self {
+        Self {
+            event_type: event.event.event_type.into(),
+            event_data: StrView(event.event.event_data),
+            event_index: event.event.event_index.into(),
+            decoded_event_data: event
+                .decoded_event_data
+                .map(|value| AnnotatedMoveStructView::from(value).into()),
+        }
+    }
+}
+
+/// The state changes of a dry run transaction, with the values decoded
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateDiffView {
+    pub objects: Vec<ObjectDiffView>,
+    pub balance_changes: Vec<BalanceChangeView>,
+    pub events: Vec<TransactionEventView>,
+}
+
+impl From<StateDiff> for StateDiffView {
+    fn from(diff: StateDiff) -> Self {
+        Self {
+            objects: diff.objects.into_iter().map(Into::into).collect(),
+            balance_changes: diff.balance_changes.into_iter().map(Into::into).collect(),
+            events: diff.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DryRunTransactionResponseView {
     pub raw_output: RawTransactionOutputView,
     pub vm_error_info: VMErrorInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<StateDiffView>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -1,7 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{AbortLocationView, BytesView};
+use super::{AbortLocationView, BytesView, H256View};
 use crate::jsonrpc_types::{
     move_types::{AnnotatedMoveValueView, TypeTagView},
     StrView,
@@ -158,3 +158,29 @@ impl From<AnnotatedFunctionReturnValue> for AnnotatedFunctionReturnValueView {
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ViewFunctionResultView {
+    pub vm_status: VMStatusView,
+    pub return_values: Option<Vec<AnnotatedFunctionReturnValueView>>,
+    pub gas_used: StrView<u64>,
+}
+
+impl ViewFunctionResultView {
+    pub fn new(result: AnnotatedFunctionResult, gas_used: u64) -> Self {
+        let result = AnnotatedFunctionResultView::from(result);
+        Self {
+            vm_status: result.vm_status,
+            return_values: result.return_values,
+            gas_used: gas_used.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ViewFunctionsResultView {
+    /// The state root all the view functions were executed against
+    pub state_root: H256View,
+    /// The results in the same order as the function calls
+    pub results: Vec<ViewFunctionResultView>,
+}
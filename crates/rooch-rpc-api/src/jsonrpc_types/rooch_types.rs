@@ -7,6 +7,7 @@ use super::{
 };
 use crate::jsonrpc_types::account_view::BalanceInfoView;
 use crate::jsonrpc_types::btc::ord::InscriptionStateView;
+use crate::jsonrpc_types::btc::runes::RuneBalancesStateView;
 use crate::jsonrpc_types::btc::utxo::UTXOStateView;
 use crate::jsonrpc_types::event_view::{EventView, IndexerEventView};
 use crate::jsonrpc_types::field_view::IndexerFieldView;
@@ -36,6 +37,7 @@ pub type IndexerObjectStatePageView = PageView<IndexerObjectStateView, IndexerSt
 
 pub type UTXOPageView = PageView<UTXOStateView, IndexerStateIDView>;
 pub type InscriptionPageView = PageView<InscriptionStateView, IndexerStateIDView>;
+pub type RuneBalancesPageView = PageView<RuneBalancesStateView, IndexerStateIDView>;
 pub type StateChangeSetPageView = PageView<StateChangeSetWithTxOrderView, StrView<u64>>;
 
 pub type FieldPageView = PageView<IndexerFieldView, StrView<u64>>;
@@ -1,7 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::jsonrpc_types::H256View;
+use crate::jsonrpc_types::{H256View, StrView};
 use moveos_types::h256::H256;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -43,6 +43,35 @@ impl StateOptions {
     }
 }
 
+/// The historical state to execute against, by the state root or by the tx order.
+/// At most one of them can be specified, the latest state is used if neither is specified.
+/// It fails with the error code 5 if the state of the historical root has been pruned entirely
+/// or partially.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoricalStateOptions {
+    /// The state root to execute against
+    pub state_root: Option<H256View>,
+    /// Execute against the state root after the transaction of the tx order
+    pub tx_order: Option<StrView<u64>>,
+}
+
+impl HistoricalStateOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state_root(mut self, state_root: Option<H256>) -> Self {
+        self.state_root = state_root.map(H256View::from);
+        self
+    }
+
+    pub fn tx_order(mut self, tx_order: Option<u64>) -> Self {
+        self.tx_order = tx_order.map(StrView);
+        self
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Default)]
 #[serde(rename_all = "camelCase", default)]
 pub struct EventOptions {
@@ -7,15 +7,20 @@ use super::{
     TypeTagView, UnitedAddressView,
 };
 use anyhow::Result;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::Op;
-use moveos_types::moveos_std::object::ObjectMeta;
+use move_core_types::language_storage::TypeTag;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{is_dynamic_field_type, ObjectMeta};
 use moveos_types::state::{FieldKey, ObjectChange};
+use moveos_types::state_proof::StateProof;
 use moveos_types::state_resolver::StateKV;
 use moveos_types::{
     moveos_std::object::{human_readable_flag, ObjectID},
     state::{AnnotatedState, ObjectState, StateChangeSet},
 };
 use rooch_types::indexer::state::{IndexerStateID, ObjectStateFilter};
+use rooch_types::indexer::Filter;
 use rooch_types::state::{StateChangeSetWithTxOrder, SyncStateFilter};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -404,6 +409,12 @@ pub enum ObjectStateFilterView {
     Owner(UnitedAddressView),
     /// Query by object ids.
     ObjectId(ObjectIDVecView),
+    /// Query the objects matching all of the filters.
+    And(Vec<ObjectStateFilterView>),
+    /// Query the objects matching any of the filters.
+    Or(Vec<ObjectStateFilterView>),
+    /// Query the objects not matching the filter.
+    Not(Box<ObjectStateFilterView>),
 }
 
 impl ObjectStateFilterView {
@@ -426,10 +437,150 @@ impl ObjectStateFilterView {
             ObjectStateFilterView::ObjectId(object_id_vec_view) => {
                 ObjectStateFilter::ObjectId(object_id_vec_view.into())
             }
+            ObjectStateFilterView::And(filters) => ObjectStateFilter::And(
+                filters
+                    .into_iter()
+                    .map(|filter| Self::try_into_object_state_filter(filter, query_option.clone()))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            ObjectStateFilterView::Or(filters) => ObjectStateFilter::Or(
+                filters
+                    .into_iter()
+                    .map(|filter| Self::try_into_object_state_filter(filter, query_option.clone()))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            ObjectStateFilterView::Not(filter) => ObjectStateFilter::Not(Box::new(
+                Self::try_into_object_state_filter(*filter, query_option)?,
+            )),
         })
     }
 }
 
+impl ObjectStateFilterView {
+    fn try_matches(&self, item: &ObjectStateChangeView) -> Result<bool> {
+        let object_type_matches = |object_type: &StructTagView| match &item.metadata.object_type.0 {
+            TypeTag::Struct(struct_tag) => struct_tag.as_ref() == &object_type.0,
+            _ => false,
+        };
+        // an object transferred away still matches its previous owner
+        let owner_matches = |owner: &UnitedAddressView| {
+            owner.0.rooch_address == item.metadata.owner.0
+                || item
+                    .previous_owner
+                    .as_ref()
+                    .is_some_and(|previous_owner| owner.0.rooch_address == previous_owner.0)
+        };
+        Ok(match self {
+            ObjectStateFilterView::ObjectTypeWithOwner { object_type, owner } => {
+                object_type_matches(object_type) && owner_matches(owner)
+            }
+            ObjectStateFilterView::ObjectType(object_type) => object_type_matches(object_type),
+            ObjectStateFilterView::Owner(owner) => owner_matches(owner),
+            ObjectStateFilterView::ObjectId(object_ids) => object_ids.0.contains(&item.metadata.id),
+            ObjectStateFilterView::And(filters) => {
+                for filter in filters {
+                    if !filter.try_matches(item)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            ObjectStateFilterView::Or(filters) => {
+                for filter in filters {
+                    if filter.try_matches(item)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            ObjectStateFilterView::Not(filter) => !filter.try_matches(item)?,
+        })
+    }
+}
+
+impl Filter<ObjectStateChangeView> for ObjectStateFilterView {
+    fn matches(&self, item: &ObjectStateChangeView) -> bool {
+        self.try_matches(item).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectStateChangeTypeView {
+    New,
+    Modified,
+    Removed,
+    MetadataModified,
+}
+
+/// The change of an object state made by a transaction. Used as the item of `subscribeObjectChanges`.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectStateChangeView {
+    /// The tx order of the transaction made the change
+    pub tx_order: StrView<u64>,
+    pub change_type: ObjectStateChangeTypeView,
+    /// The object metadata after the change, or the last metadata if the object is removed
+    pub metadata: ObjectMetaView,
+    /// The owner before the change, None if the object is new or the previous owner is unknown
+    pub previous_owner: Option<RoochAddressView>,
+    /// The new object value, None if the object is removed or the change is `metadata_modified`,
+    /// e.g. only the fields of the object are changed
+    pub value: Option<BytesView>,
+}
+
+impl ObjectStateChangeView {
+    /// Flatten the object changes in the change set, include the child objects.
+    /// The dynamic field objects are skipped, same as the indexer.
+    /// The `previous_owners` are the owners of the changed objects before the transaction.
+    pub fn from_state_change_set(
+        tx_order: u64,
+        state_change_set: StateChangeSet,
+        previous_owners: &BTreeMap<ObjectID, AccountAddress>,
+    ) -> Vec<Self> {
+        let mut changes = vec![];
+        for object_change in state_change_set.changes.into_values() {
+            Self::collect_object_change(tx_order, object_change, previous_owners, &mut changes);
+        }
+        changes
+    }
+
+    fn collect_object_change(
+        tx_order: u64,
+        object_change: ObjectChange,
+        previous_owners: &BTreeMap<ObjectID, AccountAddress>,
+        changes: &mut Vec<ObjectStateChangeView>,
+    ) {
+        let ObjectChange {
+            metadata,
+            value,
+            fields,
+        } = object_change;
+        if !is_dynamic_field_type(&metadata.object_type) {
+            let (change_type, value) = match value {
+                Some(Op::New(value)) => (ObjectStateChangeTypeView::New, Some(value.into())),
+                Some(Op::Modify(value)) => {
+                    (ObjectStateChangeTypeView::Modified, Some(value.into()))
+                }
+                Some(Op::Delete) => (ObjectStateChangeTypeView::Removed, None),
+                None => (ObjectStateChangeTypeView::MetadataModified, None),
+            };
+            let previous_owner = previous_owners
+                .get(&metadata.id)
+                .map(|owner| (*owner).into());
+            changes.push(ObjectStateChangeView {
+                tx_order: tx_order.into(),
+                change_type,
+                metadata: metadata.into(),
+                previous_owner,
+                value,
+            });
+        }
+        for field_change in fields.into_values() {
+            Self::collect_object_change(tx_order, field_change, previous_owners, changes);
+        }
+    }
+}
+
 /// Object state view. Used as return type of `getObjectStates`.
 #[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ObjectStateView {
@@ -496,6 +647,39 @@ impl From<AnnotatedState> for ObjectStateView {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct StateProofView {
+    /// The global state root the proof is generated against
+    pub state_root: H256View,
+    pub object_id: ObjectIDView,
+    pub field_key: FieldKeyView,
+    /// The state of the field, None if the field does not exist
+    pub state: Option<ObjectStateView>,
+    /// The BCS encoded StateProof
+    pub proof: BytesView,
+}
+
+impl StateProofView {
+    pub fn new(
+        state_root: H256,
+        object_id: ObjectID,
+        field_key: FieldKey,
+        proof: StateProof,
+    ) -> Result<Self> {
+        Ok(Self {
+            state_root: state_root.into(),
+            object_id: object_id.into(),
+            field_key: field_key.into(),
+            state: proof.state().cloned().map(Into::into),
+            proof: bcs::to_bytes(&proof)?.into(),
+        })
+    }
+
+    pub fn decode_proof(&self) -> Result<StateProof> {
+        Ok(bcs::from_bytes(&self.proof.0)?)
+    }
+}
+
 impl HumanReadableDisplay for ObjectStateView {
     fn to_human_readable_string(&self, verbose: bool, indent: usize) -> String {
         self.metadata.to_human_readable_string(verbose, indent)
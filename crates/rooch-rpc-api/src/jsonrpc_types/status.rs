@@ -6,7 +6,7 @@ use accumulator::accumulator_info::AccumulatorInfo;
 use bitcoin::BlockHash;
 use moveos_types::h256::H256;
 use moveos_types::{startup_info::StartupInfo, state::ObjectState};
-use rooch_types::da::status::DAServerStatus;
+use rooch_types::da::status::{AdapterSubmitStatSnapshot, DAServerStatus};
 use rooch_types::into_address::FromAddress;
 use rooch_types::{
     bitcoin::types::BlockHeightHash, sequencer::SequencerInfo, service_status::ServiceStatus,
@@ -82,6 +82,9 @@ pub struct DAInfoView {
     pub last_avail_tx_order: Option<StrView<u64>>,
     pub last_avail_block_update_time: Option<StrView<u64>>,
     pub avail_backends: Vec<(String, StrView<u128>)>,
+    /// The backend identifiers and their submit counters since server start
+    #[serde(default)]
+    pub backend_submit_stats: Vec<(String, AdapterSubmitStatView)>,
 }
 
 impl From<DAServerStatus> for DAInfoView {
@@ -98,6 +101,34 @@ impl From<DAServerStatus> for DAInfoView {
                 .into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
+            backend_submit_stats: info
+                .backend_submit_stats
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AdapterSubmitStatView {
+    pub submitted_segments: StrView<u64>,
+    pub submitted_bytes: StrView<u64>,
+    /// Segments whose content had been stored before (content-addressed backends only)
+    pub deduplicated_segments: StrView<u64>,
+    pub retries: StrView<u64>,
+    /// Segments failed after all retries
+    pub failures: StrView<u64>,
+}
+
+impl From<AdapterSubmitStatSnapshot> for AdapterSubmitStatView {
+    fn from(stat: AdapterSubmitStatSnapshot) -> Self {
+        AdapterSubmitStatView {
+            submitted_segments: stat.submitted_segments.into(),
+            submitted_bytes: stat.submitted_bytes.into(),
+            deduplicated_segments: stat.deduplicated_segments.into(),
+            retries: stat.retries.into(),
+            failures: stat.failures.into(),
         }
     }
 }
@@ -6,12 +6,14 @@ use crate::jsonrpc_types::{
     H256View, TransactionExecutionInfoView, TransactionSequenceInfoView, TransactionView,
     UnitedAddressView,
 };
+use accumulator::proof::AccumulatorProof;
 use bitcoin::hashes::Hash;
 use rooch_types::address::RoochAddress;
 use rooch_types::indexer::transaction::TransactionFilter;
 use rooch_types::indexer::Filter;
 use rooch_types::transaction::{
-    L1Block, L1Transaction, LedgerTransaction, LedgerTxData, TransactionWithInfo,
+    L1Block, L1Transaction, LedgerTransaction, LedgerTxData, TransactionAccumulatorProof,
+    TransactionWithInfo,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -178,6 +180,12 @@ pub enum TransactionFilterView {
         /// right endpoint of transaction order, exclusive
         to_order: StrView<u64>,
     },
+    /// Return transactions matching all of the filters.
+    And(Vec<TransactionFilterView>),
+    /// Return transactions matching any of the filters.
+    Or(Vec<TransactionFilterView>),
+    /// Return transactions not matching the filter.
+    Not(Box<TransactionFilterView>),
     All,
 }
 
@@ -202,6 +210,13 @@ impl From<TransactionFilterView> for TransactionFilter {
                 from_order: from_order.0,
                 to_order: to_order.0,
             },
+            TransactionFilterView::And(filters) => {
+                Self::And(filters.into_iter().map(Into::into).collect())
+            }
+            TransactionFilterView::Or(filters) => {
+                Self::Or(filters.into_iter().map(Into::into).collect())
+            }
+            TransactionFilterView::Not(filter) => Self::Not(Box::new((*filter).into())),
             TransactionFilterView::All => Self::All,
         }
     }
@@ -239,6 +254,23 @@ impl TransactionFilterView {
                 from_order.0 <= item.transaction.sequence_info.tx_order.0
                     && item.transaction.sequence_info.tx_order.0 < to_order.0
             }
+            TransactionFilterView::And(filters) => {
+                for filter in filters {
+                    if !filter.try_matches(item)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            TransactionFilterView::Or(filters) => {
+                for filter in filters {
+                    if filter.try_matches(item)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            TransactionFilterView::Not(filter) => !filter.try_matches(item)?,
             TransactionFilterView::All => true,
         })
     }
@@ -249,3 +281,43 @@ impl Filter<TransactionWithInfoView> for TransactionFilterView {
         self.try_matches(item).unwrap_or_default()
     }
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionAccumulatorProofView {
+    pub tx_order: StrView<u64>,
+    pub tx_hash: H256View,
+    /// The index of the tx in the tx accumulator
+    pub leaf_index: StrView<u64>,
+    /// The tx order of the accumulator root the proof is generated against
+    pub accumulator_tx_order: StrView<u64>,
+    /// The tx accumulator root after the tx of `accumulator_tx_order` is appended
+    pub accumulator_root: H256View,
+    /// The siblings of the proof, ordered from the bottom level to the root level
+    pub siblings: Vec<H256View>,
+}
+
+impl From<TransactionAccumulatorProof> for TransactionAccumulatorProofView {
+    fn from(proof: TransactionAccumulatorProof) -> Self {
+        Self {
+            tx_order: proof.tx_order.into(),
+            tx_hash: proof.tx_hash.into(),
+            leaf_index: proof.leaf_index.into(),
+            accumulator_tx_order: proof.accumulator_tx_order.into(),
+            accumulator_root: proof.accumulator_root.into(),
+            siblings: proof.proof.siblings.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<TransactionAccumulatorProofView> for TransactionAccumulatorProof {
+    fn from(view: TransactionAccumulatorProofView) -> Self {
+        Self {
+            tx_order: view.tx_order.0,
+            tx_hash: view.tx_hash.0,
+            leaf_index: view.leaf_index.0,
+            accumulator_tx_order: view.accumulator_tx_order.0,
+            accumulator_root: view.accumulator_root.0,
+            proof: AccumulatorProof::new(view.siblings.into_iter().map(|h| h.0).collect()),
+        }
+    }
+}
@@ -11,6 +11,10 @@ use jsonrpsee::types::{ErrorCode, ErrorObject, ErrorObjectOwned};
 use rooch_types::error::RoochError;
 use thiserror::Error;
 
+/// The error code of the requests against a historical state root which has been pruned entirely
+/// or partially, the client can retry against a more recent state root.
+pub const STATE_ROOT_NOT_AVAILABLE_ERROR_CODE: i32 = 5;
+
 #[derive(Debug, Error)]
 pub enum RpcError {
     #[error("Service unavailable")]
@@ -37,8 +41,28 @@ impl From<RpcError> for ErrorObjectOwned {
                 "Service unavailable".to_string(),
                 None::<()>,
             ),
+            RpcError::RoochError(err @ RoochError::StateRootNotAvailable(_)) => ErrorObject::owned(
+                STATE_ROOT_NOT_AVAILABLE_ERROR_CODE,
+                err.to_string(),
+                None::<()>,
+            ),
             RpcError::RoochError(err) => ErrorObject::owned(1, err.to_string(), None::<()>),
-            RpcError::InternalError(err) => ErrorObject::owned(2, err.to_string(), None::<()>),
+            RpcError::InternalError(err) => {
+                // The errors of the services are wrapped in anyhow, keep the typed code of the
+                // pruned state root
+                let state_root_not_available = err.chain().any(|e| {
+                    matches!(
+                        e.downcast_ref::<RoochError>(),
+                        Some(RoochError::StateRootNotAvailable(_))
+                    )
+                });
+                let code = if state_root_not_available {
+                    STATE_ROOT_NOT_AVAILABLE_ERROR_CODE
+                } else {
+                    2
+                };
+                ErrorObject::owned(code, err.to_string(), None::<()>)
+            }
             RpcError::BcsError(err) => ErrorObject::owned(3, err.to_string(), None::<()>),
             RpcError::UnexpectedError(err) => ErrorObject::owned(4, err.to_string(), None::<()>),
         }
@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::state_proof::verify_state_proof;
 use anyhow::{Ok, Result};
 use bitcoincore_rpc::RawTx;
 use jsonrpsee::http_client::HttpClient;
@@ -13,17 +14,21 @@ use moveos_types::state::{FieldKey, MoveStructState};
 use moveos_types::{access_path::AccessPath, state::ObjectState, transaction::FunctionCall};
 use rooch_rpc_api::api::btc_api::BtcAPIClient;
 use rooch_rpc_api::api::rooch_api::RoochAPIClient;
+use rooch_rpc_api::jsonrpc_types::btc::fee::FeeRateEstimateView;
 use rooch_rpc_api::jsonrpc_types::btc::ord::{InscriptionFilterView, InscriptionObjectView};
+use rooch_rpc_api::jsonrpc_types::btc::runes::RuneBalancesFilterView;
 use rooch_rpc_api::jsonrpc_types::btc::utxo::{UTXOFilterView, UTXOObjectView};
-use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionFilterView;
+use rooch_rpc_api::jsonrpc_types::transaction_view::{
+    TransactionAccumulatorProofView, TransactionFilterView,
+};
 use rooch_rpc_api::jsonrpc_types::{
     account_view::BalanceInfoView, transaction_view::TransactionWithInfoView, InscriptionPageView,
-    Status, StructTagOrObjectIDView, UTXOPageView,
+    RuneBalancesPageView, Status, StructTagOrObjectIDView, UTXOPageView,
 };
 use rooch_rpc_api::jsonrpc_types::{
     AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView, EventOptions,
     EventPageView, FieldKeyView, ObjectIDVecView, ObjectIDView, RoochAddressView, StateOptions,
-    StatePageView, StructTagView,
+    StatePageView, StateProofView, StructTagView,
 };
 use rooch_rpc_api::jsonrpc_types::{ExecuteTransactionResponseView, ObjectStateView};
 use rooch_rpc_api::jsonrpc_types::{
@@ -31,6 +36,7 @@ use rooch_rpc_api::jsonrpc_types::{
 };
 use rooch_rpc_api::jsonrpc_types::{
     StateChangeSetPageView, StrView, SyncStateFilterView, TransactionWithInfoPageView, TxOptions,
+    ViewFunctionsResultView,
 };
 use rooch_types::address::BitcoinAddress;
 use rooch_types::bitcoin::multisign_account::MultisignAccountInfo;
@@ -74,11 +80,54 @@ impl RoochRpcClient {
         function_call: FunctionCall,
     ) -> Result<AnnotatedFunctionResultView> {
         self.http
-            .execute_view_function(function_call.into())
+            .execute_view_function(function_call.into(), None)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub async fn execute_view_functions(
+        &self,
+        function_calls: Vec<FunctionCall>,
+        state_root: Option<H256>,
+    ) -> Result<ViewFunctionsResultView> {
+        self.http
+            .execute_view_functions(
+                function_calls.into_iter().map(Into::into).collect(),
+                state_root.map(Into::into),
+            )
             .await
             .map_err(|e| anyhow::anyhow!(e))
     }
 
+    pub async fn get_state_proof(
+        &self,
+        object_id: ObjectID,
+        field_key: FieldKey,
+        state_root: Option<H256>,
+    ) -> Result<StateProofView> {
+        Ok(self
+            .http
+            .get_state_proof(
+                object_id.into(),
+                field_key.into(),
+                state_root.map(Into::into),
+            )
+            .await?)
+    }
+
+    /// Get the field state and verify it against the trusted `state_root`.
+    pub async fn get_verified_field_state(
+        &self,
+        object_id: ObjectID,
+        field_key: FieldKey,
+        state_root: H256,
+    ) -> Result<Option<ObjectState>> {
+        let proof = self
+            .get_state_proof(object_id.clone(), field_key, Some(state_root))
+            .await?;
+        verify_state_proof(state_root, &object_id, field_key, &proof)
+    }
+
     pub async fn get_states(
         &self,
         access_path: AccessPath,
@@ -149,6 +198,22 @@ impl RoochRpcClient {
             .await?)
     }
 
+    pub async fn get_transaction_accumulator_proof(
+        &self,
+        tx_order: Option<u64>,
+        tx_hash: Option<H256>,
+        accumulator_tx_order: Option<u64>,
+    ) -> Result<Option<TransactionAccumulatorProofView>> {
+        Ok(self
+            .http
+            .get_transaction_accumulator_proof(
+                tx_order.map(Into::into),
+                tx_hash.map(Into::into),
+                accumulator_tx_order.map(Into::into),
+            )
+            .await?)
+    }
+
     pub async fn query_transactions(
         &self,
         filter: TransactionFilterView,
@@ -382,6 +447,24 @@ impl RoochRpcClient {
             .await?)
     }
 
+    pub async fn query_runes(
+        &self,
+        filter: RuneBalancesFilterView,
+        cursor: Option<IndexerStateID>,
+        limit: Option<u64>,
+        query_options: Option<QueryOptions>,
+    ) -> Result<RuneBalancesPageView> {
+        Ok(self
+            .http
+            .query_runes(
+                filter,
+                cursor.map(Into::into),
+                limit.map(Into::into),
+                query_options.map(|v| v.descending),
+            )
+            .await?)
+    }
+
     pub async fn get_resource<T: MoveStructState>(
         &self,
         account: RoochAddress,
@@ -427,6 +510,16 @@ impl RoochRpcClient {
             .await?)
     }
 
+    pub async fn estimate_bitcoin_fee_rate(
+        &self,
+        num_blocks: Option<u64>,
+    ) -> Result<FeeRateEstimateView> {
+        Ok(self
+            .http
+            .estimate_fee_rate(num_blocks.map(Into::into))
+            .await?)
+    }
+
     pub async fn get_utxo_object(&self, utxo_obj_id: ObjectID) -> Result<Option<UTXOObjectView>> {
         let objects = self.get_object_states(vec![utxo_obj_id], None).await?;
         let obj_state = objects.into_iter().next().flatten();
@@ -0,0 +1,82 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::state::{FieldKey, ObjectState};
+use rooch_rpc_api::jsonrpc_types::StateProofView;
+
+/// Verify the state proof returned by `rooch_getStateProof` against a trusted state root,
+/// such as the state root committed to the state commitment chain by the proposer.
+/// The proof is verified for the `object_id` and `field_key` the caller asked for,
+/// a proof of another object or field in the response is rejected.
+/// Returns the proved state of the field, None if the field does not exist.
+pub fn verify_state_proof(
+    trusted_state_root: H256,
+    object_id: &ObjectID,
+    field_key: FieldKey,
+    proof_view: &StateProofView,
+) -> Result<Option<ObjectState>> {
+    ensure!(
+        proof_view.state_root.0 == trusted_state_root,
+        "State root mismatch, expect: {:?}, actual: {:?}",
+        trusted_state_root,
+        proof_view.state_root.0
+    );
+    ensure!(
+        proof_view.object_id.0 == *object_id,
+        "Object id mismatch, expect: {}, actual: {}",
+        object_id,
+        proof_view.object_id.0
+    );
+    ensure!(
+        proof_view.field_key.0 == field_key,
+        "Field key mismatch, expect: {}, actual: {}",
+        field_key,
+        proof_view.field_key.0
+    );
+    let proof = proof_view.decode_proof()?;
+    let state = proof.verify(trusted_state_root, object_id, field_key)?;
+    let view_state: Option<ObjectState> = proof_view.state.clone().map(Into::into);
+    ensure!(
+        state == view_state,
+        "The state in the response does not match the proved state"
+    );
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moveos_store::MoveOSStore;
+    use moveos_types::test_utils::random_state_change_set;
+
+    #[test]
+    fn test_reject_proof_of_another_field() -> Result<()> {
+        let (moveos_store, _) = MoveOSStore::mock_moveos_store()?;
+        let mut change_set = random_state_change_set();
+        let state_store = moveos_store.get_state_store();
+        state_store.apply_change_set(&mut change_set)?;
+        let state_root = change_set.state_root;
+        let root_id = ObjectID::root();
+        let field_key = *change_set.changes.keys().next().unwrap();
+
+        let proof = state_store.get_state_proof(state_root, &root_id, field_key)?;
+        let proof_view = StateProofView::new(state_root, root_id.clone(), field_key, proof)?;
+        let state = verify_state_proof(state_root, &root_id, field_key, &proof_view)?;
+        assert!(state.is_some());
+
+        // A valid proof of the field is not a proof of the field the caller asked for
+        let requested_key = FieldKey::random();
+        assert!(verify_state_proof(state_root, &root_id, requested_key, &proof_view).is_err());
+        let requested_id = ObjectID::random();
+        assert!(verify_state_proof(state_root, &requested_id, field_key, &proof_view).is_err());
+
+        // The view can not claim the requested field when the proof is for another one
+        let mut forged_view = proof_view.clone();
+        forged_view.field_key = requested_key.into();
+        assert!(verify_state_proof(state_root, &root_id, requested_key, &forged_view).is_err());
+        Ok(())
+    }
+}
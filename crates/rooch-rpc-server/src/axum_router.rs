@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::service::metrics::{ServiceMetrics, TransportProtocol};
+use crate::service::rate_limiter::{RateLimitClient, RpcRateLimiter};
 use axum::extract::{ConnectInfo, Query, State};
 use axum::http::HeaderMap;
 use axum::response::sse::{Event, KeepAlive, Sse};
@@ -9,14 +10,18 @@ use axum::response::Response;
 use axum::Json;
 use futures::{Stream, StreamExt};
 use jsonrpsee::server::RandomIntegerIdProvider;
-use jsonrpsee::types::{ErrorCode, ErrorObject, Id, InvalidRequest, Params, Request};
+use jsonrpsee::types::{
+    ErrorCode, ErrorObject, ErrorObjectOwned, Id, InvalidRequest, Params, Request,
+};
 use jsonrpsee::{
     core::server::Methods, BoundedSubscriptions, ConnectionId, MethodCallback, MethodKind,
     MethodResponse, MethodSink,
 };
 use rooch_notify::subscription_handler::SubscriptionHandler;
+use rooch_rpc_api::jsonrpc_types::btc::reorg::BitcoinReorgFilterView;
 use rooch_rpc_api::jsonrpc_types::event_view::EventFilterView;
 use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionFilterView;
+use rooch_rpc_api::jsonrpc_types::ObjectStateFilterView;
 use serde::Deserialize;
 use serde_json::value::RawValue;
 use std::convert::Infallible;
@@ -37,6 +42,7 @@ pub(crate) struct CallData<'a> {
     metrics: &'a ServiceMetrics,
     request_start: Instant,
     max_response_body_size: u32,
+    rate_limit: Option<(&'a RpcRateLimiter, RateLimitClient)>,
 }
 
 #[derive(Clone)]
@@ -46,6 +52,7 @@ pub struct JsonRpcService {
     metrics: ServiceMetrics,
     id_provider: Arc<RandomIntegerIdProvider>,
     subscription_handler: Arc<SubscriptionHandler>,
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
 }
 
 impl JsonRpcService {
@@ -53,21 +60,34 @@ impl JsonRpcService {
         methods: Methods,
         metrics: ServiceMetrics,
         subscription_handler: Arc<SubscriptionHandler>,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
     ) -> Self {
         Self {
             methods,
             metrics,
             id_provider: Arc::new(RandomIntegerIdProvider),
             subscription_handler,
+            rate_limiter,
         }
     }
 
-    fn call_data(&self) -> CallData<'_> {
+    fn rate_limit_client(
+        &self,
+        client_addr: SocketAddr,
+        headers: &HeaderMap,
+    ) -> Option<RateLimitClient> {
+        self.rate_limiter.as_ref().map(|rate_limiter| {
+            RateLimitClient::from_request(rate_limiter.config(), client_addr, headers)
+        })
+    }
+
+    fn call_data(&self, client: Option<RateLimitClient>) -> CallData<'_> {
         CallData {
             methods: &self.methods,
             metrics: &self.metrics,
             request_start: self.metrics.on_request(TransportProtocol::Http),
             max_response_body_size: MAX_RESPONSE_SIZE,
+            rate_limit: self.rate_limiter.as_deref().zip(client),
         }
     }
 
@@ -75,6 +95,7 @@ impl JsonRpcService {
         &'a self,
         bounded_subscriptions: BoundedSubscriptions,
         sink: &'b MethodSink,
+        client: Option<RateLimitClient>,
     ) -> ws::WsCallData<'c> {
         ws::WsCallData {
             metrics: &self.metrics,
@@ -84,10 +105,30 @@ impl JsonRpcService {
             bounded_subscriptions,
             id_provider: &*self.id_provider,
             sink,
+            rate_limit: self.rate_limiter.as_deref().zip(client),
         }
     }
 }
 
+/// Charge the call to the rate limits, returns the error if it is rejected.
+fn check_rate_limit(
+    rate_limit: &Option<(&RpcRateLimiter, RateLimitClient)>,
+    method: &str,
+) -> Option<ErrorObjectOwned> {
+    let (rate_limiter, client) = rate_limit.as_ref()?;
+    rate_limiter.check(method, client).err().map(|kind| {
+        ErrorObject::owned(
+            ErrorCode::ServerIsBusy.code(),
+            format!(
+                "Too many requests! Over the {} rate limit of {}",
+                kind.as_str(),
+                method
+            ),
+            None::<bool>,
+        )
+    })
+}
+
 pub fn from_template<S: Into<axum::body::Body>>(
     status: hyper::StatusCode,
     body: S,
@@ -138,11 +179,12 @@ pub async fn json_rpc_handler(
 async fn process_raw_request(
     service: &JsonRpcService,
     raw_request: &str,
-    _: SocketAddr,
-    _: HeaderMap,
+    client_addr: SocketAddr,
+    headers: HeaderMap,
 ) -> MethodResponse {
     if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-        let response: MethodResponse = process_request(request, service.call_data()).await;
+        let client = service.rate_limit_client(client_addr, &headers);
+        let response: MethodResponse = process_request(request, service.call_data(client)).await;
 
         response
     } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
@@ -162,6 +204,7 @@ async fn process_request(req: Request<'_>, call: CallData<'_>) -> MethodResponse
         max_response_body_size,
         metrics,
         request_start,
+        rate_limit,
     } = call;
 
     let params_str = match req.params().parse::<serde_json::Value>() {
@@ -182,6 +225,21 @@ async fn process_request(req: Request<'_>, call: CallData<'_>) -> MethodResponse
         params = params_str,
     );
 
+    if let Some(error) = methods
+        .method_with_name(name)
+        .and_then(|(method_name, _)| check_rate_limit(&rate_limit, method_name))
+    {
+        let response = MethodResponse::error(id, error);
+        metrics.on_result(
+            name,
+            response.is_success(),
+            response.as_error_code(),
+            request_start,
+            TransportProtocol::Http,
+        );
+        return response;
+    }
+
     let response = match methods.method_with_name(name) {
         None => {
             metrics.on_call(
@@ -277,6 +335,7 @@ pub mod ws {
         pub sink: &'a MethodSink,
         pub metrics: &'a ServiceMetrics,
         pub request_start: Instant,
+        pub rate_limit: Option<(&'a RpcRateLimiter, RateLimitClient)>,
     }
 
     // A WebSocket handler that echos any message it receives.
@@ -284,12 +343,20 @@ pub mod ws {
     // This one we'll be integration testing so it can be written in the regular way.
     pub async fn ws_json_rpc_upgrade(
         ws: WebSocketUpgrade,
+        ConnectInfo(client_addr): ConnectInfo<SocketAddr>,
         State(service): State<JsonRpcService>,
+        headers: HeaderMap,
     ) -> Response {
-        ws.on_upgrade(|ws| ws_json_rpc_handler(ws, service))
+        // The calls of the connection are charged to the client of the upgrade request
+        let client = service.rate_limit_client(client_addr, &headers);
+        ws.on_upgrade(|ws| ws_json_rpc_handler(ws, service, client))
     }
 
-    async fn ws_json_rpc_handler(mut socket: WebSocket, service: JsonRpcService) {
+    async fn ws_json_rpc_handler(
+        mut socket: WebSocket,
+        service: JsonRpcService,
+        client: Option<RateLimitClient>,
+    ) {
         // #[allow(clippy::disallowed_methods)]
         let (tx, mut rx) = mpsc::channel(100);
 
@@ -303,7 +370,7 @@ pub mod ws {
                         match message {
                             Message::Text(msg) => {
                                 let response =
-                                    process_raw_request(&service, &msg, bounded_subscriptions.clone(), &sink).await;
+                                    process_raw_request(&service, &msg, bounded_subscriptions.clone(), &sink, client.clone()).await;
                                 if let Some(response) = response {
                                     let _ = sink.try_send(response.to_result());
                                 }
@@ -338,9 +405,14 @@ pub mod ws {
         raw_request: &str,
         bounded_subscriptions: BoundedSubscriptions,
         sink: &MethodSink,
+        client: Option<RateLimitClient>,
     ) -> Option<MethodResponse> {
         if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-            process_request(request, service.ws_call_data(bounded_subscriptions, sink)).await
+            process_request(
+                request,
+                service.ws_call_data(bounded_subscriptions, sink, client),
+            )
+            .await
         } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
             Some(MethodResponse::error(
                 Id::Null,
@@ -361,6 +433,7 @@ pub mod ws {
             bounded_subscriptions,
             id_provider,
             sink,
+            rate_limit,
         } = call;
         let conn_id = ConnectionId::from(0u32); // unused
 
@@ -368,6 +441,21 @@ pub mod ws {
         let name = &req.method;
         let id = req.id;
 
+        if let Some(error) = methods
+            .method_with_name(name)
+            .and_then(|(method_name, _)| check_rate_limit(&rate_limit, method_name))
+        {
+            let response = MethodResponse::error(id, error);
+            metrics.on_result(
+                name,
+                response.is_success(),
+                response.as_error_code(),
+                request_start,
+                TransportProtocol::WebSocket,
+            );
+            return Some(response);
+        }
+
         let response = match methods.method_with_name(name) {
             None => {
                 metrics.on_call(
@@ -480,9 +568,24 @@ pub struct SSEQuery {
     filter: String,
 }
 
+fn sse_stream(rx: mpsc::Receiver<Event>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = ReceiverStream::new(rx).map(Ok);
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// A stream of a single error event
+fn sse_error(message: String) -> mpsc::Receiver<Event> {
+    let (tx, rx) = mpsc::channel::<Event>(1);
+    let _ = tx.try_send(Event::default().event("error").data(message));
+    rx
+}
+
 async fn sse_handler<T, U, S, F>(
-    State(service): State<JsonRpcService>,
-    Query(query): Query<SSEQuery>,
+    service: JsonRpcService,
+    client_addr: SocketAddr,
+    headers: HeaderMap,
+    query: SSEQuery,
+    method: &str,
     parse_filter: impl FnOnce(&str) -> Result<T, serde_json::Error>,
     subscribe: F,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>>
@@ -492,20 +595,20 @@ where
     S: Stream<Item = U> + Send + 'static,
     F: FnOnce(Arc<SubscriptionHandler>, T) -> S + Send,
 {
+    // The SSE subscriptions are charged like the JSON-RPC subscribe methods
+    let rate_limit = service
+        .rate_limiter
+        .as_deref()
+        .zip(service.rate_limit_client(client_addr, &headers));
+    if let Some(error) = check_rate_limit(&rate_limit, method) {
+        return sse_stream(sse_error(error.message().to_string()));
+    }
+
     let filter = match parse_filter(query.filter.as_str()) {
         Ok(filter) => filter,
         Err(e) => {
             tracing::error!("Failed to parse event filter: {:?}", e);
-            let (tx, rx) = mpsc::channel::<Event>(1);
-            let _ = tx
-                .send(
-                    Event::default()
-                        .event("error")
-                        .data(format!("Failed to parse event filter: {}", e)),
-                )
-                .await;
-            let stream = ReceiverStream::new(rx).map(Ok);
-            return Sse::new(stream).keep_alive(KeepAlive::default());
+            return sse_stream(sse_error(format!("Failed to parse event filter: {}", e)));
         }
     };
 
@@ -525,19 +628,23 @@ where
         }
     });
 
-    let stream = ReceiverStream::new(rx).map(Ok);
-    Sse::new(stream).keep_alive(KeepAlive::default())
+    sse_stream(rx)
 }
 
 macro_rules! create_sse_handler {
-    ($name:ident, $filter_type:ty, $subscribe_method:ident) => {
+    ($name:ident, $filter_type:ty, $subscribe_method:ident, $rpc_method:literal) => {
         pub async fn $name(
+            ConnectInfo(client_addr): ConnectInfo<SocketAddr>,
             State(service): State<JsonRpcService>,
+            headers: HeaderMap,
             Query(query): Query<SSEQuery>,
         ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
             sse_handler(
-                State(service),
-                Query(query),
+                service,
+                client_addr,
+                headers,
+                query,
+                $rpc_method,
                 |filter_str| serde_json::from_str::<$filter_type>(filter_str),
                 |handler, filter| handler.$subscribe_method(filter),
             )
@@ -549,6 +656,24 @@ macro_rules! create_sse_handler {
 create_sse_handler!(
     sse_transactions_handler,
     TransactionFilterView,
-    subscribe_transactions
+    subscribe_transactions,
+    "rooch_subscribeTransactions"
+);
+create_sse_handler!(
+    sse_events_handler,
+    EventFilterView,
+    subscribe_events,
+    "rooch_subscribeEvents"
+);
+create_sse_handler!(
+    sse_object_changes_handler,
+    ObjectStateFilterView,
+    subscribe_object_changes,
+    "rooch_subscribeObjectChanges"
+);
+create_sse_handler!(
+    sse_bitcoin_reorgs_handler,
+    BitcoinReorgFilterView,
+    subscribe_bitcoin_reorgs,
+    "rooch_subscribeBitcoinReorgs"
 );
-create_sse_handler!(sse_events_handler, EventFilterView, subscribe_events);
@@ -0,0 +1,302 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+//! GraphQL read API served alongside the JSON-RPC, for the queries joining transactions, events,
+//! object states and balances in a single round trip.
+
+use crate::graphql::types::{BalancesLoader, EventLoader, ObjectChangesLoader};
+use crate::service::aggregate_service::AggregateService;
+use crate::service::rate_limiter::{RateLimitClient, RpcRateLimiter};
+use crate::service::rpc_service::RpcService;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextValidation};
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    EmptyMutation, EmptySubscription, ErrorExtensionValues, Schema, ServerError, ValidationResult,
+};
+use axum::extract::{ConnectInfo, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::Json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+mod query;
+mod types;
+
+pub use query::QueryRoot;
+
+/// The path of the GraphQL endpoint, the GraphiQL IDE is served on GET
+pub const GRAPHQL_PATH: &str = "/graphql";
+/// The method name of the GraphQL requests in the rate limit config,
+/// a request is charged its compute units times the query complexity
+pub const GRAPHQL_RATE_LIMIT_METHOD: &str = "graphql";
+/// The error code of the requests rejected by the rate limiter
+const RATE_LIMITED_ERROR_CODE: &str = "TOO_MANY_REQUESTS";
+
+/// Reject the queries nested deeper than this, e.g. transactions -> events -> ...
+const MAX_QUERY_DEPTH: usize = 8;
+/// Reject the queries more complex than this, every field counts 1 and the connections and the
+/// lists count their page size times the fields selected in them.
+const MAX_QUERY_COMPLEXITY: usize = 10_000;
+
+pub type RoochSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn build_schema(
+    rpc_service: RpcService,
+    aggregate_service: AggregateService,
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+) -> RoochSchema {
+    let mut builder = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(rpc_service)
+        .data(aggregate_service)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY);
+    if let Some(rate_limiter) = rate_limiter {
+        builder = builder.extension(RateLimitByComplexity(rate_limiter));
+    }
+    builder.finish()
+}
+
+/// Charge the rate limiter by the query complexity, which is only known after the validation.
+/// The client is taken from the request data, the requests without a client are not limited.
+struct RateLimitByComplexity(Arc<RpcRateLimiter>);
+
+impl ExtensionFactory for RateLimitByComplexity {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RateLimitByComplexityExtension(self.0.clone()))
+    }
+}
+
+struct RateLimitByComplexityExtension(Arc<RpcRateLimiter>);
+
+#[async_trait::async_trait]
+impl Extension for RateLimitByComplexityExtension {
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let result = next.run(ctx).await?;
+        if let Some(client) = ctx.data_opt::<RateLimitClient>() {
+            let compute_units = self.0.config().compute_units(GRAPHQL_RATE_LIMIT_METHOD)
+                * result.complexity.max(1) as u64;
+            if let Err(kind) =
+                self.0
+                    .check_compute_units(GRAPHQL_RATE_LIMIT_METHOD, client, compute_units)
+            {
+                let mut error = ServerError::new(
+                    format!(
+                        "Too many requests! Over the {} rate limit of {}",
+                        kind.as_str(),
+                        GRAPHQL_RATE_LIMIT_METHOD
+                    ),
+                    None,
+                );
+                let mut extensions = ErrorExtensionValues::default();
+                extensions.set("code", RATE_LIMITED_ERROR_CODE);
+                error.extensions = Some(extensions);
+                return Err(vec![error]);
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn is_rate_limited(response: &async_graphql::Response) -> bool {
+    response.errors.iter().any(|error| {
+        error
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.get("code"))
+            .is_some_and(|code| *code == async_graphql::Value::from(RATE_LIMITED_ERROR_CODE))
+    })
+}
+
+#[derive(Clone)]
+pub struct GraphQLState {
+    schema: RoochSchema,
+    rpc_service: RpcService,
+    aggregate_service: AggregateService,
+    rate_limiter: Option<Arc<RpcRateLimiter>>,
+}
+
+impl GraphQLState {
+    pub fn new(
+        rpc_service: RpcService,
+        aggregate_service: AggregateService,
+        rate_limiter: Option<Arc<RpcRateLimiter>>,
+    ) -> Self {
+        Self {
+            schema: build_schema(
+                rpc_service.clone(),
+                aggregate_service.clone(),
+                rate_limiter.clone(),
+            ),
+            rpc_service,
+            aggregate_service,
+            rate_limiter,
+        }
+    }
+}
+
+/// The GraphQL endpoint and the GraphiQL IDE at `GRAPHQL_PATH`
+pub fn router<S>(state: GraphQLState) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    axum::Router::new()
+        .route(
+            GRAPHQL_PATH,
+            axum::routing::get(graphiql_handler).post(graphql_handler),
+        )
+        .with_state(state)
+}
+
+pub async fn graphiql_handler() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint(GRAPHQL_PATH).finish())
+}
+
+pub async fn graphql_handler(
+    ConnectInfo(client_addr): ConnectInfo<SocketAddr>,
+    State(state): State<GraphQLState>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Response {
+    // The rate limit extension charges the client after the query complexity is computed
+    let mut request = request;
+    if let Some(rate_limiter) = &state.rate_limiter {
+        let client = RateLimitClient::from_request(rate_limiter.config(), client_addr, &headers);
+        request = request.data(client);
+    }
+
+    // The loaders batch the loads of the nested fields in a request
+    let request = request
+        .data(DataLoader::new(
+            EventLoader(state.rpc_service.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            ObjectChangesLoader(state.rpc_service.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            BalancesLoader(state.aggregate_service.clone()),
+            tokio::spawn,
+        ));
+    let response = state.schema.execute(request).await;
+    if is_rate_limited(&response) {
+        return (StatusCode::TOO_MANY_REQUESTS, Json(response)).into_response();
+    }
+    Json(response).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::types::IndexerCursor;
+    use async_graphql::connection::CursorType;
+    use prometheus::Registry;
+    use rooch_config::server_config::{RateLimit, RateLimitConfig};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_schema_sdl() {
+        let sdl = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .finish()
+            .sdl();
+        for type_name in [
+            "type Transaction",
+            "type Event",
+            "type Object",
+            "type UTXO",
+            "type Inscription",
+            "type TransactionConnection",
+        ] {
+            assert!(sdl.contains(type_name), "{} is missing", type_name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_complexity() {
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .finish();
+        let is_too_complex = |response: async_graphql::Response| {
+            response
+                .errors
+                .iter()
+                .any(|error| error.message.contains("too complex"))
+        };
+
+        // The nested connections multiply the page sizes
+        let response = schema
+            .execute(
+                "{ transactions(first: 200) { edges { node { events(first: 200) \
+                 { edges { node { eventType } } } } } } }",
+            )
+            .await;
+        assert!(is_too_complex(response));
+        let response = schema
+            .execute(
+                "{ transactions(first: 200) { edges { node { senderBalances(first: 100) \
+                 { balance } } } } }",
+            )
+            .await;
+        assert!(is_too_complex(response));
+
+        // The small pages pass the validation, then fail for the services are not in the schema
+        let response = schema
+            .execute(
+                "{ transactions(first: 10) { edges { node { txHash events(first: 10) \
+                 { edges { node { eventType } } } } } } }",
+            )
+            .await;
+        assert!(!response.errors.is_empty());
+        assert!(!is_too_complex(response));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_by_complexity() {
+        let config = RateLimitConfig {
+            method_limits: BTreeMap::from([(
+                GRAPHQL_RATE_LIMIT_METHOD.to_string(),
+                RateLimit {
+                    per_second: 1,
+                    burst: 50,
+                },
+            )]),
+            ..Default::default()
+        };
+        let rate_limiter = Arc::new(RpcRateLimiter::new(config, &Registry::new()));
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .extension(RateLimitByComplexity(rate_limiter))
+            .finish();
+        let client = RateLimitClient::Ip("127.0.0.1".parse().unwrap());
+        let execute =
+            |query: &str| schema.execute(async_graphql::Request::new(query).data(client.clone()));
+
+        // A cheap query is charged less than the burst, it fails later for the services are not in the schema
+        let response = execute("{ transactions(first: 1) { edges { node { txHash } } } }").await;
+        assert!(!is_rate_limited(&response));
+
+        // The nested pages are charged by their complexity, over the burst
+        let response = execute(
+            "{ transactions(first: 10) { edges { node { txHash events(first: 10) \
+             { edges { node { eventType } } } } } } }",
+        )
+        .await;
+        assert!(is_rate_limited(&response));
+    }
+
+    #[test]
+    fn test_indexer_cursor() {
+        let cursor = IndexerCursor::decode_cursor("12:3").unwrap();
+        assert_eq!((cursor.tx_order, cursor.index), (12, 3));
+        assert_eq!(cursor.encode_cursor(), "12:3");
+        assert!(IndexerCursor::decode_cursor("12").is_err());
+    }
+}
@@ -0,0 +1,309 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::graphql::types::{
+    query_events, Balance, Event, EventFilterInput, IndexerCursor, IndexerObject, Inscription,
+    ObjectFilterInput, Transaction, TransactionFilterInput, TxOrderCursor, Utxo,
+};
+use crate::service::aggregate_service::AggregateService;
+use crate::service::rpc_service::RpcService;
+use async_graphql::connection::{Connection, CursorType, Edge};
+use async_graphql::{Context, Error, Object, Result};
+use rooch_rpc_api::api::{DEFAULT_RESULT_LIMIT_USIZE, MAX_RESULT_LIMIT_USIZE};
+use rooch_rpc_api::jsonrpc_types::btc::ord::{InscriptionFilterView, InscriptionStateView};
+use rooch_rpc_api::jsonrpc_types::btc::utxo::{UTXOFilterView, UTXOStateView};
+use rooch_rpc_api::jsonrpc_types::{H256View, ObjectIDView, UnitedAddressView};
+use rooch_types::indexer::state::{ObjectStateFilter, ObjectStateType};
+use rooch_types::indexer::transaction::TransactionFilter;
+use std::str::FromStr;
+
+/// The connections are paginated forward with `first` and `after`, `first` is capped at
+/// `MAX_RESULT_LIMIT_USIZE`. The complexity of a connection is its page size times the
+/// complexity of the selected fields.
+pub(crate) fn page_limit(first: Option<u32>) -> usize {
+    first
+        .map(|first| first as usize)
+        .unwrap_or(DEFAULT_RESULT_LIMIT_USIZE)
+        .min(MAX_RESULT_LIMIT_USIZE)
+}
+
+pub(crate) fn decode_cursor<C: CursorType>(after: Option<&String>) -> Result<Option<C>> {
+    after
+        .map(|after| C::decode_cursor(after).map_err(|e| Error::new(e.to_string())))
+        .transpose()
+}
+
+/// Build the connection from the `limit + 1` queried nodes
+pub(crate) fn build_connection<C, N>(
+    mut nodes: Vec<N>,
+    limit: usize,
+    has_previous_page: bool,
+    cursor: impl Fn(&N) -> C,
+) -> Connection<C, N>
+where
+    C: CursorType + Send + Sync,
+    N: async_graphql::OutputType,
+{
+    let has_next_page = nodes.len() > limit;
+    nodes.truncate(limit);
+    let mut connection = Connection::new(has_previous_page, has_next_page);
+    connection
+        .edges
+        .extend(nodes.into_iter().map(|node| Edge::new(cursor(&node), node)));
+    connection
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Get the transaction by the hash
+    async fn transaction(&self, ctx: &Context<'_>, tx_hash: String) -> Result<Option<Transaction>> {
+        let aggregate_service = ctx.data::<AggregateService>()?;
+        let tx_hash = H256View::from_str(&tx_hash)?.into();
+        let tx = aggregate_service
+            .get_transaction_with_info(vec![tx_hash])
+            .await?
+            .pop()
+            .flatten();
+        Ok(tx.map(Transaction::from))
+    }
+
+    /// Query the transactions by the filter, in descending tx order by default
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionFilterInput>,
+        first: Option<u32>,
+        after: Option<String>,
+        descending: Option<bool>,
+    ) -> Result<Connection<TxOrderCursor, Transaction>> {
+        let rpc_service = ctx.data::<RpcService>()?;
+        let aggregate_service = ctx.data::<AggregateService>()?;
+        let limit = page_limit(first);
+        let cursor = decode_cursor::<TxOrderCursor>(after.as_ref())?;
+        let filter = TransactionFilter::try_from(filter.unwrap_or_default())?;
+
+        let txs = rpc_service
+            .query_transactions(
+                filter,
+                cursor.map(|c| c.0),
+                limit + 1,
+                descending.unwrap_or(true),
+            )
+            .await?;
+        let txs = aggregate_service
+            .build_transaction_with_infos(txs)
+            .await?
+            .into_iter()
+            .map(Transaction::from)
+            .collect();
+        Ok(build_connection(
+            txs,
+            limit,
+            after.is_some(),
+            Transaction::cursor,
+        ))
+    }
+
+    /// Query the events by the filter, in descending order by default
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EventFilterInput>,
+        first: Option<u32>,
+        after: Option<String>,
+        descending: Option<bool>,
+        decode: Option<bool>,
+    ) -> Result<Connection<IndexerCursor, Event>> {
+        let limit = page_limit(first);
+        let cursor = decode_cursor::<IndexerCursor>(after.as_ref())?;
+        let events = query_events(
+            ctx,
+            filter.unwrap_or_default().try_into()?,
+            cursor.map(Into::into),
+            limit + 1,
+            descending.unwrap_or(true),
+            decode,
+        )
+        .await?;
+        Ok(build_connection(
+            events,
+            limit,
+            after.is_some(),
+            Event::cursor,
+        ))
+    }
+
+    /// Get the object by the id from the indexer
+    async fn object(
+        &self,
+        ctx: &Context<'_>,
+        object_id: String,
+        decode: Option<bool>,
+    ) -> Result<Option<IndexerObject>> {
+        let rpc_service = ctx.data::<RpcService>()?;
+        let object_id = ObjectIDView::from_str(&object_id)?.into();
+        let object = rpc_service
+            .query_object_states(
+                ObjectStateFilter::ObjectId(vec![object_id]),
+                None,
+                1,
+                true,
+                decode.unwrap_or(false),
+                false,
+                ObjectStateType::ObjectState,
+            )
+            .await?
+            .pop();
+        Ok(object.map(IndexerObject))
+    }
+
+    /// Query the object states by the filter, the UTXOs and the inscriptions are excluded
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn objects(
+        &self,
+        ctx: &Context<'_>,
+        filter: ObjectFilterInput,
+        first: Option<u32>,
+        after: Option<String>,
+        descending: Option<bool>,
+        decode: Option<bool>,
+    ) -> Result<Connection<IndexerCursor, IndexerObject>> {
+        let rpc_service = ctx.data::<RpcService>()?;
+        let limit = page_limit(first);
+        let cursor = decode_cursor::<IndexerCursor>(after.as_ref())?;
+        let objects = rpc_service
+            .query_object_states(
+                filter.try_into()?,
+                cursor.map(Into::into),
+                limit + 1,
+                descending.unwrap_or(true),
+                decode.unwrap_or(false),
+                false,
+                ObjectStateType::ObjectState,
+            )
+            .await?
+            .into_iter()
+            .map(IndexerObject)
+            .collect();
+        Ok(build_connection(
+            objects,
+            limit,
+            after.is_some(),
+            IndexerObject::cursor,
+        ))
+    }
+
+    /// Query the UTXOs, of the owner if given
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn utxos(
+        &self,
+        ctx: &Context<'_>,
+        owner: Option<String>,
+        first: Option<u32>,
+        after: Option<String>,
+        descending: Option<bool>,
+    ) -> Result<Connection<IndexerCursor, Utxo>> {
+        let rpc_service = ctx.data::<RpcService>()?;
+        let limit = page_limit(first);
+        let cursor = decode_cursor::<IndexerCursor>(after.as_ref())?;
+        let filter = match owner {
+            Some(owner) => UTXOFilterView::Owner(UnitedAddressView::from_str(&owner)?),
+            None => UTXOFilterView::All,
+        };
+        let utxos = rpc_service
+            .query_object_states(
+                UTXOFilterView::into_global_state_filter(filter)?,
+                cursor.map(Into::into),
+                limit + 1,
+                descending.unwrap_or(true),
+                false,
+                false,
+                ObjectStateType::UTXO,
+            )
+            .await?
+            .into_iter()
+            .map(|state| UTXOStateView::try_from(state).map(Utxo))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(build_connection(
+            utxos,
+            limit,
+            after.is_some(),
+            Utxo::cursor,
+        ))
+    }
+
+    /// Query the inscriptions, of the owner if given
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn inscriptions(
+        &self,
+        ctx: &Context<'_>,
+        owner: Option<String>,
+        first: Option<u32>,
+        after: Option<String>,
+        descending: Option<bool>,
+    ) -> Result<Connection<IndexerCursor, Inscription>> {
+        let rpc_service = ctx.data::<RpcService>()?;
+        let limit = page_limit(first);
+        let cursor = decode_cursor::<IndexerCursor>(after.as_ref())?;
+        let filter = match owner {
+            Some(owner) => InscriptionFilterView::Owner(UnitedAddressView::from_str(&owner)?),
+            None => InscriptionFilterView::All,
+        };
+        let inscriptions = rpc_service
+            .query_object_states(
+                InscriptionFilterView::into_global_state_filter(filter)?,
+                cursor.map(Into::into),
+                limit + 1,
+                descending.unwrap_or(true),
+                false,
+                false,
+                ObjectStateType::Inscription,
+            )
+            .await?
+            .into_iter()
+            .map(|state| InscriptionStateView::try_from(state).map(Inscription))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(build_connection(
+            inscriptions,
+            limit,
+            after.is_some(),
+            Inscription::cursor,
+        ))
+    }
+
+    /// Get the coin balances of the owner
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn balances(
+        &self,
+        ctx: &Context<'_>,
+        owner: String,
+        first: Option<u32>,
+        after: Option<String>,
+    ) -> Result<Connection<IndexerCursor, Balance>> {
+        let aggregate_service = ctx.data::<AggregateService>()?;
+        let limit = page_limit(first);
+        let cursor = decode_cursor::<IndexerCursor>(after.as_ref())?;
+        let owner = UnitedAddressView::from_str(&owner)?;
+        let balances = aggregate_service
+            .get_balances(owner.into(), cursor.map(Into::into), limit + 1)
+            .await?;
+
+        let has_next_page = balances.len() > limit;
+        let mut connection = Connection::new(after.is_some(), has_next_page);
+        connection
+            .edges
+            .extend(
+                balances
+                    .into_iter()
+                    .take(limit)
+                    .filter_map(|(state_id, balance_info)| {
+                        state_id.map(|state_id| Edge::new(state_id.into(), Balance(balance_info)))
+                    }),
+            );
+        Ok(connection)
+    }
+}
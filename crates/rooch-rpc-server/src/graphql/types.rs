@@ -0,0 +1,857 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::graphql::query::{build_connection, decode_cursor, page_limit};
+use crate::service::aggregate_service::AggregateService;
+use crate::service::rpc_service::RpcService;
+use async_graphql::connection::{Connection, CursorType};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{Context, Enum, InputObject, Json, Object, Result};
+use moveos_types::h256::H256;
+use rooch_rpc_api::api::DEFAULT_RESULT_LIMIT_USIZE;
+use rooch_rpc_api::jsonrpc_types::account_view::BalanceInfoView;
+use rooch_rpc_api::jsonrpc_types::btc::ord::InscriptionStateView;
+use rooch_rpc_api::jsonrpc_types::btc::utxo::UTXOStateView;
+use rooch_rpc_api::jsonrpc_types::event_view::IndexerEventView;
+use rooch_rpc_api::jsonrpc_types::transaction_view::{LedgerTxDataView, TransactionWithInfoView};
+use rooch_rpc_api::jsonrpc_types::{
+    H256View, IndexerObjectStateView, KeptVMStatusView, ObjectIDView, ObjectMetaView,
+    ObjectStateChangeTypeView, ObjectStateChangeView, StructTagView, UnitedAddressView,
+};
+use rooch_types::address::RoochAddress;
+use rooch_types::indexer::event::{EventFilter, IndexerEventID};
+use rooch_types::indexer::state::{IndexerStateID, ObjectStateFilter};
+use rooch_types::indexer::transaction::TransactionFilter;
+use rooch_types::state::SyncStateFilter;
+use rooch_types::transaction::TransactionWithInfo;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// The cursor of the transactions, the tx order
+#[derive(Debug, Clone, Copy)]
+pub struct TxOrderCursor(pub u64);
+
+impl CursorType for TxOrderCursor {
+    type Error = std::num::ParseIntError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        s.parse().map(TxOrderCursor)
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// The cursor of the events and the object states, encoded as `{tx_order}:{index}`
+#[derive(Debug, Clone, Copy)]
+pub struct IndexerCursor {
+    pub tx_order: u64,
+    pub index: u64,
+}
+
+impl CursorType for IndexerCursor {
+    type Error = anyhow::Error;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let (tx_order, index) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid cursor: {}", s))?;
+        Ok(IndexerCursor {
+            tx_order: tx_order.parse()?,
+            index: index.parse()?,
+        })
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}", self.tx_order, self.index)
+    }
+}
+
+impl From<IndexerStateID> for IndexerCursor {
+    fn from(id: IndexerStateID) -> Self {
+        Self {
+            tx_order: id.tx_order,
+            index: id.state_index,
+        }
+    }
+}
+
+impl From<IndexerCursor> for IndexerStateID {
+    fn from(cursor: IndexerCursor) -> Self {
+        IndexerStateID::new(cursor.tx_order, cursor.index)
+    }
+}
+
+impl From<IndexerCursor> for IndexerEventID {
+    fn from(cursor: IndexerCursor) -> Self {
+        IndexerEventID::new(cursor.tx_order, cursor.index)
+    }
+}
+
+#[derive(InputObject, Debug, Clone, Default)]
+pub struct TransactionFilterInput {
+    /// The sender address, support rooch address and bitcoin address
+    pub sender: Option<String>,
+    /// The transaction hashes
+    pub tx_hashes: Option<Vec<String>>,
+}
+
+impl TryFrom<TransactionFilterInput> for TransactionFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(input: TransactionFilterInput) -> anyhow::Result<Self> {
+        let mut filters = vec![];
+        if let Some(sender) = input.sender {
+            filters.push(TransactionFilter::Sender(
+                UnitedAddressView::from_str(&sender)?.into(),
+            ));
+        }
+        if let Some(tx_hashes) = input.tx_hashes {
+            let tx_hashes = tx_hashes
+                .iter()
+                .map(|tx_hash| H256View::from_str(tx_hash).map(Into::into))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            filters.push(TransactionFilter::TxHashes(tx_hashes));
+        }
+        Ok(match filters.len() {
+            0 => TransactionFilter::All,
+            1 => filters.pop().expect("filters is not empty"),
+            _ => TransactionFilter::And(filters),
+        })
+    }
+}
+
+#[derive(InputObject, Debug, Clone, Default)]
+pub struct EventFilterInput {
+    /// The event struct type, e.g. `0x3::coin::MintEvent`
+    pub event_type: Option<String>,
+    /// The sender address, support rooch address and bitcoin address
+    pub sender: Option<String>,
+    /// The hash of the transaction emitted the events
+    pub tx_hash: Option<String>,
+}
+
+impl TryFrom<EventFilterInput> for EventFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(input: EventFilterInput) -> anyhow::Result<Self> {
+        let mut filters = vec![];
+        if let Some(event_type) = input.event_type {
+            filters.push(EventFilter::EventType(
+                StructTagView::from_str(&event_type)?.into(),
+            ));
+        }
+        if let Some(sender) = input.sender {
+            filters.push(EventFilter::Sender(
+                UnitedAddressView::from_str(&sender)?.into(),
+            ));
+        }
+        if let Some(tx_hash) = input.tx_hash {
+            filters.push(EventFilter::TxHash(H256View::from_str(&tx_hash)?.into()));
+        }
+        Ok(match filters.len() {
+            0 => EventFilter::All,
+            1 => filters.pop().expect("filters is not empty"),
+            _ => EventFilter::And(filters),
+        })
+    }
+}
+
+#[derive(InputObject, Debug, Clone, Default)]
+pub struct ObjectFilterInput {
+    /// The owner address, support rooch address and bitcoin address
+    pub owner: Option<String>,
+    /// The object struct type, e.g. `0x3::coin_store::CoinStore<0x3::gas_coin::RGas>`
+    pub object_type: Option<String>,
+    /// The object ids
+    pub object_ids: Option<Vec<String>>,
+}
+
+impl TryFrom<ObjectFilterInput> for ObjectStateFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(input: ObjectFilterInput) -> anyhow::Result<Self> {
+        let mut filters = vec![];
+        if let Some(owner) = input.owner {
+            filters.push(ObjectStateFilter::Owner(
+                UnitedAddressView::from_str(&owner)?.into(),
+            ));
+        }
+        if let Some(object_type) = input.object_type {
+            filters.push(ObjectStateFilter::ObjectType(
+                StructTagView::from_str(&object_type)?.into(),
+            ));
+        }
+        if let Some(object_ids) = input.object_ids {
+            let object_ids = object_ids
+                .iter()
+                .map(|object_id| ObjectIDView::from_str(object_id).map(Into::into))
+                .collect::<Result<Vec<_>, _>>()?;
+            filters.push(ObjectStateFilter::ObjectId(object_ids));
+        }
+        Ok(match filters.len() {
+            0 => anyhow::bail!("At least one of owner, objectType and objectIds is required"),
+            1 => filters.pop().expect("filters is not empty"),
+            _ => ObjectStateFilter::And(filters),
+        })
+    }
+}
+
+pub struct Transaction {
+    sender: Option<RoochAddress>,
+    view: TransactionWithInfoView,
+}
+
+impl From<TransactionWithInfo> for Transaction {
+    fn from(tx: TransactionWithInfo) -> Self {
+        Self {
+            sender: tx.transaction.sender(),
+            view: TransactionWithInfoView::new_from_transaction_with_info(tx, None),
+        }
+    }
+}
+
+impl Transaction {
+    pub fn cursor(&self) -> TxOrderCursor {
+        TxOrderCursor(self.view.transaction.sequence_info.tx_order.0)
+    }
+}
+
+#[Object]
+impl Transaction {
+    async fn tx_order(&self) -> String {
+        self.view.transaction.sequence_info.tx_order.to_string()
+    }
+
+    async fn tx_hash(&self) -> Option<String> {
+        self.view
+            .execution_info
+            .as_ref()
+            .map(|info| info.tx_hash.to_string())
+    }
+
+    /// The sender of the L2 transaction, None for the L1 block and the L1 transaction
+    async fn sender(&self) -> Option<String> {
+        self.sender.map(|sender| sender.to_string())
+    }
+
+    /// The sequenced timestamp in milliseconds
+    async fn timestamp(&self) -> String {
+        self.view.transaction.sequence_info.tx_timestamp.to_string()
+    }
+
+    async fn state_root(&self) -> Option<String> {
+        self.view
+            .execution_info
+            .as_ref()
+            .map(|info| info.state_root.to_string())
+    }
+
+    async fn gas_used(&self) -> Option<String> {
+        self.view
+            .execution_info
+            .as_ref()
+            .map(|info| info.gas_used.to_string())
+    }
+
+    async fn status(&self) -> Option<Json<KeptVMStatusView>> {
+        self.view
+            .execution_info
+            .as_ref()
+            .map(|info| Json(info.status.clone()))
+    }
+
+    /// The transaction data, same as the `data` of the JSON-RPC `LedgerTransactionView`
+    async fn data(&self) -> Json<LedgerTxDataView> {
+        Json(self.view.transaction.data.clone())
+    }
+
+    /// The events emitted by the transaction, in ascending event index
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u32>,
+        after: Option<String>,
+        decode: Option<bool>,
+    ) -> Result<Connection<IndexerCursor, Event>> {
+        let limit = page_limit(first);
+        let Some(info) = self.view.execution_info.as_ref() else {
+            return Ok(Connection::new(false, false));
+        };
+        let events = match decode_cursor::<IndexerCursor>(after.as_ref())? {
+            Some(cursor) => {
+                let filter = EventFilter::TxHash(info.tx_hash.0);
+                query_event_views(
+                    ctx.data::<RpcService>()?,
+                    filter,
+                    Some(cursor.into()),
+                    limit + 1,
+                    false,
+                    decode.unwrap_or(false),
+                )
+                .await?
+            }
+            None => ctx
+                .data::<DataLoader<EventLoader>>()?
+                .load_one(TxEventsKey {
+                    tx_order: self.cursor().0,
+                    tx_hash: info.tx_hash.0,
+                    decode: decode.unwrap_or(false),
+                    limit: limit + 1,
+                })
+                .await?
+                .unwrap_or_default(),
+        };
+        Ok(build_connection(
+            events.into_iter().map(Event).collect(),
+            limit,
+            after.is_some(),
+            Event::cursor,
+        ))
+    }
+
+    /// The object states changed by the transaction, read from the state store.
+    /// The complexity is estimated as `DEFAULT_RESULT_LIMIT_USIZE` changes.
+    #[graphql(complexity = "DEFAULT_RESULT_LIMIT_USIZE * child_complexity")]
+    async fn object_changes(&self, ctx: &Context<'_>) -> Result<Vec<ObjectChange>> {
+        let changes = ctx
+            .data::<DataLoader<ObjectChangesLoader>>()?
+            .load_one(self.cursor().0)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(ObjectChange)
+            .collect();
+        Ok(changes)
+    }
+
+    /// The current coin balances of the sender
+    #[graphql(complexity = "page_limit(first) * child_complexity")]
+    async fn sender_balances(&self, ctx: &Context<'_>, first: Option<u32>) -> Result<Vec<Balance>> {
+        let Some(sender) = self.sender else {
+            return Ok(vec![]);
+        };
+        let balances = ctx
+            .data::<DataLoader<BalancesLoader>>()?
+            .load_one(BalancesKey {
+                owner: sender,
+                limit: page_limit(first),
+            })
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(Balance)
+            .collect();
+        Ok(balances)
+    }
+}
+
+pub(crate) async fn query_event_views(
+    rpc_service: &RpcService,
+    filter: EventFilter,
+    cursor: Option<IndexerEventID>,
+    limit: usize,
+    descending_order: bool,
+    decode: bool,
+) -> anyhow::Result<Vec<IndexerEventView>> {
+    let events = if decode {
+        rpc_service
+            .query_annotated_events(filter, cursor, limit, descending_order)
+            .await?
+            .into_iter()
+            .map(IndexerEventView::from)
+            .collect()
+    } else {
+        rpc_service
+            .query_events(filter, cursor, limit, descending_order)
+            .await?
+            .into_iter()
+            .map(IndexerEventView::from)
+            .collect()
+    };
+    Ok(events)
+}
+
+pub(crate) async fn query_events(
+    ctx: &Context<'_>,
+    filter: EventFilter,
+    cursor: Option<IndexerEventID>,
+    limit: usize,
+    descending_order: bool,
+    decode: Option<bool>,
+) -> Result<Vec<Event>> {
+    let rpc_service = ctx.data::<RpcService>()?;
+    let events = query_event_views(
+        rpc_service,
+        filter,
+        cursor,
+        limit,
+        descending_order,
+        decode.unwrap_or(false),
+    )
+    .await?
+    .into_iter()
+    .map(Event)
+    .collect();
+    Ok(events)
+}
+
+/// The first `limit` events of a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TxEventsKey {
+    pub tx_order: u64,
+    pub tx_hash: H256,
+    pub decode: bool,
+    pub limit: usize,
+}
+
+/// Load the events of the transactions in a page with a query of all the transactions
+pub struct EventLoader(pub RpcService);
+
+impl EventLoader {
+    /// The query limit is the sum of the key limits, if it is used up, the transactions after
+    /// the last returned one may miss events and are queried again.
+    async fn load_events(
+        &self,
+        mut pending: Vec<TxEventsKey>,
+        decode: bool,
+        loaded: &mut HashMap<TxEventsKey, Vec<IndexerEventView>>,
+    ) -> anyhow::Result<()> {
+        pending.sort_by_key(|key| key.tx_order);
+        while !pending.is_empty() {
+            let limit = pending.iter().map(|key| key.limit).sum::<usize>();
+            let filter = EventFilter::Or(
+                pending
+                    .iter()
+                    .map(|key| EventFilter::TxHash(key.tx_hash))
+                    .collect(),
+            );
+            let events = query_event_views(&self.0, filter, None, limit, false, decode).await?;
+            let used_up = events.len() >= limit;
+            let last_tx_order = events.last().map(|event| event.indexer_event_id.tx_order.0);
+            let mut events_by_tx: HashMap<u64, Vec<IndexerEventView>> = HashMap::new();
+            for event in events {
+                events_by_tx
+                    .entry(event.indexer_event_id.tx_order.0)
+                    .or_default()
+                    .push(event);
+            }
+
+            let mut next_pending = vec![];
+            for key in pending {
+                let mut events = events_by_tx.get(&key.tx_order).cloned().unwrap_or_default();
+                // The events are in ascending tx order, so the transactions before the last
+                // returned one have all their events returned
+                if !used_up || Some(key.tx_order) < last_tx_order || events.len() >= key.limit {
+                    events.truncate(key.limit);
+                    loaded.insert(key, events);
+                } else {
+                    next_pending.push(key);
+                }
+            }
+            pending = next_pending;
+        }
+        Ok(())
+    }
+}
+
+impl Loader<TxEventsKey> for EventLoader {
+    type Value = Vec<IndexerEventView>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(
+        &self,
+        keys: &[TxEventsKey],
+    ) -> Result<HashMap<TxEventsKey, Self::Value>, Self::Error> {
+        let mut loaded = HashMap::new();
+        for decode in [false, true] {
+            let keys = keys
+                .iter()
+                .filter(|key| key.decode == decode)
+                .copied()
+                .collect::<Vec<_>>();
+            if !keys.is_empty() {
+                self.load_events(keys, decode, &mut loaded)
+                    .await
+                    .map_err(Arc::new)?;
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+/// Load the object changes of the transactions by the tx order
+pub struct ObjectChangesLoader(pub RpcService);
+
+impl Loader<u64> for ObjectChangesLoader {
+    type Value = Vec<ObjectStateChangeView>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(&self, tx_orders: &[u64]) -> Result<HashMap<u64, Self::Value>, Self::Error> {
+        let changes = self
+            .0
+            .sync_states(tx_orders.to_vec(), SyncStateFilter::All)
+            .await
+            .map_err(Arc::new)?
+            .into_iter()
+            .map(|state_change_set| {
+                (
+                    state_change_set.tx_order,
+                    // the previous owners are not queried for the history changes
+                    ObjectStateChangeView::from_state_change_set(
+                        state_change_set.tx_order,
+                        state_change_set.state_change_set,
+                        &BTreeMap::new(),
+                    ),
+                )
+            })
+            .collect();
+        Ok(changes)
+    }
+}
+
+/// The first `limit` coin balances of an owner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BalancesKey {
+    pub owner: RoochAddress,
+    pub limit: usize,
+}
+
+/// Load the balances of the distinct owners concurrently, e.g. the senders of a page of
+/// transactions
+pub struct BalancesLoader(pub AggregateService);
+
+impl Loader<BalancesKey> for BalancesLoader {
+    type Value = Vec<BalanceInfoView>;
+    type Error = Arc<anyhow::Error>;
+
+    async fn load(
+        &self,
+        keys: &[BalancesKey],
+    ) -> Result<HashMap<BalancesKey, Self::Value>, Self::Error> {
+        let balances = futures::future::try_join_all(keys.iter().map(|key| async move {
+            let balances = self
+                .0
+                .get_balances(key.owner.into(), None, key.limit)
+                .await?
+                .into_iter()
+                .map(|(_, balance_info)| balance_info)
+                .collect::<Vec<_>>();
+            anyhow::Ok((*key, balances))
+        }))
+        .await
+        .map_err(Arc::new)?;
+        Ok(balances.into_iter().collect())
+    }
+}
+
+pub struct Event(pub IndexerEventView);
+
+impl Event {
+    pub fn cursor(&self) -> IndexerCursor {
+        IndexerCursor {
+            tx_order: self.0.indexer_event_id.tx_order.0,
+            index: self.0.indexer_event_id.event_index.0,
+        }
+    }
+}
+
+#[Object]
+impl Event {
+    async fn tx_order(&self) -> String {
+        self.0.indexer_event_id.tx_order.to_string()
+    }
+
+    /// The index of the event in the transaction
+    async fn event_index(&self) -> String {
+        self.0.indexer_event_id.event_index.to_string()
+    }
+
+    async fn event_type(&self) -> String {
+        self.0.event_type.to_string()
+    }
+
+    /// The BCS encoded event data in hex
+    async fn event_data(&self) -> String {
+        format!("0x{}", hex::encode(&self.0.event_data.0))
+    }
+
+    /// The decoded event data, only available when queried with `decode: true`
+    async fn decoded_event_data(&self) -> Option<Json<serde_json::Value>> {
+        self.0.decoded_event_data.clone().map(Json)
+    }
+
+    async fn tx_hash(&self) -> String {
+        self.0.tx_hash.to_string()
+    }
+
+    async fn sender(&self) -> String {
+        self.0.sender.to_string()
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_string()
+    }
+}
+
+pub struct ObjectMeta(pub ObjectMetaView);
+
+#[Object]
+impl ObjectMeta {
+    async fn id(&self) -> String {
+        self.0.id.to_string()
+    }
+
+    async fn owner(&self) -> String {
+        self.0.owner.to_string()
+    }
+
+    async fn owner_bitcoin_address(&self) -> Option<String> {
+        self.0.owner_bitcoin_address.clone()
+    }
+
+    async fn object_type(&self) -> String {
+        self.0.object_type.to_string()
+    }
+
+    async fn flag(&self) -> u8 {
+        self.0.flag
+    }
+
+    async fn state_root(&self) -> Option<String> {
+        self.0
+            .state_root
+            .as_ref()
+            .map(|state_root| state_root.to_string())
+    }
+
+    async fn size(&self) -> String {
+        self.0.size.to_string()
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_string()
+    }
+
+    async fn updated_at(&self) -> String {
+        self.0.updated_at.to_string()
+    }
+}
+
+pub struct IndexerObject(pub IndexerObjectStateView);
+
+impl IndexerObject {
+    pub fn cursor(&self) -> IndexerCursor {
+        IndexerCursor {
+            tx_order: self.0.indexer_id.tx_order.0,
+            index: self.0.indexer_id.state_index.0,
+        }
+    }
+}
+
+#[Object(name = "Object")]
+impl IndexerObject {
+    async fn metadata(&self) -> ObjectMeta {
+        ObjectMeta(self.0.metadata.clone())
+    }
+
+    /// The BCS encoded object value in hex
+    async fn value(&self) -> String {
+        self.0.value.to_string()
+    }
+
+    /// The decoded object value, only available when queried with `decode: true`
+    async fn decoded_value(&self) -> Option<Json<serde_json::Value>> {
+        self.0.decoded_value.clone().map(Json)
+    }
+}
+
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectChangeType {
+    New,
+    Modified,
+    Removed,
+    MetadataModified,
+}
+
+impl From<ObjectStateChangeTypeView> for ObjectChangeType {
+    fn from(change_type: ObjectStateChangeTypeView) -> Self {
+        match change_type {
+            ObjectStateChangeTypeView::New => ObjectChangeType::New,
+            ObjectStateChangeTypeView::Modified => ObjectChangeType::Modified,
+            ObjectStateChangeTypeView::Removed => ObjectChangeType::Removed,
+            ObjectStateChangeTypeView::MetadataModified => ObjectChangeType::MetadataModified,
+        }
+    }
+}
+
+pub struct ObjectChange(pub ObjectStateChangeView);
+
+#[Object]
+impl ObjectChange {
+    async fn change_type(&self) -> ObjectChangeType {
+        self.0.change_type.into()
+    }
+
+    /// The object metadata after the change, or the last metadata if the object is removed
+    async fn metadata(&self) -> ObjectMeta {
+        ObjectMeta(self.0.metadata.clone())
+    }
+
+    /// The BCS encoded new object value in hex, None if the object is removed or only the
+    /// metadata is changed
+    async fn value(&self) -> Option<String> {
+        self.0.value.as_ref().map(|value| value.to_string())
+    }
+}
+
+pub struct Utxo(pub UTXOStateView);
+
+impl Utxo {
+    pub fn cursor(&self) -> IndexerCursor {
+        IndexerCursor {
+            tx_order: self.0.indexer_id.tx_order.0,
+            index: self.0.indexer_id.state_index.0,
+        }
+    }
+}
+
+#[Object(name = "UTXO")]
+impl Utxo {
+    async fn metadata(&self) -> ObjectMeta {
+        ObjectMeta(self.0.metadata.clone())
+    }
+
+    /// The bitcoin transaction id
+    async fn txid(&self) -> String {
+        self.0.value.bitcoin_txid.to_string()
+    }
+
+    async fn vout(&self) -> u32 {
+        self.0.value.vout
+    }
+
+    /// The value in satoshis
+    async fn value(&self) -> String {
+        self.0.value.value.to_string()
+    }
+
+    /// The object ids sealed in the UTXO, grouped by the protocol
+    async fn seals(&self) -> Json<HashMap<String, Vec<ObjectIDView>>> {
+        Json(self.0.value.seals.clone())
+    }
+}
+
+pub struct Inscription(pub InscriptionStateView);
+
+impl Inscription {
+    pub fn cursor(&self) -> IndexerCursor {
+        IndexerCursor {
+            tx_order: self.0.indexer_id.tx_order.0,
+            index: self.0.indexer_id.state_index.0,
+        }
+    }
+}
+
+#[Object]
+impl Inscription {
+    async fn metadata(&self) -> ObjectMeta {
+        ObjectMeta(self.0.metadata.clone())
+    }
+
+    /// The inscription id, `{txid}i{index}`
+    async fn inscription_id(&self) -> String {
+        self.0.value.id.to_string()
+    }
+
+    async fn inscription_number(&self) -> i32 {
+        self.0.value.inscription_number
+    }
+
+    async fn sequence_number(&self) -> u32 {
+        self.0.value.sequence_number
+    }
+
+    /// The sat point of the inscription, `{txid}:{vout}:{offset}`
+    async fn location(&self) -> String {
+        let location = &self.0.value.location;
+        format!(
+            "{}:{}:{}",
+            location.output.txid, location.output.vout, location.offset
+        )
+    }
+
+    async fn charms(&self) -> u16 {
+        self.0.value.charms
+    }
+
+    /// The inscription body in hex
+    async fn body(&self) -> String {
+        self.0.value.body.to_string()
+    }
+
+    async fn content_type(&self) -> Option<String> {
+        self.0
+            .value
+            .content_type
+            .as_ref()
+            .map(|content_type| content_type.to_string())
+    }
+
+    async fn content_encoding(&self) -> Option<String> {
+        self.0
+            .value
+            .content_encoding
+            .as_ref()
+            .map(|content_encoding| content_encoding.to_string())
+    }
+
+    async fn metaprotocol(&self) -> Option<String> {
+        self.0
+            .value
+            .metaprotocol
+            .as_ref()
+            .map(|metaprotocol| metaprotocol.to_string())
+    }
+
+    async fn parents(&self) -> Vec<String> {
+        self.0
+            .value
+            .parents
+            .iter()
+            .map(|parent| parent.to_string())
+            .collect()
+    }
+
+    async fn pointer(&self) -> Option<String> {
+        self.0.value.pointer.map(|pointer| pointer.to_string())
+    }
+}
+
+pub struct Balance(pub BalanceInfoView);
+
+#[Object]
+impl Balance {
+    async fn coin_type(&self) -> String {
+        self.0.coin_info.coin_type.to_string()
+    }
+
+    async fn name(&self) -> String {
+        self.0.coin_info.name.clone()
+    }
+
+    async fn symbol(&self) -> String {
+        self.0.coin_info.symbol.clone()
+    }
+
+    async fn decimals(&self) -> u8 {
+        self.0.coin_info.decimals
+    }
+
+    async fn balance(&self) -> String {
+        self.0.balance.to_string()
+    }
+}
@@ -8,6 +8,7 @@ use crate::service::aggregate_service::AggregateService;
 use crate::service::blocklist::{BlockListLayer, BlocklistConfig};
 use crate::service::error::ErrorHandler;
 use crate::service::metrics::ServiceMetrics;
+use crate::service::rate_limiter::RpcRateLimiter;
 use crate::service::rpc_service::RpcService;
 use anyhow::{ensure, Error, Result};
 use axum::http::{HeaderValue, Method};
@@ -18,8 +19,9 @@ use coerce::actor::{system::ActorSystem, IntoActor};
 use jsonrpsee::RpcModule;
 use moveos_eventbus::bus::EventBus;
 use raw_store::errors::RawStoreError;
+use rooch_config::config::Config;
 use rooch_config::da_config::derive_namespace_from_genesis;
-use rooch_config::server_config::ServerConfig;
+use rooch_config::server_config::{RateLimitConfig, ServerConfig};
 use rooch_config::settings::PROPOSER_CHECK_INTERVAL;
 use rooch_config::{RoochOpt, ServerOpt};
 use rooch_da::actor::server::DAServerActor;
@@ -38,6 +40,8 @@ use rooch_pipeline_processor::actor::processor::PipelineProcessorActor;
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
 use rooch_proposer::actor::messages::ProposeBlock;
 use rooch_proposer::actor::proposer::ProposerActor;
+use rooch_pruner::actor::messages::RunOnlineGC;
+use rooch_pruner::actor::online_gc::OnlineGCActor;
 use rooch_relayer::actor::messages::RelayTick;
 use rooch_relayer::actor::relayer::RelayerActor;
 use rooch_rpc_api::api::RoochRpcModule;
@@ -65,6 +69,7 @@ use tower_http::trace::TraceLayer;
 use tracing::{error, info};
 
 mod axum_router;
+pub mod graphql;
 pub mod metrics_server;
 pub mod server;
 pub mod service;
@@ -190,7 +195,11 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         process::exit(1);
     }));
 
-    let config = ServerConfig::new_with_port(opt.port());
+    let rate_limit_config = match &opt.rate_limit_config {
+        Some(path) => RateLimitConfig::load(path)?,
+        None => RateLimitConfig::default(),
+    };
+    let config = ServerConfig::new_with_port(opt.port()).with_rate_limit(rate_limit_config);
     let actor_system = ActorSystem::global_system();
 
     // start prometheus server
@@ -386,6 +395,25 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         None
     };
 
+    // Init online GC
+    if opt.gc.enable {
+        let online_gc = OnlineGCActor::new(
+            rooch_db.clone(),
+            &network.chain_id,
+            &opt.gc,
+            &prometheus_registry,
+        )?
+        .into_actor(Some("OnlineGC"), &actor_system)
+        .await?;
+        let online_gc_timer = Timer::start(
+            online_gc,
+            Duration::from_secs(opt.gc.interval()),
+            RunOnlineGC {},
+        );
+        timers.push(online_gc_timer);
+        info!("Online GC is enabled, interval: {}s", opt.gc.interval());
+    }
+
     let mut processor = PipelineProcessorActor::new(
         executor_proxy.clone(),
         sequencer_proxy.clone(),
@@ -520,6 +548,20 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
     );
 
     let governor_limiter = governor_conf.limiter().clone();
+    let rpc_rate_limiter = if config.rate_limit.is_enabled() {
+        info!(
+            "RPC rate limit enabled, method limits: {}, api keys: {}",
+            config.rate_limit.method_limits.len(),
+            config.rate_limit.api_key_limits.len()
+        );
+        Some(Arc::new(RpcRateLimiter::new(
+            config.rate_limit.clone(),
+            &prometheus_registry,
+        )))
+    } else {
+        None
+    };
+    let rpc_rate_limiter_clone = rpc_rate_limiter.clone();
     let interval = Duration::from_secs(60);
 
     // a separate background task to clean up
@@ -533,6 +575,9 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
             tick.tick().await;
             tracing::info!("rate limiting storage size: {}", governor_limiter.len());
             governor_limiter.retain_recent();
+            if let Some(rpc_rate_limiter) = &rpc_rate_limiter_clone {
+                rpc_rate_limiter.retain_recent();
+            }
         }
     });
 
@@ -570,6 +615,7 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         rpc_module_builder.module.clone().into(),
         ServiceMetrics::new(&prometheus_registry, &methods_names),
         subscription_handle,
+        rpc_rate_limiter.clone(),
     );
 
     let mut router = axum::Router::new();
@@ -595,6 +641,14 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
                 .route(
                     "/subscribe/sse/transactions",
                     axum::routing::get(crate::axum_router::sse_transactions_handler),
+                )
+                .route(
+                    "/subscribe/sse/object_changes",
+                    axum::routing::get(crate::axum_router::sse_object_changes_handler),
+                )
+                .route(
+                    "/subscribe/sse/bitcoin_reorgs",
+                    axum::routing::get(crate::axum_router::sse_bitcoin_reorgs_handler),
                 );
         }
         ServiceType::Http => {
@@ -607,6 +661,14 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
                 .route(
                     "/subscribe/sse/transactions",
                     axum::routing::get(crate::axum_router::sse_transactions_handler),
+                )
+                .route(
+                    "/subscribe/sse/object_changes",
+                    axum::routing::get(crate::axum_router::sse_object_changes_handler),
+                )
+                .route(
+                    "/subscribe/sse/bitcoin_reorgs",
+                    axum::routing::get(crate::axum_router::sse_bitcoin_reorgs_handler),
                 );
         }
         ServiceType::WebSocket => {
@@ -622,6 +684,15 @@ pub async fn run_start_server(opt: RoochOpt, server_opt: ServerOpt) -> Result<Se
         }
     }
 
+    if opt.graphql && !opt.service_type.is_web_socket() {
+        router = router.merge(graphql::router(graphql::GraphQLState::new(
+            rpc_service.clone(),
+            aggregate_service.clone(),
+            rpc_rate_limiter,
+        )));
+        info!("GraphQL endpoint is enabled at {}", graphql::GRAPHQL_PATH);
+    }
+
     let app = router.with_state(ser).layer(middleware);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
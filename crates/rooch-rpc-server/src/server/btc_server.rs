@@ -3,14 +3,17 @@
 
 use crate::service::rpc_service::RpcService;
 use anyhow::Result;
+use bitcoin_client::fee::{DEFAULT_FEE_ESTIMATE_BLOCKS, MAX_FEE_ESTIMATE_BLOCKS};
 use bitcoincore_rpc::bitcoin::Txid;
 use jsonrpsee::{core::async_trait, RpcModule};
 use rooch_rpc_api::api::btc_api::BtcAPIServer;
 use rooch_rpc_api::api::{RoochRpcModule, DEFAULT_RESULT_LIMIT_USIZE, MAX_RESULT_LIMIT_USIZE};
+use rooch_rpc_api::jsonrpc_types::btc::fee::FeeRateEstimateView;
 use rooch_rpc_api::jsonrpc_types::btc::ord::{InscriptionFilterView, InscriptionStateView};
+use rooch_rpc_api::jsonrpc_types::btc::runes::{RuneBalancesFilterView, RuneBalancesStateView};
 use rooch_rpc_api::jsonrpc_types::btc::utxo::{UTXOFilterView, UTXOStateView};
 use rooch_rpc_api::jsonrpc_types::{
-    BytesView, IndexerStateIDView, InscriptionPageView, StrView, UTXOPageView,
+    BytesView, IndexerStateIDView, InscriptionPageView, RuneBalancesPageView, StrView, UTXOPageView,
 };
 use rooch_rpc_api::RpcResult;
 use rooch_types::indexer::state::ObjectStateType;
@@ -116,6 +119,50 @@ impl BtcAPIServer for BtcServer {
         })
     }
 
+    async fn query_runes(
+        &self,
+        filter: RuneBalancesFilterView,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<IndexerStateIDView>,
+        limit: Option<StrView<u64>>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<RuneBalancesPageView> {
+        let limit_of = min(
+            limit.map(Into::into).unwrap_or(DEFAULT_RESULT_LIMIT_USIZE),
+            MAX_RESULT_LIMIT_USIZE,
+        );
+        let descending_order = descending_order.unwrap_or(true);
+
+        let global_state_filter = RuneBalancesFilterView::into_global_state_filter(filter)?;
+        let object_states = self
+            .rpc_service
+            .query_object_states(
+                global_state_filter,
+                cursor.map(Into::into),
+                limit_of + 1,
+                descending_order,
+                false,
+                false,
+                ObjectStateType::RuneBalances,
+            )
+            .await?;
+
+        let mut data = object_states
+            .into_iter()
+            .map(RuneBalancesStateView::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let has_next_page = data.len() > limit_of;
+        data.truncate(limit_of);
+        let next_cursor = data.last().cloned().map_or(cursor, |t| Some(t.indexer_id));
+
+        Ok(RuneBalancesPageView {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
     async fn broadcast_tx(
         &self,
         hex: BytesView,
@@ -130,6 +177,29 @@ impl BtcAPIServer for BtcServer {
 
         Ok(txid.to_string())
     }
+
+    async fn estimate_fee_rate(
+        &self,
+        num_blocks: Option<StrView<u64>>,
+    ) -> RpcResult<FeeRateEstimateView> {
+        let num_blocks = min(
+            num_blocks
+                .map(Into::into)
+                .unwrap_or(DEFAULT_FEE_ESTIMATE_BLOCKS),
+            MAX_FEE_ESTIMATE_BLOCKS,
+        )
+        .max(1);
+        let (fee_rate, block_count, latest_block_height) = self
+            .rpc_service
+            .estimate_bitcoin_fee_rate(num_blocks)
+            .await?;
+
+        Ok(FeeRateEstimateView {
+            sat_per_vbyte: fee_rate.to_sat_per_vb_ceil().into(),
+            block_count: block_count.into(),
+            latest_block_height: latest_block_height.map(Into::into),
+        })
+    }
 }
 
 impl RoochRpcModule for BtcServer {
@@ -16,20 +16,25 @@ use moveos_types::{
     moveos_std::{move_module::MoveModule, object::ObjectID},
     state::{AnnotatedState, FieldKey},
 };
-use rooch_rpc_api::api::MAX_INTERNAL_LIMIT_USIZE;
+use rooch_rpc_api::api::{MAX_INTERNAL_LIMIT_USIZE, MAX_VIEW_FUNCTION_CALLS};
 use rooch_rpc_api::jsonrpc_types::field_view::FieldFilterView;
 use rooch_rpc_api::jsonrpc_types::{
     account_view::BalanceInfoView,
+    btc::reorg::BitcoinReorgFilterView,
     event_view::{EventFilterView, EventView, IndexerEventIDView, IndexerEventView},
-    transaction_view::{TransactionFilterView, TransactionWithInfoView},
+    transaction_view::{
+        TransactionAccumulatorProofView, TransactionFilterView, TransactionWithInfoView,
+    },
     AccessPathView, BalanceInfoPageView, DryRunTransactionResponseView,
     EnumStructTagOrObjectIDView, EventOptions, EventPageView, ExecuteTransactionResponseView,
-    FieldPageView, FunctionCallView, H256View, IndexerEventPageView, IndexerObjectStatePageView,
-    IndexerStateIDView, ModuleABIView, ObjectIDVecView, ObjectIDView, ObjectStateFilterView,
-    ObjectStateView, QueryOptions, RawTransactionOutputView, RoochAddressView,
-    StateChangeSetPageView, StateChangeSetWithTxOrderView, StateKVView, StateOptions,
-    StatePageView, StrView, StructTagOrObjectIDView, StructTagView, SyncStateFilterView,
-    TransactionWithInfoPageView, TxOptions, UnitedAddressView,
+    FieldKeyView, FieldPageView, FunctionCallView, H256View, HistoricalStateOptions,
+    IndexerEventPageView, IndexerObjectStatePageView, IndexerStateIDView, ModuleABIView,
+    ObjectIDVecView, ObjectIDView, ObjectStateFilterView, ObjectStateView, QueryOptions,
+    RawTransactionOutputView, RoochAddressView, StateChangeSetPageView,
+    StateChangeSetWithTxOrderView, StateKVView, StateOptions, StatePageView, StateProofView,
+    StrView, StructTagOrObjectIDView, StructTagView, SyncStateFilterView,
+    TransactionWithInfoPageView, TxOptions, UnitedAddressView, ViewFunctionResultView,
+    ViewFunctionsResultView,
 };
 use rooch_rpc_api::jsonrpc_types::{
     repair_view::{RepairIndexerParamsView, RepairIndexerTypeView},
@@ -170,10 +175,31 @@ impl RoochAPIServer for RoochServer {
         Ok(result)
     }
 
-    async fn dry_run(&self, payload: BytesView) -> RpcResult<DryRunTransactionResponseView> {
-        let tx = bcs::from_bytes::<RoochTransactionData>(&payload.0)?;
+    async fn dry_run(
+        &self,
+        payload: BytesView,
+        historical_state: Option<HistoricalStateOptions>,
+    ) -> RpcResult<DryRunTransactionResponseView> {
+        // The unsigned transaction data, or the signed transaction, e.g. a sponsored transaction
+        // to dry run with the gas paid by the sponsor
+        let (tx, authenticator) = match bcs::from_bytes::<RoochTransactionData>(&payload.0) {
+            Ok(tx) => (tx, None),
+            Err(_) => {
+                let tx = bcs::from_bytes::<RoochTransaction>(&payload.0)?;
+                (tx.data, Some(tx.authenticator))
+            }
+        };
         let tx_hash = tx.tx_hash();
-        let tx_result = self.rpc_service.dry_run_tx(tx).await?;
+        let historical_state = historical_state.unwrap_or_default();
+        let tx_result = self
+            .rpc_service
+            .dry_run_tx(
+                tx,
+                authenticator,
+                historical_state.state_root.map(Into::into),
+                historical_state.tx_order.map(|v| v.0),
+            )
+            .await?;
         let raw_output = tx_result.raw_output;
         let raw_output_view = RawTransactionOutputView {
             tx_hash: tx_hash.into(),
@@ -185,6 +211,7 @@ impl RoochAPIServer for RoochServer {
         let tx_response = DryRunTransactionResponseView {
             raw_output: raw_output_view,
             vm_error_info: tx_result.vm_error_info.unwrap_or_default(),
+            state_diff: Some(tx_result.state_diff.into()),
         };
         Ok(tx_response)
     }
@@ -192,14 +219,48 @@ impl RoochAPIServer for RoochServer {
     async fn execute_view_function(
         &self,
         function_call: FunctionCallView,
+        historical_state: Option<HistoricalStateOptions>,
     ) -> RpcResult<AnnotatedFunctionResultView> {
+        let historical_state = historical_state.unwrap_or_default();
         Ok(self
             .rpc_service
-            .execute_view_function(function_call.into())
+            .execute_view_function(
+                function_call.into(),
+                historical_state.state_root.map(Into::into),
+                historical_state.tx_order.map(|v| v.0),
+            )
             .await?
             .into())
     }
 
+    async fn execute_view_functions(
+        &self,
+        function_calls: Vec<FunctionCallView>,
+        state_root: Option<H256View>,
+    ) -> RpcResult<ViewFunctionsResultView> {
+        if function_calls.is_empty() || function_calls.len() > MAX_VIEW_FUNCTION_CALLS {
+            return Err(RpcError::UnexpectedError(format!(
+                "the number of function calls should be between 1 and {}",
+                MAX_VIEW_FUNCTION_CALLS
+            )));
+        }
+        let result = self
+            .rpc_service
+            .execute_view_functions(
+                function_calls.into_iter().map(Into::into).collect(),
+                state_root.map(Into::into),
+            )
+            .await?;
+        Ok(ViewFunctionsResultView {
+            state_root: result.state_root.into(),
+            results: result
+                .results
+                .into_iter()
+                .map(|(result, gas_used)| ViewFunctionResultView::new(result, gas_used))
+                .collect(),
+        })
+    }
+
     async fn get_states(
         &self,
         access_path: AccessPathView,
@@ -255,6 +316,27 @@ impl RoochAPIServer for RoochServer {
         Ok(state_views)
     }
 
+    async fn get_state_proof(
+        &self,
+        object_id: ObjectIDView,
+        field_key: FieldKeyView,
+        state_root: Option<H256View>,
+    ) -> RpcResult<StateProofView> {
+        let object_id: ObjectID = object_id.into();
+        let field_key: FieldKey = field_key.into();
+        let (state_root, proof) = self
+            .rpc_service
+            .get_state_proof(
+                object_id.clone(),
+                field_key,
+                state_root.map(|h256_view| h256_view.0),
+            )
+            .await?;
+        Ok(StateProofView::new(
+            state_root, object_id, field_key, proof,
+        )?)
+    }
+
     async fn list_states(
         &self,
         access_path: AccessPathView,
@@ -500,6 +582,48 @@ impl RoochAPIServer for RoochServer {
         Ok(data)
     }
 
+    async fn get_transaction_accumulator_proof(
+        &self,
+        tx_order: Option<StrView<u64>>,
+        tx_hash: Option<H256View>,
+        accumulator_tx_order: Option<StrView<u64>>,
+    ) -> RpcResult<Option<TransactionAccumulatorProofView>> {
+        let tx_order = match (tx_order, tx_hash) {
+            (Some(tx_order), None) => tx_order.0,
+            (Some(tx_order), Some(tx_hash)) => {
+                // Both are provided, they must refer to the same transaction,
+                // otherwise the caller would get a proof of a tx it did not ask for.
+                let tx_hashes = self.rpc_service.get_tx_hashes(vec![tx_order.0]).await?;
+                match tx_hashes.first().cloned().flatten() {
+                    Some(hash) if hash == tx_hash.0 => tx_order.0,
+                    Some(hash) => {
+                        return Err(RpcError::UnexpectedError(format!(
+                            "tx_order {} and tx_hash {:?} refer to different transactions, the hash of tx_order {} is {:?}",
+                            tx_order.0, tx_hash.0, tx_order.0, hash
+                        )))
+                    }
+                    None => return Ok(None),
+                }
+            }
+            (None, Some(tx_hash)) => {
+                match self.rpc_service.get_transaction_by_hash(tx_hash.0).await? {
+                    Some(tx) => tx.sequence_info.tx_order,
+                    None => return Ok(None),
+                }
+            }
+            (None, None) => {
+                return Err(RpcError::UnexpectedError(
+                    "tx_order or tx_hash is required".to_string(),
+                ))
+            }
+        };
+        let proof = self
+            .rpc_service
+            .get_tx_accumulator_proof(tx_order, accumulator_tx_order.map(|order| order.0))
+            .await?;
+        Ok(proof.map(Into::into))
+    }
+
     async fn get_transactions_by_order(
         &self,
         cursor: Option<StrView<u64>>,
@@ -960,8 +1084,10 @@ impl RoochAPIServer for RoochServer {
         &self,
         sink: PendingSubscriptionSink,
         filter: EventFilterView,
+        from_tx_order: Option<StrView<u64>>,
     ) -> SubscriptionResult {
-        self.rpc_service.subscribe_events(sink, filter)?;
+        self.rpc_service
+            .subscribe_events(sink, filter, from_tx_order.map(|v| v.0))?;
         Ok(())
     }
 
@@ -969,8 +1095,28 @@ impl RoochAPIServer for RoochServer {
         &self,
         sink: PendingSubscriptionSink,
         filter: TransactionFilterView,
+        from_tx_order: Option<StrView<u64>>,
+    ) -> SubscriptionResult {
+        self.rpc_service
+            .subscribe_transactions(sink, filter, from_tx_order.map(|v| v.0))?;
+        Ok(())
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        sink: PendingSubscriptionSink,
+        filter: ObjectStateFilterView,
+    ) -> SubscriptionResult {
+        self.rpc_service.subscribe_object_changes(sink, filter)?;
+        Ok(())
+    }
+
+    fn subscribe_bitcoin_reorgs(
+        &self,
+        sink: PendingSubscriptionSink,
+        filter: BitcoinReorgFilterView,
     ) -> SubscriptionResult {
-        self.rpc_service.subscribe_transactions(sink, filter)?;
+        self.rpc_service.subscribe_bitcoin_reorgs(sink, filter)?;
         Ok(())
     }
 }
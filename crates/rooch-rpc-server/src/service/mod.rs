@@ -10,3 +10,5 @@ pub mod routing;
 
 pub mod blocklist;
 pub mod metrics;
+pub mod rate_limiter;
+mod subscription_replay;
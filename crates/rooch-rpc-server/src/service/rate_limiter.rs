@@ -0,0 +1,351 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use dashmap::DashMap;
+use http::HeaderMap;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use rooch_config::server_config::{RateLimit, RateLimitConfig};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+/// The buckets idle for longer than this are dropped by `retain_recent`
+const BUCKET_IDLE_EXPIRATION: Duration = Duration::from_secs(600);
+
+/// The client of a JSON-RPC call, identified by the API key or the IP
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitClient {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+impl RateLimitClient {
+    /// The API key is used only if it is configured in `api_key_limits`, otherwise the client
+    /// is identified by the forwarded IP if the peer is a trusted proxy, or the peer IP.
+    pub fn from_request(
+        config: &RateLimitConfig,
+        client_addr: SocketAddr,
+        headers: &HeaderMap,
+    ) -> Self {
+        if let Some(api_key) = headers
+            .get(config.api_key_header.as_str())
+            .and_then(|value| value.to_str().ok())
+        {
+            if config.api_key_limits.contains_key(api_key) {
+                return Self::ApiKey(api_key.to_string());
+            }
+        }
+        let peer_ip = client_addr.ip();
+        if !config.trusted_proxies.contains(&peer_ip) {
+            return Self::Ip(peer_ip);
+        }
+        Self::Ip(Self::forwarded_ip(config, headers).unwrap_or(peer_ip))
+    }
+
+    /// Every proxy appends its peer to `x-forwarded-for`, so the rightmost IP not of a trusted
+    /// proxy is the client, the IPs on its left are sent by the client and can be forged.
+    fn forwarded_ip(config: &RateLimitConfig, headers: &HeaderMap) -> Option<IpAddr> {
+        if let Some(forwarded_for) = headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+        {
+            for ip in forwarded_for.rsplit(',') {
+                let ip = ip.trim().parse::<IpAddr>().ok()?;
+                if !config.trusted_proxies.contains(&ip) {
+                    return Some(ip);
+                }
+            }
+        }
+        headers
+            .get("x-real-ip")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<IpAddr>().ok())
+    }
+}
+
+impl fmt::Display for RateLimitClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitClient::ApiKey(api_key) => write!(f, "key:{}", api_key),
+            RateLimitClient::Ip(ip) => write!(f, "ip:{}", ip),
+        }
+    }
+}
+
+/// The limit rejected the call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitKind {
+    Method,
+    ApiKey,
+    Anonymous,
+}
+
+impl RateLimitKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitKind::Method => "method",
+            RateLimitKind::ApiKey => "api_key",
+            RateLimitKind::Anonymous => "anonymous",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    available: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            available: limit.burst as f64,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.available =
+            (self.available + elapsed * limit.per_second as f64).min(limit.burst as f64);
+        self.updated_at = now;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitMetrics {
+    pub rpc_rate_limit_rejections: IntCounterVec,
+    pub rpc_compute_units: IntCounterVec,
+}
+
+impl RateLimitMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            rpc_rate_limit_rejections: register_int_counter_vec_with_registry!(
+                "rpc_rate_limit_rejections",
+                "Number of calls rejected by the rate limits, by method and limit kind",
+                &["method", "limit"],
+                registry,
+            )
+            .unwrap(),
+            rpc_compute_units: register_int_counter_vec_with_registry!(
+                "rpc_compute_units",
+                "Compute units consumed by the accepted calls, by method",
+                &["method"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Per method and per API key rate limiter of the JSON-RPC calls, charging the compute units
+/// of the method from token buckets.
+#[derive(Debug)]
+pub struct RpcRateLimiter {
+    config: RateLimitConfig,
+    buckets: DashMap<(RateLimitKind, String), TokenBucket>,
+    metrics: RateLimitMetrics,
+}
+
+impl RpcRateLimiter {
+    pub fn new(config: RateLimitConfig, registry: &Registry) -> Self {
+        Self {
+            config,
+            buckets: DashMap::new(),
+            metrics: RateLimitMetrics::new(registry),
+        }
+    }
+
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Charge the compute units of the call, returns the rejected limit if the client is over it.
+    pub fn check(&self, method: &str, client: &RateLimitClient) -> Result<(), RateLimitKind> {
+        self.check_compute_units(method, client, self.config.compute_units(method))
+    }
+
+    /// Same as `check`, but charge the given compute units, for the calls whose cost is only
+    /// known after parsing them, e.g. the GraphQL queries.
+    pub fn check_compute_units(
+        &self,
+        method: &str,
+        client: &RateLimitClient,
+        compute_units: u64,
+    ) -> Result<(), RateLimitKind> {
+        let now = Instant::now();
+
+        let method_limit = self.config.method_limits.get(method).map(|limit| {
+            (
+                RateLimitKind::Method,
+                format!("{}/{}", method, client),
+                limit,
+            )
+        });
+        let client_limit = match client {
+            RateLimitClient::ApiKey(api_key) => self
+                .config
+                .api_key_limits
+                .get(api_key)
+                .map(|limit| (RateLimitKind::ApiKey, api_key.clone(), limit)),
+            RateLimitClient::Ip(ip) => self
+                .config
+                .anonymous_limit
+                .as_ref()
+                .map(|limit| (RateLimitKind::Anonymous, ip.to_string(), limit)),
+        };
+
+        let mut charged = vec![];
+        for (kind, key, limit) in method_limit.into_iter().chain(client_limit) {
+            let bucket_key = (kind, key);
+            if self.try_acquire(&bucket_key, limit, compute_units, now) {
+                charged.push(bucket_key);
+            } else {
+                // refund the limits already charged, the call is not executed
+                for bucket_key in charged {
+                    if let Some(mut bucket) = self.buckets.get_mut(&bucket_key) {
+                        bucket.available += compute_units as f64;
+                    }
+                }
+                self.metrics
+                    .rpc_rate_limit_rejections
+                    .with_label_values(&[method, kind.as_str()])
+                    .inc();
+                return Err(kind);
+            }
+        }
+        self.metrics
+            .rpc_compute_units
+            .with_label_values(&[method])
+            .inc_by(compute_units);
+        Ok(())
+    }
+
+    fn try_acquire(
+        &self,
+        bucket_key: &(RateLimitKind, String),
+        limit: &RateLimit,
+        compute_units: u64,
+        now: Instant,
+    ) -> bool {
+        let mut bucket = self
+            .buckets
+            .entry(bucket_key.clone())
+            .or_insert_with(|| TokenBucket::new(limit, now));
+        bucket.refill(limit, now);
+        if bucket.available >= compute_units as f64 {
+            bucket.available -= compute_units as f64;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Drop the idle buckets, they are refilled to the burst anyway
+    pub fn retain_recent(&self) {
+        let now = Instant::now();
+        self.buckets.retain(|_, bucket| {
+            now.saturating_duration_since(bucket.updated_at) < BUCKET_IDLE_EXPIRATION
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn limit(per_second: u64, burst: u64) -> RateLimit {
+        RateLimit { per_second, burst }
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let config = RateLimitConfig {
+            method_compute_units: BTreeMap::from([("rooch_dryRunTransaction".to_string(), 10)]),
+            method_limits: BTreeMap::from([("rooch_dryRunTransaction".to_string(), limit(1, 20))]),
+            api_key_limits: BTreeMap::from([("key1".to_string(), limit(1, 15))]),
+            anonymous_limit: Some(limit(1, 5)),
+            ..Default::default()
+        };
+        let limiter = RpcRateLimiter::new(config, &Registry::new());
+
+        let api_key_client = RateLimitClient::ApiKey("key1".to_string());
+        let ip_client = RateLimitClient::Ip("127.0.0.1".parse().unwrap());
+
+        // The API key has 15 units, the second dry run is over the API key limit
+        assert!(limiter
+            .check("rooch_dryRunTransaction", &api_key_client)
+            .is_ok());
+        assert_eq!(
+            limiter.check("rooch_dryRunTransaction", &api_key_client),
+            Err(RateLimitKind::ApiKey)
+        );
+        // The rejected call does not consume the method limit
+        assert!(limiter.check("rooch_getChainID", &api_key_client).is_ok());
+
+        // The anonymous client has 5 units, it can not afford a dry run
+        assert_eq!(
+            limiter.check("rooch_dryRunTransaction", &ip_client),
+            Err(RateLimitKind::Anonymous)
+        );
+        for _ in 0..5 {
+            assert!(limiter.check("rooch_getChainID", &ip_client).is_ok());
+        }
+        assert_eq!(
+            limiter.check("rooch_getChainID", &ip_client),
+            Err(RateLimitKind::Anonymous)
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_client() {
+        let mut config = RateLimitConfig {
+            api_key_limits: BTreeMap::from([("key1".to_string(), limit(1, 1))]),
+            ..Default::default()
+        };
+        let client_addr: SocketAddr = "10.0.0.1:1234".parse().unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", "key1".parse().unwrap());
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &headers),
+            RateLimitClient::ApiKey("key1".to_string())
+        );
+
+        // The unknown API key is identified by the IP, the forwarded IP of an untrusted peer is ignored
+        headers.insert("x-api-key", "unknown".parse().unwrap());
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.2".parse().unwrap());
+        headers.insert("x-real-ip", "5.6.7.8".parse().unwrap());
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &headers),
+            RateLimitClient::Ip(client_addr.ip())
+        );
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &HeaderMap::new()),
+            RateLimitClient::Ip(client_addr.ip())
+        );
+
+        // The rightmost forwarded IP not of a trusted proxy is the client
+        config.trusted_proxies = vec!["10.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &headers),
+            RateLimitClient::Ip("1.2.3.4".parse().unwrap())
+        );
+        headers.insert("x-forwarded-for", "9.9.9.9, 1.2.3.4".parse().unwrap());
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &headers),
+            RateLimitClient::Ip("1.2.3.4".parse().unwrap())
+        );
+        headers.remove("x-forwarded-for");
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &headers),
+            RateLimitClient::Ip("5.6.7.8".parse().unwrap())
+        );
+        assert_eq!(
+            RateLimitClient::from_request(&config, client_addr, &HeaderMap::new()),
+            RateLimitClient::Ip(client_addr.ip())
+        );
+    }
+}
@@ -1,9 +1,11 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::service::subscription_replay::replay_then_forward;
 use anyhow::{bail, format_err, Result};
+use bitcoin_client::fee::{block_fee_rate, estimate_fee_rate, MAX_FEE_ESTIMATE_BLOCKS};
 use bitcoin_client::proxy::BitcoinClientProxy;
-use bitcoincore_rpc::bitcoin::Txid;
+use bitcoincore_rpc::bitcoin::{BlockHash, FeeRate, Txid};
 use futures::{Stream, StreamExt};
 use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::PendingSubscriptionSink;
@@ -19,43 +21,55 @@ use moveos_types::moveos_std::display::{get_object_display_id, RawDisplay};
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
 use moveos_types::moveos_std::object::{ObjectID, MAX_OBJECT_IDS_PER_QUERY};
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState, StateChangeSet};
+use moveos_types::state_proof::StateProof;
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::{FunctionCall, TransactionExecutionInfo};
+use quick_cache::sync::Cache;
 use rooch_da::proxy::DAServerProxy;
-use rooch_executor::actor::messages::DryRunTransactionResult;
+use rooch_executor::actor::messages::{DryRunTransactionResult, ExecuteViewFunctionsResult};
 use rooch_executor::proxy::ExecutorProxy;
 use rooch_indexer::proxy::IndexerProxy;
+use rooch_indexer::{INDEXER_EVENTS_TABLE_NAME, INDEXER_TRANSACTIONS_TABLE_NAME};
 use rooch_notify::subscription_handler::SubscriptionHandler;
 use rooch_pipeline_processor::proxy::PipelineProcessorProxy;
-use rooch_rpc_api::jsonrpc_types::event_view::EventFilterView;
+use rooch_rpc_api::jsonrpc_types::btc::reorg::BitcoinReorgFilterView;
+use rooch_rpc_api::jsonrpc_types::event_view::{EventFilterView, IndexerEventView};
 use rooch_rpc_api::jsonrpc_types::field_view::IndexerFieldView;
-use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionFilterView;
+use rooch_rpc_api::jsonrpc_types::transaction_view::{
+    TransactionFilterView, TransactionWithInfoView,
+};
 use rooch_rpc_api::jsonrpc_types::{
-    BitcoinStatus, DisplayFieldsView, IndexerObjectStateView, ObjectMetaView, RoochStatus, Status,
+    BitcoinStatus, DisplayFieldsView, IndexerObjectStateView, ObjectMetaView,
+    ObjectStateFilterView, QueryOptions, RoochStatus, Status,
 };
 use rooch_sequencer::proxy::SequencerProxy;
 use rooch_types::address::{BitcoinAddress, RoochAddress};
 use rooch_types::bitcoin::pending_block::PendingBlockModule;
 use rooch_types::bitcoin::BitcoinModule;
 use rooch_types::framework::address_mapping::RoochToBitcoinAddressMapping;
+use rooch_types::indexer::check_filter_depth;
 use rooch_types::indexer::event::{
     AnnotatedIndexerEvent, EventFilter, IndexerEvent, IndexerEventID,
 };
 use rooch_types::indexer::field::{FieldFilter, IndexerField};
 use rooch_types::indexer::state::{
     IndexerObjectState, IndexerStateID, ObjectStateFilter, ObjectStateType, INSCRIPTION_TYPE_TAG,
-    UTXO_TYPE_TAG,
+    RUNE_BALANCES_TYPE_TAG, UTXO_TYPE_TAG,
 };
 use rooch_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
+use rooch_types::into_address::FromAddress;
 use rooch_types::repair::{RepairIndexerParams, RepairIndexerType};
 use rooch_types::state::{StateChangeSetWithTxOrder, SyncStateFilter};
 use rooch_types::transaction::{
-    ExecuteTransactionResponse, LedgerTransaction, RoochTransaction, RoochTransactionData,
+    Authenticator, ExecuteTransactionResponse, LedgerTransaction, RoochTransaction,
+    RoochTransactionData, TransactionAccumulatorProof, TransactionWithInfo,
 };
 use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
 
 pub fn spawn_subscription<S, T>(
     sink: PendingSubscriptionSink,
@@ -97,6 +111,13 @@ pub fn spawn_subscription<S, T>(
     });
 }
 const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
+/// The page size of the indexer queries when replaying a subscription
+const SUBSCRIPTION_REPLAY_PAGE_SIZE: usize = 100;
+/// The max time waiting for the indexer to catch up the live stream before replaying a subscription
+const SUBSCRIPTION_REPLAY_INDEXER_TIMEOUT: Duration = Duration::from_secs(30);
+const SUBSCRIPTION_REPLAY_INDEXER_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// The cache keeps the fee rates of the estimation window, and the blocks replaced by the recent reorgs
+const BITCOIN_FEE_RATE_CACHE_SIZE: usize = 2 * MAX_FEE_ESTIMATE_BLOCKS as usize;
 
 /// RpcService is the implementation of the RPC service.
 /// It is the glue between the RPC server(EthAPIServer,RoochApiServer) and the rooch's actors.
@@ -114,6 +135,8 @@ pub struct RpcService {
     // pub(crate) notify: NotifyProxy,
     pub(crate) subscription_handler: Arc<SubscriptionHandler>,
     pub(crate) subscription_semaphore: Arc<Semaphore>,
+    /// The fee rates of the relayed Bitcoin blocks, keyed by the block hash so a reorg does not hit a stale entry
+    bitcoin_fee_rate_cache: Arc<Cache<BlockHash, Option<FeeRate>>>,
 }
 
 impl RpcService {
@@ -141,6 +164,7 @@ impl RpcService {
             da_server,
             subscription_handler,
             subscription_semaphore: Arc::new(Semaphore::new(max_subscriptions)),
+            bitcoin_fee_rate_cache: Arc::new(Cache::new(BITCOIN_FEE_RATE_CACHE_SIZE)),
         }
     }
 }
@@ -164,24 +188,69 @@ impl RpcService {
         self.pipeline_processor.execute_l2_tx(tx).await
     }
 
-    pub async fn dry_run_tx(&self, tx: RoochTransactionData) -> Result<DryRunTransactionResult> {
-        let verified_tx = self.executor.convert_to_verified_tx(tx).await?;
+    /// Dry run the transaction against the latest state, or against the historical state at
+    /// the `state_root` or after the transaction of `tx_order`. The `authenticator` of a signed
+    /// transaction is not validated, the sponsor of a sponsored transaction pays the gas.
+    pub async fn dry_run_tx(
+        &self,
+        tx: RoochTransactionData,
+        authenticator: Option<Authenticator>,
+        state_root: Option<H256>,
+        tx_order: Option<u64>,
+    ) -> Result<DryRunTransactionResult> {
+        let root = self
+            .executor
+            .resolve_historical_root(state_root, tx_order)
+            .await?;
+        let verified_tx = self
+            .executor
+            .convert_to_verified_tx(tx, authenticator, root)
+            .await?;
         self.executor.dry_run_transaction(verified_tx).await
     }
 
+    /// Execute the view function against the latest state, or against the historical state at
+    /// the `state_root` or after the transaction of `tx_order`.
     pub async fn execute_view_function(
         &self,
         function_call: FunctionCall,
+        state_root: Option<H256>,
+        tx_order: Option<u64>,
     ) -> Result<AnnotatedFunctionResult> {
+        let root = self
+            .executor
+            .resolve_historical_root(state_root, tx_order)
+            .await?;
         let module_id = function_call.function_id.module_id.clone();
-        if !self.exists_module(module_id.clone()).await? {
+        let module = self
+            .get_states(
+                AccessPath::module(&module_id),
+                root.as_ref().map(|root| root.state_root()),
+            )
+            .await?
+            .pop()
+            .flatten();
+        if module.is_none() {
             return Err(anyhow::anyhow!("Module does not exist: {}", module_id));
         }
 
-        let resp = self.executor.execute_view_function(function_call).await?;
+        let resp = self
+            .executor
+            .execute_view_function(function_call, root)
+            .await?;
         Ok(resp)
     }
 
+    pub async fn execute_view_functions(
+        &self,
+        function_calls: Vec<FunctionCall>,
+        state_root: Option<H256>,
+    ) -> Result<ExecuteViewFunctionsResult> {
+        self.executor
+            .execute_view_functions(function_calls, state_root)
+            .await
+    }
+
     pub async fn get_states(
         &self,
         access_path: AccessPath,
@@ -190,6 +259,17 @@ impl RpcService {
         self.executor.get_states(access_path, state_root).await
     }
 
+    pub async fn get_state_proof(
+        &self,
+        object_id: ObjectID,
+        field_key: FieldKey,
+        state_root: Option<H256>,
+    ) -> Result<(H256, StateProof)> {
+        self.executor
+            .get_state_proof(object_id, field_key, state_root)
+            .await
+    }
+
     pub async fn exists_module(&self, module_id: ModuleId) -> Result<bool> {
         let mut resp = self
             .get_states(AccessPath::module(&module_id), None)
@@ -312,6 +392,18 @@ impl RpcService {
         Ok(resp)
     }
 
+    pub async fn get_tx_accumulator_proof(
+        &self,
+        tx_order: u64,
+        accumulator_tx_order: Option<u64>,
+    ) -> Result<Option<TransactionAccumulatorProof>> {
+        let resp = self
+            .sequencer
+            .get_tx_accumulator_proof(tx_order, accumulator_tx_order)
+            .await?;
+        Ok(resp)
+    }
+
     pub async fn query_transactions(
         &self,
         filter: TransactionFilter,
@@ -641,6 +733,56 @@ impl RpcService {
             .await
     }
 
+    /// Estimate the fee rate from the latest `num_blocks` relayed Bitcoin blocks.
+    /// Return the fee rate, the number of the sampled blocks and the latest block height.
+    pub async fn estimate_bitcoin_fee_rate(
+        &self,
+        num_blocks: u64,
+    ) -> Result<(FeeRate, u64, Option<u64>)> {
+        let bitcoin_client = self
+            .bitcoin_client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Bitcoin client is not configured"))?;
+        let network =
+            bitcoincore_rpc::bitcoin::Network::from(rooch_types::bitcoin::network::Network::from(
+                self.executor.bitcoin_network().await?.network,
+            ));
+
+        let bitcoin_module = self.executor.as_module_binding::<BitcoinModule>();
+        let latest_block_height = match bitcoin_module.get_latest_block()? {
+            Some(latest_block) => latest_block.block_height,
+            None => return Ok((estimate_fee_rate(vec![]), 0, None)),
+        };
+
+        let first_block_height = latest_block_height.saturating_sub(num_blocks.saturating_sub(1));
+        let mut block_fee_rates = vec![];
+        for block_height in (first_block_height..=latest_block_height).rev() {
+            let Some(header) = bitcoin_module.get_block_by_height(block_height)? else {
+                break;
+            };
+            // Only the blocks not sampled by the previous estimations are downloaded
+            let block_hash = BlockHash::from_address(header.block_hash());
+            let fee_rate = match self.bitcoin_fee_rate_cache.get(&block_hash) {
+                Some(fee_rate) => fee_rate,
+                None => {
+                    let block = bitcoin_client.get_block(block_hash).await?;
+                    let fee_rate = block_fee_rate(network, block_height, &block);
+                    self.bitcoin_fee_rate_cache.insert(block_hash, fee_rate);
+                    fee_rate
+                }
+            };
+            if let Some(fee_rate) = fee_rate {
+                block_fee_rates.push(fee_rate);
+            }
+        }
+        let block_count = block_fee_rates.len() as u64;
+        Ok((
+            estimate_fee_rate(block_fee_rates),
+            block_count,
+            Some(latest_block_height),
+        ))
+    }
+
     pub async fn repair_indexer(
         &self,
         repair_type: RepairIndexerType,
@@ -663,6 +805,7 @@ impl RpcService {
                             ObjectStateType::ObjectState,
                             ObjectStateType::UTXO,
                             ObjectStateType::Inscription,
+                            ObjectStateType::RuneBalances,
                         ] {
                             self.repair_indexer_object_states(
                                 states.clone(),
@@ -720,6 +863,12 @@ impl RpcService {
                                 object_states_mapping.insert(state.metadata.id.clone(), state);
                             }
                         }
+                        ObjectStateType::RuneBalances => {
+                            if type_tag_match(&state.metadata.object_type, &RUNE_BALANCES_TYPE_TAG)
+                            {
+                                object_states_mapping.insert(state.metadata.id.clone(), state);
+                            }
+                        }
                     },
                     None => remove_object_ids.push(object_ids[idx].clone()),
                 }
@@ -974,25 +1123,242 @@ impl RpcService {
         }
     }
 
+    /// Subscribe the events, replay the matched events from `from_tx_order`(inclusive) first if it is `Some`.
     pub fn subscribe_events(
         &self,
         sink: PendingSubscriptionSink,
         filter: EventFilterView,
+        from_tx_order: Option<u64>,
     ) -> SubscriptionResult {
+        // The filter is matched recursively against every event, reject the deep one up front
+        check_filter_depth(EventFilter::from(filter.clone()).depth())?;
         let permit = self.acquire_subscribe_permit()?;
-        let stream = self.subscription_handler.subscribe_events(filter);
-        spawn_subscription(sink, stream, Some(permit));
+        // Subscribe the live stream before the replay, so no event is lost between them
+        let stream = self.subscription_handler.subscribe_events(filter.clone());
+        match from_tx_order {
+            Some(from_tx_order) => {
+                let (sender, receiver) = mpsc::channel(SUBSCRIPTION_REPLAY_PAGE_SIZE);
+                let service = self.clone();
+                spawn_monitored_task!(async move {
+                    if let Err(e) = service
+                        .replay_events(filter, from_tx_order, stream, sender)
+                        .await
+                    {
+                        tracing::warn!("Replay event subscription error: {:?}", e);
+                    }
+                });
+                spawn_subscription(sink, ReceiverStream::new(receiver), Some(permit));
+            }
+            None => spawn_subscription(sink, stream, Some(permit)),
+        }
         Ok(())
     }
 
+    /// Subscribe the transactions, replay the matched transactions from `from_tx_order`(inclusive) first if it is `Some`.
     pub fn subscribe_transactions(
         &self,
         sink: PendingSubscriptionSink,
         filter: TransactionFilterView,
+        from_tx_order: Option<u64>,
+    ) -> SubscriptionResult {
+        check_filter_depth(TransactionFilter::from(filter.clone()).depth())?;
+        let permit = self.acquire_subscribe_permit()?;
+        // Subscribe the live stream before the replay, so no transaction is lost between them
+        let stream = self
+            .subscription_handler
+            .subscribe_transactions(filter.clone());
+        match from_tx_order {
+            Some(from_tx_order) => {
+                let (sender, receiver) = mpsc::channel(SUBSCRIPTION_REPLAY_PAGE_SIZE);
+                let service = self.clone();
+                spawn_monitored_task!(async move {
+                    if let Err(e) = service
+                        .replay_transactions(filter, from_tx_order, stream, sender)
+                        .await
+                    {
+                        tracing::warn!("Replay transaction subscription error: {:?}", e);
+                    }
+                });
+                spawn_subscription(sink, ReceiverStream::new(receiver), Some(permit));
+            }
+            None => spawn_subscription(sink, stream, Some(permit)),
+        }
+        Ok(())
+    }
+
+    pub fn subscribe_object_changes(
+        &self,
+        sink: PendingSubscriptionSink,
+        filter: ObjectStateFilterView,
     ) -> SubscriptionResult {
+        let object_state_filter = ObjectStateFilterView::try_into_object_state_filter(
+            filter.clone(),
+            QueryOptions::default(),
+        )?;
+        check_filter_depth(object_state_filter.depth())?;
         let permit = self.acquire_subscribe_permit()?;
-        let stream = self.subscription_handler.subscribe_transactions(filter);
+        let stream = self.subscription_handler.subscribe_object_changes(filter);
         spawn_subscription(sink, stream, Some(permit));
         Ok(())
     }
+
+    pub fn subscribe_bitcoin_reorgs(
+        &self,
+        sink: PendingSubscriptionSink,
+        filter: BitcoinReorgFilterView,
+    ) -> SubscriptionResult {
+        let permit = self.acquire_subscribe_permit()?;
+        let stream = self.subscription_handler.subscribe_bitcoin_reorgs(filter);
+        spawn_subscription(sink, stream, Some(permit));
+        Ok(())
+    }
+    /// Wait until the indexer table `table_name` has indexed the transaction of `tx_order`
+    async fn wait_for_indexer(&self, table_name: &str, tx_order: u64) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            let indexed = match self.indexer.query_indexed_tx_order(table_name).await? {
+                Some(indexed_tx_order) => indexed_tx_order >= tx_order,
+                // Nothing is written to the table since the indexer started, the tables were
+                // indexed together before it.
+                None => !self
+                    .indexer
+                    .query_transactions(
+                        TransactionFilter::TxOrderRange {
+                            from_order: tx_order,
+                            to_order: tx_order + 1,
+                        },
+                        None,
+                        1,
+                        false,
+                    )
+                    .await?
+                    .is_empty(),
+            };
+            if indexed {
+                return Ok(());
+            }
+            if start.elapsed() > SUBSCRIPTION_REPLAY_INDEXER_TIMEOUT {
+                bail!(
+                    "The indexer table {} does not catch up the tx_order {} in {:?}",
+                    table_name,
+                    tx_order,
+                    SUBSCRIPTION_REPLAY_INDEXER_TIMEOUT
+                );
+            }
+            tokio::time::sleep(SUBSCRIPTION_REPLAY_INDEXER_POLL_INTERVAL).await;
+        }
+    }
+
+    // The events of the transactions after `last_tx_order` are all in the live stream, so the
+    // replay reads the indexer up to `last_tx_order`, then forwards the live stream after it.
+    // If the live stream is dropped for lagging behind during the replay, the subscription is closed
+    // and the client resumes it from the tx_order after the last completely received transaction.
+    async fn replay_events<S>(
+        &self,
+        filter: EventFilterView,
+        from_tx_order: u64,
+        live_stream: S,
+        sender: mpsc::Sender<IndexerEventView>,
+    ) -> Result<()>
+    where
+        S: Stream<Item = IndexerEventView> + Unpin,
+    {
+        let last_tx_order = self.get_sequencer_order().await?;
+        if from_tx_order <= last_tx_order {
+            self.wait_for_indexer(INDEXER_EVENTS_TABLE_NAME, last_tx_order)
+                .await?;
+        }
+        let replay_filter = EventFilter::And(vec![
+            filter.into(),
+            EventFilter::TxOrderRange {
+                from_order: from_tx_order,
+                to_order: last_tx_order.saturating_add(1),
+            },
+        ]);
+        let fetch_page = |cursor| {
+            let replay_filter = replay_filter.clone();
+            async move {
+                let events = self
+                    .query_events(replay_filter, cursor, SUBSCRIPTION_REPLAY_PAGE_SIZE, false)
+                    .await?;
+                let next_cursor = events.last().map(|event| event.indexer_event_id);
+                Ok((
+                    events.into_iter().map(IndexerEventView::from).collect(),
+                    next_cursor,
+                ))
+            }
+        };
+        replay_then_forward(
+            from_tx_order,
+            last_tx_order,
+            fetch_page,
+            live_stream,
+            |event: &IndexerEventView| event.indexer_event_id.tx_order.0,
+            sender,
+        )
+        .await
+    }
+
+    // Same as `replay_events`
+    async fn replay_transactions<S>(
+        &self,
+        filter: TransactionFilterView,
+        from_tx_order: u64,
+        live_stream: S,
+        sender: mpsc::Sender<TransactionWithInfoView>,
+    ) -> Result<()>
+    where
+        S: Stream<Item = TransactionWithInfoView> + Unpin,
+    {
+        let last_tx_order = self.get_sequencer_order().await?;
+        if from_tx_order <= last_tx_order {
+            self.wait_for_indexer(INDEXER_TRANSACTIONS_TABLE_NAME, last_tx_order)
+                .await?;
+        }
+        let replay_filter = TransactionFilter::And(vec![
+            filter.into(),
+            TransactionFilter::TxOrderRange {
+                from_order: from_tx_order,
+                to_order: last_tx_order.saturating_add(1),
+            },
+        ]);
+        let fetch_page = |cursor| {
+            let replay_filter = replay_filter.clone();
+            async move {
+                let txs = self
+                    .query_transactions(replay_filter, cursor, SUBSCRIPTION_REPLAY_PAGE_SIZE, false)
+                    .await?;
+                let next_cursor = txs.last().map(|tx| tx.tx_order);
+                let tx_hashes = txs.iter().map(|tx| tx.tx_hash).collect::<Vec<_>>();
+                let ledger_txs = self.get_transactions_by_hash(tx_hashes.clone()).await?;
+                let execution_infos = self
+                    .get_transaction_execution_infos_by_hash(tx_hashes)
+                    .await?;
+                let mut tx_views = Vec::with_capacity(ledger_txs.len());
+                for (ledger_tx_opt, execution_info_opt) in
+                    ledger_txs.into_iter().zip(execution_infos)
+                {
+                    let ledger_tx = ledger_tx_opt
+                        .ok_or(anyhow::anyhow!("LedgerTransaction should have value"))?;
+                    let execution_info = execution_info_opt.ok_or(anyhow::anyhow!(
+                        "TransactionExecutionInfo should have value"
+                    ))?;
+                    tx_views.push(TransactionWithInfoView::new_from_transaction_with_info(
+                        TransactionWithInfo::new(ledger_tx, execution_info),
+                        None,
+                    ));
+                }
+                Ok((tx_views, next_cursor))
+            }
+        };
+        replay_then_forward(
+            from_tx_order,
+            last_tx_order,
+            fetch_page,
+            live_stream,
+            |tx: &TransactionWithInfoView| tx.transaction.sequence_info.tx_order.0,
+            sender,
+        )
+        .await
+    }
 }
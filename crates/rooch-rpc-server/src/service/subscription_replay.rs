@@ -0,0 +1,164 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use futures::{Stream, StreamExt};
+use std::future::Future;
+use tokio::sync::mpsc;
+
+/// Send the matched items from `from_tx_order`(inclusive) once and in tx_order.
+///
+/// The items up to `last_tx_order` are read from the indexer page by page with `fetch_page`,
+/// which returns a page after the cursor and the cursor of its last item. Then the live items
+/// after `last_tx_order` are forwarded, the live stream must be subscribed before
+/// `last_tx_order` is read so it contains all of them.
+///
+/// It returns when the receiver is dropped or the live stream ends, e.g. the live stream is
+/// dropped for lagging behind during the replay.
+pub(crate) async fn replay_then_forward<T, C, F, Fut, S, O>(
+    from_tx_order: u64,
+    last_tx_order: u64,
+    mut fetch_page: F,
+    mut live_stream: S,
+    tx_order_of: O,
+    sender: mpsc::Sender<T>,
+) -> Result<()>
+where
+    F: FnMut(Option<C>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<C>)>>,
+    S: Stream<Item = T> + Unpin,
+    O: Fn(&T) -> u64,
+{
+    if from_tx_order <= last_tx_order {
+        let mut cursor = None;
+        loop {
+            let (items, next_cursor) = fetch_page(cursor).await?;
+            if items.is_empty() {
+                break;
+            }
+            for item in items {
+                if sender.send(item).await.is_err() {
+                    return Ok(());
+                }
+            }
+            cursor = next_cursor;
+        }
+    }
+
+    // The live items up to `last_tx_order` are replayed, the ones before `from_tx_order` are not
+    // requested
+    let first_live_tx_order = from_tx_order.max(last_tx_order.saturating_add(1));
+    while let Some(item) = live_stream.next().await {
+        if tx_order_of(&item) < first_live_tx_order {
+            continue;
+        }
+        if sender.send(item).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // (tx_order, index of the item in the tx)
+    type Item = (u64, u64);
+
+    async fn replay(
+        indexed: Vec<Item>,
+        live: Vec<Item>,
+        from_tx_order: u64,
+        last_tx_order: u64,
+        fetch_count: &AtomicUsize,
+    ) -> Vec<Item> {
+        let (sender, mut receiver) = mpsc::channel(100);
+        let fetch_page = |cursor: Option<Item>| {
+            fetch_count.fetch_add(1, Ordering::SeqCst);
+            // the indexer query of the tx_order range, 2 items per page
+            let page = indexed
+                .iter()
+                .filter(|item| item.0 >= from_tx_order && item.0 <= last_tx_order)
+                .filter(|item| cursor.map(|cursor| **item > cursor).unwrap_or(true))
+                .take(2)
+                .copied()
+                .collect::<Vec<_>>();
+            async move {
+                let next_cursor = page.last().copied();
+                Ok((page, next_cursor))
+            }
+        };
+        replay_then_forward(
+            from_tx_order,
+            last_tx_order,
+            fetch_page,
+            futures::stream::iter(live),
+            |item: &Item| item.0,
+            sender,
+        )
+        .await
+        .unwrap();
+        let mut received = vec![];
+        while let Some(item) = receiver.recv().await {
+            received.push(item);
+        }
+        received
+    }
+
+    #[tokio::test]
+    async fn test_replay_then_forward() {
+        let fetch_count = AtomicUsize::new(0);
+        let indexed = vec![(1, 0), (2, 0), (2, 1), (3, 0), (4, 0), (4, 1)];
+        // the live stream is subscribed before the replay, it overlaps with the indexed items
+        let live = vec![(3, 0), (4, 0), (4, 1), (5, 0), (5, 1), (6, 0)];
+        let received = replay(indexed, live, 2, 4, &fetch_count).await;
+        assert_eq!(
+            received,
+            vec![
+                (2, 0),
+                (2, 1),
+                (3, 0),
+                (4, 0),
+                (4, 1),
+                (5, 0),
+                (5, 1),
+                (6, 0)
+            ]
+        );
+        assert!(fetch_count.load(Ordering::SeqCst) > 1);
+    }
+
+    #[tokio::test]
+    async fn test_replay_from_future_tx_order() {
+        let fetch_count = AtomicUsize::new(0);
+        let indexed = vec![(1, 0), (2, 0)];
+        let live = vec![(3, 0), (4, 0), (5, 0), (6, 0)];
+        let received = replay(indexed, live, 5, 2, &fetch_count).await;
+        assert_eq!(received, vec![(5, 0), (6, 0)]);
+        assert_eq!(fetch_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_stops_when_receiver_dropped() {
+        let (sender, receiver) = mpsc::channel(1);
+        drop(receiver);
+        let fetch_count = AtomicUsize::new(0);
+        let fetch_page = |_cursor: Option<u64>| {
+            fetch_count.fetch_add(1, Ordering::SeqCst);
+            async { Ok((vec![1u64, 2], Some(2))) }
+        };
+        replay_then_forward(
+            1,
+            2,
+            fetch_page,
+            futures::stream::iter(vec![3u64]),
+            |tx_order: &u64| *tx_order,
+            sender,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fetch_count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -8,7 +8,8 @@ use std::time::SystemTime;
 
 use crate::messages::{
     GetSequencerInfoMessage, GetSequencerOrderMessage, GetTransactionByHashMessage,
-    GetTransactionsByHashMessage, GetTxHashsMessage, TransactionSequenceMessage,
+    GetTransactionsByHashMessage, GetTxAccumulatorProofMessage, GetTxHashsMessage,
+    TransactionSequenceMessage,
 };
 use crate::metrics::SequencerMetrics;
 use accumulator::{Accumulator, MerkleAccumulator};
@@ -27,7 +28,7 @@ use rooch_store::RoochStore;
 use rooch_types::crypto::RoochKeyPair;
 use rooch_types::sequencer::SequencerInfo;
 use rooch_types::service_status::ServiceStatus;
-use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
+use rooch_types::transaction::{LedgerTransaction, LedgerTxData, TransactionAccumulatorProof};
 use tracing::info;
 
 pub struct SequencerActor {
@@ -269,6 +270,57 @@ impl Handler<GetTxHashsMessage> for SequencerActor {
     }
 }
 
+#[async_trait]
+impl Handler<GetTxAccumulatorProofMessage> for SequencerActor {
+    async fn handle(
+        &mut self,
+        msg: GetTxAccumulatorProofMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Option<TransactionAccumulatorProof>> {
+        let GetTxAccumulatorProofMessage {
+            tx_order,
+            accumulator_tx_order,
+        } = msg;
+        let accumulator_tx_order = accumulator_tx_order.unwrap_or(tx_order);
+        if accumulator_tx_order < tx_order {
+            return Err(anyhow::anyhow!(
+                "The accumulator tx order {} should not be less than the tx order {}",
+                accumulator_tx_order,
+                tx_order
+            ));
+        }
+        let tx_hashes = self
+            .rooch_store
+            .get_tx_hashes(vec![tx_order, accumulator_tx_order])?;
+        let (Some(tx_hash), Some(accumulator_tx_hash)) = (tx_hashes[0], tx_hashes[1]) else {
+            return Ok(None);
+        };
+        let (Some(tx), Some(accumulator_tx)) = (
+            self.rooch_store.get_transaction_by_hash(tx_hash)?,
+            self.rooch_store
+                .get_transaction_by_hash(accumulator_tx_hash)?,
+        ) else {
+            return Ok(None);
+        };
+
+        let leaf_index = tx.sequence_info.tx_accumulator_leaf_index();
+        let accumulator = self
+            .tx_accumulator
+            .fork(Some(accumulator_tx.sequence_info.tx_accumulator_info()));
+        let proof = accumulator.get_proof(leaf_index)?.ok_or_else(|| {
+            anyhow::anyhow!("The accumulator proof of tx_order {} not exist", tx_order)
+        })?;
+        Ok(Some(TransactionAccumulatorProof {
+            tx_order,
+            tx_hash,
+            leaf_index,
+            accumulator_tx_order,
+            accumulator_root: accumulator_tx.sequence_info.tx_accumulator_root,
+            proof,
+        }))
+    }
+}
+
 #[async_trait]
 impl Handler<GetSequencerOrderMessage> for SequencerActor {
     async fn handle(
@@ -6,7 +6,7 @@ use coerce::actor::message::Message;
 use moveos_types::h256::H256;
 use rooch_types::{
     sequencer::SequencerInfo,
-    transaction::{LedgerTransaction, LedgerTxData},
+    transaction::{LedgerTransaction, LedgerTxData, TransactionAccumulatorProof},
 };
 use serde::{Deserialize, Serialize};
 
@@ -62,3 +62,15 @@ pub struct GetSequencerInfoMessage {}
 impl Message for GetSequencerInfoMessage {
     type Result = Result<SequencerInfo>;
 }
+
+/// Get the tx accumulator proof of the tx at `tx_order`, against the accumulator root at `accumulator_tx_order`.
+/// If `accumulator_tx_order` is None, the accumulator root of the tx itself is used.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetTxAccumulatorProofMessage {
+    pub tx_order: u64,
+    pub accumulator_tx_order: Option<u64>,
+}
+
+impl Message for GetTxAccumulatorProofMessage {
+    type Result = Result<Option<TransactionAccumulatorProof>>;
+}
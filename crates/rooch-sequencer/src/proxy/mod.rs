@@ -3,14 +3,14 @@
 
 use crate::messages::{
     GetSequencerInfoMessage, GetSequencerOrderMessage, GetTransactionByHashMessage,
-    GetTransactionsByHashMessage, GetTxHashsMessage,
+    GetTransactionsByHashMessage, GetTxAccumulatorProofMessage, GetTxHashsMessage,
 };
 use crate::{actor::sequencer::SequencerActor, messages::TransactionSequenceMessage};
 use anyhow::Result;
 use coerce::actor::ActorRef;
 use moveos_types::h256::H256;
 use rooch_types::sequencer::SequencerInfo;
-use rooch_types::transaction::{LedgerTransaction, LedgerTxData};
+use rooch_types::transaction::{LedgerTransaction, LedgerTxData, TransactionAccumulatorProof};
 
 #[derive(Clone)]
 pub struct SequencerProxy {
@@ -52,4 +52,17 @@ impl SequencerProxy {
     pub async fn get_sequencer_info(&self) -> Result<SequencerInfo> {
         self.actor.send(GetSequencerInfoMessage {}).await?
     }
+
+    pub async fn get_tx_accumulator_proof(
+        &self,
+        tx_order: u64,
+        accumulator_tx_order: Option<u64>,
+    ) -> Result<Option<TransactionAccumulatorProof>> {
+        self.actor
+            .send(GetTxAccumulatorProofMessage {
+                tx_order,
+                accumulator_tx_order,
+            })
+            .await?
+    }
 }
@@ -4,6 +4,7 @@
 use anyhow::Result;
 use coerce::actor::{system::ActorSystem, IntoActor};
 use metrics::RegistryService;
+use moveos_types::h256::H256;
 use prometheus::Registry;
 use raw_store::metrics::DBMetrics;
 use raw_store::StoreInstance;
@@ -133,3 +134,64 @@ async fn test_sequencer_concurrent() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_tx_accumulator_proof() -> Result<()> {
+    let opt = RoochOpt::new_with_temp_store()?;
+    let registry_service = RegistryService::default();
+    let rooch_db = init_rooch_db(&opt, &registry_service.default_registry())?;
+    let sequencer_key = RoochKeyPair::generate_secp256k1();
+
+    let actor_system = ActorSystem::global_system();
+    let sequencer = SequencerActor::new(
+        sequencer_key,
+        rooch_db.rooch_store,
+        ServiceStatus::Active,
+        &registry_service.default_registry(),
+        None,
+    )?
+    .into_actor(Some("SequencerForProof"), &actor_system)
+    .await?;
+    let sequencer_proxy = SequencerProxy::new(sequencer.into());
+
+    let mut ledger_txs = vec![];
+    for _ in 0..10 {
+        let tx_data = LedgerTxData::L2Tx(RoochTransaction::mock());
+        ledger_txs.push(sequencer_proxy.sequence_transaction(tx_data).await?);
+    }
+    let last_tx = ledger_txs.last().unwrap();
+    let last_root = last_tx.sequence_info.tx_accumulator_root;
+
+    for ledger_tx in ledger_txs.iter() {
+        let tx_order = ledger_tx.sequence_info.tx_order;
+        // against the accumulator root of the tx itself
+        let proof = sequencer_proxy
+            .get_tx_accumulator_proof(tx_order, None)
+            .await?
+            .expect("proof should exist");
+        let leaf_index = ledger_tx.sequence_info.tx_accumulator_leaf_index();
+        proof.verify(ledger_tx.sequence_info.tx_accumulator_root, leaf_index)?;
+
+        // against the accumulator root of the last tx
+        let proof = sequencer_proxy
+            .get_tx_accumulator_proof(tx_order, Some(last_tx.sequence_info.tx_order))
+            .await?
+            .expect("proof should exist");
+        proof.verify(last_root, leaf_index)?;
+        assert!(proof.verify(H256::random(), leaf_index).is_err());
+        // the proof should not be accepted for another tx order
+        assert!(proof.verify(last_root, leaf_index + 1).is_err());
+    }
+
+    // the accumulator tx order should not be less than the tx order
+    assert!(sequencer_proxy
+        .get_tx_accumulator_proof(last_tx.sequence_info.tx_order, Some(0))
+        .await
+        .is_err());
+    // not exist tx
+    assert!(sequencer_proxy
+        .get_tx_accumulator_proof(last_tx.sequence_info.tx_order + 1, None)
+        .await?
+        .is_none());
+    Ok(())
+}
@@ -1,16 +1,19 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use super::types::BlockHeightHash;
+use super::types::{Block, BlockHeightHash};
 use crate::addresses::BITCOIN_MOVE_ADDRESS;
 use anyhow::Result;
-use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use move_core_types::{
+    account_address::AccountAddress, ident_str, identifier::IdentStr, value::MoveValue,
+};
 use moveos_types::{
     module_binding::{ModuleBinding, MoveFunctionCaller},
     move_std::option::MoveOption,
     moveos_std::tx_context::TxContext,
-    state::MoveStructState,
     state::MoveStructType,
+    state::{MoveState, MoveStructState},
+    transaction::{FunctionCall, MoveAction},
 };
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +42,35 @@ impl MoveStructState for PendingTxs {
     }
 }
 
+/// The event emitted when the pending blocks are orphaned by a reorg.
+/// The transactions of an orphaned block are never processed, so no UTXO or Inscription is orphaned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockReorgEvent {
+    /// The height of the new block which triggers the reorg
+    pub block_height: u64,
+    /// The hash of the new block which triggers the reorg
+    pub block_hash: AccountAddress,
+    pub orphaned_blocks: Vec<BlockHeightHash>,
+}
+
+impl MoveStructType for BlockReorgEvent {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("BlockReorgEvent");
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+}
+
+impl MoveStructState for BlockReorgEvent {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            move_core_types::value::MoveTypeLayout::U64,
+            move_core_types::value::MoveTypeLayout::Address,
+            move_core_types::value::MoveTypeLayout::Vector(
+                Box::new(BlockHeightHash::type_layout()),
+            ),
+        ])
+    }
+}
+
 /// Rust bindings for BitcoinMove bitcoin module
 pub struct PendingBlockModule<'a> {
     caller: &'a dyn MoveFunctionCaller,
@@ -50,6 +82,10 @@ impl<'a> PendingBlockModule<'a> {
     pub const GET_BEST_BLOCK_FUNCTION_NAME: &'static IdentStr = ident_str!("get_best_block");
     pub const GET_REORG_BLOCK_COUNT_FUNCTION_NAME: &'static IdentStr =
         ident_str!("get_reorg_block_count");
+    pub const UPDATE_REORG_BLOCK_COUNT_FOR_LOCAL_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("update_reorg_block_count_for_local");
+    pub const ADD_PENDING_BLOCK_FOR_LOCAL_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("add_pending_block_for_local");
 
     pub fn get_ready_pending_txs(&self) -> Result<Option<PendingTxs>> {
         let call =
@@ -97,6 +133,30 @@ impl<'a> PendingBlockModule<'a> {
             })?;
         Ok(height)
     }
+
+    pub fn create_update_reorg_block_count_for_local_action(count: u64) -> MoveAction {
+        MoveAction::Function(FunctionCall::new(
+            Self::function_id(Self::UPDATE_REORG_BLOCK_COUNT_FOR_LOCAL_FUNCTION_NAME),
+            vec![],
+            vec![MoveValue::U64(count).simple_serialize().unwrap()],
+        ))
+    }
+
+    pub fn create_add_pending_block_for_local_action(
+        block_height: u64,
+        block: &Block,
+    ) -> MoveAction {
+        MoveAction::Function(FunctionCall::new(
+            Self::function_id(Self::ADD_PENDING_BLOCK_FOR_LOCAL_FUNCTION_NAME),
+            vec![],
+            vec![
+                MoveValue::U64(block_height).simple_serialize().unwrap(),
+                MoveValue::vector_u8(block.encode())
+                    .simple_serialize()
+                    .unwrap(),
+            ],
+        ))
+    }
 }
 
 impl<'a> ModuleBinding<'a> for PendingBlockModule<'a> {
@@ -0,0 +1,336 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::types::OutPoint;
+use crate::addresses::BITCOIN_MOVE_ADDRESS;
+use anyhow::{bail, Result};
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::move_std::option::MoveOption;
+use moveos_types::moveos_std::object::{self, ObjectID};
+use moveos_types::state::{MoveState, MoveStructState, MoveStructType};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub const MODULE_NAME: &IdentStr = ident_str!("runes");
+
+/// The spacer between the letters of a spaced rune name
+pub const SPACER: char = '•';
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct RuneId {
+    /// The height of the block which etched the rune
+    pub block: u64,
+    /// The index of the etching transaction in the block
+    pub tx: u32,
+}
+
+impl RuneId {
+    pub fn new(block: u64, tx: u32) -> Self {
+        Self { block, tx }
+    }
+
+    pub fn object_id(&self) -> ObjectID {
+        derive_rune_id(self)
+    }
+}
+
+impl Display for RuneId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.block, self.tx)
+    }
+}
+
+impl FromStr for RuneId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((block, tx)) = s.split_once(':') else {
+            bail!("Invalid RuneId: {}, the format is block:tx", s);
+        };
+        Ok(Self {
+            block: block.parse()?,
+            tx: tx.parse()?,
+        })
+    }
+}
+
+impl MoveStructType for RuneId {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneId");
+}
+
+impl MoveStructState for RuneId {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![u64::type_layout(), u32::type_layout()])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Terms {
+    pub amount: MoveOption<u128>,
+    pub cap: MoveOption<u128>,
+    pub height_start: MoveOption<u64>,
+    pub height_end: MoveOption<u64>,
+    pub offset_start: MoveOption<u64>,
+    pub offset_end: MoveOption<u64>,
+}
+
+impl MoveStructType for Terms {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Terms");
+}
+
+impl MoveStructState for Terms {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            MoveOption::<u128>::type_layout(),
+            MoveOption::<u128>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+            MoveOption::<u64>::type_layout(),
+        ])
+    }
+}
+
+/// The rune entry, it is a child object of the RuneStore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rune {
+    pub id: RuneId,
+    pub number: u64,
+    pub rune: u128,
+    pub spacers: u32,
+    pub divisibility: u8,
+    pub symbol: MoveOption<u32>,
+    pub premine: u128,
+    pub terms: MoveOption<Terms>,
+    pub turbo: bool,
+    pub mints: u128,
+    pub burned: u128,
+    /// The txid of the etching transaction
+    pub etching: AccountAddress,
+}
+
+impl Rune {
+    pub fn spaced_rune(&self) -> String {
+        format_spaced_rune(self.rune, self.spacers)
+    }
+
+    pub fn symbol(&self) -> Option<char> {
+        self.symbol
+            .as_ref()
+            .and_then(|symbol| char::from_u32(*symbol))
+    }
+}
+
+impl MoveStructType for Rune {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("Rune");
+}
+
+impl MoveStructState for Rune {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            RuneId::type_layout(),
+            u64::type_layout(),
+            u128::type_layout(),
+            u32::type_layout(),
+            u8::type_layout(),
+            MoveOption::<u32>::type_layout(),
+            u128::type_layout(),
+            MoveOption::<Terms>::type_layout(),
+            bool::type_layout(),
+            u128::type_layout(),
+            u128::type_layout(),
+            AccountAddress::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuneBalance {
+    pub id: RuneId,
+    pub amount: u128,
+}
+
+impl MoveStructType for RuneBalance {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneBalance");
+}
+
+impl MoveStructState for RuneBalance {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            RuneId::type_layout(),
+            u128::type_layout(),
+        ])
+    }
+}
+
+/// The rune balances of a UTXO, the object is attached to the UTXO as a seal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuneBalances {
+    pub outpoint: OutPoint,
+    pub balances: Vec<RuneBalance>,
+}
+
+impl RuneBalances {
+    pub fn object_id(&self) -> ObjectID {
+        derive_rune_balances_id(&self.outpoint)
+    }
+}
+
+impl MoveStructType for RuneBalances {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneBalances");
+}
+
+impl MoveStructState for RuneBalances {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            OutPoint::type_layout(),
+            Vec::<RuneBalance>::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuneStore {
+    /// The number of etched runes
+    pub runes: u64,
+}
+
+impl RuneStore {
+    pub fn object_id() -> ObjectID {
+        object::named_object_id(&Self::struct_tag())
+    }
+}
+
+impl MoveStructType for RuneStore {
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("RuneStore");
+}
+
+impl MoveStructState for RuneStore {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![u64::type_layout()])
+    }
+}
+
+pub fn derive_rune_id(id: &RuneId) -> ObjectID {
+    object::custom_object_id_with_parent::<RuneId, Rune>(RuneStore::object_id(), id)
+}
+
+pub fn derive_rune_balances_id(outpoint: &OutPoint) -> ObjectID {
+    object::custom_object_id_with_parent::<OutPoint, RuneBalances>(RuneStore::object_id(), outpoint)
+}
+
+/// Format the rune name, the name is a modified base-26 integer, `A` is 0, `Z` is 25, `AA` is 26.
+/// The spacers bit `i` means a spacer follows the `i`th letter.
+pub fn format_spaced_rune(rune: u128, spacers: u32) -> String {
+    let letters = if rune == u128::MAX {
+        "BCGDENLQRQWDSLRUGSNLBTMFIJAV".to_string()
+    } else {
+        let mut n = rune + 1;
+        let mut letters = Vec::new();
+        while n > 0 {
+            letters.push(char::from(b'A' + ((n - 1) % 26) as u8));
+            n = (n - 1) / 26;
+        }
+        letters.iter().rev().collect()
+    };
+    let len = letters.len();
+    let mut name = String::new();
+    for (i, c) in letters.chars().enumerate() {
+        name.push(c);
+        if i + 1 < len && i < 32 && spacers & (1 << i) != 0 {
+            name.push(SPACER);
+        }
+    }
+    name
+}
+
+/// Parse the spaced rune name to the rune name and the spacers
+pub fn parse_spaced_rune(s: &str) -> Result<(u128, u32)> {
+    let mut rune: u128 = 0;
+    let mut spacers = 0u32;
+    let mut letters = 0usize;
+    for c in s.chars() {
+        match c {
+            'A'..='Z' => {
+                if letters > 0 {
+                    rune = rune
+                        .checked_add(1)
+                        .ok_or_else(|| anyhow::anyhow!("Rune name is out of range: {}", s))?;
+                }
+                rune = rune
+                    .checked_mul(26)
+                    .and_then(|rune| rune.checked_add(c as u128 - 'A' as u128))
+                    .ok_or_else(|| anyhow::anyhow!("Rune name is out of range: {}", s))?;
+                letters += 1;
+            }
+            '.' | SPACER => {
+                if letters == 0 || letters > 32 || spacers & (1 << (letters - 1)) != 0 {
+                    bail!("Invalid spacer in rune name: {}", s);
+                }
+                spacers |= 1 << (letters - 1);
+            }
+            _ => bail!("Invalid character `{}` in rune name: {}", c, s),
+        }
+    }
+    if letters == 0 {
+        bail!("Empty rune name");
+    }
+    if spacers >= 1 << (letters - 1) {
+        bail!("Trailing spacer in rune name: {}", s);
+    }
+    Ok((rune, spacers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_spaced_rune() {
+        assert_eq!(format_spaced_rune(0, 0), "A");
+        assert_eq!(format_spaced_rune(25, 0), "Z");
+        assert_eq!(format_spaced_rune(26, 0), "AA");
+        assert_eq!(format_spaced_rune(27, 1), "A•B");
+        assert_eq!(
+            format_spaced_rune(u128::MAX, 0),
+            "BCGDENLQRQWDSLRUGSNLBTMFIJAV"
+        );
+    }
+
+    #[test]
+    fn test_parse_spaced_rune() {
+        for (rune, spacers) in [(0, 0), (25, 0), (26, 0), (27, 1), (u128::MAX, 0)] {
+            let name = format_spaced_rune(rune, spacers);
+            assert_eq!(parse_spaced_rune(&name).unwrap(), (rune, spacers));
+        }
+        assert_eq!(parse_spaced_rune("A.B").unwrap(), (27, 1));
+        assert!(parse_spaced_rune("").is_err());
+        assert!(parse_spaced_rune("A•").is_err());
+        assert!(parse_spaced_rune("•A").is_err());
+        assert!(parse_spaced_rune("a").is_err());
+        assert!(parse_spaced_rune("BCGDENLQRQWDSLRUGSNLBTMFIJAW").is_err());
+    }
+
+    #[test]
+    fn test_rune_id() {
+        let id = RuneId::from_str("840000:3").unwrap();
+        assert_eq!(id, RuneId::new(840000, 3));
+        assert_eq!(id.to_string(), "840000:3");
+        assert!(RuneId::from_str("840000").is_err());
+    }
+}
@@ -2,14 +2,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::da::batch::DABatch;
-use crate::da::segment::{Segment, SegmentID, SegmentV0};
+use crate::da::segment::{Segment, SegmentID, SegmentV0, SegmentV1};
 use lz4::EncoderBuilder;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::io;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 9;
+const BROTLI_LG_WINDOW_SIZE: u32 = 22;
+/// The max size of the decoded batch bytes of a chunk.
+/// A few bytes of compressed data could be decoded to gigabytes,
+/// so the decoder stops at this size instead of exhausting the memory.
+pub const MAX_CHUNK_DECODED_SIZE: usize = 512 * 1024 * 1024;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub enum ChunkVersion {
     V0,
+    V1,
     Unknown(u8),
 }
 
@@ -17,6 +30,7 @@ impl From<u8> for ChunkVersion {
     fn from(num: u8) -> Self {
         match num {
             0 => ChunkVersion::V0,
+            1 => ChunkVersion::V1,
             // ...
             _ => Self::Unknown(num),
         }
@@ -27,11 +41,123 @@ impl From<ChunkVersion> for u8 {
     fn from(version: ChunkVersion) -> Self {
         match version {
             ChunkVersion::V0 => 0,
+            ChunkVersion::V1 => 1,
             ChunkVersion::Unknown(num) => num,
         }
     }
 }
 
+/// The codec of the batch data in a chunk (since ChunkV1).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkCodec {
+    None,
+    Zstd,
+    Brotli,
+}
+
+impl TryFrom<u8> for ChunkCodec {
+    type Error = anyhow::Error;
+
+    fn try_from(num: u8) -> anyhow::Result<Self> {
+        match num {
+            0 => Ok(ChunkCodec::None),
+            1 => Ok(ChunkCodec::Zstd),
+            2 => Ok(ChunkCodec::Brotli),
+            _ => Err(anyhow::anyhow!("unsupported chunk codec: {}", num)),
+        }
+    }
+}
+
+impl From<ChunkCodec> for u8 {
+    fn from(codec: ChunkCodec) -> Self {
+        match codec {
+            ChunkCodec::None => 0,
+            ChunkCodec::Zstd => 1,
+            ChunkCodec::Brotli => 2,
+        }
+    }
+}
+
+impl fmt::Display for ChunkCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChunkCodec::None => write!(f, "none"),
+            ChunkCodec::Zstd => write!(f, "zstd"),
+            ChunkCodec::Brotli => write!(f, "brotli"),
+        }
+    }
+}
+
+impl FromStr for ChunkCodec {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(ChunkCodec::None),
+            "zstd" => Ok(ChunkCodec::Zstd),
+            "brotli" => Ok(ChunkCodec::Brotli),
+            _ => Err("chunk codec no match"),
+        }
+    }
+}
+
+impl ChunkCodec {
+    pub fn encode(&self, bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        match self {
+            ChunkCodec::None => Ok(bytes.to_vec()),
+            ChunkCodec::Zstd => Ok(zstd::encode_all(bytes, ZSTD_COMPRESSION_LEVEL)?),
+            ChunkCodec::Brotli => {
+                let mut compressed_bytes = Vec::new();
+                {
+                    let mut writer = brotli::CompressorWriter::new(
+                        &mut compressed_bytes,
+                        BROTLI_BUFFER_SIZE,
+                        BROTLI_QUALITY,
+                        BROTLI_LG_WINDOW_SIZE,
+                    );
+                    writer.write_all(bytes)?;
+                    writer.flush()?;
+                }
+                Ok(compressed_bytes)
+            }
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        self.decode_with_limit(bytes, MAX_CHUNK_DECODED_SIZE)
+    }
+
+    /// Decodes the bytes, fails if the decoded bytes are larger than `max_size`.
+    pub fn decode_with_limit(&self, bytes: &[u8], max_size: usize) -> anyhow::Result<Vec<u8>> {
+        match self {
+            ChunkCodec::None => Ok(bytes.to_vec()),
+            ChunkCodec::Zstd => {
+                read_to_end_with_limit(zstd::stream::read::Decoder::new(bytes)?, max_size)
+            }
+            ChunkCodec::Brotli => read_to_end_with_limit(
+                brotli::Decompressor::new(bytes, BROTLI_BUFFER_SIZE),
+                max_size,
+            ),
+        }
+    }
+}
+
+fn read_to_end_with_limit<R: Read>(reader: R, max_size: usize) -> anyhow::Result<Vec<u8>> {
+    let mut decoded_bytes = Vec::new();
+    // read one more byte than allowed to tell the exact limit from exceeding it
+    reader
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decoded_bytes)?;
+    if decoded_bytes.len() > max_size {
+        return Err(anyhow::anyhow!(
+            "decoded chunk data exceeds the max size: {} bytes",
+            max_size
+        ));
+    }
+    Ok(decoded_bytes)
+}
+
 pub trait Chunk {
     fn get_version(&self) -> ChunkVersion;
     fn to_segments(&self, max_segment_size: usize) -> Vec<Box<dyn Segment>>;
@@ -112,6 +238,102 @@ impl Chunk for ChunkV0 {
     }
 }
 
+// ChunkV1:
+// 1. each chunk maps to a batch (block number is chunk_id)
+// 2. chunk data: codec (1 byte) + batch bcs bytes encoded by the codec
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ChunkV1 {
+    pub version: ChunkVersion,
+    pub codec: ChunkCodec,
+    pub batch: DABatch,
+}
+
+impl ChunkV1 {
+    pub fn new(batch: DABatch, codec: ChunkCodec) -> Self {
+        Self {
+            version: ChunkVersion::V1,
+            codec,
+            batch,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let batch_bytes = bcs::to_bytes(&self.batch).unwrap();
+        let encoded_bytes = self
+            .codec
+            .encode(&batch_bytes)
+            .expect("encode chunk data should success");
+
+        let mut bytes = Vec::with_capacity(1 + encoded_bytes.len());
+        bytes.push(self.codec.into());
+        bytes.extend_from_slice(&encoded_bytes);
+        bytes
+    }
+
+    pub fn from_segments(segments: Vec<Box<dyn Segment>>) -> anyhow::Result<Self> {
+        let bytes = segments
+            .iter()
+            .flat_map(|segment| segment.get_data())
+            .collect::<Vec<_>>();
+        if bytes.is_empty() {
+            return Err(anyhow::anyhow!("chunk_v1: empty chunk data"));
+        }
+
+        let codec = ChunkCodec::try_from(bytes[0])?;
+        let batch_bytes = codec.decode(&bytes[1..])?;
+        let batch: DABatch = bcs::from_bytes(&batch_bytes)?;
+        Ok(Self::new(batch, codec))
+    }
+}
+
+impl Chunk for ChunkV1 {
+    fn get_version(&self) -> ChunkVersion {
+        ChunkVersion::V1
+    }
+
+    fn to_segments(&self, max_segment_size: usize) -> Vec<Box<dyn Segment>> {
+        let bytes = self.to_bytes();
+        let segments_data = bytes.chunks(max_segment_size);
+        let segments_count = segments_data.len();
+
+        let chunk_id = self.get_chunk_id();
+        segments_data
+            .enumerate()
+            .map(|(i, data)| {
+                Box::new(SegmentV1(SegmentV0 {
+                    id: SegmentID {
+                        chunk_id,
+                        segment_number: i as u64,
+                    },
+                    is_last: i == segments_count - 1,
+                    data_len: data.len() as u64,
+                    // *_checksum will be filled in to_bytes method of Segment
+                    data_checksum: 0,
+                    checksum: 0,
+                    data: data.to_vec(),
+                })) as Box<dyn Segment>
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn get_batches(&self) -> Vec<DABatch> {
+        vec![self.batch.clone()]
+    }
+
+    /// using batch.meta.block_number as chunk_id
+    fn get_chunk_id(&self) -> u128 {
+        self.batch.meta.block_range.block_number
+    }
+}
+
+/// Builds the chunk of a batch, ChunkV0 if no codec is given, otherwise ChunkV1 with the codec.
+pub fn chunk_from_batch(batch: DABatch, codec: Option<ChunkCodec>) -> Box<dyn Chunk> {
+    match codec {
+        None => Box::new(ChunkV0::from(batch)),
+        Some(codec) => Box::new(ChunkV1::new(batch, codec)),
+    }
+}
+
 pub fn chunk_from_segments(mut segments: Vec<Box<dyn Segment>>) -> anyhow::Result<Box<dyn Chunk>> {
     if segments.is_empty() {
         return Err(anyhow::anyhow!("empty segments"));
@@ -154,6 +376,7 @@ pub fn chunk_from_segments(mut segments: Vec<Box<dyn Segment>>) -> anyhow::Resul
 
     match version {
         ChunkVersion::V0 => Ok(Box::new(ChunkV0::from_segments(segments)?)),
+        ChunkVersion::V1 => Ok(Box::new(ChunkV1::from_segments(segments)?)),
         // ...
         ChunkVersion::Unknown(_) => Err(anyhow::anyhow!("unsupported segment version")),
     }
@@ -201,4 +424,60 @@ mod tests {
         // after serialization and deserialization, tx_hash will be dropped.
         assert!(act_batch.verify(true).is_ok())
     }
+
+    #[test]
+    fn test_chunk_v1() {
+        let tx_cnt = 128;
+        let keypair = RoochKeyPair::generate_secp256k1();
+
+        let tx_list = (0..tx_cnt)
+            .map(|i| random_ledger_transaction_with_order(i as u64 + 1, &keypair))
+            .collect::<Vec<_>>();
+        let batch =
+            DABatch::new(123, 1, 128, &tx_list, &keypair).expect("create batch should success");
+
+        for codec in [ChunkCodec::None, ChunkCodec::Zstd, ChunkCodec::Brotli] {
+            let chunk = chunk_from_batch(batch.clone(), Some(codec));
+            assert_eq!(chunk.get_version(), ChunkVersion::V1);
+            let segments = chunk.to_segments(1023);
+            assert!(segments
+                .iter()
+                .all(|segment| segment.get_version() == ChunkVersion::V1));
+
+            let chunk = chunk_from_segments(segments).unwrap();
+            assert_eq!(chunk.get_version(), ChunkVersion::V1);
+            let batches = chunk.get_batches();
+            let act_batch = batches.first().unwrap();
+            assert_eq!(act_batch, &batch, "codec: {}", codec);
+            assert!(act_batch.verify(true).is_ok())
+        }
+    }
+
+    #[test]
+    fn test_chunk_codec() {
+        let data = vec![7u8; 4096];
+        for codec in [ChunkCodec::None, ChunkCodec::Zstd, ChunkCodec::Brotli] {
+            let encoded = codec.encode(&data).unwrap();
+            if codec != ChunkCodec::None {
+                assert!(encoded.len() < data.len());
+            }
+            assert_eq!(codec.decode(&encoded).unwrap(), data);
+            assert_eq!(ChunkCodec::try_from(u8::from(codec)).unwrap(), codec);
+            assert_eq!(ChunkCodec::from_str(&codec.to_string()).unwrap(), codec);
+        }
+        assert!(ChunkCodec::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_chunk_codec_decode_limit() {
+        let data = vec![7u8; 4096];
+        for codec in [ChunkCodec::Zstd, ChunkCodec::Brotli] {
+            let encoded = codec.encode(&data).unwrap();
+            assert_eq!(codec.decode_with_limit(&encoded, data.len()).unwrap(), data);
+            let err = codec
+                .decode_with_limit(&encoded, data.len() - 1)
+                .unwrap_err();
+            assert!(err.to_string().contains("exceeds"), "codec: {}", codec);
+        }
+    }
 }
@@ -84,10 +84,10 @@ impl SegmentV0 {
     }
 }
 
-impl Segment for SegmentV0 {
-    fn to_bytes(&self) -> Vec<u8> {
+impl SegmentV0 {
+    fn to_bytes_with_version(&self, version: ChunkVersion) -> Vec<u8> {
         let mut bytes = Vec::with_capacity(SEGMENT_V0_DATA_OFFSET + self.data_len as usize);
-        bytes.push(ChunkVersion::V0.into()); // version
+        bytes.push(version.into()); // version
         bytes.extend_from_slice(&self.id.chunk_id.to_le_bytes());
         bytes.extend_from_slice(&self.id.segment_number.to_le_bytes());
         bytes.push(self.is_last as u8);
@@ -99,6 +99,12 @@ impl Segment for SegmentV0 {
         bytes.extend_from_slice(&self.data);
         bytes
     }
+}
+
+impl Segment for SegmentV0 {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with_version(ChunkVersion::V0)
+    }
 
     fn get_version(&self) -> ChunkVersion {
         ChunkVersion::V0
@@ -117,9 +123,41 @@ impl Segment for SegmentV0 {
     }
 }
 
+// SegmentV1 shares the layout of SegmentV0, only the version differs: its data belongs to a ChunkV1.
+#[derive(Serialize, Debug, PartialEq, Clone)]
+pub struct SegmentV1(pub SegmentV0);
+
+impl SegmentV1 {
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        SegmentV0::from_bytes(bytes).map(Self)
+    }
+}
+
+impl Segment for SegmentV1 {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes_with_version(ChunkVersion::V1)
+    }
+
+    fn get_version(&self) -> ChunkVersion {
+        ChunkVersion::V1
+    }
+
+    fn get_id(&self) -> SegmentID {
+        self.0.id
+    }
+
+    fn get_data(&self) -> Vec<u8> {
+        self.0.data.clone()
+    }
+
+    fn is_last(&self) -> bool {
+        self.0.is_last
+    }
+}
+
 pub fn get_data_offset(version: ChunkVersion) -> usize {
     match version {
-        ChunkVersion::V0 => SEGMENT_V0_DATA_OFFSET,
+        ChunkVersion::V0 | ChunkVersion::V1 => SEGMENT_V0_DATA_OFFSET,
         ChunkVersion::Unknown(_) => panic!("unsupported segment version"),
     }
 }
@@ -129,6 +167,7 @@ pub fn segment_from_bytes(bytes: &[u8]) -> anyhow::Result<Box<dyn Segment>> {
 
     match ChunkVersion::from(version) {
         ChunkVersion::V0 => Ok(Box::new(SegmentV0::from_bytes(bytes)?)),
+        ChunkVersion::V1 => Ok(Box::new(SegmentV1::from_bytes(bytes)?)),
         // ...
         ChunkVersion::Unknown(_) => Err(anyhow::anyhow!(
             "failed to deserialize segment from bytes: unsupported segment version"
@@ -201,7 +240,10 @@ mod tests {
             data: vec![1, 2, 3, 4, 5],
         };
 
-        let segments: Vec<Box<dyn Segment>> = vec![Box::new(segment_v0.clone())];
+        let segments: Vec<Box<dyn Segment>> = vec![
+            Box::new(segment_v0.clone()),
+            Box::new(SegmentV1(segment_v0.clone())),
+        ];
 
         for segment in segments {
             let bytes = segment.to_bytes();
@@ -215,6 +257,12 @@ mod tests {
                     segment_v0.data_checksum = recovered_segment.data_checksum;
                     assert_eq!(&segment_v0, &recovered_segment)
                 }
+                ChunkVersion::V1 => {
+                    let recovered_segment =
+                        segment_from_bytes(&bytes).expect("successful deserialization");
+                    assert_eq!(recovered_segment.get_version(), ChunkVersion::V1);
+                    assert_eq!(recovered_segment.get_data(), segment_v0.data);
+                }
 
                 _ => panic!("unsupported segment version"),
             };
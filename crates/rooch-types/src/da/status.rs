@@ -31,4 +31,19 @@ pub struct DAServerStatus {
     /// The available backend_identifiers and their latest block numbers, e.g., [("openda-gcs", 100), ("openda-fs", 200)]
     /// Block numbers only update in runtime, not in startup. In the beginning, it should be 0.
     pub avail_backends: Vec<(String, u128)>,
+    /// The backend_identifiers and their submit counters since server start.
+    #[serde(default)]
+    pub backend_submit_stats: Vec<(String, AdapterSubmitStatSnapshot)>,
+}
+
+/// Point-in-time counters of an adapter's submissions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdapterSubmitStatSnapshot {
+    pub submitted_segments: u64,
+    pub submitted_bytes: u64,
+    /// Segments whose content had been stored before (content-addressed backends only).
+    pub deduplicated_segments: u64,
+    pub retries: u64,
+    /// Segments failed after all retries.
+    pub failures: u64,
 }
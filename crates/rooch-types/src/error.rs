@@ -147,6 +147,9 @@ pub enum RoochError {
 
     #[error("Failed to dispatch subscription: {0}")]
     FailedToDispatchSubscription(String),
+
+    #[error("State root not available: {0}")]
+    StateRootNotAvailable(String),
 }
 
 impl From<anyhow::Error> for RoochError {
@@ -53,6 +53,8 @@ pub enum BuiltinAuthValidator {
     /// Migration: Authenticator.did(txHash, signer, vmFragment, SigningEnvelope.WebAuthnV0)
     WEBAUTHN,
     DID,
+    /// The sponsored transaction, the gas is paid by the sponsor
+    Sponsor,
 }
 
 impl BuiltinAuthValidator {
@@ -61,6 +63,7 @@ impl BuiltinAuthValidator {
     const BITCOIN_MULTISIGN: u8 = 0x02;
     const WEBAUTHN_FLAG: u8 = 0x03;
     const DID_FLAG: u8 = 0x04;
+    const SPONSOR_FLAG: u8 = 0x05;
 
     pub fn flag(&self) -> u8 {
         match self {
@@ -69,6 +72,7 @@ impl BuiltinAuthValidator {
             BuiltinAuthValidator::BitcoinMultisign => Self::BITCOIN_MULTISIGN,
             BuiltinAuthValidator::WEBAUTHN => Self::WEBAUTHN_FLAG,
             BuiltinAuthValidator::DID => Self::DID_FLAG,
+            BuiltinAuthValidator::Sponsor => Self::SPONSOR_FLAG,
         }
     }
 
@@ -86,6 +90,7 @@ impl BuiltinAuthValidator {
             Self::BITCOIN_MULTISIGN => Ok(BuiltinAuthValidator::BitcoinMultisign),
             Self::WEBAUTHN_FLAG => Ok(BuiltinAuthValidator::WEBAUTHN),
             Self::DID_FLAG => Ok(BuiltinAuthValidator::DID),
+            Self::SPONSOR_FLAG => Ok(BuiltinAuthValidator::Sponsor),
             _ => Err(RoochError::KeyConversionError(
                 "Invalid key auth validator".to_owned(),
             )),
@@ -121,6 +126,11 @@ impl BuiltinAuthValidator {
                 module_address: ROOCH_FRAMEWORK_ADDRESS,
                 module_name: MoveString::from_str("did_validator").expect("Should be valid"),
             },
+            BuiltinAuthValidator::Sponsor => AuthValidator {
+                id: self.flag().into(),
+                module_address: ROOCH_FRAMEWORK_ADDRESS,
+                module_name: MoveString::from_str("sponsor_validator").expect("Should be valid"),
+            },
         }
     }
 }
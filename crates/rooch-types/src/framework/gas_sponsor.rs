@@ -0,0 +1,115 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
+use anyhow::Result;
+use move_core_types::u256::U256;
+use move_core_types::value::MoveValue;
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    move_std::option::MoveOption,
+    move_std::string::MoveString,
+    moveos_std::tx_context::TxContext,
+    state::MoveState,
+    transaction::{FunctionCall, MoveAction},
+};
+use std::str::FromStr;
+
+pub const MODULE_NAME: &IdentStr = ident_str!("gas_sponsor");
+
+/// Rust bindings for RoochFramework gas_sponsor module
+pub struct GasSponsorModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> GasSponsorModule<'a> {
+    pub const BUDGET_FUNCTION_NAME: &'static IdentStr = ident_str!("budget");
+    pub const SET_BUDGET_ENTRY_FUNCTION_NAME: &'static IdentStr = ident_str!("set_budget_entry");
+    pub const REMOVE_BUDGET_ENTRY_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("remove_budget_entry");
+
+    /// The function name of the module scope
+    pub const ANY_FUNCTION: &'static str = "*";
+
+    /// Get the remaining gas budget of the sponsor for the function, or the module if the
+    /// `function_name` is `*`
+    pub fn budget(
+        &self,
+        sponsor: AccountAddress,
+        module_address: AccountAddress,
+        module_name: &str,
+        function_name: &str,
+    ) -> Result<Option<U256>> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::BUDGET_FUNCTION_NAME),
+            vec![],
+            vec![
+                MoveValue::Address(sponsor).simple_serialize().unwrap(),
+                MoveValue::Address(module_address)
+                    .simple_serialize()
+                    .unwrap(),
+                MoveString::from_str(module_name)?.to_bytes(),
+                MoveString::from_str(function_name)?.to_bytes(),
+            ],
+        );
+        let ctx = TxContext::new_readonly_ctx(sponsor);
+        let budget = self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<MoveOption<U256>>(&value.value)
+                    .expect("should be a valid MoveOption<U256>")
+                    .into()
+            })?;
+        Ok(budget)
+    }
+
+    pub fn set_budget_action(
+        module_address: AccountAddress,
+        module_name: &str,
+        function_name: &str,
+        budget: U256,
+    ) -> Result<MoveAction> {
+        Ok(Self::create_move_action(
+            Self::SET_BUDGET_ENTRY_FUNCTION_NAME,
+            vec![],
+            vec![
+                module_address.to_move_value(),
+                MoveString::from_str(module_name)?.to_move_value(),
+                MoveString::from_str(function_name)?.to_move_value(),
+                MoveValue::U256(budget),
+            ],
+        ))
+    }
+
+    pub fn remove_budget_action(
+        module_address: AccountAddress,
+        module_name: &str,
+        function_name: &str,
+    ) -> Result<MoveAction> {
+        Ok(Self::create_move_action(
+            Self::REMOVE_BUDGET_ENTRY_FUNCTION_NAME,
+            vec![],
+            vec![
+                module_address.to_move_value(),
+                MoveString::from_str(module_name)?.to_move_value(),
+                MoveString::from_str(function_name)?.to_move_value(),
+            ],
+        ))
+    }
+}
+
+impl<'a> ModuleBinding<'a> for GasSponsorModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
@@ -21,6 +21,7 @@ pub mod empty;
 pub mod ethereum;
 pub mod ethereum_address;
 pub mod gas_coin;
+pub mod gas_sponsor;
 pub mod genesis;
 pub mod indexer;
 pub mod multi_coin_store;
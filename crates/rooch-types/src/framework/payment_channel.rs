@@ -379,6 +379,8 @@ impl<'a> PaymentChannelModule<'a> {
         ident_str!("create_payment_hub");
     pub const DEPOSIT_TO_HUB_ENTRY_FUNCTION_NAME: &'static IdentStr =
         ident_str!("deposit_to_hub_entry");
+    pub const TRANSFER_TO_HUB_ENTRY_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("transfer_to_hub_entry");
     pub const OPEN_CHANNEL_ENTRY_FUNCTION_NAME: &'static IdentStr =
         ident_str!("open_channel_entry");
     pub const AUTHORIZE_SUB_CHANNEL_ENTRY_FUNCTION_NAME: &'static IdentStr =
@@ -441,6 +443,18 @@ impl<'a> PaymentChannelModule<'a> {
         )
     }
 
+    pub fn transfer_to_hub_entry_action(
+        coin_type: StructTag,
+        receiver: AccountAddress,
+        amount: U256,
+    ) -> MoveAction {
+        Self::create_move_action(
+            Self::TRANSFER_TO_HUB_ENTRY_FUNCTION_NAME,
+            vec![TypeTag::Struct(Box::new(coin_type))],
+            vec![MoveValue::Address(receiver), MoveValue::U256(amount)],
+        )
+    }
+
     pub fn open_channel_entry_action(
         coin_type: StructTag,
         channel_receiver: AccountAddress,
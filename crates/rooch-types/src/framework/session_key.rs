@@ -4,9 +4,11 @@
 use crate::addresses::ROOCH_FRAMEWORK_ADDRESS;
 use crate::authentication_key::AuthenticationKey;
 use anyhow::Result;
+use move_core_types::u256::U256;
 use move_core_types::value::MoveValue;
 use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
 use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::moveos_std::simple_map::SimpleMap;
 use moveos_types::{
     module_binding::{ModuleBinding, MoveFunctionCaller},
     move_std::option::MoveOption,
@@ -183,6 +185,58 @@ impl MoveStructState for SessionKey {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct TransferCap {
+    pub cap: U256,
+    pub transferred: U256,
+}
+
+impl MoveStructType for TransferCap {
+    const ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("TransferCap");
+}
+
+impl MoveStructState for TransferCap {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            move_core_types::value::MoveTypeLayout::U256,
+            move_core_types::value::MoveTypeLayout::U256,
+        ])
+    }
+}
+
+/// The optional limits of a session key, the zero values mean no limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionKeyLimit {
+    pub max_gas: U256,
+    pub gas_used: U256,
+    pub max_tx_count: u64,
+    pub tx_count: u64,
+    pub expiration_time: u64,
+    /// The transfer caps of the coin types, the key is the canonical coin type name
+    pub transfer_caps: SimpleMap<MoveString, TransferCap>,
+}
+
+impl MoveStructType for SessionKeyLimit {
+    const ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SessionKeyLimit");
+}
+
+impl MoveStructState for SessionKeyLimit {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            move_core_types::value::MoveTypeLayout::U256,
+            move_core_types::value::MoveTypeLayout::U256,
+            move_core_types::value::MoveTypeLayout::U64,
+            move_core_types::value::MoveTypeLayout::U64,
+            move_core_types::value::MoveTypeLayout::U64,
+            SimpleMap::<MoveString, TransferCap>::type_layout(),
+        ])
+    }
+}
+
 /// Rust bindings for RoochFramework session_key module
 pub struct SessionKeyModule<'a> {
     caller: &'a dyn MoveFunctionCaller,
@@ -194,6 +248,12 @@ impl<'a> SessionKeyModule<'a> {
         ident_str!("create_session_key_entry");
     pub const GET_SESSION_KEYS_HANDLE_FUNCTION_NAME: &'static IdentStr =
         ident_str!("get_session_keys_handle");
+    pub const GET_SESSION_KEY_LIMIT_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("get_session_key_limit");
+    pub const CREATE_SESSION_KEY_WITH_LIMIT_ENTRY_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("create_session_key_with_limit_entry");
+    pub const SET_SESSION_KEY_LIMIT_ENTRY_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("set_session_key_limit_entry");
 
     pub fn get_session_key(
         &self,
@@ -226,6 +286,37 @@ impl<'a> SessionKeyModule<'a> {
         Ok(session_key)
     }
 
+    pub fn get_session_key_limit(
+        &self,
+        account_address: AccountAddress,
+        auth_key: &AuthenticationKey,
+    ) -> Result<Option<SessionKeyLimit>> {
+        let call = FunctionCall::new(
+            Self::function_id(Self::GET_SESSION_KEY_LIMIT_FUNCTION_NAME),
+            vec![],
+            vec![
+                MoveValue::Address(account_address)
+                    .simple_serialize()
+                    .unwrap(),
+                MoveValue::vector_u8(auth_key.as_ref().to_vec())
+                    .simple_serialize()
+                    .unwrap(),
+            ],
+        );
+        let ctx = TxContext::new_readonly_ctx(account_address);
+        let limit = self
+            .caller
+            .call_function(&ctx, call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<MoveOption<SessionKeyLimit>>(&value.value)
+                    .expect("should be a valid MoveOption<SessionKeyLimit>")
+                    .into()
+            })?;
+        Ok(limit)
+    }
+
     pub fn get_session_keys_handle(
         &self,
         account_address: AccountAddress,
@@ -272,6 +363,65 @@ impl<'a> SessionKeyModule<'a> {
             ],
         )
     }
+
+    /// Create a session key with the limits, the `transfer_caps` are the canonical coin type
+    /// names and the max amounts the session key can transfer
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_session_key_with_limit_action(
+        app_name: MoveString,
+        app_url: MoveString,
+        authentication_key: Vec<u8>,
+        scopes: Vec<SessionScope>,
+        max_inactive_interval: u64,
+        max_gas: U256,
+        max_tx_count: u64,
+        expiration_time: u64,
+        transfer_caps: Vec<(MoveString, U256)>,
+    ) -> MoveAction {
+        let (cap_coin_types, cap_amounts): (Vec<_>, Vec<_>) = transfer_caps.into_iter().unzip();
+        Self::create_move_action(
+            Self::CREATE_SESSION_KEY_WITH_LIMIT_ENTRY_FUNCTION_NAME,
+            vec![],
+            vec![
+                app_name.to_move_value(),
+                app_url.to_move_value(),
+                MoveValue::vector_u8(authentication_key),
+                scopes
+                    .iter()
+                    .map(|scope| MoveString::from(scope.to_string()))
+                    .collect::<Vec<_>>()
+                    .to_move_value(),
+                MoveValue::U64(max_inactive_interval),
+                MoveValue::U256(max_gas),
+                MoveValue::U64(max_tx_count),
+                MoveValue::U64(expiration_time),
+                cap_coin_types.to_move_value(),
+                cap_amounts.to_move_value(),
+            ],
+        )
+    }
+
+    pub fn set_session_key_limit_action(
+        authentication_key: Vec<u8>,
+        max_gas: U256,
+        max_tx_count: u64,
+        expiration_time: u64,
+        transfer_caps: Vec<(MoveString, U256)>,
+    ) -> MoveAction {
+        let (cap_coin_types, cap_amounts): (Vec<_>, Vec<_>) = transfer_caps.into_iter().unzip();
+        Self::create_move_action(
+            Self::SET_SESSION_KEY_LIMIT_ENTRY_FUNCTION_NAME,
+            vec![],
+            vec![
+                MoveValue::vector_u8(authentication_key),
+                MoveValue::U256(max_gas),
+                MoveValue::U64(max_tx_count),
+                MoveValue::U64(expiration_time),
+                cap_coin_types.to_move_value(),
+                cap_amounts.to_move_value(),
+            ],
+        )
+    }
 }
 
 impl<'a> ModuleBinding<'a> for SessionKeyModule<'a> {
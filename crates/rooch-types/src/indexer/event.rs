@@ -116,10 +116,31 @@ pub enum EventFilter {
         /// right endpoint of transaction order, exclusive
         to_order: u64,
     },
+    /// Return events matching all of the filters.
+    And(Vec<EventFilter>),
+    /// Return events matching any of the filters.
+    Or(Vec<EventFilter>),
+    /// Return events not matching the filter.
+    Not(Box<EventFilter>),
     All,
 }
 
 impl EventFilter {
+    /// The nesting depth of the `And`/`Or`/`Not` combinators, 0 for the other filters.
+    pub fn depth(&self) -> usize {
+        match self {
+            EventFilter::And(filters) | EventFilter::Or(filters) => {
+                1 + filters
+                    .iter()
+                    .map(|filter| filter.depth())
+                    .max()
+                    .unwrap_or(0)
+            }
+            EventFilter::Not(filter) => 1 + filter.depth(),
+            _ => 0,
+        }
+    }
+
     fn try_matches(&self, item: &IndexerEvent) -> Result<bool> {
         Ok(match self {
             EventFilter::EventTypeWithSender {
@@ -147,6 +168,23 @@ impl EventFilter {
                 *from_order <= item.indexer_event_id.tx_order
                     && item.indexer_event_id.tx_order < *to_order
             }
+            EventFilter::And(filters) => {
+                for filter in filters {
+                    if !filter.try_matches(item)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            EventFilter::Or(filters) => {
+                for filter in filters {
+                    if filter.try_matches(item)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            EventFilter::Not(filter) => !filter.try_matches(item)?,
             EventFilter::All => true,
         })
     }
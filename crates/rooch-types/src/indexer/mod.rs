@@ -6,6 +6,19 @@ pub mod field;
 pub mod state;
 pub mod transaction;
 
+/// The max nesting depth of the `And`/`Or`/`Not` filter combinators
+pub const MAX_FILTER_DEPTH: usize = 8;
+
+/// Check the nesting depth of a filter before it is matched recursively.
+pub fn check_filter_depth(depth: usize) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        depth <= MAX_FILTER_DEPTH,
+        "Filter nesting depth exceeds the maximum {}",
+        MAX_FILTER_DEPTH
+    );
+    Ok(())
+}
+
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::bitcoin::ord::Inscription;
+use crate::bitcoin::runes::RuneBalances;
 use crate::bitcoin::utxo::UTXO;
 use crate::indexer::Filter;
 use anyhow::Result;
@@ -22,6 +23,8 @@ pub static UTXO_TYPE_TAG: Lazy<TypeTag> = Lazy::new(UTXO::type_tag);
 
 pub static INSCRIPTION_TYPE_TAG: Lazy<TypeTag> = Lazy::new(Inscription::type_tag);
 
+pub static RUNE_BALANCES_TYPE_TAG: Lazy<TypeTag> = Lazy::new(RuneBalances::type_tag);
+
 /// Index all Object state, include child object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexerObjectState {
@@ -56,9 +59,10 @@ impl IndexerObjectState {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ObjectStateType {
-    ObjectState, //all object states exclude utxo and inscription
+    ObjectState, //all object states exclude utxo, inscription and rune balances
     UTXO,
     Inscription,
+    RuneBalances,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -66,6 +70,7 @@ pub struct IndexerObjectStateChangeSet {
     pub object_states: IndexerObjectStateChanges,
     pub object_state_utxos: IndexerObjectStateChanges,
     pub object_state_inscriptions: IndexerObjectStateChanges,
+    pub object_state_rune_balances: IndexerObjectStateChanges,
 }
 
 impl IndexerObjectStateChangeSet {
@@ -76,6 +81,10 @@ impl IndexerObjectStateChangeSet {
             self.object_state_inscriptions
                 .update_object_states
                 .push(state)
+        } else if type_tag_match(&state.metadata.object_type, &RUNE_BALANCES_TYPE_TAG) {
+            self.object_state_rune_balances
+                .update_object_states
+                .push(state)
         } else {
             self.object_states.update_object_states.push(state)
         }
@@ -86,6 +95,10 @@ impl IndexerObjectStateChangeSet {
             self.object_state_utxos.new_object_states.push(state)
         } else if type_tag_match(&state.metadata.object_type, &INSCRIPTION_TYPE_TAG) {
             self.object_state_inscriptions.new_object_states.push(state)
+        } else if type_tag_match(&state.metadata.object_type, &RUNE_BALANCES_TYPE_TAG) {
+            self.object_state_rune_balances
+                .new_object_states
+                .push(state)
         } else {
             self.object_states.new_object_states.push(state)
         }
@@ -100,6 +113,10 @@ impl IndexerObjectStateChangeSet {
             self.object_state_inscriptions
                 .remove_object_states
                 .push(object_id.to_string())
+        } else if type_tag_match(object_type, &RUNE_BALANCES_TYPE_TAG) {
+            self.object_state_rune_balances
+                .remove_object_states
+                .push(object_id.to_string())
         } else {
             self.object_states
                 .remove_object_states
@@ -120,6 +137,7 @@ pub struct IndexerObjectStatesIndexGenerator {
     pub object_states_index_generator: u64,
     pub object_state_utxos_index_generator: u64,
     pub object_state_inscriptions_generator: u64,
+    pub object_state_rune_balances_generator: u64,
 }
 
 impl IndexerObjectStatesIndexGenerator {
@@ -128,6 +146,8 @@ impl IndexerObjectStatesIndexGenerator {
             self.object_state_utxos_index_generator += 1;
         } else if type_tag_match(object_type, &INSCRIPTION_TYPE_TAG) {
             self.object_state_inscriptions_generator += 1;
+        } else if type_tag_match(object_type, &RUNE_BALANCES_TYPE_TAG) {
+            self.object_state_rune_balances_generator += 1;
         } else {
             self.object_states_index_generator += 1;
         }
@@ -138,6 +158,8 @@ impl IndexerObjectStatesIndexGenerator {
             self.object_state_utxos_index_generator
         } else if type_tag_match(object_type, &INSCRIPTION_TYPE_TAG) {
             self.object_state_inscriptions_generator
+        } else if type_tag_match(object_type, &RUNE_BALANCES_TYPE_TAG) {
+            self.object_state_rune_balances_generator
         } else {
             self.object_states_index_generator
         }
@@ -358,9 +380,30 @@ pub enum ObjectStateFilter {
     Owner(AccountAddress),
     /// Query by object ids.
     ObjectId(Vec<ObjectID>),
+    /// Query the objects matching all of the filters.
+    And(Vec<ObjectStateFilter>),
+    /// Query the objects matching any of the filters.
+    Or(Vec<ObjectStateFilter>),
+    /// Query the objects not matching the filter.
+    Not(Box<ObjectStateFilter>),
 }
 
 impl ObjectStateFilter {
+    /// The nesting depth of the `And`/`Or`/`Not` combinators, 0 for the other filters.
+    pub fn depth(&self) -> usize {
+        match self {
+            ObjectStateFilter::And(filters) | ObjectStateFilter::Or(filters) => {
+                1 + filters
+                    .iter()
+                    .map(|filter| filter.depth())
+                    .max()
+                    .unwrap_or(0)
+            }
+            ObjectStateFilter::Not(filter) => 1 + filter.depth(),
+            _ => 0,
+        }
+    }
+
     fn try_matches(&self, item: &IndexerObjectState) -> Result<bool> {
         Ok(match self {
             ObjectStateFilter::ObjectTypeWithOwner {
@@ -371,6 +414,23 @@ impl ObjectStateFilter {
             ObjectStateFilter::ObjectId(object_ids) => {
                 object_ids.len() == 1 && object_ids[0] == item.metadata.id
             }
+            ObjectStateFilter::And(filters) => {
+                for filter in filters {
+                    if !filter.try_matches(item)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            ObjectStateFilter::Or(filters) => {
+                for filter in filters {
+                    if filter.try_matches(item)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            ObjectStateFilter::Not(filter) => !filter.try_matches(item)?,
         })
     }
 }
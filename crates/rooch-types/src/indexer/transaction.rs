@@ -87,10 +87,31 @@ pub enum TransactionFilter {
         /// right endpoint of transaction order, exclusive
         to_order: u64,
     },
+    /// Return transactions matching all of the filters.
+    And(Vec<TransactionFilter>),
+    /// Return transactions matching any of the filters.
+    Or(Vec<TransactionFilter>),
+    /// Return transactions not matching the filter.
+    Not(Box<TransactionFilter>),
     All,
 }
 
 impl TransactionFilter {
+    /// The nesting depth of the `And`/`Or`/`Not` combinators, 0 for the other filters.
+    pub fn depth(&self) -> usize {
+        match self {
+            TransactionFilter::And(filters) | TransactionFilter::Or(filters) => {
+                1 + filters
+                    .iter()
+                    .map(|filter| filter.depth())
+                    .max()
+                    .unwrap_or(0)
+            }
+            TransactionFilter::Not(filter) => 1 + filter.depth(),
+            _ => 0,
+        }
+    }
+
     fn try_matches(&self, item: &TransactionWithInfo) -> Result<bool> {
         Ok(match self {
             TransactionFilter::Sender(sender) => {
@@ -121,6 +142,23 @@ impl TransactionFilter {
                 *from_order <= item.transaction.sequence_info.tx_order
                     && item.transaction.sequence_info.tx_order < *to_order
             }
+            TransactionFilter::And(filters) => {
+                for filter in filters {
+                    if !filter.try_matches(item)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            TransactionFilter::Or(filters) => {
+                for filter in filters {
+                    if filter.try_matches(item)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            TransactionFilter::Not(filter) => !filter.try_matches(item)?,
             TransactionFilter::All => true,
         })
     }
@@ -21,6 +21,7 @@ use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
 
 use crate::{
+    address::RoochAddress,
     crypto::{RoochKeyPair, Signature, SignatureScheme},
     framework::{
         auth_payload::{AuthPayload, MultisignAuthPayload, SignData},
@@ -28,6 +29,7 @@ use crate::{
     },
     rooch_network::{BuiltinChainID, RoochNetwork},
 };
+use move_core_types::account_address::AccountAddress;
 use moveos_types::h256::sha2_256_of;
 
 use super::RoochTransactionData;
@@ -251,6 +253,84 @@ impl BuiltinAuthenticator for DIDAuthenticator {
     }
 }
 
+/// The authenticator payload of the sponsored transaction, the same as `SponsorAuthPayload` in `sponsor_validator.move`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SponsorAuthPayload {
+    pub sender_auth_validator_id: u64,
+    pub sender_payload: Vec<u8>,
+    pub sponsor: AccountAddress,
+    pub sponsor_payload: Vec<u8>,
+}
+
+/// The sponsored transaction authenticator, it wraps the sender's authenticator and the gas
+/// sponsor's Bitcoin authenticator, the gas is charged to the sponsor.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SponsorAuthenticator {
+    pub payload: SponsorAuthPayload,
+}
+
+impl SponsorAuthenticator {
+    pub fn new(
+        sender_authenticator: Authenticator,
+        sponsor: RoochAddress,
+        sponsor_authenticator: BitcoinAuthenticator,
+    ) -> Self {
+        Self {
+            payload: SponsorAuthPayload {
+                sender_auth_validator_id: sender_authenticator.auth_validator_id,
+                sender_payload: sender_authenticator.payload,
+                sponsor: sponsor.into(),
+                sponsor_payload: sponsor_authenticator.payload(),
+            },
+        }
+    }
+
+    /// Sign the transaction as the gas sponsor, the sponsor's key must be a Secp256k1 key
+    pub fn sign(
+        sender_authenticator: Authenticator,
+        sponsor_kp: &RoochKeyPair,
+        tx_data: &RoochTransactionData,
+    ) -> Result<Self> {
+        let sponsor = sponsor_kp.public().rooch_address()?;
+        let sponsor_authenticator = BitcoinAuthenticator::sign(sponsor_kp, tx_data);
+        Ok(Self::new(
+            sender_authenticator,
+            sponsor,
+            sponsor_authenticator,
+        ))
+    }
+
+    /// Decode the sponsor authenticator, return None if the authenticator is not sponsored
+    pub fn try_from_authenticator(authenticator: &Authenticator) -> Result<Option<Self>> {
+        if authenticator.auth_validator_id != BuiltinAuthValidator::Sponsor.flag() as u64 {
+            return Ok(None);
+        }
+        let payload = bcs::from_bytes::<SponsorAuthPayload>(&authenticator.payload)?;
+        Ok(Some(Self { payload }))
+    }
+
+    pub fn sponsor(&self) -> RoochAddress {
+        self.payload.sponsor.into()
+    }
+
+    pub fn sender_authenticator(&self) -> Authenticator {
+        Authenticator::new(
+            self.payload.sender_auth_validator_id,
+            self.payload.sender_payload.clone(),
+        )
+    }
+}
+
+impl BuiltinAuthenticator for SponsorAuthenticator {
+    fn auth_validator_id(&self) -> u64 {
+        BuiltinAuthValidator::Sponsor.flag().into()
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        bcs::to_bytes(&self.payload).expect("Serialize SponsorAuthenticator should success")
+    }
+}
+
 /// Helper function to compute Bitcoin message digest
 /// Uses bitcoin consensus encoding format to match the standard Bitcoin message signing
 fn bitcoin_message_digest(message: &[u8]) -> Vec<u8> {
@@ -335,6 +415,15 @@ impl Authenticator {
         Ok(did_auth.into())
     }
 
+    /// Create a sponsored authenticator, the gas is paid by the owner of `sponsor_kp`
+    pub fn sponsor(
+        sender_authenticator: Authenticator,
+        sponsor_kp: &RoochKeyPair,
+        tx_data: &RoochTransactionData,
+    ) -> Result<Self> {
+        SponsorAuthenticator::sign(sender_authenticator, sponsor_kp, tx_data).map(Into::into)
+    }
+
     /// Create a custom authenticator
     pub fn new(auth_validator_id: u64, payload: Vec<u8>) -> Self {
         Self {
@@ -634,6 +723,37 @@ mod tests {
         println!("[PASS] Bitcoin message digest verified");
     }
 
+    #[test]
+    fn test_sponsor_authenticator() {
+        let tx_data = create_test_tx_data();
+        let sender_kp = RoochKeyPair::generate_secp256k1();
+        let sponsor_kp = RoochKeyPair::generate_secp256k1();
+        let sender_authenticator = Authenticator::bitcoin(&sender_kp, &tx_data);
+
+        let authenticator =
+            Authenticator::sponsor(sender_authenticator.clone(), &sponsor_kp, &tx_data).unwrap();
+        assert_eq!(
+            authenticator.auth_validator_id,
+            BuiltinAuthValidator::Sponsor.flag() as u64
+        );
+        let sponsor_authenticator = SponsorAuthenticator::try_from_authenticator(&authenticator)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            sponsor_authenticator.sponsor(),
+            sponsor_kp.public().rooch_address().unwrap()
+        );
+        assert_eq!(
+            sponsor_authenticator.sender_authenticator(),
+            sender_authenticator
+        );
+        assert!(
+            SponsorAuthenticator::try_from_authenticator(&sender_authenticator)
+                .unwrap()
+                .is_none()
+        );
+    }
+
     // Helper function to create test transaction data
     fn create_test_tx_data() -> crate::transaction::RoochTransactionData {
         use crate::address::RoochAddress;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use accumulator::accumulator_info::AccumulatorInfo;
+use accumulator::proof::AccumulatorProof;
 use framework_types::addresses::ROOCH_FRAMEWORK_ADDRESS;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
@@ -14,6 +15,7 @@ use serde::{Deserialize, Serialize};
 pub mod authenticator;
 mod ledger_transaction;
 pub mod rooch;
+pub mod state_diff;
 
 use crate::test_utils::random_accumulator_info;
 pub use authenticator::Authenticator;
@@ -114,11 +116,56 @@ impl TransactionSequenceInfo {
         )
     }
 
+    /// The index of the tx in the tx accumulator.
+    /// It is not always equal to the tx_order, some networks have txs that occupy extra accumulator leaves.
+    pub fn tx_accumulator_leaf_index(&self) -> u64 {
+        self.tx_accumulator_num_leaves.saturating_sub(1)
+    }
+
     pub fn random() -> Self {
         TransactionSequenceInfo::new(rand::random(), random_bytes(), random_accumulator_info(), 0)
     }
 }
 
+/// The inclusion proof of a transaction in the tx accumulator.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionAccumulatorProof {
+    pub tx_order: u64,
+    pub tx_hash: H256,
+    /// The index of the tx in the tx accumulator
+    pub leaf_index: u64,
+    /// The tx order of the accumulator root the proof is generated against
+    pub accumulator_tx_order: u64,
+    /// The tx accumulator root after the tx of `accumulator_tx_order` is appended
+    pub accumulator_root: H256,
+    pub proof: AccumulatorProof,
+}
+
+impl TransactionAccumulatorProof {
+    /// Verify the tx is included in the accumulator with the `expected_root`.
+    /// The `expected_root` should come from a trusted source, such as a DA batch or a proposed block,
+    /// instead of the `accumulator_root` in the proof.
+    /// The `expected_leaf_index` should be derived from the `tx_order` by the caller,
+    /// so the proof also binds the `tx_order` to the tx.
+    pub fn verify(&self, expected_root: H256, expected_leaf_index: u64) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.accumulator_root == expected_root,
+            "Accumulator root mismatch, expect: {:?}, actual: {:?}",
+            expected_root,
+            self.accumulator_root
+        );
+        anyhow::ensure!(
+            self.leaf_index == expected_leaf_index,
+            "Accumulator leaf index mismatch for tx order {}, expect: {}, actual: {}",
+            self.tx_order,
+            expected_leaf_index,
+            self.leaf_index
+        );
+        self.proof
+            .verify(expected_root, self.tx_hash, expected_leaf_index)
+    }
+}
+
 impl MoveStructType for TransactionSequenceInfo {
     const ADDRESS: AccountAddress = ROOCH_FRAMEWORK_ADDRESS;
     const MODULE_NAME: &'static IdentStr = ident_str!("transaction");
@@ -139,6 +139,68 @@ impl PartiallySignedRoochTransaction {
         Ok(())
     }
 
+    /// Verify the authenticator is signed by one of the participants of the multisign account
+    /// and its signature matches the transaction data.
+    pub fn verify_authenticator(
+        &self,
+        authenticator: &BitcoinAuthenticator,
+        participant_public_keys: &[Vec<u8>],
+    ) -> Result<()> {
+        if !participant_public_keys.contains(&authenticator.payload.public_key) {
+            return Err(anyhow::anyhow!(
+                "Authenticator from address {:?} is not a participant of the multisign account",
+                authenticator.payload.from_address()
+            ));
+        }
+        authenticator.payload.verify(&self.data).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid signature from address {:?}, err: {:?}",
+                authenticator.payload.from_address(),
+                e
+            )
+        })
+    }
+
+    /// Verify all the authenticators of the PSRT, see `verify_authenticator`.
+    pub fn verify_authenticators(&self, participant_public_keys: &[Vec<u8>]) -> Result<()> {
+        for authenticator in self.authenticators.iter() {
+            self.verify_authenticator(authenticator, participant_public_keys)?;
+        }
+        Ok(())
+    }
+
+    /// Merge the authenticators of another PSRT of the same transaction data,
+    /// the authenticators already contained are skipped.
+    /// Every authenticator of the other PSRT is verified against the transaction data and
+    /// the participants of the multisign account, the PSRT is unchanged if any of them fails.
+    pub fn merge(
+        &mut self,
+        other: PartiallySignedRoochTransaction,
+        participant_public_keys: &[Vec<u8>],
+    ) -> Result<()> {
+        if self.data != other.data {
+            return Err(anyhow::anyhow!(
+                "Cannot merge partially signed transactions with different transaction data, tx hash: {:?} and {:?}",
+                self.data.tx_hash(),
+                other.data.tx_hash()
+            ));
+        }
+        if self.threshold != other.threshold {
+            return Err(anyhow::anyhow!(
+                "Cannot merge partially signed transactions with different threshold: {} and {}",
+                self.threshold,
+                other.threshold
+            ));
+        }
+        other.verify_authenticators(participant_public_keys)?;
+        for authenticator in other.authenticators {
+            if !self.contains_authenticator(&authenticator) {
+                self.authenticators.push(authenticator);
+            }
+        }
+        Ok(())
+    }
+
     pub fn threshold(&self) -> u64 {
         self.threshold
     }
@@ -307,3 +369,110 @@ impl Display for RoochTransaction {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_psrt(signers: &[&RoochKeyPair]) -> PartiallySignedRoochTransaction {
+        let mut psrt = PartiallySignedRoochTransaction::new(RoochTransaction::mock().data, 2);
+        for kp in signers {
+            psrt.add_authenticator(BitcoinAuthenticator::sign(kp, &psrt.data))
+                .unwrap();
+        }
+        psrt
+    }
+
+    fn public_keys(kps: &[&RoochKeyPair]) -> Vec<Vec<u8>> {
+        kps.iter().map(|kp| kp.public().as_ref().to_vec()).collect()
+    }
+
+    #[test]
+    fn test_merge_partially_signed_transaction() {
+        let kp1 = RoochKeyPair::generate_secp256k1();
+        let kp2 = RoochKeyPair::generate_secp256k1();
+        let participants = public_keys(&[&kp1, &kp2]);
+        let mut psrt = new_psrt(&[&kp1]);
+
+        // The signer already in the PSRT is skipped
+        let mut other = psrt.clone();
+        other.authenticators.clear();
+        other
+            .add_authenticator(BitcoinAuthenticator::sign(&kp1, &other.data))
+            .unwrap();
+        other
+            .add_authenticator(BitcoinAuthenticator::sign(&kp2, &other.data))
+            .unwrap();
+        psrt.merge(other.clone(), &participants).unwrap();
+        assert_eq!(psrt.signatories(), 2);
+        psrt.merge(other, &participants).unwrap();
+        assert_eq!(psrt.signatories(), 2);
+        psrt.verify_authenticators(&participants).unwrap();
+        assert!(psrt.is_fully_signed());
+    }
+
+    #[test]
+    fn test_merge_partially_signed_transaction_mismatch() {
+        let kp1 = RoochKeyPair::generate_secp256k1();
+        let kp2 = RoochKeyPair::generate_secp256k1();
+        let participants = public_keys(&[&kp1, &kp2]);
+        let mut psrt = new_psrt(&[&kp1]);
+
+        // The PSRT of another tx data
+        let mut other_tx = psrt.clone();
+        other_tx.authenticators.clear();
+        other_tx.data.sequence_number += 1;
+        other_tx
+            .add_authenticator(BitcoinAuthenticator::sign(&kp2, &other_tx.data))
+            .unwrap();
+        assert!(psrt.merge(other_tx, &participants).is_err());
+
+        // The PSRT of the same tx data with another threshold
+        let mut other_threshold = PartiallySignedRoochTransaction::new(psrt.data.clone(), 3);
+        other_threshold
+            .add_authenticator(BitcoinAuthenticator::sign(&kp2, &other_threshold.data))
+            .unwrap();
+        assert!(psrt.merge(other_threshold, &participants).is_err());
+
+        // The failed merges do not change the PSRT
+        assert_eq!(psrt.signatories(), 1);
+    }
+
+    #[test]
+    fn test_merge_partially_signed_transaction_invalid_authenticator() {
+        let kp1 = RoochKeyPair::generate_secp256k1();
+        let kp2 = RoochKeyPair::generate_secp256k1();
+        let outsider = RoochKeyPair::generate_secp256k1();
+        let participants = public_keys(&[&kp1, &kp2]);
+        let mut psrt = new_psrt(&[&kp1]);
+
+        // The signature of another tx data under the same tx data
+        let mut other_data = psrt.data.clone();
+        other_data.sequence_number += 1;
+        let mut forged = PartiallySignedRoochTransaction::new(psrt.data.clone(), 2);
+        forged
+            .add_authenticator(BitcoinAuthenticator::sign(&kp2, &other_data))
+            .unwrap();
+        assert!(psrt.merge(forged, &participants).is_err());
+
+        // The signature of a signer out of the participants
+        let mut non_participant = PartiallySignedRoochTransaction::new(psrt.data.clone(), 2);
+        non_participant
+            .add_authenticator(BitcoinAuthenticator::sign(&outsider, &non_participant.data))
+            .unwrap();
+        assert!(psrt.merge(non_participant, &participants).is_err());
+
+        // A valid authenticator does not get merged along with an invalid one
+        let mut mixed = PartiallySignedRoochTransaction::new(psrt.data.clone(), 2);
+        mixed
+            .add_authenticator(BitcoinAuthenticator::sign(&kp2, &mixed.data))
+            .unwrap();
+        mixed
+            .add_authenticator(BitcoinAuthenticator::sign(&outsider, &mixed.data))
+            .unwrap();
+        assert!(psrt.merge(mixed, &participants).is_err());
+
+        assert_eq!(psrt.signatories(), 1);
+        assert!(!psrt.is_fully_signed());
+    }
+}
@@ -0,0 +1,428 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::framework::coin_store::{CoinStore, CoinStoreInfo};
+use anyhow::Result;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::Op;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use move_core_types::u256::U256;
+use move_resource_viewer::{AnnotatedMoveStruct, MoveValueAnnotator};
+use moveos_types::moveos_std::event::TransactionEvent;
+use moveos_types::moveos_std::object::{is_dynamic_field_type, ObjectMeta};
+use moveos_types::state::{
+    MoveStructType, ObjectChange, ObjectState, PlaceholderStruct, StateChangeSet,
+};
+use moveos_types::state_resolver::MoveOSResolver;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectDiffType {
+    New,
+    Modified,
+    Removed,
+}
+
+/// The value change of an object made by a transaction
+#[derive(Debug, Clone)]
+pub struct ObjectDiff {
+    pub diff_type: ObjectDiffType,
+    /// The object metadata after the transaction, or before the transaction if it is removed
+    pub metadata: ObjectMeta,
+    /// The decoded value before the transaction, None if the object is new or the value can not
+    /// be decoded
+    pub before: Option<AnnotatedMoveStruct>,
+    /// The decoded value after the transaction, None if the object is removed or the value can
+    /// not be decoded, e.g. the type is published by the transaction itself
+    pub after: Option<AnnotatedMoveStruct>,
+    /// The BCS bytes of the value before the transaction, None if the object is new or the
+    /// state before the transaction is not read
+    pub before_value: Option<Vec<u8>>,
+    /// The BCS bytes of the value after the transaction, None if the object is removed
+    pub after_value: Option<Vec<u8>>,
+}
+
+/// The balance change of a coin type of an address, summed over its coin stores
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub owner: AccountAddress,
+    pub coin_type: StructTag,
+    pub before: U256,
+    pub after: U256,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnotatedTransactionEvent {
+    pub event: TransactionEvent,
+    /// None if the event data can not be decoded
+    pub decoded_event_data: Option<AnnotatedMoveStruct>,
+}
+
+/// The annotated state diff of a transaction output, against the state before the transaction.
+/// It is used to show what a dry run transaction will change before signing it.
+#[derive(Debug, Clone, Default)]
+pub struct StateDiff {
+    pub objects: Vec<ObjectDiff>,
+    pub balance_changes: Vec<BalanceChange>,
+    pub events: Vec<AnnotatedTransactionEvent>,
+}
+
+impl StateDiff {
+    /// Build the diff of the change set, the `resolver` should read the state before the
+    /// transaction. The objects with only the metadata or the fields changed, and the dynamic
+    /// field objects are skipped.
+    pub fn build<R: MoveOSResolver>(
+        resolver: &R,
+        changeset: &StateChangeSet,
+        events: &[TransactionEvent],
+    ) -> Result<Self> {
+        let annotator = MoveValueAnnotator::new(resolver);
+        let mut objects = vec![];
+        let mut balances = BTreeMap::new();
+        for object_change in changeset.changes.values() {
+            Self::collect_object_diff(
+                resolver,
+                &annotator,
+                object_change,
+                &mut objects,
+                &mut balances,
+            )?;
+        }
+
+        let balance_changes = balances
+            .into_iter()
+            .filter(|(_, (before, after))| before != after)
+            .map(|((owner, coin_type), (before, after))| BalanceChange {
+                owner,
+                coin_type,
+                before,
+                after,
+            })
+            .collect();
+        let events = events
+            .iter()
+            .map(|event| AnnotatedTransactionEvent {
+                event: event.clone(),
+                decoded_event_data: annotator
+                    .view_resource(&event.event_type, &event.event_data)
+                    .ok(),
+            })
+            .collect();
+        Ok(Self {
+            objects,
+            balance_changes,
+            events,
+        })
+    }
+
+    /// The diff of the change set without reading the state before the transaction and without
+    /// decoding the values, it is returned when the annotated diff can not be built.
+    pub fn raw(changeset: &StateChangeSet, events: &[TransactionEvent]) -> Self {
+        let mut objects = vec![];
+        for object_change in changeset.changes.values() {
+            Self::collect_raw_object_diff(object_change, &mut objects);
+        }
+        let events = events
+            .iter()
+            .map(|event| AnnotatedTransactionEvent {
+                event: event.clone(),
+                decoded_event_data: None,
+            })
+            .collect();
+        Self {
+            objects,
+            balance_changes: vec![],
+            events,
+        }
+    }
+
+    fn collect_raw_object_diff(object_change: &ObjectChange, objects: &mut Vec<ObjectDiff>) {
+        let metadata = &object_change.metadata;
+        if let (Some(value), false) = (
+            &object_change.value,
+            is_dynamic_field_type(&metadata.object_type),
+        ) {
+            let (diff_type, after_value) = match value {
+                Op::New(value) => (ObjectDiffType::New, Some(value.clone())),
+                Op::Modify(value) => (ObjectDiffType::Modified, Some(value.clone())),
+                Op::Delete => (ObjectDiffType::Removed, None),
+            };
+            objects.push(ObjectDiff {
+                diff_type,
+                metadata: metadata.clone(),
+                before: None,
+                after: None,
+                before_value: None,
+                after_value,
+            });
+        }
+        for field_change in object_change.fields.values() {
+            Self::collect_raw_object_diff(field_change, objects);
+        }
+    }
+
+    fn collect_object_diff<R: MoveOSResolver>(
+        resolver: &R,
+        annotator: &MoveValueAnnotator<R>,
+        object_change: &ObjectChange,
+        objects: &mut Vec<ObjectDiff>,
+        balances: &mut BTreeMap<(AccountAddress, StructTag), (U256, U256)>,
+    ) -> Result<()> {
+        let metadata = &object_change.metadata;
+        if let (Some(value), false) = (
+            &object_change.value,
+            is_dynamic_field_type(&metadata.object_type),
+        ) {
+            let before = resolver.get_object(&metadata.id)?;
+            let (diff_type, after) = match value {
+                Op::New(value) => (
+                    ObjectDiffType::New,
+                    Some(ObjectState::new(metadata.clone(), value.clone())),
+                ),
+                Op::Modify(value) => (
+                    ObjectDiffType::Modified,
+                    Some(ObjectState::new(metadata.clone(), value.clone())),
+                ),
+                Op::Delete => (ObjectDiffType::Removed, None),
+            };
+
+            Self::collect_balance(before.as_ref(), after.as_ref(), balances);
+            let decode = |state: ObjectState| {
+                state
+                    .into_annotated_state(annotator)
+                    .ok()
+                    .map(|state| state.decoded_value)
+            };
+            objects.push(ObjectDiff {
+                diff_type,
+                metadata: match (&after, &before) {
+                    (None, Some(before)) => before.metadata.clone(),
+                    _ => metadata.clone(),
+                },
+                before_value: before.as_ref().map(|state| state.value.clone()),
+                after_value: after.as_ref().map(|state| state.value.clone()),
+                before: before.and_then(decode),
+                after: after.and_then(decode),
+            });
+        }
+        for field_change in object_change.fields.values() {
+            Self::collect_object_diff(resolver, annotator, field_change, objects, balances)?;
+        }
+        Ok(())
+    }
+
+    fn collect_balance(
+        before: Option<&ObjectState>,
+        after: Option<&ObjectState>,
+        balances: &mut BTreeMap<(AccountAddress, StructTag), (U256, U256)>,
+    ) {
+        let coin_store = |state: Option<&ObjectState>| {
+            state.and_then(|state| match &state.metadata.object_type {
+                TypeTag::Struct(struct_tag)
+                    if CoinStore::<PlaceholderStruct>::struct_tag_match_without_type_param(
+                        struct_tag,
+                    ) =>
+                {
+                    let owner = state.metadata.owner;
+                    CoinStoreInfo::try_from(state.clone())
+                        .ok()
+                        .map(|info| (owner, info))
+                }
+                _ => None,
+            })
+        };
+        if let Some((owner, info)) = coin_store(before) {
+            let entry = balances
+                .entry((owner, info.coin_type()))
+                .or_insert((U256::zero(), U256::zero()));
+            entry.0 += info.balance();
+        }
+        if let Some((owner, info)) = coin_store(after) {
+            let entry = balances
+                .entry((owner, info.coin_type()))
+                .or_insert((U256::zero(), U256::zero()));
+            entry.1 += info.balance();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framework::coin_store::CoinStore;
+    use crate::framework::gas_coin::RGas;
+    use move_core_types::language_storage::ModuleId;
+    use move_core_types::metadata::Metadata;
+    use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+    use moveos_types::h256::H256;
+    use moveos_types::moveos_std::object::ObjectID;
+    use moveos_types::state::FieldKey;
+    use moveos_types::state_resolver::{StateKV, StateResolver, StatelessResolver};
+
+    /// The state before the transaction, without any module so no value can be decoded
+    struct MockResolver {
+        root: ObjectMeta,
+        objects: BTreeMap<FieldKey, ObjectState>,
+    }
+
+    impl MockResolver {
+        fn new(objects: Vec<ObjectState>) -> Self {
+            Self {
+                root: ObjectMeta::genesis_root(),
+                objects: objects
+                    .into_iter()
+                    .map(|object| (object.metadata.id.field_key(), object))
+                    .collect(),
+            }
+        }
+    }
+
+    impl StatelessResolver for MockResolver {
+        fn get_field_at(&self, _state_root: H256, key: &FieldKey) -> Result<Option<ObjectState>> {
+            Ok(self.objects.get(key).cloned())
+        }
+
+        fn list_fields_at(
+            &self,
+            _state_root: H256,
+            _cursor: Option<FieldKey>,
+            _limit: usize,
+        ) -> Result<Vec<StateKV>> {
+            Ok(vec![])
+        }
+    }
+
+    impl StateResolver for MockResolver {
+        fn root(&self) -> &ObjectMeta {
+            &self.root
+        }
+    }
+
+    impl ResourceResolver for MockResolver {
+        fn get_resource_with_metadata(
+            &self,
+            _address: &AccountAddress,
+            _resource_tag: &StructTag,
+            _metadata: &[Metadata],
+        ) -> Result<(Option<Vec<u8>>, usize)> {
+            Ok((None, 0))
+        }
+    }
+
+    impl ModuleResolver for MockResolver {
+        fn get_module_metadata(&self, _module_id: &ModuleId) -> Vec<Metadata> {
+            vec![]
+        }
+
+        fn get_module(&self, _module_id: &ModuleId) -> Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+    }
+
+    fn coin_store(id: ObjectID, owner: AccountAddress, balance: u64) -> ObjectState {
+        let mut metadata = ObjectMeta::genesis_meta(id, CoinStore::<RGas>::type_tag());
+        metadata.owner = owner;
+        ObjectState::new_with_struct(metadata, CoinStore::<RGas>::new(balance.into(), false))
+            .unwrap()
+    }
+
+    fn change(state: &ObjectState, op: Op<()>) -> ObjectChange {
+        let value = match op {
+            Op::New(()) => Op::New(state.value.clone()),
+            Op::Modify(()) => Op::Modify(state.value.clone()),
+            Op::Delete => Op::Delete,
+        };
+        ObjectChange::new(state.metadata.clone(), value)
+    }
+
+    #[test]
+    fn test_state_diff() {
+        let owner = AccountAddress::random();
+        let (new_id, modified_id, removed_id) =
+            (ObjectID::random(), ObjectID::random(), ObjectID::random());
+        let modified_before = coin_store(modified_id.clone(), owner, 100);
+        let removed_before = coin_store(removed_id.clone(), owner, 10);
+        let resolver = MockResolver::new(vec![modified_before.clone(), removed_before.clone()]);
+
+        let new_after = coin_store(new_id.clone(), owner, 5);
+        let modified_after = coin_store(modified_id.clone(), owner, 60);
+        let mut changeset = StateChangeSet::new(resolver.root.state_root(), resolver.root.size);
+        changeset
+            .add_change(change(&new_after, Op::New(())))
+            .unwrap();
+        changeset
+            .add_change(change(&modified_after, Op::Modify(())))
+            .unwrap();
+        changeset
+            .add_change(change(&removed_before, Op::Delete))
+            .unwrap();
+
+        let diff = StateDiff::build(&resolver, &changeset, &[]).unwrap();
+        assert_eq!(diff.objects.len(), 3);
+        let object_diff = |id: &ObjectID| {
+            diff.objects
+                .iter()
+                .find(|diff| &diff.metadata.id == id)
+                .unwrap()
+        };
+
+        let new = object_diff(&new_id);
+        assert_eq!(new.diff_type, ObjectDiffType::New);
+        assert_eq!(new.before_value, None);
+        assert_eq!(new.after_value, Some(new_after.value.clone()));
+
+        let modified = object_diff(&modified_id);
+        assert_eq!(modified.diff_type, ObjectDiffType::Modified);
+        assert_eq!(modified.before_value, Some(modified_before.value.clone()));
+        assert_eq!(modified.after_value, Some(modified_after.value.clone()));
+
+        let removed = object_diff(&removed_id);
+        assert_eq!(removed.diff_type, ObjectDiffType::Removed);
+        assert_eq!(removed.metadata, removed_before.metadata);
+        assert_eq!(removed.before_value, Some(removed_before.value.clone()));
+        assert_eq!(removed.after_value, None);
+
+        // The types can not be resolved, the raw values are returned without the decoded ones
+        assert!(diff
+            .objects
+            .iter()
+            .all(|diff| diff.before.is_none() && diff.after.is_none()));
+
+        // 100 + 10 before, 5 + 60 after
+        assert_eq!(
+            diff.balance_changes,
+            vec![BalanceChange {
+                owner,
+                coin_type: RGas::struct_tag(),
+                before: U256::from(110u64),
+                after: U256::from(65u64),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_raw_state_diff() {
+        let owner = AccountAddress::random();
+        let object = coin_store(ObjectID::random(), owner, 100);
+        let mut changeset = StateChangeSet::new(
+            ObjectMeta::genesis_root().state_root(),
+            ObjectMeta::genesis_root().size,
+        );
+        changeset
+            .add_change(change(&object, Op::Modify(())))
+            .unwrap();
+        // The metadata only change is not a value diff
+        changeset
+            .add_change(ObjectChange::meta(
+                coin_store(ObjectID::random(), owner, 1).metadata,
+            ))
+            .unwrap();
+
+        let diff = StateDiff::raw(&changeset, &[]);
+        assert_eq!(diff.objects.len(), 1);
+        assert_eq!(diff.objects[0].diff_type, ObjectDiffType::Modified);
+        assert_eq!(diff.objects[0].before_value, None);
+        assert_eq!(diff.objects[0].after_value, Some(object.value));
+        assert!(diff.balance_changes.is_empty());
+    }
+}
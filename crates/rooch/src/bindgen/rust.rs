@@ -0,0 +1,756 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use move_core_types::account_address::AccountAddress;
+use rooch_rpc_api::jsonrpc_types::{
+    ModuleABIView, MoveABIStructTag, MoveABIType, MoveFunctionView, MoveStructView,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+type ModuleKey = (AccountAddress, String);
+
+/// Generate the Rust bindings of the modules, every Move module becomes a Rust module with:
+///
+/// * a serde struct for every Move struct, with `MoveStructType` for the non-generic ones
+/// * a `ModuleBinding` with a `FunctionCall` builder for every function, a `MoveAction` builder
+///   for the entry functions, and return value decoders for the public functions, from the
+///   `FunctionResult` of the caller or the `AnnotatedFunctionResultView` of the RPC
+///
+/// The generated builders and decoders return `anyhow::Result` instead of panicking.
+///
+/// The struct types of the generated modules could reference each other. Other struct types are
+/// only mapped for the well known std types, function arguments and return values of the other
+/// types are passed as BCS bytes, and structs with fields of the other types are skipped.
+pub fn generate(modules: &[ModuleABIView]) -> Result<String> {
+    let structs = representable_structs(modules);
+    let mut out = String::new();
+    writeln!(out, "// Generated by `rooch move bindgen`, do not edit.")?;
+    for module in modules {
+        writeln!(out)?;
+        generate_module(&mut out, module, &structs)?;
+    }
+    Ok(out)
+}
+
+fn module_key(module: &ModuleABIView) -> ModuleKey {
+    (module.address.0, module.name.0.to_string())
+}
+
+fn rust_ident(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else if matches!(name, "self" | "Self" | "super" | "crate") {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn address_literal(address: &AccountAddress) -> String {
+    format!(
+        "::move_core_types::account_address::AccountAddress::new({:?})",
+        address.into_bytes()
+    )
+}
+
+// The structs which all the fields could be mapped to Rust types, found by removing the structs
+// referencing unmapped types until nothing changes.
+fn representable_structs(modules: &[ModuleABIView]) -> BTreeMap<ModuleKey, BTreeSet<String>> {
+    let mut structs = modules
+        .iter()
+        .map(|module| {
+            let names = module
+                .structs
+                .iter()
+                .filter(|s| !s.is_native)
+                .map(|s| s.name.0.to_string())
+                .collect::<BTreeSet<_>>();
+            (module_key(module), names)
+        })
+        .collect::<BTreeMap<_, _>>();
+    loop {
+        let mut removed = vec![];
+        for module in modules {
+            let key = module_key(module);
+            for s in &module.structs {
+                let name = s.name.0.to_string();
+                if structs[&key].contains(&name)
+                    && s.fields
+                        .iter()
+                        .any(|field| rust_type(&field.ty.0, &key, &structs, true).is_none())
+                {
+                    removed.push((key.clone(), name));
+                }
+            }
+        }
+        if removed.is_empty() {
+            return structs;
+        }
+        for (key, name) in removed {
+            if let Some(names) = structs.get_mut(&key) {
+                names.remove(&name);
+            }
+        }
+    }
+}
+
+/// Map the Move type to a Rust type, `None` if the type could not be mapped.
+/// The generic type params are mapped to `T{index}` in a struct and not mapped in a function.
+fn rust_type(
+    ty: &MoveABIType,
+    module: &ModuleKey,
+    structs: &BTreeMap<ModuleKey, BTreeSet<String>>,
+    in_struct: bool,
+) -> Option<String> {
+    Some(match ty {
+        MoveABIType::Bool => "bool".to_string(),
+        MoveABIType::U8 => "u8".to_string(),
+        MoveABIType::U16 => "u16".to_string(),
+        MoveABIType::U32 => "u32".to_string(),
+        MoveABIType::U64 => "u64".to_string(),
+        MoveABIType::U128 => "u128".to_string(),
+        MoveABIType::U256 => "::move_core_types::u256::U256".to_string(),
+        MoveABIType::Address => "::move_core_types::account_address::AccountAddress".to_string(),
+        MoveABIType::Signer => return None,
+        MoveABIType::Vector(inner) => {
+            format!("Vec<{}>", rust_type(inner, module, structs, in_struct)?)
+        }
+        MoveABIType::Reference { to, .. } => rust_type(to, module, structs, in_struct)?,
+        MoveABIType::GenericTypeParam { index } if in_struct => format!("T{}", index),
+        MoveABIType::GenericTypeParam { .. } => return None,
+        MoveABIType::Struct(tag) => rust_struct_type(tag, module, structs, in_struct)?,
+    })
+}
+
+fn rust_struct_type(
+    tag: &MoveABIStructTag,
+    module: &ModuleKey,
+    structs: &BTreeMap<ModuleKey, BTreeSet<String>>,
+    in_struct: bool,
+) -> Option<String> {
+    let module_name = tag.module.as_str();
+    let struct_name = tag.name.as_str();
+    if tag.address == AccountAddress::ONE {
+        match (module_name, struct_name) {
+            ("string", "String") => {
+                return Some("::moveos_types::move_std::string::MoveString".to_string())
+            }
+            ("ascii", "String") => {
+                return Some("::moveos_types::move_std::ascii::MoveAsciiString".to_string())
+            }
+            ("option", "Option") => {
+                let inner = rust_type(tag.type_params.first()?, module, structs, in_struct)?;
+                return Some(format!(
+                    "::moveos_types::move_std::option::MoveOption<{}>",
+                    inner
+                ));
+            }
+            _ => {}
+        }
+    }
+    // `Object<T>` has the same BCS layout as its `ObjectID`
+    if tag.address == AccountAddress::TWO
+        && module_name == "object"
+        && (struct_name == "ObjectID" || struct_name == "Object")
+    {
+        return Some("::moveos_types::moveos_std::object::ObjectID".to_string());
+    }
+
+    let key = (tag.address, module_name.to_string());
+    if !structs
+        .get(&key)
+        .map(|names| names.contains(struct_name))
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let path = if &key == module {
+        struct_name.to_string()
+    } else {
+        format!("super::{}::{}", rust_ident(module_name), struct_name)
+    };
+    if tag.type_params.is_empty() {
+        Some(path)
+    } else {
+        let type_params = tag
+            .type_params
+            .iter()
+            .map(|ty| rust_type(ty, module, structs, in_struct))
+            .collect::<Option<Vec<_>>>()?;
+        Some(format!("{}<{}>", path, type_params.join(", ")))
+    }
+}
+
+fn generate_module(
+    out: &mut String,
+    module: &ModuleABIView,
+    structs: &BTreeMap<ModuleKey, BTreeSet<String>>,
+) -> Result<()> {
+    let key = module_key(module);
+    let module_name = module.name.0.as_str();
+    writeln!(
+        out,
+        "/// Bindings of the Move module `{}::{}`",
+        module.address.0.to_hex_literal(),
+        module_name
+    )?;
+    writeln!(out, "#[allow(dead_code, clippy::all)]")?;
+    writeln!(out, "pub mod {} {{", rust_ident(module_name))?;
+    writeln!(
+        out,
+        "    pub const MODULE_NAME: &::move_core_types::identifier::IdentStr = ::move_core_types::ident_str!(\"{}\");",
+        module_name
+    )?;
+    writeln!(
+        out,
+        "    pub const MODULE_ADDRESS: ::move_core_types::account_address::AccountAddress = {};",
+        address_literal(&module.address.0)
+    )?;
+
+    for s in &module.structs {
+        writeln!(out)?;
+        if structs[&key].contains(s.name.0.as_str()) {
+            generate_struct(out, s, &key, structs)?;
+        } else {
+            writeln!(
+                out,
+                "    // Skipped struct `{}`, it is native or has fields of unsupported types",
+                s.name.0
+            )?;
+        }
+    }
+
+    let binding_name = format!("{}Module", camel_case(module_name));
+    writeln!(out)?;
+    writeln!(
+        out,
+        "    fn function_id(function_name: &::move_core_types::identifier::IdentStr) -> ::moveos_types::move_types::FunctionId {{"
+    )?;
+    writeln!(
+        out,
+        "        ::moveos_types::move_types::FunctionId::new(::move_core_types::language_storage::ModuleId::new(MODULE_ADDRESS, MODULE_NAME.to_owned()), function_name.to_owned())"
+    )?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    pub struct {}<'a> {{", binding_name)?;
+    writeln!(
+        out,
+        "        pub caller: &'a dyn ::moveos_types::module_binding::MoveFunctionCaller,"
+    )?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "    impl<'a> ::moveos_types::module_binding::ModuleBinding<'a> for {}<'a> {{",
+        binding_name
+    )?;
+    writeln!(
+        out,
+        "        const MODULE_NAME: &'static ::move_core_types::identifier::IdentStr = MODULE_NAME;"
+    )?;
+    writeln!(
+        out,
+        "        const MODULE_ADDRESS: ::move_core_types::account_address::AccountAddress = MODULE_ADDRESS;"
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "        fn new(caller: &'a impl ::moveos_types::module_binding::MoveFunctionCaller) -> Self {{"
+    )?;
+    writeln!(out, "            Self {{ caller }}")?;
+    writeln!(out, "        }}")?;
+    writeln!(out, "    }}")?;
+    writeln!(out)?;
+    writeln!(out, "    impl<'a> {}<'a> {{", binding_name)?;
+    for (i, function) in module.functions.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        generate_function(out, module, function, &key, structs)?;
+    }
+    writeln!(out, "    }}")?;
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+fn generate_struct(
+    out: &mut String,
+    s: &MoveStructView,
+    module: &ModuleKey,
+    structs: &BTreeMap<ModuleKey, BTreeSet<String>>,
+) -> Result<()> {
+    let name = s.name.0.as_str();
+    let type_params = (0..s.type_params.len())
+        .map(|i| format!("T{}", i))
+        .collect::<Vec<_>>();
+    let mut fields = vec![];
+    for field in &s.fields {
+        let ty = rust_type(&field.ty.0, module, structs, true)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported field type of struct {}", name))?;
+        fields.push((rust_ident(field.name.0.as_str()), ty));
+    }
+    // The type params not used by the fields, such as the phantom type params
+    let unused_type_params = type_params
+        .iter()
+        .filter(|param| {
+            !fields.iter().any(|(_, ty)| {
+                ty.split(|c: char| !c.is_alphanumeric())
+                    .any(|t| &t == param)
+            })
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    writeln!(out, "    /// Move struct `{}`", name)?;
+    writeln!(
+        out,
+        "    #[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]"
+    )?;
+    if type_params.is_empty() {
+        writeln!(out, "    pub struct {} {{", name)?;
+    } else {
+        writeln!(
+            out,
+            "    pub struct {}<{}> {{",
+            name,
+            type_params.join(", ")
+        )?;
+    }
+    for (field_name, ty) in &fields {
+        writeln!(out, "        pub {}: {},", field_name, ty)?;
+    }
+    if !unused_type_params.is_empty() {
+        writeln!(out, "        #[serde(skip)]")?;
+        writeln!(
+            out,
+            "        pub _phantom: ::std::marker::PhantomData<({},)>,",
+            unused_type_params.join(", ")
+        )?;
+    }
+    writeln!(out, "    }}")?;
+
+    if type_params.is_empty() {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "    impl ::moveos_types::state::MoveStructType for {} {{",
+            name
+        )?;
+        writeln!(
+            out,
+            "        const ADDRESS: ::move_core_types::account_address::AccountAddress = MODULE_ADDRESS;"
+        )?;
+        writeln!(
+            out,
+            "        const MODULE_NAME: &'static ::move_core_types::identifier::IdentStr = MODULE_NAME;"
+        )?;
+        writeln!(
+            out,
+            "        const STRUCT_NAME: &'static ::move_core_types::identifier::IdentStr = ::move_core_types::ident_str!(\"{}\");",
+            name
+        )?;
+        writeln!(out, "    }}")?;
+    }
+    Ok(())
+}
+
+// A function param or return value, `ty` is `None` if it is passed as BCS bytes
+struct ValueType {
+    ty: Option<String>,
+    move_ty: String,
+}
+
+impl ValueType {
+    fn rust_type(&self) -> &str {
+        self.ty.as_deref().unwrap_or("Vec<u8>")
+    }
+}
+
+fn generate_function(
+    out: &mut String,
+    module: &ModuleABIView,
+    function: &MoveFunctionView,
+    key: &ModuleKey,
+    structs: &BTreeMap<ModuleKey, BTreeSet<String>>,
+) -> Result<()> {
+    let move_name = function.name.0.as_str();
+    let function_id = format!(
+        "{}::{}::{}",
+        module.address.0.to_hex_literal(),
+        module.name.0,
+        move_name
+    );
+    let value_type = |ty: &MoveABIType| ValueType {
+        ty: rust_type(ty, key, structs, false),
+        move_ty: ty.to_string(),
+    };
+    // The signer params are filled by the VM
+    let params = function
+        .params
+        .iter()
+        .filter(|ty| match &ty.0 {
+            MoveABIType::Signer => false,
+            MoveABIType::Reference { to, .. } => to.as_ref() != &MoveABIType::Signer,
+            _ => true,
+        })
+        .map(|ty| value_type(&ty.0))
+        .collect::<Vec<_>>();
+    let returns = function
+        .return_
+        .iter()
+        .map(|ty| value_type(&ty.0))
+        .collect::<Vec<_>>();
+
+    let mut param_decls = vec![];
+    let mut param_names = vec![];
+    if !function.type_params.is_empty() {
+        param_decls.push("ty_args: Vec<::move_core_types::language_storage::TypeTag>".to_string());
+        param_names.push("ty_args".to_string());
+    }
+    for (i, param) in params.iter().enumerate() {
+        param_decls.push(format!("arg{}: {}", i, param.rust_type()));
+        param_names.push(format!("arg{}", i));
+    }
+    let param_decls = param_decls.join(", ");
+    let param_names = param_names.join(", ");
+    let args = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| match &param.ty {
+            Some(_) => format!("::bcs::to_bytes(&arg{})?", i),
+            None => format!("arg{}", i),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let ty_args = if function.type_params.is_empty() {
+        "vec![]"
+    } else {
+        "ty_args"
+    };
+    let fn_ident = rust_ident(move_name);
+
+    writeln!(out, "        /// Build the call of `{}`", function_id)?;
+    for (i, param) in params.iter().enumerate() {
+        if param.ty.is_none() {
+            writeln!(
+                out,
+                "        /// `arg{}` is the BCS bytes of `{}`",
+                i, param.move_ty
+            )?;
+        }
+    }
+    writeln!(
+        out,
+        "        pub fn {}_call({}) -> ::anyhow::Result<::moveos_types::transaction::FunctionCall> {{",
+        move_name, param_decls
+    )?;
+    writeln!(
+        out,
+        "            Ok(::moveos_types::transaction::FunctionCall::new(function_id(::move_core_types::ident_str!(\"{}\")), {}, vec![{}]))",
+        move_name, ty_args, args
+    )?;
+    writeln!(out, "        }}")?;
+
+    if function.is_entry {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "        /// Build the transaction action of the entry function `{}`",
+            function_id
+        )?;
+        writeln!(
+            out,
+            "        pub fn {}_action({}) -> ::anyhow::Result<::moveos_types::transaction::MoveAction> {{",
+            move_name, param_decls
+        )?;
+        writeln!(
+            out,
+            "            Ok(::moveos_types::transaction::MoveAction::Function(Self::{}_call({})?))",
+            move_name, param_names
+        )?;
+        writeln!(out, "        }}")?;
+        return Ok(());
+    }
+
+    let return_type = match returns.len() {
+        0 => "()".to_string(),
+        1 => returns[0].rust_type().to_string(),
+        _ => format!(
+            "({})",
+            returns
+                .iter()
+                .map(|ret| ret.rust_type())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let decoded_values = returns
+        .iter()
+        .enumerate()
+        .map(|(i, ret)| match &ret.ty {
+            Some(_) => format!("::bcs::from_bytes(&values[{}].value)?", i),
+            None => format!("values[{}].value.clone()", i),
+        })
+        .collect::<Vec<_>>();
+    let decoded = match decoded_values.len() {
+        0 => "()".to_string(),
+        1 => decoded_values[0].clone(),
+        _ => format!("({})", decoded_values.join(", ")),
+    };
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "        /// Decode the return values of `{}`, from `execute_view_function` or the caller",
+        function_id
+    )?;
+    writeln!(
+        out,
+        "        pub fn decode_{}_result(result: ::moveos_types::function_return_value::FunctionResult) -> ::anyhow::Result<{}> {{",
+        move_name, return_type
+    )?;
+    writeln!(
+        out,
+        "            let values = result.into_result().map_err(|status| ::anyhow::anyhow!(\"Execute function {} failed: {{:?}}\", status))?;",
+        function_id
+    )?;
+    writeln!(
+        out,
+        "            ::anyhow::ensure!(values.len() == {}, \"Expect {} return values, got {{}}\", values.len());",
+        returns.len(),
+        returns.len()
+    )?;
+    writeln!(out, "            Ok({})", decoded)?;
+    writeln!(out, "        }}")?;
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "        /// Decode the return values of `{}`, from the `executeViewFunction` RPC",
+        function_id
+    )?;
+    writeln!(
+        out,
+        "        pub fn decode_{}_view_result(result: ::rooch_rpc_api::jsonrpc_types::AnnotatedFunctionResultView) -> ::anyhow::Result<{}> {{",
+        move_name, return_type
+    )?;
+    writeln!(
+        out,
+        "            Self::decode_{}_result(::moveos_types::function_return_value::FunctionResult::try_from(result)?)",
+        move_name
+    )?;
+    writeln!(out, "        }}")?;
+
+    writeln!(out)?;
+    writeln!(out, "        /// Call `{}` by the caller", function_id)?;
+    let method_params = if param_decls.is_empty() {
+        String::new()
+    } else {
+        format!(", {}", param_decls)
+    };
+    writeln!(
+        out,
+        "        pub fn {}(&self, ctx: &::moveos_types::moveos_std::tx_context::TxContext{}) -> ::anyhow::Result<{}> {{",
+        fn_ident, method_params, return_type
+    )?;
+    writeln!(
+        out,
+        "            Self::decode_{}_result(self.caller.call_function(ctx, Self::{}_call({})?)?)",
+        move_name, move_name, param_names
+    )?;
+    writeln!(out, "        }}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::identifier::Identifier;
+    use rooch_rpc_api::jsonrpc_types::{MoveFunctionTypeParamView, MoveStructFieldView, StrView};
+
+    fn ident(name: &str) -> StrView<Identifier> {
+        StrView(Identifier::new(name).unwrap())
+    }
+
+    fn struct_type(address: AccountAddress, module: &str, name: &str) -> MoveABIType {
+        MoveABIType::Struct(Box::new(MoveABIStructTag::new(
+            address,
+            Identifier::new(module).unwrap(),
+            Identifier::new(name).unwrap(),
+            vec![],
+        )))
+    }
+
+    fn counter_module() -> ModuleABIView {
+        let address = AccountAddress::from_hex_literal("0x42").unwrap();
+        ModuleABIView {
+            address: StrView(address),
+            name: ident("counter"),
+            friends: vec![],
+            functions: vec![
+                MoveFunctionView {
+                    name: ident("increase"),
+                    is_entry: true,
+                    type_params: vec![],
+                    params: vec![
+                        StrView(MoveABIType::Reference {
+                            mutable: false,
+                            to: Box::new(MoveABIType::Signer),
+                        }),
+                        StrView(MoveABIType::U64),
+                    ],
+                    return_: vec![],
+                },
+                MoveFunctionView {
+                    name: ident("value"),
+                    is_entry: false,
+                    type_params: vec![MoveFunctionTypeParamView {
+                        constraints: vec![],
+                    }],
+                    params: vec![
+                        StrView(struct_type(AccountAddress::TWO, "object", "ObjectID")),
+                        StrView(MoveABIType::GenericTypeParam { index: 0 }),
+                    ],
+                    return_: vec![
+                        StrView(struct_type(address, "counter", "Counter")),
+                        StrView(struct_type(address, "counter", "Other")),
+                    ],
+                },
+            ],
+            structs: vec![
+                MoveStructView {
+                    name: ident("Counter"),
+                    is_native: false,
+                    abilities: vec![],
+                    type_params: vec![],
+                    fields: vec![
+                        MoveStructFieldView {
+                            name: ident("value"),
+                            ty: StrView(MoveABIType::U64),
+                        },
+                        MoveStructFieldView {
+                            name: ident("name"),
+                            ty: StrView(struct_type(AccountAddress::ONE, "string", "String")),
+                        },
+                    ],
+                },
+                MoveStructView {
+                    name: ident("Other"),
+                    is_native: false,
+                    abilities: vec![],
+                    type_params: vec![],
+                    fields: vec![MoveStructFieldView {
+                        name: ident("type"),
+                        ty: StrView(struct_type(address, "unknown", "Unknown")),
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_generate_rust_bindings() {
+        let code = generate(&[counter_module()]).unwrap();
+        assert!(code.contains("pub mod counter {"));
+        assert!(code.contains("pub struct Counter {"));
+        assert!(code.contains("pub name: ::moveos_types::move_std::string::MoveString,"));
+        assert!(code.contains("Skipped struct `Other`"));
+        assert!(code.contains("pub fn increase_call(arg0: u64)"));
+        assert!(code.contains("pub fn increase_action(arg0: u64)"));
+        assert!(code.contains(
+            "pub fn value_call(ty_args: Vec<::move_core_types::language_storage::TypeTag>, arg0: ::moveos_types::moveos_std::object::ObjectID, arg1: Vec<u8>)"
+        ));
+        assert!(code.contains("::anyhow::Result<(Counter, Vec<u8>)>"));
+        assert!(code.contains("pub struct CounterModule<'a>"));
+    }
+
+    // The golden file is compiled by `generated` below, update it when the generated code changes
+    #[test]
+    fn test_generate_golden_bindings() {
+        let code = generate(&[counter_module()]).unwrap();
+        assert_eq!(code, include_str!("testdata/counter.rs.golden"));
+    }
+
+    mod generated {
+        include!("testdata/counter.rs.golden");
+    }
+
+    #[test]
+    fn test_generated_bindings() {
+        use generated::counter::{Counter, CounterModule};
+        use move_core_types::vm_status::StatusCode;
+        use moveos_types::moveos_std::object::ObjectID;
+        use moveos_types::transaction::MoveAction;
+        use rooch_rpc_api::jsonrpc_types::{
+            AnnotatedFunctionResultView, AnnotatedFunctionReturnValueView, FunctionReturnValueView,
+            VMStatusView,
+        };
+
+        let action = CounterModule::increase_action(7).unwrap();
+        let MoveAction::Function(call) = action else {
+            panic!("Expect a function call action");
+        };
+        assert_eq!(call.function_id.function_name.as_str(), "increase");
+        assert_eq!(call.args, vec![bcs::to_bytes(&7u64).unwrap()]);
+
+        let object_id = ObjectID::random();
+        let call = CounterModule::value_call(vec![], object_id.clone(), vec![1]).unwrap();
+        assert_eq!(call.args, vec![bcs::to_bytes(&object_id).unwrap(), vec![1]]);
+
+        let counter = Counter {
+            value: 42,
+            name: "counter".into(),
+        };
+        let return_value = |type_tag: &str, value: Vec<u8>| AnnotatedFunctionReturnValueView {
+            value: FunctionReturnValueView {
+                type_tag: type_tag.parse().unwrap(),
+                value: value.into(),
+            },
+            decoded_value: serde_json::Value::Null,
+        };
+        let result = AnnotatedFunctionResultView {
+            vm_status: VMStatusView::Executed,
+            return_values: Some(vec![
+                return_value("0x42::counter::Counter", bcs::to_bytes(&counter).unwrap()),
+                return_value("0x42::counter::Other", vec![2]),
+            ]),
+        };
+        let (decoded, other) = CounterModule::decode_value_view_result(result.clone()).unwrap();
+        assert_eq!(decoded, counter);
+        assert_eq!(other, vec![2]);
+
+        let failed = AnnotatedFunctionResultView {
+            vm_status: VMStatusView::Error((StatusCode::ABORTED as u64).into()),
+            return_values: None,
+        };
+        assert!(CounterModule::decode_value_view_result(failed).is_err());
+        let missing = AnnotatedFunctionResultView {
+            return_values: Some(vec![]),
+            ..result
+        };
+        assert!(CounterModule::decode_value_view_result(missing).is_err());
+    }
+}
@@ -7,6 +7,7 @@ pub mod create_multisign;
 pub mod export;
 pub mod import;
 pub mod list;
+pub mod multisign;
 pub mod nullify;
 pub mod object;
 pub mod show;
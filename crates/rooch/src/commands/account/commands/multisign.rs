@@ -0,0 +1,156 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, FileOrHexInput, WalletContextOptions};
+use crate::commands::transaction::commands::sign::SignInput;
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use moveos_types::h256::H256;
+use moveos_types::module_binding::MoveFunctionCaller;
+use rooch_types::{
+    address::RoochAddress,
+    bitcoin::multisign_account::MultisignAccountModule,
+    error::{RoochError, RoochResult},
+};
+use serde::{Deserialize, Serialize};
+
+/// Tools for the multisign account transactions
+#[derive(Debug, Parser)]
+pub struct MultisignCommand {
+    #[clap(subcommand)]
+    cmd: MultisignSubCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MultisignSubCommand {
+    Status(StatusCommand),
+}
+
+impl MultisignCommand {
+    pub async fn execute_serialized(self) -> RoochResult<String> {
+        match self.cmd {
+            MultisignSubCommand::Status(status) => status.execute_serialized().await,
+        }
+    }
+}
+
+/// Show the signing status of a multisign account transaction, which participants have signed
+#[derive(Debug, Parser)]
+pub struct StatusCommand {
+    /// The transaction data or partially signed transaction hex or file path
+    input: FileOrHexInput,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+
+    /// Return command outputs in json format
+    #[clap(long, default_value = "false")]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipantStatusView {
+    pub participant_address: RoochAddress,
+    pub participant_bitcoin_address: String,
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisignStatusOutput {
+    pub multisign_address: RoochAddress,
+    pub tx_hash: H256,
+    pub threshold: u64,
+    pub signatories: u64,
+    pub fully_signed: bool,
+    pub participants: Vec<ParticipantStatusView>,
+}
+
+#[async_trait]
+impl CommandAction<Option<MultisignStatusOutput>> for StatusCommand {
+    async fn execute(self) -> RoochResult<Option<MultisignStatusOutput>> {
+        let context = self.context_options.build()?;
+        let bitcoin_network = context.get_bitcoin_network().await?;
+        let client = context.get_client().await?;
+        let multisign_account_module = client.as_module_binding::<MultisignAccountModule>();
+
+        let sign_input = SignInput::try_from(self.input)?;
+        let multisign_address = sign_input.sender();
+        if !multisign_account_module.is_multisign_account(multisign_address.into())? {
+            return Err(RoochError::CommandArgumentError(format!(
+                "The transaction sender {} is not a multisign account",
+                multisign_address
+            )));
+        }
+        let threshold = multisign_account_module.threshold(multisign_address.into())?;
+        let (tx_data, authenticators) = match sign_input {
+            SignInput::RoochTransactionData(tx_data) => (tx_data, vec![]),
+            SignInput::PartiallySignedRoochTransaction(psrt) => (psrt.data, psrt.authenticators),
+        };
+        let tx_hash = tx_data.tx_hash();
+        // Only count the signatures of the transaction, a PSRT may be tampered or combined from
+        // the signatures of another transaction
+        for authenticator in authenticators.iter() {
+            authenticator.payload.verify(&tx_data).map_err(|e| {
+                RoochError::CommandArgumentError(format!(
+                    "Invalid signature of {} for the transaction {:?}: {}",
+                    authenticator
+                        .payload
+                        .from_address()
+                        .unwrap_or_else(|_| hex::encode(&authenticator.payload.public_key)),
+                    tx_hash,
+                    e
+                ))
+            })?;
+        }
+
+        let mut participants = vec![];
+        for p in multisign_account_module.participants(multisign_address.into())? {
+            let signed = authenticators
+                .iter()
+                .any(|authenticator| authenticator.payload.public_key == p.public_key);
+            participants.push(ParticipantStatusView {
+                participant_address: p.participant_address.into(),
+                participant_bitcoin_address: p
+                    .participant_bitcoin_address
+                    .format(bitcoin_network)?,
+                signed,
+            });
+        }
+        let signatories = participants.iter().filter(|p| p.signed).count() as u64;
+
+        let output = MultisignStatusOutput {
+            multisign_address,
+            tx_hash,
+            threshold,
+            signatories,
+            fully_signed: signatories >= threshold,
+            participants,
+        };
+        if self.json {
+            Ok(Some(output))
+        } else {
+            println!("Multisign Address: {}", output.multisign_address);
+            println!("Transaction hash: {:?}", output.tx_hash);
+            println!(
+                "Collected signatures: {}/{}",
+                output.signatories, output.threshold
+            );
+            for participant in output.participants.iter() {
+                println!(
+                    " {} ({}): {}",
+                    participant.participant_address,
+                    participant.participant_bitcoin_address,
+                    if participant.signed {
+                        "signed"
+                    } else {
+                        "not signed"
+                    }
+                );
+            }
+            if output.fully_signed {
+                println!("The transaction is fully signed and ready to submit");
+            }
+            Ok(None)
+        }
+    }
+}
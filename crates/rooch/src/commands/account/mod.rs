@@ -5,9 +5,9 @@ use crate::cli_types::CommandAction;
 use async_trait::async_trait;
 use commands::{
     balance::BalanceCommand, create::CreateCommand, create_multisign::CreateMultisignCommand,
-    export::ExportCommand, import::ImportCommand, list::ListCommand, nullify::NullifyCommand,
-    object::ObjectCommand, show::ShowCommand, sign::SignCommand, switch::SwitchCommand,
-    transfer::TransferCommand, tx::TxCommand, verify::VerifyCommand,
+    export::ExportCommand, import::ImportCommand, list::ListCommand, multisign::MultisignCommand,
+    nullify::NullifyCommand, object::ObjectCommand, show::ShowCommand, sign::SignCommand,
+    switch::SwitchCommand, transfer::TransferCommand, tx::TxCommand, verify::VerifyCommand,
 };
 use rooch_rpc_api::jsonrpc_types::json_to_table_display::json_to_table;
 use rooch_types::error::RoochResult;
@@ -56,6 +56,7 @@ impl CommandAction<String> for Account {
             AccountCommand::Tx(tx) => tx.execute_serialized().await,
             AccountCommand::Object(object) => object.execute_serialized().await,
             AccountCommand::Show(show) => show.execute_serialized().await,
+            AccountCommand::Multisign(multisign) => multisign.execute_serialized().await,
         }
     }
 }
@@ -77,4 +78,5 @@ pub enum AccountCommand {
     Tx(TxCommand),
     Object(ObjectCommand),
     Show(ShowCommand),
+    Multisign(MultisignCommand),
 }
@@ -1,8 +1,10 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use super::coin_selection::{ChangePolicy, CoinSelectionStrategy};
 use super::transaction_builder::TransactionBuilder;
-use super::FileOutput;
+use super::utxo_selector::UTXOSelector;
+use super::{resolve_fee_rate, FileOutput};
 use crate::cli_types::{CommandAction, WalletContextOptions};
 use crate::commands::bitcoin::FileOutputData;
 use async_trait::async_trait;
@@ -96,6 +98,33 @@ pub struct FileInfo {
     pub output_type: String,
 }
 
+/// The options applied to the TransactionBuilders of build-tx
+#[derive(Debug, Clone)]
+struct BuilderOptions {
+    fee_rate: FeeRate,
+    lock_time: Option<LockTime>,
+    change_address: Option<Address>,
+    coin_selection: CoinSelectionStrategy,
+    change_policy: ChangePolicy,
+    protect_inscriptions: bool,
+}
+
+impl BuilderOptions {
+    fn apply<'a>(&self, tx_builder: TransactionBuilder<'a>) -> TransactionBuilder<'a> {
+        let mut tx_builder = tx_builder
+            .with_fee_rate(self.fee_rate)
+            .with_coin_selection(self.coin_selection)
+            .with_change_policy(self.change_policy);
+        if let Some(lock_time) = self.lock_time {
+            tx_builder = tx_builder.with_lock_time(lock_time);
+        }
+        if let Some(change_address) = &self.change_address {
+            tx_builder = tx_builder.with_change_address(change_address.clone());
+        }
+        tx_builder
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct BuildTx {
     #[clap(long, short = 's', value_parser=ParsedAddress::parse, default_value = "default")]
@@ -125,6 +154,23 @@ pub struct BuildTx {
     #[clap(long)]
     max_inputs: Option<usize>,
 
+    /// The strategy to select the UTXOs when the inputs are not specified
+    #[clap(long, value_enum, default_value_t = CoinSelectionStrategy::OldestFirst)]
+    coin_selection: CoinSelectionStrategy,
+
+    /// Never spend the UTXOs which carry inscriptions
+    #[clap(long)]
+    protect_inscriptions: bool,
+
+    /// The min value of the change output in satoshi, the change below it is added to the fee.
+    /// The dust limit of the change address is always respected.
+    #[clap(long)]
+    min_change: Option<u64>,
+
+    /// The number of the recent blocks to estimate the fee rate when the fee rate is not specified
+    #[clap(long)]
+    fee_estimate_blocks: Option<u64>,
+
     #[clap(flatten)]
     pub(crate) context_options: WalletContextOptions,
 }
@@ -142,12 +188,26 @@ impl CommandAction<String> for BuildTx {
         let max_inputs = self.max_inputs.unwrap_or(usize::MAX);
         let send_all = self.outputs.iter().any(|o| o.amount == OutputAmount::All);
 
-        // The CLI parser interprets the numeric argument as sat/kwu (sat per 1000 weight units),
-        // but users expect sat/vbyte. Re-wrap with the same numeric value as sat/vbyte.
-        let fee_rate = self.fee_rate.map(|fr| {
-            let numeric = fr.to_sat_per_kwu() as u64;
-            FeeRate::from_sat_per_vb(numeric).unwrap()
-        });
+        let fee_rate = resolve_fee_rate(&client, self.fee_rate, self.fee_estimate_blocks).await?;
+        let change_address = match self.change_address {
+            Some(change_address) => Some(
+                context
+                    .resolve_bitcoin_address(change_address)
+                    .await?
+                    .to_bitcoin_address(bitcoin_network)?,
+            ),
+            None => None,
+        };
+        let options = BuilderOptions {
+            fee_rate,
+            lock_time: self.lock_time,
+            change_address,
+            coin_selection: self.coin_selection,
+            change_policy: ChangePolicy {
+                min_change: self.min_change.map(Amount::from_sat),
+            },
+            protect_inscriptions: self.protect_inscriptions,
+        };
 
         // Validate :all usage - only one output can use :all
         if send_all {
@@ -194,9 +254,7 @@ impl CommandAction<String> for BuildTx {
                 bitcoin_network,
                 inputs,
                 self.skip_check_seal,
-                fee_rate,
-                self.lock_time,
-                self.change_address,
+                &options,
                 self.outputs,
                 self.output_file,
             )
@@ -206,13 +264,12 @@ impl CommandAction<String> for BuildTx {
         // Case 2: Auto-loading UTXOs (no manual inputs)
         if send_all || self.max_inputs.is_some() {
             // Load all UTXOs first
-            let mut temp_selector = crate::commands::bitcoin::utxo_selector::UTXOSelector::new(
-                client.clone(),
-                sender.clone(),
-                vec![],
-                self.skip_check_seal,
-            )
-            .await?;
+            let mut temp_selector =
+                UTXOSelector::new(client.clone(), sender.clone(), vec![], self.skip_check_seal)
+                    .await?;
+            if self.protect_inscriptions {
+                temp_selector.protect_inscriptions().await?;
+            }
 
             let all_utxos = temp_selector.load_all_utxos().await?;
             debug!("Loaded {} UTXOs", all_utxos.len());
@@ -229,9 +286,7 @@ impl CommandAction<String> for BuildTx {
                     sender,
                     bitcoin_network,
                     chunks,
-                    fee_rate,
-                    self.lock_time,
-                    self.change_address,
+                    &options,
                     self.outputs,
                     self.output_file,
                 )
@@ -246,9 +301,7 @@ impl CommandAction<String> for BuildTx {
                 // Estimate fee
                 let utxo_count = all_utxos.len();
                 let estimated_vsize = 100 + utxo_count * 60 + 2 * 43;
-                let fee_rate_val =
-                    fee_rate.unwrap_or_else(|| FeeRate::from_sat_per_vb(10).unwrap());
-                let estimated_fee = fee_rate_val
+                let estimated_fee = fee_rate
                     .fee_vb(estimated_vsize as u64)
                     .unwrap_or(Amount::from_sat(estimated_vsize as u64));
 
@@ -285,9 +338,7 @@ impl CommandAction<String> for BuildTx {
                     bitcoin_network,
                     all_utxos,
                     self.skip_check_seal,
-                    fee_rate,
-                    self.lock_time,
-                    self.change_address,
+                    &options,
                     specific_outputs,
                     self.output_file,
                 )
@@ -303,9 +354,7 @@ impl CommandAction<String> for BuildTx {
                 bitcoin_network,
                 vec![], // Empty inputs triggers auto-loading
                 self.skip_check_seal,
-                fee_rate,
-                self.lock_time,
-                self.change_address,
+                &options,
                 specific_outputs,
                 self.output_file,
             )
@@ -320,9 +369,7 @@ impl CommandAction<String> for BuildTx {
             bitcoin_network,
             vec![], // Empty inputs triggers auto-loading
             self.skip_check_seal,
-            fee_rate,
-            self.lock_time,
-            self.change_address,
+            &options,
             self.outputs,
             self.output_file,
         )
@@ -337,26 +384,15 @@ async fn build_single_transaction(
     bitcoin_network: rooch_types::bitcoin::network::Network,
     inputs: Vec<ObjectID>,
     skip_check_seal: bool,
-    fee_rate: Option<FeeRate>,
-    lock_time: Option<LockTime>,
-    change_address: Option<ParsedAddress>,
+    options: &BuilderOptions,
     outputs: Vec<ParsedOutput>,
     output_file: Option<String>,
 ) -> RoochResult<String> {
     let btc_network = bitcoin::Network::from(bitcoin_network);
-    let mut tx_builder =
-        TransactionBuilder::new(context, client, sender, inputs, skip_check_seal).await?;
-
-    if let Some(fee_rate) = fee_rate {
-        tx_builder = tx_builder.with_fee_rate(fee_rate);
-    }
-    if let Some(lock_time) = lock_time {
-        tx_builder = tx_builder.with_lock_time(lock_time);
-    }
-    if let Some(change_address) = change_address {
-        let change_btc_addr = context.resolve_bitcoin_address(change_address).await?;
-        let change_address = change_btc_addr.to_bitcoin_address(btc_network)?;
-        tx_builder = tx_builder.with_change_address(change_address);
+    let mut tx_builder = options
+        .apply(TransactionBuilder::new(context, client, sender, inputs, skip_check_seal).await?);
+    if options.protect_inscriptions {
+        tx_builder = tx_builder.protect_inscriptions().await?;
     }
 
     let mut converted_outputs = Vec::new();
@@ -388,9 +424,7 @@ async fn build_multiple_transactions(
     sender: Address,
     bitcoin_network: rooch_types::bitcoin::network::Network,
     chunks: Vec<Vec<UTXOObjectView>>,
-    fee_rate: Option<FeeRate>,
-    lock_time: Option<LockTime>,
-    change_address: Option<ParsedAddress>,
+    options: &BuilderOptions,
     outputs: Vec<ParsedOutput>,
     output_file: Option<String>,
 ) -> RoochResult<String> {
@@ -406,13 +440,8 @@ async fn build_multiple_transactions(
         resolved_outputs.push((address, output.amount.clone()));
     }
 
-    // Pre-resolve change address for vsize estimation (outside the loop)
-    let change_bitcoin_addr = if let Some(ref change_addr) = change_address {
-        let change_btc_addr = context.resolve_bitcoin_address(change_addr.clone()).await?;
-        Some(change_btc_addr.to_bitcoin_address(btc_network)?)
-    } else {
-        None
-    };
+    // The change address is resolved before building, use it for vsize estimation
+    let change_bitcoin_addr = options.change_address.clone();
 
     let mut all_files = Vec::new();
     let total_chunks = chunks.len();
@@ -443,8 +472,7 @@ async fn build_multiple_transactions(
                             )
                         };
 
-                        let fee_rate_val =
-                            fee_rate.unwrap_or_else(|| FeeRate::from_sat_per_vb(10).unwrap());
+                        let fee_rate_val = options.fee_rate;
 
                         debug!(
                             "utxo_count={}, estimated_vsize={}, fee_rate_val={:?}",
@@ -480,20 +508,13 @@ async fn build_multiple_transactions(
             .collect();
 
         // Use pre-loaded UTXOs directly, avoiding redundant queries
-        let mut tx_builder =
-            TransactionBuilder::with_utxos(context, client.clone(), sender.clone(), chunk_utxos);
-
-        if let Some(fee_rate_val) = fee_rate {
-            tx_builder = tx_builder.with_fee_rate(fee_rate_val);
-        }
-        if let Some(lock_time) = lock_time {
-            tx_builder = tx_builder.with_lock_time(lock_time);
-        }
-        if let Some(ref change_addr) = change_address {
-            let change_btc_addr = context.resolve_bitcoin_address(change_addr.clone()).await?;
-            let change_address = change_btc_addr.to_bitcoin_address(btc_network)?;
-            tx_builder = tx_builder.with_change_address(change_address);
-        }
+        // The inscriptions are already protected when loading all the UTXOs
+        let tx_builder = options.apply(TransactionBuilder::with_utxos(
+            context,
+            client.clone(),
+            sender.clone(),
+            chunk_utxos,
+        ));
 
         let psbt = tx_builder.build(chunk_outputs).await?;
         debug!("Transaction {} PSBT built successfully", chunk_idx + 1);
@@ -532,28 +553,20 @@ async fn build_single_transaction_with_utxos(
     bitcoin_network: rooch_types::bitcoin::network::Network,
     utxos: Vec<UTXOObjectView>,
     _skip_check_seal: bool, // UTXOs already checked during load_all_utxos
-    fee_rate: Option<FeeRate>,
-    lock_time: Option<LockTime>,
-    change_address: Option<ParsedAddress>,
+    options: &BuilderOptions,
     outputs: Vec<ParsedOutput>,
     output_file: Option<String>,
 ) -> RoochResult<String> {
     let btc_network = bitcoin::Network::from(bitcoin_network);
 
     // Use TransactionBuilder with pre-loaded UTXOs to avoid redundant queries
-    let mut tx_builder = TransactionBuilder::with_utxos(context, client.clone(), sender, utxos);
-
-    if let Some(fee_rate) = fee_rate {
-        tx_builder = tx_builder.with_fee_rate(fee_rate);
-    }
-    if let Some(lock_time) = lock_time {
-        tx_builder = tx_builder.with_lock_time(lock_time);
-    }
-    if let Some(change_address) = change_address {
-        let change_btc_addr = context.resolve_bitcoin_address(change_address).await?;
-        let change_address = change_btc_addr.to_bitcoin_address(btc_network)?;
-        tx_builder = tx_builder.with_change_address(change_address);
-    }
+    // The inscriptions are already protected when loading all the UTXOs
+    let tx_builder = options.apply(TransactionBuilder::with_utxos(
+        context,
+        client.clone(),
+        sender,
+        utxos,
+    ));
 
     let mut converted_outputs = Vec::new();
     for output in outputs {
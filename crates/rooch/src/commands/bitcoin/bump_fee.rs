@@ -0,0 +1,146 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::coin_selection::{ChangePolicy, CoinSelectionStrategy};
+use super::transaction_builder::TransactionBuilder;
+use super::{resolve_fee_rate, FileOutput, FileOutputData};
+use crate::cli_types::{CommandAction, FileOrHexInput, WalletContextOptions};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use bitcoin::{Amount, FeeRate, Psbt, ScriptBuf, Transaction, Witness};
+use clap::{Parser, ValueEnum};
+use rooch_types::address::ParsedAddress;
+use rooch_types::error::RoochResult;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BumpFeeMethod {
+    /// Replace the transaction with a higher fee one, the transaction must signal replace-by-fee
+    Rbf,
+    /// Spend the change output of the transaction with a child transaction which pays the fee
+    Cpfp,
+}
+
+#[derive(Debug, Parser)]
+pub struct BumpFee {
+    /// The psbt or tx file path or hex string of the transaction to bump the fee
+    input: FileOrHexInput,
+
+    /// The method to bump the fee
+    #[clap(long, value_enum, default_value_t = BumpFeeMethod::Rbf)]
+    method: BumpFeeMethod,
+
+    /// The address which pays the extra fee, if not specified, the active address will be used
+    #[clap(long, short = 's', value_parser=ParsedAddress::parse, default_value = "default")]
+    sender: ParsedAddress,
+
+    /// The new fee rate in sat/vB, if not specified, the fee rate is estimated from the recent blocks
+    #[clap(long)]
+    fee_rate: Option<FeeRate>,
+
+    /// The number of the recent blocks to estimate the fee rate when the fee rate is not specified
+    #[clap(long)]
+    fee_estimate_blocks: Option<u64>,
+
+    /// The change address of the transaction, the extra fee is paid by the output to it.
+    /// If not specified, the sender will be used.
+    /// The RBF uses the change output marked in the PSBT first, and refuses to bump the fee
+    /// if more than one output pays to the change address without a marked change output
+    #[clap(long, value_parser=ParsedAddress::parse)]
+    change_address: Option<ParsedAddress>,
+
+    /// The strategy to select the UTXOs when the change is not enough to pay the RBF fee
+    #[clap(long, value_enum, default_value_t = CoinSelectionStrategy::OldestFirst)]
+    coin_selection: CoinSelectionStrategy,
+
+    /// Never spend the UTXOs which carry inscriptions
+    #[clap(long)]
+    protect_inscriptions: bool,
+
+    /// The min value of the change output in satoshi, the change below it is added to the fee
+    #[clap(long)]
+    min_change: Option<u64>,
+
+    /// The output file path
+    /// If not provided, the file will be written to temp directory
+    #[clap(long)]
+    output_file: Option<String>,
+
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<FileOutput> for BumpFee {
+    async fn execute(self) -> RoochResult<FileOutput> {
+        let context = self.context_options.build_require_password()?;
+        let client = context.get_client().await?;
+
+        let bitcoin_network = context.get_bitcoin_network().await?;
+        let sender = context
+            .resolve_bitcoin_address(self.sender)
+            .await?
+            .to_bitcoin_address(bitcoin_network)?;
+        let fee_rate = resolve_fee_rate(&client, self.fee_rate, self.fee_estimate_blocks).await?;
+
+        let mut tx_builder =
+            TransactionBuilder::new(&context, client.clone(), sender, vec![], false)
+                .await?
+                .with_fee_rate(fee_rate)
+                .with_coin_selection(self.coin_selection)
+                .with_change_policy(ChangePolicy {
+                    min_change: self.min_change.map(Amount::from_sat),
+                });
+        if let Some(change_address) = self.change_address {
+            let change_address = context
+                .resolve_bitcoin_address(change_address)
+                .await?
+                .to_bitcoin_address(bitcoin_network)?;
+            tx_builder = tx_builder.with_change_address(change_address);
+        }
+        if self.protect_inscriptions {
+            tx_builder = tx_builder.protect_inscriptions().await?;
+        }
+
+        let psbt = match Psbt::deserialize(&self.input.data) {
+            Ok(psbt) => {
+                debug!("bump fee of psbt: {}", psbt.unsigned_tx.compute_txid());
+                match self.method {
+                    BumpFeeMethod::Rbf => tx_builder.bump_fee_rbf(psbt).await?,
+                    BumpFeeMethod::Cpfp => {
+                        // Only the txid and the outputs of the parent are required,
+                        // the txid of the segwit transaction does not depend on the signatures.
+                        tx_builder
+                            .build_cpfp(psbt.extract_tx_unchecked_fee_rate())
+                            .await?
+                    }
+                }
+            }
+            Err(_) => {
+                let tx: Transaction = bitcoin::consensus::deserialize(&self.input.data)
+                    .map_err(|e| anyhow!("The input is neither a psbt nor a transaction: {}", e))?;
+                debug!("bump fee of tx: {}", tx.compute_txid());
+                match self.method {
+                    BumpFeeMethod::Rbf => {
+                        // The signatures are dropped, the spent UTXOs and the signers of all the
+                        // inputs are filled from the UTXOs in Rooch when building the replacement.
+                        let mut unsigned_tx = tx;
+                        for input in unsigned_tx.input.iter_mut() {
+                            input.script_sig = ScriptBuf::new();
+                            input.witness = Witness::default();
+                        }
+                        tx_builder
+                            .bump_fee_rbf(Psbt::from_unsigned_tx(unsigned_tx)?)
+                            .await?
+                    }
+                    BumpFeeMethod::Cpfp => tx_builder.build_cpfp(tx).await?,
+                }
+            }
+        };
+
+        Ok(FileOutput::write_to_file(
+            FileOutputData::Psbt(psbt),
+            self.output_file,
+        )?)
+    }
+}
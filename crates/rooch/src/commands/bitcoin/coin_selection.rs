@@ -0,0 +1,375 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use bitcoin::{Amount, FeeRate, Script, TxOut};
+use clap::ValueEnum;
+
+/// The max number of the branches to explore in the branch-and-bound search, same as Bitcoin Core.
+const BNB_TOTAL_TRIES: usize = 100_000;
+
+/// The strategy used to select the UTXOs which fund the transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum CoinSelectionStrategy {
+    /// Spend the oldest UTXOs first, the UTXOs are loaded page by page.
+    #[default]
+    OldestFirst,
+    /// Spend the largest UTXOs first, minimize the number of the inputs.
+    LargestFirst,
+    /// Search for the inputs which match the target without a change output, fall back to largest-first.
+    BranchAndBound,
+    /// Spend all the UTXOs and merge them into the change output.
+    Consolidate,
+}
+
+/// The parameters to calculate the effective value of the UTXOs
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionParams {
+    pub fee_rate: FeeRate,
+    /// The vbytes added to the transaction by an input
+    pub input_vbytes: u64,
+    /// The cost to add a change output, the branch-and-bound solution can waste up to this value as fee
+    pub cost_of_change: Amount,
+}
+
+impl Default for SelectionParams {
+    fn default() -> Self {
+        Self {
+            fee_rate: FeeRate::ZERO,
+            input_vbytes: 0,
+            cost_of_change: Amount::ZERO,
+        }
+    }
+}
+
+impl SelectionParams {
+    /// The value of the UTXO minus the fee to spend it, None if the UTXO is not worth spending
+    pub fn effective_value(&self, amount: Amount) -> Option<Amount> {
+        let input_fee = self.fee_rate.fee_vb(self.input_vbytes)?;
+        amount
+            .checked_sub(input_fee)
+            .filter(|value| *value > Amount::ZERO)
+    }
+}
+
+/// Select the UTXOs from the `amounts` whose effective values cover the `target`.
+/// Return the indexes of the selected UTXOs, or None if the UTXOs are not enough.
+pub fn select_coins(
+    strategy: CoinSelectionStrategy,
+    amounts: &[Amount],
+    target: Amount,
+    params: &SelectionParams,
+) -> Option<Vec<usize>> {
+    let candidates = amounts
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, amount)| params.effective_value(*amount).map(|value| (idx, value)))
+        .collect::<Vec<_>>();
+    match strategy {
+        CoinSelectionStrategy::OldestFirst => select_in_order(candidates, target),
+        CoinSelectionStrategy::LargestFirst => select_largest_first(candidates, target),
+        CoinSelectionStrategy::BranchAndBound => {
+            select_branch_and_bound(&candidates, target, params.cost_of_change)
+                .or_else(|| select_largest_first(candidates, target))
+        }
+        CoinSelectionStrategy::Consolidate => {
+            let total = candidates.iter().map(|(_, value)| *value).sum::<Amount>();
+            (total >= target).then(|| candidates.into_iter().map(|(idx, _)| idx).collect())
+        }
+    }
+}
+
+fn select_in_order(candidates: Vec<(usize, Amount)>, target: Amount) -> Option<Vec<usize>> {
+    let mut selected = vec![];
+    let mut total = Amount::ZERO;
+    for (idx, value) in candidates {
+        if total >= target {
+            break;
+        }
+        selected.push(idx);
+        total += value;
+    }
+    (total >= target).then_some(selected)
+}
+
+fn select_largest_first(
+    mut candidates: Vec<(usize, Amount)>,
+    target: Amount,
+) -> Option<Vec<usize>> {
+    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+    select_in_order(candidates, target)
+}
+
+/// The depth-first search of the branch-and-bound algorithm, see `SelectCoinsBnB` in Bitcoin Core.
+/// Find the inputs whose total effective value is in `[target, target + cost_of_change]`,
+/// the solution with the least excess is preferred.
+fn select_branch_and_bound(
+    candidates: &[(usize, Amount)],
+    target: Amount,
+    cost_of_change: Amount,
+) -> Option<Vec<usize>> {
+    let mut candidates = candidates.to_vec();
+    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+    let values = candidates
+        .iter()
+        .map(|(_, value)| value.to_sat())
+        .collect::<Vec<_>>();
+    let target = target.to_sat();
+    let upper_bound = target.saturating_add(cost_of_change.to_sat());
+    let mut remaining = values.iter().sum::<u64>();
+    if remaining < target {
+        return None;
+    }
+
+    // The include(true) or exclude(false) decision of the sorted candidates
+    let mut selection: Vec<bool> = vec![];
+    let mut current_value = 0u64;
+    let mut best: Option<(u64, Vec<bool>)> = None;
+    for _ in 0..BNB_TOTAL_TRIES {
+        let backtrack = if current_value + remaining < target || current_value > upper_bound {
+            true
+        } else if current_value >= target {
+            let excess = current_value - target;
+            if !best
+                .as_ref()
+                .is_some_and(|(best_excess, _)| excess >= *best_excess)
+            {
+                best = Some((excess, selection.clone()));
+            }
+            if excess == 0 {
+                break;
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            // Walk back to the last included candidate, and exclude it
+            while let Some(false) = selection.last() {
+                selection.pop();
+                remaining += values[selection.len()];
+            }
+            match selection.last_mut() {
+                Some(included) => {
+                    *included = false;
+                    current_value -= values[selection.len() - 1];
+                }
+                // All the branches are explored
+                None => break,
+            }
+        } else {
+            let position = selection.len();
+            remaining -= values[position];
+            current_value += values[position];
+            selection.push(true);
+        }
+    }
+
+    best.map(|(_, selection)| {
+        selection
+            .into_iter()
+            .enumerate()
+            .filter(|(_, included)| *included)
+            .map(|(position, _)| candidates[position].0)
+            .collect()
+    })
+}
+
+/// The policy to decide whether the transaction has a change output
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChangePolicy {
+    /// The change below this value is added to the fee,
+    /// the dust limit of the change script is always respected.
+    pub min_change: Option<Amount>,
+}
+
+impl ChangePolicy {
+    /// The min value of the change output
+    pub fn change_threshold(&self, change_script: &Script) -> Amount {
+        self.min_change
+            .unwrap_or(Amount::ZERO)
+            .max(change_script.minimal_non_dust())
+    }
+
+    /// The fee to add the change output to the transaction
+    pub fn change_output_fee(change_script: &Script, fee_rate: FeeRate) -> Amount {
+        let change_output = TxOut {
+            value: Amount::ZERO,
+            script_pubkey: change_script.to_owned(),
+        };
+        fee_rate
+            .fee_vb(change_output.size() as u64)
+            .unwrap_or(Amount::MAX_MONEY)
+    }
+
+    /// The cost of the change output used by the branch-and-bound selection,
+    /// an excess lower than it does not create a change output.
+    pub fn cost_of_change(&self, change_script: &Script, fee_rate: FeeRate) -> Amount {
+        Self::change_output_fee(change_script, fee_rate) + self.change_threshold(change_script)
+    }
+
+    /// The value of the change output for the `excess` of the inputs over the outputs and fee,
+    /// None if the change is too small and should be added to the fee.
+    pub fn change_value(
+        &self,
+        excess: Amount,
+        change_script: &Script,
+        fee_rate: FeeRate,
+    ) -> Option<Amount> {
+        excess
+            .checked_sub(Self::change_output_fee(change_script, fee_rate))
+            .filter(|change| *change >= self.change_threshold(change_script))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::ScriptBuf;
+
+    fn sats(values: &[u64]) -> Vec<Amount> {
+        values
+            .iter()
+            .map(|value| Amount::from_sat(*value))
+            .collect()
+    }
+
+    fn zero_fee_params(cost_of_change: u64) -> SelectionParams {
+        SelectionParams {
+            fee_rate: FeeRate::ZERO,
+            input_vbytes: 58,
+            cost_of_change: Amount::from_sat(cost_of_change),
+        }
+    }
+
+    #[test]
+    fn test_select_coins_strategies() {
+        let amounts = sats(&[1_000, 5_000, 2_000, 8_000, 3_000]);
+        let params = zero_fee_params(0);
+        let target = Amount::from_sat(7_000);
+
+        let selected = select_coins(
+            CoinSelectionStrategy::OldestFirst,
+            &amounts,
+            target,
+            &params,
+        );
+        assert_eq!(selected, Some(vec![0, 1, 2]));
+
+        let selected = select_coins(
+            CoinSelectionStrategy::LargestFirst,
+            &amounts,
+            target,
+            &params,
+        );
+        assert_eq!(selected, Some(vec![3]));
+
+        let mut selected = select_coins(
+            CoinSelectionStrategy::BranchAndBound,
+            &amounts,
+            target,
+            &params,
+        )
+        .unwrap();
+        selected.sort();
+        let total = selected.iter().map(|idx| amounts[*idx]).sum::<Amount>();
+        assert_eq!(total, target);
+
+        let selected = select_coins(
+            CoinSelectionStrategy::Consolidate,
+            &amounts,
+            target,
+            &params,
+        );
+        assert_eq!(selected, Some(vec![0, 1, 2, 3, 4]));
+
+        for strategy in CoinSelectionStrategy::value_variants() {
+            assert_eq!(
+                select_coins(*strategy, &amounts, Amount::from_sat(20_000), &params),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        let amounts = sats(&[4_000, 3_000, 7_500, 1_200]);
+        let target = Amount::from_sat(8_600);
+        // 7_500 + 1_200 = 8_700 is in the window of the cost of change
+        let selected = select_coins(
+            CoinSelectionStrategy::BranchAndBound,
+            &amounts,
+            target,
+            &zero_fee_params(600),
+        );
+        assert_eq!(selected, Some(vec![2, 3]));
+
+        // No solution in the window, fall back to largest-first
+        let selected = select_coins(
+            CoinSelectionStrategy::BranchAndBound,
+            &amounts,
+            target,
+            &zero_fee_params(0),
+        );
+        assert_eq!(selected, Some(vec![2, 0]));
+    }
+
+    #[test]
+    fn test_effective_value() {
+        let params = SelectionParams {
+            fee_rate: FeeRate::from_sat_per_vb(10).unwrap(),
+            input_vbytes: 58,
+            cost_of_change: Amount::ZERO,
+        };
+        assert_eq!(
+            params.effective_value(Amount::from_sat(1_000)),
+            Some(Amount::from_sat(420))
+        );
+        assert_eq!(params.effective_value(Amount::from_sat(580)), None);
+        // The UTXOs which cost more fee than their value are never selected
+        let amounts = sats(&[500, 2_000]);
+        let selected = select_coins(
+            CoinSelectionStrategy::Consolidate,
+            &amounts,
+            Amount::from_sat(1_000),
+            &params,
+        );
+        assert_eq!(selected, Some(vec![1]));
+    }
+
+    #[test]
+    fn test_change_policy() {
+        // A P2TR script
+        let change_script = ScriptBuf::from_bytes([&[0x51, 0x20][..], &[1u8; 32][..]].concat());
+        let fee_rate = FeeRate::from_sat_per_vb(10).unwrap();
+        let policy = ChangePolicy::default();
+        let dust = change_script.minimal_non_dust();
+        let change_fee = ChangePolicy::change_output_fee(&change_script, fee_rate);
+
+        assert_eq!(policy.change_threshold(&change_script), dust);
+        assert_eq!(
+            policy.change_value(change_fee + dust, &change_script, fee_rate),
+            Some(dust)
+        );
+        assert_eq!(
+            policy.change_value(
+                change_fee + dust - Amount::from_sat(1),
+                &change_script,
+                fee_rate
+            ),
+            None
+        );
+
+        let policy = ChangePolicy {
+            min_change: Some(Amount::from_sat(10_000)),
+        };
+        assert_eq!(
+            policy.change_value(change_fee + dust, &change_script, fee_rate),
+            None
+        );
+        assert_eq!(
+            policy.cost_of_change(&change_script, fee_rate),
+            change_fee + Amount::from_sat(10_000)
+        );
+    }
+}
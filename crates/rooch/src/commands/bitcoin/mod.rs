@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_types::CommandAction;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use bitcoin::{consensus::Encodable, Psbt, Transaction, Txid};
+use bitcoin::{consensus::Encodable, FeeRate, Psbt, Transaction, Txid};
 use clap::{Parser, Subcommand};
+use rooch_rpc_client::Client;
 use rooch_types::error::RoochResult;
 use serde::{Deserialize, Serialize};
 use std::{env, fs::File, io::Write, path::PathBuf, time::Duration};
@@ -13,12 +14,15 @@ use tracing::debug;
 
 use self::broadcast_tx::BroadcastTx;
 use self::build_tx::BuildTx;
+use self::bump_fee::BumpFee;
 use self::sign_tx::SignTx;
 use self::transfer::Transfer;
 use self::verify_psbt::VerifyPsbt;
 
 pub mod broadcast_tx;
 pub mod build_tx;
+pub mod bump_fee;
+pub mod coin_selection;
 pub mod sign_tx;
 pub mod transaction_builder;
 pub mod transfer;
@@ -65,6 +69,56 @@ where
     }
 }
 
+/// The fee rate in sat/vB used when the fee rate is not specified and can not be estimated
+pub const DEFAULT_FEE_RATE_SAT_PER_VB: u64 = 10;
+
+/// Resolve the fee rate of the transaction.
+/// The CLI parser interprets the numeric argument as sat/kwu (sat per 1000 weight units),
+/// but users expect sat/vbyte, so the same numeric value is re-wrapped as sat/vbyte.
+/// If the fee rate is not specified, estimate it from the recent Bitcoin blocks relayed to Rooch.
+pub async fn resolve_fee_rate(
+    client: &Client,
+    fee_rate: Option<FeeRate>,
+    fee_estimate_blocks: Option<u64>,
+) -> Result<FeeRate> {
+    if let Some(fee_rate) = fee_rate {
+        let numeric = fee_rate.to_sat_per_kwu();
+        return FeeRate::from_sat_per_vb(numeric)
+            .ok_or_else(|| anyhow!("Invalid fee rate: {} sat/vB", numeric));
+    }
+    let default_fee_rate = FeeRate::from_sat_per_vb_unchecked(DEFAULT_FEE_RATE_SAT_PER_VB);
+    let estimate = retry_rpc_call(|| async {
+        client
+            .rooch
+            .estimate_bitcoin_fee_rate(fee_estimate_blocks)
+            .await
+    })
+    .await;
+    match estimate {
+        Ok(estimate) if estimate.block_count.0 > 0 => {
+            debug!(
+                "Estimated fee rate {} sat/vB from {} blocks",
+                estimate.sat_per_vbyte, estimate.block_count
+            );
+            Ok(FeeRate::from_sat_per_vb(estimate.sat_per_vbyte.0).unwrap_or(default_fee_rate))
+        }
+        Ok(_) => {
+            eprintln!(
+                "No relayed Bitcoin block with transactions to estimate the fee rate, use the default fee rate {} sat/vB",
+                DEFAULT_FEE_RATE_SAT_PER_VB
+            );
+            Ok(default_fee_rate)
+        }
+        Err(e) => {
+            eprintln!(
+                "Failed to estimate the fee rate, use the default fee rate {} sat/vB: {}",
+                DEFAULT_FEE_RATE_SAT_PER_VB, e
+            );
+            Ok(default_fee_rate)
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct Bitcoin {
     #[clap(subcommand)]
@@ -75,6 +129,7 @@ pub struct Bitcoin {
 #[derive(Debug, Subcommand)]
 pub enum BitcoinCommands {
     BuildTx(BuildTx),
+    BumpFee(BumpFee),
     SignTx(SignTx),
     BroadcastTx(BroadcastTx),
     Transfer(Transfer),
@@ -86,6 +141,7 @@ impl CommandAction<String> for Bitcoin {
     async fn execute(self) -> RoochResult<String> {
         match self.cmd {
             BitcoinCommands::BuildTx(build_tx) => build_tx.execute().await,
+            BitcoinCommands::BumpFee(bump_fee) => bump_fee.execute_serialized().await,
             BitcoinCommands::SignTx(sign_tx) => sign_tx.execute_serialized().await,
             BitcoinCommands::BroadcastTx(broadcast_tx) => broadcast_tx.execute_serialized().await,
             BitcoinCommands::Transfer(transfer) => transfer.execute_serialized().await,
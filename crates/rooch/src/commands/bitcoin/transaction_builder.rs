@@ -1,17 +1,20 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+use super::coin_selection::{ChangePolicy, CoinSelectionStrategy, SelectionParams};
 use super::utxo_selector::UTXOSelector;
 use anyhow::{anyhow, bail, Result};
 use bitcoin::{
-    absolute::LockTime, bip32::Fingerprint, transaction::Version, Address, Amount, FeeRate,
-    OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+    absolute::LockTime, bip32::Fingerprint, psbt::raw::ProprietaryKey, transaction::Version,
+    Address, Amount, FeeRate, OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
+    Witness,
 };
 use moveos_types::{module_binding::MoveFunctionCaller, moveos_std::object::ObjectID};
 use rooch_rpc_api::jsonrpc_types::btc::utxo::UTXOObjectView;
 use rooch_rpc_client::{wallet_context::WalletContext, Client};
-use rooch_types::address::RoochAddress;
+use rooch_types::address::{BitcoinAddress, RoochAddress};
 use rooch_types::bitcoin::multisign_account::{self, PrecomputedMultisigPsbtData};
+use rooch_types::bitcoin::utxo::derive_utxo_id;
 use tracing::debug;
 
 // Use retry configuration and functions from mod.rs
@@ -24,6 +27,7 @@ pub struct TransactionBuilder<'a> {
     utxo_selector: UTXOSelector,
     fee_rate: FeeRate,
     change_address: Address,
+    change_policy: ChangePolicy,
     lock_time: Option<LockTime>,
 }
 
@@ -31,6 +35,11 @@ impl<'a> TransactionBuilder<'a> {
     const ADDITIONAL_INPUT_VBYTES: usize = 58;
     const ADDITIONAL_OUTPUT_VBYTES: usize = 43;
     const SCHNORR_SIGNATURE_SIZE: usize = 64;
+    /// The min fee rate to pay for the replacement transaction's own bandwidth, see BIP125 rule 4
+    const INCREMENTAL_RELAY_FEE_RATE: FeeRate = FeeRate::BROADCAST_MIN;
+    /// The prefix of the PSBT proprietary key which marks the change output
+    const PSBT_PROPRIETARY_PREFIX: &'static [u8] = b"rooch";
+    const PSBT_CHANGE_OUTPUT_SUBTYPE: u8 = 0;
 
     pub async fn new(
         wallet_context: &'a WalletContext,
@@ -47,6 +56,7 @@ impl<'a> TransactionBuilder<'a> {
             utxo_selector,
             fee_rate: FeeRate::from_sat_per_vb(10).unwrap(),
             change_address: sender,
+            change_policy: ChangePolicy::default(),
             lock_time: None,
         })
     }
@@ -65,6 +75,7 @@ impl<'a> TransactionBuilder<'a> {
             utxo_selector,
             fee_rate: FeeRate::from_sat_per_vb(10).unwrap(),
             change_address: sender,
+            change_policy: ChangePolicy::default(),
             lock_time: None,
         }
     }
@@ -84,6 +95,22 @@ impl<'a> TransactionBuilder<'a> {
         self
     }
 
+    pub fn with_coin_selection(mut self, strategy: CoinSelectionStrategy) -> Self {
+        self.utxo_selector = self.utxo_selector.with_strategy(strategy);
+        self
+    }
+
+    pub fn with_change_policy(mut self, change_policy: ChangePolicy) -> Self {
+        self.change_policy = change_policy;
+        self
+    }
+
+    /// Never spend the UTXOs which carry inscriptions
+    pub async fn protect_inscriptions(mut self) -> Result<Self> {
+        self.utxo_selector.protect_inscriptions().await?;
+        Ok(self)
+    }
+
     /// Estimate vsize for a transaction with given inputs and outputs
     /// This creates a temporary transaction and calculates its vsize
     pub fn estimate_vbytes_with(inputs: usize, outputs: Vec<Address>) -> usize {
@@ -109,6 +136,18 @@ impl<'a> TransactionBuilder<'a> {
         .vsize()
     }
 
+    /// Estimate the vsize of the transaction after it is signed,
+    /// the unsigned inputs are assumed to be spent via the taproot key path.
+    pub fn estimate_signed_vsize(tx: &Transaction) -> usize {
+        let mut tx = tx.clone();
+        for input in tx.input.iter_mut() {
+            if input.witness.is_empty() && input.script_sig.is_empty() {
+                input.witness = Witness::from_slice(&[&[0; Self::SCHNORR_SIGNATURE_SIZE]]);
+            }
+        }
+        tx.vsize()
+    }
+
     pub async fn build_transfer(self, receipient: Address, amount: Amount) -> Result<Psbt> {
         self.build(vec![(receipient, amount)]).await
     }
@@ -120,8 +159,24 @@ impl<'a> TransactionBuilder<'a> {
             .map(|(address, _)| address.clone())
             .collect::<Vec<_>>();
 
+        let change_script = self.change_address.script_pubkey();
+        self.utxo_selector.set_selection_params(SelectionParams {
+            fee_rate: self.fee_rate,
+            input_vbytes: Self::ADDITIONAL_INPUT_VBYTES as u64,
+            cost_of_change: self
+                .change_policy
+                .cost_of_change(&change_script, self.fee_rate),
+        });
+        let base_fee = self
+            .fee_rate
+            .fee_vb(Self::estimate_vbytes_with(0, output_address.clone()) as u64)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", self.fee_rate))?;
+
         // Select UTXOs first to get accurate input count
-        let mut utxos = self.utxo_selector.select_utxos(total_output).await?;
+        let mut utxos = self
+            .utxo_selector
+            .select_utxos(total_output + base_fee)
+            .await?;
 
         // Recalculate estimate_fee with actual input count
         let actual_input_count = utxos.len();
@@ -215,15 +270,52 @@ impl<'a> TransactionBuilder<'a> {
             }
         }
 
-        let change = total_input - total_output - fee;
-        if change > Amount::from_sat(0) {
+        let excess = total_input.checked_sub(total_output + fee).ok_or_else(|| {
+            anyhow!(
+                "not enough BTC funds, input: {}, output: {}, fee: {}",
+                total_input,
+                total_output,
+                fee
+            )
+        })?;
+        // The change below the threshold of the change policy is added to the fee
+        let mut change_index = None;
+        if let Some(change) = self
+            .change_policy
+            .change_value(excess, &change_script, self.fee_rate)
+        {
+            change_index = Some(tx.output.len());
             tx.output.push(TxOut {
                 value: change,
-                script_pubkey: self.change_address.script_pubkey(),
+                script_pubkey: change_script,
             });
+        } else {
+            debug!("TransactionBuilder: no change output, excess={}", excess);
         }
         let mut psbt = Psbt::from_unsigned_tx(tx)?;
+        if let Some(change_index) = change_index {
+            Self::mark_change_output(&mut psbt, change_index);
+        }
+        let inputs = utxos
+            .iter()
+            .enumerate()
+            .map(|(idx, utxo)| {
+                let owner = utxo.owner_bitcoin_address().ok_or_else(|| {
+                    anyhow!("Can not recognize the owner of UTXO {}", utxo.outpoint())
+                })?;
+                Ok((idx, owner, utxo.amount()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.fill_psbt_inputs(&mut psbt, inputs).await?;
+        Ok(psbt)
+    }
 
+    /// Fill the spent UTXO and the signer info of the PSBT inputs
+    async fn fill_psbt_inputs(
+        &self,
+        psbt: &mut Psbt,
+        inputs: Vec<(usize, BitcoinAddress, Amount)>,
+    ) -> Result<()> {
         let multisign_account_module = self
             .client
             .as_module_binding::<multisign_account::MultisignAccountModule>();
@@ -234,16 +326,12 @@ impl<'a> TransactionBuilder<'a> {
         let mut non_multisign_addresses: std::collections::HashSet<RoochAddress> =
             std::collections::HashSet::new();
 
-        for (idx, utxo) in utxos.iter().enumerate() {
+        for (idx, bitcoin_addr, amount) in inputs {
             let input = &mut psbt.inputs[idx];
 
-            let bitcoin_addr = utxo.owner_bitcoin_address().ok_or_else(|| {
-                anyhow!("Can not recognize the owner of UTXO {}", utxo.outpoint())
-            })?;
-
             if bitcoin_addr.is_witness() {
                 input.witness_utxo = Some(TxOut {
-                    value: utxo.amount(),
+                    value: amount,
                     script_pubkey: bitcoin_addr.script_pubkey()?,
                 });
             } else {
@@ -301,9 +389,396 @@ impl<'a> TransactionBuilder<'a> {
             }
         }
 
+        Ok(())
+    }
+
+    /// The owners and the values of the UTXOs spent by the transaction, the UTXOs are queried from Rooch
+    /// if the PSBT does not carry them. The UTXOs of an unconfirmed transaction are still unspent in Rooch.
+    async fn prevouts(
+        &self,
+        psbt: Option<&Psbt>,
+        tx: &Transaction,
+    ) -> Result<Vec<(BitcoinAddress, Amount)>> {
+        let mut prevouts = vec![];
+        for (idx, input) in tx.input.iter().enumerate() {
+            let witness_utxo = psbt.and_then(|psbt| psbt.inputs.get(idx)?.witness_utxo.as_ref());
+            let prevout = match witness_utxo {
+                Some(witness_utxo) => (
+                    BitcoinAddress::from(&witness_utxo.script_pubkey),
+                    witness_utxo.value,
+                ),
+                None => {
+                    let utxo = self
+                        .utxo_selector
+                        .get_utxo(&input.previous_output.into())
+                        .await?;
+                    let owner = utxo.owner_bitcoin_address().ok_or_else(|| {
+                        anyhow!("Can not recognize the owner of UTXO {}", utxo.outpoint())
+                    })?;
+                    (owner, utxo.amount())
+                }
+            };
+            prevouts.push(prevout);
+        }
+        Ok(prevouts)
+    }
+
+    fn change_output_key() -> ProprietaryKey {
+        ProprietaryKey {
+            prefix: Self::PSBT_PROPRIETARY_PREFIX.to_vec(),
+            subtype: Self::PSBT_CHANGE_OUTPUT_SUBTYPE,
+            key: vec![],
+        }
+    }
+
+    /// Mark the change output in the PSBT, so the change output can be identified when bumping the fee
+    fn mark_change_output(psbt: &mut Psbt, change_index: usize) {
+        psbt.outputs[change_index]
+            .proprietary
+            .insert(Self::change_output_key(), vec![]);
+    }
+
+    /// The index of the change output which pays the RBF fee.
+    /// The change output marked in the PSBT is used first, otherwise it is the output paying to the change address.
+    /// The change output can not be identified if more than one output pays to the change address,
+    /// such as the payment and the change are paid to the same address.
+    fn change_output_index(psbt: &Psbt, change_address: &Address) -> Result<Option<usize>> {
+        let txid = psbt.unsigned_tx.compute_txid();
+        let change_output_key = Self::change_output_key();
+        let marked = psbt
+            .outputs
+            .iter()
+            .enumerate()
+            .filter(|(_, output)| output.proprietary.contains_key(&change_output_key))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        match marked.as_slice() {
+            [] => {}
+            [idx] => return Ok(Some(*idx)),
+            _ => bail!(
+                "Transaction {} marks more than one change output: {:?}",
+                txid,
+                marked
+            ),
+        }
+
+        let change_script = change_address.script_pubkey();
+        let matched = psbt
+            .unsigned_tx
+            .output
+            .iter()
+            .enumerate()
+            .filter(|(_, output)| output.script_pubkey == change_script)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        match matched.as_slice() {
+            [] => Ok(None),
+            [idx] => Ok(Some(*idx)),
+            _ => bail!(
+                "Outputs {:?} of transaction {} all pay to the change address {}, can not identify the change output to pay the fee",
+                matched,
+                txid,
+                change_address
+            ),
+        }
+    }
+
+    /// The fee of the replacement transaction with the given vsize,
+    /// it pays the target fee rate and at least the incremental relay fee over the replaced transaction.
+    fn replacement_fee(fee_rate: FeeRate, replaced_fee: Amount, vsize: usize) -> Result<Amount> {
+        let fee = fee_rate
+            .fee_vb(vsize as u64)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", fee_rate))?;
+        let incremental_fee = Self::INCREMENTAL_RELAY_FEE_RATE
+            .fee_vb(vsize as u64)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", fee_rate))?;
+        Ok(fee.max(replaced_fee + incremental_fee))
+    }
+
+    /// Bump the fee of the transaction via replace-by-fee(BIP125).
+    /// The fee is paid by the change output, more UTXOs are added if the change is not enough.
+    /// The change output is the one marked in the PSBT, or the only output paying to the change address.
+    /// The signatures of the PSBT are dropped, the returned PSBT needs to be signed again.
+    pub async fn bump_fee_rbf(mut self, psbt: Psbt) -> Result<Psbt> {
+        let tx = psbt.unsigned_tx.clone();
+        let txid = tx.compute_txid();
+        let prevouts = self.prevouts(Some(&psbt), &tx).await?;
+        let change_index = Self::change_output_index(&psbt, &self.change_address)?;
+        let (tx, inputs, change_index) = Self::replace_by_fee(
+            &mut self.utxo_selector,
+            tx,
+            prevouts,
+            change_index,
+            self.fee_rate,
+            &self.change_address,
+            self.change_policy,
+        )
+        .await?;
+
+        // All the inputs, including the original ones, are filled from the spent UTXOs,
+        // so the replacement of a raw transaction can be signed too.
+        let mut new_psbt = Psbt::from_unsigned_tx(tx)?;
+        if let Some(change_index) = change_index {
+            Self::mark_change_output(&mut new_psbt, change_index);
+        }
+        self.fill_psbt_inputs(&mut new_psbt, inputs).await?;
+        debug!(
+            "bump_fee_rbf: replaced {} with {}",
+            txid,
+            new_psbt.unsigned_tx.compute_txid()
+        );
+        Ok(new_psbt)
+    }
+
+    /// Build the unsigned replacement of the transaction, and return it with the spent UTXOs of all its inputs
+    /// and the index of its change output.
+    /// The prevouts are the owners and the values of the UTXOs spent by the original inputs.
+    /// The change output at `change_index` pays the fee, the new change is paid to the change address
+    /// if the original transaction has no change output.
+    async fn replace_by_fee(
+        utxo_selector: &mut UTXOSelector,
+        mut tx: Transaction,
+        prevouts: Vec<(BitcoinAddress, Amount)>,
+        mut change_index: Option<usize>,
+        fee_rate: FeeRate,
+        change_address: &Address,
+        change_policy: ChangePolicy,
+    ) -> Result<(
+        Transaction,
+        Vec<(usize, BitcoinAddress, Amount)>,
+        Option<usize>,
+    )> {
+        let txid = tx.compute_txid();
+        if !tx.is_explicitly_rbf() {
+            bail!(
+                "Transaction {} does not signal replace-by-fee, please bump the fee via CPFP",
+                txid
+            );
+        }
+        if prevouts.len() != tx.input.len() {
+            bail!(
+                "Transaction {} has {} inputs, but {} spent UTXOs are provided",
+                txid,
+                tx.input.len(),
+                prevouts.len()
+            );
+        }
+        let mut total_input = prevouts.iter().map(|(_, value)| *value).sum::<Amount>();
+        let replaced_fee = total_input
+            .checked_sub(tx.output.iter().map(|output| output.value).sum())
+            .ok_or_else(|| anyhow!("The outputs of transaction {} exceed the inputs", txid))?;
+        let mut inputs = prevouts
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (owner, value))| (idx, owner, value))
+            .collect::<Vec<_>>();
+
+        // The change output is removed, and added back with the remaining value
+        let change_script = match change_index {
+            Some(idx) => {
+                if idx >= tx.output.len() {
+                    bail!(
+                        "The change output {} of transaction {} does not exist",
+                        idx,
+                        txid
+                    );
+                }
+                tx.output.remove(idx).script_pubkey
+            }
+            None => change_address.script_pubkey(),
+        };
+        let total_output = tx.output.iter().map(|output| output.value).sum::<Amount>();
+
+        // The replacement must not spend the UTXOs spent by the original transaction twice
+        utxo_selector.exclude_utxos(
+            tx.input
+                .iter()
+                .map(|input| derive_utxo_id(&input.previous_output.into())),
+        );
+        utxo_selector.set_selection_params(SelectionParams {
+            fee_rate,
+            input_vbytes: Self::ADDITIONAL_INPUT_VBYTES as u64,
+            cost_of_change: change_policy.cost_of_change(&change_script, fee_rate),
+        });
+
+        loop {
+            let mut tx_with_change = tx.clone();
+            tx_with_change.output.push(TxOut {
+                value: Amount::ZERO,
+                script_pubkey: change_script.clone(),
+            });
+            let fee_with_change = Self::replacement_fee(
+                fee_rate,
+                replaced_fee,
+                Self::estimate_signed_vsize(&tx_with_change),
+            )?;
+            let change = total_input
+                .checked_sub(total_output + fee_with_change)
+                .filter(|change| *change >= change_policy.change_threshold(&change_script));
+            if let Some(change) = change {
+                tx.output.push(TxOut {
+                    value: change,
+                    script_pubkey: change_script,
+                });
+                change_index = Some(tx.output.len() - 1);
+                break;
+            }
+
+            let fee =
+                Self::replacement_fee(fee_rate, replaced_fee, Self::estimate_signed_vsize(&tx))?;
+            if total_input >= total_output + fee {
+                debug!(
+                    "replace_by_fee: no change output, fee={}",
+                    total_input - total_output
+                );
+                change_index = None;
+                break;
+            }
+            let utxos = utxo_selector
+                .select_utxos(total_output + fee - total_input)
+                .await?;
+            for utxo in utxos {
+                let owner = utxo.owner_bitcoin_address().ok_or_else(|| {
+                    anyhow!("Can not recognize the owner of UTXO {}", utxo.outpoint())
+                })?;
+                inputs.push((tx.input.len(), owner, utxo.amount()));
+                tx.input.push(Self::utxo_to_txin(&utxo));
+                total_input += utxo.amount();
+            }
+        }
+        debug!(
+            "replace_by_fee: fee {} -> {}",
+            replaced_fee,
+            total_input - tx.output.iter().map(|output| output.value).sum::<Amount>()
+        );
+        Ok((tx, inputs, change_index))
+    }
+
+    /// Bump the fee of the parent transaction via child-pays-for-parent.
+    /// The child spends the change output of the parent, and pays the fee to make the
+    /// fee rate of the package reach the target fee rate.
+    pub async fn build_cpfp(self, parent: Transaction) -> Result<Psbt> {
+        let input_values = self
+            .prevouts(None, &parent)
+            .await?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        let (tx, change_value) = Self::child_pays_for_parent(
+            &parent,
+            &input_values,
+            self.fee_rate,
+            self.lock_time.unwrap_or(LockTime::ZERO),
+            &self.change_address,
+            self.change_policy,
+        )?;
+
+        let mut psbt = Psbt::from_unsigned_tx(tx)?;
+        self.fill_psbt_inputs(
+            &mut psbt,
+            vec![(
+                0,
+                BitcoinAddress::from(self.change_address.clone()),
+                change_value,
+            )],
+        )
+        .await?;
         Ok(psbt)
     }
 
+    /// Build the unsigned child transaction which spends the change output of the parent,
+    /// and return it with the value of the spent change output.
+    /// The input values are the values of the UTXOs spent by the parent.
+    fn child_pays_for_parent(
+        parent: &Transaction,
+        input_values: &[Amount],
+        fee_rate: FeeRate,
+        lock_time: LockTime,
+        change_address: &Address,
+        change_policy: ChangePolicy,
+    ) -> Result<(Transaction, Amount)> {
+        let parent_txid = parent.compute_txid();
+        let change_script = change_address.script_pubkey();
+        let (vout, parent_output) = parent
+            .output
+            .iter()
+            .enumerate()
+            .rfind(|(_, output)| output.script_pubkey == change_script)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Transaction {} has no output to the change address {}",
+                    parent_txid,
+                    change_address
+                )
+            })?;
+        let parent_fee = input_values
+            .iter()
+            .copied()
+            .sum::<Amount>()
+            .checked_sub(parent.output.iter().map(|output| output.value).sum())
+            .ok_or_else(|| {
+                anyhow!(
+                    "The outputs of transaction {} exceed the inputs",
+                    parent_txid
+                )
+            })?;
+
+        let mut tx = Transaction {
+            version: Version::TWO,
+            lock_time,
+            input: vec![TxIn {
+                previous_output: OutPoint {
+                    txid: parent_txid,
+                    vout: vout as u32,
+                },
+                script_sig: ScriptBuf::default(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: Witness::default(),
+            }],
+            output: vec![TxOut {
+                value: Amount::ZERO,
+                script_pubkey: change_script.clone(),
+            }],
+        };
+        let parent_vsize = Self::estimate_signed_vsize(parent);
+        let child_vsize = Self::estimate_signed_vsize(&tx);
+        let package_fee = fee_rate
+            .fee_vb((parent_vsize + child_vsize) as u64)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", fee_rate))?;
+        let child_fee = package_fee
+            .checked_sub(parent_fee)
+            .filter(|fee| *fee > Amount::ZERO)
+            .ok_or_else(|| {
+                anyhow!(
+                    "The fee rate of transaction {} is already higher than {}",
+                    parent_txid,
+                    fee_rate
+                )
+            })?;
+        let min_child_fee = Self::INCREMENTAL_RELAY_FEE_RATE
+            .fee_vb(child_vsize as u64)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", fee_rate))?;
+        let child_fee = child_fee.max(min_child_fee);
+        let change = parent_output
+            .value
+            .checked_sub(child_fee)
+            .filter(|change| *change >= change_policy.change_threshold(&change_script))
+            .ok_or_else(|| {
+                anyhow!(
+                    "The change output {} of transaction {} is not enough to pay the fee {}",
+                    parent_output.value,
+                    parent_txid,
+                    child_fee
+                )
+            })?;
+        tx.output[0].value = change;
+        debug!(
+            "child_pays_for_parent: parent_vsize={}, parent_fee={}, child_vsize={}, child_fee={}",
+            parent_vsize, parent_fee, child_vsize, child_fee
+        );
+        Ok((tx, parent_output.value))
+    }
+
     fn utxo_to_txin(utxo: &UTXOObjectView) -> TxIn {
         TxIn {
             previous_output: utxo.outpoint().into(),
@@ -313,3 +788,361 @@ impl<'a> TransactionBuilder<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::bitcoin::utxo_selector::tests::{
+        test_address, test_client, test_outpoint, test_sender, test_utxo,
+    };
+    use bitcoin::hashes::Hash;
+    use bitcoin::{Network, WPubkeyHash};
+
+    fn test_recipient() -> Address {
+        test_address("bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3")
+    }
+
+    fn new_tx(
+        inputs: Vec<OutPoint>,
+        outputs: Vec<(&Address, u64)>,
+        sequence: Sequence,
+    ) -> Transaction {
+        Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: inputs
+                .into_iter()
+                .map(|previous_output| TxIn {
+                    previous_output,
+                    script_sig: ScriptBuf::new(),
+                    sequence,
+                    witness: Witness::default(),
+                })
+                .collect(),
+            output: outputs
+                .into_iter()
+                .map(|(address, value)| TxOut {
+                    value: Amount::from_sat(value),
+                    script_pubkey: address.script_pubkey(),
+                })
+                .collect(),
+        }
+    }
+
+    fn total_output(tx: &Transaction) -> Amount {
+        tx.output.iter().map(|output| output.value).sum()
+    }
+
+    fn assert_replacement_fee(
+        tx: &Transaction,
+        inputs: &[(usize, BitcoinAddress, Amount)],
+        fee_rate: FeeRate,
+        replaced_fee: Amount,
+    ) {
+        let fee = inputs.iter().map(|(_, _, value)| *value).sum::<Amount>() - total_output(tx);
+        let vsize = TransactionBuilder::estimate_signed_vsize(tx) as u64;
+        assert!(fee >= fee_rate.fee_vb(vsize).unwrap());
+        assert!(
+            fee >= replaced_fee
+                + TransactionBuilder::INCREMENTAL_RELAY_FEE_RATE
+                    .fee_vb(vsize)
+                    .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bump_fee_rbf_from_change() {
+        let sender = test_sender();
+        let recipient = test_recipient();
+        let fee_rate = FeeRate::from_sat_per_vb(10).unwrap();
+        let spent_outpoint = test_outpoint(1, 0);
+        let tx = new_tx(
+            vec![spent_outpoint],
+            vec![(&recipient, 50_000), (&sender, 49_800)],
+            Sequence::ENABLE_RBF_NO_LOCKTIME,
+        );
+        let mut utxo_selector = UTXOSelector::with_utxos(
+            test_client().await,
+            sender.clone(),
+            vec![
+                test_utxo(spent_outpoint, 100_000, &sender),
+                test_utxo(test_outpoint(2, 0), 30_000, &sender),
+            ],
+        );
+
+        let (new_tx, inputs, change_index) = TransactionBuilder::replace_by_fee(
+            &mut utxo_selector,
+            tx,
+            vec![(
+                BitcoinAddress::from(sender.clone()),
+                Amount::from_sat(100_000),
+            )],
+            Some(1),
+            fee_rate,
+            &sender,
+            ChangePolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        // The original input is kept and filled with its spent UTXO
+        assert_eq!(new_tx.input.len(), 1);
+        assert_eq!(new_tx.input[0].previous_output, spent_outpoint);
+        assert_eq!(
+            inputs,
+            vec![(
+                0,
+                BitcoinAddress::from(sender.clone()),
+                Amount::from_sat(100_000)
+            )]
+        );
+        assert_eq!(new_tx.output.len(), 2);
+        assert_eq!(new_tx.output[0].value, Amount::from_sat(50_000));
+        assert_eq!(new_tx.output[0].script_pubkey, recipient.script_pubkey());
+        assert_eq!(new_tx.output[1].script_pubkey, sender.script_pubkey());
+        assert!(new_tx.output[1].value < Amount::from_sat(49_800));
+        assert_eq!(change_index, Some(1));
+        assert_replacement_fee(&new_tx, &inputs, fee_rate, Amount::from_sat(200));
+    }
+
+    #[tokio::test]
+    async fn test_bump_fee_rbf_with_new_inputs() {
+        let sender = test_sender();
+        let recipient = test_recipient();
+        let fee_rate = FeeRate::from_sat_per_vb(10).unwrap();
+        let spent_outpoint = test_outpoint(1, 0);
+        let new_outpoint = test_outpoint(2, 0);
+        // No change output to pay the fee
+        let tx = new_tx(
+            vec![spent_outpoint],
+            vec![(&recipient, 99_900)],
+            Sequence::ENABLE_RBF_NO_LOCKTIME,
+        );
+        let mut utxo_selector = UTXOSelector::with_utxos(
+            test_client().await,
+            sender.clone(),
+            vec![
+                test_utxo(spent_outpoint, 100_000, &sender),
+                test_utxo(new_outpoint, 30_000, &sender),
+            ],
+        );
+
+        let (new_tx, inputs, change_index) = TransactionBuilder::replace_by_fee(
+            &mut utxo_selector,
+            tx,
+            vec![(
+                BitcoinAddress::from(sender.clone()),
+                Amount::from_sat(100_000),
+            )],
+            None,
+            fee_rate,
+            &sender,
+            ChangePolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        // The UTXO spent by the original input is not selected again
+        assert_eq!(new_tx.input.len(), 2);
+        assert_eq!(new_tx.input[0].previous_output, spent_outpoint);
+        assert_eq!(new_tx.input[1].previous_output, new_outpoint);
+        assert_eq!(
+            inputs,
+            vec![
+                (
+                    0,
+                    BitcoinAddress::from(sender.clone()),
+                    Amount::from_sat(100_000)
+                ),
+                (
+                    1,
+                    BitcoinAddress::from(sender.clone()),
+                    Amount::from_sat(30_000)
+                ),
+            ]
+        );
+        assert_eq!(new_tx.output[0].value, Amount::from_sat(99_900));
+        assert_eq!(new_tx.output[1].script_pubkey, sender.script_pubkey());
+        assert_eq!(change_index, Some(1));
+        assert_replacement_fee(&new_tx, &inputs, fee_rate, Amount::from_sat(100));
+    }
+
+    #[tokio::test]
+    async fn test_bump_fee_rbf_requires_signal() {
+        let sender = test_sender();
+        let tx = new_tx(
+            vec![test_outpoint(1, 0)],
+            vec![(&test_recipient(), 99_900)],
+            Sequence::MAX,
+        );
+        let mut utxo_selector =
+            UTXOSelector::with_utxos(test_client().await, sender.clone(), vec![]);
+        let result = TransactionBuilder::replace_by_fee(
+            &mut utxo_selector,
+            tx,
+            vec![(
+                BitcoinAddress::from(sender.clone()),
+                Amount::from_sat(100_000),
+            )],
+            None,
+            FeeRate::from_sat_per_vb(10).unwrap(),
+            &sender,
+            ChangePolicy::default(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rbf_change_output_index() {
+        let sender = test_sender();
+        let recipient = test_recipient();
+        let spent_outpoint = test_outpoint(1, 0);
+
+        // The only output paying to the change address
+        let psbt = Psbt::from_unsigned_tx(new_tx(
+            vec![spent_outpoint],
+            vec![(&sender, 49_800), (&recipient, 50_000)],
+            Sequence::ENABLE_RBF_NO_LOCKTIME,
+        ))
+        .unwrap();
+        assert_eq!(
+            TransactionBuilder::change_output_index(&psbt, &sender).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            TransactionBuilder::change_output_index(
+                &psbt,
+                &test_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq")
+            )
+            .unwrap(),
+            None
+        );
+
+        // The payment and the change are paid to the same address
+        let mut psbt = Psbt::from_unsigned_tx(new_tx(
+            vec![spent_outpoint],
+            vec![(&sender, 50_000), (&sender, 49_800)],
+            Sequence::ENABLE_RBF_NO_LOCKTIME,
+        ))
+        .unwrap();
+        assert!(TransactionBuilder::change_output_index(&psbt, &sender).is_err());
+
+        // The change output marked in the PSBT is identified
+        TransactionBuilder::mark_change_output(&mut psbt, 1);
+        assert_eq!(
+            TransactionBuilder::change_output_index(&psbt, &sender).unwrap(),
+            Some(1)
+        );
+        // The marked change output is preferred to the output paying to the change address
+        assert_eq!(
+            TransactionBuilder::change_output_index(&psbt, &recipient).unwrap(),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bump_fee_rbf_keeps_payment_to_change_address() {
+        let sender = test_sender();
+        let fee_rate = FeeRate::from_sat_per_vb(10).unwrap();
+        let spent_outpoint = test_outpoint(1, 0);
+        // The payment to the sender itself is followed by the change output
+        let tx = new_tx(
+            vec![spent_outpoint],
+            vec![(&sender, 50_000), (&sender, 49_800)],
+            Sequence::ENABLE_RBF_NO_LOCKTIME,
+        );
+        let mut utxo_selector = UTXOSelector::with_utxos(
+            test_client().await,
+            sender.clone(),
+            vec![test_utxo(spent_outpoint, 100_000, &sender)],
+        );
+
+        let (new_tx, inputs, change_index) = TransactionBuilder::replace_by_fee(
+            &mut utxo_selector,
+            tx,
+            vec![(
+                BitcoinAddress::from(sender.clone()),
+                Amount::from_sat(100_000),
+            )],
+            Some(1),
+            fee_rate,
+            &sender,
+            ChangePolicy::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(new_tx.output.len(), 2);
+        assert_eq!(new_tx.output[0].value, Amount::from_sat(50_000));
+        assert!(new_tx.output[1].value < Amount::from_sat(49_800));
+        assert_eq!(change_index, Some(1));
+        assert_replacement_fee(&new_tx, &inputs, fee_rate, Amount::from_sat(200));
+    }
+
+    #[test]
+    fn test_build_cpfp() {
+        let sender = test_sender();
+        let recipient = test_recipient();
+        let fee_rate = FeeRate::from_sat_per_vb(10).unwrap();
+        let parent = new_tx(
+            vec![test_outpoint(1, 0)],
+            vec![(&recipient, 50_000), (&sender, 49_900)],
+            Sequence::ENABLE_RBF_NO_LOCKTIME,
+        );
+        let input_values = vec![Amount::from_sat(100_000)];
+
+        let (child, change_value) = TransactionBuilder::child_pays_for_parent(
+            &parent,
+            &input_values,
+            fee_rate,
+            LockTime::ZERO,
+            &sender,
+            ChangePolicy::default(),
+        )
+        .unwrap();
+        assert_eq!(change_value, Amount::from_sat(49_900));
+        assert_eq!(child.input.len(), 1);
+        assert_eq!(
+            child.input[0].previous_output,
+            OutPoint {
+                txid: parent.compute_txid(),
+                vout: 1
+            }
+        );
+        assert_eq!(child.output.len(), 1);
+        assert_eq!(child.output[0].script_pubkey, sender.script_pubkey());
+        // The package of the parent and the child pays the target fee rate
+        let package_fee = Amount::from_sat(100) + change_value - child.output[0].value;
+        let package_vsize = TransactionBuilder::estimate_signed_vsize(&parent)
+            + TransactionBuilder::estimate_signed_vsize(&child);
+        assert!(package_fee >= fee_rate.fee_vb(package_vsize as u64).unwrap());
+
+        // The parent already pays the target fee rate
+        assert!(TransactionBuilder::child_pays_for_parent(
+            &parent,
+            &[Amount::from_sat(120_000)],
+            fee_rate,
+            LockTime::ZERO,
+            &sender,
+            ChangePolicy::default(),
+        )
+        .is_err());
+
+        // The parent has no output to the change address
+        let other_address = Address::from_script(
+            &ScriptBuf::new_p2wpkh(&WPubkeyHash::from_byte_array([7u8; 20])),
+            Network::Bitcoin,
+        )
+        .unwrap();
+        assert!(TransactionBuilder::child_pays_for_parent(
+            &parent,
+            &input_values,
+            fee_rate,
+            LockTime::ZERO,
+            &other_address,
+            ChangePolicy::default(),
+        )
+        .is_err());
+    }
+}
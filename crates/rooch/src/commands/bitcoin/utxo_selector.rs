@@ -1,19 +1,23 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use anyhow::{bail, Result};
 use bitcoin::{Address, Amount};
 use moveos_types::moveos_std::object::{ObjectID, GENESIS_STATE_ROOT};
 use rooch_rpc_api::jsonrpc_types::{
+    btc::ord::{InscriptionFilterView, InscriptionObjectView},
     btc::utxo::{UTXOFilterView, UTXOObjectView, UTXOStateView},
     IndexerStateIDView,
 };
 use rooch_rpc_client::Client;
+use rooch_types::address::BitcoinAddress;
 use rooch_types::bitcoin::{types::OutPoint, utxo::derive_utxo_id};
 use tracing::debug;
 
+use super::coin_selection::{select_coins, CoinSelectionStrategy, SelectionParams};
+
 // Use retry configuration and functions from mod.rs
 use super::{is_rate_limit_error, retry_rpc_call, MAX_RETRIES, RETRY_DELAY};
 
@@ -25,6 +29,10 @@ pub struct UTXOSelector {
     loaded_page: Option<(Option<IndexerStateIDView>, bool)>,
     candidate_utxos: VecDeque<UTXOObjectView>,
     skip_seal_check: bool,
+    strategy: CoinSelectionStrategy,
+    selection_params: SelectionParams,
+    /// The UTXOs which must not be spent, such as the UTXOs carrying inscriptions
+    excluded_utxos: HashSet<ObjectID>,
 }
 
 impl UTXOSelector {
@@ -41,6 +49,9 @@ impl UTXOSelector {
             loaded_page: None,
             candidate_utxos: VecDeque::new(),
             skip_seal_check,
+            strategy: CoinSelectionStrategy::default(),
+            selection_params: SelectionParams::default(),
+            excluded_utxos: HashSet::new(),
         };
         selector.load_specific_utxos().await?;
         Ok(selector)
@@ -63,7 +74,106 @@ impl UTXOSelector {
             loaded_page: Some((None, false)), // Mark as no more pages to load
             candidate_utxos,
             skip_seal_check: true, // Already checked when loading
+            strategy: CoinSelectionStrategy::default(),
+            selection_params: SelectionParams::default(),
+            excluded_utxos: HashSet::new(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: CoinSelectionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Update the fee rate and change cost used to calculate the effective value of the UTXOs
+    pub fn set_selection_params(&mut self, selection_params: SelectionParams) {
+        self.selection_params = selection_params;
+    }
+
+    /// Never select the given UTXOs
+    pub fn exclude_utxos(&mut self, utxo_ids: impl IntoIterator<Item = ObjectID>) {
+        self.excluded_utxos.extend(utxo_ids);
+        let excluded_utxos = &self.excluded_utxos;
+        self.candidate_utxos
+            .retain(|utxo| !excluded_utxos.contains(&utxo.metadata.id));
+    }
+
+    /// Never select the UTXOs carrying inscriptions, the inscriptions are loaded via `query_inscriptions`.
+    /// Fail if any of the specific UTXOs carries inscriptions.
+    pub async fn protect_inscriptions(&mut self) -> Result<()> {
+        // The specific UTXOs may be owned by other addresses, such as a multisign account
+        let mut owners = vec![BitcoinAddress::from(self.sender.clone())];
+        for owner in self
+            .candidate_utxos
+            .iter()
+            .filter_map(|utxo| utxo.owner_bitcoin_address())
+        {
+            if !owners.contains(&owner) {
+                owners.push(owner);
+            }
+        }
+
+        let mut inscription_outpoints = vec![];
+        for owner in owners {
+            for inscription in self.load_inscriptions(owner).await? {
+                inscription_outpoints.push(inscription.location().outpoint);
+            }
+        }
+        self.exclude_inscription_outpoints(inscription_outpoints)
+    }
+
+    /// Never select the UTXOs at the given inscription locations, even if the seal check is skipped.
+    /// Fail if any of the specific UTXOs is one of them.
+    fn exclude_inscription_outpoints(
+        &mut self,
+        inscription_outpoints: impl IntoIterator<Item = OutPoint>,
+    ) -> Result<()> {
+        let inscription_utxos = inscription_outpoints
+            .into_iter()
+            .map(|outpoint| derive_utxo_id(&outpoint))
+            .collect::<HashSet<_>>();
+        debug!(
+            "protect_inscriptions: {} UTXOs carry inscriptions",
+            inscription_utxos.len()
+        );
+
+        if let Some(utxo_id) = self
+            .specific_utxos
+            .iter()
+            .find(|utxo_id| inscription_utxos.contains(utxo_id))
+        {
+            bail!(
+                "UTXO {} carries inscriptions, it can not be spent when the inscriptions are protected",
+                utxo_id
+            );
+        }
+        self.exclude_utxos(inscription_utxos);
+        Ok(())
+    }
+
+    async fn load_inscriptions(&self, owner: BitcoinAddress) -> Result<Vec<InscriptionObjectView>> {
+        let mut inscriptions = vec![];
+        let mut cursor = None;
+        loop {
+            let page = retry_rpc_call(|| async {
+                self.client
+                    .rooch
+                    .query_inscriptions(
+                        InscriptionFilterView::Owner(owner.clone().into()),
+                        cursor.clone().map(Into::into),
+                        None,
+                        None,
+                    )
+                    .await
+            })
+            .await?;
+            inscriptions.extend(page.data.into_iter().map(InscriptionObjectView::from));
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
         }
+        Ok(inscriptions)
     }
 
     async fn load_specific_utxos(&mut self) -> Result<()> {
@@ -135,6 +245,10 @@ impl UTXOSelector {
                         if !self.skip_seal_check && skip_utxo(&utxo_view, minimal_non_dust) {
                             continue;
                         }
+                        if self.excluded_utxos.contains(&utxo_view.metadata.id) {
+                            debug!("UTXO {} is excluded, skip.", utxo.outpoint());
+                            continue;
+                        }
                         if utxo_view.metadata.owner_bitcoin_address.is_none() {
                             debug!(
                                 "Can not recognize the owner of UTXO {}, metadata: {:?}, skip.",
@@ -188,6 +302,9 @@ impl UTXOSelector {
     }
 
     pub async fn select_utxos(&mut self, expected_amount: Amount) -> Result<Vec<UTXOObjectView>> {
+        if self.strategy != CoinSelectionStrategy::OldestFirst {
+            return self.select_utxos_with_strategy(expected_amount).await;
+        }
         let mut utxos = vec![];
         let mut total_input = Amount::from_sat(0);
         let mut iteration_count = 0;
@@ -226,6 +343,55 @@ impl UTXOSelector {
         Ok(utxos)
     }
 
+    /// Select the UTXOs from all the candidates, the effective values of the selected UTXOs cover the expected amount
+    async fn select_utxos_with_strategy(
+        &mut self,
+        expected_amount: Amount,
+    ) -> Result<Vec<UTXOObjectView>> {
+        while !matches!(self.loaded_page, Some((_, false))) {
+            self.load_utxos().await?;
+        }
+        // The oldest UTXO is at the back of the deque
+        let candidates = self.candidate_utxos.drain(..).rev().collect::<Vec<_>>();
+        let amounts = candidates
+            .iter()
+            .map(|utxo| utxo.amount())
+            .collect::<Vec<_>>();
+        let selected = select_coins(
+            self.strategy,
+            &amounts,
+            expected_amount,
+            &self.selection_params,
+        )
+        .map(|selected| selected.into_iter().collect::<HashSet<_>>());
+
+        let mut utxos = vec![];
+        for (idx, utxo) in candidates.into_iter().enumerate() {
+            if selected
+                .as_ref()
+                .is_some_and(|selected| selected.contains(&idx))
+            {
+                utxos.push(utxo);
+            } else {
+                self.candidate_utxos.push_front(utxo);
+            }
+        }
+        if selected.is_none() {
+            bail!("not enough BTC funds");
+        }
+        debug!(
+            "select_utxos: {:?} selected {} UTXOs totaling {} satoshi",
+            self.strategy,
+            utxos.len(),
+            utxos
+                .iter()
+                .map(|utxo| utxo.amount())
+                .sum::<Amount>()
+                .to_sat()
+        );
+        Ok(utxos)
+    }
+
     /// Load all UTXOs for the sender address and return them
     /// This is used when we need to know the total count of UTXOs before building transactions
     pub async fn load_all_utxos(&mut self) -> Result<Vec<UTXOObjectView>> {
@@ -293,3 +459,114 @@ fn skip_utxo(utxo_state_view: &UTXOStateView, minimal_non_dust: Amount) -> bool
     }
     false
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use bitcoin::hashes::Hash;
+    use bitcoin::Txid;
+    use clap::ValueEnum;
+    use moveos_types::moveos_std::object::ObjectMeta;
+    use moveos_types::state::MoveType;
+    use rooch_rpc_api::jsonrpc_types::btc::utxo::UTXOView;
+    use rooch_rpc_api::jsonrpc_types::ObjectMetaView;
+    use rooch_rpc_client::ClientBuilder;
+    use rooch_types::bitcoin::utxo::UTXO;
+    use rooch_types::into_address::IntoAddress;
+    use std::str::FromStr;
+
+    pub(crate) fn test_address(address: &str) -> Address {
+        Address::from_str(address).unwrap().assume_checked()
+    }
+
+    pub(crate) fn test_sender() -> Address {
+        test_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+    }
+
+    /// The client never connects to the server, all the UTXOs are pre-loaded
+    pub(crate) async fn test_client() -> Client {
+        ClientBuilder::default()
+            .build("http://127.0.0.1:6767")
+            .await
+            .unwrap()
+    }
+
+    pub(crate) fn test_outpoint(seed: u8, vout: u32) -> bitcoin::OutPoint {
+        bitcoin::OutPoint {
+            txid: Txid::from_byte_array([seed; 32]),
+            vout,
+        }
+    }
+
+    pub(crate) fn test_utxo(
+        outpoint: bitcoin::OutPoint,
+        value: u64,
+        owner: &Address,
+    ) -> UTXOObjectView {
+        let utxo = UTXO::new(
+            outpoint.txid.into_address(),
+            outpoint.vout,
+            value,
+            Default::default(),
+        );
+        let metadata =
+            ObjectMetaView::from(ObjectMeta::genesis_meta(utxo.object_id(), UTXO::type_tag()))
+                .with_owner_bitcoin_address(Some(BitcoinAddress::from(owner.clone()).to_string()));
+        UTXOObjectView {
+            metadata,
+            value: UTXOView::try_new_from_utxo(utxo).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_protect_inscriptions() {
+        let sender = test_sender();
+        let inscription_outpoint = test_outpoint(1, 0);
+        let utxos = vec![
+            test_utxo(inscription_outpoint, 50_000, &sender),
+            test_utxo(test_outpoint(2, 0), 10_000, &sender),
+            test_utxo(test_outpoint(3, 1), 30_000, &sender),
+        ];
+        let inscription_utxo_id = derive_utxo_id(&inscription_outpoint.into());
+
+        for strategy in CoinSelectionStrategy::value_variants() {
+            // The pre-loaded UTXOs skip the seal check, the inscriptions are still protected
+            let mut selector =
+                UTXOSelector::with_utxos(test_client().await, sender.clone(), utxos.clone())
+                    .with_strategy(*strategy);
+            selector
+                .exclude_inscription_outpoints(vec![inscription_outpoint.into()])
+                .unwrap();
+            let selected = selector
+                .select_utxos(Amount::from_sat(35_000))
+                .await
+                .unwrap();
+            assert!(
+                selected
+                    .iter()
+                    .all(|utxo| utxo.metadata.id != inscription_utxo_id),
+                "{:?} selected the inscription UTXO",
+                strategy
+            );
+
+            // Only the inscription UTXO can cover the amount
+            let mut selector =
+                UTXOSelector::with_utxos(test_client().await, sender.clone(), utxos.clone())
+                    .with_strategy(*strategy);
+            selector
+                .exclude_inscription_outpoints(vec![inscription_outpoint.into()])
+                .unwrap();
+            assert!(selector
+                .select_utxos(Amount::from_sat(40_001))
+                .await
+                .is_err());
+        }
+
+        // The specific UTXOs carrying inscriptions can not be spent
+        let mut selector = UTXOSelector::with_utxos(test_client().await, sender, utxos);
+        selector.specific_utxos = vec![inscription_utxo_id];
+        assert!(selector
+            .exclude_inscription_outpoints(vec![inscription_outpoint.into()])
+            .is_err());
+    }
+}
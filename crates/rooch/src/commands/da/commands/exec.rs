@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_types::WalletContextOptions;
+use crate::commands::da::commands::follower::{FollowerServer, DEFAULT_FOLLOWER_RPC_ADDR};
 use crate::commands::da::commands::{
     build_rooch_db, LedgerTxGetter, SequencedTxStore, StateRootFetcher, TxMetaStore,
 };
@@ -14,6 +15,7 @@ use clap::Parser;
 use coerce::actor::system::ActorSystem;
 use coerce::actor::IntoActor;
 use hdrhistogram::Histogram;
+use jsonrpsee::server::ServerHandle;
 use metrics::RegistryService;
 use moveos_common::utils::to_bytes;
 use moveos_eventbus::bus::EventBus;
@@ -43,6 +45,7 @@ use rooch_types::transaction::{
     L1BlockWithBody, LedgerTransaction, LedgerTxData, TransactionSequenceInfo,
 };
 use std::cmp::{max, min, PartialEq};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
@@ -64,7 +67,7 @@ pub struct ExecCommand {
     #[clap(
         long = "mode",
         default_value = "sync",
-        help = "Execution mode: exec, seq, all, sync, sync-exec. Default is sync"
+        help = "Execution mode: exec, seq, all, sync, sync-exec, follow. Default is sync"
     )]
     pub mode: ExecMode,
     #[clap(long = "segment-dir")]
@@ -99,6 +102,11 @@ pub struct ExecCommand {
     pub max_block_number: Option<u128>,
     #[clap(long = "bypass-verify", help = "bypass verification of state root")]
     pub bypass_verify: bool,
+    #[clap(
+        long = "follower-rpc-addr",
+        help = "Address of the read-only JSON-RPC server in `mode=follow`, default is 127.0.0.1:6768"
+    )]
+    pub follower_rpc_addr: Option<SocketAddr>,
 
     #[clap(long = "btc-rpc-url")]
     pub btc_rpc_url: String,
@@ -144,6 +152,12 @@ pub enum ExecMode {
     Sync,
     /// Sync from DA automatically and `Exec` mode
     SyncExec,
+    /// Follow DA as a long-running replica node: `Sync` mode without trusting RoochNetwork RPC,
+    /// every batch is verified by its signatures and tx accumulator, and the executed states are
+    /// served by a read-only JSON-RPC server.
+    /// DA batches don't record state roots, so the execution results are NOT verified against
+    /// the sequencer's state roots, only against the local exp-root file if it has any
+    Follow,
 }
 
 impl PartialEq for ExecMode {
@@ -160,9 +174,14 @@ impl ExecMode {
             ExecMode::All => 0b11,
             ExecMode::Sync => 0b111,
             ExecMode::SyncExec => 0b110,
+            ExecMode::Follow => 0b1111,
         }
     }
 
+    pub fn need_follow(&self) -> bool {
+        self.as_bits() & 0b1000 != 0
+    }
+
     pub fn need_sync(&self) -> bool {
         self.as_bits() & 0b100 != 0
     }
@@ -186,6 +205,8 @@ impl ExecMode {
             ExecMode::All => "state+accumulator root",
             ExecMode::Sync => "state+accumulator root",
             ExecMode::SyncExec => "state root",
+            // DA records no state roots, state roots are only verified against the local exp-root file
+            ExecMode::Follow => "accumulator root",
         };
         if bypass_verify {
             if raw_targets == "state root" {
@@ -292,14 +313,26 @@ impl ExecCommand {
         )
         .await?;
 
-        let exp_roots = tx_meta_store.get_exp_roots();
-        let client = self.context_options.build()?.get_client().await?;
-        let state_root_fetcher =
-            StateRootFetcher::new(client, exp_roots.clone(), tx_anomalies.clone());
+        // follower never requests RoochNetwork RPC, DA is the only source of truth.
+        // DA has no state roots, so follow mode can't verify the state root of each block
+        let state_root_fetcher = if self.mode.need_follow() {
+            None
+        } else {
+            let exp_roots = tx_meta_store.get_exp_roots();
+            let client = self.context_options.build()?.get_client().await?;
+            Some(StateRootFetcher::new(
+                client,
+                exp_roots.clone(),
+                tx_anomalies.clone(),
+            ))
+        };
 
         let ledger_tx_loader = if self.mode.need_sync() {
+            let open_da_path = self.open_da_path.clone().ok_or_else(|| {
+                anyhow::anyhow!("`--open-da` is required in mode: {:?}", self.mode)
+            })?;
             LedgerTxGetter::new_with_auto_sync(
-                self.open_da_path.clone().unwrap(),
+                open_da_path,
                 self.segment_dir.clone(),
                 shutdown_signal,
             )?
@@ -328,6 +361,7 @@ impl ExecCommand {
             tx_anomalies,
             state_root_fetcher,
             check_l1_tx_executed_start_from,
+            follower_rpc_addr: self.follower_rpc_addr,
         })
     }
 }
@@ -352,10 +386,13 @@ struct ExecInner {
     done: Arc<AtomicU64>,
     executed_tx_order: Arc<AtomicU64>,
 
-    state_root_fetcher: StateRootFetcher,
+    // None in follow mode
+    state_root_fetcher: Option<StateRootFetcher>,
 
     tx_anomalies: Option<TxAnomalies>,
     check_l1_tx_executed_start_from: u64,
+
+    follower_rpc_addr: Option<SocketAddr>,
 }
 
 struct ExecMsg {
@@ -419,9 +456,30 @@ impl ExecInner {
         }
     }
 
+    async fn start_follower_rpc_server(&self) -> anyhow::Result<ServerHandle> {
+        let addr = match self.follower_rpc_addr {
+            Some(addr) => addr,
+            None => DEFAULT_FOLLOWER_RPC_ADDR.parse()?,
+        };
+        FollowerServer::new(
+            self.executor.clone(),
+            self.produced.clone(),
+            self.done.clone(),
+            self.executed_tx_order.clone(),
+        )
+        .start(addr)
+        .await
+    }
+
     async fn run(&self, shutdown_signal: watch::Receiver<()>) -> anyhow::Result<()> {
         self.start_logging_task(shutdown_signal.clone());
 
+        let follower_rpc_handle = if self.mode.need_follow() {
+            Some(self.start_follower_rpc_server().await?)
+        } else {
+            None
+        };
+
         // larger buffer size to avoid rx starving caused by consumer has to access disks and request btc block.
         // after consumer load data(ledger_tx) from disk/btc client, burst to executor, need large buffer to avoid blocking.
         // 16384 is a magic number, it's a trade-off between memory usage and performance. (usually tx count inside a block is under 8192, MAX_TXS_PER_BLOCK_IN_FIX)
@@ -429,7 +487,12 @@ impl ExecInner {
         let producer = self.produce_tx(tx, shutdown_signal);
         let consumer = self.consume_tx(rx);
 
-        self.join_producer_and_consumer(producer, consumer).await
+        let result = self.join_producer_and_consumer(producer, consumer).await;
+        if let Some(handle) = follower_rpc_handle {
+            let _ = handle.stop();
+            handle.stopped().await;
+        }
+        result
     }
 
     fn update_startup_info_after_rollback(
@@ -592,7 +655,9 @@ impl ExecInner {
             }
             let tx_list = tx_list.unwrap();
             let last_tx_order_in_list = tx_list.last().map(|tx| tx.sequence_info.tx_order).unwrap();
-            self.state_root_fetcher.fetch_and_add(last_tx_order_in_list).await?;
+            if let Some(state_root_fetcher) = &self.state_root_fetcher {
+                state_root_fetcher.fetch_and_add(last_tx_order_in_list).await?;
+            }
             for ledger_tx in tx_list {
                 let tx_order = ledger_tx.sequence_info.tx_order;
                 if tx_order > max_verified_tx_order && !self.mode.need_sync() {
@@ -895,9 +960,11 @@ impl ExecInner {
         match exp_state_root {
             Some(expected_root) => {
                 if root.state_root.unwrap() != expected_root {
-                    if let Some(last_eq_value) = last_eq_tx_order {
+                    if let (Some(last_eq_value), Some(state_root_fetcher)) =
+                        (last_eq_tx_order.as_ref(), &self.state_root_fetcher)
+                    {
                         let mid_tx_order = (*last_eq_value + tx_order) / 2;
-                        self.state_root_fetcher.fetch_and_add(mid_tx_order).await?;
+                        state_root_fetcher.fetch_and_add(mid_tx_order).await?;
                         info!("state root of tx_order: {} fetched, it's in the middle of last_eq_tx_order: {} and first not_eq_tx_order: {}", mid_tx_order, *last_eq_value, tx_order);
                     }
 
@@ -919,7 +986,7 @@ impl ExecInner {
     }
 }
 
-async fn build_btc_client_proxy(
+pub(crate) async fn build_btc_client_proxy(
     btc_rpc_url: String,
     btc_rpc_user_name: String,
     btc_rpc_password: String,
@@ -1032,6 +1099,15 @@ mod tests {
         assert!(mode.need_exec());
         assert!(!mode.need_seq());
         assert!(!mode.need_all());
-        assert!(mode.need_sync())
+        assert!(mode.need_sync());
+        assert!(!mode.need_follow());
+
+        let mode = ExecMode::Follow;
+        assert!(mode.need_exec());
+        assert!(mode.need_seq());
+        assert!(mode.need_all());
+        assert!(mode.need_sync());
+        assert!(mode.need_follow());
+        assert_ne!(mode, ExecMode::Sync);
     }
 }
@@ -0,0 +1,182 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use jsonrpsee::core::async_trait;
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::server::{Server, ServerHandle};
+use moveos_types::moveos_std::object::ObjectID;
+use moveos_types::state::FieldKey;
+use rooch_executor::proxy::ExecutorProxy;
+use rooch_rpc_api::jsonrpc_types::{
+    AccessPathView, AnnotatedFunctionResultView, FieldKeyView, FunctionCallView, H256View,
+    ObjectIDView, ObjectStateView, StateOptions, StateProofView, StrView,
+};
+use rooch_rpc_api::RpcResult;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) const DEFAULT_FOLLOWER_RPC_ADDR: &str = "127.0.0.1:6768";
+
+/// The progress of a follower node replaying transactions from DA.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FollowerStatusView {
+    /// The number of transactions loaded from DA.
+    pub produced: StrView<u64>,
+    /// The number of transactions executed.
+    pub done: StrView<u64>,
+    /// The max tx_order executed.
+    pub executed_tx_order: StrView<u64>,
+    /// The state root after the max executed tx_order.
+    pub state_root: H256View,
+    /// The global size after the max executed tx_order.
+    pub size: StrView<u64>,
+}
+
+/// Read-only RPC served by a follower node, the states are all executed from DA locally.
+#[rpc(server, namespace = "rooch")]
+#[async_trait]
+pub trait FollowerAPI {
+    #[method(name = "getFollowerStatus")]
+    async fn get_follower_status(&self) -> RpcResult<FollowerStatusView>;
+
+    /// Execute a read-only function call at the latest executed state
+    #[method(name = "executeViewFunction")]
+    async fn execute_view_function(
+        &self,
+        function_call: FunctionCallView,
+    ) -> RpcResult<AnnotatedFunctionResultView>;
+
+    /// Get the states by access_path
+    #[method(name = "getStates")]
+    async fn get_states(
+        &self,
+        access_path: AccessPathView,
+        state_option: Option<StateOptions>,
+    ) -> RpcResult<Vec<Option<ObjectStateView>>>;
+
+    /// Get the state of the field with its proof against the state root
+    #[method(name = "getStateProof")]
+    async fn get_state_proof(
+        &self,
+        object_id: ObjectIDView,
+        field_key: FieldKeyView,
+        state_root: Option<H256View>,
+    ) -> RpcResult<StateProofView>;
+}
+
+pub(crate) struct FollowerServer {
+    executor: ExecutorProxy,
+    produced: Arc<AtomicU64>,
+    done: Arc<AtomicU64>,
+    executed_tx_order: Arc<AtomicU64>,
+}
+
+impl FollowerServer {
+    pub(crate) fn new(
+        executor: ExecutorProxy,
+        produced: Arc<AtomicU64>,
+        done: Arc<AtomicU64>,
+        executed_tx_order: Arc<AtomicU64>,
+    ) -> Self {
+        Self {
+            executor,
+            produced,
+            done,
+            executed_tx_order,
+        }
+    }
+
+    pub(crate) async fn start(self, addr: SocketAddr) -> anyhow::Result<ServerHandle> {
+        let server = Server::builder().build(addr).await?;
+        let local_addr = server.local_addr()?;
+        let handle = server.start(self.into_rpc());
+        info!(
+            "Follower read-only JSON-RPC server listening on {}",
+            local_addr
+        );
+        Ok(handle)
+    }
+
+    // the reader executor does not follow the executor automatically, sync it before reading
+    async fn sync_reader(&self) -> anyhow::Result<()> {
+        self.executor.sync_state().await
+    }
+}
+
+#[async_trait]
+impl FollowerAPIServer for FollowerServer {
+    async fn get_follower_status(&self) -> RpcResult<FollowerStatusView> {
+        let root = self.executor.get_root().await?;
+        Ok(FollowerStatusView {
+            produced: self.produced.load(Ordering::Relaxed).into(),
+            done: self.done.load(Ordering::Relaxed).into(),
+            executed_tx_order: self.executed_tx_order.load(Ordering::Relaxed).into(),
+            state_root: root.metadata.state_root().into(),
+            size: root.metadata.size.into(),
+        })
+    }
+
+    async fn execute_view_function(
+        &self,
+        function_call: FunctionCallView,
+    ) -> RpcResult<AnnotatedFunctionResultView> {
+        self.sync_reader().await?;
+        Ok(self
+            .executor
+            .execute_view_function(function_call.into(), None)
+            .await?
+            .into())
+    }
+
+    async fn get_states(
+        &self,
+        access_path: AccessPathView,
+        state_option: Option<StateOptions>,
+    ) -> RpcResult<Vec<Option<ObjectStateView>>> {
+        access_path.0.validate_max_object_ids()?;
+        self.sync_reader().await?;
+        let state_option = state_option.unwrap_or_default();
+        let state_root = state_option.state_root.map(|h256_view| h256_view.0);
+        let state_views = if state_option.decode {
+            self.executor
+                .get_annotated_states(access_path.into(), state_root)
+                .await?
+                .into_iter()
+                .map(|s| s.map(ObjectStateView::from))
+                .collect()
+        } else {
+            self.executor
+                .get_states(access_path.into(), state_root)
+                .await?
+                .into_iter()
+                .map(|s| s.map(ObjectStateView::from))
+                .collect()
+        };
+        Ok(state_views)
+    }
+
+    async fn get_state_proof(
+        &self,
+        object_id: ObjectIDView,
+        field_key: FieldKeyView,
+        state_root: Option<H256View>,
+    ) -> RpcResult<StateProofView> {
+        self.sync_reader().await?;
+        let object_id: ObjectID = object_id.into();
+        let field_key: FieldKey = field_key.into();
+        let (state_root, proof) = self
+            .executor
+            .get_state_proof(
+                object_id.clone(),
+                field_key,
+                state_root.map(|h256_view| h256_view.0),
+            )
+            .await?;
+        Ok(StateProofView::new(
+            state_root, object_id, field_key, proof,
+        )?)
+    }
+}
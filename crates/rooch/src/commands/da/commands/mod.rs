@@ -42,12 +42,14 @@ use tracing::{error, info, warn};
 pub mod accumulator_anomaly;
 pub mod exec;
 pub mod find_first;
+pub mod follower;
 pub mod index;
 pub mod namespace;
 pub mod pack;
 pub mod repair;
 pub mod unpack;
 pub mod verify;
+pub mod verify_block;
 
 const DEFAULT_MAX_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
 
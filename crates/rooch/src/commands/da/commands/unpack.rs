@@ -101,7 +101,7 @@ impl UnpackInner {
     }
 
     // unpack batches from segment_dir to batch_dir.
-    // warn: ChunkV0 only in present
+    // both ChunkV0 and ChunkV1 (decoded by its codec) are supported.
     fn unpack(&mut self, force: bool, unpack_chunk_id_opt: Option<u128>) -> anyhow::Result<()> {
         const TOP_N: usize = 20;
 
@@ -114,7 +114,7 @@ impl UnpackInner {
 
         for (chunk_id, segment_numbers) in &self.chunks {
             if self.unpacked.contains(chunk_id) {
-                // For ChunkV0, chunk_id is block_number
+                // For ChunkV0 and ChunkV1, chunk_id is block_number
                 continue;
             }
 
@@ -0,0 +1,350 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::commands::da::commands::exec::build_btc_client_proxy;
+use crate::commands::da::commands::{build_rooch_db, LedgerTxGetter};
+use crate::utils::derive_builtin_genesis_namespace;
+use bitcoin::hashes::Hash;
+use bitcoin_client::proxy::BitcoinClientProxy;
+use clap::Parser;
+use coerce::actor::system::ActorSystem;
+use moveos_store::transaction_store::TransactionStore as TxExecutionInfoStore;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::ObjectMeta;
+use rooch_anomalies::load_tx_anomalies;
+use rooch_config::R_OPT_NET_HELP;
+use rooch_executor::verifier::{
+    BlockVerifyResult, StateTransition, StateTransitionVerifier, TxVerifyResult,
+};
+use rooch_proposer::scc::StateCommitmentChain;
+use rooch_rpc_api::jsonrpc_types::{KeptVMStatusView, StateChangeSetView};
+use rooch_store::da_store::DAMetaStore;
+use rooch_store::state_store::StateStore;
+use rooch_store::transaction_store::TransactionStore;
+use rooch_types::bitcoin::types::Block as BitcoinBlock;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::rooch_network::{BuiltinChainID, RoochChainID};
+use rooch_types::transaction::{L1BlockWithBody, LedgerTxData};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Re-execute blocks from DA segments on top of the state root committed by the previous block,
+/// and report the first block whose state root diverges from the one committed by the proposer,
+/// or the first transaction whose state transition diverges from the recorded one.
+/// The database must contain the proposed blocks and their execution info (e.g. a synced full node).
+#[derive(Debug, Parser)]
+pub struct VerifyBlockCommand {
+    #[clap(long = "segment-dir")]
+    pub segment_dir: PathBuf,
+    #[clap(long = "start-block", help = "The first block number to verify")]
+    pub start_block: u128,
+    #[clap(
+        long = "end-block",
+        help = "The last block number to verify(inclusive), default is start-block"
+    )]
+    pub end_block: Option<u128>,
+    #[clap(
+        long = "output",
+        short = 'o',
+        help = "Path to write the JSON report, print to stdout if not set"
+    )]
+    pub output: Option<PathBuf>,
+
+    #[clap(
+        long = "btc-rpc-url",
+        help = "Bitcoin RPC for the bodies of L1 blocks, required if the blocks contain L1Block transactions"
+    )]
+    pub btc_rpc_url: Option<String>,
+    #[clap(long = "btc-rpc-user-name")]
+    pub btc_rpc_user_name: Option<String>,
+    #[clap(long = "btc-rpc-password")]
+    pub btc_rpc_password: Option<String>,
+    #[clap(long = "btc-local-block-store-dir")]
+    pub btc_local_block_store_dir: Option<PathBuf>,
+
+    #[clap(long = "data-dir", short = 'd')]
+    pub base_data_dir: PathBuf,
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: BuiltinChainID,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateTransitionView {
+    pub state_root: H256,
+    pub size: u64,
+    pub gas_used: u64,
+    pub status: KeptVMStatusView,
+}
+
+impl From<StateTransition> for StateTransitionView {
+    fn from(transition: StateTransition) -> Self {
+        Self {
+            state_root: transition.state_root,
+            size: transition.size,
+            gas_used: transition.gas_used,
+            status: transition.status.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxDivergence {
+    pub block_number: u128,
+    pub tx_order: u64,
+    pub tx_hash: H256,
+    /// None if the execution info of the transaction is not found in the database.
+    pub expected: Option<StateTransitionView>,
+    pub actual: StateTransitionView,
+    /// The state change set produced by the re-execution.
+    pub actual_state_change_set: StateChangeSetView,
+    /// The state change set recorded in the database.
+    pub expected_state_change_set: Option<StateChangeSetView>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockVerifyReport {
+    pub start_block: u128,
+    pub end_block: u128,
+    /// The state root committed by the block before start-block, the first transaction is executed on it.
+    pub pre_state_root: H256,
+    /// The number of re-executed transactions, including the diverging one.
+    pub verified_txs: u64,
+    /// The transactions skipped for having no execution info in RoochNetwork.
+    pub skipped_txs: u64,
+    /// The state root after the last re-executed transaction.
+    pub post_state_root: H256,
+    /// The first transaction diverging from the recorded state transition,
+    /// the verification stops at it.
+    pub first_divergence: Option<TxDivergence>,
+    /// The first block whose state root diverges from the committed one,
+    /// the verification stops at it.
+    pub first_block_divergence: Option<BlockVerifyResult>,
+}
+
+impl BlockVerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.first_divergence.is_none() && self.first_block_divergence.is_none()
+    }
+}
+
+impl VerifyBlockCommand {
+    pub async fn execute(self) -> RoochResult<String> {
+        let end_block = self.end_block.unwrap_or(self.start_block);
+        if end_block < self.start_block {
+            return Err(RoochError::CommandArgumentError(format!(
+                "end-block: {} is less than start-block: {}",
+                end_block, self.start_block
+            )));
+        }
+
+        let (_root, rooch_db) = build_rooch_db(
+            Some(self.base_data_dir.clone()),
+            Some(RoochChainID::Builtin(self.chain_id)),
+            false,
+            None,
+            None,
+        );
+        let (rooch_store, moveos_store) =
+            (rooch_db.rooch_store.clone(), rooch_db.moveos_store.clone());
+        let tx_anomalies = load_tx_anomalies(derive_builtin_genesis_namespace(self.chain_id)?)?;
+        let check_l1_tx_executed_start_from = tx_anomalies
+            .as_ref()
+            .and_then(|anomalies| anomalies.check_l1_tx_executed_start_from)
+            .unwrap_or(0);
+        let bitcoin_client_proxy = self.build_btc_client_proxy().await?;
+
+        let ledger_tx_getter = LedgerTxGetter::new(self.segment_dir.clone(), false)?;
+
+        // the committed blocks are read only, the SCC must not be repaired here
+        let scc = StateCommitmentChain::load(rooch_store.clone(), moveos_store.clone())?;
+        let pre_state = get_pre_state(&rooch_db, &scc, self.start_block)?;
+        let pre_state_root = pre_state.state_root();
+        info!(
+            "Start to verify from block: {}, pre state root: {:?}",
+            self.start_block, pre_state_root
+        );
+        let mut verifier =
+            StateTransitionVerifier::new(pre_state, moveos_store.clone(), rooch_store.clone())?;
+        let mut verified_txs = 0;
+        let mut skipped_txs = 0;
+        let mut first_divergence = None;
+        let mut first_block_divergence = None;
+
+        'blocks: for block_number in self.start_block..=end_block {
+            let tx_list = ledger_tx_getter
+                .load_ledger_tx_list(block_number, true, true)
+                .await?
+                .ok_or_else(|| {
+                    RoochError::CommandArgumentError(format!(
+                        "block: {} not found in segment-dir: {:?}",
+                        block_number, self.segment_dir
+                    ))
+                })?;
+            for mut ledger_tx in tx_list {
+                let tx_order = ledger_tx.sequence_info.tx_order;
+                let tx_hash = ledger_tx.tx_hash();
+                if let Some(tx_anomalies) = &tx_anomalies {
+                    if tx_anomalies.has_no_execution_info(&tx_hash) {
+                        skipped_txs += 1;
+                        continue;
+                    }
+                }
+
+                let l1_block_with_body = match &ledger_tx.data {
+                    LedgerTxData::L1Block(block) => {
+                        let bitcoin_client_proxy = bitcoin_client_proxy.as_ref().ok_or_else(|| {
+                            RoochError::CommandArgumentError(format!(
+                                "btc-rpc-url is required to verify L1Block transaction, tx_order: {}",
+                                tx_order
+                            ))
+                        })?;
+                        let block_hash = bitcoin::block::BlockHash::from_slice(&block.block_hash)?;
+                        let btc_block = bitcoin_client_proxy.get_block(block_hash).await?;
+                        let block_body = BitcoinBlock::from(btc_block);
+                        Some(L1BlockWithBody::new(block.clone(), block_body.encode()))
+                    }
+                    _ => None,
+                };
+
+                let bypass_l1_executed_check = tx_order < check_l1_tx_executed_start_from;
+                let result =
+                    verifier.verify_tx(ledger_tx, l1_block_with_body, bypass_l1_executed_check)?;
+                verified_txs += 1;
+                if !result.is_match() {
+                    first_divergence = Some(build_divergence(&rooch_db, block_number, result)?);
+                    break 'blocks;
+                }
+            }
+
+            let block_result = verifier.verify_block(&scc.get_block(block_number)?);
+            if !block_result.is_match() {
+                first_block_divergence = Some(block_result);
+                break;
+            }
+        }
+
+        let post_state_root = verifier.root().state_root();
+        let report = BlockVerifyReport {
+            start_block: self.start_block,
+            end_block,
+            pre_state_root,
+            verified_txs,
+            skipped_txs,
+            post_state_root,
+            first_divergence,
+            first_block_divergence,
+        };
+        if let Some(divergence) = &report.first_divergence {
+            info!(
+                "State transition diverges at block: {}, tx_order: {}, tx_hash: {:?}",
+                divergence.block_number, divergence.tx_order, divergence.tx_hash
+            );
+        }
+        if let Some(divergence) = &report.first_block_divergence {
+            info!(
+                "State root diverges from the committed one at block: {}, committed: {:?}, actual: {:?}",
+                divergence.block_number, divergence.committed_state_root, divergence.actual_state_root
+            );
+        }
+
+        let report_json = serde_json::to_string_pretty(&report)?;
+        match self.output {
+            Some(output) => {
+                fs::write(&output, &report_json)?;
+                Ok(format!(
+                    "Block verify report written to {:?}, ok: {}",
+                    output,
+                    report.is_ok()
+                ))
+            }
+            None => Ok(report_json),
+        }
+    }
+
+    async fn build_btc_client_proxy(&self) -> anyhow::Result<Option<BitcoinClientProxy>> {
+        match &self.btc_rpc_url {
+            Some(btc_rpc_url) => {
+                let actor_system = ActorSystem::global_system();
+                let proxy = build_btc_client_proxy(
+                    btc_rpc_url.clone(),
+                    self.btc_rpc_user_name.clone().unwrap_or_default(),
+                    self.btc_rpc_password.clone().unwrap_or_default(),
+                    self.btc_local_block_store_dir.clone(),
+                    &actor_system,
+                )
+                .await?;
+                Ok(Some(proxy))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+// the state committed by the block before `start_block`
+fn get_pre_state(
+    rooch_db: &rooch_db::RoochDB,
+    scc: &StateCommitmentChain,
+    start_block: u128,
+) -> anyhow::Result<ObjectMeta> {
+    let prev_block_number = start_block
+        .checked_sub(1)
+        .ok_or_else(|| anyhow::anyhow!("genesis block could not be re-executed from DA"))?;
+    let prev_block = scc.get_block(prev_block_number)?;
+    // the global size is not committed in the block, take it from the execution info
+    // of the last transaction of the block, which must have the committed state root
+    let prev_tx_order = rooch_db
+        .rooch_store
+        .get_block_state(prev_block_number)?
+        .block_range
+        .tx_order_end;
+    let prev_tx_hash = rooch_db
+        .rooch_store
+        .get_tx_hashes(vec![prev_tx_order])?
+        .pop()
+        .flatten()
+        .ok_or_else(|| anyhow::anyhow!("tx hash not found for tx_order: {}", prev_tx_order))?;
+    let prev_execution_info = rooch_db
+        .moveos_store
+        .get_tx_execution_info(prev_tx_hash)?
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "execution info not found for tx_order: {}, tx_hash: {:?}",
+                prev_tx_order,
+                prev_tx_hash
+            )
+        })?;
+    anyhow::ensure!(
+        prev_execution_info.state_root == prev_block.state_root,
+        "state root of tx_order: {} is {:?}, but block: {} committed {:?}",
+        prev_tx_order,
+        prev_execution_info.state_root,
+        prev_block_number,
+        prev_block.state_root
+    );
+    Ok(ObjectMeta::root_metadata(
+        prev_block.state_root,
+        prev_execution_info.size,
+    ))
+}
+
+fn build_divergence(
+    rooch_db: &rooch_db::RoochDB,
+    block_number: u128,
+    result: TxVerifyResult,
+) -> anyhow::Result<TxDivergence> {
+    let expected_state_change_set = rooch_db
+        .rooch_store
+        .get_state_change_set(result.tx_order)?
+        .map(|change_set_ext| StateChangeSetView::from(change_set_ext.state_change_set));
+    Ok(TxDivergence {
+        block_number,
+        tx_order: result.tx_order,
+        tx_hash: result.tx_hash,
+        expected: result.expected.map(Into::into),
+        actual: result.actual.into(),
+        actual_state_change_set: StateChangeSetView::from(result.state_change_set),
+        expected_state_change_set,
+    })
+}
@@ -13,6 +13,7 @@ use crate::commands::da::commands::pack::PackCommand;
 use crate::commands::da::commands::repair::RepairCommand;
 use crate::commands::da::commands::unpack::UnpackCommand;
 use crate::commands::da::commands::verify::VerifyCommand;
+use crate::commands::da::commands::verify_block::VerifyBlockCommand;
 use async_trait::async_trait;
 use clap::Parser;
 use rooch_types::error::RoochResult;
@@ -52,6 +53,7 @@ impl CommandAction<String> for DA {
                 find_first.execute().await?;
                 Ok("".to_owned())
             }
+            DACommand::VerifyBlock(verify_block) => verify_block.execute().await,
         }
     }
 }
@@ -68,4 +70,5 @@ pub enum DACommand {
     Repair(RepairCommand),
     AccumulatorAnomaly(AccumulatorAnomalyCommand),
     FindFirst(FindFirstCommand),
+    VerifyBlock(Box<VerifyBlockCommand>),
 }
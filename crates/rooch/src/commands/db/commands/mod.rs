@@ -39,6 +39,7 @@ pub mod rocksdb_stats;
 pub mod rollback;
 pub mod stat_changeset;
 pub mod state_prune;
+pub mod state_size_report;
 pub mod tx_accumulator_compact;
 pub mod verify_order;
 
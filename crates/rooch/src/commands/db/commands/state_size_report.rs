@@ -0,0 +1,132 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::CommandAction;
+use crate::utils::open_rooch_db_readonly;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use clap::Parser;
+use moveos_types::h256::H256;
+use rooch_pruner::historical_state::{HistoricalStateCollector, HistoricalStateConfig};
+use rooch_pruner::marker::estimate_bloom_parameters;
+use rooch_pruner::{BloomFilterMarker, StateAttributionReport, StateAttributor};
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::framework::chain_id::ChainID;
+use rooch_types::rooch_network::{BuiltinChainID, RoochChainID};
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::info;
+
+/// Report which object types, owners and dynamic field tables hold the state.
+///
+/// The live tree at the state root is walked and every node is attributed to the top-level
+/// object which holds it. With `--stale`, the nodes not reachable from the protected roots
+/// (the nodes GC would reclaim) are attributed to the object types of the old object versions.
+/// The output is JSON, so the growth could be tracked over time.
+#[derive(Debug, Parser)]
+pub struct StateSizeReportCommand {
+    /// Base data directory for the blockchain data
+    #[clap(long = "data-dir", short = 'd')]
+    pub base_data_dir: Option<PathBuf>,
+
+    /// Chain ID to specify which blockchain network
+    #[clap(long, short = 'n')]
+    pub chain_id: BuiltinChainID,
+
+    /// Target state root hash, default is the latest state root
+    #[clap(long)]
+    pub state_root: Option<String>,
+
+    /// The number of entries to keep for every ranking in the report
+    #[clap(long, default_value_t = 100)]
+    pub top_n: usize,
+
+    /// Also attribute the reclaimable stale nodes, this scans the whole state node store
+    #[clap(long)]
+    pub stale: bool,
+
+    /// Number of recent state roots to protect when attributing the stale nodes, same as GC
+    ///
+    /// Default: 0 (auto-detect based on network: Local=1, Dev=1000, Test=1000, Main=30000)
+    #[clap(long = "protected-roots-count", default_value_t = 0)]
+    pub protected_roots_count: usize,
+
+    /// Batch size for marking the protected roots
+    #[clap(long, default_value_t = 10000)]
+    pub batch_size: usize,
+
+    /// Target false positive rate of the marker, a false positive skips a live node
+    #[clap(long, default_value_t = 0.0001)]
+    pub marker_target_fp_rate: f64,
+
+    /// Write the report to the file instead of stdout
+    #[clap(long, short = 'o')]
+    pub output: Option<PathBuf>,
+}
+
+#[async_trait]
+impl CommandAction<String> for StateSizeReportCommand {
+    async fn execute(self) -> RoochResult<String> {
+        if self.batch_size == 0 {
+            return Err(RoochError::CommandArgumentError(
+                "Batch size must be greater than 0".to_string(),
+            ));
+        }
+        if !(self.marker_target_fp_rate > 0.0 && self.marker_target_fp_rate < 1.0) {
+            return Err(RoochError::CommandArgumentError(
+                "marker_target_fp_rate must be in (0, 1)".to_string(),
+            ));
+        }
+
+        let (root, rooch_db, _start_time) = open_rooch_db_readonly(
+            self.base_data_dir.clone(),
+            Some(RoochChainID::Builtin(self.chain_id)),
+        );
+        let state_root = match &self.state_root {
+            Some(state_root) => H256::from_str(state_root.strip_prefix("0x").unwrap_or(state_root))
+                .map_err(|e| anyhow!("invalid state_root hex: {}", e))?,
+            None => root.state_root(),
+        };
+
+        let attributor = StateAttributor::new(rooch_db.moveos_store.clone(), self.top_n);
+        let estimated_nodes = attributor.estimate_node_count().unwrap_or(1_000_000);
+        let (bloom_bits, bloom_hash_fns) =
+            estimate_bloom_parameters(estimated_nodes, self.marker_target_fp_rate);
+        info!(
+            "State size report at state_root {:?}, estimated nodes {}, marker bits {}",
+            state_root, estimated_nodes, bloom_bits
+        );
+        let marker = BloomFilterMarker::new(bloom_bits, bloom_hash_fns);
+
+        let live = attributor.attribute_live(state_root, &marker)?;
+        let stale = if self.stale {
+            let protected_roots_count = if self.protected_roots_count == 0 {
+                HistoricalStateConfig::new_with_network(&ChainID::from(self.chain_id))
+                    .protected_roots_count
+            } else {
+                self.protected_roots_count
+            };
+            let protected_roots = HistoricalStateCollector::new(
+                rooch_db.moveos_store.clone(),
+                rooch_db.rooch_store.clone(),
+                HistoricalStateConfig {
+                    protected_roots_count,
+                },
+            )
+            .collect_recent_state_roots()?;
+            Some(attributor.attribute_stale(protected_roots, &marker, self.batch_size)?)
+        } else {
+            None
+        };
+
+        let report = StateAttributionReport { live, stale };
+        let output = serde_json::to_string_pretty(&report)?;
+        match self.output {
+            Some(path) => {
+                std::fs::write(&path, &output)?;
+                Ok(format!("State size report written to {:?}", path))
+            }
+            None => Ok(output),
+        }
+    }
+}
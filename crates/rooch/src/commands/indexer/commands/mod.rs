@@ -12,6 +12,8 @@ use rooch_indexer::IndexerStore;
 use rooch_types::rooch_network::RoochChainID;
 
 pub mod bench;
+#[cfg(feature = "indexer-postgres")]
+pub mod migrate_to_postgres;
 pub mod rebuild;
 
 pub const BATCH_SIZE: usize = 5000;
@@ -26,11 +28,17 @@ fn init_indexer(
     let registry_service = RegistryService::default();
 
     let indexer_db_path = store_config.get_indexer_dir();
-    let indexer_store = IndexerStore::new(
+    let indexer_db_url = store_config.get_indexer_db_url();
+    let indexer_store = IndexerStore::new_with_db_url(
         indexer_db_path.clone(),
+        indexer_db_url,
+        &registry_service.default_registry(),
+    )?;
+    let indexer_reader = IndexerReader::new_with_db_url(
+        indexer_db_path,
+        indexer_db_url,
         &registry_service.default_registry(),
     )?;
-    let indexer_reader = IndexerReader::new(indexer_db_path, &registry_service.default_registry())?;
 
     Ok((indexer_store, indexer_reader))
 }
@@ -8,6 +8,8 @@ use rooch_types::error::RoochResult;
 
 use crate::cli_types::CommandAction;
 use crate::commands::indexer::commands::bench::BenchCommand;
+#[cfg(feature = "indexer-postgres")]
+use crate::commands::indexer::commands::migrate_to_postgres::MigrateToPostgresCommand;
 use crate::commands::indexer::commands::rebuild::RebuildCommand;
 
 pub mod commands;
@@ -29,6 +31,10 @@ impl CommandAction<String> for Indexer {
             IndexerCommand::Bench(bench) => bench.execute().await.map(|resp| {
                 serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
             }),
+            #[cfg(feature = "indexer-postgres")]
+            IndexerCommand::MigrateToPostgres(migrate) => migrate.execute().await.map(|resp| {
+                serde_json::to_string_pretty(&resp).expect("Failed to serialize response")
+            }),
         }
     }
 }
@@ -38,4 +44,6 @@ impl CommandAction<String> for Indexer {
 pub enum IndexerCommand {
     Rebuild(RebuildCommand),
     Bench(BenchCommand),
+    #[cfg(feature = "indexer-postgres")]
+    MigrateToPostgres(MigrateToPostgresCommand),
 }
@@ -0,0 +1,149 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bindgen::{self, BindgenLang};
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::CompiledModule;
+use move_cli::{base::reroot_path, Move};
+use move_core_types::identifier::Identifier;
+use moveos_types::access_path::AccessPath;
+use moveos_types::move_std::string::MoveString;
+use moveos_types::moveos_std::move_module::MoveModule;
+use moveos_types::state::ObjectState;
+use moveos_verifier::build::run_verifier;
+use rooch_rpc_api::jsonrpc_types::ModuleABIView;
+use rooch_types::address::ParsedAddress;
+use rooch_types::error::{RoochError, RoochResult};
+use std::collections::BTreeMap;
+use std::io::stderr;
+use std::path::PathBuf;
+
+/// Generate typed bindings for calling the Move modules.
+///
+/// The bindings are generated from the package at `path` by default, or from the on-chain
+/// modules with `--package-address` and `--module`.
+#[derive(Parser)]
+#[clap(name = "bindgen")]
+pub struct BindgenCommand {
+    /// The language of the bindings
+    #[clap(long, value_enum, default_value = "rust")]
+    lang: BindgenLang,
+
+    /// Generate from the modules published at the address instead of the local package
+    #[clap(long, requires = "modules")]
+    package_address: Option<ParsedAddress>,
+
+    /// The names of the on-chain modules to generate, used with `--package-address`
+    #[clap(long = "module", id = "modules", num_args = 1..)]
+    modules: Vec<String>,
+
+    /// Write the bindings to the file instead of stdout
+    #[clap(long, short = 'o')]
+    output: Option<PathBuf>,
+
+    /// Named addresses for the move binary
+    ///
+    /// Example: alice=0x1234, bob=default, alice2=alice
+    ///
+    /// Note: This will fail if there are duplicates in the Move.toml file remove those first.
+    #[clap(long, value_parser = crate::utils::parse_map::<String, String>, default_value = "")]
+    named_addresses: BTreeMap<String, String>,
+
+    #[clap(flatten)]
+    context_options: WalletContextOptions,
+
+    #[clap(flatten)]
+    move_args: Move,
+}
+
+impl BindgenCommand {
+    fn compile_package_modules(&self) -> RoochResult<Vec<CompiledModule>> {
+        let context = self.context_options.build()?;
+        let mut config = self.move_args.build_config.clone();
+        config
+            .additional_named_addresses
+            .extend(context.parse_and_resolve_addresses(self.named_addresses.clone())?);
+        let package_path = reroot_path(self.move_args.package_path.clone())?;
+
+        let mut package = config
+            .clone()
+            .compile_package_no_exit(&package_path, &mut stderr())?;
+        run_verifier(package_path, config, &mut package)?;
+        Ok(package.root_modules_map().iter_modules_owned())
+    }
+
+    async fn fetch_onchain_modules(
+        &self,
+        package_address: ParsedAddress,
+    ) -> RoochResult<Vec<CompiledModule>> {
+        let context = self.context_options.build()?;
+        let address = context.resolve_address(package_address)?;
+        let module_names = self
+            .modules
+            .iter()
+            .map(|name| Identifier::new(name.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let client = context.get_client().await?;
+        let states = client
+            .rooch
+            .get_states(AccessPath::modules(address, module_names), None)
+            .await?;
+
+        let mut modules = vec![];
+        for (name, state) in self.modules.iter().zip(states) {
+            let state = state.ok_or_else(|| {
+                RoochError::CommandArgumentError(format!(
+                    "Module {}::{} not found",
+                    address.to_hex_literal(),
+                    name
+                ))
+            })?;
+            let module = ObjectState::from(state).value_as_df::<MoveString, MoveModule>()?;
+            let compiled_module =
+                CompiledModule::deserialize(&module.value.byte_codes).map_err(|e| {
+                    RoochError::CommandArgumentError(format!(
+                        "Failed to deserialize module {}: {:?}",
+                        name, e
+                    ))
+                })?;
+            modules.push(compiled_module);
+        }
+        Ok(modules)
+    }
+}
+
+#[async_trait]
+impl CommandAction<String> for BindgenCommand {
+    async fn execute(self) -> RoochResult<String> {
+        let modules = match self.package_address.clone() {
+            Some(package_address) => self.fetch_onchain_modules(package_address).await?,
+            None => self.compile_package_modules()?,
+        };
+        if modules.is_empty() {
+            return Err(RoochError::CommandArgumentError(
+                "No module to generate bindings".to_string(),
+            ));
+        }
+        let abis = modules
+            .into_iter()
+            .map(ModuleABIView::from)
+            .collect::<Vec<_>>();
+
+        let code = match self.lang {
+            BindgenLang::Rust => bindgen::rust::generate(&abis)?,
+        };
+        match self.output {
+            Some(path) => {
+                std::fs::write(&path, code)?;
+                Ok(format!(
+                    "Generated the bindings of {} modules to {:?}",
+                    abis.len(),
+                    path
+                ))
+            }
+            None => Ok(code),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) RoochNetwork
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod bindgen;
 pub mod build;
 pub mod coverage;
 pub mod disassemble;
@@ -4,11 +4,11 @@
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
 use commands::{
-    build::BuildCommand, coverage::CoverageCommand, disassemble::DisassembleCommand,
-    docgen::DocgenCommand, errmap::ErrmapCommand, info::InfoCommand,
-    integration_test::IntegrationTestCommand, new::NewCommand, prove::ProveCommand,
-    publish::Publish, run_function::RunFunction, run_view_function::RunViewFunction,
-    unit_test::TestCommand,
+    bindgen::BindgenCommand, build::BuildCommand, coverage::CoverageCommand,
+    disassemble::DisassembleCommand, docgen::DocgenCommand, errmap::ErrmapCommand,
+    info::InfoCommand, integration_test::IntegrationTestCommand, new::NewCommand,
+    prove::ProveCommand, publish::Publish, run_function::RunFunction,
+    run_view_function::RunViewFunction, unit_test::TestCommand,
 };
 use rooch_types::error::RoochResult;
 use serde_json::{json, Value};
@@ -27,6 +27,7 @@ pub struct MoveCli {
 #[derive(Subcommand)]
 #[clap(name = "move")]
 pub enum MoveCommand {
+    Bindgen(BindgenCommand),
     Build(BuildCommand),
     Coverage(CoverageCommand),
     Disassemble(DisassembleCommand),
@@ -47,6 +48,7 @@ pub enum MoveCommand {
 impl CommandAction<String> for MoveCli {
     async fn execute(self) -> RoochResult<String> {
         match self.cmd {
+            MoveCommand::Bindgen(c) => c.execute().await,
             MoveCommand::Build(c) => c.execute_serialized().await,
             MoveCommand::Coverage(c) => c.execute_serialized().await,
             MoveCommand::Disassemble(c) => c.execute_serialized().await,
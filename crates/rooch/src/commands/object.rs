@@ -9,17 +9,20 @@ use move_command_line_common::types::ParsedStructType;
 use move_core_types::language_storage::TypeTag;
 use moveos_types::move_types::type_tag_match;
 use rooch_rpc_api::jsonrpc_types::btc::ord::InscriptionFilterView;
+use rooch_rpc_api::jsonrpc_types::btc::runes::RuneBalancesFilterView;
 use rooch_rpc_api::jsonrpc_types::btc::utxo::UTXOFilterView;
 use rooch_rpc_api::jsonrpc_types::{ObjectStateFilterView, QueryOptions, RoochAddressView};
 use rooch_types::address::ParsedAddress;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::function_arg::ParsedObjectID;
 use rooch_types::indexer::state::{
-    IndexerStateID, ObjectStateType, INSCRIPTION_TYPE_TAG, UTXO_TYPE_TAG,
+    IndexerStateID, ObjectStateType, INSCRIPTION_TYPE_TAG, RUNE_BALANCES_TYPE_TAG, UTXO_TYPE_TAG,
 };
-use rooch_types::{error::RoochResult, function_arg::ParsedObjectID};
 
 pub const QUERY_OBJECT_STATES_METHOD: &str = "rooch_queryObjectStates";
 pub const QUERY_UTXOS_METHOD: &str = "btc_queryUTXOs";
 pub const QUERY_INSCRIPTIONS_METHOD: &str = "btc_queryInscriptions";
+pub const QUERY_RUNES_METHOD: &str = "btc_queryRunes";
 #[derive(Parser)]
 pub struct ObjectCommand {
     /// Object ids. Separate multiple IDs with a space.
@@ -103,6 +106,8 @@ impl CommandAction<String> for ObjectCommand {
                 ObjectStateType::UTXO
             } else if type_tag_match(&obj_type, &INSCRIPTION_TYPE_TAG) {
                 ObjectStateType::Inscription
+            } else if type_tag_match(&obj_type, &RUNE_BALANCES_TYPE_TAG) {
+                ObjectStateType::RuneBalances
             } else {
                 ObjectStateType::ObjectState
             }
@@ -136,6 +141,13 @@ impl CommandAction<String> for ObjectCommand {
                     ObjectStateFilterView::ObjectId(object_id) => {
                         UTXOFilterView::ObjectId(object_id)
                     }
+                    ObjectStateFilterView::And(_)
+                    | ObjectStateFilterView::Or(_)
+                    | ObjectStateFilterView::Not(_) => {
+                        return Err(RoochError::CommandArgumentError(
+                            "Composite filters are not supported by UTXO queries".to_string(),
+                        ));
+                    }
                 };
                 let result = client
                     .rooch
@@ -159,6 +171,14 @@ impl CommandAction<String> for ObjectCommand {
                     ObjectStateFilterView::ObjectId(object_id) => {
                         InscriptionFilterView::ObjectId(object_id)
                     }
+                    ObjectStateFilterView::And(_)
+                    | ObjectStateFilterView::Or(_)
+                    | ObjectStateFilterView::Not(_) => {
+                        return Err(RoochError::CommandArgumentError(
+                            "Composite filters are not supported by inscription queries"
+                                .to_string(),
+                        ));
+                    }
                 };
                 let result = client
                     .rooch
@@ -171,6 +191,37 @@ impl CommandAction<String> for ObjectCommand {
                     .await?;
                 serde_json::to_string_pretty(&result).unwrap()
             }
+            ObjectStateType::RuneBalances => {
+                let rune_balances_filter = match filter.unwrap() {
+                    ObjectStateFilterView::ObjectTypeWithOwner {
+                        object_type: _,
+                        owner,
+                    } => RuneBalancesFilterView::Owner(owner),
+                    ObjectStateFilterView::ObjectType(_object_type) => RuneBalancesFilterView::All,
+                    ObjectStateFilterView::Owner(owner) => RuneBalancesFilterView::Owner(owner),
+                    ObjectStateFilterView::ObjectId(object_id) => {
+                        RuneBalancesFilterView::ObjectId(object_id)
+                    }
+                    ObjectStateFilterView::And(_)
+                    | ObjectStateFilterView::Or(_)
+                    | ObjectStateFilterView::Not(_) => {
+                        return Err(RoochError::CommandArgumentError(
+                            "Composite filters are not supported by rune balances queries"
+                                .to_string(),
+                        ));
+                    }
+                };
+                let result = client
+                    .rooch
+                    .query_runes(
+                        rune_balances_filter,
+                        self.cursor.clone(),
+                        self.limit,
+                        Some(query_options),
+                    )
+                    .await?;
+                serde_json::to_string_pretty(&result).unwrap()
+            }
             ObjectStateType::ObjectState => {
                 let result = client
                     .rooch
@@ -3,6 +3,8 @@
 
 use crate::cli_types::{TransactionOptions, WalletContextOptions};
 use clap::Parser;
+use move_command_line_common::types::ParsedStructType;
+use move_core_types::u256::U256;
 use moveos_types::module_binding::MoveFunctionCaller;
 use moveos_types::move_std::string::MoveString;
 use rooch_key::keystore::account_keystore::AccountKeystore;
@@ -11,6 +13,7 @@ use rooch_types::{
     error::{RoochError, RoochResult},
     framework::session_key::{SessionKey, SessionKeyModule, SessionScope},
 };
+use std::str::FromStr;
 
 /// Create a new session key on-chain
 #[derive(Debug, Parser)]
@@ -31,6 +34,23 @@ pub struct CreateCommand {
     #[clap(long, default_value = "3600")]
     pub max_inactive_interval: u64,
 
+    /// The max gas the session key can spend, in RGas. 0 means no limit.
+    #[clap(long, default_value = "0")]
+    pub max_gas: U256,
+
+    /// The max number of transactions the session key can send. 0 means no limit.
+    #[clap(long, default_value = "0")]
+    pub max_tx_count: u64,
+
+    /// The absolute expiration time of the session key, a timestamp in seconds. 0 means no expiration time.
+    #[clap(long, default_value = "0")]
+    pub expiration_time: u64,
+
+    /// The max amount of a coin type the session key can transfer, format: coin_type=amount.
+    /// For example: 0x3::gas_coin::RGas=100000000. The coin types without a transfer cap are not limited.
+    #[clap(long = "transfer-cap")]
+    pub transfer_caps: Vec<String>,
+
     #[clap(flatten)]
     pub tx_options: TransactionOptions,
 
@@ -48,14 +68,49 @@ impl CreateCommand {
         let session_auth_key = context.generate_session_key(&sender)?;
         let session_scope = self.scope;
 
-        let action =
-            rooch_types::framework::session_key::SessionKeyModule::create_session_key_action(
+        let mapping = context.address_mapping();
+        let transfer_caps = self
+            .transfer_caps
+            .iter()
+            .map(|transfer_cap| -> RoochResult<(MoveString, U256)> {
+                let (coin_type, amount) = transfer_cap.rsplit_once('=').ok_or_else(|| {
+                    RoochError::CommandArgumentError(format!(
+                        "Invalid transfer cap {}, the format should be coin_type=amount",
+                        transfer_cap
+                    ))
+                })?;
+                let coin_type = ParsedStructType::parse(coin_type)?.into_struct_tag(&mapping)?;
+                let amount = U256::from_str(amount)
+                    .map_err(|e| RoochError::CommandArgumentError(e.to_string()))?;
+                Ok((MoveString::from(coin_type.to_canonical_string()), amount))
+            })
+            .collect::<RoochResult<Vec<_>>>()?;
+
+        let has_limit = self.max_gas > U256::zero()
+            || self.max_tx_count > 0
+            || self.expiration_time > 0
+            || !transfer_caps.is_empty();
+        let action = if has_limit {
+            SessionKeyModule::create_session_key_with_limit_action(
+                self.app_name,
+                self.app_url,
+                session_auth_key.as_ref().to_vec(),
+                vec![session_scope.clone()],
+                self.max_inactive_interval,
+                self.max_gas,
+                self.max_tx_count,
+                self.expiration_time,
+                transfer_caps,
+            )
+        } else {
+            SessionKeyModule::create_session_key_action(
                 self.app_name,
                 self.app_url,
                 session_auth_key.as_ref().to_vec(),
                 session_scope.clone(),
                 self.max_inactive_interval,
-            );
+            )
+        };
 
         println!("Generated new session key {session_auth_key} for address [{sender}]",);
 
@@ -0,0 +1,116 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{FileOutput, FileOutputData};
+use crate::cli_types::{CommandAction, FileOrHexInput, WalletContextOptions};
+use async_trait::async_trait;
+use moveos_types::module_binding::MoveFunctionCaller;
+use rooch_types::{
+    bitcoin::multisign_account::MultisignAccountModule,
+    error::{RoochError, RoochResult},
+    transaction::rooch::PartiallySignedRoochTransaction,
+};
+
+/// Combine the partially signed transactions of a multisign account transaction
+/// The signatures collected from the co-signers are merged into one transaction,
+/// every signature is verified against the transaction data and the participants of the multisign account
+#[derive(Debug, clap::Parser)]
+pub struct CombineCommand {
+    /// The partially signed transaction hex or file paths to be combined
+    /// All the inputs should be signed from the same transaction data
+    #[clap(required = true, num_args = 2..)]
+    inputs: Vec<FileOrHexInput>,
+
+    /// The output file path for the combined transaction
+    /// If not specified, the combined output will write to temp directory.
+    #[clap(long, short = 'o')]
+    output: Option<String>,
+
+    /// Return command outputs in json format
+    #[clap(long, default_value = "false")]
+    json: bool,
+
+    #[clap(flatten)]
+    context: WalletContextOptions,
+}
+
+impl CombineCommand {
+    async fn combine(
+        context: WalletContextOptions,
+        inputs: Vec<FileOrHexInput>,
+    ) -> RoochResult<FileOutputData> {
+        let mut psrts = inputs.into_iter().map(|input| {
+            bcs::from_bytes::<PartiallySignedRoochTransaction>(&input.data).map_err(|e| {
+                RoochError::CommandArgumentError(format!(
+                    "Invalid partially signed transaction hex, err: {:?}, hex: {}",
+                    e,
+                    hex::encode(&input.data)
+                ))
+            })
+        });
+        let mut combined = psrts
+            .next()
+            .expect("at least two inputs are required by clap")?;
+
+        let context = context.build()?;
+        let client = context.get_client().await?;
+        let participant_public_keys = client
+            .as_module_binding::<MultisignAccountModule>()
+            .participants(combined.sender().into())?
+            .into_iter()
+            .map(|participant| participant.public_key)
+            .collect::<Vec<_>>();
+
+        combined.verify_authenticators(&participant_public_keys)?;
+        for psrt in psrts {
+            combined.merge(psrt?, &participant_public_keys)?;
+        }
+
+        if combined.is_fully_signed() {
+            Ok(FileOutputData::SignedRoochTransaction(
+                combined.try_into_rooch_transaction()?,
+            ))
+        } else {
+            Ok(FileOutputData::PartiallySignedRoochTransaction(combined))
+        }
+    }
+}
+
+#[async_trait]
+impl CommandAction<Option<FileOutput>> for CombineCommand {
+    async fn execute(self) -> RoochResult<Option<FileOutput>> {
+        let file_output_data = Self::combine(self.context, self.inputs).await?;
+        let collected = match &file_output_data {
+            FileOutputData::PartiallySignedRoochTransaction(psrt) => {
+                Some((psrt.signatories(), psrt.threshold()))
+            }
+            _ => None,
+        };
+        let file_output = FileOutput::write_to_file(file_output_data, self.output)?;
+
+        if !self.json {
+            match collected {
+                None => {
+                    println!("Signed transaction is written to {:?}", file_output.path);
+                    println!(
+                        "You can submit the transaction with `rooch tx submit {}`",
+                        file_output.path
+                    );
+                }
+                Some((signatories, threshold)) => {
+                    println!(
+                        "Partially signed transaction is written to {:?}",
+                        file_output.path
+                    );
+                    println!(
+                        "Collected signatures: {}/{}, you can collect more signatures with `rooch tx sign {}`",
+                        signatories, threshold, file_output.path
+                    );
+                }
+            }
+            Ok(None)
+        } else {
+            Ok(Some(file_output))
+        }
+    }
+}
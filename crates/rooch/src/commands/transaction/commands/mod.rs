@@ -10,12 +10,15 @@ use serde::{Deserialize, Serialize};
 use std::{env, fs::File, io::Write, path::PathBuf};
 
 pub mod build;
+pub mod combine;
 pub mod get_transactions_by_hash;
 pub mod get_transactions_by_order;
+pub mod prove;
 pub mod query;
 pub mod sign;
 pub mod sign_order;
 pub mod submit;
+pub mod verify_proof;
 
 pub(crate) enum FileOutputData {
     RoochTransactionData(RoochTransactionData),
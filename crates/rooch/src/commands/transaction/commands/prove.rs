@@ -0,0 +1,61 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use moveos_types::h256::H256;
+use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionAccumulatorProofView;
+use rooch_types::error::{RoochError, RoochResult};
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Get the inclusion proof of a transaction in the tx accumulator
+#[derive(Debug, clap::Parser)]
+pub struct ProveCommand {
+    /// The order of the transaction to prove
+    #[clap(long, conflicts_with = "tx_hash", required_unless_present = "tx_hash")]
+    pub tx_order: Option<u64>,
+
+    /// The hash of the transaction to prove
+    #[clap(long)]
+    pub tx_hash: Option<H256>,
+
+    /// Generate the proof against the accumulator root after the tx of this order is appended,
+    /// such as the last tx order of a DA batch or a proposed block.
+    /// Default is the tx itself.
+    #[clap(long)]
+    pub accumulator_tx_order: Option<u64>,
+
+    /// Write the proof to the file in json, it can be verified offline by `rooch transaction verify-proof`
+    #[clap(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<TransactionAccumulatorProofView> for ProveCommand {
+    async fn execute(self) -> RoochResult<TransactionAccumulatorProofView> {
+        let client = self.context_options.build()?.get_client().await?;
+
+        let proof = client
+            .rooch
+            .get_transaction_accumulator_proof(
+                self.tx_order,
+                self.tx_hash,
+                self.accumulator_tx_order,
+            )
+            .await?
+            .ok_or_else(|| {
+                RoochError::CommandArgumentError("The transaction does not exist".to_string())
+            })?;
+
+        if let Some(output) = self.output {
+            let mut file = File::create(output)?;
+            file.write_all(serde_json::to_string_pretty(&proof)?.as_bytes())?;
+        }
+        Ok(proof)
+    }
+}
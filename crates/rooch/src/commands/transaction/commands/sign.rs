@@ -241,6 +241,7 @@ impl CommandAction<Option<FileOutput>> for SignCommand {
                     file_output.path
                 );
                 println!("You can send the partially signed transaction to other signers, and sign it later with `rooch tx sign {}`", file_output.path);
+                println!("Or combine the partially signed transactions signed by other signers with `rooch tx combine {} <other psrt files>`", file_output.path);
             }
             Ok(None)
         } else {
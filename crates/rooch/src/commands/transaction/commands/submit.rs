@@ -6,13 +6,14 @@ use async_trait::async_trait;
 use rooch_rpc_api::jsonrpc_types::ExecuteTransactionResponseView;
 use rooch_types::{
     error::{RoochError, RoochResult},
-    transaction::RoochTransaction,
+    transaction::{rooch::PartiallySignedRoochTransaction, RoochTransaction},
 };
 
 /// Get transactions by order
 #[derive(Debug, clap::Parser)]
 pub struct SubmitCommand {
-    /// Transaction data hex or file location to be used for submitting
+    /// Signed transaction hex or file location to be used for submitting
+    /// A fully signed multisign partially signed transaction is also accepted
     input: FileOrHexInput,
 
     #[clap(flatten)]
@@ -28,13 +29,28 @@ impl CommandAction<ExecuteTransactionResponseView> for SubmitCommand {
     async fn execute(self) -> RoochResult<ExecuteTransactionResponseView> {
         let context = self.context.build()?;
 
-        let signed_tx = bcs::from_bytes::<RoochTransaction>(&self.input.data).map_err(|e| {
-            RoochError::CommandArgumentError(format!(
-                "Invalid signed transaction hex, err: {:?}, hex: {}",
-                e,
-                hex::encode(&self.input.data)
-            ))
-        })?;
+        let signed_tx = match bcs::from_bytes::<RoochTransaction>(&self.input.data) {
+            Ok(signed_tx) => signed_tx,
+            Err(e) => match bcs::from_bytes::<PartiallySignedRoochTransaction>(&self.input.data) {
+                Ok(psrt) => {
+                    if !psrt.is_fully_signed() {
+                        return Err(RoochError::CommandArgumentError(format!(
+                            "The partially signed transaction is not fully signed, collected signatures: {}/{}",
+                            psrt.signatories(),
+                            psrt.threshold()
+                        )));
+                    }
+                    psrt.try_into_rooch_transaction()?
+                }
+                Err(_) => {
+                    return Err(RoochError::CommandArgumentError(format!(
+                        "Invalid signed transaction hex, err: {:?}, hex: {}",
+                        e,
+                        hex::encode(&self.input.data)
+                    )))
+                }
+            },
+        };
 
         //TODO support no json output
         let response = context.execute(signed_tx).await?;
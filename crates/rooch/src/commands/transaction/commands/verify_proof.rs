@@ -0,0 +1,100 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::CommandAction;
+use crate::commands::da::commands::{collect_chunk, get_tx_list_from_chunk};
+use crate::utils::derive_builtin_genesis_namespace;
+use async_trait::async_trait;
+use moveos_types::h256::H256;
+use rooch_anomalies::{load_tx_anomalies, AccumulatorIndexMapper};
+use rooch_config::R_OPT_NET_HELP;
+use rooch_rpc_api::jsonrpc_types::transaction_view::TransactionAccumulatorProofView;
+use rooch_types::error::{RoochError, RoochResult};
+use rooch_types::rooch_network::BuiltinChainID;
+use rooch_types::transaction::TransactionAccumulatorProof;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Verify a tx accumulator proof offline, against an accumulator root from a trusted source,
+/// such as a DA batch or a proposed block.
+#[derive(Debug, clap::Parser)]
+pub struct VerifyProofCommand {
+    /// The proof file written by `rooch transaction prove --output`
+    #[clap(long)]
+    pub proof: PathBuf,
+
+    /// The trusted tx accumulator root, such as the tx accumulator root of a proposed block
+    #[clap(
+        long,
+        conflicts_with = "segment_dir",
+        required_unless_present = "segment_dir"
+    )]
+    pub accumulator_root: Option<H256>,
+
+    /// The DA segment dir, the trusted accumulator root is taken from the last tx of the DA batch `chunk_id`
+    #[clap(long, requires = "chunk_id")]
+    pub segment_dir: Option<PathBuf>,
+
+    /// The DA chunk id(block number) of the batch in the segment dir
+    #[clap(long)]
+    pub chunk_id: Option<u128>,
+
+    /// The network of the tx, used to derive the accumulator leaf index from the tx order
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    pub chain_id: BuiltinChainID,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyProofOutput {
+    pub tx_order: u64,
+    pub tx_hash: H256,
+    pub accumulator_tx_order: u64,
+    pub accumulator_root: H256,
+}
+
+#[async_trait]
+impl CommandAction<VerifyProofOutput> for VerifyProofCommand {
+    async fn execute(self) -> RoochResult<VerifyProofOutput> {
+        let proof_view: TransactionAccumulatorProofView =
+            serde_json::from_slice(&std::fs::read(&self.proof)?)?;
+        let proof: TransactionAccumulatorProof = proof_view.into();
+
+        let accumulator_root = match (self.accumulator_root, self.segment_dir, self.chunk_id) {
+            (Some(accumulator_root), _, _) => accumulator_root,
+            (None, Some(segment_dir), Some(chunk_id)) => {
+                let segment_numbers = collect_chunk(segment_dir.clone(), chunk_id)?;
+                // the batch signature and tx orders are verified when loading the tx list
+                let tx_list = get_tx_list_from_chunk(segment_dir, chunk_id, segment_numbers, true)?;
+                let last_tx = tx_list.last().ok_or_else(|| {
+                    RoochError::CommandArgumentError(format!("DA batch {} is empty", chunk_id))
+                })?;
+                if last_tx.sequence_info.tx_order != proof.accumulator_tx_order {
+                    return Err(RoochError::CommandArgumentError(format!(
+                        "The proof is generated against tx order {}, but the last tx order of DA batch {} is {}",
+                        proof.accumulator_tx_order, chunk_id, last_tx.sequence_info.tx_order
+                    )));
+                }
+                last_tx.sequence_info.tx_accumulator_root
+            }
+            _ => {
+                return Err(RoochError::CommandArgumentError(
+                    "accumulator-root or segment-dir with chunk-id is required".to_string(),
+                ))
+            }
+        };
+
+        // some networks have txs that occupy extra accumulator leaves,
+        // so the leaf index is derived from the tx order with the network's tx anomalies
+        let genesis_namespace = derive_builtin_genesis_namespace(self.chain_id)?;
+        let accumulator_index_mapper =
+            AccumulatorIndexMapper::new(load_tx_anomalies(genesis_namespace)?);
+        let leaf_index = accumulator_index_mapper.get_index_for_order(proof.tx_order);
+        proof.verify(accumulator_root, leaf_index)?;
+        Ok(VerifyProofOutput {
+            tx_order: proof.tx_order,
+            tx_hash: proof.tx_hash,
+            accumulator_tx_order: proof.accumulator_tx_order,
+            accumulator_root,
+        })
+    }
+}
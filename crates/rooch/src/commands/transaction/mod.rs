@@ -4,9 +4,11 @@
 use crate::cli_types::CommandAction;
 use crate::commands::transaction::commands::sign_order::SignOrderCommand;
 use crate::commands::transaction::commands::{
-    build::BuildCommand, get_transactions_by_hash::GetTransactionsByHashCommand,
-    get_transactions_by_order::GetTransactionsByOrderCommand, query::QueryCommand,
-    sign::SignCommand, submit::SubmitCommand,
+    build::BuildCommand, combine::CombineCommand,
+    get_transactions_by_hash::GetTransactionsByHashCommand,
+    get_transactions_by_order::GetTransactionsByOrderCommand, prove::ProveCommand,
+    query::QueryCommand, sign::SignCommand, submit::SubmitCommand,
+    verify_proof::VerifyProofCommand,
 };
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
@@ -31,7 +33,10 @@ impl CommandAction<String> for Transaction {
             TransactionCommand::Build(cmd) => cmd.execute_serialized().await,
             TransactionCommand::Sign(cmd) => cmd.execute_serialized().await,
             TransactionCommand::Submit(cmd) => cmd.execute_serialized().await,
+            TransactionCommand::Combine(cmd) => cmd.execute_serialized().await,
             TransactionCommand::SignOrder(cmd) => cmd.execute(),
+            TransactionCommand::Prove(cmd) => cmd.execute_serialized().await,
+            TransactionCommand::VerifyProof(cmd) => cmd.execute_serialized().await,
         }
     }
 }
@@ -44,5 +49,8 @@ pub enum TransactionCommand {
     Query(QueryCommand),
     Sign(SignCommand),
     Submit(SubmitCommand),
+    Combine(CombineCommand),
     SignOrder(SignOrderCommand),
+    Prove(ProveCommand),
+    VerifyProof(VerifyProofCommand),
 }
@@ -22,6 +22,7 @@ use commands::{
 use once_cell::sync::Lazy;
 use rooch_types::error::RoochResult;
 
+pub mod bindgen;
 pub mod cli_types;
 pub mod commands;
 pub mod utils;
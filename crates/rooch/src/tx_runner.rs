@@ -296,6 +296,7 @@ pub async fn dry_run_tx_locally(
         Some(vm_error_info) => Ok(DryRunTransactionResponseView {
             raw_output: raw_output_view,
             vm_error_info,
+            state_diff: None,
         }),
         None => {
             let empty_error_info = VMErrorInfo {
@@ -305,6 +306,7 @@ pub async fn dry_run_tx_locally(
             Ok(DryRunTransactionResponseView {
                 raw_output: raw_output_view,
                 vm_error_info: empty_error_info,
+                state_diff: None,
             })
         }
     }
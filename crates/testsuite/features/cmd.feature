@@ -101,6 +101,9 @@ Feature: Rooch CLI integration tests
       Then assert: "'{{$.transaction[-1]}}' not_contains error"
       Then cmd: "transaction query --start-time 0 --end-time 1735689600 --limit 1"
       Then assert: "'{{$.transaction[-1]}}' not_contains error"
+      Then cmd: "transaction prove --tx-order 1 --accumulator-tx-order 2"
+      Then assert: "'{{$.transaction[-1]}}' not_contains error"
+      Then assert: "{{$.transaction[-1].tx_order}} == 1"
 
       # alias tx for transaction
       Then cmd: "tx get-transactions-by-order --cursor 1 --limit 2 --descending-order true"
@@ -21,10 +21,11 @@ module bitcoin_move::bitcoin{
     
     use bitcoin_move::network;
     use bitcoin_move::types::{Self, Block, Header, Transaction, BlockHeightHash, OutPoint};
-    use bitcoin_move::utxo::{Self, UTXOSeal};
+    use bitcoin_move::utxo::{Self, UTXOSeal, SealOut};
     use bitcoin_move::pending_block::{Self, PendingBlock};
     use bitcoin_move::script_buf;
     use bitcoin_move::bbn;
+    use bitcoin_move::runes::{Self, Runestone};
 
     friend bitcoin_move::genesis;
 
@@ -118,7 +119,7 @@ module bitcoin_move::bitcoin{
     fun process_tx(btc_block_store: &mut BitcoinBlockStore, pblock: &mut Object<PendingBlock>, tx: &Transaction, is_coinbase: bool){
         let block_height = pending_block::block_height(pblock);
         let txid = types::tx_id(tx);
-        let repeat_txid = process_utxo(block_height, pblock, tx, is_coinbase);
+        let repeat_txid = process_utxo(btc_block_store, block_height, pblock, tx, is_coinbase);
         
         if (repeat_txid) {
             table::upsert(&mut btc_block_store.txs, txid, *tx);
@@ -135,7 +136,7 @@ module bitcoin_move::bitcoin{
         };
     }
 
-    fun process_utxo(block_height: u64, pending_block: &mut Object<PendingBlock>, tx: &Transaction, is_coinbase: bool) : bool{
+    fun process_utxo(btc_block_store: &BitcoinBlockStore, block_height: u64, pending_block: &mut Object<PendingBlock>, tx: &Transaction, is_coinbase: bool) : bool{
         let txinput = types::tx_input(tx);
         let input_utxos = vector::empty();
 
@@ -180,16 +181,14 @@ module bitcoin_move::bitcoin{
         let skip_ordinals = block_height >= ORDINALS_PAUSE_HEIGHT && network::is_mainnet() && rooch_framework::chain_id::is_main();
         if(!skip_ordinals){
             let seal_outs = bitcoin_move::inscription_updater::process_tx(pending_block, tx, &mut input_utxos);
-            let seal_outs_len = vector::length(&seal_outs);
-            if (seal_outs_len > 0) {
-                let seal_out_idx = 0;
-                while (seal_out_idx < seal_outs_len) {
-                    let seal_out = vector::pop_back(&mut seal_outs);
-                    let (output_index, utxo_seal) = utxo::unpack_seal_out(seal_out);
-                    simple_multimap::add(&mut output_seals, output_index, utxo_seal);
-                    seal_out_idx = seal_out_idx + 1;
-                };
-            };
+            add_seal_outs(&mut output_seals, seal_outs);
+        };
+        //The runes are not paused with the ordinals, the rune balances must follow every UTXO spend after the runes are active
+        if(runes::is_runes_active(block_height)){
+            let runestone = runes::decipher(tx);
+            let rune_committed = tx_commits_to_rune(btc_block_store, block_height, tx, &runestone);
+            let seal_outs = runes::process_tx(pending_block, block_height, tx, runestone, rune_committed, &mut input_utxos);
+            add_seal_outs(&mut output_seals, seal_outs);
         };
     
         // create new utxo
@@ -206,6 +205,55 @@ module bitcoin_move::bitcoin{
         repeat_txid
     }
 
+    fun add_seal_outs(output_seals: &mut SimpleMultiMap<u32, UTXOSeal>, seal_outs: vector<SealOut>){
+        let seal_outs_len = vector::length(&seal_outs);
+        let seal_out_idx = 0;
+        while (seal_out_idx < seal_outs_len) {
+            let seal_out = vector::pop_back(&mut seal_outs);
+            let (output_index, utxo_seal) = utxo::unpack_seal_out(seal_out);
+            simple_multimap::add(output_seals, output_index, utxo_seal);
+            seal_out_idx = seal_out_idx + 1;
+        };
+    }
+
+    /// Check whether the tx commits to the etched rune name.
+    /// An input must reveal the name commitment in its tapscript, and spend a taproot output
+    /// which has at least `runes::commit_confirmations()` confirmations.
+    fun tx_commits_to_rune(btc_block_store: &BitcoinBlockStore, block_height: u64, tx: &Transaction, runestone: &Option<Runestone>): bool{
+        let commitment_opt = runes::etching_commitment(runestone);
+        if (option::is_none(&commitment_opt)) {
+            return false
+        };
+        let commitment = option::destroy_some(commitment_opt);
+        let txinput = types::tx_input(tx);
+        let idx = 0;
+        let input_len = vector::length(txinput);
+        while (idx < input_len) {
+            let txin = vector::borrow(txinput, idx);
+            idx = idx + 1;
+            let tapscript = types::witness_tapscript(types::txin_witness(txin));
+            if (option::is_none(&tapscript) || !runes::script_contains_push(option::borrow(&tapscript), &commitment)) {
+                continue
+            };
+            let outpoint = types::txin_previous_output(txin);
+            let prev_txid = types::outpoint_txid(outpoint);
+            //The commit tx maybe not exists if we do not sync the block from genesis
+            if (!table::contains(&btc_block_store.txs, prev_txid)) {
+                continue
+            };
+            let prev_outputs = types::tx_output(table::borrow(&btc_block_store.txs, prev_txid));
+            let vout = (types::outpoint_vout(outpoint) as u64);
+            if (vout >= vector::length(prev_outputs) || !script_buf::is_p2tr(types::txout_script_pubkey(vector::borrow(prev_outputs, vout)))) {
+                continue
+            };
+            let commit_height = *table::borrow(&btc_block_store.tx_to_height, prev_txid);
+            if (block_height - commit_height + 1 >= runes::commit_confirmations()) {
+                return true
+            };
+        };
+        false
+    }
+
     fun handle_new_utxo(tx: &Transaction, is_coinbase: bool, output_seals: &mut SimpleMultiMap<u32, UTXOSeal>, block_height: u64, sender: Option<address>) :bool {
         let txid = types::tx_id(tx);
         let txoutput = types::tx_output(tx);
@@ -471,6 +519,22 @@ module bitcoin_move::bitcoin{
         // Transactions can be verified on-demand using submit_tx_with_proof
     }
 
+    /// Execute the block with the tx bodies, as the relayer does in the full block mode.
+    /// The coinbase tx finishes the pending block, so it is executed after the other txs.
+    #[test_only]
+    public fun execute_l1_block_with_txs_for_test(block_height: u64, block: Block){
+        let block_hash = types::header_to_hash(types::header(&block));
+        let txids = vector::map_ref(types::txdata(&block), |tx| types::tx_id(tx));
+        assert!(pending_block::add_pending_block(block_height, block_hash, block), ErrorBlockAlreadyProcessed);
+        let idx = 1;
+        let txids_len = vector::length(&txids);
+        while (idx < txids_len) {
+            execute_l1_tx(block_hash, *vector::borrow(&txids, idx));
+            idx = idx + 1;
+        };
+        execute_l1_tx(block_hash, *vector::borrow(&txids, 0));
+    }
+
 
     #[test_only]
     public fun add_latest_block(block_height: u64, block_hash: address){
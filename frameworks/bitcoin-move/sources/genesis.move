@@ -9,6 +9,7 @@ module bitcoin_move::genesis{
     use rooch_framework::bitcoin_address::{Self, BitcoinAddress};
     use bitcoin_move::bitcoin;
     use bitcoin_move::ord;
+    use bitcoin_move::runes;
     use bitcoin_move::utxo;
     use bitcoin_move::network;
     use bitcoin_move::pending_block;
@@ -41,6 +42,7 @@ module bitcoin_move::genesis{
         network::genesis_init(genesis_context.network);
         utxo::genesis_init();
         ord::genesis_init();
+        runes::genesis_init();
         bbn::genesis_init();
         bitcoin::genesis_init(&genesis_account, genesis_context.genesis_block_height, genesis_context.genesis_block_hash);
         pending_block::genesis_init(genesis_context.reorg_block_count);
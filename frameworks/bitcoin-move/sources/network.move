@@ -146,6 +146,23 @@ module bitcoin_move::network{
         }
     }
 
+    /// Runes first rune height, the runes are activated at the fourth halving on mainnet.
+    /// https://github.com/ordinals/ord/blob/master/src/chain.rs
+    public fun first_rune_height() : u64 {
+        let n = network();
+        if (n == NETWORK_BITCOIN) {
+            (SUBSIDY_HALVING_INTERVAL as u64) * 4
+        } else if (n == NETWORK_REGTEST) {
+            0
+        } else if (n == NETWORK_SIGNET) {
+            0
+        } else if (n == NETWORK_TESTNET) {
+            (SUBSIDY_HALVING_INTERVAL as u64) * 12
+        } else {
+            abort ErrorUnknownNetwork
+        }
+    }
+
     /// Block Rewards
     public fun subsidy_by_height(height: u64): u64 {
         let epoch = (height as u32) / SUBSIDY_HALVING_INTERVAL;
@@ -14,12 +14,14 @@ module bitcoin_move::pending_block{
     use moveos_std::simple_map::{Self, SimpleMap};
     use moveos_std::event;
     use moveos_std::type_info;
+    use moveos_std::bcs;
     
     use bitcoin_move::types::{Self, Transaction, Header, Block, BlockHeightHash};
 
     friend bitcoin_move::genesis;
     friend bitcoin_move::bitcoin;
     friend bitcoin_move::inscription_updater;
+    friend bitcoin_move::runes;
 
     const ErrorBlockAlreadyProcessed:u64 = 1;
     const ErrorPendingBlockNotFound:u64 = 2;
@@ -62,6 +64,18 @@ module bitcoin_move::pending_block{
         success: bool,
     }
 
+    /// Emitted once per reorg, it contains all the orphaned blocks replaced by the new block.
+    /// A pending block is orphaned only before any of its transactions is processed,
+    /// a processed block aborts the reorg with `ErrorReorgFailed`. So the orphaned blocks never
+    /// create UTXO or Inscription objects, the downstream consumers only need to drop the blocks.
+    struct BlockReorgEvent has copy, drop{
+        /// The height of the new block which triggers the reorg
+        block_height: u64,
+        /// The hash of the new block which triggers the reorg
+        block_hash: address,
+        orphaned_blocks: vector<BlockHeightHash>,
+    }
+
     struct ReadyPendingBlockHeader has copy, drop, store {
         block_height: u64,
         block_hash: address,
@@ -126,7 +140,7 @@ module bitcoin_move::pending_block{
         let store = borrow_mut_store();
         if(simple_map::contains_key(&store.pending_blocks, &block_height)){
             // block already exists, need to process reorg
-            handle_reorg(store, block_height);
+            handle_reorg(store, block_height, block_hash);
         };
         let (header, txs) = types::unpack_block(block);
         add_pending_block_internal(store, block_height, block_hash, header, txs)
@@ -154,7 +168,7 @@ module bitcoin_move::pending_block{
             return false
         };
         if(simple_map::contains_key(&store.pending_blocks, &block_height)){
-            handle_reorg(store, block_height);
+            handle_reorg(store, block_height, block_hash);
         };
         let prev_block_hash = types::prev_blockhash(&header);
         let block_obj = object::new_with_id(block_hash, PendingBlock{
@@ -199,11 +213,16 @@ module bitcoin_move::pending_block{
         remove_pending_block(block_obj, true);
     }
 
-    fun handle_reorg(store: &mut PendingStore, reorg_block_height: u64){
+    fun handle_reorg(store: &mut PendingStore, reorg_block_height: u64, new_block_hash: address){
         let (_, reorg_block_hash) = simple_map::remove(&mut store.pending_blocks, &reorg_block_height);
         let reorg_block = take_pending_block(reorg_block_hash);
         let next_block_hash_option = object::borrow(&reorg_block).next_block_hash;
-        handle_reorg_block(reorg_block);
+        let event = BlockReorgEvent{
+            block_height: reorg_block_height,
+            block_hash: new_block_hash,
+            orphaned_blocks: vector::empty(),
+        };
+        handle_reorg_block(reorg_block, &mut event);
         while(option::is_some(&next_block_hash_option)){
             let next_block_hash = option::destroy_some(next_block_hash_option);
             let next_block = take_pending_block(next_block_hash);
@@ -211,12 +230,13 @@ module bitcoin_move::pending_block{
             
             next_block_hash_option = object::borrow(&next_block).next_block_hash;
 
-            handle_reorg_block(next_block);
+            handle_reorg_block(next_block, &mut event);
             simple_map::remove(&mut store.pending_blocks, &next_block_height);
         };
+        event::emit(event);
     }
 
-    fun handle_reorg_block(obj: Object<PendingBlock>){
+    fun handle_reorg_block(obj: Object<PendingBlock>, reorg_event: &mut BlockReorgEvent){
         let block_height = object::borrow(&obj).block_height;
         let block_hash = object::borrow(&obj).block_hash;
         // If the block already processed, we can't remove it, and reorg failed
@@ -228,6 +248,7 @@ module bitcoin_move::pending_block{
             });
             abort ErrorReorgFailed
         };
+        vector::push_back(&mut reorg_event.orphaned_blocks, types::new_block_height_hash(block_height, block_hash));
         remove_pending_block(obj, false);
         event::emit(ReorgEvent{
             block_height,
@@ -260,6 +281,14 @@ module bitcoin_move::pending_block{
         block.block_height
     }
 
+    /// Get the index of the tx in the block
+    public(friend) fun tx_index(pending_block: &Object<PendingBlock>, txid: address): u64{
+        let tx_ids: &vector<address> = object::borrow_field(pending_block, TX_IDS_KEY);
+        let (found, index) = vector::index_of(tx_ids, &txid);
+        assert!(found, ErrorPendingTxNotFound);
+        index
+    }
+
     /// The intermediate is used to store the intermediate state during the tx processing
     public(friend) fun take_intermediate<I: store>(pending_block: &mut Object<PendingBlock>): I{
         let intermediate_name = type_info::type_name<I>();
@@ -468,4 +497,13 @@ module bitcoin_move::pending_block{
         let store = borrow_mut_store();
         store.reorg_block_count = count;
     } 
+
+    /// Add the block with the tx bodies as a pending block for local env to testing,
+    /// the txs of the block can then be executed one by one via `bitcoin::execute_l1_tx`
+    public entry fun add_pending_block_for_local(block_height: u64, block_bytes: vector<u8>){
+        assert!(rooch_framework::chain_id::is_local(), ErrorUnsupportedChain);
+        let block = bcs::from_bytes<Block>(block_bytes);
+        let block_hash = types::header_to_hash(types::header(&block));
+        assert!(add_pending_block(block_height, block_hash, block), ErrorBlockAlreadyProcessed);
+    }
 }
@@ -0,0 +1,1328 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// Runes protocol indexer, follows the ord implementation
+/// https://github.com/ordinals/ord/tree/master/crates/ordinals/src/runestone.rs
+/// https://github.com/ordinals/ord/blob/master/src/index/updater/rune_updater.rs
+/// The runes are processed with the block txs executed via `bitcoin::execute_l1_tx`, like the inscriptions,
+/// so they are inactive in the header-only mode, where `bitcoin::execute_l1_block` only processes the block header.
+module bitcoin_move::runes{
+    use std::vector;
+    use std::option::{Self, Option};
+    use std::string::{Self, String};
+    use moveos_std::object::{Self, Object, ObjectID};
+    use moveos_std::type_info;
+    use moveos_std::event;
+    use moveos_std::simple_multimap::{Self, SimpleMultiMap};
+    use bitcoin_move::types::{Self, Transaction, TxOut, OutPoint};
+    use bitcoin_move::script_buf::{Self, ScriptBuf};
+    use bitcoin_move::opcode;
+    use bitcoin_move::network;
+    use bitcoin_move::utxo::{Self, UTXO, SealOut};
+    use bitcoin_move::pending_block::{Self, PendingBlock};
+
+    friend bitcoin_move::genesis;
+    friend bitcoin_move::bitcoin;
+
+    const ErrorAlreadyInit: u64 = 1;
+
+    const TAG_BODY: u128 = 0;
+    const TAG_DIVISIBILITY: u128 = 1;
+    const TAG_FLAGS: u128 = 2;
+    const TAG_SPACERS: u128 = 3;
+    const TAG_RUNE: u128 = 4;
+    const TAG_SYMBOL: u128 = 5;
+    const TAG_PREMINE: u128 = 6;
+    const TAG_CAP: u128 = 8;
+    const TAG_AMOUNT: u128 = 10;
+    const TAG_HEIGHT_START: u128 = 12;
+    const TAG_HEIGHT_END: u128 = 14;
+    const TAG_OFFSET_START: u128 = 16;
+    const TAG_OFFSET_END: u128 = 18;
+    const TAG_MINT: u128 = 20;
+    const TAG_POINTER: u128 = 22;
+
+    const FLAG_ETCHING: u128 = 1;
+    const FLAG_TERMS: u128 = 2;
+    const FLAG_TURBO: u128 = 4;
+
+    const MAX_DIVISIBILITY: u128 = 38;
+    const MAX_SPACERS: u128 = 134217727;
+    const MAX_CHAR: u128 = 0x10FFFF;
+    const SURROGATE_START: u128 = 0xD800;
+    const SURROGATE_END: u128 = 0xDFFF;
+    const U128_MAX: u128 = 340282366920938463463374607431768211455;
+    const U64_MAX: u64 = 18446744073709551615;
+    const U32_MAX: u128 = 4294967295;
+
+    /// The runes etched without a name get a reserved name, which is greater than or equal to this value
+    const RESERVED_RUNE: u128 = 6402364363415443603228541259936211926;
+    /// The commit transaction of the rune name must have at least this confirmations when the rune is etched
+    const COMMIT_CONFIRMATIONS: u64 = 6;
+    const SUBSIDY_HALVING_INTERVAL: u64 = 210000;
+    /// The shortest rune name length is decreased by one every interval, SUBSIDY_HALVING_INTERVAL / 12
+    const UNLOCK_INTERVAL: u64 = 17500;
+    /// The max varint length of a u128
+    const MAX_VARINT_LEN: u64 = 19;
+
+    const INSTRUCTION_PUSH: u8 = 0;
+    const INSTRUCTION_OP: u8 = 1;
+    const INSTRUCTION_ERROR: u8 = 2;
+
+    #[data_struct]
+    struct RuneId has store, copy, drop {
+        /// The height of the block which etched the rune
+        block: u64,
+        /// The index of the etching transaction in the block
+        tx: u32,
+    }
+
+    struct Terms has store, copy, drop {
+        amount: Option<u128>,
+        cap: Option<u128>,
+        height_start: Option<u64>,
+        height_end: Option<u64>,
+        offset_start: Option<u64>,
+        offset_end: Option<u64>,
+    }
+
+    struct Etching has store, copy, drop {
+        divisibility: Option<u8>,
+        premine: Option<u128>,
+        rune: Option<u128>,
+        spacers: Option<u32>,
+        symbol: Option<u32>,
+        terms: Option<Terms>,
+        turbo: bool,
+    }
+
+    struct Edict has store, copy, drop {
+        id: RuneId,
+        amount: u128,
+        output: u32,
+    }
+
+    /// The deciphered runestone of a transaction.
+    /// If the runestone is a cenotaph, the edicts and the pointer are ignored,
+    /// the etching only keeps the rune name, and all the input runes are burned.
+    struct Runestone has store, copy, drop {
+        edicts: vector<Edict>,
+        etching: Option<Etching>,
+        mint: Option<RuneId>,
+        pointer: Option<u32>,
+        cenotaph: bool,
+    }
+
+    /// The rune entry object, it is a child object of the RuneStore, the id is derived from the RuneId
+    struct Rune has key {
+        id: RuneId,
+        /// The etching sequence number of the rune
+        number: u64,
+        /// The rune name, the modified base-26 integer
+        rune: u128,
+        spacers: u32,
+        divisibility: u8,
+        /// The unicode code point of the currency symbol
+        symbol: Option<u32>,
+        premine: u128,
+        terms: Option<Terms>,
+        turbo: bool,
+        mints: u128,
+        burned: u128,
+        /// The txid of the etching transaction
+        etching: address,
+    }
+
+    #[data_struct]
+    struct RuneBalance has store, copy, drop {
+        id: RuneId,
+        amount: u128,
+    }
+
+    /// The rune balances of a UTXO, the object is attached to the UTXO as a seal,
+    /// and owned by the owner of the UTXO
+    struct RuneBalances has key {
+        outpoint: OutPoint,
+        balances: vector<RuneBalance>,
+    }
+
+    struct RuneStore has key {
+        /// The number of etched runes
+        runes: u64,
+    }
+
+    struct RuneEtchedEvent has copy, drop {
+        id: RuneId,
+        rune_obj_id: ObjectID,
+        rune: u128,
+        spacers: u32,
+        txid: address,
+    }
+
+    public(friend) fun genesis_init() {
+        let store_id = object::named_object_id<RuneStore>();
+        if (!object::exists_object(store_id)) {
+            let store_obj = object::new_named_object(RuneStore { runes: 0 });
+            object::to_shared(store_obj);
+        }
+    }
+
+    /// Init the RuneStore for the chain started before the runes support.
+    /// The rune balances are only tracked from the block which the RuneStore is created.
+    public fun init_for_upgrade(){
+        let store_id = object::named_object_id<RuneStore>();
+        assert!(!object::exists_object(store_id), ErrorAlreadyInit);
+        genesis_init()
+    }
+
+    fun borrow_mut_store(): &mut Object<RuneStore> {
+        let store_id = object::named_object_id<RuneStore>();
+        object::borrow_mut_object_shared<RuneStore>(store_id)
+    }
+
+    /// The runes are processed after the first rune height of the network, and the RuneStore is initialized
+    public fun is_runes_active(block_height: u64): bool {
+        block_height >= network::first_rune_height() && object::exists_object(object::named_object_id<RuneStore>())
+    }
+
+    // ======== Runestone ========
+
+    /// Decipher the runestone of the tx, return none if the tx does not contain a runestone
+    public fun decipher(tx: &Transaction): Option<Runestone> {
+        let (found, valid, payload) = payload(tx);
+        if (!found) {
+            return option::none()
+        };
+        if (!valid) {
+            return option::some(new_cenotaph(option::none(), option::none()))
+        };
+        let (ok, integers) = integers(&payload);
+        if (!ok) {
+            return option::some(new_cenotaph(option::none(), option::none()))
+        };
+        let output_len = (vector::length(types::tx_output(tx)) as u128);
+        let flaw = false;
+        let edicts = vector::empty<Edict>();
+        let fields = simple_multimap::new<u128, u128>();
+        let len = vector::length(&integers);
+        let i = 0;
+        while (i < len) {
+            let tag = *vector::borrow(&integers, i);
+            if (tag == TAG_BODY) {
+                let id = RuneId { block: 0, tx: 0 };
+                let j = i + 1;
+                while (j < len) {
+                    if (j + 4 > len) {
+                        // trailing integers
+                        flaw = true;
+                        break
+                    };
+                    let next = next_rune_id(&id, *vector::borrow(&integers, j), *vector::borrow(&integers, j + 1));
+                    if (option::is_none(&next)) {
+                        flaw = true;
+                        break
+                    };
+                    let output = *vector::borrow(&integers, j + 3);
+                    if (output > output_len) {
+                        flaw = true;
+                        break
+                    };
+                    id = option::destroy_some(next);
+                    vector::push_back(&mut edicts, Edict { id, amount: *vector::borrow(&integers, j + 2), output: (output as u32) });
+                    j = j + 4;
+                };
+                break
+            };
+            if (i + 1 >= len) {
+                // truncated field
+                flaw = true;
+                break
+            };
+            simple_multimap::add(&mut fields, tag, *vector::borrow(&integers, i + 1));
+            i = i + 2;
+        };
+
+        let flags = option::destroy_with_default(take_bounded(&mut fields, TAG_FLAGS, U128_MAX), 0);
+        let (is_etching, flags) = take_flag(flags, FLAG_ETCHING);
+        let (has_terms, flags) = take_flag(flags, FLAG_TERMS);
+        let (turbo, flags) = take_flag(flags, FLAG_TURBO);
+
+        let etching = if (is_etching) {
+            let terms = if (has_terms) {
+                option::some(Terms {
+                    cap: take_bounded(&mut fields, TAG_CAP, U128_MAX),
+                    height_start: to_u64_option(take_bounded(&mut fields, TAG_HEIGHT_START, (U64_MAX as u128))),
+                    height_end: to_u64_option(take_bounded(&mut fields, TAG_HEIGHT_END, (U64_MAX as u128))),
+                    amount: take_bounded(&mut fields, TAG_AMOUNT, U128_MAX),
+                    offset_start: to_u64_option(take_bounded(&mut fields, TAG_OFFSET_START, (U64_MAX as u128))),
+                    offset_end: to_u64_option(take_bounded(&mut fields, TAG_OFFSET_END, (U64_MAX as u128))),
+                })
+            } else {
+                option::none()
+            };
+            option::some(Etching {
+                divisibility: to_u8_option(take_bounded(&mut fields, TAG_DIVISIBILITY, MAX_DIVISIBILITY)),
+                premine: take_bounded(&mut fields, TAG_PREMINE, U128_MAX),
+                rune: take_bounded(&mut fields, TAG_RUNE, U128_MAX),
+                spacers: to_u32_option(take_bounded(&mut fields, TAG_SPACERS, MAX_SPACERS)),
+                symbol: take_symbol(&mut fields),
+                terms,
+                turbo,
+            })
+        } else {
+            option::none()
+        };
+
+        let mint = option::none<RuneId>();
+        let mint_values = field_values(&fields, TAG_MINT, 2);
+        if (option::is_some(&mint_values)) {
+            let mint_values = option::destroy_some(mint_values);
+            let block = *vector::borrow(&mint_values, 0);
+            let tx = *vector::borrow(&mint_values, 1);
+            if (block <= (U64_MAX as u128) && tx <= U32_MAX && !(block == 0 && tx > 0)) {
+                mint = option::some(RuneId { block: (block as u64), tx: (tx as u32) });
+                remove_field_values(&mut fields, TAG_MINT, 2);
+            };
+        };
+
+        let pointer = option::none<u32>();
+        let pointer_values = field_values(&fields, TAG_POINTER, 1);
+        if (option::is_some(&pointer_values)) {
+            let value = *vector::borrow(option::borrow(&pointer_values), 0);
+            if (value < output_len) {
+                pointer = option::some((value as u32));
+                remove_field_values(&mut fields, TAG_POINTER, 1);
+            };
+        };
+
+        if (option::is_some(&etching) && supply_overflow(option::borrow(&etching))) {
+            flaw = true;
+        };
+        if (flags != 0) {
+            // unrecognized flag
+            flaw = true;
+        };
+        let tags = simple_multimap::keys(&fields);
+        if (vector::any(&tags, |tag| *tag % 2 == 0)) {
+            // unrecognized even tag
+            flaw = true;
+        };
+
+        if (flaw) {
+            let rune = if (option::is_some(&etching)) {
+                option::borrow(&etching).rune
+            } else {
+                option::none()
+            };
+            return option::some(new_cenotaph(rune, mint))
+        };
+
+        option::some(Runestone {
+            edicts,
+            etching,
+            mint,
+            pointer,
+            cenotaph: false,
+        })
+    }
+
+    fun new_cenotaph(rune: Option<u128>, mint: Option<RuneId>): Runestone {
+        let etching = if (option::is_some(&rune)) {
+            option::some(Etching {
+                divisibility: option::none(),
+                premine: option::none(),
+                rune,
+                spacers: option::none(),
+                symbol: option::none(),
+                terms: option::none(),
+                turbo: false,
+            })
+        } else {
+            option::none()
+        };
+        Runestone {
+            edicts: vector::empty(),
+            etching,
+            mint,
+            pointer: option::none(),
+            cenotaph: true,
+        }
+    }
+
+    /// Find the payload in the first `OP_RETURN OP_13` output, return (found, valid, payload).
+    /// The payload is invalid if the script contains non-push opcodes or is malformed.
+    fun payload(tx: &Transaction): (bool, bool, vector<u8>) {
+        let outputs = types::tx_output(tx);
+        let i = 0;
+        let len = vector::length(outputs);
+        while (i < len) {
+            let script = script_buf::bytes(types::txout_script_pubkey(vector::borrow(outputs, i)));
+            let script_len = vector::length(script);
+            if (script_len >= 2
+                && *vector::borrow(script, 0) == opcode::op_return()
+                && *vector::borrow(script, 1) == opcode::op_pushnum_13()) {
+                let payload = vector::empty<u8>();
+                let pos = 2;
+                while (pos < script_len) {
+                    let (kind, data_start, data_len, next_pos) = next_instruction(script, pos);
+                    if (kind != INSTRUCTION_PUSH) {
+                        return (true, false, vector::empty())
+                    };
+                    vector::append(&mut payload, vector::slice(script, data_start, data_start + data_len));
+                    pos = next_pos;
+                };
+                return (true, true, payload)
+            };
+            i = i + 1;
+        };
+        (false, false, vector::empty())
+    }
+
+    /// Decode the script instruction at `pos`, return (kind, data start, data length, next position)
+    fun next_instruction(script: &vector<u8>, pos: u64): (u8, u64, u64, u64) {
+        let len = vector::length(script);
+        let op = *vector::borrow(script, pos);
+        let pos = pos + 1;
+        let (size_len, data_len) = if (op <= opcode::op_pushbytes_75()) {
+            (0, (op as u64))
+        } else if (op == opcode::op_pushdata1()) {
+            (1, 0)
+        } else if (op == opcode::op_pushdata2()) {
+            (2, 0)
+        } else if (op == opcode::op_pushdata4()) {
+            (4, 0)
+        } else {
+            return (INSTRUCTION_OP, pos, 0, pos)
+        };
+        if (pos + size_len > len) {
+            return (INSTRUCTION_ERROR, pos, 0, len)
+        };
+        let i = 0;
+        while (i < size_len) {
+            data_len = data_len | ((*vector::borrow(script, pos + i) as u64) << ((8 * i) as u8));
+            i = i + 1;
+        };
+        pos = pos + size_len;
+        if (pos + data_len > len) {
+            return (INSTRUCTION_ERROR, pos, 0, len)
+        };
+        (INSTRUCTION_PUSH, pos, data_len, pos + data_len)
+    }
+
+    fun integers(payload: &vector<u8>): (bool, vector<u128>) {
+        let integers = vector::empty<u128>();
+        let len = vector::length(payload);
+        let pos = 0;
+        while (pos < len) {
+            let (ok, value, next_pos) = decode_varint(payload, pos);
+            if (!ok) {
+                return (false, vector::empty())
+            };
+            vector::push_back(&mut integers, value);
+            pos = next_pos;
+        };
+        (true, integers)
+    }
+
+    /// Decode a LEB128 varint at `pos`, return (ok, value, next position)
+    fun decode_varint(buffer: &vector<u8>, pos: u64): (bool, u128, u64) {
+        let len = vector::length(buffer);
+        let n: u128 = 0;
+        let i = 0;
+        while (pos + i < len) {
+            if (i >= MAX_VARINT_LEN) {
+                // overlong
+                return (false, 0, pos)
+            };
+            let byte = (*vector::borrow(buffer, pos + i) as u128);
+            let value = byte & 0x7f;
+            if (i == MAX_VARINT_LEN - 1 && (value & 0x7c) != 0) {
+                // overflow
+                return (false, 0, pos)
+            };
+            n = n | (value << ((7 * i) as u8));
+            if ((byte & 0x80) == 0) {
+                return (true, n, pos + i + 1)
+            };
+            i = i + 1;
+        };
+        // unterminated
+        (false, 0, pos)
+    }
+
+    fun next_rune_id(id: &RuneId, block: u128, tx: u128): Option<RuneId> {
+        if (block > (U64_MAX as u128) || tx > U32_MAX || (id.block as u128) + block > (U64_MAX as u128)) {
+            return option::none()
+        };
+        let next_block = id.block + (block as u64);
+        let next_tx = if (block == 0) {
+            if ((id.tx as u128) + tx > U32_MAX) {
+                return option::none()
+            };
+            id.tx + (tx as u32)
+        } else {
+            (tx as u32)
+        };
+        option::some(RuneId { block: next_block, tx: next_tx })
+    }
+
+    fun take_flag(flags: u128, flag: u128): (bool, u128) {
+        if ((flags & flag) != 0) {
+            (true, flags - flag)
+        } else {
+            (false, flags)
+        }
+    }
+
+    fun field_values(fields: &SimpleMultiMap<u128, u128>, tag: u128, n: u64): Option<vector<u128>> {
+        if (!simple_multimap::contains_key(fields, &tag)) {
+            return option::none()
+        };
+        let values = simple_multimap::borrow(fields, &tag);
+        if (vector::length(values) < n) {
+            return option::none()
+        };
+        option::some(vector::slice(values, 0, n))
+    }
+
+    fun remove_field_values(fields: &mut SimpleMultiMap<u128, u128>, tag: u128, n: u64) {
+        let values = simple_multimap::borrow_mut(fields, &tag);
+        let i = 0;
+        while (i < n) {
+            vector::remove(values, 0);
+            i = i + 1;
+        };
+        let is_empty = vector::is_empty(values);
+        if (is_empty) {
+            let (_tag, _values) = simple_multimap::remove(fields, &tag);
+        };
+    }
+
+    /// Take the first value of the tag if it is not greater than `max`, the invalid value is left in the fields
+    fun take_bounded(fields: &mut SimpleMultiMap<u128, u128>, tag: u128, max: u128): Option<u128> {
+        let values = field_values(fields, tag, 1);
+        if (option::is_none(&values)) {
+            return option::none()
+        };
+        let value = *vector::borrow(option::borrow(&values), 0);
+        if (value > max) {
+            return option::none()
+        };
+        remove_field_values(fields, tag, 1);
+        option::some(value)
+    }
+
+    fun take_symbol(fields: &mut SimpleMultiMap<u128, u128>): Option<u32> {
+        let values = field_values(fields, TAG_SYMBOL, 1);
+        if (option::is_none(&values)) {
+            return option::none()
+        };
+        let value = *vector::borrow(option::borrow(&values), 0);
+        if (value > MAX_CHAR || (value >= SURROGATE_START && value <= SURROGATE_END)) {
+            return option::none()
+        };
+        remove_field_values(fields, TAG_SYMBOL, 1);
+        option::some((value as u32))
+    }
+
+    fun supply_overflow(etching: &Etching): bool {
+        let premine = option::destroy_with_default(etching.premine, 0);
+        let (cap, amount) = if (option::is_some(&etching.terms)) {
+            let terms = option::borrow(&etching.terms);
+            (option::destroy_with_default(terms.cap, 0), option::destroy_with_default(terms.amount, 0))
+        } else {
+            (0, 0)
+        };
+        if (cap != 0 && amount > U128_MAX / cap) {
+            return true
+        };
+        premine > U128_MAX - cap * amount
+    }
+
+    fun to_u8_option(value: Option<u128>): Option<u8> {
+        if (option::is_some(&value)) {
+            option::some((option::destroy_some(value) as u8))
+        } else {
+            option::none()
+        }
+    }
+
+    fun to_u32_option(value: Option<u128>): Option<u32> {
+        if (option::is_some(&value)) {
+            option::some((option::destroy_some(value) as u32))
+        } else {
+            option::none()
+        }
+    }
+
+    fun to_u64_option(value: Option<u128>): Option<u64> {
+        if (option::is_some(&value)) {
+            option::some((option::destroy_some(value) as u64))
+        } else {
+            option::none()
+        }
+    }
+
+    /// Return the commitment of the rune name if the runestone etches a named rune,
+    /// the etching tx must reveal the commitment in the tapscript of an input
+    public fun etching_commitment(runestone: &Option<Runestone>): Option<vector<u8>> {
+        if (option::is_none(runestone)) {
+            return option::none()
+        };
+        let etching = &option::borrow(runestone).etching;
+        if (option::is_none(etching) || option::is_none(&option::borrow(etching).rune)) {
+            return option::none()
+        };
+        option::some(commitment(*option::borrow(&option::borrow(etching).rune)))
+    }
+
+    /// The commitment of the rune name, the little endian bytes of the name with the trailing zeros trimmed
+    public fun commitment(rune: u128): vector<u8> {
+        let bytes = vector::empty<u8>();
+        while (rune > 0) {
+            vector::push_back(&mut bytes, ((rune & 0xff) as u8));
+            rune = rune >> 8;
+        };
+        bytes
+    }
+
+    /// Check whether the script pushes the data, the instructions after a malformed one are ignored
+    public fun script_contains_push(script: &ScriptBuf, data: &vector<u8>): bool {
+        let bytes = script_buf::bytes(script);
+        let len = vector::length(bytes);
+        let data_len = vector::length(data);
+        let pos = 0;
+        while (pos < len) {
+            let (kind, data_start, push_len, next_pos) = next_instruction(bytes, pos);
+            if (kind == INSTRUCTION_ERROR) {
+                break
+            };
+            if (kind == INSTRUCTION_PUSH && push_len == data_len && vector::slice(bytes, data_start, data_start + push_len) == *data) {
+                return true
+            };
+            pos = next_pos;
+        };
+        false
+    }
+
+    public fun commit_confirmations(): u64 {
+        COMMIT_CONFIRMATIONS
+    }
+
+    /// The minimum rune name which can be etched at the block height.
+    /// Before the first rune height, only the names with 13 or more letters are available,
+    /// then the minimum length is decreased by one every UNLOCK_INTERVAL blocks.
+    public fun minimum_at_height(block_height: u64): u128 {
+        let offset = block_height + 1;
+        let start = network::first_rune_height();
+        let end = start + SUBSIDY_HALVING_INTERVAL;
+        if (offset < start) {
+            return step(12)
+        };
+        if (offset >= end) {
+            return 0
+        };
+        let progress = offset - start;
+        let length = 12 - progress / UNLOCK_INTERVAL;
+        let end_step = step(length - 1);
+        let start_step = step(length);
+        let remainder = ((progress % UNLOCK_INTERVAL) as u128);
+        start_step - ((start_step - end_step) * remainder / (UNLOCK_INTERVAL as u128))
+    }
+
+    /// The value of the first rune name with `length + 1` letters
+    fun step(length: u64): u128 {
+        let value = 0;
+        let i = 0;
+        while (i < length) {
+            value = value * 26 + 26;
+            i = i + 1;
+        };
+        value
+    }
+
+    // ======== Process ========
+
+    /// Process the runestone of the tx, move the rune balances of the input UTXOs to the outputs,
+    /// and return the seals of the new RuneBalances objects.
+    /// `rune_committed` indicates whether the tx commits to the etched rune name in a mature taproot input.
+    public(friend) fun process_tx(pending_block: &Object<PendingBlock>, block_height: u64, tx: &Transaction, runestone: Option<Runestone>, rune_committed: bool, input_utxos: &mut vector<UTXO>): vector<SealOut> {
+        let unallocated = take_input_balances(input_utxos);
+        if (option::is_none(&runestone) && vector::is_empty(&unallocated)) {
+            return vector::empty()
+        };
+        let txid = types::tx_id(tx);
+        let outputs = types::tx_output(tx);
+        let output_len = vector::length(outputs);
+        let allocated = vector::empty<vector<RuneBalance>>();
+        let i = 0;
+        while (i < output_len) {
+            vector::push_back(&mut allocated, vector::empty());
+            i = i + 1;
+        };
+        let burned = vector::empty<RuneBalance>();
+        let is_cenotaph = false;
+        let pointer = option::none<u32>();
+
+        if (option::is_some(&runestone)) {
+            let runestone = option::destroy_some(runestone);
+            is_cenotaph = runestone.cenotaph;
+            pointer = runestone.pointer;
+            if (option::is_some(&runestone.mint)) {
+                let id = *option::borrow(&runestone.mint);
+                let minted = mint(block_height, id);
+                if (option::is_some(&minted)) {
+                    add_balance(&mut unallocated, id, option::destroy_some(minted));
+                };
+            };
+            let (etched, etched_id, etched_rune) = etched(pending_block, block_height, txid, &runestone, rune_committed);
+            if (!is_cenotaph) {
+                if (etched) {
+                    let premine = option::destroy_with_default(option::borrow(&runestone.etching).premine, 0);
+                    add_balance(&mut unallocated, etched_id, premine);
+                };
+                allocate_edicts(outputs, &runestone.edicts, etched, etched_id, &mut unallocated, &mut allocated);
+            };
+            if (etched) {
+                create_rune(etched_id, etched_rune, txid, &runestone);
+            };
+        };
+
+        if (is_cenotaph) {
+            vector::append(&mut burned, unallocated);
+        } else {
+            let vout = if (option::is_some(&pointer)) {
+                option::some((option::destroy_some(pointer) as u64))
+            } else {
+                first_non_op_return_output(outputs)
+            };
+            if (option::is_some(&vout)) {
+                let output_balances = vector::borrow_mut(&mut allocated, option::destroy_some(vout));
+                vector::for_each(unallocated, |balance| {
+                    let RuneBalance { id, amount } = balance;
+                    add_balance(output_balances, id, amount);
+                });
+            } else {
+                vector::append(&mut burned, unallocated);
+            };
+        };
+
+        let seal_outs = vector::empty<SealOut>();
+        let vout = 0;
+        while (vout < output_len) {
+            let balances = *vector::borrow(&allocated, vout);
+            if (!vector::is_empty(&balances)) {
+                let txout = vector::borrow(outputs, vout);
+                if (script_buf::is_op_return(types::txout_script_pubkey(txout))) {
+                    vector::append(&mut burned, balances);
+                } else {
+                    let outpoint = types::new_outpoint(txid, (vout as u32));
+                    let object_id = create_balances_object(outpoint, balances, types::txout_object_address(txout));
+                    let seal = utxo::new_utxo_seal(type_info::type_name<RuneBalances>(), object_id);
+                    vector::push_back(&mut seal_outs, utxo::new_seal_out((vout as u32), seal));
+                };
+            };
+            vout = vout + 1;
+        };
+
+        vector::for_each(burned, |balance| {
+            let RuneBalance { id, amount } = balance;
+            burn(id, amount);
+        });
+        seal_outs
+    }
+
+    fun allocate_edicts(outputs: &vector<TxOut>, edicts: &vector<Edict>, etched: bool, etched_id: RuneId, unallocated: &mut vector<RuneBalance>, allocated: &mut vector<vector<RuneBalance>>) {
+        let output_len = vector::length(outputs);
+        let i = 0;
+        let len = vector::length(edicts);
+        while (i < len) {
+            let edict = *vector::borrow(edicts, i);
+            i = i + 1;
+            let id = edict.id;
+            if (id.block == 0 && id.tx == 0) {
+                if (!etched) {
+                    continue
+                };
+                id = etched_id;
+            };
+            let balance = balance_of(unallocated, &id);
+            if (balance == 0) {
+                continue
+            };
+            if ((edict.output as u64) == output_len) {
+                let destinations = non_op_return_outputs(outputs);
+                let destination_len = vector::length(&destinations);
+                let j = 0;
+                if (edict.amount == 0) {
+                    // divide the balance between the non OP_RETURN outputs, the remainder goes to the first outputs
+                    let amount = if (destination_len > 0) { balance / (destination_len as u128) } else { 0 };
+                    let remainder = if (destination_len > 0) { ((balance % (destination_len as u128)) as u64) } else { 0 };
+                    while (j < destination_len) {
+                        let output_amount = if (j < remainder) { amount + 1 } else { amount };
+                        allocate(unallocated, allocated, id, output_amount, *vector::borrow(&destinations, j));
+                        j = j + 1;
+                    };
+                } else {
+                    // distribute the amount to each non OP_RETURN output while the balance lasts
+                    while (j < destination_len) {
+                        let output_amount = min(edict.amount, balance_of(unallocated, &id));
+                        allocate(unallocated, allocated, id, output_amount, *vector::borrow(&destinations, j));
+                        j = j + 1;
+                    };
+                };
+            } else {
+                let amount = if (edict.amount == 0) { balance } else { min(edict.amount, balance) };
+                allocate(unallocated, allocated, id, amount, (edict.output as u64));
+            };
+        };
+    }
+
+    fun allocate(unallocated: &mut vector<RuneBalance>, allocated: &mut vector<vector<RuneBalance>>, id: RuneId, amount: u128, output: u64) {
+        if (amount > 0) {
+            sub_balance(unallocated, &id, amount);
+            add_balance(vector::borrow_mut(allocated, output), id, amount);
+        };
+    }
+
+    fun mint(block_height: u64, id: RuneId): Option<u128> {
+        let rune_obj_id = rune_object_id(id);
+        if (!object::exists_object_with_type<Rune>(rune_obj_id)) {
+            return option::none()
+        };
+        let rune_obj = object::borrow_mut_object_extend<Rune>(rune_obj_id);
+        let rune = object::borrow_mut(rune_obj);
+        if (option::is_none(&rune.terms)) {
+            return option::none()
+        };
+        let terms = *option::borrow(&rune.terms);
+        let start = mint_start(rune.id.block, &terms);
+        if (option::is_some(&start) && block_height < option::destroy_some(start)) {
+            return option::none()
+        };
+        let end = mint_end(rune.id.block, &terms);
+        if (option::is_some(&end) && block_height >= option::destroy_some(end)) {
+            return option::none()
+        };
+        if (rune.mints >= option::destroy_with_default(terms.cap, 0)) {
+            return option::none()
+        };
+        rune.mints = rune.mints + 1;
+        option::some(option::destroy_with_default(terms.amount, 0))
+    }
+
+    fun mint_start(etching_height: u64, terms: &Terms): Option<u64> {
+        let relative = if (option::is_some(&terms.offset_start)) {
+            option::some(saturating_add(etching_height, *option::borrow(&terms.offset_start)))
+        } else {
+            option::none()
+        };
+        if (option::is_some(&relative) && option::is_some(&terms.height_start)) {
+            option::some(max(option::destroy_some(relative), *option::borrow(&terms.height_start)))
+        } else if (option::is_some(&relative)) {
+            relative
+        } else {
+            terms.height_start
+        }
+    }
+
+    fun mint_end(etching_height: u64, terms: &Terms): Option<u64> {
+        let relative = if (option::is_some(&terms.offset_end)) {
+            option::some(saturating_add(etching_height, *option::borrow(&terms.offset_end)))
+        } else {
+            option::none()
+        };
+        if (option::is_some(&relative) && option::is_some(&terms.height_end)) {
+            let relative = option::destroy_some(relative);
+            let absolute = *option::borrow(&terms.height_end);
+            option::some(if (relative < absolute) { relative } else { absolute })
+        } else if (option::is_some(&relative)) {
+            relative
+        } else {
+            terms.height_end
+        }
+    }
+
+    /// Return (etched, rune id, rune name) of the etching in the runestone
+    fun etched(pending_block: &Object<PendingBlock>, block_height: u64, txid: address, runestone: &Runestone, rune_committed: bool): (bool, RuneId, u128) {
+        if (option::is_none(&runestone.etching)) {
+            return (false, RuneId { block: 0, tx: 0 }, 0)
+        };
+        let etching = option::borrow(&runestone.etching);
+        let tx_index = (pending_block::tx_index(pending_block, txid) as u32);
+        let rune = if (option::is_some(&etching.rune)) {
+            let rune = *option::borrow(&etching.rune);
+            if (rune < minimum_at_height(block_height) || rune >= RESERVED_RUNE || exists_rune_name(rune) || !rune_committed) {
+                return (false, RuneId { block: 0, tx: 0 }, 0)
+            };
+            rune
+        } else {
+            RESERVED_RUNE + (((block_height as u128) << 32) | (tx_index as u128))
+        };
+        (true, RuneId { block: block_height, tx: tx_index }, rune)
+    }
+
+    fun create_rune(id: RuneId, rune: u128, txid: address, runestone: &Runestone) {
+        let store_obj = borrow_mut_store();
+        let store = object::borrow_mut(store_obj);
+        let number = store.runes;
+        store.runes = number + 1;
+        let entry = if (runestone.cenotaph) {
+            Rune {
+                id,
+                number,
+                rune,
+                spacers: 0,
+                divisibility: 0,
+                symbol: option::none(),
+                premine: 0,
+                terms: option::none(),
+                turbo: false,
+                mints: 0,
+                burned: 0,
+                etching: txid,
+            }
+        } else {
+            let etching = option::borrow(&runestone.etching);
+            Rune {
+                id,
+                number,
+                rune,
+                spacers: option::destroy_with_default(etching.spacers, 0),
+                divisibility: option::destroy_with_default(etching.divisibility, 0),
+                symbol: etching.symbol,
+                premine: option::destroy_with_default(etching.premine, 0),
+                terms: etching.terms,
+                turbo: etching.turbo,
+                mints: 0,
+                burned: 0,
+                etching: txid,
+            }
+        };
+        let spacers = entry.spacers;
+        // record a rune name to RuneId mapping
+        object::add_field(store_obj, rune, id);
+        let rune_obj = object::new_with_parent_and_id(store_obj, id, entry);
+        let rune_obj_id = object::id(&rune_obj);
+        object::transfer_extend(rune_obj, @bitcoin_move);
+        event::emit(RuneEtchedEvent {
+            id,
+            rune_obj_id,
+            rune,
+            spacers,
+            txid,
+        });
+    }
+
+    fun burn(id: RuneId, amount: u128) {
+        let rune_obj_id = rune_object_id(id);
+        if (amount == 0 || !object::exists_object_with_type<Rune>(rune_obj_id)) {
+            return
+        };
+        let rune_obj = object::borrow_mut_object_extend<Rune>(rune_obj_id);
+        let rune = object::borrow_mut(rune_obj);
+        rune.burned = rune.burned + amount;
+    }
+
+    /// Take the RuneBalances seals from the input UTXOs and merge the balances
+    fun take_input_balances(input_utxos: &mut vector<UTXO>): vector<RuneBalance> {
+        let unallocated = vector::empty<RuneBalance>();
+        let i = 0;
+        let len = vector::length(input_utxos);
+        while (i < len) {
+            let input_utxo = vector::borrow_mut(input_utxos, i);
+            let object_ids = utxo::remove_seals_internal<RuneBalances>(input_utxo);
+            vector::for_each(object_ids, |object_id| {
+                let RuneBalances { outpoint: _, balances } = object::remove(object::take_object_extend<RuneBalances>(object_id));
+                vector::for_each(balances, |balance| {
+                    let RuneBalance { id, amount } = balance;
+                    add_balance(&mut unallocated, id, amount);
+                });
+            });
+            i = i + 1;
+        };
+        unallocated
+    }
+
+    fun create_balances_object(outpoint: OutPoint, balances: vector<RuneBalance>, owner: address): ObjectID {
+        let store_obj = borrow_mut_store();
+        let object_id = object::custom_object_id_with_parent<OutPoint, RuneBalances>(object::id(store_obj), outpoint);
+        if (object::exists_object(object_id)) {
+            //Before BIP34, some coinbase txid may be reused, we need to remove the old balances
+            let RuneBalances { outpoint: _, balances: _ } = object::remove(object::take_object_extend<RuneBalances>(object_id));
+        };
+        let obj = object::new_with_parent_and_id(store_obj, outpoint, RuneBalances { outpoint, balances });
+        let object_id = object::id(&obj);
+        object::transfer_extend(obj, owner);
+        object_id
+    }
+
+    fun add_balance(balances: &mut vector<RuneBalance>, id: RuneId, amount: u128) {
+        if (amount == 0) {
+            return
+        };
+        let i = 0;
+        let len = vector::length(balances);
+        while (i < len) {
+            let balance = vector::borrow_mut(balances, i);
+            if (balance.id == id) {
+                balance.amount = balance.amount + amount;
+                return
+            };
+            i = i + 1;
+        };
+        vector::push_back(balances, RuneBalance { id, amount });
+    }
+
+    fun sub_balance(balances: &mut vector<RuneBalance>, id: &RuneId, amount: u128) {
+        let i = 0;
+        let len = vector::length(balances);
+        while (i < len) {
+            let balance = vector::borrow_mut(balances, i);
+            if (balance.id == *id) {
+                balance.amount = balance.amount - amount;
+                if (balance.amount == 0) {
+                    vector::remove(balances, i);
+                };
+                return
+            };
+            i = i + 1;
+        };
+    }
+
+    fun balance_of(balances: &vector<RuneBalance>, id: &RuneId): u128 {
+        let i = 0;
+        let len = vector::length(balances);
+        while (i < len) {
+            let balance = vector::borrow(balances, i);
+            if (balance.id == *id) {
+                return balance.amount
+            };
+            i = i + 1;
+        };
+        0
+    }
+
+    fun non_op_return_outputs(outputs: &vector<TxOut>): vector<u64> {
+        let destinations = vector::empty<u64>();
+        let i = 0;
+        let len = vector::length(outputs);
+        while (i < len) {
+            if (!script_buf::is_op_return(types::txout_script_pubkey(vector::borrow(outputs, i)))) {
+                vector::push_back(&mut destinations, i);
+            };
+            i = i + 1;
+        };
+        destinations
+    }
+
+    fun first_non_op_return_output(outputs: &vector<TxOut>): Option<u64> {
+        let i = 0;
+        let len = vector::length(outputs);
+        while (i < len) {
+            if (!script_buf::is_op_return(types::txout_script_pubkey(vector::borrow(outputs, i)))) {
+                return option::some(i)
+            };
+            i = i + 1;
+        };
+        option::none()
+    }
+
+    fun min(a: u128, b: u128): u128 {
+        if (a < b) { a } else { b }
+    }
+
+    fun max(a: u64, b: u64): u64 {
+        if (a > b) { a } else { b }
+    }
+
+    fun saturating_add(a: u64, b: u64): u64 {
+        if (b > U64_MAX - a) { U64_MAX } else { a + b }
+    }
+
+    // ======== Rune ========
+
+    public fun new_rune_id(block: u64, tx: u32): RuneId {
+        RuneId { block, tx }
+    }
+
+    public fun rune_id_block(id: &RuneId): u64 {
+        id.block
+    }
+
+    public fun rune_id_tx(id: &RuneId): u32 {
+        id.tx
+    }
+
+    public fun rune_object_id(id: RuneId): ObjectID {
+        let store_id = object::named_object_id<RuneStore>();
+        object::custom_object_id_with_parent<RuneId, Rune>(store_id, id)
+    }
+
+    public fun exists_rune(id: RuneId): bool {
+        object::exists_object_with_type<Rune>(rune_object_id(id))
+    }
+
+    public fun borrow_rune(id: RuneId): &Rune {
+        let rune_obj = object::borrow_object<Rune>(rune_object_id(id));
+        object::borrow(rune_obj)
+    }
+
+    public fun exists_rune_name(rune: u128): bool {
+        let store_id = object::named_object_id<RuneStore>();
+        let store_obj = object::borrow_object<RuneStore>(store_id);
+        object::contains_field(store_obj, rune)
+    }
+
+    /// Get the RuneId via the rune name
+    public fun rune_id_by_name(rune: u128): Option<RuneId> {
+        let store_id = object::named_object_id<RuneStore>();
+        let store_obj = object::borrow_object<RuneStore>(store_id);
+        if (object::contains_field(store_obj, rune)) {
+            option::some(*object::borrow_field(store_obj, rune))
+        } else {
+            option::none()
+        }
+    }
+
+    public fun rune_number(rune: &Rune): u64 {
+        rune.number
+    }
+
+    public fun rune_name(rune: &Rune): u128 {
+        rune.rune
+    }
+
+    public fun rune_spacers(rune: &Rune): u32 {
+        rune.spacers
+    }
+
+    public fun rune_divisibility(rune: &Rune): u8 {
+        rune.divisibility
+    }
+
+    public fun rune_symbol(rune: &Rune): Option<u32> {
+        rune.symbol
+    }
+
+    public fun rune_premine(rune: &Rune): u128 {
+        rune.premine
+    }
+
+    public fun rune_mints(rune: &Rune): u128 {
+        rune.mints
+    }
+
+    public fun rune_burned(rune: &Rune): u128 {
+        rune.burned
+    }
+
+    public fun rune_etching(rune: &Rune): address {
+        rune.etching
+    }
+
+    /// The spaced rune name, such as `UNCOMMON•GOODS`
+    public fun spaced_rune_name(rune: &Rune): String {
+        format_spaced_rune(rune.rune, rune.spacers)
+    }
+
+    /// Format the rune name, the name is a modified base-26 integer, `A` is 0, `Z` is 25, `AA` is 26
+    public fun format_rune(rune: u128): String {
+        format_spaced_rune(rune, 0)
+    }
+
+    public fun format_spaced_rune(rune: u128, spacers: u32): String {
+        let letters = vector::empty<u8>();
+        if (rune == U128_MAX) {
+            letters = b"BCGDENLQRQWDSLRUGSNLBTMFIJAV";
+        } else {
+            let n = rune + 1;
+            while (n > 0) {
+                vector::push_back(&mut letters, ((((n - 1) % 26) as u8) + 65));
+                n = (n - 1) / 26;
+            };
+            vector::reverse(&mut letters);
+        };
+        let name = vector::empty<u8>();
+        let len = vector::length(&letters);
+        let i = 0;
+        while (i < len) {
+            vector::push_back(&mut name, *vector::borrow(&letters, i));
+            if (i + 1 < len && i < 32 && (spacers & (1 << (i as u8))) != 0) {
+                // the spacer `•`
+                vector::append(&mut name, x"e280a2");
+            };
+            i = i + 1;
+        };
+        string::utf8(name)
+    }
+
+    // ======== RuneBalances ========
+
+    public fun balances_object_id(outpoint: OutPoint): ObjectID {
+        let store_id = object::named_object_id<RuneStore>();
+        object::custom_object_id_with_parent<OutPoint, RuneBalances>(store_id, outpoint)
+    }
+
+    /// Get the rune balances of the UTXO
+    public fun balances_of(outpoint: OutPoint): vector<RuneBalance> {
+        let object_id = balances_object_id(outpoint);
+        if (!object::exists_object_with_type<RuneBalances>(object_id)) {
+            return vector::empty()
+        };
+        let balances_obj = object::borrow_object<RuneBalances>(object_id);
+        object::borrow(balances_obj).balances
+    }
+
+    public fun balance_id(balance: &RuneBalance): RuneId {
+        balance.id
+    }
+
+    public fun balance_amount(balance: &RuneBalance): u128 {
+        balance.amount
+    }
+
+    #[test_only]
+    fun encode_varint(n: u128): vector<u8> {
+        let bytes = vector::empty<u8>();
+        while ((n >> 7) > 0) {
+            vector::push_back(&mut bytes, (((n & 0x7f) as u8) | 0x80));
+            n = n >> 7;
+        };
+        vector::push_back(&mut bytes, (n as u8));
+        bytes
+    }
+
+    #[test_only]
+    public fun runestone_script_for_test(integers: vector<u128>): ScriptBuf {
+        let payload = vector::empty<u8>();
+        vector::for_each(integers, |n| {
+            vector::append(&mut payload, encode_varint(n));
+        });
+        let script = script_buf::new(vector[opcode::op_return(), opcode::op_pushnum_13()]);
+        script_buf::push_data(&mut script, payload);
+        script
+    }
+
+    #[test_only]
+    fun runestone_tx(script: ScriptBuf): Transaction {
+        let outputs = vector[
+            types::new_txout_for_test(546, script_buf::new(x"5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c")),
+            types::new_txout_for_test(0, script),
+        ];
+        types::new_tx_for_test(outputs)
+    }
+
+    #[test]
+    fun test_varint() {
+        let values = vector[0, 1, 127, 128, 16383, 16384, 1000000, U128_MAX];
+        vector::for_each(values, |n| {
+            let bytes = encode_varint(n);
+            let (ok, value, pos) = decode_varint(&bytes, 0);
+            assert!(ok, 1);
+            assert!(value == n, 2);
+            assert!(pos == vector::length(&bytes), 3);
+        });
+        let (ok, value, _) = decode_varint(&x"ff7f", 0);
+        assert!(ok && value == 16383, 4);
+        // unterminated
+        let (ok, _, _) = decode_varint(&x"80", 0);
+        assert!(!ok, 5);
+        // overflow
+        let (ok, _, _) = decode_varint(&x"ffffffffffffffffffffffffffffffffffffff04", 0);
+        assert!(!ok, 6);
+        // overlong
+        let (ok, _, _) = decode_varint(&x"8080808080808080808080808080808080808000", 0);
+        assert!(!ok, 7);
+    }
+
+    #[test]
+    fun test_decipher_etching() {
+        // flags: etching | terms, rune: AB, premine: 1000, cap: 10, amount: 100, edict 0:0 all to the output 0
+        let tx = runestone_tx(runestone_script_for_test(vector[
+            TAG_FLAGS, FLAG_ETCHING | FLAG_TERMS,
+            TAG_RUNE, 27,
+            TAG_PREMINE, 1000,
+            TAG_CAP, 10,
+            TAG_AMOUNT, 100,
+            TAG_SPACERS, 1,
+            TAG_BODY, 0, 0, 0, 0,
+        ]));
+        let runestone = option::destroy_some(decipher(&tx));
+        assert!(!runestone.cenotaph, 1);
+        let etching = option::destroy_some(runestone.etching);
+        assert!(etching.rune == option::some(27), 2);
+        assert!(etching.premine == option::some(1000), 3);
+        assert!(etching.spacers == option::some(1), 4);
+        let terms = option::destroy_some(etching.terms);
+        assert!(terms.cap == option::some(10), 5);
+        assert!(terms.amount == option::some(100), 6);
+        assert!(vector::length(&runestone.edicts) == 1, 7);
+        let edict = vector::borrow(&runestone.edicts, 0);
+        assert!(edict.id == RuneId { block: 0, tx: 0 } && edict.amount == 0 && edict.output == 0, 8);
+        assert!(option::is_none(&runestone.mint), 9);
+    }
+
+    #[test]
+    fun test_decipher_mint_and_edicts() {
+        let tx = runestone_tx(runestone_script_for_test(vector[
+            TAG_MINT, 840000,
+            TAG_MINT, 3,
+            TAG_POINTER, 0,
+            TAG_BODY, 840000, 3, 10, 0, 0, 2, 20, 2,
+        ]));
+        let runestone = option::destroy_some(decipher(&tx));
+        assert!(!runestone.cenotaph, 1);
+        assert!(runestone.mint == option::some(RuneId { block: 840000, tx: 3 }), 2);
+        assert!(runestone.pointer == option::some(0), 3);
+        assert!(vector::length(&runestone.edicts) == 2, 4);
+        // the tx of the second edict id is the delta of the previous one
+        let edict = vector::borrow(&runestone.edicts, 1);
+        assert!(edict.id == RuneId { block: 840000, tx: 5 } && edict.amount == 20 && edict.output == 2, 5);
+    }
+
+    #[test]
+    fun test_decipher_cenotaph() {
+        let tx = types::new_tx_for_test(vector[types::new_txout_for_test(0, script_buf::new(x"6a5d51"))]);
+        assert!(option::destroy_some(decipher(&tx)).cenotaph, 1);
+
+        // unrecognized even tag
+        let tx = runestone_tx(runestone_script_for_test(vector[TAG_CAP, 1]));
+        assert!(option::destroy_some(decipher(&tx)).cenotaph, 2);
+
+        // edict output is greater than the output count
+        let tx = runestone_tx(runestone_script_for_test(vector[TAG_BODY, 1, 1, 1, 3]));
+        assert!(option::destroy_some(decipher(&tx)).cenotaph, 3);
+
+        // the rune name is kept in the cenotaph
+        let tx = runestone_tx(runestone_script_for_test(vector[TAG_FLAGS, FLAG_ETCHING | 8, TAG_RUNE, 27]));
+        let runestone = option::destroy_some(decipher(&tx));
+        assert!(runestone.cenotaph, 4);
+        assert!(option::destroy_some(runestone.etching).rune == option::some(27), 5);
+
+        // unrecognized odd tag is ignored
+        let tx = runestone_tx(runestone_script_for_test(vector[TAG_SYMBOL, SURROGATE_START]));
+        assert!(!option::destroy_some(decipher(&tx)).cenotaph, 6);
+
+        let tx = types::new_tx_for_test(vector[types::new_txout_for_test(0, script_buf::new(x"6a51"))]);
+        assert!(option::is_none(&decipher(&tx)), 7);
+    }
+
+    #[test]
+    fun test_format_rune() {
+        assert!(format_rune(0) == string::utf8(b"A"), 1);
+        assert!(format_rune(25) == string::utf8(b"Z"), 2);
+        assert!(format_rune(26) == string::utf8(b"AA"), 3);
+        assert!(format_rune(U128_MAX) == string::utf8(b"BCGDENLQRQWDSLRUGSNLBTMFIJAV"), 4);
+        assert!(format_spaced_rune(27, 1) == string::utf8(x"41e280a242"), 5);
+        assert!(commitment(0) == vector::empty(), 6);
+        assert!(commitment(256) == x"0001", 7);
+    }
+
+    #[test]
+    fun test_script_contains_push() {
+        let script = script_buf::new(x"20a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684cac0063021b0168");
+        assert!(script_contains_push(&script, &commitment(283)), 1);
+        assert!(!script_contains_push(&script, &commitment(27)), 2);
+    }
+}
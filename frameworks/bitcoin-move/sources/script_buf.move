@@ -153,6 +153,13 @@ module bitcoin_move::script_buf{
             && push_opbyte == ((script_len - 2) as u8)
     }
 
+    /// Checks if the script is a pay to taproot script, the version 1 witness program with 32 bytes.
+    public fun is_p2tr(self: &ScriptBuf): bool{
+        vector::length(&self.bytes) == 34
+            && *vector::borrow(&self.bytes, 0) == opcode::op_pushnum_1()
+            && *vector::borrow(&self.bytes, 1) == opcode::op_pushbytes_32()
+    }
+
     /// Get the witness program from a witness program script.
     public fun witness_program(self: &ScriptBuf): vector<u8>{
         vector::slice(&self.bytes, 2, vector::length(&self.bytes))
@@ -379,6 +386,7 @@ module bitcoin_move::script_buf{
         assert!(is_witness_program(&sb), 1002);
         //std::debug::print(&witness_program(&sb));
         assert!(witness_program(&sb) == x"a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c", 1003);
+        assert!(is_p2tr(&sb), 1004);
     }
 
     #[test]
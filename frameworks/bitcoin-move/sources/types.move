@@ -394,6 +394,49 @@ module bitcoin_move::types {
         }
     }
 
+    #[test_only]
+    public fun new_tx_for_test(output: vector<TxOut>): Transaction {
+        let id = moveos_std::tx_context::fresh_address_for_testing();
+        Transaction {
+            id,
+            version: 2u32,
+            lock_time: 0u32,
+            input: vector::empty(),
+            output,
+        }
+    }
+
+    #[test_only]
+    public fun new_tx_with_input_for_test(input: vector<TxIn>, output: vector<TxOut>): Transaction {
+        let id = moveos_std::tx_context::fresh_address_for_testing();
+        Transaction {
+            id,
+            version: 2u32,
+            lock_time: 0u32,
+            input,
+            output,
+        }
+    }
+
+    #[test_only]
+    public fun new_txin_for_test(previous_output: OutPoint, witness: vector<vector<u8>>): TxIn {
+        TxIn {
+            previous_output,
+            script_sig: vector::empty(),
+            sequence: U32_MAX,
+            witness: Witness { witness },
+        }
+    }
+
+    #[test_only]
+    public fun new_txout_for_test(value: u64, script_pubkey: ScriptBuf): TxOut {
+        TxOut {
+            value,
+            script_pubkey,
+            recipient_address: bitcoin_address::empty(),
+        }
+    }
+
     #[test_only]
     public fun new_block_for_test(header: Header, txdata: vector<Transaction>): Block {
         Block {
@@ -0,0 +1,295 @@
+#[test_only]
+module bitcoin_move::runes_test {
+
+    use std::string;
+    use std::vector;
+    use std::option;
+    use moveos_std::object;
+    use rooch_framework::bitcoin_address;
+    use bitcoin_move::types::{Self, Transaction, TxIn, TxOut, OutPoint};
+    use bitcoin_move::script_buf;
+    use bitcoin_move::bitcoin;
+    use bitcoin_move::utxo;
+    use bitcoin_move::runes::{Self, RuneId, RuneBalances};
+    use bitcoin_move::genesis;
+
+    const TAG_BODY: u128 = 0;
+    const TAG_DIVISIBILITY: u128 = 1;
+    const TAG_FLAGS: u128 = 2;
+    const TAG_RUNE: u128 = 4;
+    const TAG_PREMINE: u128 = 6;
+    const TAG_CAP: u128 = 8;
+    const TAG_AMOUNT: u128 = 10;
+    const TAG_HEIGHT_START: u128 = 12;
+    const TAG_HEIGHT_END: u128 = 14;
+    const TAG_MINT: u128 = 20;
+    const TAG_POINTER: u128 = 22;
+
+    const FLAG_ETCHING: u128 = 1;
+    const FLAG_TERMS: u128 = 2;
+
+    /// The first rune name with 13 letters, AAAAAAAAAAAAA
+    const FIRST_THIRTEEN_LETTERS_RUNE: u128 = 99246114928149462;
+    const TAPROOT_SCRIPT: vector<u8> = x"5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c";
+
+    fun mine_block(block_height: u64, txs: vector<Transaction>) {
+        let miner = bitcoin_address::from_string(&string::utf8(b"bc1pevdrc8yqmgd94h2mpz9st0u77htmx935hzck3ruwsvcf4w7wrnqqd0yvze"));
+        let (header, txdata) = types::unpack_block(types::fake_block_for_test(0, miner));
+        vector::append(&mut txdata, txs);
+        bitcoin::execute_l1_block_with_txs_for_test(block_height, types::new_block_for_test(header, txdata));
+    }
+
+    fun taproot_output(): TxOut {
+        types::new_txout_for_test(546, script_buf::new(TAPROOT_SCRIPT))
+    }
+
+    fun spend(outpoints: vector<OutPoint>): vector<TxIn> {
+        vector::map(outpoints, |outpoint| types::new_txin_for_test(outpoint, vector::empty()))
+    }
+
+    /// The tx has `taproot_outputs` taproot outputs, and the runestone output is the last one
+    fun runestone_tx(input: vector<TxIn>, taproot_outputs: u64, integers: vector<u128>): Transaction {
+        let output = vector::empty();
+        let i = 0;
+        while (i < taproot_outputs) {
+            vector::push_back(&mut output, taproot_output());
+            i = i + 1;
+        };
+        vector::push_back(&mut output, types::new_txout_for_test(0, runes::runestone_script_for_test(integers)));
+        types::new_tx_with_input_for_test(input, output)
+    }
+
+    /// Spend the taproot output of the commit tx, and reveal the rune name commitment in the tapscript
+    fun reveal_input(commit_outpoint: OutPoint, rune: u128): TxIn {
+        let tapscript = script_buf::new(x"20a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684cac0063");
+        script_buf::push_data(&mut tapscript, runes::commitment(rune));
+        script_buf::push_opcode(&mut tapscript, 0x68);
+        let signature = vector::empty<u8>();
+        let control_block = x"c0a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c";
+        let i = 0;
+        while (i < 64) {
+            vector::push_back(&mut signature, 1);
+            i = i + 1;
+        };
+        types::new_txin_for_test(commit_outpoint, vector[signature, *script_buf::bytes(&tapscript), control_block])
+    }
+
+    fun outpoint(tx: &Transaction, vout: u32): OutPoint {
+        types::new_outpoint(types::tx_id(tx), vout)
+    }
+
+    fun assert_balance(outpoint: OutPoint, id: RuneId, amount: u128) {
+        let balances = runes::balances_of(outpoint);
+        assert!(vector::length(&balances) == 1, 100);
+        let balance = vector::borrow(&balances, 0);
+        assert!(runes::balance_id(balance) == id, 101);
+        assert!(runes::balance_amount(balance) == amount, 102);
+        let utxo = object::borrow(utxo::borrow_utxo(outpoint));
+        assert!(utxo::get_seals<RuneBalances>(utxo) == vector[runes::balances_object_id(outpoint)], 103);
+    }
+
+    fun assert_no_balance(outpoint: OutPoint) {
+        assert!(vector::is_empty(&runes::balances_of(outpoint)), 104);
+        if (utxo::exists_utxo(outpoint)) {
+            assert!(!utxo::has_seal<RuneBalances>(object::borrow(utxo::borrow_utxo(outpoint))), 105);
+        };
+    }
+
+    #[test]
+    fun test_minimum_at_height() {
+        genesis::init_for_test();
+        // the first rune height of the test network is 0, the 13 letters names are locked before it
+        assert!(string::length(&runes::format_rune(runes::minimum_at_height(0))) == 12, 1);
+        assert!(runes::format_rune(runes::minimum_at_height(17500)) == string::utf8(b"AAAAAAAAAAAA"), 2);
+        assert!(string::length(&runes::format_rune(runes::minimum_at_height(17500 * 11))) == 1, 3);
+        assert!(runes::minimum_at_height(209999) == 0, 4);
+    }
+
+    #[test]
+    fun test_rune_name_not_exists() {
+        genesis::init_for_test();
+        assert!(!runes::exists_rune_name(27), 1);
+        assert!(!runes::exists_rune(runes::new_rune_id(840000, 1)), 2);
+    }
+
+    #[test]
+    fun test_etch_with_premine() {
+        genesis::init_for_test();
+        // the etching without a rune name gets a reserved name, no commitment is required
+        let etch_tx = runestone_tx(vector::empty(), 2, vector[TAG_FLAGS, FLAG_ETCHING, TAG_DIVISIBILITY, 2, TAG_PREMINE, 1000]);
+        mine_block(1, vector[etch_tx]);
+
+        // the coinbase tx is the first tx of the block
+        let id = runes::new_rune_id(1, 1);
+        assert!(runes::exists_rune(id), 1);
+        let rune = runes::borrow_rune(id);
+        assert!(runes::rune_number(rune) == 0, 2);
+        assert!(runes::rune_divisibility(rune) == 2, 3);
+        assert!(runes::rune_premine(rune) == 1000, 4);
+        assert!(runes::rune_mints(rune) == 0, 5);
+        assert!(runes::rune_burned(rune) == 0, 6);
+        assert!(runes::rune_etching(rune) == types::tx_id(&etch_tx), 7);
+        assert!(runes::rune_id_by_name(runes::rune_name(rune)) == option::some(id), 8);
+
+        // the premine goes to the first non OP_RETURN output without edicts
+        assert_balance(outpoint(&etch_tx, 0), id, 1000);
+        assert_no_balance(outpoint(&etch_tx, 1));
+        assert_no_balance(outpoint(&etch_tx, 2));
+    }
+
+    #[test]
+    fun test_etch_with_name_commitment() {
+        genesis::init_for_test();
+        let rune_name = FIRST_THIRTEEN_LETTERS_RUNE;
+        assert!(runes::format_rune(rune_name) == string::utf8(b"AAAAAAAAAAAAA"), 1);
+        let commit_tx = types::new_tx_with_input_for_test(vector::empty(), vector[taproot_output(), taproot_output()]);
+        mine_block(1, vector[commit_tx]);
+        mine_block(2, vector::empty());
+        mine_block(3, vector::empty());
+        mine_block(4, vector::empty());
+
+        // the commit tx only has 5 confirmations
+        let premature_tx = runestone_tx(vector[reveal_input(outpoint(&commit_tx, 0), rune_name)], 1, vector[TAG_FLAGS, FLAG_ETCHING, TAG_RUNE, rune_name, TAG_PREMINE, 500]);
+        mine_block(5, vector[premature_tx]);
+        assert!(!runes::exists_rune(runes::new_rune_id(5, 1)), 2);
+        assert!(!runes::exists_rune_name(rune_name), 3);
+        assert_no_balance(outpoint(&premature_tx, 0));
+
+        let reveal_tx = runestone_tx(vector[reveal_input(outpoint(&commit_tx, 1), rune_name)], 1, vector[TAG_FLAGS, FLAG_ETCHING, TAG_RUNE, rune_name, TAG_PREMINE, 500]);
+        mine_block(6, vector[reveal_tx]);
+        let id = runes::new_rune_id(6, 1);
+        assert!(runes::rune_id_by_name(rune_name) == option::some(id), 4);
+        let rune = runes::borrow_rune(id);
+        assert!(runes::rune_name(rune) == rune_name, 5);
+        assert!(runes::rune_premine(rune) == 500, 6);
+        assert_balance(outpoint(&reveal_tx, 0), id, 500);
+    }
+
+    #[test]
+    fun test_mint_with_cap() {
+        genesis::init_for_test();
+        let etch_tx = runestone_tx(vector::empty(), 1, vector[TAG_FLAGS, FLAG_ETCHING | FLAG_TERMS, TAG_CAP, 2, TAG_AMOUNT, 100]);
+        mine_block(1, vector[etch_tx]);
+        let id = runes::new_rune_id(1, 1);
+        assert_no_balance(outpoint(&etch_tx, 0));
+
+        let mint_tx1 = runestone_tx(vector::empty(), 1, vector[TAG_MINT, 1, TAG_MINT, 1]);
+        let mint_tx2 = runestone_tx(vector::empty(), 1, vector[TAG_MINT, 1, TAG_MINT, 1]);
+        let mint_tx3 = runestone_tx(vector::empty(), 1, vector[TAG_MINT, 1, TAG_MINT, 1]);
+        mine_block(2, vector[mint_tx1, mint_tx2, mint_tx3]);
+        assert_balance(outpoint(&mint_tx1, 0), id, 100);
+        assert_balance(outpoint(&mint_tx2, 0), id, 100);
+        // the cap is reached
+        assert_no_balance(outpoint(&mint_tx3, 0));
+        assert!(runes::rune_mints(runes::borrow_rune(id)) == 2, 1);
+    }
+
+    #[test]
+    fun test_mint_with_height_window() {
+        genesis::init_for_test();
+        // the mint is open in the block [3, 5)
+        let etch_tx = runestone_tx(vector::empty(), 1, vector[TAG_FLAGS, FLAG_ETCHING | FLAG_TERMS, TAG_CAP, 10, TAG_AMOUNT, 100, TAG_HEIGHT_START, 3, TAG_HEIGHT_END, 5]);
+        let early_mint_tx = runestone_tx(vector::empty(), 1, vector[TAG_MINT, 1, TAG_MINT, 1]);
+        mine_block(1, vector[etch_tx, early_mint_tx]);
+        let id = runes::new_rune_id(1, 1);
+        assert_no_balance(outpoint(&early_mint_tx, 0));
+
+        let mint_txs = vector::empty();
+        let block_height = 2;
+        while (block_height <= 5) {
+            let mint_tx = runestone_tx(vector::empty(), 1, vector[TAG_MINT, 1, TAG_MINT, 1]);
+            mine_block(block_height, vector[mint_tx]);
+            vector::push_back(&mut mint_txs, mint_tx);
+            block_height = block_height + 1;
+        };
+        assert_no_balance(outpoint(vector::borrow(&mint_txs, 0), 0));
+        assert_balance(outpoint(vector::borrow(&mint_txs, 1), 0), id, 100);
+        assert_balance(outpoint(vector::borrow(&mint_txs, 2), 0), id, 100);
+        // the height end is exclusive
+        assert_no_balance(outpoint(vector::borrow(&mint_txs, 3), 0));
+        assert!(runes::rune_mints(runes::borrow_rune(id)) == 2, 1);
+    }
+
+    #[test]
+    fun test_edict_split_with_output_len() {
+        genesis::init_for_test();
+        // the edict output equals to the output count, split the premine between the 3 non OP_RETURN outputs
+        let etch_tx = runestone_tx(vector::empty(), 3, vector[TAG_FLAGS, FLAG_ETCHING, TAG_PREMINE, 1000, TAG_BODY, 0, 0, 0, 4]);
+        mine_block(1, vector[etch_tx]);
+        let id = runes::new_rune_id(1, 1);
+        assert_balance(outpoint(&etch_tx, 0), id, 334);
+        assert_balance(outpoint(&etch_tx, 1), id, 333);
+        assert_balance(outpoint(&etch_tx, 2), id, 333);
+        assert_no_balance(outpoint(&etch_tx, 3));
+
+        // the edict amount goes to each non OP_RETURN output, the rest goes to the first one
+        let split_tx = runestone_tx(spend(vector[outpoint(&etch_tx, 0)]), 3, vector[TAG_BODY, 1, 1, 100, 4]);
+        mine_block(2, vector[split_tx]);
+        assert_no_balance(outpoint(&etch_tx, 0));
+        assert_balance(outpoint(&split_tx, 0), id, 134);
+        assert_balance(outpoint(&split_tx, 1), id, 100);
+        assert_balance(outpoint(&split_tx, 2), id, 100);
+        assert!(runes::rune_burned(runes::borrow_rune(id)) == 0, 1);
+    }
+
+    #[test]
+    fun test_cenotaph_burn() {
+        genesis::init_for_test();
+        let etch_tx = runestone_tx(vector::empty(), 1, vector[TAG_FLAGS, FLAG_ETCHING, TAG_PREMINE, 1000]);
+        mine_block(1, vector[etch_tx]);
+        let id = runes::new_rune_id(1, 1);
+        assert_balance(outpoint(&etch_tx, 0), id, 1000);
+
+        // the unrecognized even tag makes the runestone a cenotaph, all the input runes are burned
+        let cenotaph_tx = runestone_tx(spend(vector[outpoint(&etch_tx, 0)]), 2, vector[TAG_CAP, 1, TAG_BODY, 1, 1, 0, 1]);
+        mine_block(2, vector[cenotaph_tx]);
+        assert_no_balance(outpoint(&etch_tx, 0));
+        assert_no_balance(outpoint(&cenotaph_tx, 0));
+        assert_no_balance(outpoint(&cenotaph_tx, 1));
+        assert!(runes::rune_burned(runes::borrow_rune(id)) == 1000, 1);
+    }
+
+    #[test]
+    fun test_pointer() {
+        genesis::init_for_test();
+        let etch_tx = runestone_tx(vector::empty(), 2, vector[TAG_FLAGS, FLAG_ETCHING, TAG_PREMINE, 1000, TAG_POINTER, 1]);
+        mine_block(1, vector[etch_tx]);
+        let id = runes::new_rune_id(1, 1);
+        assert_no_balance(outpoint(&etch_tx, 0));
+        assert_balance(outpoint(&etch_tx, 1), id, 1000);
+
+        // the pointer to the OP_RETURN output burns the unallocated runes
+        let burn_tx = runestone_tx(spend(vector[outpoint(&etch_tx, 1)]), 2, vector[TAG_POINTER, 2]);
+        mine_block(2, vector[burn_tx]);
+        assert_no_balance(outpoint(&burn_tx, 0));
+        assert_no_balance(outpoint(&burn_tx, 1));
+        assert!(runes::rune_burned(runes::borrow_rune(id)) == 1000, 1);
+    }
+
+    #[test]
+    fun test_transfer_across_utxos() {
+        genesis::init_for_test();
+        let etch_tx = runestone_tx(vector::empty(), 2, vector[TAG_FLAGS, FLAG_ETCHING, TAG_PREMINE, 1000, TAG_BODY, 0, 0, 600, 0, 0, 0, 400, 1]);
+        mine_block(1, vector[etch_tx]);
+        let id = runes::new_rune_id(1, 1);
+        assert_balance(outpoint(&etch_tx, 0), id, 600);
+        assert_balance(outpoint(&etch_tx, 1), id, 400);
+
+        // merge the balances of the two UTXOs, and move a part of them to the second output
+        let transfer_tx = runestone_tx(spend(vector[outpoint(&etch_tx, 0), outpoint(&etch_tx, 1)]), 2, vector[TAG_BODY, 1, 1, 250, 1]);
+        mine_block(2, vector[transfer_tx]);
+        assert!(!utxo::exists_utxo(outpoint(&etch_tx, 0)), 1);
+        assert!(!utxo::exists_utxo(outpoint(&etch_tx, 1)), 2);
+        assert_no_balance(outpoint(&etch_tx, 0));
+        assert_no_balance(outpoint(&etch_tx, 1));
+        assert_balance(outpoint(&transfer_tx, 0), id, 750);
+        assert_balance(outpoint(&transfer_tx, 1), id, 250);
+
+        // the tx without a runestone moves the runes to the first non OP_RETURN output
+        let plain_tx = types::new_tx_with_input_for_test(spend(vector[outpoint(&transfer_tx, 0)]), vector[taproot_output()]);
+        mine_block(3, vector[plain_tx]);
+        assert_no_balance(outpoint(&transfer_tx, 0));
+        assert_balance(outpoint(&plain_tx, 0), id, 750);
+        assert!(runes::rune_burned(runes::borrow_rune(id)) == 0, 3);
+    }
+}
@@ -53,6 +53,13 @@ module moveos_std::tx_context {
         is_upgrade: bool,
     }
 
+    /// The account paying the gas of the transaction if it is not the sender,
+    /// it is written by the executor, e.g. the sponsor of the sponsored transaction.
+    struct GasPaymentAccount has copy, drop, store {
+        account: address,
+        pay_gas_by_module_account: bool,
+    }
+
     /// Return the address of the user that signed the current transaction
     public fun sender(): address {
         borrow().sender
@@ -155,11 +162,15 @@ module moveos_std::tx_context {
     }
 
     /// Get the gas payment account of the transaction
-    /// Currently, the gas payment account is the sender of the transaction.
-    /// In the future, the gas payment account may be different from the sender.
+    /// The gas payment account is the sender of the transaction, or the sponsor if the transaction is sponsored.
     public fun tx_gas_payment_account(): address {
         let ctx = borrow();
-        ctx.sender
+        let gas_payment_account = get<GasPaymentAccount>(ctx);
+        if (option::is_some(&gas_payment_account)) {
+            option::destroy_some(gas_payment_account).account
+        } else {
+            ctx.sender
+        }
     }
 
     /// The result is only available in the `post_execute` function.
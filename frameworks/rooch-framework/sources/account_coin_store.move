@@ -19,6 +19,7 @@ module rooch_framework::account_coin_store {
 
     friend rooch_framework::genesis;
     friend rooch_framework::account;
+    friend rooch_framework::gas_coin;
 
     //
     // Errors.
@@ -250,6 +251,22 @@ module rooch_framework::account_coin_store {
     fun withdraw_internal<CoinType: key>(
         addr: address,
         amount: u256,
+    ): Coin<CoinType> {
+        withdraw_from_stores<CoinType>(addr, amount, true)
+    }
+
+    /// Withdraw the gas from the account, the gas does not count to the transfer caps of the session key
+    public(friend) fun withdraw_gas_internal<CoinType: key>(
+        addr: address,
+        amount: u256,
+    ): Coin<CoinType> {
+        withdraw_from_stores<CoinType>(addr, amount, false)
+    }
+
+    fun withdraw_from_stores<CoinType: key>(
+        addr: address,
+        amount: u256,
+        check_transfer_cap: bool,
     ): Coin<CoinType> {
         // Check CoinType-specific coin store first
         let coin_type = type_info::type_name<CoinType>();
@@ -271,7 +288,11 @@ module rooch_framework::account_coin_store {
             };
             
             let coin_store = borrow_mut_account_coin_store<CoinType>(addr);
-            coin_store::withdraw_internal(coin_store, withdraw_amount)
+            if (check_transfer_cap) {
+                coin_store::withdraw_internal(coin_store, withdraw_amount)
+            } else {
+                coin_store::withdraw_without_transfer_cap_internal(coin_store, withdraw_amount)
+            }
         } else {
             coin::zero<CoinType>()
         };
@@ -280,7 +301,11 @@ module rooch_framework::account_coin_store {
         if (coin_store_balance < amount) {
             let generic_amount = amount - coin_store_balance;
             let generic_store = borrow_mut_multi_coin_store(addr);
-            let generic_coin = multi_coin_store::withdraw(generic_store, coin_type, generic_amount);
+            let generic_coin = if (check_transfer_cap) {
+                multi_coin_store::withdraw(generic_store, coin_type, generic_amount)
+            } else {
+                multi_coin_store::withdraw_without_transfer_cap_internal(generic_store, coin_type, generic_amount)
+            };
             let generic_coin_store_coin = coin::convert_generic_coin_to_coin<CoinType>(generic_coin);
             coin::merge(&mut coin_store_coin, generic_coin_store_coin);
         };
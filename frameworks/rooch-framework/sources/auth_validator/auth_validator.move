@@ -43,6 +43,10 @@ module rooch_framework::auth_validator {
     const ErrorValidateSessionIsExpired: u64 = 1012;
     /// The function call is beyond the session's scope
     const ErrorValidateFunctionCallBeyondSessionScope: u64 = 1013;
+    /// The session key exceeds its max gas or max transaction count limit
+    const ErrorValidateSessionLimitExceeded: u64 = 1014;
+    /// The session key reaches the absolute expiration time of its limits
+    const ErrorValidateSessionExpirationTimeReached: u64 = 1015;
 
     /// DID VM fragment encoding prefix
     const DID_VM_FRAGMENT_PREFIX: vector<u8> = b"DID_VM:";
@@ -99,6 +103,14 @@ module rooch_framework::auth_validator {
         ErrorValidateFunctionCallBeyondSessionScope
     }
 
+    public fun error_validate_session_limit_exceeded(): u64 {
+        ErrorValidateSessionLimitExceeded
+    }
+
+    public fun error_validate_session_expiration_time_reached(): u64 {
+        ErrorValidateSessionExpirationTimeReached
+    }
+
     /// The Authentication Validator
     struct AuthValidator has store, copy, drop {
         id: u64,
@@ -13,6 +13,7 @@ module rooch_framework::bitcoin_validator {
     use rooch_framework::bitcoin_address::{Self, BitcoinAddress};
 
     friend rooch_framework::transaction_validator;
+    friend rooch_framework::sponsor_validator;
 
     /// there defines auth validator id for each auth validator
     const BITCOIN_AUTH_VALIDATOR_ID: u64 = 1;
@@ -42,8 +43,13 @@ module rooch_framework::bitcoin_validator {
     }
 
     public(friend) fun validate(authenticator_payload: vector<u8>) :BitcoinAddress{
+        validate_with_address(tx_context::sender(), authenticator_payload)
+    }
+
+    /// Validate the authenticator is signed by the `signer_address`, it is the sender for the
+    /// normal transactions, and the gas sponsor for the sponsored transactions.
+    public(friend) fun validate_with_address(signer_address: address, authenticator_payload: vector<u8>) :BitcoinAddress{
 
-        let sender = tx_context::sender();
         let tx_hash = tx_context::tx_hash();
         let payload = auth_payload::from_bytes(authenticator_payload);
 
@@ -60,9 +66,9 @@ module rooch_framework::bitcoin_validator {
 
         let rooch_addr = bitcoin_address::to_rooch_address(&bitcoin_addr);
 
-        // Check if the sender is related to the Rooch address
+        // Check if the signer is related to the Rooch address
         assert!(
-            sender == rooch_addr,
+            signer_address == rooch_addr,
             auth_validator::error_validate_invalid_authenticator()
         );
         bitcoin_addr
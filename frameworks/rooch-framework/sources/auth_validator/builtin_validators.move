@@ -8,6 +8,7 @@ module rooch_framework::builtin_validators{
     use rooch_framework::bitcoin_validator;
     use rooch_framework::webauthn_validator;
     use rooch_framework::did_validator;
+    use rooch_framework::sponsor_validator;
 
     friend rooch_framework::genesis;
 
@@ -20,6 +21,7 @@ module rooch_framework::builtin_validators{
     /// WebAuthn validator ID (DEPRECATED). Use DID validator (ID=4) with WebAuthnV0 envelope instead.
     const WEBAUTHN_VALIDATOR_ID: u64 = 3;
     const DID_VALIDATOR_ID: u64 = 4;
+    const SPONSOR_VALIDATOR_ID: u64 = 5;
 
 
     public(friend) fun genesis_init(_genesis_account: &signer) {
@@ -38,6 +40,10 @@ module rooch_framework::builtin_validators{
         // DID_AUTH_VALIDATOR_ID: u64 = 4;
         let id = auth_validator_registry::register_internal_with_id<did_validator::DIDValidator>(DID_VALIDATOR_ID);
         assert!(id == did_validator::auth_validator_id(), ErrorGenesisInit);
+
+        // SPONSOR_AUTH_VALIDATOR_ID: u64 = 5;
+        let id = auth_validator_registry::register_internal_with_id<sponsor_validator::SponsorValidator>(SPONSOR_VALIDATOR_ID);
+        assert!(id == sponsor_validator::auth_validator_id(), ErrorGenesisInit);
     }
 
 
@@ -54,11 +60,18 @@ module rooch_framework::builtin_validators{
         assert!(id == did_validator::auth_validator_id(), ErrorGenesisInit);
     }
 
+    /// This function is for init sponsor validator when framework is upgraded.
+    public entry fun init_sponsor_validator() {
+        let id = auth_validator_registry::register_internal_with_id<sponsor_validator::SponsorValidator>(SPONSOR_VALIDATOR_ID);
+        assert!(id == sponsor_validator::auth_validator_id(), ErrorGenesisInit);
+    }
+
     public fun is_builtin_auth_validator(auth_validator_id: u64): bool {
         auth_validator_id == SESSION_VALIDATOR_ID || 
         auth_validator_id == BITCOIN_VALIDATOR_ID || 
         auth_validator_id == BITCOIN_MULTISIGN_VALIDATOR_ID ||
         auth_validator_id == WEBAUTHN_VALIDATOR_ID ||
-        auth_validator_id == DID_VALIDATOR_ID
+        auth_validator_id == DID_VALIDATOR_ID ||
+        auth_validator_id == SPONSOR_VALIDATOR_ID
     }
 }
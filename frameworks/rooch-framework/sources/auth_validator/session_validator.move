@@ -154,6 +154,10 @@ module rooch_framework::session_validator {
         
         let session_key = option::extract(&mut session_key_option);
         assert!(!session_key::is_expired(&session_key), auth_validator::error_validate_session_is_expired());
+        assert!(
+            !session_key::is_limit_expired(sender_addr, auth_key),
+            auth_validator::error_validate_session_expiration_time_reached()
+        );
         
         assert!(session_key::in_session_scope(&session_key), auth_validator::error_validate_function_call_beyond_session_scope());
         auth_key
@@ -0,0 +1,49 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// This module implements the sponsor auth validator.
+/// A sponsored transaction carries two authenticators, the sender's authenticator and the gas
+/// sponsor's Bitcoin authenticator, both sign the same transaction hash. The gas is charged to the sponsor.
+module rooch_framework::sponsor_validator {
+
+    use moveos_std::bcs;
+    use rooch_framework::bitcoin_validator;
+
+    friend rooch_framework::transaction_validator;
+
+    /// there defines auth validator id for each auth validator
+    const SPONSOR_VALIDATOR_ID: u64 = 5;
+
+    struct SponsorValidator has store, drop {}
+
+    #[data_struct]
+    /// The authenticator payload of the sponsored transaction
+    struct SponsorAuthPayload has copy, store, drop {
+        /// The auth validator id of the sender's authenticator
+        sender_auth_validator_id: u64,
+        /// The payload of the sender's authenticator
+        sender_payload: vector<u8>,
+        /// The gas sponsor address
+        sponsor: address,
+        /// The Bitcoin authenticator payload signed by the sponsor
+        sponsor_payload: vector<u8>,
+    }
+
+    public fun auth_validator_id(): u64 {
+        SPONSOR_VALIDATOR_ID
+    }
+
+    /// Validate the sponsor's authenticator, return the sender's auth validator id and payload, and the sponsor address.
+    /// The sender's authenticator is validated by the transaction_validator.
+    public(friend) fun validate(authenticator_payload: vector<u8>): (u64, vector<u8>, address) {
+        let payload = bcs::from_bytes<SponsorAuthPayload>(authenticator_payload);
+        let SponsorAuthPayload {
+            sender_auth_validator_id,
+            sender_payload,
+            sponsor,
+            sponsor_payload,
+        } = payload;
+        bitcoin_validator::validate_with_address(sponsor, sponsor_payload);
+        (sender_auth_validator_id, sender_payload, sponsor)
+    }
+}
@@ -10,6 +10,7 @@ module rooch_framework::coin_store {
     use moveos_std::type_info;
     use moveos_std::event;
     use rooch_framework::coin::{Self, Coin};
+    use rooch_framework::session_key;
 
     friend rooch_framework::account_coin_store;
     friend rooch_framework::multi_coin_store;
@@ -248,9 +249,19 @@ module rooch_framework::coin_store {
         coin_store.balance.value = coin_store.balance.value + value;
     }
 
+    /// The coins withdrawn from the coin store of an account count to the transfer caps of the account's session key
     public(friend) fun withdraw_internal<CoinType: key>(
         coin_store_obj: &mut Object<CoinStore<CoinType>>,
         amount: u256
+    ): Coin<CoinType> {
+        session_key::record_transfer(object::owner(coin_store_obj), type_info::type_name<CoinType>(), amount);
+        withdraw_without_transfer_cap_internal(coin_store_obj, amount)
+    }
+
+    /// The withdraw function skipping the transfer caps of the session key, only for the gas payment
+    public(friend) fun withdraw_without_transfer_cap_internal<CoinType: key>(
+        coin_store_obj: &mut Object<CoinStore<CoinType>>,
+        amount: u256
     ): Coin<CoinType> {
         let object_id = object::id(coin_store_obj);
         let coin_store = object::borrow_mut(coin_store_obj);
@@ -53,7 +53,7 @@ module rooch_framework::gas_coin {
 
     /// deduct gas coin from the given account.
     public(friend) fun deduct_gas(addr: address, amount: u256):Coin<RGas> {
-        account_coin_store::withdraw_extend<RGas>(addr, amount)
+        account_coin_store::withdraw_gas_internal<RGas>(addr, amount)
     }
 
     /// Refund gas to account store
@@ -0,0 +1,172 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+/// This module manages the gas budgets of the sponsors of the sponsored transactions.
+/// A sponsor only pays the gas of the function calls in its budget scopes, the function scope
+/// takes precedence over the module scope(the function name is `*`).
+module rooch_framework::gas_sponsor {
+
+    use std::option::{Self, Option};
+    use std::string::{Self, String};
+    use std::signer;
+    use moveos_std::account;
+    use moveos_std::table::{Self, Table};
+    use moveos_std::tx_context;
+    use moveos_std::tx_meta::{Self, FunctionCallMeta};
+
+    friend rooch_framework::transaction_validator;
+
+    /// The sponsored transaction is not a function call
+    const ErrorSponsoredTxNotFunctionCall: u64 = 1;
+    /// The function call is not in the sponsor's budget scopes, or the budget is not enough to pay the gas
+    const ErrorSponsorBudgetExceeded: u64 = 2;
+
+    const ANY_FUNCTION: vector<u8> = b"*";
+
+    /// The function or module the sponsor pays the gas for
+    struct SponsorScope has copy, store, drop {
+        module_address: address,
+        module_name: String,
+        /// `*` means all the functions of the module
+        function_name: String,
+    }
+
+    /// The remaining gas budgets of the sponsor, in RGas
+    struct SponsorBudgets has key {
+        budgets: Table<SponsorScope, u256>,
+    }
+
+    public fun new_sponsor_scope(module_address: address, module_name: String, function_name: String): SponsorScope {
+        SponsorScope {
+            module_address,
+            module_name,
+            function_name,
+        }
+    }
+
+    /// Set the gas budget of the scope, the budget is replaced if it already exists
+    public fun set_budget(sponsor: &signer, scope: SponsorScope, budget: u256) {
+        let sponsor_addr = signer::address_of(sponsor);
+        if (!account::exists_resource<SponsorBudgets>(sponsor_addr)) {
+            account::move_resource_to(sponsor, SponsorBudgets {
+                budgets: table::new(),
+            });
+        };
+        let budgets = account::borrow_mut_resource<SponsorBudgets>(sponsor_addr);
+        table::upsert(&mut budgets.budgets, scope, budget);
+    }
+
+    public entry fun set_budget_entry(
+        sponsor: &signer,
+        module_address: address,
+        module_name: String,
+        function_name: String,
+        budget: u256,
+    ) {
+        set_budget(sponsor, new_sponsor_scope(module_address, module_name, function_name), budget);
+    }
+
+    public fun remove_budget(sponsor: &signer, scope: SponsorScope) {
+        let sponsor_addr = signer::address_of(sponsor);
+        if (!account::exists_resource<SponsorBudgets>(sponsor_addr)) {
+            return
+        };
+        let budgets = account::borrow_mut_resource<SponsorBudgets>(sponsor_addr);
+        if (table::contains(&budgets.budgets, scope)) {
+            table::remove(&mut budgets.budgets, scope);
+        };
+    }
+
+    public entry fun remove_budget_entry(
+        sponsor: &signer,
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    ) {
+        remove_budget(sponsor, new_sponsor_scope(module_address, module_name, function_name));
+    }
+
+    /// Get the remaining gas budget of the scope, return none if the scope is not sponsored
+    public fun budget(sponsor: address, module_address: address, module_name: String, function_name: String): Option<u256> {
+        scope_budget(sponsor, new_sponsor_scope(module_address, module_name, function_name))
+    }
+
+    fun scope_budget(sponsor: address, scope: SponsorScope): Option<u256> {
+        if (!account::exists_resource<SponsorBudgets>(sponsor)) {
+            return option::none()
+        };
+        let budgets = account::borrow_resource<SponsorBudgets>(sponsor);
+        if (table::contains(&budgets.budgets, scope)) {
+            option::some(*table::borrow(&budgets.budgets, scope))
+        } else {
+            option::none()
+        }
+    }
+
+    /// Find the budget scope of the function call, the function scope takes precedence over the module scope
+    fun matched_scope(sponsor: address, function_meta: &FunctionCallMeta): Option<SponsorScope> {
+        let module_address = *tx_meta::function_meta_module_address(function_meta);
+        let module_name = *tx_meta::function_meta_module_name(function_meta);
+        let function_scope = new_sponsor_scope(module_address, module_name, *tx_meta::function_meta_function_name(function_meta));
+        if (option::is_some(&scope_budget(sponsor, function_scope))) {
+            return option::some(function_scope)
+        };
+        let module_scope = new_sponsor_scope(module_address, module_name, string::utf8(ANY_FUNCTION));
+        if (option::is_some(&scope_budget(sponsor, module_scope))) {
+            return option::some(module_scope)
+        };
+        option::none()
+    }
+
+    fun current_function_meta(): FunctionCallMeta {
+        let function_meta_opt = tx_meta::function_meta(&tx_context::tx_meta());
+        assert!(option::is_some(&function_meta_opt), ErrorSponsoredTxNotFunctionCall);
+        option::destroy_some(function_meta_opt)
+    }
+
+    /// Check the sponsor's budget of the current transaction can pay the `gas`
+    public(friend) fun check_budget(sponsor: address, gas: u256) {
+        let function_meta = current_function_meta();
+        let scope_opt = matched_scope(sponsor, &function_meta);
+        assert!(option::is_some(&scope_opt), ErrorSponsorBudgetExceeded);
+        let remaining = option::destroy_some(scope_budget(sponsor, option::destroy_some(scope_opt)));
+        assert!(remaining >= gas, ErrorSponsorBudgetExceeded);
+    }
+
+    /// Charge the used gas of the current transaction to the sponsor's budget.
+    /// It is called after the transaction is executed, so it never aborts, the budget is at least zero.
+    public(friend) fun charge_budget(sponsor: address, gas_used: u256) {
+        let function_meta_opt = tx_meta::function_meta(&tx_context::tx_meta());
+        if (option::is_none(&function_meta_opt)) {
+            return
+        };
+        let scope_opt = matched_scope(sponsor, option::borrow(&function_meta_opt));
+        if (option::is_none(&scope_opt)) {
+            return
+        };
+        let budgets = account::borrow_mut_resource<SponsorBudgets>(sponsor);
+        let remaining = table::borrow_mut(&mut budgets.budgets, option::destroy_some(scope_opt));
+        *remaining = if (*remaining > gas_used) { *remaining - gas_used } else { 0 };
+    }
+
+    #[test]
+    fun test_set_and_remove_budget() {
+        rooch_framework::genesis::init_for_test();
+        let sponsor = account::create_signer_for_testing(@0x42);
+        let module_scope = new_sponsor_scope(@0x1, string::utf8(b"game"), string::utf8(ANY_FUNCTION));
+        let function_scope = new_sponsor_scope(@0x1, string::utf8(b"game"), string::utf8(b"play"));
+        assert!(option::is_none(&scope_budget(@0x42, module_scope)), 1);
+
+        set_budget(&sponsor, module_scope, 1000);
+        set_budget(&sponsor, function_scope, 100);
+        set_budget(&sponsor, function_scope, 200);
+        assert!(scope_budget(@0x42, module_scope) == option::some(1000), 2);
+        assert!(scope_budget(@0x42, function_scope) == option::some(200), 3);
+
+        let function_meta = tx_meta::new_function_call_meta(@0x1, string::utf8(b"game"), string::utf8(b"play"));
+        assert!(matched_scope(@0x42, &function_meta) == option::some(function_scope), 4);
+        remove_budget(&sponsor, function_scope);
+        assert!(option::is_none(&scope_budget(@0x42, function_scope)), 5);
+        assert!(matched_scope(@0x42, &function_meta) == option::some(module_scope), 6);
+    }
+}
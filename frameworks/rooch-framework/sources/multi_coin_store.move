@@ -11,6 +11,7 @@ module rooch_framework::multi_coin_store {
     
     use moveos_std::event;
     use rooch_framework::coin::{Self, GenericCoin, Coin};
+    use rooch_framework::session_key;
 
     friend rooch_framework::account_coin_store;
     friend rooch_framework::coin_migration;
@@ -292,10 +293,21 @@ module rooch_framework::multi_coin_store {
         coin_store_field.balance.value = coin_store_field.balance.value + value;
     }
 
+    /// The coins withdrawn from the multi coin store of an account count to the transfer caps of the account's session key
     public(friend) fun withdraw_internal(
         coin_store_obj: &mut Object<MultiCoinStore>,
         coin_type: string::String,
         amount: u256
+    ): GenericCoin {
+        session_key::record_transfer(object::owner(coin_store_obj), coin_type, amount);
+        withdraw_without_transfer_cap_internal(coin_store_obj, coin_type, amount)
+    }
+
+    /// The withdraw function skipping the transfer caps of the session key, only for the gas payment
+    public(friend) fun withdraw_without_transfer_cap_internal(
+        coin_store_obj: &mut Object<MultiCoinStore>,
+        coin_type: string::String,
+        amount: u256
     ): GenericCoin {
         let object_id = object::id(coin_store_obj);
         check_coin_store_not_frozen(coin_store_obj, coin_type);
@@ -34,6 +34,7 @@ module rooch_framework::payment_channel {
     use rooch_framework::payment_revenue;
     use rooch_framework::onchain_config;
     use rooch_framework::core_addresses;
+    use rooch_framework::session_key;
 
     friend rooch_framework::transaction_gas;
 
@@ -418,7 +419,9 @@ module rooch_framework::payment_channel {
         // Withdraw from sender's hub
         let sender_hub_obj = borrow_or_create_payment_hub(sender_addr);
         let sender_hub = object::borrow_mut(sender_hub_obj);
-        let coin = multi_coin_store::withdraw_by_type<CoinType>(&mut sender_hub.multi_coin_store, amount);
+        let coin = coin::convert_generic_coin_to_coin<CoinType>(
+            withdraw_from_hub_store(sender_addr, sender_hub, coin_type_name, amount)
+        );
         let sender_hub_id = object::id(sender_hub_obj);
 
         // Deposit to receiver's hub
@@ -464,7 +467,7 @@ module rooch_framework::payment_channel {
         // Withdraw from sender's hub
         let sender_hub_obj = borrow_or_create_payment_hub(sender_addr);
         let sender_hub = object::borrow_mut(sender_hub_obj);
-        let coin = multi_coin_store::withdraw(&mut sender_hub.multi_coin_store, coin_type, amount);
+        let coin = withdraw_from_hub_store(sender_addr, sender_hub, coin_type, amount);
         let sender_hub_id = object::id(sender_hub_obj);
 
         // Deposit to receiver's hub
@@ -668,7 +671,7 @@ module rooch_framework::payment_channel {
             // Withdraw funds from the payment hub and transfer to the receiver.
             let hub_obj = borrow_or_create_payment_hub(channel.sender);
             let hub = object::borrow_mut(hub_obj);
-            let generic_payment = multi_coin_store::withdraw(&mut hub.multi_coin_store, channel.coin_type, incremental_amount);
+            let generic_payment = withdraw_from_hub_store(channel.sender, hub, channel.coin_type, incremental_amount);
 
             // Deposit the coin as revenue into the receiver's revenue hub
             payment_revenue::deposit_revenue_generic(
@@ -1058,7 +1061,7 @@ module rooch_framework::payment_channel {
             let hub_obj = borrow_or_create_payment_hub(channel.sender);
             let hub = object::borrow_mut(hub_obj);
             let coin_type_name = channel.coin_type;
-            let generic_payment = multi_coin_store::withdraw(&mut hub.multi_coin_store, coin_type_name, total_incremental_amount);
+            let generic_payment = withdraw_from_hub_store(channel.sender, hub, coin_type_name, total_incremental_amount);
             
             // Deposit as revenue from channel closure
             payment_revenue::deposit_revenue_generic(
@@ -1351,7 +1354,7 @@ module rooch_framework::payment_channel {
             let hub_obj = borrow_or_create_payment_hub(channel.sender);
             let hub = object::borrow_mut(hub_obj);
             let coin_type_name = channel.coin_type;
-            let generic_payment = multi_coin_store::withdraw(&mut hub.multi_coin_store, coin_type_name, final_amount);
+            let generic_payment = withdraw_from_hub_store(channel.sender, hub, coin_type_name, final_amount);
             
             // Deposit as revenue from channel cancellation
             payment_revenue::deposit_revenue_generic(
@@ -1658,6 +1661,13 @@ module rooch_framework::payment_channel {
         });
     }
 
+    /// Withdraw the coins paid out of the owner's payment hub. The multi coin store of the hub is
+    /// not owned by the account, so the coins count to the transfer caps of the owner's session key here
+    fun withdraw_from_hub_store(hub_owner: address, hub: &mut PaymentHub, coin_type: String, amount: u256): GenericCoin {
+        session_key::record_transfer(hub_owner, coin_type, amount);
+        multi_coin_store::withdraw(&mut hub.multi_coin_store, coin_type, amount)
+    }
+
     /// Internal function to withdraw specific coin type from payment hub 
     /// (no signer required and does not check for active channels)
     /// Used by system contracts like transaction_gas module
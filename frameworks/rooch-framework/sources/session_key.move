@@ -15,8 +15,12 @@ module rooch_framework::session_key {
     use moveos_std::hash;
     use moveos_std::address;
     use moveos_std::string_utils;
+    use moveos_std::simple_map::{Self, SimpleMap};
 
     friend rooch_framework::transaction_validator;
+    friend rooch_framework::coin_store;
+    friend rooch_framework::multi_coin_store;
+    friend rooch_framework::payment_channel;
     friend rooch_framework::session_validator;
     friend rooch_framework::did;
 
@@ -35,6 +39,10 @@ module rooch_framework::session_key {
     const ErrorSessionScopePartLengthNotMatch: u64 = 4;
     /// The max inactive interval is invalid
     const ErrorInvalidMaxInactiveInterval: u64 = 5;
+    /// The coin transfer of the session key exceeds the transfer cap of the coin type
+    const ErrorSessionKeyTransferCapExceeded: u64 = 6;
+    /// The lengths of the coin types and the transfer caps do not match.
+    const ErrorTransferCapPartLengthNotMatch: u64 = 7;
 
     // Signature scheme constant, similar to session_validator.move
     const SIGNATURE_SCHEME_ED25519: u8 = 0;
@@ -86,6 +94,33 @@ module rooch_framework::session_key {
         keys: Table<vector<u8>, SessionKey>,
     }
 
+    /// The max amount of a coin type the session key can transfer out of the account
+    struct TransferCap has store,copy,drop {
+        cap: u256,
+        transferred: u256,
+    }
+
+    /// The optional limits of a session key, to reduce the loss when the session key is leaked
+    struct SessionKeyLimit has store,copy,drop {
+        /// The max gas the session key can spend, in RGas, 0 means no limit
+        max_gas: u256,
+        /// The gas the session key has spent, in RGas
+        gas_used: u256,
+        /// The max number of transactions the session key can send, 0 means no limit
+        max_tx_count: u64,
+        /// The number of transactions the session key has sent
+        tx_count: u64,
+        /// The absolute expiration time of the session key, in seconds, 0 means no expiration time
+        expiration_time: u64,
+        /// The transfer caps of the coin types, the key is the coin type name
+        /// The coin types without a transfer cap are not limited
+        transfer_caps: SimpleMap<std::string::String, TransferCap>,
+    }
+
+    struct SessionKeyLimits has key {
+        limits: Table<vector<u8>, SessionKeyLimit>,
+    }
+
     public fun new_session_scope(module_address: address, module_name: std::string::String, function_name: std::string::String) : SessionScope {
         SessionScope {
             module_address: module_address,
@@ -109,7 +144,7 @@ module rooch_framework::session_key {
         };
 
         let session_key = option::extract(&mut session_key_option);
-        is_expired(&session_key)
+        is_expired(&session_key) || is_limit_expired(account_address, authentication_key)
     }
 
     public fun has_session_key(account_address: address) : bool {
@@ -264,7 +299,11 @@ module rooch_framework::session_key {
         authentication_key: vector<u8>,
         scope_strings: vector<std::string::String>,
         max_inactive_interval: u64) {
-        
+        let scopes = parse_scope_strings(scope_strings);
+        create_session_key(sender, app_name, app_url, authentication_key, scopes, max_inactive_interval);
+    }
+
+    fun parse_scope_strings(scope_strings: vector<std::string::String>): vector<SessionScope> {
         let scopes = vector::empty<SessionScope>();
         let idx = 0;
         let scope_count = vector::length(&scope_strings);
@@ -275,8 +314,181 @@ module rooch_framework::session_key {
             vector::push_back(&mut scopes, scope);
             idx = idx + 1;
         };
-        
+        scopes
+    }
+
+    /// Create session key with scope strings and limits entry function
+    /// See `set_session_key_limit_entry` for the limit arguments
+    public entry fun create_session_key_with_limit_entry(
+        sender: &signer,
+        app_name: std::string::String,
+        app_url: std::string::String,
+        authentication_key: vector<u8>,
+        scope_strings: vector<std::string::String>,
+        max_inactive_interval: u64,
+        max_gas: u256,
+        max_tx_count: u64,
+        expiration_time: u64,
+        cap_coin_types: vector<std::string::String>,
+        cap_amounts: vector<u256>) {
+
+        let scopes = parse_scope_strings(scope_strings);
         create_session_key(sender, app_name, app_url, authentication_key, scopes, max_inactive_interval);
+        let limit = new_session_key_limit_with_transfer_caps(max_gas, max_tx_count, expiration_time, cap_coin_types, cap_amounts);
+        set_session_key_limit(sender, authentication_key, limit);
+    }
+
+    public fun new_session_key_limit(max_gas: u256, max_tx_count: u64, expiration_time: u64): SessionKeyLimit {
+        SessionKeyLimit {
+            max_gas,
+            gas_used: 0,
+            max_tx_count,
+            tx_count: 0,
+            expiration_time,
+            transfer_caps: simple_map::new(),
+        }
+    }
+
+    /// Set the transfer cap of the coin type, the transferred amount is reset
+    public fun set_transfer_cap(limit: &mut SessionKeyLimit, coin_type: std::string::String, cap: u256) {
+        simple_map::upsert(&mut limit.transfer_caps, coin_type, TransferCap {
+            cap,
+            transferred: 0,
+        });
+    }
+
+    fun new_session_key_limit_with_transfer_caps(
+        max_gas: u256,
+        max_tx_count: u64,
+        expiration_time: u64,
+        cap_coin_types: vector<std::string::String>,
+        cap_amounts: vector<u256>): SessionKeyLimit {
+        assert!(vector::length(&cap_coin_types) == vector::length(&cap_amounts), ErrorTransferCapPartLengthNotMatch);
+        let limit = new_session_key_limit(max_gas, max_tx_count, expiration_time);
+        let idx = 0;
+        while (idx < vector::length(&cap_coin_types)) {
+            set_transfer_cap(&mut limit, *vector::borrow(&cap_coin_types, idx), *vector::borrow(&cap_amounts, idx));
+            idx = idx + 1;
+        };
+        limit
+    }
+
+    /// Set the limits of the session key, the previous limits and the usage are replaced
+    public fun set_session_key_limit(sender: &signer, authentication_key: vector<u8>, limit: SessionKeyLimit) {
+        //Can not change the limits by the session key
+        assert!(!auth_validator::is_validate_via_session_key(), ErrorSessionKeyCreatePermissionDenied);
+        let sender_addr = signer::address_of(sender);
+        assert!(exists_session_key(sender_addr, authentication_key), ErrorSessionKeyIsInvalid);
+        if (!account::exists_resource<SessionKeyLimits>(sender_addr)){
+            let limits = table::new<vector<u8>, SessionKeyLimit>();
+            account::move_resource_to<SessionKeyLimits>(sender, SessionKeyLimits{limits});
+        };
+        let session_key_limits = account::borrow_mut_resource<SessionKeyLimits>(sender_addr);
+        table::upsert(&mut session_key_limits.limits, authentication_key, limit);
+    }
+
+    /// Set the limits of the session key
+    /// `max_gas`(in RGas), `max_tx_count` and `expiration_time`(absolute timestamp in seconds) are not limited if they are 0
+    /// `cap_coin_types` and `cap_amounts` are the coin type names and the max amounts the session key can transfer
+    public entry fun set_session_key_limit_entry(
+        sender: &signer,
+        authentication_key: vector<u8>,
+        max_gas: u256,
+        max_tx_count: u64,
+        expiration_time: u64,
+        cap_coin_types: vector<std::string::String>,
+        cap_amounts: vector<u256>) {
+        let limit = new_session_key_limit_with_transfer_caps(max_gas, max_tx_count, expiration_time, cap_coin_types, cap_amounts);
+        set_session_key_limit(sender, authentication_key, limit);
+    }
+
+    public fun get_session_key_limit(account_address: address, authentication_key: vector<u8>) : Option<SessionKeyLimit> {
+        if (!account::exists_resource<SessionKeyLimits>(account_address)){
+            return option::none()
+        };
+        let session_key_limits = account::borrow_resource<SessionKeyLimits>(account_address);
+        if (!table::contains(&session_key_limits.limits, authentication_key)){
+            option::none()
+        }else{
+            option::some(*table::borrow(&session_key_limits.limits, authentication_key))
+        }
+    }
+
+    /// Check the session key is expired by the absolute expiration time of its limits
+    public(friend) fun is_limit_expired(account_address: address, authentication_key: vector<u8>) : bool {
+        let limit_option = get_session_key_limit(account_address, authentication_key);
+        if (option::is_none(&limit_option)){
+            return false
+        };
+        let limit = option::destroy_some(limit_option);
+        limit.expiration_time > 0 && limit.expiration_time <= timestamp::now_seconds()
+    }
+
+    /// Check the session key can send one more transaction which spends at most `gas`
+    public(friend) fun is_limit_exceeded(account_address: address, authentication_key: vector<u8>, gas: u256) : bool {
+        let limit_option = get_session_key_limit(account_address, authentication_key);
+        if (option::is_none(&limit_option)){
+            return false
+        };
+        let limit = option::destroy_some(limit_option);
+        if (limit.max_tx_count > 0 && limit.tx_count >= limit.max_tx_count){
+            return true
+        };
+        limit.max_gas > 0 && limit.gas_used + gas > limit.max_gas
+    }
+
+    /// Record the gas used by the transaction sent via the session key
+    public(friend) fun record_session_key_usage(authentication_key: vector<u8>, gas_used: u256) {
+        let sender_addr = tx_context::sender();
+        if (!account::exists_resource<SessionKeyLimits>(sender_addr)){
+            return
+        };
+        let session_key_limits = account::borrow_mut_resource<SessionKeyLimits>(sender_addr);
+        if (!table::contains(&session_key_limits.limits, authentication_key)){
+            return
+        };
+        let limit = table::borrow_mut(&mut session_key_limits.limits, authentication_key);
+        limit.tx_count = limit.tx_count + 1;
+        limit.gas_used = limit.gas_used + gas_used;
+    }
+
+    /// Record the coin transfer out of the account, abort if the transfer cap of the session key is exceeded
+    /// It only works when the current transaction is sent by the account via the session key
+    /// It is called where the coins leave the stores of the account: the coin stores, the multi coin store
+    /// and the payment hub, so all the withdraw and transfer paths are covered
+    public(friend) fun record_transfer(account_address: address, coin_type: std::string::String, amount: u256) {
+        if (account_address != tx_context::sender()){
+            return
+        };
+        let session_key_option = auth_validator::get_session_key_from_ctx_option();
+        if (option::is_none(&session_key_option)){
+            return
+        };
+        let authentication_key = option::destroy_some(session_key_option);
+        if (!account::exists_resource<SessionKeyLimits>(account_address)){
+            return
+        };
+        let session_key_limits = account::borrow_mut_resource<SessionKeyLimits>(account_address);
+        if (!table::contains(&session_key_limits.limits, authentication_key)){
+            return
+        };
+        let limit = table::borrow_mut(&mut session_key_limits.limits, authentication_key);
+        if (!simple_map::contains_key(&limit.transfer_caps, &coin_type)){
+            return
+        };
+        let transfer_cap = simple_map::borrow_mut(&mut limit.transfer_caps, &coin_type);
+        transfer_cap.transferred = transfer_cap.transferred + amount;
+        assert!(transfer_cap.transferred <= transfer_cap.cap, ErrorSessionKeyTransferCapExceeded);
+    }
+
+    #[test_only]
+    public fun record_session_key_usage_for_test(authentication_key: vector<u8>, gas_used: u256) {
+        record_session_key_usage(authentication_key, gas_used);
+    }
+
+    #[test_only]
+    public fun is_limit_exceeded_for_test(account_address: address, authentication_key: vector<u8>, gas: u256) : bool {
+        is_limit_exceeded(account_address, authentication_key, gas)
     }
 
     /// Check the current tx is in the session scope or not
@@ -355,6 +567,12 @@ module rooch_framework::session_key {
         // If the session key is not exists, do nothing
         if (table::contains(&session_keys.keys, authentication_key)){
             table::remove(&mut session_keys.keys, authentication_key);
+        };
+        if (account::exists_resource<SessionKeyLimits>(sender_addr)){
+            let session_key_limits = account::borrow_mut_resource<SessionKeyLimits>(sender_addr);
+            if (table::contains(&session_key_limits.limits, authentication_key)){
+                table::remove(&mut session_key_limits.limits, authentication_key);
+            }
         }
     }
 
@@ -40,4 +40,35 @@ module rooch_framework::session_key_test{
         
     }
 
+    #[test]
+    fun test_session_key_limit(){
+        rooch_framework::genesis::init_for_test();
+        let sender_addr = tx_context::sender();
+        let sender = moveos_std::account::create_signer_for_testing(sender_addr);
+        let scope = session_key::new_session_scope(@0x1, std::string::utf8(b"*"), std::string::utf8(b"*"));
+        let authentication_key = bcs::to_bytes(&sender_addr);
+        session_key::create_session_key(&sender, std::string::utf8(b"test"), std::string::utf8(b"https://test.rooch.network"), authentication_key, vector::singleton(scope), 0);
+        assert!(option::is_none(&session_key::get_session_key_limit(sender_addr, authentication_key)), 1000);
+        assert!(!session_key::is_limit_exceeded_for_test(sender_addr, authentication_key, 1000), 1001);
+
+        let now = timestamp::now_seconds();
+        let limit = session_key::new_session_key_limit(1000, 2, now + 10);
+        session_key::set_session_key_limit(&sender, authentication_key, limit);
+        assert!(!session_key::is_limit_exceeded_for_test(sender_addr, authentication_key, 600), 1002);
+        session_key::record_session_key_usage_for_test(authentication_key, 600);
+        // the gas limit is exceeded
+        assert!(session_key::is_limit_exceeded_for_test(sender_addr, authentication_key, 600), 1003);
+        assert!(!session_key::is_limit_exceeded_for_test(sender_addr, authentication_key, 400), 1004);
+        session_key::record_session_key_usage_for_test(authentication_key, 100);
+        // the tx count limit is exceeded
+        assert!(session_key::is_limit_exceeded_for_test(sender_addr, authentication_key, 1), 1005);
+
+        assert!(!session_key::is_expired_session_key(sender_addr, authentication_key), 1006);
+        timestamp::fast_forward_seconds_for_test(10);
+        assert!(session_key::is_expired_session_key(sender_addr, authentication_key), 1007);
+
+        session_key::remove_session_key(&sender, authentication_key);
+        assert!(option::is_none(&session_key::get_session_key_limit(sender_addr, authentication_key)), 1008);
+    }
+
 }
@@ -30,6 +30,8 @@ module rooch_framework::transaction_validator {
     use rooch_framework::bitcoin_address;
     use rooch_framework::webauthn_validator;
     use rooch_framework::did_validator;
+    use rooch_framework::sponsor_validator;
+    use rooch_framework::gas_sponsor;
 
     const MAX_U64: u128 = 18446744073709551615;
 
@@ -81,8 +83,27 @@ module rooch_framework::transaction_validator {
             auth_validator::error_validate_max_gas_amount_exceeded(),
         );
 
+        // === validate the gas sponsor ===
+
+        // The sponsored transaction wraps the sender's authenticator, the gas is paid by the sponsor
+        let (auth_validator_id, authenticator_payload, gas_payer) = if (auth_validator_id == sponsor_validator::auth_validator_id()) {
+            let (sender_auth_validator_id, sender_payload, sponsor) = sponsor_validator::validate(authenticator_payload);
+            // The sender's authenticator must be validated by the built-in auth validators below
+            assert!(
+                sender_auth_validator_id == session_validator::auth_validator_id() ||
+                sender_auth_validator_id == bitcoin_validator::auth_validator_id() ||
+                sender_auth_validator_id == webauthn_validator::auth_validator_id() ||
+                sender_auth_validator_id == did_validator::auth_validator_id(),
+                auth_validator::error_validate_invalid_authenticator(),
+            );
+            gas_sponsor::check_budget(sponsor, gas);
+            (sender_auth_validator_id, sender_payload, sponsor)
+        }else{
+            (auth_validator_id, authenticator_payload, sender)
+        };
+
         // Check total gas balance (account store + payment hub)
-        let total_gas_balance = transaction_gas::total_available_gas_balance(sender);
+        let total_gas_balance = transaction_gas::total_available_gas_balance(gas_payer);
 
         // we do not need to check the gas balance in local or dev chain
         if(!chain_id::is_local_or_dev()){
@@ -121,6 +142,14 @@ module rooch_framework::transaction_validator {
             (bitcoin_address, option::none(), option::none(), option::some(*auth_validator))
         };
         
+        // === validate the session key limits ===
+        if (option::is_some(&session_key)) {
+            assert!(
+                !session_key::is_limit_exceeded(sender, *option::borrow(&session_key), gas),
+                auth_validator::error_validate_session_limit_exceeded(),
+            );
+        };
+
         // We enable the smart contract account(DID account) to send transaction via session key, and the smart contract account does not have a bitcoin address.
         // But for compatibility, we still need to return a empty bitcoin address in the TxValidateResult.
         let bitcoin_address = if (option::is_some(&bitcoin_address_opt)) {
@@ -192,7 +221,7 @@ module rooch_framework::transaction_validator {
 
         let session_key_opt = auth_validator::get_session_key_from_ctx_option();
         if (option::is_some(&session_key_opt)) {
-            let session_key = option::extract(&mut session_key_opt);
+            let session_key = *option::borrow(&session_key_opt);
             session_key::active_session_key(session_key);
         };
         // Increment sequence number
@@ -216,6 +245,13 @@ module rooch_framework::transaction_validator {
             //If it is not a function call, we use the framework address as the contract address
             @rooch_framework
         };
+        if (gas_payment_account != sender) {
+            gas_sponsor::charge_budget(gas_payment_account, gas_used_after_scale);
+        };
+        if (option::is_some(&session_key_opt)) {
+            session_key::record_session_key_usage(option::destroy_some(session_key_opt), gas_used_after_scale);
+        };
+
         let sequencer_address = onchain_config::sequencer();
         let remaining_gas_coin = transaction_fee::distribute_fee(paid_gas, gas_used_after_scale, contract_address, sequencer_address);
         
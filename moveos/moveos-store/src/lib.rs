@@ -7,7 +7,7 @@
 use crate::config_store::{ConfigDBStore, ConfigStore, STARTUP_INFO_KEY};
 use crate::event_store::{EventDBStore, EventStore};
 use crate::state_store::statedb::StateDBStore;
-use crate::state_store::{nodes_to_write_batch, NodeDBStore, NodeRecycleDBStore};
+use crate::state_store::{nodes_to_write_batch, NodeDBStore, NodeRecycleDBStore, StateWriteFence};
 use crate::transaction_store::{TransactionDBStore, TransactionStore};
 use accumulator::inmemory::InMemoryAccumulator;
 use anyhow::{Error, Result};
@@ -90,6 +90,7 @@ pub struct MoveOSStore {
     pub config_store: ConfigDBStore,
     pub state_store: StateDBStore,
     pub node_recycle_store: NodeRecycleDBStore,
+    pub state_write_fence: StateWriteFence,
 }
 
 impl MoveOSStore {
@@ -113,6 +114,7 @@ impl MoveOSStore {
             StateDBStore::new(node_store.clone(), registry, store_config.state_cache_size);
 
         let node_recycle_store = NodeRecycleDBStore::new(instance.clone());
+        let state_write_fence = state_store.get_write_fence().clone();
         let store = Self {
             node_store,
             event_store: EventDBStore::new(instance.clone()),
@@ -120,6 +122,7 @@ impl MoveOSStore {
             config_store: ConfigDBStore::new(instance.clone()),
             state_store,
             node_recycle_store,
+            state_write_fence,
         };
         Ok(store)
     }
@@ -156,6 +159,10 @@ impl MoveOSStore {
         &self.node_recycle_store
     }
 
+    pub fn get_state_write_fence(&self) -> &StateWriteFence {
+        &self.state_write_fence
+    }
+
     pub fn handle_tx_output(
         &self,
         _tx_order: u64,
@@ -212,6 +219,7 @@ impl MoveOSStore {
         // atomic save updates
         let inner_store = self.node_store.get_store().store();
         let mut cf_batches: Vec<WriteBatchCF> = Vec::new();
+        let changed_node_hashes: Vec<H256> = changed_nodes.keys().copied().collect();
         let write_batch = nodes_to_write_batch(changed_nodes);
         cf_batches.push(WriteBatchCF {
             batch: write_batch,
@@ -234,7 +242,13 @@ impl MoveOSStore {
         // use non-sync write here:
         // 1. we could replay tx from rooch store(which has sync write after sequenced) at startup.
         // 2. output write sequentially
-        inner_store.write_cf_batch(cf_batches, false)?;
+        {
+            // the online GC re-checks its deletion against the committed state root under the fence
+            let _fence = self.state_write_fence.write_guard();
+            self.state_write_fence
+                .record_written(changed_node_hashes.iter());
+            inner_store.write_cf_batch(cf_batches, false)?;
+        }
 
         let out = TransactionOutput::new(status, changeset, events, gas_used, is_upgrade);
 
@@ -6,7 +6,9 @@ pub mod statedb;
 
 use crate::{STATE_NODE_COLUMN_FAMILY_NAME, STATE_NODE_RECYCLE_COLUMN_FAMILY_NAME};
 use anyhow::Result;
+use moveos_common::bloom_filter::BloomFilter;
 use moveos_types::h256::H256;
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use raw_store::rocks::batch::WriteBatch;
 use raw_store::traits::KVStore;
 use raw_store::WriteOp;
@@ -14,6 +16,7 @@ use raw_store::{derive_store, CodecKVStore};
 use smt::{NodeReader, NodeWriter};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 derive_store!(NodeDBStore, H256, Vec<u8>, STATE_NODE_COLUMN_FAMILY_NAME);
 derive_store!(
@@ -23,6 +26,61 @@ derive_store!(
     STATE_NODE_RECYCLE_COLUMN_FAMILY_NAME
 );
 
+/// Fence between the state writes and the online node deletion in the same process.
+///
+/// Every state node writer (the transaction output, `StateDBStore::apply_change_set` and
+/// `StateDBStore::update_nodes`) writes while holding the read side, and records the written
+/// nodes when a GC cycle is recording. A node deletion holding the write side sees a committed
+/// state root which covers all the nodes written before it, and keeps every node written during
+/// the cycle, so the nodes only reachable from an uncommitted root (e.g. a state-only execution)
+/// are never deleted by the cycle which saw them written.
+#[derive(Clone, Default)]
+pub struct StateWriteFence {
+    lock: Arc<RwLock<()>>,
+    written_nodes: Arc<Mutex<Option<BloomFilter>>>,
+}
+
+impl StateWriteFence {
+    /// Hold it while writing state nodes, the writes do not block each other
+    pub fn write_guard(&self) -> RwLockReadGuard<'_, ()> {
+        self.lock.read()
+    }
+
+    /// Hold it while deleting state nodes, it waits for the in-flight writes
+    pub fn delete_guard(&self) -> RwLockWriteGuard<'_, ()> {
+        self.lock.write()
+    }
+
+    /// Start recording the written nodes, `bits` must be a power of two.
+    pub fn start_recording(&self, bits: usize, hash_fns: u8) {
+        *self.written_nodes.lock() = Some(BloomFilter::new(bits, hash_fns));
+    }
+
+    pub fn stop_recording(&self) {
+        *self.written_nodes.lock() = None;
+    }
+
+    /// Record the nodes written under the `write_guard`
+    pub fn record_written<'a, I>(&self, nodes: I)
+    where
+        I: IntoIterator<Item = &'a H256>,
+    {
+        if let Some(filter) = self.written_nodes.lock().as_mut() {
+            for node in nodes {
+                filter.insert(node);
+            }
+        }
+    }
+
+    /// Whether the node may be written since the recording started
+    pub fn maybe_written(&self, node: &H256) -> bool {
+        self.written_nodes
+            .lock()
+            .as_ref()
+            .is_some_and(|filter| filter.contains(node))
+    }
+}
+
 impl NodeDBStore {
     pub fn put(&self, key: H256, node: Vec<u8>) -> Result<()> {
         self.put_raw(key.as_bytes().to_vec(), node)
@@ -2,17 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::state_store::metrics::StateDBMetrics;
-use crate::state_store::NodeDBStore;
+use crate::state_store::{NodeDBStore, StateWriteFence};
 use anyhow::{Error, Ok, Result};
 use function_name::named;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::Op;
 use moveos_types::h256::H256;
-use moveos_types::moveos_std::object::GENESIS_STATE_ROOT;
+use moveos_types::moveos_std::object::{ObjectID, GENESIS_STATE_ROOT};
 use moveos_types::state::FieldKey;
 use moveos_types::state::ObjectChange;
 use moveos_types::state::ObjectState;
 use moveos_types::state::StateChangeSet;
+use moveos_types::state_proof::{FieldProof, StateProof};
 use moveos_types::state_resolver::RootObjectResolver;
 use moveos_types::state_resolver::StateKV;
 use moveos_types::state_resolver::StateResolver;
@@ -20,7 +21,7 @@ use moveos_types::state_resolver::StatelessResolver;
 use prometheus::Registry;
 use quick_cache::sync::Cache;
 use smt::{SMTIterator, TreeChangeSet};
-use smt::{SMTree, UpdateSet};
+use smt::{SMTree, SparseMerkleProof, UpdateSet};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
@@ -33,6 +34,7 @@ pub struct StateDBStore {
     pub smt: SMTree<FieldKey, ObjectState, NodeDBStore>,
     metrics: Arc<StateDBMetrics>,
     cache: Arc<Cache<(H256, FieldKey), Option<ObjectState>>>,
+    write_fence: StateWriteFence,
 }
 
 impl StateDBStore {
@@ -42,9 +44,21 @@ impl StateDBStore {
             smt: SMTree::new(node_store, registry),
             metrics: Arc::new(StateDBMetrics::new(registry)),
             cache: Arc::new(Cache::new(cache_size)),
+            write_fence: StateWriteFence::default(),
         }
     }
 
+    /// The fence shared by all the state node writers of the store
+    pub fn get_write_fence(&self) -> &StateWriteFence {
+        &self.write_fence
+    }
+
+    fn write_nodes_fenced(&self, nodes: BTreeMap<H256, Vec<u8>>) -> Result<()> {
+        let _fence = self.write_fence.write_guard();
+        self.write_fence.record_written(nodes.keys());
+        self.node_store.write_nodes(nodes)
+    }
+
     #[named]
     pub fn update_fields<I>(&self, pre_state_root: H256, update_set: I) -> Result<TreeChangeSet>
     where
@@ -76,7 +90,7 @@ impl StateDBStore {
             .with_label_values(&[fn_name])
             .start_timer();
         let size = nodes.values().map(|v| 32 + v.len()).sum::<usize>();
-        self.node_store.write_nodes(nodes)?;
+        self.write_nodes_fenced(nodes)?;
         self.metrics
             .state_update_nodes_bytes
             .with_label_values(&[fn_name])
@@ -202,7 +216,7 @@ impl StateDBStore {
 
     pub fn apply_change_set(&self, state_change_set: &mut StateChangeSet) -> Result<()> {
         let (nodes, _stale_indices) = self.change_set_to_nodes(state_change_set)?;
-        self.node_store.write_nodes(nodes)?;
+        self.write_nodes_fenced(nodes)?;
         Ok(())
     }
 
@@ -220,6 +234,40 @@ impl StateDBStore {
             .start_timer();
         self.smt.iter(state_root, starting_key)
     }
+
+    /// Returns the field state and the merkle proof of the `key` in the tree of `state_root`.
+    pub fn get_with_proof(
+        &self,
+        state_root: H256,
+        key: FieldKey,
+    ) -> Result<(Option<ObjectState>, SparseMerkleProof)> {
+        self.smt.get_with_proof(state_root, key)
+    }
+
+    /// Returns the proof of the `field_key` in the object `object_id` against the global `state_root`.
+    pub fn get_state_proof(
+        &self,
+        state_root: H256,
+        object_id: &ObjectID,
+        field_key: FieldKey,
+    ) -> Result<StateProof> {
+        let mut proofs = vec![];
+        let mut current_root = state_root;
+        for key in StateProof::field_key_path(object_id, field_key) {
+            let (state, proof) = self.get_with_proof(current_root, key)?;
+            let next_root = state.as_ref().map(|state| state.state_root());
+            proofs.push(FieldProof {
+                field_key: key,
+                state,
+                proof,
+            });
+            match next_root {
+                Some(root) => current_root = root,
+                None => break,
+            }
+        }
+        Ok(StateProof::new(proofs))
+    }
 }
 
 impl StatelessResolver for StateDBStore {
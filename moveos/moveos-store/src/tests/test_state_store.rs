@@ -5,6 +5,9 @@ use crate::{MoveOSStore, StoreMeta};
 use anyhow::Result;
 use moveos_config::store_config::RocksdbConfig;
 use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::state::FieldKey;
+use moveos_types::state_resolver::{RootObjectResolver, StateResolver};
 use moveos_types::test_utils::random_state_change_set;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::RocksDB;
@@ -67,6 +70,55 @@ async fn test_statedb_state_root() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_statedb_state_proof() -> Result<()> {
+    let (moveos_store, _) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let mut change_set = random_state_change_set();
+    let state_store = moveos_store.get_state_store();
+    state_store.apply_change_set(&mut change_set)?;
+    let state_root = change_set.state_root;
+    let resolver = RootObjectResolver::new(
+        ObjectMeta::root_metadata(state_root, change_set.global_size),
+        &moveos_store,
+    );
+
+    for (key, change) in change_set.changes.iter() {
+        let object_id = change.metadata.id.clone();
+        let proof = state_store.get_state_proof(state_root, &ObjectID::root(), *key)?;
+        let state = proof.verify(state_root, &ObjectID::root(), *key)?;
+        assert_eq!(state, resolver.get_object(&object_id)?);
+        assert!(state.is_some());
+
+        for field_key in change.fields.keys() {
+            let proof = state_store.get_state_proof(state_root, &object_id, *field_key)?;
+            let state = proof.verify(state_root, &object_id, *field_key)?;
+            assert_eq!(state, resolver.get_field(&object_id, field_key)?);
+            assert!(state.is_some());
+            // the proof should not be valid for another state root
+            assert!(proof
+                .verify(H256::random(), &object_id, *field_key)
+                .is_err());
+        }
+
+        // non-inclusion proof of a not exists field
+        let not_exists_key = FieldKey::random();
+        let proof = state_store.get_state_proof(state_root, &object_id, not_exists_key)?;
+        assert_eq!(proof.verify(state_root, &object_id, not_exists_key)?, None);
+    }
+
+    // non-inclusion proof of a field in a not exists object
+    let not_exists_object_id = ObjectID::random();
+    let field_key = FieldKey::random();
+    let proof = state_store.get_state_proof(state_root, &not_exists_object_id, field_key)?;
+    assert_eq!(proof.proofs.len(), 1);
+    assert_eq!(
+        proof.verify(state_root, &not_exists_object_id, field_key)?,
+        None
+    );
+    Ok(())
+}
+
 // #[tokio::test]
 // async fn test_child_state_db_dump_and_apply() -> Result<()> {
 //     let mut moveos_store = MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
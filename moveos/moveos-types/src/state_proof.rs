@@ -0,0 +1,105 @@
+// Copyright (c) RoochNetwork
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::h256::H256;
+use crate::moveos_std::object::ObjectID;
+use crate::state::{FieldKey, ObjectState};
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use smt::SparseMerkleProof;
+
+/// The proof of a field in one level of the state tree.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FieldProof {
+    pub field_key: FieldKey,
+    /// The state of the field, None if the field does not exist.
+    pub state: Option<ObjectState>,
+    /// The inclusion proof if the state exists, otherwise the non-inclusion proof.
+    pub proof: SparseMerkleProof,
+}
+
+/// The proof of an object field against a global state root.
+///
+/// Every object is a field of its parent object, so the proof is a chain of field proofs
+/// from the root object down to the field. The first one is proved against the global state root,
+/// and every following one is proved against the state root of the previous object.
+/// If an object on the path does not exist, the chain ends with the non-inclusion proof of that object.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateProof {
+    pub proofs: Vec<FieldProof>,
+}
+
+impl StateProof {
+    pub fn new(proofs: Vec<FieldProof>) -> Self {
+        Self { proofs }
+    }
+
+    /// The field keys on the path from the root object to the field.
+    pub fn field_key_path(object_id: &ObjectID, field_key: FieldKey) -> Vec<FieldKey> {
+        let mut path = vec![field_key];
+        let mut id = object_id.clone();
+        while let Some(parent) = id.parent() {
+            path.push(id.field_key());
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// The state of the field, None if the field or one of its parent objects does not exist.
+    pub fn state(&self) -> Option<&ObjectState> {
+        self.proofs.last().and_then(|proof| proof.state.as_ref())
+    }
+
+    /// Verify the proof of the `field_key` in the object `object_id` against the `state_root`.
+    /// Returns the proved state of the field, None if the field does not exist.
+    pub fn verify(
+        &self,
+        state_root: H256,
+        object_id: &ObjectID,
+        field_key: FieldKey,
+    ) -> Result<Option<ObjectState>> {
+        let path = Self::field_key_path(object_id, field_key);
+        ensure!(
+            !self.proofs.is_empty() && self.proofs.len() <= path.len(),
+            "Invalid state proof, expect at most {} field proofs, but got {}",
+            path.len(),
+            self.proofs.len()
+        );
+        let mut expected_root = state_root;
+        for (idx, (field_proof, expected_key)) in self.proofs.iter().zip(path.iter()).enumerate() {
+            ensure!(
+                field_proof.field_key == *expected_key,
+                "Invalid state proof, the field key at level {} mismatch, expect: {}, actual: {}",
+                idx,
+                expected_key,
+                field_proof.field_key
+            );
+            field_proof.proof.verify(
+                expected_root,
+                field_proof.field_key,
+                field_proof.state.clone(),
+            )?;
+            let is_last = idx == self.proofs.len() - 1;
+            match &field_proof.state {
+                Some(state) => {
+                    if is_last {
+                        ensure!(
+                            idx == path.len() - 1,
+                            "Invalid state proof, the proof chain is incomplete"
+                        );
+                    }
+                    expected_root = state.state_root();
+                }
+                None => {
+                    ensure!(
+                        is_last,
+                        "Invalid state proof, the object at level {} does not exist but the proof chain continues",
+                        idx
+                    );
+                }
+            }
+        }
+        Ok(self.state().cloned())
+    }
+}
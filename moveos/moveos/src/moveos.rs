@@ -387,33 +387,12 @@ impl MoveOS {
         tx_context: &TxContext,
         function_call: FunctionCall,
     ) -> FunctionResult {
-        if tx_context.max_gas_amount > GasScheduleConfig::READONLY_MAX_GAS_AMOUNT {
-            return FunctionResult::err(
-                PartialVMError::new(StatusCode::MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND)
-                    .with_message("Max gas amount too large for readonly function".to_string())
-                    .finish(Location::Undefined),
-            );
-        }
-        let cost_table = match self.load_cost_table(&root) {
-            Ok(cost_table) => cost_table,
+        let gas_meter = match self.new_readonly_gas_meter(&root, tx_context) {
+            Ok(gas_meter) => gas_meter,
             Err(e) => {
                 return FunctionResult::err(e);
             }
         };
-
-        let feature_resolver = RootObjectResolver::new(root.clone(), &self.db);
-        let feature_store_opt = load_feature_store_object(&feature_resolver);
-        let has_io_tired_write_feature = match feature_store_opt {
-            None => false,
-            Some(feature_store) => feature_store.has_value_size_gas_feature(),
-        };
-
-        let mut gas_meter = MoveOSGasMeter::new(
-            cost_table,
-            tx_context.max_gas_amount,
-            has_io_tired_write_feature,
-        );
-        gas_meter.set_metering(true);
         let resolver = RootObjectResolver::new(root, &self.db);
         let mut session = self
             .vm
@@ -437,6 +416,120 @@ impl MoveOS {
         }
     }
 
+    /// Execute a batch of view functions against the same root.
+    /// The calls share one read-only session, every call gets a fresh gas meter,
+    /// so an expensive call can not starve the calls after it,
+    /// and the gas used by each call is returned along with its result.
+    /// The session is finished once after the batch to check that no call changed the state,
+    /// if the check fails, the error is returned for every call which succeeded.
+    pub fn execute_view_functions(
+        &self,
+        root: ObjectMeta,
+        function_calls: Vec<FunctionCall>,
+    ) -> Vec<(FunctionResult, u64)> {
+        let tx_context = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        let (cost_table, has_io_tired_write_feature) =
+            match self.load_readonly_gas_config(&root, &tx_context) {
+                Ok(config) => config,
+                Err(e) => {
+                    let vm_status = e.into_vm_status();
+                    return function_calls
+                        .iter()
+                        .map(|_| {
+                            (
+                                FunctionResult {
+                                    vm_status: vm_status.clone(),
+                                    return_values: None,
+                                },
+                                0,
+                            )
+                        })
+                        .collect();
+                }
+            };
+        let new_gas_meter = || {
+            let mut gas_meter = MoveOSGasMeter::new(
+                cost_table.clone(),
+                tx_context.max_gas_amount,
+                has_io_tired_write_feature,
+            );
+            gas_meter.set_metering(true);
+            gas_meter
+        };
+
+        let resolver = RootObjectResolver::new(root, &self.db);
+        let mut session =
+            self.vm
+                .new_readonly_session(&resolver, tx_context.clone(), new_gas_meter());
+        let mut results = Vec::with_capacity(function_calls.len());
+        for function_call in function_calls {
+            session.replace_gas_meter(new_gas_meter());
+            let result = match session.execute_function_bypass_visibility(function_call) {
+                Ok(return_values) => FunctionResult::ok(return_values),
+                Err(e) => {
+                    if tracing::enabled!(tracing::Level::DEBUG) {
+                        tracing::warn!("execute_view_functions error:{:?}", e);
+                    }
+                    FunctionResult::err(e)
+                }
+            };
+            results.push((result, session.query_gas_used()));
+        }
+
+        // Finish the session to check that the calls did not change the state.
+        if let Err(e) = session.finish_with_extensions(KeptVMStatus::Executed) {
+            let vm_status = e.into_vm_status();
+            for (result, _) in results.iter_mut() {
+                if result.vm_status == VMStatus::Executed {
+                    *result = FunctionResult {
+                        vm_status: vm_status.clone(),
+                        return_values: None,
+                    };
+                }
+            }
+        }
+        results
+    }
+
+    fn new_readonly_gas_meter(
+        &self,
+        root: &ObjectMeta,
+        tx_context: &TxContext,
+    ) -> VMResult<MoveOSGasMeter> {
+        let (cost_table, has_io_tired_write_feature) =
+            self.load_readonly_gas_config(root, tx_context)?;
+        let mut gas_meter = MoveOSGasMeter::new(
+            cost_table,
+            tx_context.max_gas_amount,
+            has_io_tired_write_feature,
+        );
+        gas_meter.set_metering(true);
+        Ok(gas_meter)
+    }
+
+    fn load_readonly_gas_config(
+        &self,
+        root: &ObjectMeta,
+        tx_context: &TxContext,
+    ) -> VMResult<(CostTable, bool)> {
+        if tx_context.max_gas_amount > GasScheduleConfig::READONLY_MAX_GAS_AMOUNT {
+            return Err(
+                PartialVMError::new(StatusCode::MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND)
+                    .with_message("Max gas amount too large for readonly function".to_string())
+                    .finish(Location::Undefined),
+            );
+        }
+        let cost_table = self.load_cost_table(root)?;
+
+        let feature_resolver = RootObjectResolver::new(root.clone(), &self.db);
+        let feature_store_opt = load_feature_store_object(&feature_resolver);
+        let has_io_tired_write_feature = match feature_store_opt {
+            None => false,
+            Some(feature_store) => feature_store.has_value_size_gas_feature(),
+        };
+        Ok((cost_table, has_io_tired_write_feature))
+    }
+
     // Execute action with pre_execute and post_execute.
     // Return the action execution status if success,
     // else return VMError and a bool which indicate if we should respawn the session.
@@ -718,6 +718,11 @@ where
         Ok(())
     }
 
+    /// Replace the gas meter of the session, the calls executed after it are metered by the new one.
+    pub(crate) fn replace_gas_meter(&mut self, gas_meter: G) -> G {
+        std::mem::replace(&mut self.gas_meter, gas_meter)
+    }
+
     pub(crate) fn query_gas_used(&self) -> u64 {
         let max_gas_amount = self.tx_context().max_gas_amount;
         let gas_left: u64 = self.gas_meter.balance_internal().into();
@@ -109,6 +109,15 @@ use tree_cache::TreeCache;
 /// The hardcoded maximum height of a [`JellyfishMerkleTree`] in nibbles.
 pub const ROOT_NIBBLE_HEIGHT: usize = SMTNodeHash::LEN * 2;
 
+/// The message prefix of [`MissingNodeError`], the Move VM keeps only the message of the
+/// resolver errors, so the missing node is recognized by the prefix there.
+pub const MISSING_NODE_ERROR_PREFIX: &str = "Missing node at";
+
+/// The node does not exist in the storage, e.g. it has been deleted by the pruner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("{} {:?}.", MISSING_NODE_ERROR_PREFIX, .0)]
+pub struct MissingNodeError(pub H256);
+
 /// `TreeReader` defines the interface between
 /// [`JellyfishMerkleTree`](struct.JellyfishMerkleTree.html)
 /// and underlying storage holding nodes.
@@ -118,7 +127,7 @@ pub(crate) trait TreeReader<K, V> {
         self.get_node_option(node_key)?.ok_or_else(|| {
             let backtrace = format!("{:#?}", Backtrace::new());
             debug!("backtrace: {}", backtrace);
-            format_err!("Missing node at {:?}.", node_key)
+            MissingNodeError((*node_key).into()).into()
         })
     }
 
@@ -19,7 +19,10 @@ use std::{
 };
 
 use crate::metrics::SMTMetrics;
-pub use jellyfish_merkle::{hash::SPARSE_MERKLE_PLACEHOLDER_HASH, proof::SparseMerkleProof};
+pub use jellyfish_merkle::{
+    hash::SPARSE_MERKLE_PLACEHOLDER_HASH, proof::SparseMerkleProof, MissingNodeError,
+    MISSING_NODE_ERROR_PREFIX,
+};
 pub use smt_object::{DecodeToObject, EncodeToObject, Key, SMTObject, Value};
 pub use update_set::UpdateSet;
 
@@ -4,6 +4,7 @@
 import { useCallback, useMemo } from 'react'
 import {
   ErrorValidateInvalidAccountAuthKey,
+  ErrorValidateSessionExpirationTimeReached,
   ErrorValidateSessionIsExpired,
   isRoochClient,
   RoochClient,
@@ -51,7 +52,8 @@ export function useDefaultClient(params: UseRoochClientParams) {
         if (state === 'error') {
           if (
             error!.code === ErrorValidateInvalidAccountAuthKey ||
-            error!.code === ErrorValidateSessionIsExpired
+            error!.code === ErrorValidateSessionIsExpired ||
+            error!.code === ErrorValidateSessionExpirationTimeReached
           ) {
             if (currentSession) {
               removeSession(currentSession)
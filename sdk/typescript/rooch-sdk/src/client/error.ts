@@ -14,6 +14,8 @@ export const ErrorValidateInvalidAuthenticator = 1010
 export const ErrorValidateNotInstalledAuthValidator = 1011
 export const ErrorValidateSessionIsExpired = 1012
 export const ErrorValidateFunctionCallBeyondSessionScope = 1013
+export const ErrorValidateSessionLimitExceeded = 1014
+export const ErrorValidateSessionExpirationTimeReached = 1015
 
 const CODE_TO_ERROR_TYPE: Record<number, string> = {
   1001: 'SequenceNuberTooOld',
@@ -29,6 +31,8 @@ const CODE_TO_ERROR_TYPE: Record<number, string> = {
   1011: 'NotInstalledAuthValidator',
   1012: 'SessionIsExpired',
   1013: 'CallFunctionBeyondSessionScop',
+  1014: 'SessionLimitExceeded',
+  1015: 'SessionExpirationTimeReached',
 }
 
 export class RoochHTTPTransportError extends Error {}